@@ -37,6 +37,46 @@ fn leptos_ssr_bench(b: &mut Bencher) {
 		});
 	});
 }
+// Like `leptos_ssr_bench`, but disposes the runtime after each render, as the server
+// integrations do after each request, so that its allocations are reused by the next one.
+// Compare the two with `cargo bench` to measure what reusing runtimes saves.
+#[bench]
+fn leptos_ssr_bench_pooled_runtime(b: &mut Bencher) {
+	b.iter(|| {
+		use leptos::*;
+
+		#[component]
+		fn Counter(cx: Scope, initial: i32) -> impl IntoView {
+			let (value, set_value) = create_signal(cx, initial);
+			view! {
+				cx,
+				<div>
+					<button on:click=move |_| set_value.update(|value| *value -= 1)>"-1"</button>
+					<span>"Value: " {move || value().to_string()} "!"</span>
+					<button on:click=move |_| set_value.update(|value| *value += 1)>"+1"</button>
+				</div>
+			}
+		}
+
+		let runtime = create_runtime();
+		let rendered = run_scope(runtime, |cx| {
+			view! {
+				cx,
+				<main>
+					<h1>"Welcome to our benchmark page."</h1>
+					<p>"Here's some introductory text."</p>
+					<Counter initial=1/>
+					<Counter initial=2/>
+					<Counter initial=3/>
+				</main>
+			}.into_view(cx).render_to_string(cx)
+		});
+		runtime.dispose();
+
+		assert!(rendered.starts_with("<main><h1>Welcome to our benchmark page.</h1>"));
+	});
+}
+
 /* 
 #[bench]
 fn tera_ssr_bench(b: &mut Bencher) {
@@ -65,24 +65,57 @@ impl ResponseOptions {
 /// ```
 pub fn handle_server_fns() -> Route {
     web::post().to(
-        |req: HttpRequest, params: web::Path<String>, body: web::Bytes| async move {
+        |req: HttpRequest, _: web::Path<String>, body: web::Bytes| async move {
             {
-                let path = params.into_inner();
                 let accept_header = req
                     .headers()
                     .get("Accept")
                     .and_then(|value| value.to_str().ok());
 
-                if let Some(server_fn) = server_fn_by_path(path.as_str()) {
+                // the full path tells apart server functions of different apps
+                if let Some(server_fn) = server_fn_by_path(req.path()) {
                     let body: &[u8] = &body;
 
                     let runtime = create_runtime();
                     let (cx, disposer) = raw_scope_and_disposer(runtime);
                     let res_options = ResponseOptions::default();
+                    let cookies = CookieJar::from_header(
+                        req.headers()
+                            .get("Cookie")
+                            .and_then(|value| value.to_str().ok()),
+                    );
 
                     // provide HttpRequest as context in server scope
                     provide_context(cx, req.clone());
                     provide_context(cx, res_options.clone());
+                    provide_context(cx, cookies.clone());
+                    if let Some(config) = req.app_data::<SessionConfig>() {
+                        provide_context(cx, config.clone());
+                    }
+                    // Add this so that server functions can ask the client to navigate
+                    let redirect = ServerFnRedirect::default();
+                    provide_context(cx, redirect.clone());
+
+                    // the client accepts the server function's encoding, which also gives the
+                    // content type of a binary result
+                    let encoding = accept_header.and_then(Encoding::from_content_type);
+                    // if this isn't a fetch() call, it's probably a <form> submit or something,
+                    // which is redirected back to the referrer
+                    let is_form_submit =
+                        encoding.is_none() && !matches!(accept_header, Some("application/json"));
+                    // only a browser's <form> is sent back to a page that can show the submission
+                    let is_browser_form = is_form_submit
+                        && FormSubmission::is_browser_form(
+                            req.headers()
+                                .get("Content-Type")
+                                .and_then(|value| value.to_str().ok()),
+                            accept_header,
+                        );
+                    let referer = req
+                        .headers()
+                        .get("Referer")
+                        .and_then(|value| value.to_str().ok())
+                        .unwrap_or("/");
 
                     match server_fn(cx, body).await {
                         Ok(serialized) => {
@@ -95,22 +128,31 @@ pub fn handle_server_fns() -> Route {
                             let mut res: HttpResponseBuilder;
                             let mut res_parts = res_options.0.write().await;
                          
-                            if accept_header == Some("application/json")
-                                || accept_header == Some("application/x-www-form-urlencoded")
-                                || accept_header == Some("application/cbor")
-                            {
+                            let redirect = redirect.take();
+                            if !is_form_submit {
                                 res = HttpResponse::Ok();
+                                // the client navigates once it has read the result
+                                if let Some(location) = &redirect {
+                                    res.insert_header((REDIRECT_HEADER, location.as_str()));
+                                }
                             }
-                            // otherwise, it's probably a <form> submit or something: redirect back to the referrer
+                            // otherwise, redirect back to the referrer, which can show the result,
+                            // or wherever the server function asked to go
                             else {
-                                let referer = req
-                                    .headers()
-                                    .get("Referer")
-                                    .and_then(|value| value.to_str().ok())
-                                    .unwrap_or("/");
+                                if is_browser_form {
+                                    store_form_submission(
+                                        &cookies,
+                                        req.path(),
+                                        body,
+                                        Ok(&serialized),
+                                    );
+                                }
                                 res = HttpResponse::SeeOther();
-                                res.insert_header(("Location", referer))
-                                    .content_type("application/json");
+                                res.insert_header((
+                                    "Location",
+                                    redirect.as_deref().unwrap_or(referer),
+                                ))
+                                .content_type("application/json");
                             };
                             // Override StatusCode if it was set in a Resource or Element
                             if let Some(status) = res_parts.status {
@@ -127,10 +169,16 @@ pub fn handle_server_fns() -> Route {
                                     }
                                 })
                                 .count();
+                            // Add any cookies set by the server function
+                            for cookie in cookies.take_set_cookie_headers() {
+                                res.append_header(("Set-Cookie", cookie));
+                            }
 
                             match serialized {
                                 Payload::Binary(data) => {
-                                    res.content_type("application/cbor");
+                                    res.content_type(
+                                        encoding.unwrap_or(Encoding::Cbor).content_type(),
+                                    );
                                     res.body(Bytes::from(data))
                                 }
                                 Payload::Url(data) => {
@@ -143,6 +191,17 @@ pub fn handle_server_fns() -> Route {
                                 }
                             }
                         }
+                        // a <form> submitted without JavaScript goes back to the referrer,
+                        // which can show the error along with the values that were submitted
+                        Err(e) if is_browser_form => {
+                            store_form_submission(&cookies, req.path(), body, Err(&e));
+                            let mut res = HttpResponse::SeeOther();
+                            res.insert_header(("Location", referer));
+                            for cookie in cookies.take_set_cookie_headers() {
+                                res.append_header(("Set-Cookie", cookie));
+                            }
+                            res.finish()
+                        }
                         Err(e) => HttpResponse::InternalServerError().body(e.to_string()),
                     }
                 } else {
@@ -154,6 +213,28 @@ pub fn handle_server_fns() -> Route {
     )
 }
 
+/// Stores what was submitted to a server function by a `<form>` without JavaScript, and what it
+/// returned, so that the `<ActionForm/>` on the page the browser is redirected to can show them.
+fn store_form_submission(
+    cookies: &CookieJar,
+    path: &str,
+    body: &[u8],
+    output: Result<&Payload, &ServerFnError>,
+) {
+    let output = match output {
+        Ok(Payload::Json(data)) | Ok(Payload::Url(data)) => Ok(data.clone()),
+        // the form reads results as JSON
+        Ok(Payload::Binary(_)) => return,
+        Err(e) => Err(e.clone()),
+    };
+    FormSubmission {
+        url: path.to_string(),
+        input: String::from_utf8_lossy(body).into_owned(),
+        output,
+    }
+    .store(cookies);
+}
+
 /// Returns an Actix [Route](actix_web::Route) that listens for a `GET` request and tries
 /// to route it using [leptos_router], serving an HTML stream of your application.
 ///
@@ -199,12 +280,32 @@ pub fn render_app_to_stream<IV>(
 ) -> Route
 where IV: IntoView
 {
+    // read the stylesheet once, so that critical CSS can be extracted for each request
+    let critical_stylesheet: Option<Arc<str>> = options
+        .critical_css
+        .as_ref()
+        .and_then(|path| match std::fs::read_to_string(path) {
+            Ok(stylesheet) => Some(stylesheet),
+            // the page still works without critical CSS, so it's rendered without it
+            Err(e) => {
+                leptos::error!("could not read the critical CSS stylesheet `{path}`: {e}");
+                None
+            }
+        })
+        .map(Arc::from);
+
     web::get().to(move |req: HttpRequest| {
         let options = options.clone();
+        let critical_stylesheet = critical_stylesheet.clone();
         let app_fn = app_fn.clone();
         let res_options = ResponseOptions::default();
         let res_options_default = res_options.clone();
         async move {
+            let cookies = CookieJar::from_header(
+                req.headers()
+                    .get("Cookie")
+                    .and_then(|value| value.to_str().ok()),
+            );
             let path = req.path();
 
             let query = req.query_string();
@@ -214,14 +315,68 @@ where IV: IntoView
                 "http://leptos".to_string() + path + "?" + query
             };
 
+            // partial-page requests (e.g., from htmx) only get the matched route's HTML
+            let is_partial = options
+                .partial_render_header
+                .as_deref()
+                .map(|header| req.headers().contains_key(header))
+                .unwrap_or(false);
+            // the same URL renders differently with and without that header, so caches need
+            // to tell them apart
+            let vary = options
+                .partial_render_header
+                .as_deref()
+                .and_then(|header| http::header::HeaderValue::from_str(header).ok());
+            if is_partial {
+                return render_partial(
+                    app_fn,
+                    path,
+                    req,
+                    cookies,
+                    options.asset_manifest.clone(),
+                    res_options,
+                    vary,
+                )
+                .await;
+            }
+
+            let redirect = ServerRedirect::default();
+            let route_status = ServerStatus::default();
             let app = {
                 let app_fn = app_fn.clone();
+                let asset_manifest = options.asset_manifest.clone();
+                let collect_selectors = critical_stylesheet.is_some();
+                let islands = options.islands;
+                let in_order_streaming = options.in_order_streaming;
+                let cookies = cookies.clone();
+                let redirect = redirect.clone();
+                let route_status = route_status.clone();
                 move |cx| {
                     let integration = ServerIntegration { path: path.clone() };
                     provide_context(cx, RouterIntegrationContext::new(integration));
                     provide_context(cx, MetaContext::new());
                     provide_context(cx, res_options_default.clone());
+                    provide_context(cx, client_hints(req.headers()));
                     provide_context(cx, req.clone());
+                    provide_context(cx, asset_manifest.clone());
+                    provide_context(cx, cookies.clone());
+                    if let Some(config) = req.app_data::<SessionConfig>() {
+                        provide_context(cx, config.clone());
+                    }
+                    provide_context(cx, redirect.clone());
+                    provide_context(cx, route_status.clone());
+                    if islands {
+                        provide_context(cx, IslandManifest::default());
+                    }
+                    if in_order_streaming {
+                        provide_context(cx, StreamingOptions {
+                            mode: StreamingMode::InOrder,
+                            ..Default::default()
+                        });
+                    }
+                    if collect_selectors {
+                        collect_rendered_selectors(cx);
+                    }
 
                     (app_fn)(cx).into_view(cx)
                 }
@@ -240,7 +395,7 @@ where IV: IntoView
                 }
                
 
-                let site_ip = &options.site_address.ip().to_string();
+                let site_ip = options.site_address.ip().to_string();
                 let reload_port = options.reload_port;
                 let site_root = &options.site_root;
                 let pkg_path = &options.site_pkg_dir;
@@ -253,10 +408,21 @@ where IV: IntoView
                     _ => format!("{}/{}", site_root, pkg_path),
                 };
              
-                let leptos_autoreload = match std::env::var("LEPTOS_WATCH").is_ok() {
+                // resolve the bundle to its fingerprinted filenames, if an asset manifest was loaded
+                let js_path = options
+                    .asset_manifest
+                    .resolve(&format!("/{bundle_path}/{output_name}.js"));
+                let wasm_path = options
+                    .asset_manifest
+                    .resolve(&format!("/{bundle_path}/{wasm_output_name}.wasm"));
+
+                // the scripts are allowed by the nonce the app provides, so they're only written once
+                // it has rendered
+                let live_reload = std::env::var("LEPTOS_WATCH").is_ok();
+                let leptos_autoreload = move |nonce: &str| match live_reload {
                     true => format!(
                         r#"
-                        <script crossorigin="">(function () {{
+                        <script crossorigin=""{nonce}>(function () {{
                             var ws = new WebSocket('ws://{site_ip}:{reload_port}/live_reload');
                             ws.onmessage = (ev) => {{
                                 let msg = JSON.parse(event.data);
@@ -280,34 +446,65 @@ where IV: IntoView
                     false => "".to_string(),
                 };
 
-                let head = format!(
+                // in islands mode, each island loads its own bundle once the page has rendered
+                let islands_mode = options.islands;
+                let hydration_scripts = move |nonce: &str| match islands_mode {
+                    true => "".to_string(),
+                    false => format!(
+                        r#"<link rel="modulepreload" href="{js_path}">
+                            <link rel="preload" href="{wasm_path}" as="fetch" type="application/wasm" crossorigin="">
+                            <script type="module"{nonce}>import init, {{ hydrate }} from '{js_path}'; init('{wasm_path}').then(hydrate);</script>"#
+                    ),
+                };
+
+                // the attributes of `<html>` are set by the app, so they're only known once it has rendered
+                let head = |html_attributes: String, nonce: String| format!(
                     r#"<!DOCTYPE html>
-                    <html lang="en">
+                    <html lang="en"{html_attributes}>
                         <head>
                             <meta charset="utf-8"/>
                             <meta name="viewport" content="width=device-width, initial-scale=1"/>
-                            <link rel="modulepreload" href="/{bundle_path}/{output_name}.js">
-                            <link rel="preload" href="/{bundle_path}/{wasm_output_name}.wasm" as="fetch" type="application/wasm" crossorigin="">
-                            <script type="module">import init, {{ hydrate }} from '/{bundle_path}/{output_name}.js'; init('/{bundle_path}/{wasm_output_name}.wasm').then(hydrate);</script>
-                            {leptos_autoreload}
-                            "#
+                            {}
+                            {}
+                            "#,
+                    hydration_scripts(&nonce),
+                    leptos_autoreload(&nonce),
                 );
                 let tail = "</body></html>";
 
-            let (stream, runtime, _) = render_to_stream_with_prefix_undisposed(
+            let (stream, runtime, scope) = render_to_stream_with_prefix_undisposed(
                 app,
                 move |cx| {
                     let head = use_context::<MetaContext>(cx)
-                        .map(|meta| meta.dehydrate())
+                        .map(|meta| meta.with_nonce(use_nonce(cx)))
+                        .map(|meta| match (&critical_stylesheet, take_rendered_selectors(cx)) {
+                            (Some(stylesheet), Some(selectors)) => meta
+                                .dehydrate_with_critical_css(&extract_critical_css(stylesheet, &selectors)),
+                            _ => meta.dehydrate(),
+                        })
                         .unwrap_or_default();
                     format!("{head}</head><body>").into()
                 });
 
+            let cx = Scope { runtime, id: scope };
+            let html_attributes = use_context::<MetaContext>(cx)
+                .map(|meta| meta.html_attributes())
+                .unwrap_or_default();
+            let head = head(html_attributes, nonce_attr(cx));
+            let asset_manifest = options.asset_manifest.clone();
+
             let mut stream = Box::pin(futures::stream::once(async move { head.clone() }) 
                 .chain(stream)
                 .chain(futures::stream::once(async move {
+                    // Load the bundles of the islands that were rendered
+                    let islands = use_context::<IslandManifest>(cx)
+                        .map(|manifest| manifest.bootstrap_script(use_nonce(cx).as_ref(), |bundle| (
+                            asset_manifest.resolve(&format!("/{bundle_path}/{bundle}.js")),
+                            asset_manifest.resolve(&format!("/{bundle_path}/{bundle}_bg.wasm")),
+                        )))
+                        .unwrap_or_default();
                     runtime.dispose();
-                    tail.to_string()
+                    format!("{islands}{tail}")
                 }))
                 .map(|html| Ok(web::Bytes::from(html)) as Result<web::Bytes>));
 
@@ -319,7 +516,14 @@ where IV: IntoView
             let res_options = res_options.0.read().await;
 
             let (status, mut headers) = (res_options.status.clone(), res_options.headers.clone());
-            let status = status.unwrap_or_default();
+            // the status of an error page the router rendered, unless the app set one itself
+            let status = status
+                .or_else(|| {
+                    route_status
+                        .take()
+                        .and_then(|status| StatusCode::from_u16(status).ok())
+                })
+                .unwrap_or_default();
             
             let complete_stream =
             futures::stream::iter([first_chunk.unwrap(), second_chunk.unwrap(), third_chunk.unwrap()])
@@ -333,12 +537,97 @@ where IV: IntoView
                 res.headers_mut().append(key, value);
                 }
             };
+            if let Some(vary) = vary {
+                res.headers_mut().append(http::header::VARY, vary);
+            }
+            // Add any cookies set while rendering the app shell
+            for cookie in cookies.take_set_cookie_headers() {
+                if let Ok(value) = http::header::HeaderValue::from_str(&cookie) {
+                    res.headers_mut().append(http::header::SET_COOKIE, value);
+                }
+            }
             // Set status to what is returned in the function
             let res_status = res.status_mut();
             *res_status = status;
+            // Redirect instead, if the router asked for it
+            if let Some(location) = redirect.take() {
+                if let Ok(value) = http::header::HeaderValue::from_str(&location) {
+                    *res.status_mut() = StatusCode::MOVED_PERMANENTLY;
+                    res.headers_mut().insert(http::header::LOCATION, value);
+                }
+            }
             // Return the response
             res
 
         }
     })
 }
+
+/// Reads the user's preferences from the client hint headers, for the media query signals.
+fn client_hints(headers: &HeaderMap) -> ClientHints {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    ClientHints::from_headers(
+        header("Sec-CH-Prefers-Color-Scheme"),
+        header("Sec-CH-Prefers-Reduced-Motion"),
+    )
+}
+
+/// Renders only the view of the matched route, without the document shell or hydration scripts,
+/// once the resources read under each `<Suspense/>` have loaded.
+async fn render_partial<IV>(
+    app_fn: impl Fn(leptos::Scope) -> IV + 'static,
+    path: String,
+    req: HttpRequest,
+    cookies: CookieJar,
+    asset_manifest: AssetManifest,
+    res_options: ResponseOptions,
+    vary: Option<http::header::HeaderValue>,
+) -> HttpResponse
+where
+    IV: IntoView,
+{
+    let html = render_fragment_to_string_async({
+        let cookies = cookies.clone();
+        let res_options = res_options.clone();
+        move |cx| {
+            let integration = ServerIntegration { path };
+            provide_context(cx, RouterIntegrationContext::new(integration));
+            provide_context(cx, MetaContext::new());
+            provide_context(cx, res_options);
+            provide_context(cx, client_hints(req.headers()));
+            if let Some(config) = req.app_data::<SessionConfig>() {
+                provide_context(cx, config.clone());
+            }
+            provide_context(cx, req);
+            provide_context(cx, asset_manifest);
+            provide_context(cx, cookies);
+
+            let partial = PartialRender::default();
+            provide_context(cx, partial.clone());
+            // renders nothing but the matched route into `partial`
+            _ = app_fn(cx).into_view(cx);
+            partial.take()
+        }
+    })
+    .await;
+
+    let res_options = res_options.0.read().await;
+    let mut res = HttpResponse::Ok().content_type("text/html").body(html);
+    for (key, value) in res_options.headers.clone().drain() {
+        if let Some(key) = key {
+            res.headers_mut().append(key, value);
+        }
+    }
+    for cookie in cookies.take_set_cookie_headers() {
+        if let Ok(value) = http::header::HeaderValue::from_str(&cookie) {
+            res.headers_mut().append(http::header::SET_COOKIE, value);
+        }
+    }
+    if let Some(vary) = vary {
+        res.headers_mut().append(http::header::VARY, vary);
+    }
+    if let Some(status) = res_options.status {
+        *res.status_mut() = status;
+    }
+    res
+}
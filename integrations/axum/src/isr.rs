@@ -0,0 +1,303 @@
+use crate::{render_app_to_stream, PinnedHtmlStream};
+use axum::{
+    body::{Body, Bytes, StreamBody},
+    http::{HeaderMap, HeaderValue, Method, Request, StatusCode, Uri},
+};
+use futures::Future;
+use http::Response;
+use leptos::*;
+use leptos_router::Matcher;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{Arc, RwLock},
+    time::{Duration, Instant},
+};
+
+/// A cache of rendered pages for incremental static regeneration (ISR), used with
+/// [render_app_with_isr].
+///
+/// Each route that should be cached declares how long its pages stay fresh. A fresh page is
+/// served from the cache without rendering the app. Once it's stale, it's still served from
+/// the cache, while it's rendered again in the background for the next request.
+///
+/// The cache can be cloned cheaply, so a clone can be kept to [purge](IsrCache::purge) pages
+/// whose content changed, e.g., in a server function that saves an edit.
+///
+/// ```
+/// use leptos_axum::IsrCache;
+/// use std::time::Duration;
+///
+/// let cache = IsrCache::new()
+///     .revalidate("/", Duration::from_secs(60))
+///     .revalidate("/blog/:slug", Duration::from_secs(600));
+///
+/// // after a post is edited, render it again on the next request
+/// cache.purge("/blog/hello-world");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IsrCache {
+    routes: Arc<Vec<(Matcher, Duration)>>,
+    pages: Arc<RwLock<HashMap<String, CachedPage>>>,
+}
+
+#[derive(Clone, Debug)]
+struct CachedPage {
+    html: Bytes,
+    headers: HeaderMap,
+    generated_at: Instant,
+    invalidated: bool,
+    regenerating: bool,
+}
+
+enum Lookup {
+    Fresh(CachedPage),
+    Stale(CachedPage),
+    Missing,
+}
+
+impl IsrCache {
+    /// Creates a cache that doesn't cache any routes yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Caches the pages of the route with the given path, which uses the same syntax as the
+    /// `path` of a [Route](leptos_router::Route), and renders them again once they are older
+    /// than `interval`. The first route that matches a path is used.
+    pub fn revalidate(mut self, path: &str, interval: Duration) -> Self {
+        Arc::make_mut(&mut self.routes).push((Matcher::new(path), interval));
+        self
+    }
+
+    /// Removes the cached page for `path`, so that it's rendered on the next request.
+    pub fn purge(&self, path: &str) {
+        self.pages.write().unwrap().remove(path);
+    }
+
+    /// Removes every cached page.
+    pub fn purge_all(&self) {
+        self.pages.write().unwrap().clear();
+    }
+
+    /// Marks the cached page for `path` as stale, so that it's served once more while it's
+    /// rendered again in the background.
+    pub fn invalidate(&self, path: &str) {
+        if let Some(page) = self.pages.write().unwrap().get_mut(path) {
+            page.invalidated = true;
+        }
+    }
+
+    fn interval(&self, path: &str) -> Option<Duration> {
+        self.routes
+            .iter()
+            .find(|(matcher, _)| matcher.test(path).is_some())
+            .map(|(_, interval)| *interval)
+    }
+
+    fn lookup(&self, path: &str, interval: Duration) -> Lookup {
+        let mut pages = self.pages.write().unwrap();
+        match pages.get_mut(path) {
+            None => Lookup::Missing,
+            Some(page) if !page.invalidated && page.generated_at.elapsed() < interval => {
+                Lookup::Fresh(page.clone())
+            }
+            Some(page) => {
+                // only one request regenerates a stale page
+                let page_to_serve = page.clone();
+                if page.regenerating {
+                    Lookup::Fresh(page_to_serve)
+                } else {
+                    page.regenerating = true;
+                    Lookup::Stale(page_to_serve)
+                }
+            }
+        }
+    }
+}
+
+/// Returns an Axum [Handler](axum::handler::Handler) like [render_app_to_stream], but serves
+/// the routes declared in the [IsrCache] from it, rendering each page again in the background
+/// once it's stale.
+///
+/// Cached pages are rendered from the path alone, without the headers or cookies of the request,
+/// since they are served to every user. Only `GET` requests without a query string are cached,
+/// and only responses with the status `200 OK`. `Set-Cookie` headers are never cached, and
+/// responses marked `Cache-Control: private` or `no-store` are rendered for each request.
+/// ```
+/// use axum::Router;
+/// use leptos::*;
+/// use leptos_axum::{render_app_with_isr, IsrCache};
+/// use leptos_config::get_configuration;
+/// use std::time::Duration;
+///
+/// #[component]
+/// fn MyApp(cx: Scope) -> impl IntoView {
+///   view! { cx, <main>"Hello, world!"</main> }
+/// }
+///
+/// # if false { // don't actually try to run a server in a doctest...
+/// #[tokio::main]
+/// async fn main() {
+///     let conf = get_configuration(Some("Cargo.toml")).await.unwrap();
+///     let leptos_options = conf.leptos_options;
+///     let addr = leptos_options.site_address.clone();
+///
+///     let cache = IsrCache::new().revalidate("/blog/:slug", Duration::from_secs(600));
+///     let app = Router::new().fallback(render_app_with_isr(
+///         leptos_options,
+///         cache,
+///         |cx| view! { cx, <MyApp/> },
+///     ));
+///
+///     axum::Server::bind(&addr)
+///         .serve(app.into_make_service())
+///         .await
+///         .unwrap();
+/// }
+/// # }
+/// ```
+pub fn render_app_with_isr<IV>(
+    options: LeptosOptions,
+    cache: IsrCache,
+    app_fn: impl Fn(leptos::Scope) -> IV + Clone + Send + 'static,
+) -> impl Fn(
+    Request<Body>,
+) -> Pin<Box<dyn Future<Output = Response<StreamBody<PinnedHtmlStream>>> + Send + 'static>>
+       + Clone
+       + Send
+       + 'static
+where
+    IV: IntoView,
+{
+    let render = render_app_to_stream(options, app_fn);
+
+    move |req: Request<Body>| {
+        let render = render.clone();
+        let cache = cache.clone();
+        Box::pin(async move {
+            let path = req.uri().path().to_string();
+            let interval = match cache.interval(&path) {
+                // a query could change the page, and would give each visitor their own copy
+                Some(interval) if req.method() == Method::GET && req.uri().query().is_none() => {
+                    interval
+                }
+                _ => return render(req).await,
+            };
+
+            match cache.lookup(&path, interval) {
+                Lookup::Fresh(page) => cached_response(page),
+                Lookup::Stale(page) => {
+                    let regenerating = Regenerating {
+                        cache: cache.clone(),
+                        path: path.clone(),
+                    };
+                    let uri = req.uri().clone();
+                    tokio::spawn(async move {
+                        let _regenerating = regenerating;
+                        regenerate(render, cache, path, uri).await
+                    });
+                    cached_response(page)
+                }
+                Lookup::Missing => {
+                    match regenerate(
+                        render.clone(),
+                        cache.clone(),
+                        path.clone(),
+                        req.uri().clone(),
+                    )
+                    .await
+                    {
+                        Some(page) => cached_response(page),
+                        // errors and redirects aren't cached, so they're rendered for each request
+                        None => render(req).await,
+                    }
+                }
+            }
+        })
+    }
+}
+
+/// Renders the page at `uri` and stores it in the cache, if it rendered successfully.
+async fn regenerate<R>(render: R, cache: IsrCache, path: String, uri: Uri) -> Option<CachedPage>
+where
+    R: Fn(
+        Request<Body>,
+    )
+        -> Pin<Box<dyn Future<Output = Response<StreamBody<PinnedHtmlStream>>> + Send + 'static>>,
+{
+    let mut req = Request::new(Body::empty());
+    *req.uri_mut() = uri;
+
+    let res = render(req).await;
+    let (parts, body) = res.into_parts();
+    let page = match shared_headers(parts.status, parts.headers) {
+        Some(headers) => hyper::body::to_bytes(body)
+            .await
+            .ok()
+            .map(|html| CachedPage {
+                html,
+                headers,
+                generated_at: Instant::now(),
+                invalidated: false,
+                regenerating: false,
+            }),
+        None => None,
+    };
+
+    if let Some(page) = &page {
+        cache.pages.write().unwrap().insert(path, page.clone());
+    }
+    page
+}
+
+/// Clears the `regenerating` flag of a stale page when the task rendering it again ends, even if
+/// rendering failed or panicked, so that the stale page is served and rendered again on the next
+/// request instead of staying stale forever.
+struct Regenerating {
+    cache: IsrCache,
+    path: String,
+}
+
+impl Drop for Regenerating {
+    fn drop(&mut self) {
+        // the lock is poisoned if the task panicked while holding it
+        if let Ok(mut pages) = self.cache.pages.write() {
+            if let Some(page) = pages.get_mut(&self.path) {
+                page.regenerating = false;
+            }
+        }
+    }
+}
+
+/// Returns the headers of a response that can be served to every user, without the headers that
+/// only apply to the request it was rendered for, or `None` if the response can't be cached.
+fn shared_headers(status: StatusCode, mut headers: HeaderMap) -> Option<HeaderMap> {
+    if status != StatusCode::OK {
+        return None;
+    }
+    let is_private = headers
+        .get_all(http::header::CACHE_CONTROL)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .any(|value| value.contains("private") || value.contains("no-store"));
+    if is_private {
+        return None;
+    }
+    headers.remove(http::header::SET_COOKIE);
+    Some(headers)
+}
+
+fn cached_response(page: CachedPage) -> Response<StreamBody<PinnedHtmlStream>> {
+    let html = page.html;
+    let body = futures::stream::once(async move { Ok(html) });
+    let mut res = Response::new(StreamBody::new(Box::pin(body) as PinnedHtmlStream));
+    res.headers_mut().extend(page.headers);
+    if !res.headers().contains_key(http::header::CONTENT_TYPE) {
+        res.headers_mut().insert(
+            http::header::CONTENT_TYPE,
+            HeaderValue::from_static("text/html; charset=utf-8"),
+        );
+    }
+    res
+}
@@ -1,6 +1,6 @@
 use axum::{
     body::{Body, Bytes, Full, StreamBody},
-    extract::Path,
+    extract::{OriginalUri, Path},
     http::{HeaderMap, HeaderValue, Request, StatusCode},
     response::IntoResponse,
 };
@@ -13,6 +13,9 @@ use leptos_router::*;
 use std::{io, pin::Pin, sync::Arc};
 use tokio::{sync::RwLock, task::spawn_blocking};
 
+mod isr;
+pub use isr::*;
+
 /// A struct to hold the parts of the incoming Request. Since `http::Request` isn't cloneable, we're forced
 /// to construct this for Leptos to use in Axum
 #[derive(Debug, Clone)]
@@ -91,15 +94,17 @@ pub async fn generate_request_parts(req: Request<Body>) -> RequestParts {
 /// Leptos provides a generic implementation of `handle_server_fns`. If access to more specific parts of the Request is desired,
 /// you can specify your own server fn handler based on this one and give it it's own route in the server macro.
 pub async fn handle_server_fns(
-    Path(fn_name): Path<String>,
+    _: Path<String>,
     headers: HeaderMap,
     req: Request<Body>,
 ) -> impl IntoResponse {
-    // Axum Path extractor doesn't remove the first slash from the path, while Actix does
-    let fn_name: String = match fn_name.strip_prefix("/") {
-        Some(path) => path.to_string(),
-        None => fn_name,
-    };
+    // the full path tells apart server functions of different apps, even in a nested router
+    let full_path = req
+        .extensions()
+        .get::<OriginalUri>()
+        .map(|uri| uri.path().to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let session_config = req.extensions().get::<SessionConfig>().cloned();
 
     let (tx, rx) = futures::channel::oneshot::channel();
     spawn_blocking({
@@ -108,7 +113,7 @@ pub async fn handle_server_fns(
                 .expect("couldn't spawn runtime")
                 .block_on({
                     async move {
-                        let res = if let Some(server_fn) = server_fn_by_path(fn_name.as_str()) {
+                        let res = if let Some(server_fn) = server_fn_by_path(&full_path) {
                             let runtime = create_runtime();
                             let (cx, disposer) = raw_scope_and_disposer(runtime);
 
@@ -117,6 +122,43 @@ pub async fn handle_server_fns(
                             provide_context(cx, req_parts.clone());
                             // Add this so that we can set headers and status of the response
                             provide_context(cx, ResponseOptions::default());
+                            // Add this so that server functions can read and set cookies
+                            let cookies = CookieJar::from_header(
+                                req_parts
+                                    .headers
+                                    .get("Cookie")
+                                    .and_then(|value| value.to_str().ok()),
+                            );
+                            provide_context(cx, cookies.clone());
+                            if let Some(config) = session_config {
+                                provide_context(cx, config);
+                            }
+                            // Add this so that server functions can ask the client to navigate
+                            let redirect = ServerFnRedirect::default();
+                            provide_context(cx, redirect.clone());
+
+                            let accept_header =
+                                headers.get("Accept").and_then(|value| value.to_str().ok());
+                            // the client accepts the server function's encoding, which also gives the
+                            // content type of a binary result
+                            let encoding = accept_header.and_then(Encoding::from_content_type);
+                            // if this isn't a fetch() call, it's probably a <form> submit or
+                            // something, which is redirected back to the referrer
+                            let is_form_submit = encoding.is_none()
+                                && !matches!(accept_header, Some("application/json"));
+                            // only a browser's <form> is sent back to a page that can show the
+                            // submission
+                            let is_browser_form = is_form_submit
+                                && FormSubmission::is_browser_form(
+                                    headers
+                                        .get("Content-Type")
+                                        .and_then(|value| value.to_str().ok()),
+                                    accept_header,
+                                );
+                            let referer = headers
+                                .get("Referer")
+                                .and_then(|value| value.to_str().ok())
+                                .unwrap_or("/");
 
                             match server_fn(cx, &req_parts.body).await {
                                 Ok(serialized) => {
@@ -127,11 +169,16 @@ pub async fn handle_server_fns(
                                     disposer.dispose();
                                     runtime.dispose();
 
-                                    // if this is Accept: application/json then send a serialized JSON response
-                                    let accept_header =
-                                        headers.get("Accept").and_then(|value| value.to_str().ok());
                                     let mut res = Response::builder();
 
+                                    if is_browser_form {
+                                        store_form_submission(
+                                            &cookies,
+                                            &req_parts,
+                                            Ok(&serialized),
+                                        );
+                                    }
+
                                     // Add headers from ResponseParts if they exist. These should be added as long
                                     // as the server function returns an OK response
                                     let res_options_outer = res_options.unwrap().0;
@@ -144,27 +191,32 @@ pub async fn handle_server_fns(
                                     match res.headers_mut() {
                                         Some(header_ref) => {
                                             header_ref.extend(res_headers.drain());
+                                            // Add any cookies set by the server function
+                                            for cookie in cookies.take_set_cookie_headers() {
+                                                if let Ok(value) = HeaderValue::from_str(&cookie) {
+                                                    header_ref
+                                                        .append(http::header::SET_COOKIE, value);
+                                                }
+                                            }
                                         }
                                         None => (),
                                     };
 
-                                    if accept_header == Some("application/json")
-                                        || accept_header
-                                            == Some("application/x-www-form-urlencoded")
-                                        || accept_header == Some("application/cbor")
-                                    {
+                                    let redirect = redirect.take();
+                                    if !is_form_submit {
                                         res = res.status(StatusCode::OK);
+                                        // the client navigates once it has read the result
+                                        if let Some(location) = &redirect {
+                                            res = res.header(REDIRECT_HEADER, location.as_str());
+                                        }
                                     }
-                                    // otherwise, it's probably a <form> submit or something: redirect back to the referrer
+                                    // otherwise, redirect back to the referrer, which can show the
+                                    // result, or wherever the server function asked to go
                                     else {
-                                        let referer = headers
-                                            .get("Referer")
-                                            .and_then(|value| value.to_str().ok())
-                                            .unwrap_or("/");
-
-                                        res = res
-                                            .status(StatusCode::SEE_OTHER)
-                                            .header("Location", referer);
+                                        res = res.status(StatusCode::SEE_OTHER).header(
+                                            "Location",
+                                            redirect.as_deref().unwrap_or(referer),
+                                        );
                                     }
                                     // Override StatusCode if it was set in a Resource or Element
                                     res = match status {
@@ -173,7 +225,10 @@ pub async fn handle_server_fns(
                                     };
                                     match serialized {
                                         Payload::Binary(data) => res
-                                            .header("Content-Type", "application/cbor")
+                                            .header(
+                                                "Content-Type",
+                                                encoding.unwrap_or(Encoding::Cbor).content_type(),
+                                            )
                                             .body(Full::from(data)),
                                         Payload::Url(data) => res
                                             .header(
@@ -186,6 +241,18 @@ pub async fn handle_server_fns(
                                             .body(Full::from(data)),
                                     }
                                 }
+                                // a <form> submitted without JavaScript goes back to the referrer,
+                                // which can show the error along with the values that were submitted
+                                Err(e) if is_browser_form => {
+                                    store_form_submission(&cookies, &req_parts, Err(&e));
+                                    let mut res = Response::builder()
+                                        .status(StatusCode::SEE_OTHER)
+                                        .header("Location", referer);
+                                    for cookie in cookies.take_set_cookie_headers() {
+                                        res = res.header(http::header::SET_COOKIE, cookie);
+                                    }
+                                    res.body(Full::from(String::new()))
+                                }
                                 Err(e) => Response::builder()
                                     .status(StatusCode::INTERNAL_SERVER_ERROR)
                                     .body(Full::from(e.to_string())),
@@ -208,6 +275,27 @@ pub async fn handle_server_fns(
     rx.await.unwrap()
 }
 
+/// Stores what was submitted to a server function by a `<form>` without JavaScript, and what it
+/// returned, so that the `<ActionForm/>` on the page the browser is redirected to can show them.
+fn store_form_submission(
+    cookies: &CookieJar,
+    req_parts: &RequestParts,
+    output: Result<&Payload, &ServerFnError>,
+) {
+    let output = match output {
+        Ok(Payload::Json(data)) | Ok(Payload::Url(data)) => Ok(data.clone()),
+        // the form reads results as JSON
+        Ok(Payload::Binary(_)) => return,
+        Err(e) => Err(e.clone()),
+    };
+    FormSubmission {
+        url: req_parts.uri.path().to_string(),
+        input: String::from_utf8_lossy(&req_parts.body).into_owned(),
+        output,
+    }
+    .store(cookies);
+}
+
 pub type PinnedHtmlStream = Pin<Box<dyn Stream<Item = io::Result<Bytes>> + Send>>;
 
 /// Returns an Axum [Handler](axum::handler::Handler) that listens for a `GET` request and tries
@@ -266,9 +354,24 @@ pub fn render_app_to_stream<IV>(
 where
     IV: IntoView,
 {
+    // read the stylesheet once, so that critical CSS can be extracted for each request
+    let critical_stylesheet: Option<Arc<str>> = options
+        .critical_css
+        .as_ref()
+        .and_then(|path| match std::fs::read_to_string(path) {
+            Ok(stylesheet) => Some(stylesheet),
+            // the page still works without critical CSS, so it's rendered without it
+            Err(e) => {
+                leptos::error!("could not read the critical CSS stylesheet `{path}`: {e}");
+                None
+            }
+        })
+        .map(Arc::from);
+
     move |req: Request<Body>| {
         Box::pin({
             let options = options.clone();
+            let critical_stylesheet = critical_stylesheet.clone();
             let app_fn = app_fn.clone();
             let default_res_options = ResponseOptions::default();
             let res_options2 = default_res_options.clone();
@@ -286,6 +389,29 @@ where
                     full_path = "http://leptos".to_string() + &path.to_string()
                 }
 
+                // partial-page requests (e.g., from htmx) only get the matched route's HTML
+                let is_partial = options
+                    .partial_render_header
+                    .as_deref()
+                    .map(|header| req.headers().contains_key(header))
+                    .unwrap_or(false);
+                // the same URL renders differently with and without that header, so caches need
+                // to tell them apart
+                let vary = options
+                    .partial_render_header
+                    .as_deref()
+                    .and_then(|header| HeaderValue::from_str(header).ok());
+                if is_partial {
+                    return render_partial(
+                        app_fn,
+                        full_path,
+                        req,
+                        options.asset_manifest.clone(),
+                        vary,
+                    )
+                    .await;
+                }
+
                 let site_root = &options.site_root;
                 let pkg_path = &options.site_pkg_dir;
 
@@ -307,13 +433,24 @@ where
                     wasm_output_name.push_str("_bg");
                 }
 
-                let site_ip = &options.site_address.ip().to_string();
+                let site_ip = options.site_address.ip().to_string();
                 let reload_port = options.reload_port;
 
-                let leptos_autoreload = match std::env::var("LEPTOS_WATCH").is_ok() {
+                // resolve the bundle to its fingerprinted filenames, if an asset manifest was loaded
+                let js_path = options
+                    .asset_manifest
+                    .resolve(&format!("/{bundle_path}/{output_name}.js"));
+                let wasm_path = options
+                    .asset_manifest
+                    .resolve(&format!("/{bundle_path}/{wasm_output_name}.wasm"));
+
+                // the scripts are allowed by the nonce the app provides, so they're only written once
+                // it has rendered
+                let live_reload = std::env::var("LEPTOS_WATCH").is_ok();
+                let leptos_autoreload = move |nonce: &str| match live_reload {
                     true => format!(
                         r#"
-                        <script crossorigin="">(function () {{
+                        <script crossorigin=""{nonce}>(function () {{
                             var ws = new WebSocket('ws://{site_ip}:{reload_port}/live_reload');
                             ws.onmessage = (ev) => {{
                                 let msg = JSON.parse(event.data);
@@ -337,21 +474,43 @@ where
                     false => "".to_string(),
                 };
 
-                let head = format!(
-                    r#"<!DOCTYPE html>
-                    <html lang="en">
+                // in islands mode, each island loads its own bundle once the page has rendered
+                let islands_mode = options.islands;
+                let hydration_scripts = move |nonce: &str| match islands_mode {
+                    true => "".to_string(),
+                    false => format!(
+                        r#"<link rel="modulepreload" href="{js_path}">
+                            <link rel="preload" href="{wasm_path}" as="fetch" type="application/wasm" crossorigin="">
+                            <script type="module"{nonce}>import init, {{ hydrate }} from '{js_path}'; init('{wasm_path}').then(hydrate);</script>"#
+                    ),
+                };
+
+                // the attributes of `<html>` and the nonce are set by the app, so they're sent once
+                // it has rendered
+                let (head_tx, head_rx) = futures::channel::oneshot::channel();
+                let head = async move {
+                    let (html_attributes, nonce): (String, String) =
+                        head_rx.await.unwrap_or_default();
+                    format!(
+                        r#"<!DOCTYPE html>
+                    <html lang="en"{html_attributes}>
                         <head>
                             <meta charset="utf-8"/>
                             <meta name="viewport" content="width=device-width, initial-scale=1"/>
-                            <link rel="modulepreload" href="/{bundle_path}/{output_name}.js">
-                            <link rel="preload" href="/{bundle_path}/{wasm_output_name}.wasm" as="fetch" type="application/wasm" crossorigin="">
-                            <script type="module">import init, {{ hydrate }} from '/{bundle_path}/{output_name}.js'; init('/{bundle_path}/{wasm_output_name}.wasm').then(hydrate);</script>
-                            {leptos_autoreload}
-                            "#
-                );
+                            {}
+                            {}
+                            "#,
+                        hydration_scripts(&nonce),
+                        leptos_autoreload(&nonce),
+                    )
+                };
                 let tail = "</body></html>";
 
                 let (mut tx, rx) = futures::channel::mpsc::channel(8);
+                let asset_manifest = options.asset_manifest.clone();
+                let collect_selectors = critical_stylesheet.is_some();
+                let islands = options.islands;
+                let in_order_streaming = options.in_order_streaming;
 
                 spawn_blocking({
                     let app_fn = app_fn.clone();
@@ -365,6 +524,9 @@ where
                                         .run_until(async {
                                             let app = {
                                                 let full_path = full_path.clone();
+                                                let asset_manifest = asset_manifest.clone();
+                                                let session_config =
+                                                    req.extensions().get::<SessionConfig>().cloned();
                                                 let req_parts = generate_request_parts(req).await;
                                                 move |cx| {
                                                     let integration = ServerIntegration {
@@ -375,8 +537,36 @@ where
                                                         RouterIntegrationContext::new(integration),
                                                     );
                                                     provide_context(cx, MetaContext::new());
+                                                    provide_context(
+                                                        cx,
+                                                        CookieJar::from_header(
+                                                            req_parts
+                                                                .headers
+                                                                .get("Cookie")
+                                                                .and_then(|value| value.to_str().ok()),
+                                                        ),
+                                                    );
+                                                    if let Some(config) = session_config {
+                                                        provide_context(cx, config);
+                                                    }
+                                                    provide_context(cx, client_hints(&req_parts.headers));
                                                     provide_context(cx, req_parts);
                                                     provide_context(cx, default_res_options);
+                                                    provide_context(cx, ServerRedirect::default());
+                                                    provide_context(cx, ServerStatus::default());
+                                                    provide_context(cx, asset_manifest);
+                                                    if islands {
+                                                        provide_context(cx, IslandManifest::default());
+                                                    }
+                                                    if in_order_streaming {
+                                                        provide_context(cx, StreamingOptions {
+                                                            mode: StreamingMode::InOrder,
+                                                            ..Default::default()
+                                                        });
+                                                    }
+                                                    if collect_selectors {
+                                                        collect_rendered_selectors(cx);
+                                                    }
                                                     app_fn(cx).into_view(cx)
                                                 }
                                             };
@@ -384,24 +574,97 @@ where
                                             let (bundle, runtime, scope) =
                                                 render_to_stream_with_prefix_undisposed(
                                                     app,
-                                                    |cx| {
+                                                    move |cx| {
                                                         let head = use_context::<MetaContext>(cx)
-                                                            .map(|meta| meta.dehydrate())
+                                                            .map(|meta| meta.with_nonce(use_nonce(cx)))
+                                                            .map(|meta| {
+                                                                match (
+                                                                    &critical_stylesheet,
+                                                                    take_rendered_selectors(cx),
+                                                                ) {
+                                                                    (Some(stylesheet), Some(selectors)) => meta
+                                                                        .dehydrate_with_critical_css(
+                                                                            &extract_critical_css(
+                                                                                stylesheet, &selectors,
+                                                                            ),
+                                                                        ),
+                                                                    _ => meta.dehydrate(),
+                                                                }
+                                                            })
                                                             .unwrap_or_default();
                                                         format!("{head}</head><body>").into()
                                                     },
                                                 );
+                                            let cx = Scope { runtime, id: scope };
+                                            _ = head_tx.send((
+                                                use_context::<MetaContext>(cx)
+                                                    .map(|meta| meta.html_attributes())
+                                                    .unwrap_or_default(),
+                                                nonce_attr(cx),
+                                            ));
+
                                             let mut shell = Box::pin(bundle);
                                             while let Some(fragment) = shell.next().await {
                                                 _ = tx.send(fragment).await;
                                             }
 
+                                            // Load the bundles of the islands that were rendered
+                                            if let Some(manifest) = use_context::<IslandManifest>(cx) {
+                                                _ = tx
+                                                    .send(manifest.bootstrap_script(use_nonce(cx).as_ref(), |bundle| {
+                                                        (
+                                                            asset_manifest.resolve(&format!(
+                                                                "/{bundle_path}/{bundle}.js"
+                                                            )),
+                                                            asset_manifest.resolve(&format!(
+                                                                "/{bundle_path}/{bundle}_bg.wasm"
+                                                            )),
+                                                        )
+                                                    }))
+                                                    .await;
+                                            }
+
                                             // Extract the value of ResponseOptions from here
-                                            let cx = Scope { runtime, id: scope };
                                             let res_options =
                                                 use_context::<ResponseOptions>(cx).unwrap();
 
-                                            let new_res_parts = res_options.0.read().await.clone();
+                                            let mut new_res_parts = res_options.0.read().await.clone();
+
+                                            // Add any cookies set while rendering
+                                            if let Some(cookies) = use_context::<CookieJar>(cx) {
+                                                for cookie in cookies.take_set_cookie_headers() {
+                                                    if let Ok(value) = HeaderValue::from_str(&cookie) {
+                                                        new_res_parts
+                                                            .headers
+                                                            .append(http::header::SET_COOKIE, value);
+                                                    }
+                                                }
+                                            }
+
+                                            if let Some(vary) = vary {
+                                                new_res_parts.headers.append(http::header::VARY, vary);
+                                            }
+
+                                            // Use the status of an error page the router rendered,
+                                            // unless the app set one itself
+                                            if new_res_parts.status.is_none() {
+                                                new_res_parts.status = use_context::<ServerStatus>(cx)
+                                                    .and_then(|status| status.take())
+                                                    .and_then(|status| StatusCode::from_u16(status).ok());
+                                            }
+
+                                            // Redirect instead, if the router asked for it
+                                            if let Some(location) = use_context::<ServerRedirect>(cx)
+                                                .and_then(|redirect| redirect.take())
+                                            {
+                                                if let Ok(value) = HeaderValue::from_str(&location) {
+                                                    new_res_parts.status =
+                                                        Some(StatusCode::MOVED_PERMANENTLY);
+                                                    new_res_parts
+                                                        .headers
+                                                        .insert(http::header::LOCATION, value);
+                                                }
+                                            }
 
                                             let mut writable = res_options2.0.write().await;
                                             *writable = new_res_parts;
@@ -417,7 +680,7 @@ where
                 });
 
                 let mut stream = Box::pin(
-                    futures::stream::once(async move { head.clone() })
+                    futures::stream::once(head)
                         .chain(rx)
                         .chain(futures::stream::once(async { tail.to_string() }))
                         .map(|html| Ok(Bytes::from(html))),
@@ -454,3 +717,92 @@ where
         })
     }
 }
+
+/// Reads the user's preferences from the client hint headers, for the media query signals.
+fn client_hints(headers: &HeaderMap) -> ClientHints {
+    let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok());
+    ClientHints::from_headers(
+        header("Sec-CH-Prefers-Color-Scheme"),
+        header("Sec-CH-Prefers-Reduced-Motion"),
+    )
+}
+
+/// Renders only the view of the matched route, without the document shell or hydration scripts,
+/// once the resources read under each `<Suspense/>` have loaded.
+async fn render_partial<IV>(
+    app_fn: impl Fn(leptos::Scope) -> IV + Send + 'static,
+    full_path: String,
+    req: Request<Body>,
+    asset_manifest: AssetManifest,
+    vary: Option<HeaderValue>,
+) -> Response<StreamBody<PinnedHtmlStream>>
+where
+    IV: IntoView,
+{
+    let session_config = req.extensions().get::<SessionConfig>().cloned();
+    let req_parts = generate_request_parts(req).await;
+
+    let (html, res_parts) = spawn_blocking(move || {
+        tokio::runtime::Runtime::new()
+            .expect("couldn't spawn runtime")
+            .block_on(tokio::task::LocalSet::new().run_until(async move {
+                let res_options = ResponseOptions::default();
+                let cookies = CookieJar::from_header(
+                    req_parts
+                        .headers
+                        .get("Cookie")
+                        .and_then(|value| value.to_str().ok()),
+                );
+
+                let html = render_fragment_to_string_async({
+                    let res_options = res_options.clone();
+                    let cookies = cookies.clone();
+                    move |cx| {
+                        let integration = ServerIntegration { path: full_path };
+                        provide_context(cx, RouterIntegrationContext::new(integration));
+                        provide_context(cx, MetaContext::new());
+                        provide_context(cx, cookies);
+                        if let Some(config) = session_config {
+                            provide_context(cx, config);
+                        }
+                        provide_context(cx, client_hints(&req_parts.headers));
+                        provide_context(cx, req_parts);
+                        provide_context(cx, res_options);
+                        provide_context(cx, asset_manifest);
+
+                        let partial = PartialRender::default();
+                        provide_context(cx, partial.clone());
+                        // renders nothing but the matched route into `partial`
+                        _ = app_fn(cx).into_view(cx);
+                        partial.take()
+                    }
+                })
+                .await;
+
+                let mut res_parts = res_options.0.read().await.clone();
+                for cookie in cookies.take_set_cookie_headers() {
+                    if let Ok(value) = HeaderValue::from_str(&cookie) {
+                        res_parts.headers.append(http::header::SET_COOKIE, value);
+                    }
+                }
+                (html, res_parts)
+            }))
+    })
+    .await
+    .expect("failed to render partial response");
+
+    let body = futures::stream::once(async move { Ok(Bytes::from(html)) });
+    let mut res = Response::new(StreamBody::new(Box::pin(body) as PinnedHtmlStream));
+    if let Some(status) = res_parts.status {
+        *res.status_mut() = status;
+    }
+    res.headers_mut().insert(
+        http::header::CONTENT_TYPE,
+        HeaderValue::from_static("text/html; charset=utf-8"),
+    );
+    res.headers_mut().extend(res_parts.headers);
+    if let Some(vary) = vary {
+        res.headers_mut().append(http::header::VARY, vary);
+    }
+    res
+}
@@ -0,0 +1,145 @@
+use axum::body::Body;
+use http::{HeaderMap, Request};
+use leptos::*;
+use leptos_axum::{render_app_with_isr, IsrCache, PinnedHtmlStream};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+type Handler = Box<
+    dyn Fn(
+        Request<Body>,
+    ) -> Pin<
+        Box<dyn Future<Output = http::Response<axum::body::StreamBody<PinnedHtmlStream>>> + Send>,
+    >,
+>;
+
+/// An app that counts how many times it has been rendered, and sets a cookie each time.
+fn counting_app(cache: IsrCache) -> (Handler, Arc<AtomicUsize>) {
+    let renders = Arc::new(AtomicUsize::new(0));
+    let handler = render_app_with_isr(
+        LeptosOptions::builder().output_name("isr_test").build(),
+        cache,
+        {
+            let renders = Arc::clone(&renders);
+            move |cx| {
+                let render = renders.fetch_add(1, Ordering::SeqCst) + 1;
+                if let Some(cookies) = use_context::<CookieJar>(cx) {
+                    cookies.set(Cookie::new("visitor", render.to_string()));
+                }
+                view! { cx, <p>{format!("render {render}")}</p> }
+            }
+        },
+    );
+    (Box::new(handler), renders)
+}
+
+async fn get(handler: &Handler, uri: &str) -> (String, HeaderMap) {
+    let req = Request::get(uri).body(Body::empty()).unwrap();
+    let (parts, body) = handler(req).await.into_parts();
+    let html = hyper::body::to_bytes(body).await.unwrap();
+    (String::from_utf8(html.to_vec()).unwrap(), parts.headers)
+}
+
+/// Waits for a page to be rendered in the background.
+async fn wait_for_renders(renders: &AtomicUsize, count: usize) {
+    for _ in 0..100 {
+        if renders.load(Ordering::SeqCst) >= count {
+            // give the cache a moment to store the page
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            return;
+        }
+        tokio::time::sleep(Duration::from_millis(20)).await;
+    }
+    panic!("page was never rendered again");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn missing_pages_are_rendered_and_cached() {
+    let (handler, renders) =
+        counting_app(IsrCache::new().revalidate("/", Duration::from_secs(3600)));
+
+    let (html, _) = get(&handler, "/").await;
+    assert!(html.contains("render 1"));
+    assert_eq!(renders.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn fresh_pages_are_served_from_the_cache() {
+    let (handler, renders) =
+        counting_app(IsrCache::new().revalidate("/", Duration::from_secs(3600)));
+
+    get(&handler, "/").await;
+    let (html, _) = get(&handler, "/").await;
+    assert!(html.contains("render 1"));
+    assert_eq!(renders.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stale_pages_are_served_while_rendered_again() {
+    let (handler, renders) = counting_app(IsrCache::new().revalidate("/", Duration::ZERO));
+
+    get(&handler, "/").await;
+    let (html, _) = get(&handler, "/").await;
+    assert!(html.contains("render 1"));
+
+    wait_for_renders(&renders, 2).await;
+    let (html, _) = get(&handler, "/").await;
+    assert!(html.contains("render 2"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn invalidated_pages_are_served_once_more() {
+    let cache = IsrCache::new().revalidate("/", Duration::from_secs(3600));
+    let (handler, renders) = counting_app(cache.clone());
+
+    get(&handler, "/").await;
+    cache.invalidate("/");
+    let (html, _) = get(&handler, "/").await;
+    assert!(html.contains("render 1"));
+
+    wait_for_renders(&renders, 2).await;
+    let (html, _) = get(&handler, "/").await;
+    assert!(html.contains("render 2"));
+    assert_eq!(renders.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn purged_pages_are_rendered_on_the_next_request() {
+    let cache = IsrCache::new().revalidate("/", Duration::from_secs(3600));
+    let (handler, _) = counting_app(cache.clone());
+
+    get(&handler, "/").await;
+    cache.purge("/");
+    let (html, _) = get(&handler, "/").await;
+    assert!(html.contains("render 2"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn requests_with_a_query_bypass_the_cache() {
+    let (handler, renders) =
+        counting_app(IsrCache::new().revalidate("/", Duration::from_secs(3600)));
+
+    get(&handler, "/").await;
+    let (html, _) = get(&handler, "/?page=2").await;
+    assert!(html.contains("render 2"));
+    let (html, _) = get(&handler, "/").await;
+    assert!(html.contains("render 1"));
+    assert_eq!(renders.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn cookies_are_not_cached() {
+    let (handler, _) = counting_app(IsrCache::new().revalidate("/", Duration::from_secs(3600)));
+
+    let (_, headers) = get(&handler, "/").await;
+    assert!(!headers.contains_key(http::header::SET_COOKIE));
+    let (_, headers) = get(&handler, "/").await;
+    assert!(!headers.contains_key(http::header::SET_COOKIE));
+}
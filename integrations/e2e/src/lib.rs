@@ -0,0 +1,319 @@
+#![forbid(unsafe_code)]
+
+//! Helpers for end-to-end tests of Leptos apps in a real browser.
+//!
+//! A [TestServer] serves the app from a random local port, and a [Browser] drives a headless
+//! browser through a [WebDriver](https://www.w3.org/TR/webdriver/) server like `chromedriver`
+//! or `geckodriver`.
+//!
+//! The hard part of testing a server-rendered app is that the page appears before it works:
+//! the HTML is on screen while the WebAssembly is still loading, so a click that happens too
+//! early does nothing. The app marks the page once it has been hydrated, and keeps count of
+//! the resources that `<Suspense/>` is waiting for, so the [Browser] can wait until the page is
+//! interactive and finished loading before it acts on it or checks it.
+//!
+//! ```ignore
+//! # async fn app() -> axum::Router { unimplemented!() }
+//! use leptos_e2e::*;
+//!
+//! #[tokio::test]
+//! async fn increments_the_counter() -> Result<(), E2eError> {
+//!     let server = TestServer::axum(app().await).await?;
+//!     let browser = Browser::connect("http://localhost:4444").await?;
+//!
+//!     // waits until the app has been hydrated
+//!     browser.goto(&server.url("/")).await?;
+//!     browser.click("button").await?;
+//!     browser.assert_text("button", "Click Me: 1").await?;
+//!
+//!     browser.close().await
+//! }
+//! ```
+
+use fantoccini::{elements::Element, Client, ClientBuilder, Locator};
+use std::{
+    net::{SocketAddr, TcpListener},
+    time::{Duration, Instant},
+};
+use thiserror::Error;
+
+/// The property of `window` that is set once the app has been hydrated or mounted.
+const HYDRATED: &str = "__LEPTOS_HYDRATED";
+/// The property of `window` that counts the resources `<Suspense/>` is waiting for.
+const PENDING_SUSPENSE: &str = "__LEPTOS_PENDING_SUSPENSE";
+/// How often to check the page while waiting for it.
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// An error that occurred in an end-to-end test.
+#[derive(Error, Debug)]
+pub enum E2eError {
+    /// The test server couldn't be started.
+    #[error("couldn't start the test server: {0}")]
+    Server(#[from] std::io::Error),
+    /// No browser session could be started with the WebDriver server.
+    #[error("couldn't connect to the WebDriver server: {0}")]
+    Session(#[from] fantoccini::error::NewSessionError),
+    /// A WebDriver command failed, e.g., because an element wasn't found.
+    #[error("WebDriver command failed: {0}")]
+    Command(#[from] fantoccini::error::CmdError),
+    /// The page didn't reach the expected state in time.
+    #[error("timed out after {0:?} waiting for {1}")]
+    Timeout(Duration, String),
+    /// An element's text wasn't the expected one.
+    #[error("expected the text of `{selector}` to be {expected:?}, but it was {actual:?}")]
+    TextMismatch {
+        /// The CSS selector of the element.
+        selector: String,
+        /// The text that was expected.
+        expected: String,
+        /// The text that the element had when the test gave up.
+        actual: String,
+    },
+}
+
+/// Serves an app on a random port of `127.0.0.1` for the duration of a test. The server is shut
+/// down when this is dropped.
+#[derive(Debug)]
+pub struct TestServer {
+    addr: SocketAddr,
+    shutdown: Shutdown,
+}
+
+#[derive(Debug)]
+enum Shutdown {
+    #[cfg(feature = "axum")]
+    Axum(Option<tokio::sync::oneshot::Sender<()>>),
+    #[cfg(feature = "actix")]
+    Actix(actix_web::dev::ServerHandle),
+    #[allow(dead_code)]
+    None,
+}
+
+impl TestServer {
+    /// Serves an Axum router, e.g., one with the routes from `leptos_axum`, in the background.
+    #[cfg(feature = "axum")]
+    pub async fn axum(app: axum::Router) -> Result<Self, E2eError> {
+        let listener = listen()?;
+        let addr = listener.local_addr()?;
+        let (tx, rx) = tokio::sync::oneshot::channel::<()>();
+        let server = axum::Server::from_tcp(listener)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::Other, e))?
+            .serve(app.into_make_service())
+            .with_graceful_shutdown(async {
+                _ = rx.await;
+            });
+        tokio::spawn(server);
+        Ok(Self {
+            addr,
+            shutdown: Shutdown::Axum(Some(tx)),
+        })
+    }
+
+    /// Serves an Actix app, given the same factory as [HttpServer::new](actix_web::HttpServer::new),
+    /// in the background. Tests that use this should run with `#[actix_web::test]`.
+    #[cfg(feature = "actix")]
+    pub fn actix<F, I, S, B>(factory: F) -> Result<Self, E2eError>
+    where
+        F: Fn() -> I + Send + Clone + 'static,
+        I: actix_web::dev::IntoServiceFactory<S, actix_web::dev::Request>,
+        S: actix_web::dev::ServiceFactory<
+                actix_web::dev::Request,
+                Config = actix_web::dev::AppConfig,
+            > + 'static,
+        S::Error: Into<actix_web::Error> + 'static,
+        S::InitError: std::fmt::Debug,
+        S::Response: Into<actix_web::HttpResponse<B>> + 'static,
+        B: actix_web::body::MessageBody + 'static,
+    {
+        let listener = listen()?;
+        let addr = listener.local_addr()?;
+        let server = actix_web::HttpServer::new(factory)
+            .workers(1)
+            .listen(listener)?
+            .run();
+        let handle = server.handle();
+        actix_web::rt::spawn(server);
+        Ok(Self {
+            addr,
+            shutdown: Shutdown::Actix(handle),
+        })
+    }
+
+    /// The address the app is served on.
+    pub fn addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// The URL of the given path on the test server, e.g. `server.url("/todos")`.
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}/{}", self.addr, path.trim_start_matches('/'))
+    }
+}
+
+impl Drop for TestServer {
+    fn drop(&mut self) {
+        match &mut self.shutdown {
+            #[cfg(feature = "axum")]
+            Shutdown::Axum(tx) => {
+                if let Some(tx) = tx.take() {
+                    _ = tx.send(());
+                }
+            }
+            #[cfg(feature = "actix")]
+            Shutdown::Actix(handle) => {
+                _ = handle.stop(false);
+            }
+            Shutdown::None => {}
+        }
+    }
+}
+
+#[allow(dead_code)]
+fn listen() -> std::io::Result<TcpListener> {
+    let listener = TcpListener::bind("127.0.0.1:0")?;
+    listener.set_nonblocking(true)?;
+    Ok(listener)
+}
+
+/// A headless browser session, driven through a WebDriver server.
+///
+/// Every method that looks at the page first waits until the app has been hydrated and no
+/// `<Suspense/>` is waiting for a resource, so tests don't race the WebAssembly bootstrap or
+/// data loading.
+#[derive(Clone, Debug)]
+pub struct Browser {
+    client: Client,
+    timeout: Duration,
+}
+
+impl Browser {
+    /// Starts a headless Chrome or Firefox session with the WebDriver server at the given URL,
+    /// e.g., `http://localhost:9515` for `chromedriver` or `http://localhost:4444` for
+    /// `geckodriver`.
+    pub async fn connect(webdriver_url: &str) -> Result<Self, E2eError> {
+        let mut capabilities = serde_json::Map::new();
+        capabilities.insert(
+            "goog:chromeOptions".to_string(),
+            serde_json::json!({ "args": ["--headless", "--disable-gpu"] }),
+        );
+        capabilities.insert(
+            "moz:firefoxOptions".to_string(),
+            serde_json::json!({ "args": ["-headless"] }),
+        );
+        let client = ClientBuilder::native()
+            .capabilities(capabilities)
+            .connect(webdriver_url)
+            .await?;
+        Ok(Self::from_client(client))
+    }
+
+    /// Wraps a session that was started with custom capabilities.
+    pub fn from_client(client: Client) -> Self {
+        Self {
+            client,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Sets how long to wait for hydration, `<Suspense/>`, or the expected text before giving
+    /// up. Defaults to 10 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The underlying WebDriver client, for anything the helpers don't cover.
+    pub fn client(&self) -> &Client {
+        &self.client
+    }
+
+    /// Loads the URL and waits until the app has been hydrated and has finished loading.
+    pub async fn goto(&self, url: &str) -> Result<(), E2eError> {
+        self.client.goto(url).await?;
+        self.wait_until_ready().await
+    }
+
+    /// Waits until the app has been hydrated, or mounted if it is rendered in the browser, so
+    /// that its event listeners are attached.
+    pub async fn wait_for_hydration(&self) -> Result<(), E2eError> {
+        self.wait_for(
+            "the app to be hydrated",
+            &format!("return window.{HYDRATED} === true;"),
+        )
+        .await
+    }
+
+    /// Waits until no `<Suspense/>` on the page is waiting for a resource.
+    pub async fn wait_for_suspense(&self) -> Result<(), E2eError> {
+        self.wait_for(
+            "pending <Suspense/> resources to load",
+            &format!("return !window.{PENDING_SUSPENSE};"),
+        )
+        .await
+    }
+
+    /// Waits until the app has been hydrated and no `<Suspense/>` is waiting for a resource.
+    pub async fn wait_until_ready(&self) -> Result<(), E2eError> {
+        self.wait_for_hydration().await?;
+        self.wait_for_suspense().await
+    }
+
+    /// Finds the first element that matches the CSS selector, once the page is ready.
+    pub async fn find(&self, selector: &str) -> Result<Element, E2eError> {
+        self.wait_until_ready().await?;
+        Ok(self.client.find(Locator::Css(selector)).await?)
+    }
+
+    /// Returns the text of the first element that matches the CSS selector, once the page is
+    /// ready.
+    pub async fn text(&self, selector: &str) -> Result<String, E2eError> {
+        Ok(self.find(selector).await?.text().await?)
+    }
+
+    /// Clicks the first element that matches the CSS selector, once the page is ready.
+    pub async fn click(&self, selector: &str) -> Result<(), E2eError> {
+        self.find(selector).await?.click().await?;
+        Ok(())
+    }
+
+    /// Checks that the text of the first element that matches the CSS selector is `expected`.
+    ///
+    /// Updates to the DOM may take a moment after an event, so this checks again until the
+    /// timeout before it fails with [E2eError::TextMismatch].
+    pub async fn assert_text(&self, selector: &str, expected: &str) -> Result<(), E2eError> {
+        let start = Instant::now();
+        loop {
+            let actual = self.text(selector).await?;
+            if actual == expected {
+                return Ok(());
+            }
+            if start.elapsed() > self.timeout {
+                return Err(E2eError::TextMismatch {
+                    selector: selector.to_string(),
+                    expected: expected.to_string(),
+                    actual,
+                });
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+
+    /// Ends the browser session.
+    pub async fn close(self) -> Result<(), E2eError> {
+        Ok(self.client.close().await?)
+    }
+
+    async fn wait_for(&self, what: &str, script: &str) -> Result<(), E2eError> {
+        let start = Instant::now();
+        loop {
+            let done = self.client.execute(script, vec![]).await?;
+            if done.as_bool() == Some(true) {
+                return Ok(());
+            }
+            if start.elapsed() > self.timeout {
+                return Err(E2eError::Timeout(self.timeout, what.to_string()));
+            }
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    }
+}
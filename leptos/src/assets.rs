@@ -0,0 +1,25 @@
+use leptos_config::AssetManifest;
+use leptos_reactive::{use_context, Scope};
+
+/// Resolves the path of a static asset to its fingerprinted filename, using the
+/// [AssetManifest] provided in context.
+///
+/// The server integrations provide the manifest from
+/// [LeptosOptions](leptos_config::LeptosOptions) for every request. In the browser, provide
+/// the same manifest with [provide_context](leptos_reactive::provide_context) at the root of
+/// your app so that hydrated attributes match the server-rendered HTML. If no manifest has
+/// been provided, the path is returned unchanged.
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// provide_context(cx, AssetManifest::from_iter([("img/logo.png", "img/logo.51bc2e.png")]));
+/// assert_eq!(use_asset_path(cx, "/img/logo.png"), "/img/logo.51bc2e.png");
+/// assert_eq!(use_asset_path(cx, "/img/other.png"), "/img/other.png");
+/// # });
+/// ```
+pub fn use_asset_path(cx: Scope, path: &str) -> String {
+    match use_context::<AssetManifest>(cx) {
+        Some(manifest) => manifest.resolve(path),
+        None => path.to_string(),
+    }
+}
@@ -0,0 +1,134 @@
+use leptos_dom::{Errors, Fragment, IntoView, View};
+use leptos_macro::component;
+use leptos_reactive::{
+    create_rw_signal, provide_context, use_context, RefetchContext, RwSignal, Scope,
+};
+use std::rc::Rc;
+
+/// When an `Err(_)` is rendered anywhere in its `children`, shows the `fallback` instead,
+/// until the error goes away again.
+///
+/// The `fallback` is called with the [Errors] that were caught. Errors rendered inside a
+/// [`Suspense`](crate::Suspense) are shown in place of that `Suspense` instead of the whole
+/// boundary, so that a region streamed from the server and the same region after hydration
+/// show the fallback in the same place.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use leptos_macro::*;
+/// # use leptos_dom::*; use leptos::*;
+/// # if false {
+/// # run_scope(create_runtime(), |cx| {
+/// async fn fetch_post(id: u32) -> Result<String, String> {
+///     Err(format!("post {id} not found"))
+/// }
+///
+/// let post = create_resource(cx, || 1, fetch_post);
+///
+/// view! { cx,
+///   <ErrorBoundary fallback=|cx: Scope, errors: RwSignal<Errors>| view! { cx,
+///     <ul>
+///       {move || errors.with(|errors| errors.iter()
+///         .map(|e| view! { cx, <li>{e.to_string()}</li> })
+///         .collect::<Vec<_>>())}
+///     </ul>
+///   }>
+///     <Suspense fallback=|| "Loading...">
+///       {move || post.read().map(|post| post.map(|post| view! { cx, <p>{post}</p> }))}
+///     </Suspense>
+///   </ErrorBoundary>
+/// };
+/// # });
+/// # }
+/// ```
+///
+/// Resources created within the `children` are refetched when the boundary is
+/// [reset](ErrorBoundaryContext::reset), so a "Try again" button in the `fallback` retries the
+/// loads that failed, rather than showing the same errors again:
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Retry(cx: Scope) -> impl IntoView {
+///   let boundary = use_error_boundary(cx).expect("<Retry/> is used in an <ErrorBoundary/>");
+///   view! { cx,
+///     <button on:click=move |_| boundary.reset()>"Try again"</button>
+///   }
+/// }
+/// ```
+#[component(transparent)]
+pub fn ErrorBoundary<F, IV>(
+    cx: Scope,
+    /// Returns the UI that is shown instead of the `children` when they render an error.
+    fallback: F,
+    /// The UI that is shown while there are no errors.
+    children: Box<dyn Fn(Scope) -> Fragment>,
+) -> impl IntoView
+where
+    F: Fn(Scope, RwSignal<Errors>) -> IV + 'static,
+    IV: IntoView,
+{
+    let errors = create_rw_signal(cx, Errors::default());
+    provide_context(cx, errors);
+
+    let resources = RefetchContext::new(cx);
+    provide_context(cx, resources);
+    provide_context(cx, ErrorBoundaryContext { errors, resources });
+
+    let fallback = BoundaryFallback(Rc::new(move |cx, errors| {
+        fallback(cx, errors).into_view(cx)
+    }));
+    provide_context(cx, fallback.clone());
+
+    // rendered only once, so that errors they report aren't removed and added again
+    let children = children(cx).into_view(cx);
+
+    move || {
+        if errors.with(Errors::is_empty) {
+            children.clone()
+        } else {
+            (fallback.0)(cx, errors)
+        }
+    }
+}
+
+/// The `fallback` of the nearest [ErrorBoundary], which a [`Suspense`](crate::Suspense)
+/// renders in its own place when its children render an error.
+#[derive(Clone)]
+#[allow(clippy::type_complexity)]
+pub(crate) struct BoundaryFallback(pub(crate) Rc<dyn Fn(Scope, RwSignal<Errors>) -> View>);
+
+/// The nearest [ErrorBoundary], which can be used to retry after an error, e.g., in its
+/// `fallback`. Use [use_error_boundary] to get it.
+#[derive(Clone, Copy, Debug)]
+pub struct ErrorBoundaryContext {
+    errors: RwSignal<Errors>,
+    resources: RefetchContext,
+}
+
+impl ErrorBoundaryContext {
+    /// The errors caught by the boundary.
+    pub fn errors(&self) -> RwSignal<Errors> {
+        self.errors
+    }
+
+    /// Refetches the [Resources](leptos_reactive::Resource) created within the boundary's
+    /// `children`, and clears its errors, so that the `children` are shown again. Errors that
+    /// are rendered again, e.g., when a refetched resource fails again, show the `fallback`
+    /// again.
+    pub fn reset(&self) {
+        // refetched first, so that a `Suspense` shows its fallback instead of the old error
+        self.resources.refetch();
+        self.errors.set(Errors::default());
+    }
+
+    /// The same boundary, for a [`Suspense`](crate::Suspense) that collects the errors of its
+    /// own children.
+    pub(crate) fn with_errors(self, errors: RwSignal<Errors>) -> Self {
+        Self { errors, ..self }
+    }
+}
+
+/// Returns the nearest [ErrorBoundary] above this scope, if there is one.
+pub fn use_error_boundary(cx: Scope) -> Option<ErrorBoundaryContext> {
+    use_context(cx)
+}
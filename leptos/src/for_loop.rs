@@ -1,6 +1,6 @@
 use leptos_dom::IntoView;
 use leptos_macro::component;
-use leptos_reactive::Scope;
+use leptos_reactive::{DiffedSignal, ListPatch, ReadSignal, Scope};
 use std::hash::Hash;
 
 /// Iterates over children and displays them, keyed by the `key` function given.
@@ -8,6 +8,10 @@ use std::hash::Hash;
 /// This is much more efficient than naively iterating over nodes with `.iter().map(|n| view! { cx,  ... })...`,
 /// as it avoids re-creating DOM nodes that are not being changed.
 ///
+/// The `key` is usually a field of each item, which can be written as [`key!(id)`](crate::key!)
+/// instead of `|item| item.id.clone()`. If the view also needs each item's position, use
+/// [`<ForEnumerate/>`](ForEnumerate) instead.
+///
 /// ```
 /// # use leptos::*;
 ///
@@ -62,3 +66,152 @@ where
 {
     leptos_dom::Each::new(each, key, view).into_view(cx)
 }
+
+/// Like [`<For/>`](For), but also passes the view a signal with each item's index.
+///
+/// When items are added, removed, or reordered, the index signals of the items that moved
+/// are updated, but their views are not created again.
+///
+/// ```
+/// # use leptos::*;
+///
+/// #[derive(Clone, Debug, PartialEq, Eq)]
+/// struct Player {
+///   name: String,
+///   score: u32
+/// }
+///
+/// #[component]
+/// fn Leaderboard(cx: Scope, players: ReadSignal<Vec<Player>>) -> impl IntoView {
+///   view! {
+///     cx,
+///     <ol>
+///       <ForEnumerate
+///         each=players
+///         key=key!(name)
+///         // the index is a signal, so only the text node showing it updates
+///         view=move |index: ReadSignal<usize>, player: Player| {
+///           view! {
+///             cx,
+///             <li>{move || index.get() + 1} ". " {player.name} ": " {player.score}</li>
+///           }
+///         }
+///       />
+///     </ol>
+///   }
+/// }
+/// ```
+#[component(transparent)]
+pub fn ForEnumerate<IF, I, T, EF, N, KF, K>(
+    cx: Scope,
+    /// Items over which the component should iterate.
+    each: IF,
+    /// A key function that will be applied to each item.
+    key: KF,
+    /// The view that will be displayed for each item, given its index and the item.
+    view: EF,
+) -> impl IntoView
+where
+    IF: Fn() -> I + 'static,
+    I: IntoIterator<Item = T>,
+    EF: Fn(ReadSignal<usize>, T) -> N + 'static,
+    N: IntoView,
+    KF: Fn(&T) -> K + 'static,
+    K: Eq + Hash + 'static,
+    T: 'static,
+{
+    leptos_dom::EachEnumerated::new(each, key, view).into_view(cx)
+}
+
+/// Like [`<For/>`](For), but for a list held in a [DiffedSignal], whose changes are applied to
+/// the DOM patch by patch instead of being diffed again.
+///
+/// `<For/>` compares the keys of every item each time its list changes. That is wasted work
+/// for a long list that changes a few items at a time, e.g., a table with thousands of rows
+/// that is kept up to date by deltas from a websocket. Here, the [ListPatch]es computed by the
+/// [DiffedSignal] are applied directly: an inserted row is rendered, a removed row is removed,
+/// and a moved row is moved, without touching the rest. An updated item's view is created again.
+///
+/// The items are keyed by the `key` function given to [create_diffed_signal](leptos_reactive::create_diffed_signal).
+///
+/// ```
+/// # use leptos::*;
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct Row {
+///   id: u64,
+///   price: f64,
+/// }
+///
+/// #[component]
+/// fn Prices(cx: Scope) -> impl IntoView {
+///   let rows = create_diffed_signal(cx, Vec::<Row>::new(), key!(id));
+///
+///   // e.g., in a websocket message handler
+///   let on_delta = move |changed: Row| {
+///     rows.update(|rows| match rows.iter_mut().find(|row| row.id == changed.id) {
+///       Some(row) => *row = changed,
+///       None => rows.push(changed),
+///     })
+///   };
+///
+///   view! {
+///     cx,
+///     <table>
+///       <ForPatched
+///         each=rows
+///         view=move |row: Row| view! { cx, <tr><td>{row.id}</td><td>{row.price}</td></tr> }
+///       />
+///     </table>
+///   }
+/// }
+/// ```
+#[component(transparent)]
+pub fn ForPatched<T, EF, N>(
+    cx: Scope,
+    /// The list over which the component should iterate.
+    each: DiffedSignal<Vec<T>, ListPatch<T>>,
+    /// The view that will be displayed for each item.
+    view: EF,
+) -> impl IntoView
+where
+    EF: Fn(T) -> N + 'static,
+    N: IntoView,
+    T: Clone + 'static,
+{
+    leptos_dom::EachPatched::new(each, view).into_view(cx)
+}
+
+/// Creates a `key` function for [`<For/>`](For) or [`<ForEnumerate/>`](ForEnumerate)
+/// that returns a field of each item, cloning it.
+///
+/// - `key!(id)` is the same as `|item| item.id.clone()`; for [Copy] fields like numbers,
+///   this is just a copy.
+/// - Nested fields and tuple fields can be given as a path: `key!(author.id)`, `key!(0)`.
+/// - `key!()` uses a clone of the item itself, for lists of keys like numbers or strings.
+///
+/// ```
+/// # use leptos::*;
+/// struct Todo {
+///   id: usize,
+///   owner: (String, u32),
+/// }
+///
+/// // <For/> calls the key function in the same way
+/// fn key_of<T, K>(item: &T, key: impl Fn(&T) -> K) -> K {
+///   key(item)
+/// }
+///
+/// let todo = Todo { id: 3, owner: ("Ada".to_string(), 7) };
+/// assert_eq!(key_of(&todo, key!(id)), 3);
+/// assert_eq!(key_of(&todo, key!(owner.0)), "Ada");
+/// assert_eq!(key_of(&42, key!()), 42);
+/// ```
+#[macro_export]
+macro_rules! key {
+    () => {
+        |item: &_| ::std::clone::Clone::clone(item)
+    };
+    ($($field:tt).+) => {
+        |item: &_| ::std::clone::Clone::clone(&item.$($field).+)
+    };
+}
@@ -77,6 +77,9 @@
 //!   from the server to the client.
 //! - `miniserde` In SSR/hydrate mode, uses [miniserde](https://docs.rs/miniserde/latest/miniserde/) to serialize resources and send them
 //!   from the server to the client.
+//! - `legacy-each-diff` Uses the original algorithm to work out which items of a keyed list
+//!   (like [For]) to move in the DOM, rather than the one based on the longest increasing
+//!   subsequence, to compare the two.
 //!
 //! **Important Note:** You must enable one of `csr`, `hydrate`, or `ssr` to tell Leptos
 //! which mode your app is operating in.
@@ -138,8 +141,16 @@ pub use leptos_server::*;
 pub use tracing;
 pub use typed_builder;
 
+mod assets;
+pub use assets::*;
+mod error_boundary;
+pub use error_boundary::*;
 mod for_loop;
 pub use for_loop::*;
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "markdown")]
+pub use markdown::*;
 mod suspense;
 pub use suspense::*;
 mod transition;
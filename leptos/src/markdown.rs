@@ -0,0 +1,317 @@
+use leptos_dom::{Fragment, IntoView, View};
+use leptos_macro::{component, view};
+use leptos_reactive::{MaybeSignal, Scope};
+use pulldown_cmark::{Alignment, CodeBlockKind, Event, Options, Parser, Tag};
+use std::{fmt, rc::Rc};
+
+/// Renders markdown as a view, e.g., for the pages of a blog or a documentation site.
+///
+/// The markdown is turned into elements and text, rather than into an HTML string that is set
+/// as the `inner_html` of an element, so it renders the same way on the server and in the
+/// browser, and is hydrated like any other view. It's also safe to render markdown written by
+/// users: text is always escaped, raw HTML in the markdown is left out, and links and images
+/// whose URLs would run scripts (like `javascript:` URLs) are left out too.
+///
+/// Tables, footnotes, strikethrough, and task lists are supported, in addition to
+/// [CommonMark](https://commonmark.org/). Some kinds of markdown can be rendered with your own
+/// components instead, using [MarkdownRenderers].
+///
+/// This component is only available with the `markdown` feature.
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Post(cx: Scope, body: ReadSignal<String>) -> impl IntoView {
+///   let renderers = MarkdownRenderers::default().code_block(|cx, language, code| {
+///     view! { cx, <pre class="highlighted" data-language=language>{code}</pre> }
+///   });
+///
+///   view! { cx,
+///     <article>
+///       <Markdown source=body renderers=renderers/>
+///     </article>
+///   }
+/// }
+/// ```
+#[component]
+pub fn Markdown(
+    cx: Scope,
+    /// The markdown to render.
+    #[prop(into)]
+    source: MaybeSignal<String>,
+    /// Renders some kinds of markdown with your own components.
+    #[prop(optional)]
+    renderers: MarkdownRenderers,
+) -> impl IntoView {
+    move || source.with(|source| render_markdown(cx, source, &renderers))
+}
+
+/// Renders some kinds of markdown with your own components in a [Markdown] component, instead of
+/// as the usual HTML elements.
+///
+/// Each renderer is given the scope and what is needed to render that kind of markdown. Links
+/// and images are only given to their renderers if their URLs are safe.
+#[derive(Clone, Default)]
+#[allow(clippy::type_complexity)]
+pub struct MarkdownRenderers {
+    code_block: Option<Rc<dyn Fn(Scope, Option<String>, String) -> View>>,
+    heading: Option<Rc<dyn Fn(Scope, u8, Option<String>, Fragment) -> View>>,
+    link: Option<Rc<dyn Fn(Scope, String, Option<String>, Fragment) -> View>>,
+    image: Option<Rc<dyn Fn(Scope, String, String, Option<String>) -> View>>,
+}
+
+impl MarkdownRenderers {
+    /// Renders code blocks, given the block's language, if it has one, and its code. This is
+    /// where syntax highlighting can be added.
+    pub fn code_block<IV: IntoView>(
+        mut self,
+        render: impl Fn(Scope, Option<String>, String) -> IV + 'static,
+    ) -> Self {
+        self.code_block = Some(Rc::new(move |cx, language, code| {
+            render(cx, language, code).into_view(cx)
+        }));
+        self
+    }
+
+    /// Renders headings, given their level from `1` to `6`, their `id` if one was given with
+    /// `# Heading {#id}`, and their contents.
+    pub fn heading<IV: IntoView>(
+        mut self,
+        render: impl Fn(Scope, u8, Option<String>, Fragment) -> IV + 'static,
+    ) -> Self {
+        self.heading = Some(Rc::new(move |cx, level, id, children| {
+            render(cx, level, id, children).into_view(cx)
+        }));
+        self
+    }
+
+    /// Renders links, given their URL, their title if they have one, and their contents, e.g.,
+    /// to render links within the app with the router's `<A/>` component.
+    pub fn link<IV: IntoView>(
+        mut self,
+        render: impl Fn(Scope, String, Option<String>, Fragment) -> IV + 'static,
+    ) -> Self {
+        self.link = Some(Rc::new(move |cx, href, title, children| {
+            render(cx, href, title, children).into_view(cx)
+        }));
+        self
+    }
+
+    /// Renders images, given their URL, their alt text, and their title if they have one.
+    pub fn image<IV: IntoView>(
+        mut self,
+        render: impl Fn(Scope, String, String, Option<String>) -> IV + 'static,
+    ) -> Self {
+        self.image = Some(Rc::new(move |cx, src, alt, title| {
+            render(cx, src, alt, title).into_view(cx)
+        }));
+        self
+    }
+}
+
+impl fmt::Debug for MarkdownRenderers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MarkdownRenderers")
+            .field("code_block", &self.code_block.is_some())
+            .field("heading", &self.heading.is_some())
+            .field("link", &self.link.is_some())
+            .field("image", &self.image.is_some())
+            .finish()
+    }
+}
+
+/// The contents of an element that hasn't ended yet.
+#[derive(Default)]
+struct Frame {
+    children: Vec<View>,
+    /// The text in the element, for elements that are rendered from their text, like code blocks.
+    text: String,
+}
+
+#[derive(Default)]
+struct Table {
+    alignments: Vec<Alignment>,
+    in_head: bool,
+    column: usize,
+}
+
+fn render_markdown(cx: Scope, source: &str, renderers: &MarkdownRenderers) -> View {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_TASKLISTS
+        | Options::ENABLE_HEADING_ATTRIBUTES;
+
+    let mut frames = vec![Frame::default()];
+    let mut table = Table::default();
+
+    for event in Parser::new_ext(source, options) {
+        let view = match event {
+            Event::Start(tag) => {
+                match tag {
+                    Tag::Table(alignments) => table.alignments = alignments,
+                    Tag::TableHead => {
+                        table.in_head = true;
+                        table.column = 0;
+                    }
+                    Tag::TableRow => table.column = 0,
+                    _ => {}
+                }
+                frames.push(Frame::default());
+                continue;
+            }
+            Event::End(tag) => {
+                let frame = frames.pop().unwrap_or_default();
+                match render_tag(cx, tag, frame, renderers, &mut table) {
+                    Some(view) => view,
+                    None => continue,
+                }
+            }
+            Event::Text(text) => {
+                if let Some(frame) = frames.last_mut() {
+                    frame.text.push_str(&text);
+                }
+                text.to_string().into_view(cx)
+            }
+            Event::Code(code) => view! { cx, <code>{code.to_string()}</code> }.into_view(cx),
+            // raw HTML could run scripts
+            Event::Html(_) => continue,
+            Event::FootnoteReference(name) => view! { cx,
+                <sup class="footnote-reference">
+                    <a href=format!("#{name}")>{name.to_string()}</a>
+                </sup>
+            }
+            .into_view(cx),
+            Event::SoftBreak => "\n".into_view(cx),
+            Event::HardBreak => view! { cx, <br/> }.into_view(cx),
+            Event::Rule => view! { cx, <hr/> }.into_view(cx),
+            Event::TaskListMarker(checked) => {
+                view! { cx, <input type="checkbox" disabled=true checked=checked/> }.into_view(cx)
+            }
+        };
+        if let Some(frame) = frames.last_mut() {
+            frame.children.push(view);
+        }
+    }
+
+    Fragment::new(frames.pop().unwrap_or_default().children).into_view(cx)
+}
+
+fn render_tag(
+    cx: Scope,
+    tag: Tag,
+    frame: Frame,
+    renderers: &MarkdownRenderers,
+    table: &mut Table,
+) -> Option<View> {
+    let Frame { children, text } = frame;
+    let view = match tag {
+        Tag::Paragraph => view! { cx, <p>{children}</p> }.into_view(cx),
+        Tag::Heading(level, id, classes) => {
+            let level = level as u8;
+            let id = id.map(String::from);
+            if let Some(heading) = &renderers.heading {
+                return Some(heading(cx, level, id, Fragment::new(children)));
+            }
+            let class = (!classes.is_empty()).then(|| classes.join(" "));
+            match level {
+                1 => view! { cx, <h1 id=id class=class>{children}</h1> }.into_view(cx),
+                2 => view! { cx, <h2 id=id class=class>{children}</h2> }.into_view(cx),
+                3 => view! { cx, <h3 id=id class=class>{children}</h3> }.into_view(cx),
+                4 => view! { cx, <h4 id=id class=class>{children}</h4> }.into_view(cx),
+                5 => view! { cx, <h5 id=id class=class>{children}</h5> }.into_view(cx),
+                _ => view! { cx, <h6 id=id class=class>{children}</h6> }.into_view(cx),
+            }
+        }
+        Tag::BlockQuote => view! { cx, <blockquote>{children}</blockquote> }.into_view(cx),
+        Tag::CodeBlock(kind) => {
+            let language = match kind {
+                CodeBlockKind::Fenced(info) => info.split_whitespace().next().map(String::from),
+                CodeBlockKind::Indented => None,
+            };
+            if let Some(code_block) = &renderers.code_block {
+                return Some(code_block(cx, language, text));
+            }
+            let class = language.map(|language| format!("language-{language}"));
+            view! { cx, <pre><code class=class>{text}</code></pre> }.into_view(cx)
+        }
+        Tag::List(Some(start)) => {
+            let start = (start != 1).then(|| start.to_string());
+            view! { cx, <ol start=start>{children}</ol> }.into_view(cx)
+        }
+        Tag::List(None) => view! { cx, <ul>{children}</ul> }.into_view(cx),
+        Tag::Item => view! { cx, <li>{children}</li> }.into_view(cx),
+        Tag::FootnoteDefinition(name) => view! { cx,
+            <div class="footnote-definition" id=name.to_string()>
+                <sup class="footnote-definition-label">{name.to_string()}</sup>
+                {children}
+            </div>
+        }
+        .into_view(cx),
+        Tag::Table(_) => {
+            // the rows are put in a `<tbody>`, as the browser would do when parsing the
+            // server-rendered HTML, so that hydration finds the same elements
+            let mut head = children;
+            let rows = head.split_off(head.len().min(1));
+            view! { cx, <table>{head}<tbody>{rows}</tbody></table> }.into_view(cx)
+        }
+        Tag::TableHead => {
+            table.in_head = false;
+            view! { cx, <thead><tr>{children}</tr></thead> }.into_view(cx)
+        }
+        Tag::TableRow => view! { cx, <tr>{children}</tr> }.into_view(cx),
+        Tag::TableCell => {
+            let style = match table.alignments.get(table.column) {
+                Some(Alignment::Left) => Some("text-align: left"),
+                Some(Alignment::Center) => Some("text-align: center"),
+                Some(Alignment::Right) => Some("text-align: right"),
+                Some(Alignment::None) | None => None,
+            }
+            .map(String::from);
+            table.column += 1;
+            if table.in_head {
+                view! { cx, <th style=style>{children}</th> }.into_view(cx)
+            } else {
+                view! { cx, <td style=style>{children}</td> }.into_view(cx)
+            }
+        }
+        Tag::Emphasis => view! { cx, <em>{children}</em> }.into_view(cx),
+        Tag::Strong => view! { cx, <strong>{children}</strong> }.into_view(cx),
+        Tag::Strikethrough => view! { cx, <del>{children}</del> }.into_view(cx),
+        Tag::Link(_, href, title) => {
+            if !is_safe_url(&href) {
+                return Some(Fragment::new(children).into_view(cx));
+            }
+            let href = href.to_string();
+            let title = (!title.is_empty()).then(|| title.to_string());
+            match &renderers.link {
+                Some(link) => link(cx, href, title, Fragment::new(children)),
+                None => view! { cx, <a href=href title=title>{children}</a> }.into_view(cx),
+            }
+        }
+        Tag::Image(_, src, title) => {
+            if !is_safe_url(&src) {
+                return None;
+            }
+            let src = src.to_string();
+            let title = (!title.is_empty()).then(|| title.to_string());
+            match &renderers.image {
+                Some(image) => image(cx, src, text, title),
+                None => view! { cx, <img src=src alt=text title=title/> }.into_view(cx),
+            }
+        }
+    };
+    Some(view)
+}
+
+/// Whether a link or image URL is relative, or uses a scheme that can't run scripts.
+fn is_safe_url(url: &str) -> bool {
+    // a scheme comes before the first `:`, unless that's after the start of a path, query, or
+    // fragment
+    match url.split_once(':') {
+        Some((scheme, _)) if !scheme.contains(['/', '?', '#']) => matches!(
+            scheme.trim().to_ascii_lowercase().as_str(),
+            "http" | "https" | "mailto" | "tel"
+        ),
+        _ => true,
+    }
+}
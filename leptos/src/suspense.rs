@@ -1,10 +1,17 @@
 use cfg_if::cfg_if;
 use leptos_macro::component;
-use std::rc::Rc;
-use leptos_dom::{DynChild, Fragment, IntoView, Component};
-use leptos_reactive::{provide_context, Scope, SuspenseContext};
+use std::{cell::Cell, rc::Rc};
+use leptos_dom::{
+    Component, DynChild, Errors, Fragment, HydrationCtx, HydrationKey, IntoView, View,
+};
+use leptos_reactive::{
+    create_rw_signal, provide_context, use_context, Scope, SuspenseContext,
+};
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+use leptos_reactive::{create_effect, queue_microtask};
 #[cfg(not(any(feature = "csr", feature = "hydrate")))]
-use leptos_dom::{HydrationCtx, HydrationKey};
+use leptos_dom::AwaitSuspense;
+use crate::error_boundary::{use_error_boundary, BoundaryFallback};
 
 /// If any [Resources](leptos_reactive::Resource) are read in the `children` of this
 /// component, it will show the `fallback` while they are loading. Once all are resolved,
@@ -14,6 +21,16 @@ use leptos_dom::{HydrationCtx, HydrationKey};
 /// those resources are read under the suspense), so you cannot assume that resources have
 /// `Some` value in `children`.
 ///
+/// If the `children` render an error inside an [`ErrorBoundary`](crate::ErrorBoundary),
+/// the boundary's fallback is shown in place of this component, both in the HTML streamed
+/// from the server and after hydration, and whenever the `children` render an error later on.
+/// So is the fallback for a resource whose value the server couldn't serialize, or the browser
+/// couldn't deserialize.
+///
+/// In the browser, the element that contains this component is marked with `aria-busy="true"`
+/// while resources are loading, and `focus_on_load` can move focus into the `children` once they
+/// have loaded, so that the swap is announced to assistive technology.
+///
 /// ```
 /// # use leptos_reactive::*;
 /// # use leptos_macro::*;
@@ -59,6 +76,15 @@ pub fn Suspense<F, E>(
     fallback: F,
     /// Children will be displayed once all `async` [Resources](leptos_reactive::Resource) have resolved.
     children: Box<dyn Fn(Scope) -> Fragment>,
+    /// Whether to set `aria-busy="true"` on the element that contains this component while
+    /// resources are loading, so that screen readers wait for the content. Defaults to `true`.
+    #[prop(optional)]
+    aria_busy: Option<bool>,
+    /// A CSS selector, like `"h1, h2"`, for the element in the `children` that should receive
+    /// focus when they replace the `fallback`, so that keyboard and screen reader users are
+    /// taken to the content that just loaded.
+    #[prop(optional, into)]
+    focus_on_load: Option<String>,
 ) -> impl IntoView
 where
     F: Fn() -> E + 'static,
@@ -71,9 +97,82 @@ where
     // provide this SuspenseContext to any resources below it
     provide_context(cx, context);
 
+    // errors rendered by the children are collected here rather than by the ErrorBoundary,
+    // so that its fallback can be shown in this component's place: a streamed fragment can
+    // only replace this component, and hydration has to render the same thing
+    let boundary = use_context::<BoundaryFallback>(cx).map(|fallback| {
+        let errors = create_rw_signal(cx, Errors::default());
+        provide_context(cx, errors);
+        if let Some(boundary) = use_error_boundary(cx) {
+            provide_context(cx, boundary.with_errors(errors));
+        }
+        (fallback, errors)
+    });
+
+    // resources whose values the server sent, but couldn't be used, show the fallback too
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    if let Some((_, errors)) = &boundary {
+        let errors = *errors;
+        create_effect(cx, move |reported: Option<Vec<HydrationKey>>| {
+            let reported = reported.unwrap_or_default();
+            let resource_errors = context.errors();
+            if reported.is_empty() && resource_errors.is_empty() {
+                return reported;
+            }
+            let keys = resource_errors
+                .iter()
+                .map(|(id, _)| HydrationKey {
+                    previous: format!("resource-{id:?}"),
+                    offset: 0,
+                })
+                .collect::<Vec<_>>();
+            errors.update(|errors| {
+                for key in &reported {
+                    errors.remove(key);
+                }
+                for (key, (_, error)) in keys.iter().zip(resource_errors) {
+                    errors.insert(key.clone(), error);
+                }
+            });
+            keys
+        });
+    }
+
+    // the children are read again whenever resources change, so only the view that swaps them
+    // for the fallback tracks the errors, which can also be rendered after the children were,
+    // e.g., when a signal they read changes
+    let with_errors = Rc::new(
+        move |cx: Scope, id: HydrationKey, child: View| match &boundary {
+            Some((fallback, errors)) => {
+                let (fallback, errors) = (fallback.clone(), *errors);
+                let first_run = Cell::new(true);
+                DynChild::new(move || {
+                    if errors.with(Errors::is_empty) {
+                        child.clone()
+                    } else {
+                        // when hydrating, the fallback takes the place the children had
+                        if first_run.replace(false) {
+                            HydrationCtx::continue_from(id.clone());
+                        }
+                        (fallback.0)(cx, errors)
+                    }
+                })
+                .into_view(cx)
+            }
+            None => child,
+        },
+    );
+
     let orig_child = Rc::new(children);
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    let was_loading = Rc::new(Cell::new(false));
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    {
+        _ = aria_busy;
+        _ = focus_on_load;
+    }
 
-    Component::new("Suspense", move |cx| {
+    let view = Component::new("Suspense", move |cx| {
         #[cfg(not(any(feature = "csr", feature = "hydrate")))]
         let current_id = HydrationCtx::peek();
 
@@ -81,22 +180,33 @@ where
             cfg_if! {
                 if #[cfg(any(feature = "csr", feature = "hydrate"))] {
                     if context.ready() {
-                        orig_child(cx).into_view(cx)
+                        let id = HydrationCtx::peek();
+                        let child = orig_child(cx).into_view(cx);
+                        let child = with_errors(cx, id, child);
+                        if was_loading.replace(false) {
+                            if let Some(selector) = &focus_on_load {
+                                focus_after_mount(&child, selector);
+                            }
+                        }
+                        child
                     } else {
+                        was_loading.set(true);
                         fallback().into_view(cx)
                     }
                 } else {
                     // run the child; we'll probably throw this away, but it will register resource reads
+                    let child_id = HydrationCtx::peek();
                     let child = orig_child(cx).into_view(cx);
             
                     let initial = {    
                         // no resources were read under this, so just return the child
                         if context.pending_resources.get() == 0 {
-                            child.clone()
+                            with_errors(cx, child_id, child.clone())
                         }
                         // show the fallback, but also prepare to stream HTML
                         else {
                             let orig_child = Rc::clone(&orig_child);
+                            let with_errors = Rc::clone(&with_errors);
                             
                             cx.register_suspense(
                                 context,
@@ -109,17 +219,22 @@ where
                                         offset: current_id.offset + 1
                                     };
                                     move || {
-                                        HydrationCtx::continue_from(fragment_id);
-                                        orig_child(cx)
-                                            .into_view(cx)
+                                        HydrationCtx::continue_from(fragment_id.clone());
+                                        let child = orig_child(cx).into_view(cx);
+                                        with_errors(cx, fragment_id, child)
                                             .render_to_string(cx)
                                             .to_string()
                                     }
                                 }
                             );
                 
-                            // return the fallback for now, wrapped in fragment identifer
-                            fallback().into_view(cx)
+                            if use_context::<AwaitSuspense>(cx).is_some() {
+                                // the children replace this once they've loaded
+                                AwaitSuspense::placeholder(cx, &current_id.to_string())
+                            } else {
+                                // return the fallback for now, wrapped in fragment identifer
+                                fallback().into_view(cx)
+                            }
                         }
                     };
             
@@ -130,4 +245,46 @@ where
             }
         })
     })
+    .into_view(cx);
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    if aria_busy.unwrap_or(true) {
+        let view = view.clone();
+        create_effect(cx, move |_| {
+            let busy = !context.ready();
+            let view = view.clone();
+            // the view is only mounted once the parent that renders it has finished
+            queue_microtask(move || {
+                if let Some(parent) = view.parent_element() {
+                    if busy {
+                        _ = parent.set_attribute("aria-busy", "true");
+                    } else {
+                        _ = parent.remove_attribute("aria-busy");
+                    }
+                }
+            });
+        });
+    }
+
+    view
+}
+
+/// Focuses the first element in the view that matches the selector, once it has been mounted.
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn focus_after_mount(view: &View, selector: &str) {
+    use leptos_dom::wasm_bindgen::JsCast;
+
+    let view = view.clone();
+    let selector = selector.to_string();
+    queue_microtask(move || {
+        if let Some(el) = view.query_selector(&selector) {
+            // headings and other elements that can't normally be focused need a tabindex
+            if !el.has_attribute("tabindex") {
+                _ = el.set_attribute("tabindex", "-1");
+            }
+            if let Some(el) = el.dyn_ref::<leptos_dom::web_sys::HtmlElement>() {
+                _ = el.focus();
+            }
+        }
+    });
 }
\ No newline at end of file
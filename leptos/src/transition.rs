@@ -66,7 +66,15 @@ pub fn Transition<F, E>(
     #[prop(optional)]
     set_pending: Option<SignalSetter<bool>>,
     /// Will be displayed once all resources have resolved.
-    children: Box<dyn Fn(Scope) -> Fragment>
+    children: Box<dyn Fn(Scope) -> Fragment>,
+    /// Whether to set `aria-busy="true"` on the element that contains this component while
+    /// resources are loading. Defaults to `true`.
+    #[prop(optional)]
+    aria_busy: Option<bool>,
+    /// A CSS selector for the element in the `children` that should receive focus once they
+    /// have loaded. See [`Suspense`](crate::Suspense).
+    #[prop(optional, into)]
+    focus_on_load: Option<String>,
 ) -> impl IntoView
 where
     F: Fn() -> E + 'static,
@@ -75,8 +83,8 @@ where
     let prev_children = std::rc::Rc::new(RefCell::new(None::<Vec<View>>));
     crate::Suspense(
         cx,
-        crate::SuspenseProps::builder()
-            .fallback({
+        crate::SuspenseProps {
+            fallback: {
                 let prev_child = Rc::clone(&prev_children);
                 let set_pending = set_pending.clone();
                 move || {
@@ -89,15 +97,17 @@ where
                         fallback().into_view(cx)
                     }
                 }
-            })
-            .children(Box::new(move |cx| {
+            },
+            children: Box::new(move |cx| {
                 let frag = children(cx);
                 *prev_children.borrow_mut() = Some(frag.nodes.clone());
                 if let Some(set_pending) = &set_pending {
                     set_pending.set(false);
                 }
                 frag
-            }))
-            .build()
+            }),
+            aria_busy,
+            focus_on_load,
+        }
     )
 }
\ No newline at end of file
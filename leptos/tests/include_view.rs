@@ -0,0 +1,78 @@
+#![cfg(not(any(feature = "csr", feature = "hydrate")))]
+
+use leptos::*;
+
+#[test]
+fn attributes_can_be_quoted_unquoted_or_empty() {
+    let html = render_fragment_to_string(|cx| include_view!(cx, "tests/templates/attributes.html"));
+    assert_eq!(
+        html,
+        r#"<a href="/help" class="link" data-id="42" title="a &amp; b &amp;bogus; A" hidden>Help</a>"#
+    );
+}
+
+#[test]
+fn void_elements_dont_need_to_be_closed() {
+    let html =
+        render_fragment_to_string(|cx| include_view!(cx, "tests/templates/void_elements.html"));
+    assert_eq!(
+        html,
+        r#"<p>Name<br/>Email<input type="email"/><img src="/a.png" alt/></p>"#
+    );
+}
+
+#[test]
+fn comments_doctypes_and_whitespace_are_left_out() {
+    let html = render_fragment_to_string(|cx| include_view!(cx, "tests/templates/comments.html"));
+    assert_eq!(html, "<div><span>kept</span></div>");
+}
+
+#[test]
+fn entities_are_decoded() {
+    let html = render_fragment_to_string(|cx| include_view!(cx, "tests/templates/entities.html"));
+    assert_eq!(html, "<p>— © A</p>");
+}
+
+#[test]
+fn holes_are_filled_in_wherever_they_appear() {
+    let html = render_fragment_to_string(|cx| {
+        include_view!(
+            cx,
+            "tests/templates/holes.html",
+            class = "card-title",
+            title = "Hello",
+            author = "Ann",
+        )
+    });
+    assert!(
+        html.starts_with(r#"<article><h2 class="card-title">Hello</h2><p>By Ann, about Hello</p>"#),
+        "{html}"
+    );
+
+    // but not in scripts
+    assert!(html.contains("{{title}}"), "{html}");
+}
+
+#[test]
+fn holes_can_be_reactive() {
+    let html = render_fragment_to_string(|cx| {
+        let (title, _) = create_signal(cx, "Hello");
+        include_view!(
+            cx,
+            "tests/templates/holes.html",
+            class = move || title.get().to_lowercase(),
+            title = move || title.get(),
+            author = "Ann",
+        )
+    });
+    assert!(
+        html.starts_with(r#"<article><h2 class="hello">Hello</h2><p>By Ann, about Hello</p>"#),
+        "{html}"
+    );
+}
+
+#[test]
+fn capitalized_tags_are_elements() {
+    let html = render_fragment_to_string(|cx| include_view!(cx, "tests/templates/uppercase.html"));
+    assert_eq!(html, "<div>Shouting</div>");
+}
@@ -73,3 +73,46 @@ fn test_classes() {
         );
     });
 }
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_with_nonce() {
+    use leptos::*;
+
+    let rendered = render_to_string(|cx| {
+        provide_nonce(cx, "r4\"nd0m");
+        view! { cx, <p>"Hello"</p> }
+    });
+
+    assert!(rendered.starts_with("<style nonce=\"r4&quot;nd0m\">"));
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+#[test]
+fn ssr_islands_mark_their_hydration_keys() {
+    use leptos::*;
+
+    #[component(island = "counter")]
+    fn Counter(cx: Scope) -> impl IntoView {
+        view! { cx, <button>"+1"</button> }
+    }
+
+    _ = create_scope(create_runtime(), |cx| {
+        let manifest = IslandManifest::default();
+        provide_context(cx, manifest.clone());
+
+        let rendered = view! { cx, <main><Counter/></main> }
+            .into_view(cx)
+            .render_to_string(cx);
+
+        let islands = manifest.islands();
+        assert_eq!(islands.len(), 1);
+        assert_eq!(islands[0].bundle, "counter");
+        assert!(rendered.contains(&format!(
+            "<leptos-island data-bundle=\"counter\" data-hk=\"{}\" data-hk-end=\"{}\"",
+            islands[0].start, islands[0].end
+        )));
+        // the island's keys continue from its start, as they do when it's hydrated
+        assert!(rendered.contains(&format!("<button id=\"_{}", islands[0].start)));
+    });
+}
@@ -0,0 +1,70 @@
+use crate::errors::LeptosConfigError;
+use std::{collections::HashMap, fs, path::Path, sync::Arc};
+
+/// Maps the original path of a static asset (like `pkg/app.wasm`) to the fingerprinted path
+/// it was written to (like `pkg/app.3f9a1c.wasm`), so that cache-busted bundles can be
+/// served without changing the code that references them.
+///
+/// The manifest is a flat JSON object from original path to hashed path. Leading slashes are
+/// ignored when looking up a path, so `/pkg/app.js` and `pkg/app.js` resolve to the same entry.
+/// ```
+/// # use leptos_config::AssetManifest;
+/// let manifest = AssetManifest::from_iter([("pkg/app.wasm", "pkg/app.3f9a1c.wasm")]);
+/// assert_eq!(manifest.resolve("/pkg/app.wasm"), "/pkg/app.3f9a1c.wasm");
+/// assert_eq!(manifest.resolve("pkg/app.wasm"), "pkg/app.3f9a1c.wasm");
+/// assert_eq!(manifest.resolve("/pkg/app.css"), "/pkg/app.css");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AssetManifest(Arc<HashMap<String, String>>);
+
+impl AssetManifest {
+    /// Loads a manifest from a JSON file at the given path.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, LeptosConfigError> {
+        let text = fs::read_to_string(path.as_ref()).map_err(|e| {
+            LeptosConfigError::ConfigError(format!(
+                "could not read asset manifest {}: {e}",
+                path.as_ref().display()
+            ))
+        })?;
+        Self::from_json(&text)
+    }
+
+    /// Parses a manifest from a JSON object mapping original paths to hashed paths.
+    pub fn from_json(json: &str) -> Result<Self, LeptosConfigError> {
+        let entries: HashMap<String, String> = serde_json::from_str(json)
+            .map_err(|e| LeptosConfigError::ConfigError(format!("invalid asset manifest: {e}")))?;
+        Ok(Self::from_iter(entries))
+    }
+
+    /// Returns the fingerprinted path for the given asset, or the path itself
+    /// if it isn't in the manifest.
+    pub fn resolve(&self, path: &str) -> String {
+        let (slash, key) = match path.strip_prefix('/') {
+            Some(key) => ("/", key),
+            None => ("", path),
+        };
+        match self.0.get(key) {
+            Some(hashed) => format!("{slash}{}", hashed.trim_start_matches('/')),
+            None => path.to_string(),
+        }
+    }
+
+    /// Whether the manifest has no entries.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl<K, V> FromIterator<(K, V)> for AssetManifest
+where
+    K: Into<String>,
+    V: Into<String>,
+{
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(Arc::new(
+            iter.into_iter()
+                .map(|(k, v)| (k.into().trim_start_matches('/').to_string(), v.into()))
+                .collect(),
+        ))
+    }
+}
@@ -1,18 +1,56 @@
+use std::fmt;
 use thiserror::Error;
 
 #[derive(Debug, Error, Clone)]
 pub enum LeptosConfigError {
-    #[error("Cargo.toml not found in package root")]
+    #[error("Cargo.toml or leptos.toml not found in package root")]
     ConfigNotFound,
-    #[error("package.metadata.leptos section missing from Cargo.toml")]
+    #[error(
+        "package.metadata.leptos section missing from Cargo.toml, and no leptos.toml was found"
+    )]
     ConfigSectionNotFound,
     #[error("Failed to get Leptos Environment. Did you set LEPTOS_ENV?")]
     EnvError,
     #[error("Config Error: {0}")]
     ConfigError(String),
+    #[error("Invalid Leptos configuration:\n{}", format_problems(.0))]
+    Invalid(Vec<ConfigProblem>),
 }
 impl From<config::ConfigError> for LeptosConfigError {
     fn from(e: config::ConfigError) -> Self {
         Self::ConfigError(e.to_string())
     }
 }
+
+/// A single problem found while validating the layered configuration. All problems
+/// are collected before returning, so they can be fixed in one pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigProblem {
+    /// The key that has a problem, e.g. `site-address`.
+    pub key: String,
+    /// A description of what is wrong and how to fix it.
+    pub message: String,
+}
+
+impl ConfigProblem {
+    pub(crate) fn new(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.key, self.message)
+    }
+}
+
+fn format_problems(problems: &[ConfigProblem]) -> String {
+    problems
+        .iter()
+        .map(|problem| format!("  - {problem}"))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -1,23 +1,51 @@
+mod assets;
 pub mod errors;
 
-use crate::errors::LeptosConfigError;
-use config::{Config, File, FileFormat};
+pub use assets::*;
+
+use crate::errors::{ConfigProblem, LeptosConfigError};
+use config::{
+    builder::DefaultState, Config, ConfigBuilder, ConfigError, Environment, File, FileFormat,
+    Source, Value, ValueKind,
+};
 use regex::Regex;
+use serde::de::DeserializeOwned;
 use std::convert::TryFrom;
+use std::ffi::OsStr;
 use std::fs;
+use std::path::Path;
 use std::{env::VarError, net::SocketAddr, str::FromStr};
 use typed_builder::TypedBuilder;
 
 /// A Struct to allow us to parse LeptosOptions from the file. Not really needed, most interactions should
 /// occur with LeptosOptions
 #[derive(Clone, serde::Deserialize)]
-pub struct ConfFile {
+pub struct ConfFile<T = ()> {
     pub leptos_options: LeptosOptions,
+    /// Your own typed settings, read from the `settings` table. See [get_configuration_with_settings].
+    #[serde(default)]
+    pub settings: T,
 }
 /// This struct serves as a convenient place to store details used for configuring Leptos.
 /// It's used in our actix and axum integrations to generate the
 /// correct path for WASM, JS, and Websockets, as well as other configuration tasks.
 /// It shares keys with cargo-leptos, to allow for easy interoperability
+///
+/// Besides reading it with [get_configuration], it can be built in code. One server can host
+/// several apps by building options for each, with its own `output_name`, `site_root`, and
+/// `site_pkg_dir`, and giving each app's server functions a different prefix.
+/// ```
+/// # use leptos_config::*;
+/// let admin = LeptosOptions::builder()
+///     .output_name("admin")
+///     .site_root("target/admin")
+///     .site_address(([0, 0, 0, 0], 8080))
+///     .env(Env::PROD)
+///     .build();
+///
+/// assert_eq!(admin.site_pkg_dir, "pkg");
+/// assert_eq!(admin.reload_port, 3001);
+/// ```
 #[derive(TypedBuilder, Clone, serde::Deserialize)]
 pub struct LeptosOptions {
     /// The name of the WASM and JS files generated by wasm-bindgen. Defaults to the crate name with underscores instead of dashes
@@ -44,6 +72,37 @@ pub struct LeptosOptions {
     /// Defaults to `3001`
     #[builder(default = 3001)]
     pub reload_port: u32,
+    /// Maps static assets to their fingerprinted filenames. Loaded from the JSON file given by the
+    /// `asset-manifest` key, if any; see [AssetManifest].
+    #[builder(default)]
+    #[serde(skip)]
+    pub asset_manifest: AssetManifest,
+    /// The path on disk to the app's stylesheet. When set, the server integrations inline only the
+    /// rules that match each page's rendered HTML into the `<head>`, and defer loading the full stylesheet.
+    /// Set with the `critical-css` key.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(default)]
+    pub critical_css: Option<String>,
+    /// The name of a request header that marks a partial-page request, like `HX-Request` for htmx.
+    /// The server integrations answer requests carrying this header with only the HTML of the matched
+    /// route, without the document shell or hydration scripts, once its `<Suspense/>` data has loaded.
+    /// Responses name this header in `Vary`, so caches keep both versions of a page apart. Set with the
+    /// `partial-render-header` key.
+    #[builder(default, setter(strip_option, into))]
+    #[serde(default)]
+    pub partial_render_header: Option<String>,
+    /// Whether the app is built in islands mode, where each island is compiled into a bundle of
+    /// its own. The server integrations then load only the bundles of the islands that a page
+    /// rendered, instead of hydrating the whole app. Set with the `islands` key.
+    #[builder(default)]
+    #[serde(default)]
+    pub islands: bool,
+    /// Whether the server integrations stream the HTML for each `<Suspense/>` in the order it
+    /// appears in the page, rather than as soon as it resolves. Set with the `in-order-streaming`
+    /// key. An app that provides its own `StreamingOptions` chooses the order there instead.
+    #[builder(default)]
+    #[serde(default)]
+    pub in_order_streaming: bool,
 }
 
 /// An enum that can be used to define the environment Leptos is running in. Can be passed to [RenderOptions].
@@ -127,20 +186,110 @@ impl TryFrom<String> for Env {
 }
 /// Loads [LeptosOptions] from a Cargo.toml with layered overrides. If an env var is specified, like `LEPTOS_ENV`,
 /// it will override a setting in the file.
+///
+/// See [get_configuration_with_settings] for the full list of layers.
 pub async fn get_configuration(path: Option<&str>) -> Result<ConfFile, LeptosConfigError> {
+    get_configuration_with_settings(path).await
+}
+
+/// Loads [LeptosOptions], along with your own typed settings, from layered sources.
+/// Later layers override earlier ones:
+/// 1. the `[package.metadata.leptos]` section of `Cargo.toml`
+/// 2. a `leptos.toml` file next to `Cargo.toml`, whose top-level keys are the same as the metadata section
+/// 3. the section for the active profile, e.g. `[package.metadata.leptos.profile.release]` or
+///    `[profile.release]` in `leptos.toml`. The profile is read from `LEPTOS_PROFILE`, and defaults to
+///    `dev` in debug builds and `release` otherwise.
+/// 4. environment variables with a `LEPTOS_` prefix, e.g. `LEPTOS_RELOAD_PORT=5001`. Use `__` to
+///    reach into a table, e.g. `LEPTOS_SETTINGS__API_URL` sets `api_url` in your custom settings.
+///
+/// Your custom settings live in a `settings` table (`[package.metadata.leptos.settings]` or
+/// `[settings]` in `leptos.toml`) and are deserialized into `T`. If that table is missing,
+/// `T::default()` is used. Unlike the other keys, which can be kebab-case, their keys are used
+/// as they are written.
+///
+/// If `path` points at a `leptos.toml`, `Cargo.toml` is not read at all.
+///
+/// Rather than stopping at the first bad value, every problem is collected and
+/// returned together in [LeptosConfigError::Invalid].
+pub async fn get_configuration_with_settings<T>(
+    path: Option<&str>,
+) -> Result<ConfFile<T>, LeptosConfigError>
+where
+    T: DeserializeOwned + Default,
+{
     // Allow Cargo.toml path to be specified in case of workspace wonkiness
-    let text = match path {
-        Some(p) => fs::read_to_string(p).map_err(|_| LeptosConfigError::ConfigNotFound)?,
-        None => fs::read_to_string("Cargo.toml").map_err(|_| LeptosConfigError::ConfigNotFound)?,
-    };
+    let path = Path::new(path.unwrap_or("Cargo.toml"));
+    let is_leptos_toml = path.file_name() == Some(OsStr::new("leptos.toml"));
+
+    let mut layers = Vec::new();
+    if is_leptos_toml {
+        let text = fs::read_to_string(path).map_err(|_| LeptosConfigError::ConfigNotFound)?;
+        layers.push(leptos_toml_layer(&text));
+    } else {
+        let cargo_toml = fs::read_to_string(path).ok();
+        let leptos_toml = fs::read_to_string(path.with_file_name("leptos.toml")).ok();
+        if cargo_toml.is_none() && leptos_toml.is_none() {
+            return Err(LeptosConfigError::ConfigNotFound);
+        }
+        if let Some(layer) = cargo_toml.as_deref().and_then(cargo_toml_layer) {
+            layers.push(layer);
+        }
+        if let Some(text) = leptos_toml {
+            layers.push(leptos_toml_layer(&text));
+        }
+        if layers.is_empty() {
+            return Err(LeptosConfigError::ConfigSectionNotFound);
+        }
+    }
+
+    let profile = std::env::var("LEPTOS_PROFILE").unwrap_or_else(|_| {
+        if cfg!(debug_assertions) {
+            "dev".to_string()
+        } else {
+            "release".to_string()
+        }
+    });
+
+    let files = file_layers(&layers).build()?;
+    let mut overrides = Vec::new();
+    match files.get_table(&format!("leptos_options.profile.{profile}")) {
+        Ok(table) => {
+            for (key, value) in table {
+                flatten_into(format!("leptos_options.{key}"), value, &mut overrides);
+            }
+        }
+        Err(ConfigError::NotFound(_)) => {}
+        Err(e) => return Err(e.into()),
+    }
+    // Add in settings from environment variables (with a prefix of LEPTOS and '__' as separator)
+    // E.g. `LEPTOS_RELOAD_PORT=5001 would set `LeptosOptions.reload_port`
+    let env = Environment::with_prefix("LEPTOS")
+        .prefix_separator("_")
+        .separator("__")
+        .try_parsing(true)
+        .collect()?;
+    for (key, value) in env {
+        if key != "profile" {
+            overrides.push((format!("leptos_options.{key}"), value));
+        }
+    }
+
+    let mut builder = file_layers(&layers);
+    // profile values are pushed before environment values, so the environment wins
+    for (key, value) in overrides {
+        builder = builder.set_override(key, value)?;
+    }
+    let settings = builder.build()?;
 
+    validate(&settings)
+}
+
+/// Turns the `[package.metadata.leptos]` section of a Cargo.toml into a config layer.
+fn cargo_toml_layer(text: &str) -> Option<String> {
     let re: Regex =
         Regex::new(r#"(?m)^\[package.metadata.leptos\]|(?m)^\[\[workspace.metadata.leptos\]\]"#)
             .unwrap();
-    let start = match re.find(&text) {
-        Some(found) => found.start(),
-        None => return Err(LeptosConfigError::ConfigSectionNotFound),
-    };
+    let start = re.find(text)?.start();
 
     // so that serde error messages have right line number
     let newlines = text[..start].matches('\n').count();
@@ -148,17 +297,204 @@ pub async fn get_configuration(path: Option<&str>) -> Result<ConfFile, LeptosCon
     let toml = input
         .replace("[package.metadata.leptos]", "[leptos_options]")
         .replace("[[workspace.metadata.leptos]]", "[leptos_options]")
-        .replace('-', "_");
-    let settings = Config::builder()
-        // Read the "default" configuration file
-        .add_source(File::from_str(&toml, FileFormat::Toml))
-        // Layer on the environment-specific values.
-        // Add in settings from environment variables (with a prefix of LEPTOS and '_' as separator)
-        // E.g. `LEPTOS_RELOAD_PORT=5001 would set `LeptosOptions.reload_port`
-        .add_source(config::Environment::with_prefix("LEPTOS").separator("_"))
-        .build()?;
-
-    settings
-        .try_deserialize()
-        .map_err(|e| LeptosConfigError::ConfigError(e.to_string()))
+        .replace("[package.metadata.leptos.", "[leptos_options.");
+    Some(normalize_keys(&toml))
+}
+
+/// Turns a `leptos.toml` file into a config layer, nesting all of its tables under `leptos_options`.
+fn leptos_toml_layer(text: &str) -> String {
+    let re: Regex = Regex::new(r#"(?m)^\[([^\[\]]+)\]"#).unwrap();
+    let toml = re.replace_all(text, "[leptos_options.$1]");
+    normalize_keys(&format!("[leptos_options]\n{toml}"))
+}
+
+/// Cargo uses kebab-case keys, but [LeptosOptions] uses snake_case fields. The keys of the
+/// `settings` tables are left as they are, because they belong to the app.
+fn normalize_keys(toml: &str) -> String {
+    let table: Regex = Regex::new(r#"^\s*\[\[?([^\[\]]+)\]\]?"#).unwrap();
+    let key: Regex = Regex::new(r#"^(\s*)([A-Za-z0-9_-]+)(\s*=)"#).unwrap();
+    let mut in_settings = false;
+    toml.split('\n')
+        .map(|line| {
+            if let Some(caps) = table.captures(line) {
+                in_settings = is_settings_table(&caps[1]);
+                line.into()
+            } else if in_settings {
+                line.into()
+            } else {
+                key.replace(line, |caps: &regex::Captures| {
+                    format!("{}{}{}", &caps[1], caps[2].replace('-', "_"), &caps[3])
+                })
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Whether a table, like `leptos_options.settings.api` or `leptos_options.profile.dev.settings`,
+/// is one of the app's `settings` tables.
+fn is_settings_table(name: &str) -> bool {
+    let path = name.split('.').map(str::trim).collect::<Vec<_>>();
+    matches!(
+        path.as_slice(),
+        ["leptos_options", "settings", ..] | ["leptos_options", "profile", _, "settings", ..]
+    )
+}
+
+fn file_layers(layers: &[String]) -> ConfigBuilder<DefaultState> {
+    layers.iter().fold(Config::builder(), |builder, layer| {
+        builder.add_source(File::from_str(layer, FileFormat::Toml))
+    })
+}
+
+fn flatten_into(key: String, value: Value, out: &mut Vec<(String, Value)>) {
+    match value.kind {
+        ValueKind::Table(table) => {
+            for (child, value) in table {
+                flatten_into(format!("{key}.{child}"), value, out);
+            }
+        }
+        _ => out.push((key, value)),
+    }
+}
+
+/// Reads an optional key, recording a problem if it is present but has the wrong type.
+fn read_key<V: DeserializeOwned>(
+    settings: &Config,
+    key: &str,
+    problems: &mut Vec<ConfigProblem>,
+) -> Option<V> {
+    match settings.get::<V>(&format!("leptos_options.{key}")) {
+        Ok(value) => Some(value),
+        Err(ConfigError::NotFound(_)) => None,
+        Err(e) => {
+            problems.push(ConfigProblem::new(key.replace('_', "-"), e.to_string()));
+            None
+        }
+    }
+}
+
+fn http_header_name_is_invalid(name: &str) -> bool {
+    name.is_empty()
+        || !name
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&b))
+}
+
+fn validate<T>(settings: &Config) -> Result<ConfFile<T>, LeptosConfigError>
+where
+    T: DeserializeOwned + Default,
+{
+    let mut problems = Vec::new();
+
+    let output_name = read_key::<String>(settings, "output_name", &mut problems);
+    let site_root = read_key::<String>(settings, "site_root", &mut problems);
+    let site_pkg_dir = read_key::<String>(settings, "site_pkg_dir", &mut problems);
+    let env = read_key::<String>(settings, "env", &mut problems);
+    let site_address = read_key::<String>(settings, "site_address", &mut problems);
+    let reload_port = read_key::<u32>(settings, "reload_port", &mut problems);
+    let asset_manifest = read_key::<String>(settings, "asset_manifest", &mut problems);
+    let critical_css = read_key::<String>(settings, "critical_css", &mut problems);
+    let partial_render_header =
+        read_key::<String>(settings, "partial_render_header", &mut problems);
+    let islands = read_key::<bool>(settings, "islands", &mut problems);
+    let in_order_streaming = read_key::<bool>(settings, "in_order_streaming", &mut problems);
+    let custom = read_key::<T>(settings, "settings", &mut problems);
+
+    let output_name = match output_name {
+        Some(name) if !name.trim().is_empty() => Some(name.replace('-', "_")),
+        Some(_) => {
+            problems.push(ConfigProblem::new("output-name", "must not be empty"));
+            None
+        }
+        None => {
+            problems.push(ConfigProblem::new(
+                "output-name",
+                "is required; set it in [package.metadata.leptos], leptos.toml, or LEPTOS_OUTPUT_NAME",
+            ));
+            None
+        }
+    };
+    let env = env.and_then(|env| match Env::try_from(env) {
+        Ok(env) => Some(env),
+        Err(e) => {
+            problems.push(ConfigProblem::new("env", e));
+            None
+        }
+    });
+    let site_address = site_address.and_then(|addr| match addr.parse::<SocketAddr>() {
+        Ok(addr) => Some(addr),
+        Err(_) => {
+            problems.push(ConfigProblem::new(
+                "site-address",
+                format!("`{addr}` is not a socket address; use a value like `127.0.0.1:3000`"),
+            ));
+            None
+        }
+    });
+    let asset_manifest = asset_manifest.and_then(|path| match AssetManifest::load(&path) {
+        Ok(manifest) => Some(manifest),
+        Err(e) => {
+            problems.push(ConfigProblem::new("asset-manifest", e.to_string()));
+            None
+        }
+    });
+    if let Some(path) = &critical_css {
+        if !Path::new(path).is_file() {
+            problems.push(ConfigProblem::new(
+                "critical-css",
+                format!("`{path}` is not a file; point it at your app's compiled stylesheet"),
+            ));
+        }
+    }
+    if let Some(header) = &partial_render_header {
+        if http_header_name_is_invalid(header) {
+            problems.push(ConfigProblem::new(
+                "partial-render-header",
+                format!("`{header}` is not a valid header name; use a value like `HX-Request`"),
+            ));
+        }
+    }
+    let reload_port = reload_port.and_then(|port| {
+        if port == 0 || port > u16::MAX as u32 {
+            problems.push(ConfigProblem::new(
+                "reload-port",
+                format!("`{port}` is not a valid port; use a value between 1 and 65535"),
+            ));
+            None
+        } else {
+            Some(port)
+        }
+    });
+    if let (Some(addr), Some(port)) = (&site_address, reload_port) {
+        if addr.port() as u32 == port {
+            problems.push(ConfigProblem::new(
+                "reload-port",
+                format!("`{port}` is already used by `site-address`; pick a different port"),
+            ));
+        }
+    }
+
+    match output_name {
+        Some(output_name) if problems.is_empty() => {
+            let defaults = LeptosOptions::builder().output_name(output_name).build();
+            Ok(ConfFile {
+                leptos_options: LeptosOptions {
+                    site_root: site_root.unwrap_or(defaults.site_root),
+                    site_pkg_dir: site_pkg_dir.unwrap_or(defaults.site_pkg_dir),
+                    env: env.unwrap_or(defaults.env),
+                    site_address: site_address.unwrap_or(defaults.site_address),
+                    reload_port: reload_port.unwrap_or(defaults.reload_port),
+                    asset_manifest: asset_manifest.unwrap_or(defaults.asset_manifest),
+                    critical_css,
+                    partial_render_header,
+                    islands: islands.unwrap_or(defaults.islands),
+                    in_order_streaming: in_order_streaming.unwrap_or(defaults.in_order_streaming),
+                    ..defaults
+                },
+                settings: custom.unwrap_or_default(),
+            })
+        }
+        _ => Err(LeptosConfigError::Invalid(problems)),
+    }
 }
@@ -0,0 +1,224 @@
+use futures::executor::block_on;
+use leptos_config::{
+    errors::LeptosConfigError, get_configuration, get_configuration_with_settings, ConfFile, Env,
+};
+use std::{collections::HashMap, fs, path::PathBuf, sync::Mutex};
+
+/// Environment variables are shared by every test, so only one test reads the configuration
+/// at a time.
+static ENV: Mutex<()> = Mutex::new(());
+
+/// Writes the files of a project into a directory of its own, and returns the directory.
+fn project(name: &str, files: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("leptos_config_{name}_{}", std::process::id()));
+    _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, contents) in files {
+        fs::write(dir.join(file), contents).unwrap();
+    }
+    dir
+}
+
+/// Reads the configuration at `path` with the given `LEPTOS_*` environment variables set.
+fn load<T>(path: PathBuf, vars: &[(&str, &str)]) -> Result<ConfFile<T>, LeptosConfigError>
+where
+    T: serde::de::DeserializeOwned + Default,
+{
+    let _lock = ENV.lock().unwrap_or_else(|e| e.into_inner());
+    for (var, value) in vars {
+        std::env::set_var(var, value);
+    }
+    let conf = block_on(get_configuration_with_settings(path.to_str()));
+    for (var, _) in vars {
+        std::env::remove_var(var);
+    }
+    conf
+}
+
+fn options(conf: Result<ConfFile, LeptosConfigError>) -> leptos_config::LeptosOptions {
+    match conf {
+        Ok(conf) => conf.leptos_options,
+        Err(e) => panic!("{e}"),
+    }
+}
+
+fn problems<T>(conf: Result<ConfFile<T>, LeptosConfigError>) -> Vec<String> {
+    match conf {
+        Err(LeptosConfigError::Invalid(problems)) => {
+            problems.into_iter().map(|problem| problem.key).collect()
+        }
+        Err(e) => panic!("expected invalid configuration, found {e}"),
+        Ok(_) => panic!("expected invalid configuration"),
+    }
+}
+
+const CARGO_TOML: &str = r#"
+[package]
+name = "app"
+
+[package.metadata.leptos]
+output-name = "my-app"
+site-root = "target/site"
+reload-port = 3005
+
+[package.metadata.leptos.profile.release]
+site-root = "dist"
+reload-port = 4000
+"#;
+
+#[test]
+fn cargo_toml_metadata_is_read() {
+    let dir = project("cargo", &[("Cargo.toml", CARGO_TOML)]);
+    let options = options(load(dir.join("Cargo.toml"), &[("LEPTOS_PROFILE", "dev")]));
+    assert_eq!(options.output_name, "my_app");
+    assert_eq!(options.site_root, "target/site");
+    assert_eq!(options.reload_port, 3005);
+    // and the rest are defaults
+    assert_eq!(options.site_pkg_dir, "pkg");
+    assert!(matches!(options.env, Env::DEV));
+}
+
+#[test]
+fn leptos_toml_overrides_cargo_toml() {
+    let dir = project(
+        "leptos_toml",
+        &[
+            ("Cargo.toml", CARGO_TOML),
+            ("leptos.toml", "site-root = \"public\"\nenv = \"PROD\"\n"),
+        ],
+    );
+    let options = options(load(dir.join("Cargo.toml"), &[("LEPTOS_PROFILE", "dev")]));
+    assert_eq!(options.output_name, "my_app");
+    assert_eq!(options.site_root, "public");
+    assert!(matches!(options.env, Env::PROD));
+}
+
+#[test]
+fn leptos_toml_can_be_read_on_its_own() {
+    let dir = project(
+        "leptos_toml_only",
+        &[
+            ("Cargo.toml", CARGO_TOML),
+            ("leptos.toml", "output-name = \"other\"\n"),
+        ],
+    );
+    let options = options(load(dir.join("leptos.toml"), &[]));
+    assert_eq!(options.output_name, "other");
+    // Cargo.toml isn't read
+    assert_eq!(options.site_root, "pkg");
+}
+
+#[test]
+fn the_active_profile_overrides_the_files() {
+    let dir = project("profile", &[("Cargo.toml", CARGO_TOML)]);
+    let release = options(load(
+        dir.join("Cargo.toml"),
+        &[("LEPTOS_PROFILE", "release")],
+    ));
+    assert_eq!(release.site_root, "dist");
+    assert_eq!(release.reload_port, 4000);
+
+    let dev = options(load(dir.join("Cargo.toml"), &[("LEPTOS_PROFILE", "dev")]));
+    assert_eq!(dev.site_root, "target/site");
+    assert_eq!(dev.reload_port, 3005);
+}
+
+#[test]
+fn leptos_toml_profiles_are_read() {
+    let dir = project(
+        "leptos_toml_profile",
+        &[(
+            "leptos.toml",
+            "output-name = \"app\"\n\n[profile.staging]\nsite-address = \"0.0.0.0:8080\"\n",
+        )],
+    );
+    let options = options(load(
+        dir.join("leptos.toml"),
+        &[("LEPTOS_PROFILE", "staging")],
+    ));
+    assert_eq!(options.site_address.to_string(), "0.0.0.0:8080");
+}
+
+#[test]
+fn environment_variables_override_the_profile() {
+    let dir = project("env", &[("Cargo.toml", CARGO_TOML)]);
+    let options = options(load(
+        dir.join("Cargo.toml"),
+        &[
+            ("LEPTOS_PROFILE", "release"),
+            ("LEPTOS_RELOAD_PORT", "5000"),
+        ],
+    ));
+    assert_eq!(options.reload_port, 5000);
+    assert_eq!(options.site_root, "dist");
+}
+
+#[test]
+fn settings_keep_their_keys() {
+    let dir = project(
+        "settings",
+        &[(
+            "Cargo.toml",
+            r#"
+[package.metadata.leptos]
+output-name = "app"
+
+[package.metadata.leptos.settings]
+api-url = "https://example.com"
+page_size = "20"
+"#,
+        )],
+    );
+    let conf = load::<HashMap<String, String>>(
+        dir.join("Cargo.toml"),
+        &[("LEPTOS_SETTINGS__TOKEN", "secret")],
+    );
+    let settings = match conf {
+        Ok(conf) => conf.settings,
+        Err(e) => panic!("{e}"),
+    };
+    assert_eq!(
+        settings.get("api-url").map(String::as_str),
+        Some("https://example.com")
+    );
+    assert_eq!(settings.get("api_url"), None);
+    assert_eq!(settings.get("page_size").map(String::as_str), Some("20"));
+    assert_eq!(settings.get("token").map(String::as_str), Some("secret"));
+}
+
+#[test]
+fn every_problem_is_collected() {
+    let dir = project(
+        "problems",
+        &[(
+            "leptos.toml",
+            "env = \"staging\"\nsite-address = \"nowhere\"\nreload-port = 0\n",
+        )],
+    );
+    assert_eq!(
+        problems(load::<()>(dir.join("leptos.toml"), &[])),
+        ["output-name", "env", "site-address", "reload-port"]
+    );
+}
+
+#[test]
+fn values_of_the_wrong_type_are_problems() {
+    let dir = project(
+        "wrong_types",
+        &[(
+            "leptos.toml",
+            "output-name = \"app\"\nislands = \"maybe\"\nreload-port = \"soon\"\n",
+        )],
+    );
+    assert_eq!(
+        problems(load::<()>(dir.join("leptos.toml"), &[])),
+        ["reload-port", "islands"]
+    );
+}
+
+#[test]
+fn a_missing_config_is_an_error() {
+    let dir = project("missing", &[]);
+    let conf = block_on(get_configuration(dir.join("Cargo.toml").to_str()));
+    assert!(matches!(conf, Err(LeptosConfigError::ConfigNotFound)));
+}
@@ -0,0 +1,149 @@
+//! Typed helpers for [WAI-ARIA](https://www.w3.org/TR/wai-aria-1.2/) attributes.
+
+use crate::{macro_helpers::Attribute, IntoAttribute};
+use leptos_reactive::Scope;
+use std::{fmt, rc::Rc};
+
+macro_rules! roles {
+  ($($variant:ident => $name:literal,)*) => {
+    /// A [WAI-ARIA role](https://www.w3.org/TR/wai-aria-1.2/#role_definitions),
+    /// which can be set with [`HtmlElement::role`](crate::HtmlElement::role).
+    ///
+    /// Only the roles that can be used by authors are included, not the
+    /// abstract ones.
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    pub enum Role {
+      $(
+        #[doc = concat!("The [`", $name, "`](https://www.w3.org/TR/wai-aria-1.2/#", $name, ") role.")]
+        $variant,
+      )*
+    }
+
+    impl Role {
+      /// The value of the `role` attribute for this role.
+      ///
+      /// ```
+      /// # use leptos_dom::*;
+      /// assert_eq!(Role::AlertDialog.as_str(), "alertdialog");
+      /// ```
+      pub fn as_str(&self) -> &'static str {
+        match self {
+          $(Self::$variant => $name,)*
+        }
+      }
+    }
+  };
+}
+
+roles! {
+  Alert => "alert",
+  AlertDialog => "alertdialog",
+  Application => "application",
+  Article => "article",
+  Banner => "banner",
+  Blockquote => "blockquote",
+  Button => "button",
+  Caption => "caption",
+  Cell => "cell",
+  Checkbox => "checkbox",
+  Code => "code",
+  ColumnHeader => "columnheader",
+  Combobox => "combobox",
+  Complementary => "complementary",
+  ContentInfo => "contentinfo",
+  Definition => "definition",
+  Deletion => "deletion",
+  Dialog => "dialog",
+  Document => "document",
+  Emphasis => "emphasis",
+  Feed => "feed",
+  Figure => "figure",
+  Form => "form",
+  Generic => "generic",
+  Grid => "grid",
+  GridCell => "gridcell",
+  Group => "group",
+  Heading => "heading",
+  Img => "img",
+  Insertion => "insertion",
+  Link => "link",
+  List => "list",
+  Listbox => "listbox",
+  ListItem => "listitem",
+  Log => "log",
+  Main => "main",
+  Marquee => "marquee",
+  Math => "math",
+  Menu => "menu",
+  Menubar => "menubar",
+  MenuItem => "menuitem",
+  MenuItemCheckbox => "menuitemcheckbox",
+  MenuItemRadio => "menuitemradio",
+  Meter => "meter",
+  Navigation => "navigation",
+  None => "none",
+  Note => "note",
+  Option => "option",
+  Paragraph => "paragraph",
+  Presentation => "presentation",
+  ProgressBar => "progressbar",
+  Radio => "radio",
+  RadioGroup => "radiogroup",
+  Region => "region",
+  Row => "row",
+  RowGroup => "rowgroup",
+  RowHeader => "rowheader",
+  Scrollbar => "scrollbar",
+  Search => "search",
+  Searchbox => "searchbox",
+  Separator => "separator",
+  Slider => "slider",
+  SpinButton => "spinbutton",
+  Status => "status",
+  Strong => "strong",
+  Subscript => "subscript",
+  Superscript => "superscript",
+  Switch => "switch",
+  Tab => "tab",
+  Table => "table",
+  TabList => "tablist",
+  TabPanel => "tabpanel",
+  Term => "term",
+  Textbox => "textbox",
+  Time => "time",
+  Timer => "timer",
+  Toolbar => "toolbar",
+  Tooltip => "tooltip",
+  Tree => "tree",
+  TreeGrid => "treegrid",
+  TreeItem => "treeitem",
+}
+
+impl fmt::Display for Role {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl IntoAttribute for Role {
+  fn into_attribute(self, _: Scope) -> Attribute {
+    Attribute::String(self.as_str().to_string())
+  }
+}
+
+impl IntoAttribute for Option<Role> {
+  fn into_attribute(self, cx: Scope) -> Attribute {
+    Attribute::Option(cx, self.map(|role| role.as_str().to_string()))
+  }
+}
+
+/// Converts `true` and `false` into the strings `"true"` and `"false"`, because
+/// ARIA states like `aria-expanded` are not boolean attributes: they must be
+/// present and set to `"false"` to mean `false`.
+pub(crate) fn aria_value(value: Attribute) -> Attribute {
+  match value {
+    Attribute::Bool(value) => Attribute::String(value.to_string()),
+    Attribute::Fn(cx, f) => Attribute::Fn(cx, Rc::new(move || aria_value(f()))),
+    value => value,
+  }
+}
@@ -0,0 +1,259 @@
+//! Reactive access to the clipboard.
+
+use crate::{use_permission, use_timeout, PermissionState, Timer};
+use leptos_reactive::{
+  create_effect, create_rw_signal, RwSignal, Scope, Signal,
+};
+use std::{fmt, time::Duration};
+
+/// An error while accessing the clipboard.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ClipboardError {
+  /// The browser doesn't support the
+  /// [Clipboard API](https://developer.mozilla.org/en-US/docs/Web/API/Clipboard_API),
+  /// or the page isn't served over HTTPS. The clipboard is never available
+  /// during server rendering.
+  Unsupported,
+  /// The user or browser didn't allow access to the clipboard.
+  NotAllowed,
+  /// Any other error, with its message.
+  Other(String),
+}
+
+impl fmt::Display for ClipboardError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::Unsupported => f.write_str("the clipboard is not available"),
+      Self::NotAllowed => {
+        f.write_str("access to the clipboard was not allowed")
+      }
+      Self::Other(message) => write!(f, "clipboard error: {message}"),
+    }
+  }
+}
+
+impl std::error::Error for ClipboardError {}
+
+/// Options for [use_clipboard_with_options].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClipboardOptions {
+  /// How long [Clipboard::copied] stays `true` after copying. Defaults to 1.5
+  /// seconds.
+  pub copied_duration: Duration,
+}
+
+impl Default for ClipboardOptions {
+  fn default() -> Self {
+    Self {
+      copied_duration: Duration::from_millis(1500),
+    }
+  }
+}
+
+/// Reactive access to the clipboard, created with [use_clipboard].
+///
+/// Copying and reading are asynchronous; their results are written to the
+/// signals returned by [Clipboard::text], [Clipboard::copied], and
+/// [Clipboard::error]. During server rendering, nothing is copied or read and
+/// the signals keep their initial values.
+#[derive(Clone, Copy, Debug)]
+pub struct Clipboard {
+  text: RwSignal<Option<String>>,
+  copied: RwSignal<bool>,
+  error: RwSignal<Option<ClipboardError>>,
+  permission: RwSignal<PermissionState>,
+  reset: Timer,
+}
+
+/// Returns a [Clipboard] that can copy text to, and read text from, the
+/// system clipboard.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn CopyButton(cx: Scope, text: String) -> impl IntoView {
+///   let clipboard = use_clipboard(cx);
+///
+///   view! { cx,
+///     <button on:click=move |_| clipboard.copy(text.clone())>
+///       {move || if clipboard.copied().get() { "Copied!" } else { "Copy" }}
+///     </button>
+///   }
+/// }
+/// ```
+pub fn use_clipboard(cx: Scope) -> Clipboard {
+  use_clipboard_with_options(cx, ClipboardOptions::default())
+}
+
+/// Like [use_clipboard], but with the given options.
+pub fn use_clipboard_with_options(
+  cx: Scope,
+  options: ClipboardOptions,
+) -> Clipboard {
+  let copied = create_rw_signal(cx, false);
+  let reset =
+    use_timeout(cx, options.copied_duration, move || copied.set(false));
+  // only start the timer once something is copied
+  reset.pause();
+
+  // not every browser reports this, so it's also updated after each read
+  let permission = create_rw_signal(cx, PermissionState::Unknown);
+  let reported = use_permission(cx, "clipboard-read");
+  create_effect(cx, move |_| {
+    let reported = reported.get();
+    if reported != PermissionState::Unknown {
+      permission.set(reported);
+    }
+  });
+
+  Clipboard {
+    text: create_rw_signal(cx, None),
+    copied,
+    error: create_rw_signal(cx, None),
+    permission,
+    reset,
+  }
+}
+
+impl Clipboard {
+  /// Writes the text to the clipboard. Once it has been written,
+  /// [Clipboard::copied] is `true` for
+  /// [ClipboardOptions::copied_duration].
+  pub fn copy(&self, text: impl Into<String>) {
+    let text = text.into();
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+      let this = *self;
+      leptos_reactive::spawn_local(async move {
+        match web::call("writeText", Some(&text)).await {
+          Ok(_) => {
+            this.text.set(Some(text));
+            this.error.set(None);
+            this.copied.set(true);
+            this.reset.reset();
+          }
+          Err(e) => this.fail(e),
+        }
+      });
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    _ = text;
+  }
+
+  /// Reads text from the clipboard into [Clipboard::text]. Browsers usually
+  /// ask the user for permission the first time; the result is available from
+  /// [Clipboard::permission].
+  pub fn read(&self) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+      let this = *self;
+      leptos_reactive::spawn_local(async move {
+        match web::call("readText", None).await {
+          Ok(text) => {
+            this.text.set(text.as_string());
+            this.error.set(None);
+            this.permission.set(PermissionState::Granted);
+          }
+          Err(e) => this.fail(e),
+        }
+      });
+    }
+  }
+
+  /// The text that was most recently copied or read.
+  pub fn text(&self) -> Signal<Option<String>> {
+    self.text.into()
+  }
+
+  /// Whether text was copied within the last
+  /// [ClipboardOptions::copied_duration], e.g., to show a "Copied!" message.
+  pub fn copied(&self) -> Signal<bool> {
+    self.copied.into()
+  }
+
+  /// The error from the most recent copy or read, if it failed.
+  pub fn error(&self) -> Signal<Option<ClipboardError>> {
+    self.error.into()
+  }
+
+  /// Whether the page is allowed to read from the clipboard, as reported by
+  /// the browser or as found by the most recent read.
+  pub fn permission(&self) -> Signal<PermissionState> {
+    self.permission.into()
+  }
+
+  /// Whether the clipboard is available. This is always `false` during server
+  /// rendering.
+  pub fn is_supported(&self) -> bool {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    return web::clipboard().is_some();
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    false
+  }
+
+  // only called in the browser
+  #[cfg_attr(
+    not(all(target_arch = "wasm32", feature = "web")),
+    allow(dead_code)
+  )]
+  fn fail(&self, error: ClipboardError) {
+    if error == ClipboardError::NotAllowed {
+      self.permission.set(PermissionState::Denied);
+    }
+    self.error.set(Some(error));
+  }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod web {
+  use super::ClipboardError;
+  use wasm_bindgen::{JsCast, JsValue};
+  use wasm_bindgen_futures::JsFuture;
+
+  /// `navigator.clipboard`, which is only defined in secure contexts.
+  pub(super) fn clipboard() -> Option<JsValue> {
+    js_sys::Reflect::get(&crate::window().navigator(), &"clipboard".into())
+      .ok()
+      .filter(|clipboard| !clipboard.is_undefined())
+  }
+
+  /// Calls a method of `navigator.clipboard` and awaits the `Promise` it
+  /// returns.
+  pub(super) async fn call(
+    method: &str,
+    arg: Option<&str>,
+  ) -> Result<JsValue, ClipboardError> {
+    let clipboard = clipboard().ok_or(ClipboardError::Unsupported)?;
+    let method = js_sys::Reflect::get(&clipboard, &method.into())
+      .ok()
+      .and_then(|method| method.dyn_into::<js_sys::Function>().ok())
+      .ok_or(ClipboardError::Unsupported)?;
+    let promise = match arg {
+      Some(arg) => method.call1(&clipboard, &arg.into()),
+      None => method.call0(&clipboard),
+    }
+    .map_err(to_error)?;
+    JsFuture::from(promise.unchecked_into::<js_sys::Promise>())
+      .await
+      .map_err(to_error)
+  }
+
+  fn to_error(error: JsValue) -> ClipboardError {
+    let field = |name: &str| {
+      js_sys::Reflect::get(&error, &name.into())
+        .ok()
+        .and_then(|value| value.as_string())
+    };
+    match field("name").as_deref() {
+      Some("NotAllowedError") => ClipboardError::NotAllowed,
+      _ => ClipboardError::Other(
+        field("message")
+          .or_else(|| error.as_string())
+          .unwrap_or_default(),
+      ),
+    }
+  }
+}
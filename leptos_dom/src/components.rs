@@ -1,4 +1,5 @@
 mod dyn_child;
+mod dyn_text;
 mod each;
 mod fragment;
 mod unit;
@@ -10,6 +11,7 @@ use crate::{
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use crate::{mount_child, prepare_to_move, MountKind, Mountable};
 pub use dyn_child::*;
+pub use dyn_text::*;
 pub use each::*;
 pub use fragment::*;
 use leptos_reactive::Scope;
@@ -246,8 +248,12 @@ where
     let mut repr = ComponentRepr::new_with_id(name.clone(), id);
 
     // disposed automatically when the parent scope is disposed
-    let (child, _) =
-      cx.run_child_scope(|cx| cx.untrack(|| children_fn(cx).into_view(cx)));
+    let (child, _) = cx.run_child_scope(|cx| {
+      #[cfg(debug_assertions)]
+      leptos_reactive::set_component_name(cx, name.clone());
+
+      cx.untrack(|| children_fn(cx).into_view(cx))
+    });
 
     repr.children.push(child);
 
@@ -171,6 +171,13 @@ where
         #[cfg(debug_assertions)]
         let _guard = span.enter();
 
+        #[cfg(debug_assertions)]
+        if prev_run.is_some() {
+          if let Some(reason) = leptos_reactive::render_reason(cx) {
+            tracing::trace!(%reason, "re-rendering");
+          }
+        }
+
         let (new_child, disposer) =
           cx.run_child_scope(|cx| child_fn().into_view(cx));
 
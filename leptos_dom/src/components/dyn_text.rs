@@ -1 +1,120 @@
+use crate::{hydration::HydrationCtx, Text, View};
+use cfg_if::cfg_if;
+use leptos_reactive::Scope;
+use std::fmt;
+cfg_if! {
+  if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+    use crate::document;
+    use leptos_reactive::create_render_effect;
+    use wasm_bindgen::JsCast;
+  } else {
+    use crate::ssr::HYDRATION_MARKERS;
+    use std::cell::Cell;
+  }
+}
 
+/// Creates a single text node whose content is updated in place whenever the
+/// signals read by `text` change.
+///
+/// Unlike rendering `move || text()`, which creates a [`DynChild`](crate::DynChild)
+/// with marker nodes around its content, this creates nothing but the text
+/// node, and updating it only sets its data. This makes it cheaper for views
+/// with lots of changing text, but it can only ever render text.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (count, set_count) = create_signal(cx, 0);
+///
+/// let counter = p(cx)
+///   .child("Clicked ")
+///   .child(dyn_text(cx, move || count.get()))
+///   .child(" times");
+/// # });
+/// ```
+pub fn dyn_text<T>(cx: Scope, text: impl Fn() -> T + 'static) -> View
+where
+  T: fmt::Display,
+{
+  let id = HydrationCtx::id();
+
+  cfg_if! {
+    if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+      let content = text().to_string();
+      let node = if HydrationCtx::is_hydrating() {
+        hydrate_text_node(&HydrationCtx::to_string(&id, true))
+      } else {
+        None
+      }
+      .unwrap_or_else(|| document().create_text_node(&content));
+
+      create_render_effect(cx, {
+        let node = node.clone();
+        move |prev: Option<String>| {
+          let content = text().to_string();
+          // on the first run, the node already has the content
+          if prev.map_or(false, |prev| prev != content) {
+            node.set_data(&content);
+          }
+          content
+        }
+      });
+
+      View::Text(Text {
+        node: node.unchecked_into(),
+        content: content.into(),
+      })
+    } else {
+      let _ = cx;
+      let content = text().to_string();
+
+      // the empty comment keeps the browser from merging the text with the
+      // text before it, and the marker is where hydration finds it
+      if HYDRATION_MARKERS.with(Cell::get) {
+        View::Text(Text::new(
+          format!(
+            "<!>{content}<l-m id=\"{}\"></l-m>",
+            HydrationCtx::to_string(&id, true)
+          )
+          .into(),
+        ))
+      } else {
+        View::Text(Text::new(content.into()))
+      }
+    }
+  }
+}
+
+/// Finds the text node rendered on the server before the marker with the given
+/// `id`, and removes the marker and the empty comment before the text.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn hydrate_text_node(id: &str) -> Option<web_sys::Text> {
+  let marker = match document().get_element_by_id(id) {
+    Some(marker) => marker,
+    None => {
+      crate::logging::hydration_miss(format!(
+        "text with id {id} not found, ignoring it for hydration"
+      ));
+      return None;
+    }
+  };
+
+  // the browser doesn't create a node for empty text
+  let text = marker
+    .previous_sibling()
+    .and_then(|node| node.dyn_into::<web_sys::Text>().ok())
+    .unwrap_or_else(|| {
+      let text = document().create_text_node("");
+      _ = marker.before_with_node_1(&text);
+      text
+    });
+
+  if let Some(comment) = text.previous_sibling() {
+    if comment.node_type() == web_sys::Node::COMMENT_NODE {
+      comment.unchecked_into::<web_sys::Element>().remove();
+    }
+  }
+  marker.remove();
+
+  Some(text)
+}
@@ -5,14 +5,11 @@ cfg_if! {
     use crate::{mount_child, prepare_to_move, MountKind, Mountable, RANGE};
     use once_cell::unsync::OnceCell;
     use leptos_reactive::create_effect;
-    use rustc_hash::FxHasher;
-    use std::hash::BuildHasherDefault;
     use wasm_bindgen::JsCast;
+    #[cfg(feature = "legacy-each-diff")]
     use drain_filter_polyfill::VecExt as VecDrainFilterExt;
 
-    type FxIndexSet<T> = indexmap::IndexSet<T, BuildHasherDefault<FxHasher>>;
-
-    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    #[cfg(feature = "legacy-each-diff")]
     trait VecExt {
       fn get_next_closest_mounted_sibling(
         &self,
@@ -21,7 +18,7 @@ cfg_if! {
       ) -> web_sys::Node;
     }
 
-    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    #[cfg(feature = "legacy-each-diff")]
     impl VecExt for Vec<Option<EachItem>> {
       fn get_next_closest_mounted_sibling(
         &self,
@@ -38,8 +35,22 @@ cfg_if! {
     use crate::hydration::HydrationKey;
   }
 }
+use leptos_reactive::{
+  create_rw_signal, DiffedSignal, ListPatch, ReadSignal, RwSignal, Scope,
+  ScopeDisposer, UntrackedGettableSignal,
+};
+use rustc_hash::FxHasher;
 use smallvec::SmallVec;
-use std::{borrow::Cow, cell::RefCell, fmt, hash::Hash, ops::Deref, rc::Rc};
+use std::{
+  borrow::Cow,
+  cell::RefCell,
+  fmt,
+  hash::{BuildHasherDefault, Hash},
+  ops::Deref,
+  rc::Rc,
+};
+
+type FxIndexSet<T> = indexmap::IndexSet<T, BuildHasherDefault<FxHasher>>;
 
 /// The internal representation of the [`EachKey`] core-component.
 #[derive(Clone, PartialEq, Eq)]
@@ -157,7 +168,6 @@ impl Mountable for EachRepr {
 }
 
 /// The internal representation of an [`EachKey`] item.
-#[derive(PartialEq, Eq)]
 pub(crate) struct EachItem {
   #[cfg(all(target_arch = "wasm32", feature = "web"))]
   document_fragment: web_sys::DocumentFragment,
@@ -165,10 +175,30 @@ pub(crate) struct EachItem {
   opening: Comment,
   pub(crate) child: View,
   closing: Comment,
+  /// The item's position, if the view was given a signal with its index.
+  #[cfg_attr(
+    not(all(target_arch = "wasm32", feature = "web")),
+    allow(dead_code)
+  )]
+  index: Option<RwSignal<usize>>,
+  /// Disposes of the item's scope, once the item is removed.
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  disposer: Option<ScopeDisposer>,
   #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
   pub(crate) id: HydrationKey,
 }
 
+impl PartialEq for EachItem {
+  fn eq(&self, other: &Self) -> bool {
+    // the closing marker is created for each item, so it identifies it
+    self.closing == other.closing
+      && self.child == other.child
+      && self.index == other.index
+  }
+}
+
+impl Eq for EachItem {}
+
 impl fmt::Debug for EachItem {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     use fmt::Write;
@@ -184,7 +214,28 @@ impl fmt::Debug for EachItem {
 }
 
 impl EachItem {
-  fn new(child: View) -> Self {
+  /// Creates the item in a child scope of `cx`, with the view and index
+  /// signal that `f` returns, so that they're disposed of when the item is
+  /// removed.
+  fn in_child_scope(
+    cx: Scope,
+    f: impl FnOnce(Scope) -> (View, Option<RwSignal<usize>>),
+  ) -> Self {
+    let ((child, index), disposer) = cx.run_child_scope(f);
+
+    Self::new(child, index, disposer)
+  }
+
+  fn new(
+    child: View,
+    index: Option<RwSignal<usize>>,
+    disposer: ScopeDisposer,
+  ) -> Self {
+    // items are never removed on the server, so the scope lives as long as
+    // its parent
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    let _ = disposer;
+
     let id = HydrationCtx::id();
 
     let markers = (
@@ -220,6 +271,9 @@ impl EachItem {
       opening: markers.1,
       child,
       closing: markers.0,
+      index,
+      #[cfg(all(target_arch = "wasm32", feature = "web"))]
+      disposer: Some(disposer),
       #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
       id,
     }
@@ -265,6 +319,14 @@ impl EachItem {
 
     self.document_fragment.append_with_node_1(end).unwrap();
   }
+
+  /// Disposes of the item's scope, once it has been removed from the DOM.
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  fn dispose(self) {
+    if let Some(disposer) = self.disposer {
+      disposer.dispose();
+    }
+  }
 }
 
 /// A component for efficiently rendering an iterable.
@@ -317,82 +379,299 @@ where
     debug_assertions,
     instrument(level = "trace", name = "<Each />", skip_all)
   )]
-  fn into_view(self, cx: leptos_reactive::Scope) -> crate::View {
+  fn into_view(self, cx: Scope) -> crate::View {
     let Self {
       items_fn,
       each_fn,
       key_fn,
     } = self;
 
-    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
-    let _ = key_fn;
+    render_each(cx, items_fn, key_fn, move |item, _| {
+      EachItem::in_child_scope(cx, |cx| (each_fn(item).into_view(cx), None))
+    })
+  }
+}
 
-    let component = EachRepr::default();
+/// A component for efficiently rendering an iterable, which also passes each
+/// item's index to the view.
+///
+/// The index is a signal, which is updated when the item moves to a different
+/// position without creating its view again.
+pub struct EachEnumerated<IF, I, T, EF, N, KF, K>
+where
+  IF: Fn() -> I + 'static,
+  I: IntoIterator<Item = T>,
+  EF: Fn(ReadSignal<usize>, T) -> N + 'static,
+  N: IntoView,
+  KF: Fn(&T) -> K + 'static,
+  K: Eq + Hash + 'static,
+  T: 'static,
+{
+  pub(crate) items_fn: IF,
+  pub(crate) each_fn: EF,
+  key_fn: KF,
+}
 
-    #[cfg(all(target_arch = "wasm32", feature = "web"))]
-    let (children, closing) =
-      (component.children.clone(), component.closing.node.clone());
+impl<IF, I, T, EF, N, KF, K> EachEnumerated<IF, I, T, EF, N, KF, K>
+where
+  IF: Fn() -> I + 'static,
+  I: IntoIterator<Item = T>,
+  EF: Fn(ReadSignal<usize>, T) -> N + 'static,
+  N: IntoView,
+  KF: Fn(&T) -> K,
+  K: Eq + Hash + 'static,
+  T: 'static,
+{
+  /// Creates a new [`EachEnumerated`] component.
+  pub fn new(items_fn: IF, key_fn: KF, each_fn: EF) -> Self {
+    Self {
+      items_fn,
+      each_fn,
+      key_fn,
+    }
+  }
+}
 
-    cfg_if::cfg_if! {
-      if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-        create_effect(cx, move |prev_hash_run| {
-          let mut children_borrow = children.borrow_mut();
+impl<IF, I, T, EF, N, KF, K> IntoView for EachEnumerated<IF, I, T, EF, N, KF, K>
+where
+  IF: Fn() -> I + 'static,
+  I: IntoIterator<Item = T>,
+  EF: Fn(ReadSignal<usize>, T) -> N + 'static,
+  N: IntoView,
+  KF: Fn(&T) -> K + 'static,
+  K: Eq + Hash + 'static,
+  T: 'static,
+{
+  #[cfg_attr(
+    debug_assertions,
+    instrument(level = "trace", name = "<EachEnumerated />", skip_all)
+  )]
+  fn into_view(self, cx: Scope) -> crate::View {
+    let Self {
+      items_fn,
+      each_fn,
+      key_fn,
+    } = self;
+
+    render_each(cx, items_fn, key_fn, move |item, at| {
+      EachItem::in_child_scope(cx, |cx| {
+        let index = create_rw_signal(cx, at);
+        (each_fn(index.read_only(), item).into_view(cx), Some(index))
+      })
+    })
+  }
+}
+
+/// A component for rendering a list held in a [`DiffedSignal`], which applies
+/// the [`ListPatch`]es from each change to the DOM directly, rather than
+/// diffing the whole list again like [`Each`].
+///
+/// This is meant for very long lists that change a few items at a time, e.g.,
+/// a table with thousands of rows that is updated by deltas from a websocket.
+pub struct EachPatched<T, EF, N>
+where
+  EF: Fn(T) -> N + 'static,
+  N: IntoView,
+  T: Clone + 'static,
+{
+  list: DiffedSignal<Vec<T>, ListPatch<T>>,
+  each_fn: EF,
+}
+
+impl<T, EF, N> EachPatched<T, EF, N>
+where
+  EF: Fn(T) -> N + 'static,
+  N: IntoView,
+  T: Clone + 'static,
+{
+  /// Creates a new [`EachPatched`] component.
+  pub fn new(list: DiffedSignal<Vec<T>, ListPatch<T>>, each_fn: EF) -> Self {
+    Self { list, each_fn }
+  }
+}
+
+impl<T, EF, N> IntoView for EachPatched<T, EF, N>
+where
+  EF: Fn(T) -> N + 'static,
+  N: IntoView,
+  T: Clone + 'static,
+{
+  #[cfg_attr(
+    debug_assertions,
+    instrument(level = "trace", name = "<EachPatched />", skip_all)
+  )]
+  fn into_view(self, cx: Scope) -> crate::View {
+    let Self { list, each_fn } = self;
+    let create_item = move |item: T| {
+      EachItem::in_child_scope(cx, |cx| (each_fn(item).into_view(cx), None))
+    };
+
+    let component = EachRepr::default();
+
+    *component.children.borrow_mut() = list.with_untracked(|items| {
+      items
+        .iter()
+        .cloned()
+        .map(|item| {
+          let each_item = create_item(item);
 
           #[cfg(all(target_arch = "wasm32", feature = "web"))]
-          let opening = if let Some(Some(child)) = children_borrow.get(0) {
-            child.get_opening_node()
-          } else {
-            closing.clone()
-          };
-
-          let items = items_fn();
-
-          let items = items.into_iter().collect::<SmallVec<[_; 128]>>();
-
-          let hashed_items =
-            items.iter().map(&key_fn).collect::<FxIndexSet<_>>();
-
-          if let Some(HashRun(prev_hash_run)) = prev_hash_run {
-            let cmds = diff(&prev_hash_run, &hashed_items);
-
-            apply_cmds(
-              cx,
-              #[cfg(all(target_arch = "wasm32", feature = "web"))]
-              &opening,
-              #[cfg(all(target_arch = "wasm32", feature = "web"))]
-              &closing,
-              cmds,
-              &mut children_borrow,
-              items.into_iter().map(|t| Some(t)).collect(),
-              &each_fn
-            );
-          } else {
-            *children_borrow = Vec::with_capacity(items.len());
-
-            for item in items {
-              let each_item = EachItem::new(each_fn(item).into_view(cx));
-
-              #[cfg(all(target_arch = "wasm32", feature = "web"))]
-              mount_child(MountKind::Before(&closing), &each_item);
-
-              children_borrow.push(Some(each_item));
+          mount_child(MountKind::Before(&component.closing.node), &each_item);
+
+          Some(each_item)
+        })
+        .collect()
+    });
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+      let (children, closing) =
+        (component.children.clone(), component.closing.node.clone());
+      let patches = list.patches();
+
+      create_effect(cx, move |prev: Option<()>| {
+        let patches = patches.get();
+        // the items were rendered from the current value of the list, so the
+        // patches that led to it have already been applied
+        if prev.is_none() {
+          return;
+        }
+
+        let mut children = children.borrow_mut();
+        let node_at = |children: &[Option<EachItem>], at: usize| {
+          children
+            .get(at)
+            .and_then(Option::as_ref)
+            .map(|item| item.get_opening_node())
+            .unwrap_or_else(|| closing.clone())
+        };
+
+        for patch in patches {
+          match patch {
+            ListPatch::Insert { index, value } => {
+              let item = create_item(value);
+              mount_child(MountKind::Before(&node_at(&children, index)), &item);
+              children.insert(index, Some(item));
+            }
+            ListPatch::Remove { index } => {
+              if let Some(item) = children.remove(index) {
+                item.prepare_for_move();
+                item.dispose();
+              }
+            }
+            ListPatch::Move { from, to } => {
+              let item = children.remove(from);
+              if let Some(item) = &item {
+                item.prepare_for_move();
+                mount_child(MountKind::Before(&node_at(&children, to)), item);
+              }
+              children.insert(to, item);
+            }
+            ListPatch::Update { index, value } => {
+              let item = create_item(value);
+              mount_child(MountKind::Before(&node_at(&children, index)), &item);
+              if let Some(old) =
+                std::mem::replace(&mut children[index], Some(item))
+              {
+                old.prepare_for_move();
+                old.dispose();
+              }
             }
           }
-
-          HashRun(hashed_items)
-        });
-      } else {
-        *component.children.borrow_mut() = (items_fn)()
-          .into_iter()
-          .map(|child| Some(EachItem::new((each_fn)(child).into_view(cx))))
-          .collect();
-      }
+        }
+      });
     }
 
     View::CoreComponent(CoreComponent::Each(component))
   }
 }
 
+/// Renders the items, creating each one's [`EachItem`] with `create_item`,
+/// which is given the item and its index.
+fn render_each<IF, I, T, KF, K, CF>(
+  cx: Scope,
+  items_fn: IF,
+  key_fn: KF,
+  create_item: CF,
+) -> View
+where
+  IF: Fn() -> I + 'static,
+  I: IntoIterator<Item = T>,
+  KF: Fn(&T) -> K + 'static,
+  K: Eq + Hash + 'static,
+  T: 'static,
+  CF: Fn(T, usize) -> EachItem + 'static,
+{
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  let _ = key_fn;
+
+  let component = EachRepr::default();
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  let (children, closing) =
+    (component.children.clone(), component.closing.node.clone());
+
+  cfg_if::cfg_if! {
+    if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+      create_effect(cx, move |prev_hash_run| {
+        let mut children_borrow = children.borrow_mut();
+
+        #[cfg(all(target_arch = "wasm32", feature = "web"))]
+        let opening = if let Some(Some(child)) = children_borrow.get(0) {
+          child.get_opening_node()
+        } else {
+          closing.clone()
+        };
+
+        let items = items_fn();
+
+        let items = items.into_iter().collect::<SmallVec<[_; 128]>>();
+
+        let hashed_items =
+          items.iter().map(&key_fn).collect::<FxIndexSet<_>>();
+
+        if let Some(HashRun(prev_hash_run)) = prev_hash_run {
+          let cmds = diff(&prev_hash_run, &hashed_items);
+
+          apply_cmds(
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
+            &opening,
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
+            &closing,
+            cmds,
+            &mut children_borrow,
+            items.into_iter().map(|t| Some(t)).collect(),
+            &create_item
+          );
+        } else {
+          *children_borrow = Vec::with_capacity(items.len());
+
+          for (at, item) in items.into_iter().enumerate() {
+            let each_item = create_item(item, at);
+
+            #[cfg(all(target_arch = "wasm32", feature = "web"))]
+            mount_child(MountKind::Before(&closing), &each_item);
+
+            children_borrow.push(Some(each_item));
+          }
+        }
+
+        HashRun(hashed_items)
+      });
+    } else {
+      _ = cx;
+
+      *component.children.borrow_mut() = (items_fn)()
+        .into_iter()
+        .enumerate()
+        .map(|(at, child)| Some(create_item(child, at)))
+        .collect();
+    }
+  }
+
+  View::CoreComponent(CoreComponent::Each(component))
+}
+
 #[derive(educe::Educe)]
 #[educe(Debug)]
 struct HashRun<T>(#[educe(Debug(ignore))] T);
@@ -410,26 +689,123 @@ fn diff<K: Eq + Hash>(from: &FxIndexSet<K>, to: &FxIndexSet<K>) -> Diff {
   }
 
   // Get removed items
-  let mut removed = from.difference(to);
-
-  let removed_cmds = removed
-    .clone()
+  let removed_cmds = from
+    .difference(to)
     .map(|k| from.get_full(k).unwrap().0)
     .map(|idx| DiffOpRemove { at: idx });
 
   // Get added items
-  let mut added = to.difference(from);
-
   let added_cmds =
-    added
-      .clone()
+    to.difference(from)
       .map(|k| to.get_full(k).unwrap().0)
       .map(|idx| DiffOpAdd {
         at: idx,
         mode: Default::default(),
       });
 
-  // Get moved items
+  let mut diffs = Diff {
+    removed: removed_cmds.collect(),
+    moved: if cfg!(feature = "legacy-each-diff") {
+      legacy_diff_moves(from, to)
+    } else {
+      diff_moves(from, to)
+    },
+    added: added_cmds.collect(),
+    clear: false,
+  };
+
+  apply_opts(from, to, &mut diffs);
+
+  diffs
+}
+
+/// Finds the items that are in both `from` and `to`, but at a different
+/// index.
+///
+/// The items whose old indices form the longest increasing subsequence
+/// of the new order are already in the right order relative to each
+/// other, so they stay where they are in the DOM, and only the other
+/// items are moved around them. This is the least number of DOM moves
+/// that gets from one order to the other.
+fn diff_moves<K: Eq + Hash>(
+  from: &FxIndexSet<K>,
+  to: &FxIndexSet<K>,
+) -> SmallVec<[DiffOpMove; 8]> {
+  // the old and new index of each item that was kept, in the new order
+  let kept = to
+    .iter()
+    .enumerate()
+    .filter_map(|(to_idx, k)| Some((from.get_index_of(k)?, to_idx)))
+    .collect::<Vec<_>>();
+  let old_indices = kept.iter().map(|(from, _)| *from).collect::<Vec<_>>();
+  let mut stable = longest_increasing_subsequence(&old_indices)
+    .into_iter()
+    .peekable();
+
+  kept
+    .into_iter()
+    .enumerate()
+    .filter_map(|(position, (from, to))| {
+      let is_stable = stable.next_if_eq(&position).is_some();
+      if !is_stable || from != to {
+        Some(DiffOpMove {
+          from,
+          to,
+          move_in_dom: !is_stable,
+        })
+      } else {
+        None
+      }
+    })
+    .collect()
+}
+
+/// Returns the positions in `seq` of one of its longest strictly
+/// increasing subsequences, in order.
+fn longest_increasing_subsequence(seq: &[usize]) -> Vec<usize> {
+  // `tails[len]` is the position of the smallest value that ends an
+  // increasing subsequence of length `len + 1` found so far
+  let mut tails = Vec::<usize>::new();
+  // the position before each one in the subsequence it ends
+  let mut prev = vec![None; seq.len()];
+
+  for (position, value) in seq.iter().enumerate() {
+    let len = tails.partition_point(|tail| seq[*tail] < *value);
+    if len > 0 {
+      prev[position] = Some(tails[len - 1]);
+    }
+    if len == tails.len() {
+      tails.push(position);
+    } else {
+      tails[len] = position;
+    }
+  }
+
+  let mut lis = Vec::with_capacity(tails.len());
+  let mut next = tails.last().copied();
+  while let Some(position) = next {
+    lis.push(position);
+    next = prev[position];
+  }
+  lis.reverse();
+  lis
+}
+
+/// Finds the items that are in both `from` and `to`, but at a different
+/// index, by comparing each item's old index to its new index adjusted
+/// for the items added and removed before it.
+///
+/// This is the original algorithm, which is used instead with the
+/// `legacy-each-diff` feature. It moves more items than needed when items
+/// are reordered: e.g., moving the first item to the end moves every
+/// other item too.
+fn legacy_diff_moves<K: Eq + Hash>(
+  from: &FxIndexSet<K>,
+  to: &FxIndexSet<K>,
+) -> SmallVec<[DiffOpMove; 8]> {
+  let mut removed = from.difference(to);
+  let mut added = to.difference(from);
+
   let mut normalized_idx = 0;
   let mut move_cmds = SmallVec::<[_; 8]>::with_capacity(to.len());
   let mut added_idx = added.next().map(|k| to.get_full(k).unwrap().0);
@@ -474,16 +850,27 @@ fn diff<K: Eq + Hash>(from: &FxIndexSet<K>, to: &FxIndexSet<K>) -> Diff {
     normalized_idx = normalized_idx.wrapping_add(1);
   }
 
-  let mut diffs = Diff {
-    removed: removed_cmds.collect(),
-    moved: move_cmds,
-    added: added_cmds.collect(),
-    clear: false,
-  };
+  move_cmds
+}
 
-  apply_opts(from, to, &mut diffs);
+/// Returns how many items `<Each/>` moves in the DOM to get from the keys
+/// `from` to the keys `to`, with its diffing algorithm and with the one of
+/// the `legacy-each-diff` feature, in that order.
+#[doc(hidden)]
+pub fn each_dom_moves<K: Eq + Hash>(
+  from: impl IntoIterator<Item = K>,
+  to: impl IntoIterator<Item = K>,
+) -> (usize, usize) {
+  let from = from.into_iter().collect::<FxIndexSet<_>>();
+  let to = to.into_iter().collect::<FxIndexSet<_>>();
+  let dom_moves = |moves: SmallVec<[DiffOpMove; 8]>| {
+    moves.iter().filter(|op| op.move_in_dom).count()
+  };
 
-  diffs
+  (
+    dom_moves(diff_moves(&from, &to)),
+    dom_moves(legacy_diff_moves(&from, &to)),
+  )
 }
 
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
@@ -566,18 +953,137 @@ impl Default for DiffOpAddMode {
   }
 }
 
-#[cfg(all(target_arch = "wasm32", feature = "web"))]
-fn apply_cmds<T, EF, N>(
-  cx: leptos_reactive::Scope,
+/// Applies the diff to the DOM and to `children`.
+///
+/// The items are placed from last to first, each one before the item
+/// that follows it, so that added items and items marked to be moved in
+/// the DOM end up in the right place, while the others aren't touched.
+#[cfg(all(
+  target_arch = "wasm32",
+  feature = "web",
+  not(feature = "legacy-each-diff")
+))]
+fn apply_cmds<T, CF>(
+  opening: &web_sys::Node,
+  closing: &web_sys::Node,
+  cmds: Diff,
+  children: &mut Vec<Option<EachItem>>,
+  mut items: SmallVec<[Option<T>; 128]>,
+  create_item: &CF,
+) where
+  CF: Fn(T, usize) -> EachItem,
+{
+  let range = RANGE.with(|range| (*range).clone());
+
+  if cmds.clear {
+    if opening.previous_sibling().is_none() && closing.next_sibling().is_none()
+    {
+      let parent = closing
+        .parent_node()
+        .expect("could not get closing node")
+        .unchecked_into::<web_sys::Element>();
+      parent.set_text_content(Some(""));
+
+      #[cfg(debug_assertions)]
+      parent.append_with_node_2(opening, closing).unwrap();
+
+      #[cfg(not(debug_assertions))]
+      parent.append_with_node_1(closing).unwrap();
+    } else {
+      range.set_start_before(opening).unwrap();
+      range.set_end_before(closing).unwrap();
+
+      range.delete_contents().unwrap();
+    }
+
+    // their nodes were already removed with the range
+    for item in children.drain(..).flatten() {
+      item.dispose();
+    }
+  } else {
+    for DiffOpRemove { at } in cmds.removed {
+      if let Some(item) = children[at].take() {
+        item.prepare_for_move();
+        item.dispose();
+      }
+    }
+  }
+
+  let mut new_children = Vec::with_capacity(items.len());
+  new_children.resize_with(items.len(), || None);
+  let mut needs_mount = vec![false; items.len()];
+
+  for DiffOpMove {
+    from,
+    to,
+    move_in_dom,
+  } in cmds.moved
+  {
+    new_children[to] = children[from].take();
+    needs_mount[to] = move_in_dom;
+  }
+
+  // the items that weren't removed or moved keep their index
+  for (at, item) in children.drain(..).enumerate() {
+    if item.is_some() {
+      new_children[at] = item;
+    }
+  }
+
+  // the rest were added; they're created in order
+  for (at, slot) in new_children.iter_mut().enumerate() {
+    if slot.is_none() {
+      let item = items[at].take().unwrap();
+      *slot = Some(create_item(item, at));
+      needs_mount[at] = true;
+    }
+  }
+
+  let mut next = closing.to_owned();
+
+  for (each_item, needs_mount) in
+    new_children.iter().rev().zip(needs_mount.into_iter().rev())
+  {
+    let each_item = each_item.as_ref().unwrap();
+
+    if needs_mount {
+      each_item.prepare_for_move();
+
+      mount_child(MountKind::Before(&next), each_item);
+    }
+
+    next = each_item.get_opening_node();
+  }
+
+  *children = new_children;
+
+  // Items that were moved, or that were shifted by items being added or
+  // removed before them, are told their new index without being re-rendered
+  for (at, index) in children
+    .iter()
+    .enumerate()
+    .filter_map(|(at, c)| Some((at, c.as_ref()?.index?)))
+  {
+    if index.get_untracked() != at {
+      index.set(at);
+    }
+  }
+}
+
+#[cfg(all(
+  target_arch = "wasm32",
+  feature = "web",
+  feature = "legacy-each-diff"
+))]
+fn apply_cmds<T, CF>(
   opening: &web_sys::Node,
   closing: &web_sys::Node,
   mut cmds: Diff,
   children: &mut Vec<Option<EachItem>>,
   mut items: SmallVec<[Option<T>; 128]>,
-  each_fn: &EF,
+  create_item: &CF,
 ) where
-  EF: Fn(T) -> N,
-  N: IntoView,
+  CF: Fn(T, usize) -> EachItem,
 {
   let range = RANGE.with(|range| (*range).clone());
 
@@ -623,10 +1129,18 @@ fn apply_cmds<T, EF, N>(
     }
   }
 
+  if cmds.clear {
+    // their nodes were already removed with the range
+    for item in children.iter_mut().filter_map(Option::take) {
+      item.dispose();
+    }
+  }
+
   for DiffOpRemove { at } in cmds.removed {
     let item_to_remove = std::mem::take(&mut children[at]).unwrap();
 
     item_to_remove.prepare_for_move();
+    item_to_remove.dispose();
   }
 
   for DiffOpMove {
@@ -647,9 +1161,7 @@ fn apply_cmds<T, EF, N>(
   for DiffOpAdd { at, mode } in cmds.added {
     let item = items[at].take().unwrap();
 
-    let child = each_fn(item).into_view(cx);
-
-    let each_item = EachItem::new(child);
+    let each_item = create_item(item, at);
 
     match mode {
       DiffOpAddMode::Normal => {
@@ -682,4 +1194,16 @@ fn apply_cmds<T, EF, N>(
   // items
   #[allow(unstable_name_collisions)]
   children.drain_filter(|c| c.is_none());
+
+  // Items that were moved, or that were shifted by items being added or
+  // removed before them, are told their new index without being re-rendered
+  for (at, index) in children
+    .iter()
+    .enumerate()
+    .filter_map(|(at, c)| Some((at, c.as_ref()?.index?)))
+  {
+    if index.get_untracked() != at {
+      index.set(at);
+    }
+  }
 }
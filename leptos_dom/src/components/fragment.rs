@@ -34,6 +34,12 @@ impl FromIterator<View> for Fragment {
   }
 }
 
+impl Extend<View> for Fragment {
+  fn extend<T: IntoIterator<Item = View>>(&mut self, iter: T) {
+    self.nodes.extend(iter);
+  }
+}
+
 impl From<View> for Fragment {
   fn from(view: View) -> Self {
     Fragment::new(vec![view])
@@ -51,6 +57,25 @@ impl Fragment {
     Self::new_with_id(HydrationCtx::id(), nodes())
   }
 
+  /// Creates a new [`Fragment`] from a collection of anything that can be
+  /// converted into a [`View`]. This is the same as
+  /// [`into_fragment`](IntoFragment::into_fragment), for use where an
+  /// associated function reads better.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let items = Fragment::from_views(cx, (1..=3).map(|n| li(cx).child(n)));
+  /// assert_eq!(items.nodes.len(), 3);
+  /// # });
+  /// ```
+  pub fn from_views<V: IntoView>(
+    cx: Scope,
+    views: impl IntoIterator<Item = V>,
+  ) -> Self {
+    views.into_fragment(cx)
+  }
+
   /// Creates a new [`Fragment`] with the given hydration ID from a [`Vec<Node>`].
   pub fn new_with_id(id: HydrationKey, nodes: Vec<View>) -> Self {
     Self { id, nodes }
@@ -0,0 +1,348 @@
+#![cfg(not(all(target_arch = "wasm32", feature = "web")))]
+
+//! Critical CSS extraction for server-side rendering.
+
+use leptos_reactive::{provide_context, use_context, Scope};
+use std::{cell::RefCell, collections::HashSet, rc::Rc};
+
+thread_local! {
+  /// The collector of the view that is being rendered to a string on this
+  /// thread, if its app asked for one.
+  static COLLECTOR: RefCell<Option<SelectorCollector>> = RefCell::new(None);
+}
+
+/// Provided by [collect_rendered_selectors], so that each request collects
+/// its own selectors, even when several are rendered on the same thread.
+#[derive(Clone, Debug)]
+struct SelectorCollector(Rc<RefCell<Option<RenderedSelectors>>>);
+
+/// The tag names, classes, and ids of every element rendered to HTML in a
+/// scope since [collect_rendered_selectors] was called for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenderedSelectors {
+  /// Lowercased tag names.
+  pub tags: HashSet<String>,
+  /// Class names, from `class` attributes.
+  pub classes: HashSet<String>,
+  /// Element ids, from `id` attributes.
+  pub ids: HashSet<String>,
+}
+
+impl Default for RenderedSelectors {
+  fn default() -> Self {
+    // the document shell is rendered by the integration, not the app,
+    // but its elements are always on the page
+    Self {
+      tags: ["html", "head", "body"].map(String::from).into(),
+      classes: HashSet::new(),
+      ids: HashSet::new(),
+    }
+  }
+}
+
+impl RenderedSelectors {
+  /// Records an element that is being rendered.
+  pub fn record<'a>(
+    &mut self,
+    tag_name: &str,
+    attrs: impl IntoIterator<Item = (&'a str, &'a str)>,
+  ) {
+    self.tags.insert(tag_name.to_ascii_lowercase());
+    for (name, value) in attrs {
+      match name {
+        "class" => self
+          .classes
+          .extend(value.split_whitespace().map(String::from)),
+        "id" => {
+          self.ids.insert(value.to_string());
+        }
+        _ => {}
+      }
+    }
+  }
+
+  /// Whether an element matching this (single, not comma-separated) selector
+  /// may have been rendered. Pseudo-classes and attribute selectors are
+  /// ignored, so this errs on the side of keeping a rule.
+  pub fn may_match(&self, selector: &str) -> bool {
+    let mut chars = selector.chars().peekable();
+    let mut at_compound_start = true;
+
+    while let Some(c) = chars.next() {
+      match c {
+        '.' => {
+          if !self.classes.contains(&read_ident(&mut chars)) {
+            return false;
+          }
+          at_compound_start = false;
+        }
+        '#' => {
+          if !self.ids.contains(&read_ident(&mut chars)) {
+            return false;
+          }
+          at_compound_start = false;
+        }
+        ':' => {
+          if chars.peek() == Some(&':') {
+            chars.next();
+          }
+          read_ident(&mut chars);
+          if chars.peek() == Some(&'(') {
+            skip_balanced(&mut chars, '(', ')');
+          }
+          at_compound_start = false;
+        }
+        '[' => {
+          skip_balanced_from_open(&mut chars, '[', ']');
+          at_compound_start = false;
+        }
+        '*' => at_compound_start = false,
+        c if at_compound_start && (c.is_alphabetic() || c == '_') => {
+          let tag = std::iter::once(c)
+            .chain(std::iter::from_fn(|| {
+              chars.next_if(|c| c.is_alphanumeric() || *c == '-' || *c == '_')
+            }))
+            .collect::<String>()
+            .to_ascii_lowercase();
+          if !self.tags.contains(&tag) {
+            return false;
+          }
+          at_compound_start = false;
+        }
+        // whitespace and combinators begin a new compound selector
+        _ => at_compound_start = true,
+      }
+    }
+
+    true
+  }
+}
+
+/// Starts recording the selectors of the elements rendered in `cx` and its
+/// children, usually the root scope of a request. Any selectors that were
+/// being collected in `cx` are discarded.
+///
+/// Only what is rendered before [take_rendered_selectors] is called is
+/// recorded. When streaming, that's the shell: the contents of a
+/// `<Suspense/>` that are sent later aren't included, so their rules are
+/// only applied once the full stylesheet has loaded.
+pub fn collect_rendered_selectors(cx: Scope) {
+  provide_context(
+    cx,
+    SelectorCollector(Rc::new(RefCell::new(
+      Some(RenderedSelectors::default()),
+    ))),
+  );
+}
+
+/// Stops recording and returns the selectors collected in `cx` since
+/// [collect_rendered_selectors] was called for it, if any.
+pub fn take_rendered_selectors(cx: Scope) -> Option<RenderedSelectors> {
+  use_context::<SelectorCollector>(cx)?.0.borrow_mut().take()
+}
+
+/// Records the elements rendered by `f` with the collector provided to `cx`,
+/// if there is one.
+pub(crate) fn with_selector_collector<T>(
+  cx: Scope,
+  f: impl FnOnce() -> T,
+) -> T {
+  struct Restore(Option<SelectorCollector>);
+
+  impl Drop for Restore {
+    fn drop(&mut self) {
+      COLLECTOR.with(|c| *c.borrow_mut() = self.0.take());
+    }
+  }
+
+  let collector = use_context::<SelectorCollector>(cx);
+  // restored even if rendering panics, so a later render isn't recorded here
+  let _restore = Restore(COLLECTOR.with(|c| c.replace(collector)));
+  f()
+}
+
+pub(crate) fn record_element<'a>(
+  tag_name: &str,
+  attrs: impl IntoIterator<Item = (&'a str, &'a str)>,
+) {
+  COLLECTOR.with(|c| {
+    if let Some(SelectorCollector(selectors)) = &*c.borrow() {
+      if let Some(selectors) = &mut *selectors.borrow_mut() {
+        selectors.record(tag_name, attrs);
+      }
+    }
+  })
+}
+
+/// Returns only the rules of `stylesheet` that may apply to the elements in
+/// `selectors`, so they can be inlined in a `<style>` tag in the `<head>`
+/// while the full stylesheet loads in the background.
+///
+/// Grouping rules like `@media` are kept if any rule inside them is kept.
+/// Other at-rules (`@font-face`, `@keyframes`, `@import`, etc.) are always kept.
+///
+/// ```
+/// # use leptos_dom::*;
+/// let mut selectors = RenderedSelectors::default();
+/// selectors.record("p", [("class", "intro big")]);
+///
+/// let css = ".intro { color: red; } .footer { color: blue; } \
+///            @media (min-width: 600px) { p.big { margin: 0; } #nav { top: 0; } }";
+/// assert_eq!(
+///   extract_critical_css(css, &selectors),
+///   ".intro{color: red;}@media (min-width: 600px){p.big{margin: 0;}}"
+/// );
+/// ```
+pub fn extract_critical_css(
+  stylesheet: &str,
+  selectors: &RenderedSelectors,
+) -> String {
+  let mut out = String::new();
+  filter_rules(&strip_comments(stylesheet), selectors, &mut out);
+  out
+}
+
+fn filter_rules(css: &str, selectors: &RenderedSelectors, out: &mut String) {
+  let mut rest = css;
+  while let Some((prelude, block, remaining)) = next_rule(rest) {
+    rest = remaining;
+    let prelude = prelude.trim();
+
+    match block {
+      // statements like `@import url(...);`
+      None => {
+        if prelude.starts_with('@') {
+          out.push_str(prelude);
+          out.push(';');
+        }
+      }
+      Some(block) => {
+        if let Some(at_rule) = prelude.strip_prefix('@') {
+          let name = at_rule
+            .split(|c: char| c.is_whitespace() || c == '(')
+            .next()
+            .unwrap_or_default();
+          if matches!(name, "media" | "supports" | "layer" | "container") {
+            let mut inner = String::new();
+            filter_rules(block, selectors, &mut inner);
+            if !inner.is_empty() {
+              out.push_str(prelude);
+              out.push('{');
+              out.push_str(&inner);
+              out.push('}');
+            }
+          } else {
+            out.push_str(prelude);
+            out.push('{');
+            out.push_str(block.trim());
+            out.push('}');
+          }
+        } else if prelude.split(',').any(|s| selectors.may_match(s.trim())) {
+          out.push_str(prelude);
+          out.push('{');
+          out.push_str(block.trim());
+          out.push('}');
+        }
+      }
+    }
+  }
+}
+
+/// Splits the next rule off of `css`, returning its prelude, its block (if
+/// it has one), and the remaining CSS.
+fn next_rule(css: &str) -> Option<(&str, Option<&str>, &str)> {
+  let mut depth = 0;
+  let mut open = 0;
+  let mut quote = None;
+
+  for (idx, c) in css.char_indices() {
+    match (quote, c) {
+      (Some(q), c) if c == q => quote = None,
+      (Some(_), _) => {}
+      (None, '"' | '\'') => quote = Some(c),
+      (None, ';') if depth == 0 => {
+        return Some((&css[..idx], None, &css[idx + 1..]))
+      }
+      (None, '{') => {
+        if depth == 0 {
+          open = idx;
+        }
+        depth += 1;
+      }
+      (None, '}') if depth > 0 => {
+        depth -= 1;
+        if depth == 0 {
+          return Some((
+            &css[..open],
+            Some(&css[open + 1..idx]),
+            &css[idx + 1..],
+          ));
+        }
+      }
+      _ => {}
+    }
+  }
+
+  None
+}
+
+fn strip_comments(css: &str) -> String {
+  let mut out = String::with_capacity(css.len());
+  let mut rest = css;
+  while let Some(start) = rest.find("/*") {
+    out.push_str(&rest[..start]);
+    rest = match rest[start + 2..].find("*/") {
+      Some(end) => &rest[start + 2 + end + 2..],
+      None => "",
+    };
+  }
+  out.push_str(rest);
+  out
+}
+
+fn read_ident(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+  let mut ident = String::new();
+  while let Some(&c) = chars.peek() {
+    if c == '\\' {
+      // escaped characters, like the `:` in Tailwind's `.md\:flex`
+      chars.next();
+      if let Some(c) = chars.next() {
+        ident.push(c);
+      }
+    } else if c.is_alphanumeric() || c == '-' || c == '_' || !c.is_ascii() {
+      ident.push(c);
+      chars.next();
+    } else {
+      break;
+    }
+  }
+  ident
+}
+
+fn skip_balanced(
+  chars: &mut std::iter::Peekable<std::str::Chars>,
+  open: char,
+  close: char,
+) {
+  if chars.next_if_eq(&open).is_some() {
+    skip_balanced_from_open(chars, open, close);
+  }
+}
+
+fn skip_balanced_from_open(
+  chars: &mut std::iter::Peekable<std::str::Chars>,
+  open: char,
+  close: char,
+) {
+  let mut depth = 1;
+  for c in chars.by_ref() {
+    if c == open {
+      depth += 1;
+    } else if c == close {
+      depth -= 1;
+      if depth == 0 {
+        break;
+      }
+    }
+  }
+}
@@ -0,0 +1,122 @@
+//! Signals that trail behind their source until the browser is idle.
+
+use leptos_reactive::{Scope, Signal};
+
+/// Returns a signal that follows the value of `source`, but only catches up
+/// with it once the browser is idle, like React's `useDeferredValue`.
+///
+/// Rendering an expensive part of the view from the deferred value, rather
+/// than from `source` itself, keeps the rest of the page responsive: e.g., an
+/// `<input>` that filters a long list can update on every keystroke, while the
+/// list is only filtered again once the browser has time for it. Several
+/// changes to `source` in quick succession cause only one update.
+///
+/// The update waits for
+/// [`requestIdleCallback`](https://developer.mozilla.org/en-US/docs/Web/API/window/requestIdleCallback),
+/// or for the next animation frame in browsers that don't support it. During
+/// server rendering, the deferred signal always has the same value as
+/// `source`.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (query, set_query) = create_signal(cx, String::new());
+/// let deferred_query = create_deferred_signal(cx, move || query.get());
+///
+/// let matches = move || {
+///   deferred_query.with(|query| {
+///     (0..10_000)
+///       .map(|n| n.to_string())
+///       .filter(|n| n.contains(query.as_str()))
+///       .count()
+///   })
+/// };
+/// # });
+/// ```
+pub fn create_deferred_signal<T>(
+  cx: Scope,
+  source: impl Fn() -> T + 'static,
+) -> Signal<T>
+where
+  T: Clone + 'static,
+{
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  {
+    web::create_deferred_signal(cx, source)
+  }
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  {
+    Signal::derive(cx, source)
+  }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod web {
+  use leptos_reactive::{
+    create_effect, create_rw_signal, on_cleanup, Scope, Signal,
+  };
+  use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+  };
+  use wasm_bindgen::JsValue;
+
+  pub(super) fn create_deferred_signal<T>(
+    cx: Scope,
+    source: impl Fn() -> T + 'static,
+  ) -> Signal<T>
+  where
+    T: Clone + 'static,
+  {
+    let deferred = create_rw_signal(cx, source());
+    let latest = Rc::new(RefCell::new(None));
+    let scheduled = Rc::new(Cell::new(false));
+    let disposed = Rc::new(Cell::new(false));
+
+    on_cleanup(cx, {
+      let disposed = Rc::clone(&disposed);
+      move || disposed.set(true)
+    });
+
+    create_effect(cx, move |prev: Option<()>| {
+      let value = source();
+      // the deferred signal was created with the first value
+      if prev.is_none() {
+        return;
+      }
+      *latest.borrow_mut() = Some(value);
+
+      if !scheduled.replace(true) {
+        let latest = Rc::clone(&latest);
+        let scheduled = Rc::clone(&scheduled);
+        let disposed = Rc::clone(&disposed);
+        when_idle(move || {
+          scheduled.set(false);
+          let value = latest.borrow_mut().take();
+          if let Some(value) = value {
+            if !disposed.get() {
+              deferred.set(value);
+            }
+          }
+        });
+      }
+    });
+
+    deferred.into()
+  }
+
+  fn when_idle(cb: impl Fn() + 'static) {
+    let supported = js_sys::Reflect::has(
+      &crate::window(),
+      &JsValue::from_str("requestIdleCallback"),
+    )
+    .unwrap_or(false);
+
+    if supported {
+      crate::request_idle_callback(cb);
+    } else {
+      crate::request_animation_frame(cb);
+    }
+  }
+}
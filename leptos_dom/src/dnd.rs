@@ -0,0 +1,361 @@
+//! Drag-and-drop helpers with typed payloads.
+//!
+//! [draggable] makes an element draggable, carrying a payload of any type that
+//! can be serialized, and [drop_zone] returns signals for a payload being
+//! dragged over an element and dropped on it. Payloads are passed through the
+//! [`DataTransfer`](https://developer.mozilla.org/en-US/docs/Web/API/DataTransfer)
+//! as JSON, so only drops from a [draggable] with a payload of the same type
+//! are accepted.
+//!
+//! Dragging with a mouse or touch isn't available to keyboard users, so both
+//! elements can also be used from the keyboard: pressing `Enter` or `Space` on
+//! a focused draggable picks up its payload, and pressing `Enter` or `Space`
+//! on a focused drop zone drops it there. `Escape` cancels. Both elements are
+//! made focusable if they aren't already.
+//!
+//! Nothing can be dragged during server rendering, so the signals keep their
+//! initial values.
+
+use crate::NodeRef;
+use leptos_reactive::{create_rw_signal, RwSignal, Scope, Signal};
+use serde::{de::DeserializeOwned, Serialize};
+use std::ops::Deref;
+
+// only used in the browser
+#[cfg_attr(not(all(target_arch = "wasm32", feature = "web")), allow(dead_code))]
+const PAYLOAD_MIME_TYPE: &str = "application/x-leptos-dnd+json";
+
+/// Makes the element in the `node_ref` draggable, carrying the given payload to
+/// any [drop_zone] that accepts payloads of the same type.
+///
+/// Returns a signal that is `true` while the element is being dragged, or while
+/// its payload has been picked up with the keyboard.
+///
+/// ```
+/// # use leptos::*;
+/// use leptos::dnd::{draggable, drop_zone};
+///
+/// #[component]
+/// fn Board(cx: Scope) -> impl IntoView {
+///   let card = NodeRef::<HtmlElement<Div>>::new(cx);
+///   let done = NodeRef::<HtmlElement<Div>>::new(cx);
+///
+///   // the payload can be any type that implements `Serialize`
+///   let is_dragging = draggable(cx, card, 1_usize);
+///   let done_zone = drop_zone::<_, usize>(cx, done);
+///
+///   create_effect(cx, move |_| {
+///     if let Some(id) = done_zone.dropped().get() {
+///       log!("moved card {id} to done");
+///     }
+///   });
+///
+///   view! { cx,
+///     <div _ref=card class:dragging=is_dragging>"Card 1"</div>
+///     <div _ref=done class:over=move || done_zone.is_over().get()>"Done"</div>
+///   }
+/// }
+/// ```
+pub fn draggable<T, P>(
+  cx: Scope,
+  node_ref: NodeRef<T>,
+  payload: P,
+) -> Signal<bool>
+where
+  T: Clone + Deref + 'static,
+  T::Target: AsRef<web_sys::Element>,
+  P: Serialize + 'static,
+{
+  let is_dragging = create_rw_signal(cx, false);
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  match serde_json::to_string(&payload) {
+    Ok(payload) => web::draggable(cx, node_ref, payload, is_dragging),
+    Err(e) => crate::error!("[draggable] could not serialize payload: {e}"),
+  }
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  _ = (node_ref, payload);
+
+  is_dragging.into()
+}
+
+/// Makes the element in the `node_ref` a target for payloads of type `P`
+/// dragged from a [draggable].
+///
+/// The drop is only accepted, and the browser's default handling (like opening
+/// a dropped link) only prevented, if the payload can be deserialized as a `P`.
+pub fn drop_zone<T, P>(cx: Scope, node_ref: NodeRef<T>) -> DropZone<P>
+where
+  T: Clone + Deref + 'static,
+  T::Target: AsRef<web_sys::Element>,
+  P: DeserializeOwned + 'static,
+{
+  let zone = DropZone {
+    is_over: create_rw_signal(cx, false),
+    dropped: create_rw_signal(cx, None),
+  };
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  web::drop_zone(cx, node_ref, zone);
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  _ = node_ref;
+
+  zone
+}
+
+/// The state of a [drop_zone].
+pub struct DropZone<P: 'static> {
+  is_over: RwSignal<bool>,
+  dropped: RwSignal<Option<P>>,
+}
+
+impl<P> Clone for DropZone<P> {
+  fn clone(&self) -> Self {
+    *self
+  }
+}
+
+impl<P> Copy for DropZone<P> {}
+
+impl<P> std::fmt::Debug for DropZone<P> {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("DropZone")
+      .field("is_over", &self.is_over)
+      .field("dropped", &self.dropped)
+      .finish()
+  }
+}
+
+impl<P> DropZone<P> {
+  /// Whether an acceptable payload is currently being dragged over the zone, or
+  /// the zone has keyboard focus while a payload has been picked up.
+  pub fn is_over(&self) -> Signal<bool> {
+    self.is_over.into()
+  }
+
+  /// The payload most recently dropped on the zone. This notifies subscribers
+  /// on every drop, even if the payload is equal to the previous one.
+  pub fn dropped(&self) -> Signal<Option<P>>
+  where
+    P: Clone,
+  {
+    self.dropped.into()
+  }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod web {
+  use super::{DropZone, PAYLOAD_MIME_TYPE};
+  use crate::{utilities::web::with_element, NodeRef};
+  use leptos_reactive::{on_cleanup, RwSignal, Scope};
+  use serde::de::DeserializeOwned;
+  use std::{
+    cell::{Cell, RefCell},
+    ops::Deref,
+    rc::Rc,
+  };
+  use wasm_bindgen::{prelude::Closure, JsCast};
+
+  thread_local! {
+    /// The payload that has been picked up with the keyboard, and the signal
+    /// that marks its draggable as being dragged.
+    static KEYBOARD_PAYLOAD: RefCell<Option<(String, RwSignal<bool>)>> =
+      RefCell::new(None);
+  }
+
+  pub(super) fn draggable<T>(
+    cx: Scope,
+    node_ref: NodeRef<T>,
+    payload: String,
+    is_dragging: RwSignal<bool>,
+  ) where
+    T: Clone + Deref + 'static,
+    T::Target: AsRef<web_sys::Element>,
+  {
+    let payload = Rc::new(payload);
+    with_element(cx, node_ref, move |cx, el| {
+      _ = el.set_attribute("draggable", "true");
+      make_focusable(&el);
+
+      listen(cx, &el, "dragstart", {
+        let payload = payload.clone();
+        move |ev: web_sys::DragEvent| {
+          if let Some(data) = ev.data_transfer() {
+            _ = data.set_data(PAYLOAD_MIME_TYPE, &payload);
+            data.set_effect_allowed("all");
+            is_dragging.set(true);
+          }
+        }
+      });
+      listen(cx, &el, "dragend", move |_: web_sys::DragEvent| {
+        is_dragging.set(false)
+      });
+
+      listen(cx, &el, "keydown", {
+        let payload = payload.clone();
+        move |ev: web_sys::KeyboardEvent| match ev.key().as_str() {
+          "Enter" | " " => {
+            ev.prevent_default();
+            cancel_keyboard_drag();
+            KEYBOARD_PAYLOAD.with(|current| {
+              *current.borrow_mut() = Some(((*payload).clone(), is_dragging))
+            });
+            is_dragging.set(true);
+          }
+          "Escape" => cancel_keyboard_drag(),
+          _ => {}
+        }
+      });
+    });
+    on_cleanup(cx, move || {
+      let is_current = KEYBOARD_PAYLOAD.with(|current| {
+        matches!(&*current.borrow(), Some((_, signal)) if *signal == is_dragging)
+      });
+      if is_current {
+        KEYBOARD_PAYLOAD.with(|current| current.borrow_mut().take());
+      }
+    });
+  }
+
+  pub(super) fn drop_zone<T, P>(
+    cx: Scope,
+    node_ref: NodeRef<T>,
+    zone: DropZone<P>,
+  ) where
+    T: Clone + Deref + 'static,
+    T::Target: AsRef<web_sys::Element>,
+    P: DeserializeOwned + 'static,
+  {
+    let DropZone { is_over, dropped } = zone;
+
+    with_element(cx, node_ref, move |cx, el| {
+      make_focusable(&el);
+
+      // dragenter and dragleave also fire when moving between child elements
+      let depth = Rc::new(Cell::new(0_u32));
+
+      listen(cx, &el, "dragenter", {
+        let depth = depth.clone();
+        move |ev: web_sys::DragEvent| {
+          if carries_payload(&ev) {
+            ev.prevent_default();
+            depth.set(depth.get() + 1);
+            is_over.set(true);
+          }
+        }
+      });
+      // the default action of `dragover` is to refuse the drop
+      listen(cx, &el, "dragover", move |ev: web_sys::DragEvent| {
+        if carries_payload(&ev) {
+          ev.prevent_default();
+        }
+      });
+      listen(cx, &el, "dragleave", {
+        let depth = depth.clone();
+        move |ev: web_sys::DragEvent| {
+          if carries_payload(&ev) {
+            depth.set(depth.get().saturating_sub(1));
+            if depth.get() == 0 {
+              is_over.set(false);
+            }
+          }
+        }
+      });
+      listen(cx, &el, "drop", move |ev: web_sys::DragEvent| {
+        let payload = ev
+          .data_transfer()
+          .and_then(|data| data.get_data(PAYLOAD_MIME_TYPE).ok())
+          .and_then(|json| serde_json::from_str::<P>(&json).ok());
+        if let Some(payload) = payload {
+          ev.prevent_default();
+          depth.set(0);
+          is_over.set(false);
+          dropped.set(Some(payload));
+        }
+      });
+
+      listen(cx, &el, "focus", move |_: web_sys::FocusEvent| {
+        if KEYBOARD_PAYLOAD.with(|current| current.borrow().is_some()) {
+          is_over.set(true);
+        }
+      });
+      listen(cx, &el, "blur", move |_: web_sys::FocusEvent| {
+        is_over.set(false)
+      });
+      listen(
+        cx,
+        &el,
+        "keydown",
+        move |ev: web_sys::KeyboardEvent| match ev.key().as_str() {
+          "Enter" | " " => {
+            let payload = KEYBOARD_PAYLOAD.with(|current| {
+              current
+                .borrow()
+                .as_ref()
+                .and_then(|(json, _)| serde_json::from_str::<P>(json).ok())
+            });
+            if let Some(payload) = payload {
+              ev.prevent_default();
+              cancel_keyboard_drag();
+              is_over.set(false);
+              dropped.set(Some(payload));
+            }
+          }
+          "Escape" => {
+            cancel_keyboard_drag();
+            is_over.set(false);
+          }
+          _ => {}
+        },
+      );
+    });
+  }
+
+  fn carries_payload(ev: &web_sys::DragEvent) -> bool {
+    ev.data_transfer()
+      .map(|data| data.types().includes(&PAYLOAD_MIME_TYPE.into(), 0))
+      .unwrap_or(false)
+  }
+
+  fn cancel_keyboard_drag() {
+    if let Some((_, is_dragging)) =
+      KEYBOARD_PAYLOAD.with(|current| current.borrow_mut().take())
+    {
+      is_dragging.set(false);
+    }
+  }
+
+  fn make_focusable(el: &web_sys::Element) {
+    if !el.has_attribute("tabindex") {
+      _ = el.set_attribute("tabindex", "0");
+    }
+  }
+
+  /// Adds an event listener, and removes it when the scope is disposed.
+  fn listen<E>(
+    cx: Scope,
+    el: &web_sys::Element,
+    event_name: &'static str,
+    f: impl Fn(E) + 'static,
+  ) where
+    E: JsCast + 'static,
+  {
+    let handler = Closure::wrap(Box::new(move |ev: web_sys::Event| {
+      f(ev.unchecked_into::<E>())
+    }) as Box<dyn Fn(web_sys::Event)>);
+    _ = el.add_event_listener_with_callback(
+      event_name,
+      handler.as_ref().unchecked_ref(),
+    );
+
+    let el = el.clone();
+    on_cleanup(cx, move || {
+      _ = el.remove_event_listener_with_callback(
+        event_name,
+        handler.as_ref().unchecked_ref(),
+      );
+    });
+  }
+}
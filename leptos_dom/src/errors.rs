@@ -0,0 +1,97 @@
+//! Errors rendered in the view, which are caught by an `<ErrorBoundary/>`.
+
+use crate::{HydrationCtx, HydrationKey, IntoView, View};
+use leptos_reactive::{on_cleanup, use_context, RwSignal, Scope};
+use std::{collections::HashMap, error::Error, rc::Rc};
+
+/// The errors caught by an `<ErrorBoundary/>`, keyed by where in the view each
+/// one was rendered.
+///
+/// Rendering an `Err(_)` adds its error to the nearest `RwSignal<Errors>`
+/// provided as a context, which is how an `<ErrorBoundary/>` finds out about
+/// it. The error is removed again when the scope it was rendered in is
+/// disposed, e.g., when the view is rendered again without the error.
+#[derive(Clone, Debug, Default)]
+pub struct Errors(HashMap<HydrationKey, Rc<dyn Error>>);
+
+impl Errors {
+  /// Whether there are no errors.
+  pub fn is_empty(&self) -> bool {
+    self.0.is_empty()
+  }
+
+  /// The number of errors.
+  pub fn len(&self) -> usize {
+    self.0.len()
+  }
+
+  /// Iterates over the errors, in no particular order.
+  pub fn iter(&self) -> impl Iterator<Item = &Rc<dyn Error>> {
+    self.0.values()
+  }
+
+  /// Adds an error that was rendered at the given key, replacing any error
+  /// that was already there.
+  pub fn insert(
+    &mut self,
+    key: HydrationKey,
+    error: impl Into<Box<dyn Error>>,
+  ) {
+    self.0.insert(key, Rc::from(error.into()));
+  }
+
+  /// Removes the error that was rendered at the given key.
+  pub fn remove(&mut self, key: &HydrationKey) -> Option<Rc<dyn Error>> {
+    self.0.remove(key)
+  }
+}
+
+/// Renders the `Ok` value, or reports the `Err` to the nearest
+/// `<ErrorBoundary/>` and renders nothing.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Price(cx: Scope, input: String) -> impl IntoView {
+///   view! { cx,
+///     <ErrorBoundary fallback=|_: Scope, _: RwSignal<Errors>| view! { cx,
+///       <p>"Not a number"</p>
+///     }>
+///       {input.parse::<f64>().map(|price| format!("${price:.2}"))}
+///     </ErrorBoundary>
+///   }
+/// }
+/// ```
+impl<T, E> IntoView for Result<T, E>
+where
+  T: IntoView,
+  E: Into<Box<dyn Error>>,
+{
+  fn into_view(self, cx: Scope) -> View {
+    match self {
+      Ok(view) => view.into_view(cx),
+      Err(error) => {
+        let key = HydrationCtx::id();
+        match use_context::<RwSignal<Errors>>(cx) {
+          Some(errors) => {
+            errors.update(|errors| errors.insert(key.clone(), error));
+            on_cleanup(cx, move || {
+              errors.update(|errors| {
+                errors.remove(&key);
+              })
+            });
+          }
+          None => {
+            crate::framework_log!(
+              crate::logging::Level::Warn,
+              "an error was rendered without an <ErrorBoundary/> above it: \
+               {}",
+              error.into()
+            );
+          }
+        }
+        ().into_view(cx)
+      }
+    }
+  }
+}
@@ -1,5 +1,8 @@
+pub(crate) mod metrics;
 pub mod typed;
 
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use std::cell::Cell;
 use std::{borrow::Cow, cell::RefCell, collections::HashSet};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use wasm_bindgen::{
@@ -20,6 +23,8 @@ pub fn add_event_listener<E>(
 ) where
   E: FromWasmAbi + 'static,
 {
+  #[cfg(debug_assertions)]
+  let cb = metrics::instrument_handler(event_name.clone(), cb);
   let cb = Closure::wrap(Box::new(cb) as Box<dyn FnMut(E)>).into_js_value();
   let key = event_delegation_key(&event_name);
   _ = js_sys::Reflect::set(target, &JsValue::from_str(&key), &cb);
@@ -36,68 +41,66 @@ pub fn add_event_listener_undelegated<E>(
   E: FromWasmAbi + 'static,
 {
   let event_name = intern(event_name);
+  #[cfg(debug_assertions)]
+  let cb = metrics::instrument_handler(event_name.to_string().into(), cb);
   let cb = Closure::wrap(Box::new(cb) as Box<dyn FnMut(E)>).into_js_value();
   _ = target.add_event_listener_with_callback(event_name, cb.unchecked_ref());
 }
 
-// cf eventHandler in ryansolid/dom-expressions
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+thread_local! {
+  /// The elements that views mounted with
+  /// [`mount_scoped`](crate::mount_scoped) delegate their events from.
+  static DELEGATION_ROOTS: RefCell<Vec<DelegationRoot>> = RefCell::new(Vec::new());
+  static NEXT_DELEGATION_ROOT: Cell<usize> = Cell::new(0);
+  /// Whether delegated events are listened for on the `window`. This is
+  /// `Some(false)` if the first view mounted was scoped, so that a bundle that
+  /// only embeds scoped widgets doesn't handle events for the rest of the page.
+  static WINDOW_DELEGATION: Cell<Option<bool>> = Cell::new(None);
+}
+
+/// The property set on an event once the handlers below a delegation root have
+/// run, so that listeners further up the tree don't run them again.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+const DELEGATED_UNTIL: &str = "$$$delegatedUntil";
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+struct DelegationRoot {
+  id: usize,
+  root: web_sys::Element,
+  listeners: Vec<(Cow<'static, str>, js_sys::Function)>,
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+impl DelegationRoot {
+  fn listen(&mut self, event_name: Cow<'static, str>) {
+    let key = JsValue::from_str(&event_delegation_key(&event_name));
+    let root = self.root.clone();
+    let handler = Closure::wrap(Box::new(move |ev: web_sys::Event| {
+      dispatch_delegated(&ev, &key, Some(&root))
+    }) as Box<dyn FnMut(web_sys::Event)>)
+    .into_js_value()
+    .unchecked_into::<js_sys::Function>();
+    _ = self
+      .root
+      .add_event_listener_with_callback(&event_name, &handler);
+    self.listeners.push((event_name, handler));
+  }
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub(crate) fn add_delegated_event_listener(event_name: Cow<'static, str>) {
   GLOBAL_EVENTS.with(|global_events| {
     let mut events = global_events.borrow_mut();
     if !events.contains(&event_name) {
-      // create global handler
-      let key = JsValue::from_str(&event_delegation_key(&event_name));
-      let handler = move |ev: web_sys::Event| {
-        let target = ev.target();
-        let node = ev.composed_path().get(0);
-        let mut node = if node.is_undefined() || node.is_null() {
-          JsValue::from(target)
-        } else {
-          node
-        };
-
-        // TODO reverse Shadow DOM retargetting
-
-        // TODO simulate currentTarget
-
-        while !node.is_null() {
-          let node_is_disabled =
-            js_sys::Reflect::get(&node, &JsValue::from_str("disabled"))
-              .unwrap_throw()
-              .is_truthy();
-          if !node_is_disabled {
-            let maybe_handler =
-              js_sys::Reflect::get(&node, &key).unwrap_throw();
-            if !maybe_handler.is_undefined() {
-              let f = maybe_handler.unchecked_ref::<js_sys::Function>();
-              let _ = f.call1(&node, &ev);
-
-              if ev.cancel_bubble() {
-                return;
-              }
-            }
-          }
-
-          // navigate up tree
-          let host = js_sys::Reflect::get(&node, &JsValue::from_str("host"))
-            .unwrap_throw();
-          if host.is_truthy()
-            && host != node
-            && host.dyn_ref::<web_sys::Node>().is_some()
-          {
-            node = host;
-          } else if let Some(parent) =
-            node.unchecked_into::<web_sys::Node>().parent_node()
-          {
-            node = parent.into()
-          } else {
-            node = JsValue::null()
-          }
+      if WINDOW_DELEGATION.with(Cell::get) != Some(false) {
+        delegate_from_window(&event_name);
+      }
+      DELEGATION_ROOTS.with(|roots| {
+        for root in roots.borrow_mut().iter_mut() {
+          root.listen(event_name.clone());
         }
-      };
-
-      crate::window_event_listener(&event_name, handler);
+      });
 
       // register that we've created handler
       events.insert(event_name);
@@ -105,6 +108,146 @@ pub(crate) fn add_delegated_event_listener(event_name: Cow<'static, str>) {
   })
 }
 
+/// Delegates the events of a view mounted with
+/// [`mount_scoped`](crate::mount_scoped) from its parent element, instead of
+/// the `window`. Returns an id for [`remove_delegation_root`].
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn add_delegation_root(root: &web_sys::Element) -> usize {
+  WINDOW_DELEGATION.with(|window| {
+    if window.get().is_none() {
+      window.set(Some(false));
+    }
+  });
+
+  let id = NEXT_DELEGATION_ROOT.with(|next| next.replace(next.get() + 1));
+  let mut root = DelegationRoot {
+    id,
+    root: root.clone(),
+    listeners: Vec::new(),
+  };
+  GLOBAL_EVENTS.with(|events| {
+    for event_name in events.borrow().iter() {
+      root.listen(event_name.clone());
+    }
+  });
+  DELEGATION_ROOTS.with(|roots| roots.borrow_mut().push(root));
+  id
+}
+
+/// Removes the listeners added by [`add_delegation_root`].
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn remove_delegation_root(id: usize) {
+  DELEGATION_ROOTS.with(|roots| {
+    roots.borrow_mut().retain(|root| {
+      if root.id != id {
+        return true;
+      }
+      for (event_name, handler) in &root.listeners {
+        _ = root
+          .root
+          .remove_event_listener_with_callback(event_name, handler);
+      }
+      false
+    })
+  })
+}
+
+/// Delegates events from the `window`, for views mounted with
+/// [`mount_to`](crate::mount_to).
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn enable_window_delegation() {
+  if WINDOW_DELEGATION.with(|window| window.replace(Some(true))) == Some(false)
+  {
+    GLOBAL_EVENTS.with(|events| {
+      for event_name in events.borrow().iter() {
+        delegate_from_window(event_name);
+      }
+    });
+  }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn delegate_from_window(event_name: &str) {
+  let key = JsValue::from_str(&event_delegation_key(event_name));
+  crate::window_event_listener(event_name, move |ev| {
+    dispatch_delegated(&ev, &key, None)
+  });
+}
+
+/// Runs the handlers stored under `key` on the nodes from the event's target
+/// up to `root`, or to the top of the document if there is no root.
+// cf eventHandler in ryansolid/dom-expressions
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn dispatch_delegated(
+  ev: &web_sys::Event,
+  key: &JsValue,
+  root: Option<&web_sys::Element>,
+) {
+  let delegated_until = JsValue::from_str(DELEGATED_UNTIL);
+  let root = root.map(|root| JsValue::from(root.clone()));
+
+  // a delegation root below this one has already run the handlers beneath it
+  let handled = js_sys::Reflect::get(ev, &delegated_until).unwrap_throw();
+  let mut node = if handled.is_truthy() {
+    handled
+  } else {
+    let target = ev.target();
+    let node = ev.composed_path().get(0);
+    if node.is_undefined() || node.is_null() {
+      JsValue::from(target)
+    } else {
+      node
+    }
+  };
+
+  // TODO reverse Shadow DOM retargetting
+
+  // TODO simulate currentTarget
+
+  while !node.is_null() {
+    // the root itself belongs to the view it was mounted in
+    if root.as_ref() == Some(&node) {
+      break;
+    }
+
+    let node_is_disabled =
+      js_sys::Reflect::get(&node, &JsValue::from_str("disabled"))
+        .unwrap_throw()
+        .is_truthy();
+    if !node_is_disabled {
+      let maybe_handler = js_sys::Reflect::get(&node, key).unwrap_throw();
+      if !maybe_handler.is_undefined() {
+        let f = maybe_handler.unchecked_ref::<js_sys::Function>();
+        let _ = f.call1(&node, ev);
+
+        if ev.cancel_bubble() {
+          return;
+        }
+      }
+    }
+
+    // navigate up tree
+    let host =
+      js_sys::Reflect::get(&node, &JsValue::from_str("host")).unwrap_throw();
+    if host.is_truthy()
+      && host != node
+      && host.dyn_ref::<web_sys::Node>().is_some()
+    {
+      node = host;
+    } else if let Some(parent) =
+      node.unchecked_into::<web_sys::Node>().parent_node()
+    {
+      node = parent.into()
+    } else {
+      node = JsValue::null()
+    }
+  }
+
+  if let Some(root) = root {
+    _ = js_sys::Reflect::set(ev, &delegated_until, &root);
+  }
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub(crate) fn event_delegation_key(event_name: &str) -> String {
   let event_name = intern(event_name);
@@ -0,0 +1,163 @@
+//! Timing of event handlers in debug builds, to find the handlers that make
+//! an app janky.
+
+use std::{
+  borrow::Cow,
+  cell::{Cell, RefCell},
+  rc::Rc,
+  time::Duration,
+};
+
+thread_local! {
+  static BUDGET: Cell<Duration> = Cell::new(Duration::from_millis(16));
+  static METRICS: RefCell<Vec<EventMetrics>> = RefCell::new(Vec::new());
+  #[allow(clippy::type_complexity)]
+  static SLOW_HANDLER_LISTENERS: RefCell<Vec<Rc<dyn Fn(&SlowHandler)>>> =
+    RefCell::new(Vec::new());
+}
+
+/// How often the handlers for an event ran, and how long they took, as
+/// returned by [event_metrics].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct EventMetrics {
+  /// The name of the event, like `click`.
+  pub event: Cow<'static, str>,
+  /// How many times a handler ran.
+  pub calls: u64,
+  /// How many of those took longer than the [slow_handler_budget].
+  pub slow_calls: u64,
+  /// The time that all of the handlers took together.
+  pub total: Duration,
+  /// The time that the slowest handler took.
+  pub max: Duration,
+}
+
+/// A handler that took longer than the [slow_handler_budget], as passed to
+/// [on_slow_handler].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlowHandler {
+  /// The name of the event, like `click`.
+  pub event: Cow<'static, str>,
+  /// The component that added the handler, if it was known. Components are
+  /// only known when `leptos` is built with its `tracing` feature.
+  pub component: Option<&'static str>,
+  /// How long the handler took.
+  pub duration: Duration,
+}
+
+/// Warns about each event handler that takes longer than `budget`, which is
+/// `16ms` (one frame at 60fps) by default.
+///
+/// Handlers are only timed in debug builds in the browser.
+pub fn set_slow_handler_budget(budget: Duration) {
+  BUDGET.with(|current| current.set(budget));
+}
+
+/// How long an event handler can take before it's reported as slow. See
+/// [set_slow_handler_budget].
+pub fn slow_handler_budget() -> Duration {
+  BUDGET.with(Cell::get)
+}
+
+/// The metrics for each event whose handlers ran since the page loaded or
+/// [reset_event_metrics] was called, in the order the events first fired.
+///
+/// This is meant for devtools, and is always empty in release builds.
+/// ```
+/// # use leptos_dom::*;
+/// for metrics in event_metrics() {
+///   let average = metrics.total / metrics.calls as u32;
+///   println!("{}: {} calls, {average:?} on average", metrics.event, metrics.calls);
+/// }
+/// ```
+pub fn event_metrics() -> Vec<EventMetrics> {
+  METRICS.with(|metrics| metrics.borrow().clone())
+}
+
+/// Clears the metrics returned by [event_metrics].
+pub fn reset_event_metrics() {
+  METRICS.with(|metrics| metrics.borrow_mut().clear());
+}
+
+/// Calls `f` with each event handler that takes longer than the
+/// [slow_handler_budget], in addition to the warning that's logged. This lets
+/// devtools show slow handlers as they happen.
+pub fn on_slow_handler(f: impl Fn(&SlowHandler) + 'static) {
+  SLOW_HANDLER_LISTENERS
+    .with(|listeners| listeners.borrow_mut().push(Rc::new(f)));
+}
+
+/// Wraps an event handler so that each call is timed, and the component that
+/// adds it is known while it runs.
+#[cfg(all(debug_assertions, target_arch = "wasm32", feature = "web"))]
+pub(crate) fn instrument_handler<E>(
+  event: Cow<'static, str>,
+  mut handler: impl FnMut(E) + 'static,
+) -> impl FnMut(E) + 'static {
+  let span = tracing::Span::current();
+  let component = span.metadata().map(|metadata| metadata.name());
+
+  move |ev| {
+    let _guard = span.enter();
+    let started_at = js_sys::Date::now();
+    handler(ev);
+    let duration = Duration::from_secs_f64(
+      (js_sys::Date::now() - started_at).max(0.0) / 1000.0,
+    );
+    record(&event, component, duration);
+  }
+}
+
+#[cfg_attr(
+  not(all(debug_assertions, target_arch = "wasm32", feature = "web")),
+  allow(dead_code)
+)]
+fn record(
+  event: &Cow<'static, str>,
+  component: Option<&'static str>,
+  duration: Duration,
+) {
+  let is_slow = duration > slow_handler_budget();
+
+  METRICS.with(|metrics| {
+    let mut metrics = metrics.borrow_mut();
+    let index = match metrics.iter().position(|m| &m.event == event) {
+      Some(index) => index,
+      None => {
+        metrics.push(EventMetrics {
+          event: event.clone(),
+          ..Default::default()
+        });
+        metrics.len() - 1
+      }
+    };
+    let entry = &mut metrics[index];
+    entry.calls += 1;
+    entry.total += duration;
+    entry.max = entry.max.max(duration);
+    if is_slow {
+      entry.slow_calls += 1;
+    }
+  });
+
+  if is_slow {
+    crate::framework_log!(
+      crate::logging::Level::Warn,
+      "slow `{event}` handler in <{}/>: took {duration:?}, over the budget of \
+       {:?}",
+      component.unwrap_or("unknown component"),
+      slow_handler_budget(),
+    );
+
+    let slow = SlowHandler {
+      event: event.clone(),
+      component,
+      duration,
+    };
+    // cloned so that listeners can add listeners
+    let listeners = SLOW_HANDLER_LISTENERS.with(|l| l.borrow().clone());
+    for listener in listeners {
+      listener(&slow);
+    }
+  }
+}
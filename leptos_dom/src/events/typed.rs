@@ -3,6 +3,10 @@
 use std::{borrow::Cow, marker::PhantomData};
 use wasm_bindgen::convert::FromWasmAbi;
 
+mod list;
+
+pub use list::*;
+
 /// A trait for converting types into [web_sys events](web_sys).
 pub trait EventDescriptor: Clone {
   /// The [`web_sys`] event type, such as [`web_sys::MouseEvent`].
@@ -39,7 +43,13 @@ impl<Ev: EventDescriptor> EventDescriptor for undelegated<Ev> {
   }
 }
 
-/// A custom event.
+/// A custom event, or any event that doesn't have its own type in this
+/// module. The type parameter is the [`web_sys`] class of the event, which
+/// defaults to [`web_sys::Event`].
+///
+/// Listeners for custom events are always attached directly to the element,
+/// rather than delegated, since custom events don't bubble unless they are
+/// dispatched with `bubbles: true`.
 pub struct Custom<E: FromWasmAbi = web_sys::Event> {
   name: Cow<'static, str>,
   _event_type: PhantomData<E>,
@@ -70,6 +80,12 @@ impl<E: FromWasmAbi> Custom<E> {
   /// Creates a custom event type that can be used within
   /// [`HtmlElement::on`](crate::HtmlElement::on), for events
   /// which are not covered in the [`ev`](crate::ev) module.
+  ///
+  /// ```
+  /// # use leptos_dom::{ev, web_sys};
+  /// let long_press = ev::Custom::<web_sys::PointerEvent>::new("longpress");
+  /// let toggle = ev::Custom::<web_sys::Event>::new("beforetoggle");
+  /// ```
   pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
     Self {
       name: name.into(),
@@ -77,195 +93,3 @@ impl<E: FromWasmAbi> Custom<E> {
     }
   }
 }
-
-macro_rules! generate_event_types {
-  {$(
-    $( #[$does_not_bubble:ident] )?
-    $event:ident : $web_sys_event:ident
-  ),* $(,)?} => {
-
-    $(
-      #[doc = "The "]
-      #[doc = stringify!($event)]
-      #[doc = " event."]
-      #[allow(non_camel_case_types)]
-      #[derive(Clone, Copy)]
-      pub struct $event;
-
-      impl EventDescriptor for $event {
-        type EventType = web_sys::$web_sys_event;
-
-        fn name(&self) -> Cow<'static, str> {
-          stringify!($event).into()
-        }
-
-        $(
-          generate_event_types!($does_not_bubble);
-        )?
-      }
-    )*
-  };
-
-  (does_not_bubble) => {
-    fn bubbles(&self) -> bool { false }
-  }
-}
-
-generate_event_types! {
-  // =========================================================
-  // WindowEventHandlersEventMap
-  // =========================================================
-  afterprint: Event,
-  beforeprint: Event,
-  beforeunload: BeforeUnloadEvent,
-  gamepadconnected: GamepadEvent,
-  gamepaddisconnected: GamepadEvent,
-  hashchange: HashChangeEvent,
-  languagechange: Event,
-  message: MessageEvent,
-  messageerror: MessageEvent,
-  offline: Event,
-  online: Event,
-  pagehide: PageTransitionEvent,
-  pageshow: PageTransitionEvent,
-  popstate: PopStateEvent,
-  rejectionhandled: PromiseRejectionEvent,
-  storage: StorageEvent,
-  unhandledrejection: PromiseRejectionEvent,
-  #[does_not_bubble]
-  unload: Event,
-
-  // =========================================================
-  // GlobalEventHandlersEventMap
-  // =========================================================
-  #[does_not_bubble]
-  abort: UiEvent,
-  animationcancel: AnimationEvent,
-  animationend: AnimationEvent,
-  animationiteration: AnimationEvent,
-  animationstart: AnimationEvent,
-  auxclick: MouseEvent,
-  beforeinput: InputEvent,
-  #[does_not_bubble]
-  blur: FocusEvent,
-  canplay: Event,
-  canplaythrough: Event,
-  change: Event,
-  click: MouseEvent,
-  close: Event,
-  compositionend: CompositionEvent,
-  compositionstart: CompositionEvent,
-  compositionupdate: CompositionEvent,
-  contextmenu: MouseEvent,
-  cuechange: Event,
-  dblclick: MouseEvent,
-  drag: DragEvent,
-  dragend: DragEvent,
-  dragenter: DragEvent,
-  dragleave: DragEvent,
-  dragover: DragEvent,
-  dragstart: DragEvent,
-  drop: DragEvent,
-  durationchange: Event,
-  emptied: Event,
-  ended: Event,
-  #[does_not_bubble]
-  error: ErrorEvent,
-  #[does_not_bubble]
-  focus: FocusEvent,
-  #[does_not_bubble]
-  focusin: FocusEvent,
-  #[does_not_bubble]
-  focusout: FocusEvent,
-  formdata: Event, // web_sys does not include `FormDataEvent`
-  gotpointercapture: PointerEvent,
-  input: Event,
-  invalid: Event,
-  keydown: KeyboardEvent,
-  keypress: KeyboardEvent,
-  keyup: KeyboardEvent,
-  #[does_not_bubble]
-  load: Event,
-  loadeddata: Event,
-  loadedmetadata: Event,
-  #[does_not_bubble]
-  loadstart: Event,
-  lostpointercapture: PointerEvent,
-  mousedown: MouseEvent,
-  mouseenter: MouseEvent,
-  mouseleave: MouseEvent,
-  mousemove: MouseEvent,
-  mouseout: MouseEvent,
-  mouseover: MouseEvent,
-  mouseup: MouseEvent,
-  pause: Event,
-  play: Event,
-  playing: Event,
-  pointercancel: PointerEvent,
-  pointerdown: PointerEvent,
-  pointerenter: PointerEvent,
-  pointerleave: PointerEvent,
-  pointermove: PointerEvent,
-  pointerout: PointerEvent,
-  pointerover: PointerEvent,
-  pointerup: PointerEvent,
-  #[does_not_bubble]
-  progress: ProgressEvent,
-  ratechange: Event,
-  reset: Event,
-  resize: UiEvent,
-  #[does_not_bubble]
-  scroll: Event,
-  securitypolicyviolation: SecurityPolicyViolationEvent,
-  seeked: Event,
-  seeking: Event,
-  select: Event,
-  selectionchange: Event,
-  selectstart: Event,
-  slotchange: Event,
-  stalled: Event,
-  submit: SubmitEvent,
-  suspend: Event,
-  timeupdate: Event,
-  toggle: Event,
-  touchcancel: TouchEvent,
-  touchend: TouchEvent,
-  touchmove: TouchEvent,
-  touchstart: TouchEvent,
-  transitioncancel: TransitionEvent,
-  transitionend: TransitionEvent,
-  transitionrun: TransitionEvent,
-  transitionstart: TransitionEvent,
-  volumechange: Event,
-  waiting: Event,
-  webkitanimationend: Event,
-  webkitanimationiteration: Event,
-  webkitanimationstart: Event,
-  webkittransitionend: Event,
-  wheel: WheelEvent,
-
-  // =========================================================
-  // WindowEventMap
-  // =========================================================
-  DOMContentLoaded: Event,
-  devicemotion: DeviceMotionEvent,
-  deviceorientation: DeviceOrientationEvent,
-  orientationchange: Event,
-
-  // =========================================================
-  // DocumentAndElementEventHandlersEventMap
-  // =========================================================
-  copy: Event, // ClipboardEvent is unstable
-  cut: Event, // ClipboardEvent is unstable
-  paste: Event, // ClipboardEvent is unstable
-
-  // =========================================================
-  // DocumentEventMap
-  // =========================================================
-  fullscreenchange: Event,
-  fullscreenerror: Event,
-  pointerlockchange: Event,
-  pointerlockerror: Event,
-  readystatechange: Event,
-  visibilitychange: Event,
-}
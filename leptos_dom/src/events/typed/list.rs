@@ -0,0 +1,246 @@
+//! The table of DOM events that get a typed [`EventDescriptor`].
+//!
+//! Each entry is `name: WebSysEvent`, where `WebSysEvent` is the class of
+//! the event in [`web_sys`]. Events that don't bubble are marked with
+//! `#[does_not_bubble]`, so that their listeners are attached directly to
+//! the element rather than being delegated.
+
+use super::EventDescriptor;
+use std::borrow::Cow;
+
+macro_rules! generate_event_types {
+  {$(
+    $( #[$does_not_bubble:ident] )?
+    $event:ident : $web_sys_event:ident
+  ),* $(,)?} => {
+
+    $(
+      #[doc = "The "]
+      #[doc = stringify!($event)]
+      #[doc = " event."]
+      #[allow(non_camel_case_types)]
+      #[derive(Clone, Copy)]
+      pub struct $event;
+
+      impl EventDescriptor for $event {
+        type EventType = web_sys::$web_sys_event;
+
+        fn name(&self) -> Cow<'static, str> {
+          stringify!($event).into()
+        }
+
+        $(
+          generate_event_types!($does_not_bubble);
+        )?
+      }
+    )*
+  };
+
+  (does_not_bubble) => {
+    fn bubbles(&self) -> bool { false }
+  }
+}
+
+generate_event_types! {
+  // =========================================================
+  // WindowEventHandlersEventMap
+  // =========================================================
+  afterprint: Event,
+  beforeprint: Event,
+  beforeunload: BeforeUnloadEvent,
+  gamepadconnected: GamepadEvent,
+  gamepaddisconnected: GamepadEvent,
+  hashchange: HashChangeEvent,
+  languagechange: Event,
+  message: MessageEvent,
+  messageerror: MessageEvent,
+  offline: Event,
+  online: Event,
+  pagehide: PageTransitionEvent,
+  pageshow: PageTransitionEvent,
+  popstate: PopStateEvent,
+  rejectionhandled: PromiseRejectionEvent,
+  storage: StorageEvent,
+  unhandledrejection: PromiseRejectionEvent,
+  #[does_not_bubble]
+  unload: Event,
+
+  // =========================================================
+  // GlobalEventHandlersEventMap
+  // =========================================================
+  beforeinput: InputEvent,
+  #[does_not_bubble]
+  blur: FocusEvent,
+  #[does_not_bubble]
+  cancel: Event,
+  change: Event,
+  #[does_not_bubble]
+  close: Event,
+  #[does_not_bubble]
+  cuechange: Event,
+  #[does_not_bubble]
+  error: ErrorEvent,
+  #[does_not_bubble]
+  focus: FocusEvent,
+  #[does_not_bubble]
+  focusin: FocusEvent,
+  #[does_not_bubble]
+  focusout: FocusEvent,
+  formdata: Event, // web_sys does not include `FormDataEvent`
+  input: Event,
+  #[does_not_bubble]
+  invalid: Event,
+  #[does_not_bubble]
+  load: Event,
+  reset: Event,
+  resize: UiEvent,
+  #[does_not_bubble]
+  scroll: Event,
+  #[does_not_bubble]
+  scrollend: Event,
+  securitypolicyviolation: SecurityPolicyViolationEvent,
+  select: Event,
+  selectionchange: Event,
+  selectstart: Event,
+  slotchange: Event,
+  submit: SubmitEvent,
+  #[does_not_bubble]
+  toggle: Event,
+
+  // mouse events
+  auxclick: MouseEvent,
+  click: MouseEvent,
+  contextmenu: MouseEvent,
+  dblclick: MouseEvent,
+  mousedown: MouseEvent,
+  #[does_not_bubble]
+  mouseenter: MouseEvent,
+  #[does_not_bubble]
+  mouseleave: MouseEvent,
+  mousemove: MouseEvent,
+  mouseout: MouseEvent,
+  mouseover: MouseEvent,
+  mouseup: MouseEvent,
+  wheel: WheelEvent,
+
+  // pointer events
+  gotpointercapture: PointerEvent,
+  lostpointercapture: PointerEvent,
+  pointercancel: PointerEvent,
+  pointerdown: PointerEvent,
+  #[does_not_bubble]
+  pointerenter: PointerEvent,
+  #[does_not_bubble]
+  pointerleave: PointerEvent,
+  pointermove: PointerEvent,
+  pointerout: PointerEvent,
+  pointerover: PointerEvent,
+  pointerrawupdate: PointerEvent,
+  pointerup: PointerEvent,
+
+  // touch events
+  touchcancel: TouchEvent,
+  touchend: TouchEvent,
+  touchmove: TouchEvent,
+  touchstart: TouchEvent,
+
+  // keyboard and composition events
+  compositionend: CompositionEvent,
+  compositionstart: CompositionEvent,
+  compositionupdate: CompositionEvent,
+  keydown: KeyboardEvent,
+  keypress: KeyboardEvent,
+  keyup: KeyboardEvent,
+
+  // drag and drop events
+  drag: DragEvent,
+  dragend: DragEvent,
+  dragenter: DragEvent,
+  dragleave: DragEvent,
+  dragover: DragEvent,
+  dragstart: DragEvent,
+  drop: DragEvent,
+
+  // animation and transition events
+  animationcancel: AnimationEvent,
+  animationend: AnimationEvent,
+  animationiteration: AnimationEvent,
+  animationstart: AnimationEvent,
+  transitioncancel: TransitionEvent,
+  transitionend: TransitionEvent,
+  transitionrun: TransitionEvent,
+  transitionstart: TransitionEvent,
+  webkitanimationend: Event,
+  webkitanimationiteration: Event,
+  webkitanimationstart: Event,
+  webkittransitionend: Event,
+
+  // media events, which are fired on `<audio>` and `<video>` and don't bubble
+  #[does_not_bubble]
+  abort: UiEvent,
+  #[does_not_bubble]
+  canplay: Event,
+  #[does_not_bubble]
+  canplaythrough: Event,
+  #[does_not_bubble]
+  durationchange: Event,
+  #[does_not_bubble]
+  emptied: Event,
+  #[does_not_bubble]
+  ended: Event,
+  #[does_not_bubble]
+  loadeddata: Event,
+  #[does_not_bubble]
+  loadedmetadata: Event,
+  #[does_not_bubble]
+  loadstart: Event,
+  #[does_not_bubble]
+  pause: Event,
+  #[does_not_bubble]
+  play: Event,
+  #[does_not_bubble]
+  playing: Event,
+  #[does_not_bubble]
+  progress: ProgressEvent,
+  #[does_not_bubble]
+  ratechange: Event,
+  #[does_not_bubble]
+  seeked: Event,
+  #[does_not_bubble]
+  seeking: Event,
+  #[does_not_bubble]
+  stalled: Event,
+  #[does_not_bubble]
+  suspend: Event,
+  #[does_not_bubble]
+  timeupdate: Event,
+  #[does_not_bubble]
+  volumechange: Event,
+  #[does_not_bubble]
+  waiting: Event,
+
+  // =========================================================
+  // WindowEventMap
+  // =========================================================
+  DOMContentLoaded: Event,
+  devicemotion: DeviceMotionEvent,
+  deviceorientation: DeviceOrientationEvent,
+  orientationchange: Event,
+
+  // =========================================================
+  // DocumentAndElementEventHandlersEventMap
+  // =========================================================
+  copy: Event, // ClipboardEvent is unstable
+  cut: Event, // ClipboardEvent is unstable
+  paste: Event, // ClipboardEvent is unstable
+
+  // =========================================================
+  // DocumentEventMap
+  // =========================================================
+  fullscreenchange: Event,
+  fullscreenerror: Event,
+  pointerlockchange: Event,
+  pointerlockerror: Event,
+  readystatechange: Event,
+  visibilitychange: Event,
+}
@@ -0,0 +1,280 @@
+//! Reactive access to the device's location.
+
+use leptos_reactive::{
+  create_rw_signal, on_cleanup, store_value, RwSignal, Scope, Signal,
+  StoredValue,
+};
+use std::{fmt, time::Duration};
+
+/// Options for [use_geolocation], matching the browser's
+/// [`PositionOptions`](https://developer.mozilla.org/en-US/docs/Web/API/Geolocation/watchPosition#options).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GeolocationOptions {
+  /// Whether to ask for the most accurate position the device can provide,
+  /// which may be slower and use more power. Defaults to `false`.
+  pub high_accuracy: bool,
+  /// How old a cached position can be and still be used. Defaults to `0`,
+  /// i.e., never use a cached position.
+  pub maximum_age: Duration,
+  /// How long to wait for each position before failing with
+  /// [GeolocationError::Timeout]. Defaults to `None`, i.e., wait forever.
+  pub timeout: Option<Duration>,
+  /// Whether to start watching the position as soon as the scope is created.
+  /// Defaults to `true`. Otherwise, call [Geolocation::start].
+  pub immediate: bool,
+}
+
+impl Default for GeolocationOptions {
+  fn default() -> Self {
+    Self {
+      high_accuracy: false,
+      maximum_age: Duration::ZERO,
+      timeout: None,
+      immediate: true,
+    }
+  }
+}
+
+/// A position reported by the device, from
+/// [`GeolocationCoordinates`](https://developer.mozilla.org/en-US/docs/Web/API/GeolocationCoordinates).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct GeolocationPosition {
+  /// The latitude, in degrees.
+  pub latitude: f64,
+  /// The longitude, in degrees.
+  pub longitude: f64,
+  /// The accuracy of the latitude and longitude, in meters.
+  pub accuracy: f64,
+  /// The altitude above sea level, in meters, if the device reports it.
+  pub altitude: Option<f64>,
+  /// The accuracy of the altitude, in meters, if the device reports it.
+  pub altitude_accuracy: Option<f64>,
+  /// The direction of travel, in degrees clockwise from north, if the device
+  /// reports it.
+  pub heading: Option<f64>,
+  /// The speed, in meters per second, if the device reports it.
+  pub speed: Option<f64>,
+  /// When the position was measured, in milliseconds since the Unix epoch.
+  pub timestamp: f64,
+}
+
+/// An error while getting the device's location.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GeolocationError {
+  /// The browser doesn't support geolocation, or this is server rendering.
+  Unsupported,
+  /// The user or browser didn't allow access to the location.
+  PermissionDenied,
+  /// The device couldn't determine its location.
+  PositionUnavailable,
+  /// No position was found within [GeolocationOptions::timeout].
+  Timeout,
+}
+
+impl fmt::Display for GeolocationError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(match self {
+      Self::Unsupported => "geolocation is not available",
+      Self::PermissionDenied => "access to the location was not allowed",
+      Self::PositionUnavailable => "the location could not be determined",
+      Self::Timeout => "timed out while getting the location",
+    })
+  }
+}
+
+impl std::error::Error for GeolocationError {}
+
+/// Whether a [Geolocation] is watching the device's position.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum GeolocationStatus {
+  /// Not watching the position.
+  #[default]
+  Stopped,
+  /// Watching the position, but none has been reported since starting.
+  Locating,
+  /// Watching the position, and at least one has been reported.
+  Tracking,
+}
+
+/// Reactive access to the device's location, created with [use_geolocation].
+#[derive(Clone, Copy, Debug)]
+pub struct Geolocation {
+  position: RwSignal<Option<GeolocationPosition>>,
+  error: RwSignal<Option<GeolocationError>>,
+  status: RwSignal<GeolocationStatus>,
+  // only used in the browser
+  #[cfg_attr(
+    not(all(target_arch = "wasm32", feature = "web")),
+    allow(dead_code)
+  )]
+  options: StoredValue<GeolocationOptions>,
+  watch_id: StoredValue<Option<i32>>,
+}
+
+/// Watches the device's location, using the browser's
+/// [Geolocation API](https://developer.mozilla.org/en-US/docs/Web/API/Geolocation_API).
+/// The browser will usually ask the user for permission first; its state can
+/// be tracked with `use_permission(cx, "geolocation")`.
+///
+/// Watching stops when [Geolocation::stop] is called or the scope is disposed.
+/// During server rendering, nothing is watched and the status is always
+/// [GeolocationStatus::Stopped].
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let location = use_geolocation(cx, GeolocationOptions::default());
+///
+/// let coords = move || {
+///   location.position().with(|position| {
+///     position.map(|p| format!("{:.4}, {:.4}", p.latitude, p.longitude))
+///   })
+/// };
+/// # });
+/// ```
+pub fn use_geolocation(cx: Scope, options: GeolocationOptions) -> Geolocation {
+  let immediate = options.immediate;
+  let geolocation = Geolocation {
+    position: create_rw_signal(cx, None),
+    error: create_rw_signal(cx, None),
+    status: create_rw_signal(cx, GeolocationStatus::Stopped),
+    options: store_value(cx, options),
+    watch_id: store_value(cx, None),
+  };
+  if immediate {
+    geolocation.start();
+  }
+  on_cleanup(cx, move || geolocation.stop());
+  geolocation
+}
+
+impl Geolocation {
+  /// The most recently reported position.
+  pub fn position(&self) -> Signal<Option<GeolocationPosition>> {
+    self.position.into()
+  }
+
+  /// The most recent error, which is cleared when a new position is reported.
+  pub fn error(&self) -> Signal<Option<GeolocationError>> {
+    self.error.into()
+  }
+
+  /// Whether the position is being watched.
+  pub fn status(&self) -> Signal<GeolocationStatus> {
+    self.status.into()
+  }
+
+  /// Starts watching the position, if it isn't already being watched.
+  pub fn start(&self) {
+    if self.watch_id.with(Option::is_some) {
+      return;
+    }
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    match web::watch(*self) {
+      Some(id) => {
+        self.watch_id.set(Some(id));
+        self.status.set(GeolocationStatus::Locating);
+      }
+      None => self.error.set(Some(GeolocationError::Unsupported)),
+    }
+  }
+
+  /// Stops watching the position. The last position is kept.
+  pub fn stop(&self) {
+    if let Some(Some(_id)) = self.watch_id.try_update(Option::take) {
+      #[cfg(all(target_arch = "wasm32", feature = "web"))]
+      web::clear_watch(_id);
+      self.status.set(GeolocationStatus::Stopped);
+    }
+  }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod web {
+  use super::{
+    Geolocation, GeolocationError, GeolocationPosition, GeolocationStatus,
+  };
+  use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+  fn get(value: &JsValue, key: &str) -> JsValue {
+    js_sys::Reflect::get(value, &key.into()).unwrap_or(JsValue::UNDEFINED)
+  }
+
+  fn geolocation() -> Option<JsValue> {
+    Some(get(&crate::window().navigator(), "geolocation"))
+      .filter(|geolocation| !geolocation.is_undefined())
+  }
+
+  /// Calls `navigator.geolocation.watchPosition()`, returning the watch ID.
+  pub(super) fn watch(this: Geolocation) -> Option<i32> {
+    let geolocation = geolocation()?;
+    let watch_position = get(&geolocation, "watchPosition")
+      .dyn_into::<js_sys::Function>()
+      .ok()?;
+
+    // these live as long as the page, like other listeners that can't be
+    // removed before they're called
+    let on_position = Closure::wrap(Box::new(move |position: JsValue| {
+      let coords = get(&position, "coords");
+      let number = |key: &str| get(&coords, key).as_f64();
+      this.position.set(Some(GeolocationPosition {
+        latitude: number("latitude").unwrap_or_default(),
+        longitude: number("longitude").unwrap_or_default(),
+        accuracy: number("accuracy").unwrap_or_default(),
+        altitude: number("altitude"),
+        altitude_accuracy: number("altitudeAccuracy"),
+        heading: number("heading").filter(|heading| !heading.is_nan()),
+        speed: number("speed"),
+        timestamp: get(&position, "timestamp").as_f64().unwrap_or_default(),
+      }));
+      this.error.set(None);
+      if this.watch_id.with(Option::is_some) {
+        this.status.set(GeolocationStatus::Tracking);
+      }
+    }) as Box<dyn Fn(JsValue)>)
+    .into_js_value();
+    let on_error = Closure::wrap(Box::new(move |error: JsValue| {
+      let error = match get(&error, "code").as_f64().map(|code| code as u16) {
+        Some(1) => GeolocationError::PermissionDenied,
+        Some(3) => GeolocationError::Timeout,
+        _ => GeolocationError::PositionUnavailable,
+      };
+      this.error.set(Some(error));
+    }) as Box<dyn Fn(JsValue)>)
+    .into_js_value();
+
+    let options = this.options.with(|options| {
+      let object = js_sys::Object::new();
+      let set = |key: &str, value: JsValue| {
+        _ = js_sys::Reflect::set(&object, &key.into(), &value);
+      };
+      set("enableHighAccuracy", options.high_accuracy.into());
+      set(
+        "maximumAge",
+        (options.maximum_age.as_millis() as f64).into(),
+      );
+      if let Some(timeout) = options.timeout {
+        set("timeout", (timeout.as_millis() as f64).into());
+      }
+      object
+    });
+
+    watch_position
+      .call3(&geolocation, &on_position, &on_error, &options)
+      .ok()?
+      .as_f64()
+      .map(|id| id as i32)
+  }
+
+  /// Calls `navigator.geolocation.clearWatch()`.
+  pub(super) fn clear_watch(id: i32) {
+    if let Some(geolocation) = geolocation() {
+      if let Ok(clear_watch) =
+        get(&geolocation, "clearWatch").dyn_into::<js_sys::Function>()
+      {
+        _ = clear_watch.call1(&geolocation, &id.into());
+      }
+    }
+  }
+}
@@ -1,5 +1,5 @@
 use crate::{is_server, window};
-use std::time::Duration;
+use std::{str::FromStr, time::Duration};
 use wasm_bindgen::{prelude::Closure, JsCast, JsValue, UnwrapThrowExt};
 
 /// Sets a property on a DOM element.
@@ -36,12 +36,26 @@ pub fn location_pathname() -> Option<String> {
 }
 
 /// Helper function to extract [`Event.target`](https://developer.mozilla.org/en-US/docs/Web/API/Event/target)
-/// from any event.
-pub fn event_target<T>(event: &web_sys::Event) -> T
+/// from any event, cast to the given type.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let input = input(cx).on(ev::change, |ev| {
+///   let input = event_target::<web_sys::HtmlInputElement>(&ev);
+///   log!("{} is now {}", input.name(), input.value());
+/// });
+/// # });
+/// ```
+pub fn event_target<T>(event: &impl JsCast) -> T
 where
   T: JsCast,
 {
-  event.target().unwrap_throw().unchecked_into::<T>()
+  event
+    .unchecked_ref::<web_sys::Event>()
+    .target()
+    .unwrap_throw()
+    .unchecked_into::<T>()
 }
 
 /// Helper function to extract `event.target.value` from an event.
@@ -51,22 +65,101 @@ pub fn event_target_value<T>(event: &T) -> String
 where
   T: JsCast,
 {
-  event
-    .unchecked_ref::<web_sys::Event>()
-    .target()
-    .unwrap_throw()
-    .unchecked_into::<web_sys::HtmlInputElement>()
-    .value()
+  event_target::<web_sys::HtmlInputElement>(event).value()
 }
 
 /// Helper function to extract `event.target.checked` from an event.
 ///
 /// This is useful in the `on:change` listeners for an `<input type="checkbox">` element.
-pub fn event_target_checked(ev: &web_sys::Event) -> bool {
-  ev.target()
-    .unwrap()
-    .unchecked_into::<web_sys::HtmlInputElement>()
-    .checked()
+pub fn event_target_checked(event: &impl JsCast) -> bool {
+  event_target::<web_sys::HtmlInputElement>(event).checked()
+}
+
+/// Helper function to extract `event.target.files` from an event.
+///
+/// This is useful in the `on:change` listeners for an `<input type="file">` element.
+/// Returns `None` if the target isn't a file input.
+pub fn event_target_files(event: &impl JsCast) -> Option<web_sys::FileList> {
+  event_target::<web_sys::HtmlInputElement>(event).files()
+}
+
+/// Helper function to extract [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key)
+/// from an event, like `"Enter"` or `"a"`.
+///
+/// This is useful in the `on:keydown` or `on:keyup` listeners for any element.
+pub fn event_key(event: &impl JsCast) -> String {
+  event.unchecked_ref::<web_sys::KeyboardEvent>().key()
+}
+
+/// Helper function to read a value from the
+/// [`dataset`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/dataset)
+/// of `event.target`, or of its closest ancestor that has it, parsed as a `T`.
+///
+/// This is useful when a single listener handles events for a whole list, and
+/// each item has an attribute like `data-user-id`, which may be on an ancestor
+/// of the element that was actually clicked. The `name` is converted in the
+/// same way as for [`HtmlElement::data`](crate::HtmlElement::data).
+///
+/// Returns `None` if no element has the attribute, or if it can't be parsed.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (selected, set_selected) = create_signal(cx, None::<usize>);
+///
+/// let users = ul(cx)
+///   .on(ev::click, move |ev| {
+///     set_selected.set(event_target_data::<usize>(&ev, "user-id"))
+///   })
+///   .child(li(cx).data("user-id", 1).child("Alice"))
+///   .child(li(cx).data("user-id", 2).child("Bob"));
+/// # });
+/// ```
+pub fn event_target_data<T: FromStr>(
+  event: &impl JsCast,
+  name: &str,
+) -> Option<T> {
+  let name = data_attribute_name(name);
+  event
+    .unchecked_ref::<web_sys::Event>()
+    .target()?
+    .dyn_into::<web_sys::Element>()
+    .ok()?
+    .closest(&format!("[{name}]"))
+    .ok()??
+    .get_attribute(&name)?
+    .parse()
+    .ok()
+}
+
+/// Converts the name of a
+/// [`dataset`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/dataset)
+/// value into the name of its attribute, adding the `data-` prefix and
+/// converting `camelCase` to `kebab-case`. Names that already start with
+/// `data-` are left as they are.
+///
+/// ```
+/// # use leptos_dom::*;
+/// assert_eq!(data_attribute_name("userId"), "data-user-id");
+/// assert_eq!(data_attribute_name("user-id"), "data-user-id");
+/// assert_eq!(data_attribute_name("data-user-id"), "data-user-id");
+/// ```
+pub fn data_attribute_name(name: &str) -> String {
+  if name.starts_with("data-") {
+    return name.to_string();
+  }
+
+  let mut attr_name = String::with_capacity(name.len() + 5);
+  attr_name.push_str("data-");
+  for c in name.chars() {
+    if c.is_ascii_uppercase() {
+      attr_name.push('-');
+      attr_name.push(c.to_ascii_lowercase());
+    } else {
+      attr_name.push(c);
+    }
+  }
+  attr_name
 }
 
 /// Runs the given function between the next repaint
@@ -0,0 +1,337 @@
+//! Keyboard shortcuts that are registered for as long as a scope is alive.
+
+use crate::NodeRef;
+use leptos_reactive::{provide_context, Scope};
+use std::{fmt, ops::Deref, rc::Rc, str::FromStr};
+
+/// A keyboard shortcut, parsed from chord syntax like `"mod+k"` or
+/// `"ctrl+shift+p"`.
+///
+/// Modifiers and the key are separated by `+` and are case-insensitive:
+/// - `ctrl` (or `control`), `alt` (or `option`), `shift`, and `meta` (or `cmd`,
+///   `command`, `super`, or `win`) are the usual modifiers.
+/// - `mod` is the platform's primary modifier: `⌘` on Apple platforms and
+///   `Ctrl` everywhere else, so `"mod+s"` is "save" on every platform.
+/// - The key is a [`KeyboardEvent.key`](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key/Key_Values)
+///   value, like `k`, `enter`, or `arrowup`. `esc`, `space`, `plus`, `up`,
+///   `down`, `left`, `right`, `del`, and `return` can be used as shorthand.
+///
+/// ```
+/// # use leptos_dom::*;
+/// let hotkey = "Mod+Shift+K".parse::<Hotkey>().unwrap();
+/// assert!(hotkey.primary && hotkey.shift && !hotkey.alt);
+/// assert_eq!(hotkey.key, "k");
+///
+/// assert_eq!("esc".parse::<Hotkey>().unwrap().key, "escape");
+/// assert_eq!(
+///   "hyper+k".parse::<Hotkey>(),
+///   Err(HotkeyError::UnknownModifier("hyper".to_string()))
+/// );
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Hotkey {
+  /// Whether `Ctrl` must be held.
+  pub ctrl: bool,
+  /// Whether `Alt` (`⌥` on Apple platforms) must be held.
+  pub alt: bool,
+  /// Whether `Shift` must be held.
+  pub shift: bool,
+  /// Whether `Meta` (`⌘` on Apple platforms, `⊞` on Windows) must be held.
+  pub meta: bool,
+  /// Whether the platform's primary modifier must be held.
+  pub primary: bool,
+  /// The key, as a lowercase `KeyboardEvent.key` value.
+  pub key: String,
+}
+
+/// An error while parsing a [Hotkey].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HotkeyError {
+  /// The chord has no key, like `""` or `"ctrl+"`.
+  MissingKey,
+  /// A modifier isn't one of the known modifiers.
+  UnknownModifier(String),
+}
+
+impl fmt::Display for HotkeyError {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    match self {
+      Self::MissingKey => f.write_str("the hotkey has no key"),
+      Self::UnknownModifier(modifier) => {
+        write!(f, "unknown hotkey modifier {modifier:?}")
+      }
+    }
+  }
+}
+
+impl std::error::Error for HotkeyError {}
+
+impl FromStr for Hotkey {
+  type Err = HotkeyError;
+
+  fn from_str(chord: &str) -> Result<Self, Self::Err> {
+    let mut parts = chord.split('+').map(str::trim).collect::<Vec<_>>();
+    let key = match parts.pop() {
+      Some(key) if !key.is_empty() => key.to_lowercase(),
+      _ => return Err(HotkeyError::MissingKey),
+    };
+
+    let mut hotkey = Hotkey {
+      key: match key.as_str() {
+        "esc" => "escape".to_string(),
+        "space" => " ".to_string(),
+        "plus" => "+".to_string(),
+        "up" | "down" | "left" | "right" => format!("arrow{key}"),
+        "del" => "delete".to_string(),
+        "return" => "enter".to_string(),
+        _ => key,
+      },
+      ..Default::default()
+    };
+    for modifier in parts {
+      match modifier.to_lowercase().as_str() {
+        "ctrl" | "control" => hotkey.ctrl = true,
+        "alt" | "option" => hotkey.alt = true,
+        "shift" => hotkey.shift = true,
+        "meta" | "cmd" | "command" | "super" | "win" => hotkey.meta = true,
+        "mod" => hotkey.primary = true,
+        _ => return Err(HotkeyError::UnknownModifier(modifier.to_string())),
+      }
+    }
+    Ok(hotkey)
+  }
+}
+
+/// Options for [use_hotkeys_with_options].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HotkeyOptions {
+  /// Whether the hotkeys also fire while typing in an `<input>`, `<textarea>`,
+  /// `<select>`, or `contenteditable` element. Defaults to `false`.
+  pub in_editable: bool,
+  /// Whether to call `preventDefault()` on the events that match, which stops
+  /// the browser's own shortcut for the same keys. Defaults to `true`.
+  pub prevent_default: bool,
+}
+
+impl Default for HotkeyOptions {
+  fn default() -> Self {
+    Self {
+      in_editable: false,
+      prevent_default: true,
+    }
+  }
+}
+
+/// Calls the handler when one of the given hotkeys is pressed, for as long as
+/// the scope is alive. The hotkeys are separated by commas, like
+/// `"mod+k, ctrl+shift+p"`; see [Hotkey] for the chord syntax. Hotkeys that
+/// can't be parsed are logged as errors and ignored.
+///
+/// Hotkeys don't fire while the user is typing in an editable element. If a
+/// [HotkeyScope] has been provided with [provide_hotkey_scope], they only fire
+/// while focus is inside its element.
+///
+/// Nothing is registered during server rendering.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn CommandPalette(cx: Scope) -> impl IntoView {
+///   let (open, set_open) = create_signal(cx, false);
+///
+///   use_hotkeys(cx, "mod+k", move |_| set_open.update(|open| *open = !*open));
+///   use_hotkeys(cx, "esc", move |_| set_open.set(false));
+///
+///   view! { cx, <div class:hidden=move || !open.get()>"…"</div> }
+/// }
+/// ```
+pub fn use_hotkeys(
+  cx: Scope,
+  hotkeys: &str,
+  handler: impl Fn(web_sys::KeyboardEvent) + 'static,
+) {
+  use_hotkeys_with_options(cx, hotkeys, HotkeyOptions::default(), handler)
+}
+
+/// Like [use_hotkeys], but with the given options.
+pub fn use_hotkeys_with_options(
+  cx: Scope,
+  hotkeys: &str,
+  options: HotkeyOptions,
+  handler: impl Fn(web_sys::KeyboardEvent) + 'static,
+) {
+  let hotkeys = hotkeys
+    .split(',')
+    .filter(|chord| !chord.trim().is_empty())
+    .filter_map(|chord| match chord.parse::<Hotkey>() {
+      Ok(hotkey) => Some(hotkey),
+      Err(e) => {
+        crate::error!("[use_hotkeys] could not parse {chord:?}: {e}");
+        None
+      }
+    })
+    .collect::<Vec<_>>();
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  {
+    let scope = leptos_reactive::use_context::<HotkeyScope>(cx);
+    web::listen(cx, hotkeys, options, scope, handler);
+  }
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  _ = (cx, hotkeys, options, handler);
+}
+
+/// Limits the hotkeys registered with [use_hotkeys] below a scope to the time
+/// while focus is inside an element, like a focused editor or a modal.
+/// Provided with [provide_hotkey_scope].
+#[derive(Clone)]
+pub struct HotkeyScope {
+  // only used in the browser
+  #[cfg_attr(
+    not(all(target_arch = "wasm32", feature = "web")),
+    allow(dead_code)
+  )]
+  element: Rc<dyn Fn() -> Option<web_sys::Element>>,
+}
+
+impl fmt::Debug for HotkeyScope {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("HotkeyScope").finish()
+  }
+}
+
+/// Provides a [HotkeyScope] for the element in the `node_ref`, so hotkeys
+/// registered in this scope, or any scope below it, only fire while focus is
+/// inside that element.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Editor(cx: Scope) -> impl IntoView {
+///   let editor = NodeRef::<HtmlElement<Div>>::new(cx);
+///   provide_hotkey_scope(cx, editor);
+///
+///   // only saves while the editor has focus
+///   use_hotkeys(cx, "mod+s", move |_| log!("saving"));
+///
+///   view! { cx, <div _ref=editor tabindex="0">"…"</div> }
+/// }
+/// ```
+pub fn provide_hotkey_scope<T>(cx: Scope, node_ref: NodeRef<T>)
+where
+  T: Clone + Deref + 'static,
+  T::Target: AsRef<web_sys::Element>,
+{
+  provide_context(
+    cx,
+    HotkeyScope {
+      element: Rc::new(move || {
+        node_ref.get().map(|el| el.deref().as_ref().clone())
+      }),
+    },
+  );
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod web {
+  use super::{Hotkey, HotkeyOptions, HotkeyScope};
+  use leptos_reactive::{on_cleanup, Scope};
+  use wasm_bindgen::{prelude::Closure, JsCast};
+
+  pub(super) fn listen(
+    cx: Scope,
+    hotkeys: Vec<Hotkey>,
+    options: HotkeyOptions,
+    scope: Option<HotkeyScope>,
+    handler: impl Fn(web_sys::KeyboardEvent) + 'static,
+  ) {
+    if hotkeys.is_empty() {
+      return;
+    }
+
+    let apple = is_apple_platform();
+    let listener = Closure::wrap(Box::new(move |ev: web_sys::KeyboardEvent| {
+      if !hotkeys.iter().any(|hotkey| matches(hotkey, &ev, apple)) {
+        return;
+      }
+      let target = ev
+        .target()
+        .and_then(|target| target.dyn_into::<web_sys::Element>().ok());
+      if !options.in_editable && target.as_ref().map_or(false, is_editable) {
+        return;
+      }
+      if let Some(scope) = &scope {
+        let focused_inside = match ((scope.element)(), &target) {
+          (Some(el), Some(target)) => el.contains(Some(target.as_ref())),
+          _ => false,
+        };
+        if !focused_inside {
+          return;
+        }
+      }
+
+      if options.prevent_default {
+        ev.prevent_default();
+      }
+      handler(ev);
+    }) as Box<dyn Fn(web_sys::KeyboardEvent)>);
+
+    let window = crate::window();
+    _ = window.add_event_listener_with_callback(
+      "keydown",
+      listener.as_ref().unchecked_ref(),
+    );
+    on_cleanup(cx, move || {
+      _ = window.remove_event_listener_with_callback(
+        "keydown",
+        listener.as_ref().unchecked_ref(),
+      );
+    });
+  }
+
+  fn matches(
+    hotkey: &Hotkey,
+    ev: &web_sys::KeyboardEvent,
+    apple: bool,
+  ) -> bool {
+    let ctrl = hotkey.ctrl || (hotkey.primary && !apple);
+    let meta = hotkey.meta || (hotkey.primary && apple);
+    if ev.ctrl_key() != ctrl
+      || ev.meta_key() != meta
+      || ev.alt_key() != hotkey.alt
+      || ev.shift_key() != hotkey.shift
+    {
+      return false;
+    }
+
+    if ev.key().to_lowercase() == hotkey.key {
+      return true;
+    }
+    // modifiers like `Alt` change `key` on some layouts (`⌥K` types `˚` on a
+    // Mac), so letters and digits are also matched by their physical key
+    let code = ev.code();
+    let mut chars = hotkey.key.chars();
+    match (chars.next(), chars.next()) {
+      (Some(c), None) if c.is_ascii_alphabetic() => {
+        code == format!("Key{}", c.to_ascii_uppercase())
+      }
+      (Some(c), None) if c.is_ascii_digit() => code == format!("Digit{c}"),
+      _ => false,
+    }
+  }
+
+  fn is_editable(el: &web_sys::Element) -> bool {
+    matches!(el.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT")
+      || el
+        .dyn_ref::<web_sys::HtmlElement>()
+        .map_or(false, |el| el.is_content_editable())
+  }
+
+  fn is_apple_platform() -> bool {
+    let platform = crate::window().navigator().platform().unwrap_or_default();
+    ["Mac", "iPhone", "iPad", "iPod"]
+      .iter()
+      .any(|name| platform.contains(name))
+  }
+}
@@ -27,6 +27,7 @@ cfg_if! {
     }
   } else {
     use crate::hydration::HydrationKey;
+    use crate::macro_helpers::escape_attr_owned;
     use smallvec::{smallvec, SmallVec};
 
     const HTML_ELEMENT_DEREF_UNIMPLEMENTED_MSG: &str =
@@ -44,12 +45,16 @@ cfg_if! {
 }
 
 use crate::{
+  aria::aria_value,
+  data_attribute_name,
   ev::EventDescriptor,
   hydration::HydrationCtx,
-  macro_helpers::{Attribute, Class, IntoAttribute, IntoClass, IntoProperty},
-  Element, Fragment, IntoView, NodeRef, Text, View,
+  macro_helpers::{
+    Attribute, Class, IntoAttribute, IntoClass, IntoClasses, IntoProperty,
+  },
+  Element, Fragment, IntoView, NodeRef, Role, Text, View,
 };
-use leptos_reactive::Scope;
+use leptos_reactive::{Scope, Signal};
 use std::{borrow::Cow, fmt};
 
 /// Trait which allows creating an element tag.
@@ -230,7 +235,7 @@ where
 }
 
 impl<El: ElementDescriptor> HtmlElement<El> {
-  fn new(cx: Scope, element: El) -> Self {
+  pub(crate) fn new(cx: Scope, element: El) -> Self {
     cfg_if! {
       if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
         Self {
@@ -326,7 +331,7 @@ impl<El: ElementDescriptor> HtmlElement<El> {
     {
       let mut this = self;
 
-      this.attrs.push(("id".into(), id));
+      this.attrs.push(("id".into(), escape_attr_owned(id)));
 
       this
     }
@@ -385,7 +390,7 @@ impl<El: ElementDescriptor> HtmlElement<El> {
       }
       match attr {
         Attribute::String(value) => {
-          this.attrs.push((name, value.into()));
+          this.attrs.push((name, escape_attr_owned(value)));
         }
         Attribute::Bool(include) => {
           if include {
@@ -394,16 +399,118 @@ impl<El: ElementDescriptor> HtmlElement<El> {
         }
         Attribute::Option(_, maybe) => {
           if let Some(value) = maybe {
-            this.attrs.push((name, value.into()));
+            this.attrs.push((name, escape_attr_owned(value)));
           }
         }
-        _ => unreachable!(),
+        Attribute::Raw(value) => {
+          this.attrs.push((name, value));
+        }
+        Attribute::Fn(..) => unreachable!(),
       }
 
       this
     }
   }
 
+  /// Adds an attribute to this element if the value is `Some`, and leaves it
+  /// out if the value is `None`.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let title: Option<&str> = None;
+  /// // no `title` attribute is rendered
+  /// let link = a(cx).attr("href", "/about").attr_opt("title", title);
+  /// # });
+  /// ```
+  #[track_caller]
+  pub fn attr_opt(
+    self,
+    name: impl Into<Cow<'static, str>>,
+    value: Option<impl Into<Cow<'static, str>>>,
+  ) -> Self {
+    self.attr(name, value.map(|value| value.into().into_owned()))
+  }
+
+  /// Adds a boolean attribute, like `disabled` or `hidden`, to this element
+  /// while the signal is `true`, and removes it while the signal is `false`.
+  /// When rendering on the server, the attribute is left out if the signal is
+  /// `false`.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let (pending, set_pending) = create_signal(cx, false);
+  /// let save = button(cx).bool_attr("disabled", pending).child("Save");
+  /// # });
+  /// ```
+  #[track_caller]
+  pub fn bool_attr(
+    self,
+    name: impl Into<Cow<'static, str>>,
+    value: impl Into<Signal<bool>>,
+  ) -> Self {
+    let value = value.into();
+    self.attr(name, move || value.get())
+  }
+
+  /// Adds an `aria-*` attribute to this element. The `aria-` prefix can be
+  /// left out of the name. Unlike with [`HtmlElement::attr`], `true` and
+  /// `false` (or a signal of them) are set as the strings `"true"` and
+  /// `"false"`, as ARIA states like `aria-expanded` require.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let (open, set_open) = create_signal(cx, false);
+  ///
+  /// let toggle = button(cx)
+  ///   .aria("expanded", open)
+  ///   .aria("controls", "menu")
+  ///   .child("Menu");
+  /// let menu = ul(cx).id("menu").role(Role::Menu);
+  /// # });
+  /// ```
+  #[track_caller]
+  pub fn aria(
+    self,
+    name: impl Into<Cow<'static, str>>,
+    value: impl IntoAttribute,
+  ) -> Self {
+    let name = name.into();
+    let name = if name.starts_with("aria-") {
+      name
+    } else {
+      format!("aria-{name}").into()
+    };
+    let value = aria_value(value.into_attribute(self.cx));
+
+    self.attr(name, value)
+  }
+
+  /// Adds a `data-*` attribute to this element, which can be read from its
+  /// [`dataset`](https://developer.mozilla.org/en-US/docs/Web/API/HTMLElement/dataset).
+  /// The name can be given in `kebab-case` or `camelCase`, with or without
+  /// the `data-` prefix: `"user-id"`, `"userId"`, and `"data-user-id"` all set
+  /// `data-user-id`. See [`event_target_data`] to read it back in an event
+  /// listener.
+  #[track_caller]
+  pub fn data(
+    self,
+    name: impl Into<Cow<'static, str>>,
+    value: impl IntoAttribute,
+  ) -> Self {
+    let name = data_attribute_name(&name.into());
+
+    self.attr(name, value)
+  }
+
+  /// Sets the [WAI-ARIA role](Role) of this element.
+  #[track_caller]
+  pub fn role(self, role: Role) -> Self {
+    self.attr("role", role)
+  }
+
   /// Adds a class to an element.
   #[track_caller]
   pub fn class(
@@ -446,10 +553,13 @@ impl<El: ElementDescriptor> HtmlElement<El> {
       };
 
       if include {
+        let name = escape_attr_owned(name);
         if let Some((_, ref mut value)) =
           this.attrs.iter_mut().find(|(name, _)| name == "class")
         {
-          *value = format!("{value} {name}").into();
+          if !value.split_whitespace().any(|existing| existing == name) {
+            *value = format!("{value} {name}").into();
+          }
         } else {
           this.attrs.push(("class".into(), name));
         }
@@ -459,6 +569,42 @@ impl<El: ElementDescriptor> HtmlElement<El> {
     }
   }
 
+  /// Adds several classes to an element at once. This accepts anything that
+  /// implements [IntoClasses]: a string with class names separated by spaces,
+  /// `(name, class)` pairs that toggle a class, like `("active", is_active)`,
+  /// and [Vec]s, arrays, or [Option]s of these.
+  ///
+  /// Each class name is only added once, even if it appears more than once or
+  /// was already added with [`HtmlElement::class`]; if the same name is given
+  /// more than once, the first one controls whether it is present.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let (is_active, set_is_active) = create_signal(cx, false);
+  /// let size = "card-large";
+  ///
+  /// let card = div(cx)
+  ///   .classes("card rounded")
+  ///   .classes(vec![size.to_string(), "card".to_string()])
+  ///   .classes(("active", is_active));
+  /// # });
+  /// ```
+  #[track_caller]
+  pub fn classes(self, classes: impl IntoClasses) -> Self {
+    let mut this = self;
+    let mut seen = Vec::new();
+
+    for (name, class) in classes.into_classes(this.cx) {
+      if !seen.contains(&name) {
+        seen.push(name.clone());
+        this = this.class(name, class);
+      }
+    }
+
+    this
+  }
+
   /// Sets a property on an element.
   #[track_caller]
   pub fn prop(
@@ -534,6 +680,35 @@ impl<El: ElementDescriptor> HtmlElement<El> {
     }
   }
 
+  /// Adds each of the given attributes to the element, like [attr](Self::attr).
+  ///
+  /// This can be used to pass attributes that a component received through to
+  /// one of its elements, and is what `{..attrs}` does in the `view` macro.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # use std::borrow::Cow;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let attrs: Vec<(Cow<'static, str>, Attribute)> = vec![
+  ///   ("type".into(), Attribute::String("email".to_string())),
+  ///   ("required".into(), Attribute::Bool(true)),
+  /// ];
+  /// let field = input(cx).attrs(attrs.clone());
+  /// // or, in the view macro
+  /// let field = view! { cx, <input {..attrs}/> };
+  /// # });
+  /// ```
+  #[track_caller]
+  pub fn attrs<N, A>(self, attrs: impl IntoIterator<Item = (N, A)>) -> Self
+  where
+    N: Into<Cow<'static, str>>,
+    A: IntoAttribute,
+  {
+    attrs
+      .into_iter()
+      .fold(self, |el, (name, attr)| el.attr(name, attr))
+  }
+
   /// Adds a child to this element.
   #[track_caller]
   pub fn child(self, child: impl IntoView) -> Self {
@@ -558,6 +733,64 @@ impl<El: ElementDescriptor> HtmlElement<El> {
     }
   }
 
+  /// Adds each item of a collection as a child of this element, in order.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let names = ["Alice", "Bob", "Carol"];
+  /// let list = ul(cx).children(names.map(|name| li(cx).child(name)));
+  /// # });
+  /// ```
+  #[track_caller]
+  pub fn children<V: IntoView>(
+    self,
+    children: impl IntoIterator<Item = V>,
+  ) -> Self {
+    let cx = self.cx;
+    let children = children.into_iter().map(|child| child.into_view(cx));
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+      for child in children {
+        if !HydrationCtx::is_hydrating() {
+          mount_child(MountKind::Append(self.element.as_ref()), &child);
+        }
+      }
+
+      self
+    }
+
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    {
+      let mut this = self;
+
+      this.children.extend(children);
+
+      this
+    }
+  }
+
+  /// Adds a text child that is updated in place whenever the signals read by
+  /// `text` change. See [`dyn_text`](crate::dyn_text).
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let (name, set_name) = create_signal(cx, "world".to_string());
+  /// let greeting = p(cx).child("Hello, ").dyn_text(move || name.get());
+  /// # });
+  /// ```
+  #[track_caller]
+  pub fn dyn_text<T: fmt::Display>(
+    self,
+    text: impl Fn() -> T + 'static,
+  ) -> Self {
+    let text = crate::dyn_text(self.cx, text);
+
+    self.child(text)
+  }
+
   /// Sets the inner HTML of this element from the provided
   /// string slice.
   ///
@@ -565,12 +798,15 @@ impl<El: ElementDescriptor> HtmlElement<El> {
   /// Be very careful when using this method. Always remember to
   /// sanitize the input to avoid a cross-site scripting (XSS)
   /// vulnerability.
+  ///
+  /// In browsers that enforce Trusted Types, the HTML goes through the policy
+  /// registered with [register_trusted_types_policy](crate::register_trusted_types_policy).
   pub fn inner_html(self, html: impl Into<Cow<'static, str>>) -> Self {
     let html = html.into();
 
     #[cfg(all(target_arch = "wasm32", feature = "web"))]
     {
-      self.element.as_ref().set_inner_html(&html);
+      crate::trusted_types::set_inner_html(self.element.as_ref(), &html);
 
       self
     }
@@ -593,6 +829,48 @@ impl<El: ElementDescriptor> HtmlElement<El> {
       this
     }
   }
+
+  /// Calls `f` with this element and returns the result, so that a helper
+  /// function can be used in the middle of a chain of builder methods.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// fn primary<El: ElementDescriptor>(el: HtmlElement<El>) -> HtmlElement<El> {
+  ///   el.class("btn", true).class("btn-primary", true)
+  /// }
+  ///
+  /// let save = button(cx).apply(primary).child("Save");
+  /// # });
+  /// ```
+  pub fn apply(self, f: impl FnOnce(Self) -> Self) -> Self {
+    f(self)
+  }
+
+  /// Calls `f` with this element if `condition` is `true`, and otherwise
+  /// returns the element unchanged. This adds attributes, children, etc.
+  /// conditionally without breaking up a chain of builder methods.
+  ///
+  /// The condition is only checked once, when the element is created. Pass a
+  /// signal to a method like [`HtmlElement::attr`] or [`HtmlElement::class`]
+  /// instead for something that should change over time.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let required = true;
+  /// let input = input(cx)
+  ///   .attr("name", "email")
+  ///   .apply_if(required, |el| el.attr("required", true).aria("required", true));
+  /// # });
+  /// ```
+  pub fn apply_if(self, condition: bool, f: impl FnOnce(Self) -> Self) -> Self {
+    if condition {
+      f(self)
+    } else {
+      self
+    }
+  }
 }
 
 impl<El: ElementDescriptor> IntoView for HtmlElement<El> {
@@ -694,63 +972,13 @@ macro_rules! generate_html_tags {
             let id = HydrationCtx::id();
 
             #[cfg(all(target_arch = "wasm32", feature = "web"))]
-            let element = if HydrationCtx::is_hydrating() {
-              if let Some(el) = crate::document().get_element_by_id(
-                &format!("_{id}")
-              ) {
-                #[cfg(debug_assertions)]
-                assert_eq!(
-                  el.node_name().to_ascii_uppercase(),
-                  stringify!([<$tag:upper>]),
-                  "SSR and CSR elements have the same `TopoId` \
-                    but different node kinds. This is either a \
-                    discrepancy between SSR and CSR rendering
-                    logic, which is considered a bug, or it \
-                    can also be a leptos hydration issue."
-                );
-
-                el.remove_attribute("id").unwrap();
-
-                el.unchecked_into()
-              } else if let Ok(Some(el)) = crate::document().query_selector(
-                &format!("[leptos-hk=_{id}]")
-              ) {
-                #[cfg(debug_assertions)]
-                assert_eq!(
-                  el.node_name().to_ascii_uppercase(),
-                  stringify!([<$tag:upper>]),
-                  "SSR and CSR elements have the same `TopoId` \
-                    but different node kinds. This is either a \
-                    discrepancy between SSR and CSR rendering
-                    logic, which is considered a bug, or it \
-                    can also be a leptos hydration issue."
-                );
-
-                el.remove_attribute("leptos-hk").unwrap();
-
-                el.unchecked_into()
+            let element = [<$tag:upper>].with(|template| {
+              if HydrationCtx::is_hydrating() {
+                crate::hydration::hydrate_element(&id, template)
               } else {
-                gloo::console::warn!(
-                  "element with id",
-                  format!("_{id}"),
-                  "not found, ignoring it for hydration"
-                );
-
-                [<$tag:upper>]
-                  .with(|el|
-                    el.clone_node()
-                      .unwrap()
-                      .unchecked_into()
-                  )
+                template.clone_node().unwrap().unchecked_into()
               }
-            } else {
-              [<$tag:upper>]
-                .with(|el|
-                  el.clone_node()
-                    .unwrap()
-                    .unchecked_into()
-                )
-            };
+            });
 
             Self {
               #[cfg(all(target_arch = "wasm32", feature = "web"))]
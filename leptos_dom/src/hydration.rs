@@ -1,4 +1,8 @@
-use std::{cell::RefCell, fmt::Display};
+use serde::{Deserialize, Serialize};
+use std::{
+  cell::{Cell, RefCell},
+  fmt::Display,
+};
 
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use once_cell::unsync::Lazy as LazyCell;
@@ -19,7 +23,7 @@ thread_local! {
 }
 
 /// A stable identifer within the server-rendering or hydration process.
-#[derive(Clone, Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct HydrationKey {
   /// The key of the previous component.
   pub previous: String,
@@ -42,8 +46,87 @@ impl Default for HydrationKey {
   }
 }
 
+impl std::str::FromStr for HydrationKey {
+  type Err = std::num::ParseIntError;
+
+  /// Parses a key from the way it's displayed, e.g., in the `data-hk`
+  /// attribute of an island.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// let key = "0-3-1".parse::<HydrationKey>().unwrap();
+  /// assert_eq!(key.previous, "0-3-");
+  /// assert_eq!(key.offset, 1);
+  /// assert_eq!(key.to_string(), "0-3-1");
+  /// ```
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    let split = s.rfind('-').map(|idx| idx + 1).unwrap_or_default();
+    let (previous, offset) = s.split_at(split);
+    Ok(Self {
+      previous: previous.to_string(),
+      offset: offset.parse()?,
+    })
+  }
+}
+
+impl HydrationKey {
+  /// The first key of a view rendered with `render_to_string_scoped` or
+  /// mounted with [`mount_scoped`](crate::mount_scoped). Its keys all start with the
+  /// given name, so that they don't collide with those of other apps on the
+  /// same page.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// assert_eq!(HydrationKey::scoped("cart").to_string(), "cart-0-0");
+  /// ```
+  pub fn scoped(name: &str) -> Self {
+    Self {
+      previous: format!("{name}-0-"),
+      offset: 0,
+    }
+  }
+}
+
+/// What hydration does when the HTML rendered on the server doesn't match
+/// the view being hydrated, i.e., when an element has a different tag name
+/// than the one in the view, or isn't found.
+///
+/// An element that isn't found is created, with a warning, unless the
+/// policy is [`Strict`](HydrationErrorPolicy::Strict).
+///
+/// It can be set for the whole app with [`HydrationCtx::set_error_policy`],
+/// or for part of a view with [`HydrationCtx::with_error_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HydrationErrorPolicy {
+  /// Panics when an element has a different tag name, so that mismatches
+  /// are found and fixed. This is the default in debug builds.
+  Panic,
+  /// Panics when an element has a different tag name, or isn't found.
+  Strict,
+  /// Logs a warning, and hydrates the element rendered on the server even if
+  /// it has a different tag name. This is the default in release builds.
+  WarnAndAdopt,
+  /// Logs a warning, and replaces an element with a different tag name with
+  /// a new one that matches the view.
+  WarnAndReplace,
+}
+
+impl Default for HydrationErrorPolicy {
+  fn default() -> Self {
+    #[cfg(debug_assertions)]
+    return Self::Panic;
+
+    #[cfg(not(debug_assertions))]
+    return Self::WarnAndAdopt;
+  }
+}
+
 thread_local!(static ID: RefCell<HydrationKey> = Default::default());
 
+thread_local! {
+  static ERROR_POLICY: Cell<HydrationErrorPolicy> = Default::default();
+}
+
 /// Control and utility methods for hydration.
 pub struct HydrationCtx;
 
@@ -97,6 +180,105 @@ impl HydrationCtx {
     IS_HYDRATING.with(|is_hydrating| **is_hydrating.borrow())
   }
 
+  /// Runs `f` with the hydration keys of a scoped view, hydrating it if the
+  /// server rendered it with the same name, and then resumes the keys and
+  /// hydration state of the main app.
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  pub(crate) fn with_scope<T>(name: &str, f: impl FnOnce() -> T) -> T {
+    let key = HydrationKey::scoped(name);
+    #[cfg(debug_assertions)]
+    let hydrating = crate::document()
+      .get_element_by_id(&format!("_{}0-0", key.previous))
+      .is_some()
+      || crate::document()
+        .get_element_by_id(&format!("_{}0-0o", key.previous))
+        .is_some();
+    #[cfg(not(debug_assertions))]
+    let hydrating = crate::document()
+      .get_element_by_id(&format!("_{}0-0", key.previous))
+      .is_some();
+
+    Self::with_key(key, hydrating, f)
+  }
+
+  /// Runs `f` starting from the given hydration key, hydrating if
+  /// `hydrating` is `true`, and then resumes the keys and hydration state from
+  /// before. Used to hydrate each island from where it was rendered.
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  pub(crate) fn with_key<T>(
+    key: HydrationKey,
+    hydrating: bool,
+    f: impl FnOnce() -> T,
+  ) -> T {
+    let check: fn() -> bool = if hydrating { || true } else { || false };
+
+    let prev_id = ID.with(|id| id.replace(key));
+    let prev_hydrating = IS_HYDRATING
+      .with(|is_hydrating| is_hydrating.replace(LazyCell::new(check)));
+    let value = f();
+    ID.with(|id| *id.borrow_mut() = prev_id);
+    IS_HYDRATING
+      .with(|is_hydrating| *is_hydrating.borrow_mut() = prev_hydrating);
+    value
+  }
+
+  /// Returns the [`HydrationErrorPolicy`] that elements created now are
+  /// hydrated with.
+  pub fn error_policy() -> HydrationErrorPolicy {
+    ERROR_POLICY.with(Cell::get)
+  }
+
+  /// Sets the [`HydrationErrorPolicy`] for the whole app. This should be
+  /// called before the app is hydrated.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// HydrationCtx::set_error_policy(HydrationErrorPolicy::WarnAndReplace);
+  /// assert_eq!(HydrationCtx::error_policy(), HydrationErrorPolicy::WarnAndReplace);
+  /// ```
+  pub fn set_error_policy(policy: HydrationErrorPolicy) {
+    ERROR_POLICY.with(|p| p.set(policy));
+  }
+
+  /// Hydrates the elements created by `f` with the given
+  /// [`HydrationErrorPolicy`], e.g., to tolerate mismatches in a part of the
+  /// page that depends on the browser, while panicking on them elsewhere.
+  ///
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let clock = HydrationCtx::with_error_policy(
+  ///   HydrationErrorPolicy::WarnAndReplace,
+  ///   || p(cx).child("rendered in the browser's time zone"),
+  /// );
+  /// # });
+  ///
+  /// // the previous policy is restored afterwards, even if `f` panics
+  /// let policy = HydrationCtx::error_policy();
+  /// _ = std::panic::catch_unwind(|| {
+  ///   HydrationCtx::with_error_policy(HydrationErrorPolicy::Strict, || {
+  ///     panic!("hydration failed")
+  ///   })
+  /// });
+  /// assert_eq!(HydrationCtx::error_policy(), policy);
+  /// ```
+  pub fn with_error_policy<T>(
+    policy: HydrationErrorPolicy,
+    f: impl FnOnce() -> T,
+  ) -> T {
+    /// Restores the previous policy when dropped, even if `f` panics.
+    struct RestorePolicy(HydrationErrorPolicy);
+
+    impl Drop for RestorePolicy {
+      fn drop(&mut self) {
+        ERROR_POLICY.with(|p| p.set(self.0));
+      }
+    }
+
+    let _restore = RestorePolicy(ERROR_POLICY.with(|p| p.replace(policy)));
+    f()
+  }
+
   pub(crate) fn to_string(id: &HydrationKey, closing: bool) -> String {
     #[cfg(debug_assertions)]
     return format!("_{id}{}", if closing { 'c' } else { 'o' });
@@ -109,3 +291,70 @@ impl HydrationCtx {
     }
   }
 }
+
+/// Finds the element rendered on the server with the given `id`, and checks
+/// that it has the same tag name as `template`, handling a mismatch as the
+/// current [`HydrationErrorPolicy`] says. Returns a copy of `template` if
+/// there is no element to hydrate.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn hydrate_element(
+  id: &HydrationKey,
+  template: &web_sys::HtmlElement,
+) -> web_sys::HtmlElement {
+  use wasm_bindgen::JsCast;
+
+  let policy = HydrationCtx::error_policy();
+  let create = || {
+    template
+      .clone_node()
+      .unwrap()
+      .unchecked_into::<web_sys::HtmlElement>()
+  };
+
+  let (el, attr) =
+    if let Some(el) = crate::document().get_element_by_id(&format!("_{id}")) {
+      (el, "id")
+    } else if let Ok(Some(el)) =
+      crate::document().query_selector(&format!("[leptos-hk=_{id}]"))
+    {
+      (el, "leptos-hk")
+    } else {
+      let message =
+        format!("element with id _{id} not found, ignoring it for hydration");
+      if policy == HydrationErrorPolicy::Strict {
+        panic!("{message}");
+      }
+      crate::logging::hydration_miss(message);
+      return create();
+    };
+
+  el.remove_attribute(attr).unwrap();
+
+  if el.node_name().eq_ignore_ascii_case(&template.node_name()) {
+    return el.unchecked_into();
+  }
+
+  let message = format!(
+    "element with id _{id} is a <{}> in the HTML rendered on the server, but \
+     a <{}> in the view. This is either a discrepancy between SSR and CSR \
+     rendering logic, which is considered a bug, or it can also be a leptos \
+     hydration issue.",
+    el.node_name().to_ascii_lowercase(),
+    template.node_name().to_ascii_lowercase()
+  );
+  match policy {
+    HydrationErrorPolicy::Panic | HydrationErrorPolicy::Strict => {
+      panic!("{message}")
+    }
+    HydrationErrorPolicy::WarnAndAdopt => {
+      crate::logging::hydration_miss(message);
+      el.unchecked_into()
+    }
+    HydrationErrorPolicy::WarnAndReplace => {
+      crate::logging::hydration_miss(message);
+      let new_el = create();
+      _ = el.replace_with_with_node_1(&new_el);
+      new_el
+    }
+  }
+}
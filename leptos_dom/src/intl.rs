@@ -0,0 +1,397 @@
+//! Locale-aware formatting of numbers and dates.
+
+use leptos_reactive::{
+  create_signal, provide_context, use_context, Scope, Signal,
+};
+
+/// The locale used when none has been provided with [provide_locale].
+const DEFAULT_LOCALE: &str = "en-US";
+
+#[derive(Clone, Copy)]
+struct LocaleContext(Signal<String>);
+
+/// Provides the locale, as a [BCP 47 language tag](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl#locales_argument)
+/// like `"de-DE"`, that [use_formatter] and [use_locale] read in this scope and
+/// its children. When it changes, everything formatted with it is updated.
+///
+/// The locale should be the same on the server and in the browser, e.g., taken
+/// from the URL or a cookie, so that hydration finds the same text.
+pub fn provide_locale(cx: Scope, locale: impl Into<Signal<String>>) {
+  provide_context(cx, LocaleContext(locale.into()));
+}
+
+/// Returns the locale provided with [provide_locale], or `"en-US"` if there
+/// isn't one.
+pub fn use_locale(cx: Scope) -> Signal<String> {
+  use_context::<LocaleContext>(cx)
+    .map(|locale| locale.0)
+    .unwrap_or_else(|| create_signal(cx, DEFAULT_LOCALE.to_string()).0.into())
+}
+
+/// Returns a [Formatter] for numbers and dates in the locale provided with
+/// [provide_locale]. Reading the formatter's output in a reactive closure
+/// updates it when the locale changes.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (locale, set_locale) = create_signal(cx, "en-US".to_string());
+/// provide_locale(cx, locale);
+/// let format = use_formatter(cx);
+///
+/// assert_eq!(format.number(1234.5), "1,234.5");
+/// set_locale.set("de-DE".to_string());
+/// assert_eq!(format.number(1234.5), "1.234,5");
+///
+/// view! { cx, <p>"Total: " {move || format.number(1234.5)}</p> }
+/// # ;
+/// # });
+/// ```
+pub fn use_formatter(cx: Scope) -> Formatter {
+  Formatter {
+    locale: use_locale(cx),
+    utc_offset_minutes: 0,
+  }
+}
+
+/// Options for [Formatter::number_with], which match those of
+/// [`Intl.NumberFormat`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl/NumberFormat/NumberFormat).
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberOptions {
+  /// The fewest digits shown after the decimal separator. Defaults to `0`.
+  pub min_fraction_digits: u8,
+  /// The most digits shown after the decimal separator, up to `20`; the number
+  /// is rounded to this many digits, with halves rounded away from zero.
+  /// Defaults to `3`.
+  pub max_fraction_digits: u8,
+  /// Whether to separate groups of thousands. Defaults to `true`.
+  pub grouping: bool,
+}
+
+impl Default for NumberOptions {
+  fn default() -> Self {
+    Self {
+      min_fraction_digits: 0,
+      max_fraction_digits: 3,
+      grouping: true,
+    }
+  }
+}
+
+/// Formats numbers and dates for a locale, created with [use_formatter].
+///
+/// In the browser, this uses the [`Intl`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Intl)
+/// API. On the server, and while hydrating, it uses a built-in implementation
+/// that gives the same results as `Intl` for common locales, including English,
+/// German, French, Spanish, Italian, Portuguese, Dutch, Russian, Polish,
+/// Swedish, Japanese, and Chinese, so hydration doesn't find different text
+/// than the server rendered. Other locales are formatted like their language,
+/// or like `en-US` if it isn't known.
+///
+/// Dates are shown as numbers, like `01/15/2023`, in UTC unless another offset
+/// is set with [Formatter::with_utc_offset], because the server doesn't know
+/// the user's time zone.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Formatter {
+  locale: Signal<String>,
+  utc_offset_minutes: i32,
+}
+
+impl Formatter {
+  /// Shows dates at the given offset from UTC, in minutes, e.g., `60` for
+  /// Central European Time.
+  pub fn with_utc_offset(mut self, minutes: i32) -> Self {
+    self.utc_offset_minutes = minutes;
+    self
+  }
+
+  /// The current locale.
+  pub fn locale(&self) -> String {
+    self.locale.get()
+  }
+
+  /// Formats a number with separators for thousands and at most three
+  /// fraction digits, like `1,234.5` in English.
+  pub fn number(&self, value: f64) -> String {
+    self.number_with(value, NumberOptions::default())
+  }
+
+  /// Formats a number with the given options.
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let format = use_formatter(cx);
+  /// let price = NumberOptions {
+  ///   min_fraction_digits: 2,
+  ///   max_fraction_digits: 2,
+  ///   ..Default::default()
+  /// };
+  /// assert_eq!(format.number_with(1999.0, price), "1,999.00");
+  /// # });
+  /// ```
+  pub fn number_with(&self, value: f64, options: NumberOptions) -> String {
+    let locale = self.locale.get();
+    let options = NumberOptions {
+      min_fraction_digits: options.min_fraction_digits.min(20),
+      max_fraction_digits: options
+        .max_fraction_digits
+        .max(options.min_fraction_digits)
+        .min(20),
+      ..options
+    };
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    if !crate::HydrationCtx::is_hydrating() {
+      if let Some(formatted) = intl::number(&locale, value, &options) {
+        return formatted;
+      }
+    }
+
+    format_number(LocaleData::for_locale(&locale), value, &options)
+  }
+
+  /// Formats a date, given as milliseconds since the Unix epoch like
+  /// JavaScript's `Date.now()`, as numbers, like `01/15/2023` in American
+  /// English or `15.01.2023` in German.
+  /// ```
+  /// # use leptos::*;
+  /// # run_scope(create_runtime(), |cx| {
+  /// let format = use_formatter(cx);
+  /// // 2023-01-15T23:30:00Z
+  /// assert_eq!(format.date(1_673_825_400_000.0), "01/15/2023");
+  /// assert_eq!(
+  ///   format.with_utc_offset(60).date(1_673_825_400_000.0),
+  ///   "01/16/2023"
+  /// );
+  /// # });
+  /// ```
+  pub fn date(&self, timestamp_ms: f64) -> String {
+    let locale = self.locale.get();
+    // shifting the time lets both implementations show it in UTC
+    let timestamp_ms = timestamp_ms + self.utc_offset_minutes as f64 * 60_000.0;
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    if !crate::HydrationCtx::is_hydrating() {
+      if let Some(formatted) = intl::date(&locale, timestamp_ms) {
+        return formatted;
+      }
+    }
+
+    format_date(LocaleData::for_locale(&locale), timestamp_ms)
+  }
+}
+
+#[derive(Clone, Copy)]
+enum DateOrder {
+  DayMonthYear,
+  MonthDayYear,
+  YearMonthDay,
+}
+
+/// How a locale formats numbers and dates, as `Intl` does.
+struct LocaleData {
+  group: &'static str,
+  decimal: &'static str,
+  minus: &'static str,
+  /// The fewest digits before the highest group separator, e.g., `2` for
+  /// locales that write `1234` but `12.345`.
+  min_grouping_digits: usize,
+  date_order: DateOrder,
+  date_separator: &'static str,
+}
+
+impl LocaleData {
+  const fn new(
+    group: &'static str,
+    decimal: &'static str,
+    min_grouping_digits: usize,
+    date_order: DateOrder,
+    date_separator: &'static str,
+  ) -> Self {
+    Self {
+      group,
+      decimal,
+      minus: "-",
+      min_grouping_digits,
+      date_order,
+      date_separator,
+    }
+  }
+
+  fn for_locale(locale: &str) -> Self {
+    use DateOrder::*;
+
+    let locale = locale.to_ascii_lowercase();
+    let language = locale.split(['-', '_']).next().unwrap_or_default();
+    match (locale.as_str(), language) {
+      ("en-gb" | "en-au" | "en-nz" | "en-ie", _) => {
+        Self::new(",", ".", 1, DayMonthYear, "/")
+      }
+      (_, "de") => Self::new(".", ",", 1, DayMonthYear, "."),
+      (_, "fr") => Self::new("\u{202f}", ",", 1, DayMonthYear, "/"),
+      (_, "es") => Self::new(".", ",", 2, DayMonthYear, "/"),
+      (_, "it" | "pt") => Self::new(".", ",", 1, DayMonthYear, "/"),
+      (_, "nl") => Self::new(".", ",", 1, DayMonthYear, "-"),
+      (_, "ru") => Self::new("\u{a0}", ",", 1, DayMonthYear, "."),
+      (_, "pl") => Self::new("\u{a0}", ",", 2, DayMonthYear, "."),
+      (_, "sv") => Self {
+        minus: "\u{2212}",
+        ..Self::new("\u{a0}", ",", 1, YearMonthDay, "-")
+      },
+      (_, "ja" | "zh") => Self::new(",", ".", 1, YearMonthDay, "/"),
+      _ => Self::new(",", ".", 1, MonthDayYear, "/"),
+    }
+  }
+}
+
+fn format_number(
+  locale: LocaleData,
+  value: f64,
+  options: &NumberOptions,
+) -> String {
+  if value.is_nan() {
+    return "NaN".to_string();
+  }
+  let sign = if value < 0.0 { locale.minus } else { "" };
+  if value.is_infinite() {
+    return format!("{sign}∞");
+  }
+
+  // round halves away from zero, as Intl does
+  let scale = 10_u128.pow(options.max_fraction_digits as u32);
+  let scaled = (value.abs() * scale as f64).round() as u128;
+  let (int, fraction) = (scaled / scale, scaled % scale);
+  let sign = if scaled == 0 { "" } else { sign };
+
+  let int = int.to_string();
+  let int = if options.grouping && int.len() >= 3 + locale.min_grouping_digits {
+    let mut grouped = String::new();
+    for (idx, digit) in int.chars().enumerate() {
+      if idx > 0 && (int.len() - idx) % 3 == 0 {
+        grouped.push_str(locale.group);
+      }
+      grouped.push(digit);
+    }
+    grouped
+  } else {
+    int
+  };
+
+  let mut fraction = if options.max_fraction_digits > 0 {
+    format!(
+      "{fraction:0width$}",
+      width = options.max_fraction_digits as usize
+    )
+  } else {
+    String::new()
+  };
+  while fraction.len() > options.min_fraction_digits as usize
+    && fraction.ends_with('0')
+  {
+    fraction.pop();
+  }
+
+  if fraction.is_empty() {
+    format!("{sign}{int}")
+  } else {
+    format!("{sign}{int}{}{fraction}", locale.decimal)
+  }
+}
+
+fn format_date(locale: LocaleData, timestamp_ms: f64) -> String {
+  let days = (timestamp_ms / 86_400_000.0).floor() as i64;
+  let (year, month, day) = civil_from_days(days);
+  let (month, day) = (format!("{month:02}"), format!("{day:02}"));
+  let year = year.to_string();
+  let parts = match locale.date_order {
+    DateOrder::DayMonthYear => [day, month, year],
+    DateOrder::MonthDayYear => [month, day, year],
+    DateOrder::YearMonthDay => [year, month, day],
+  };
+  parts.join(locale.date_separator)
+}
+
+/// Converts days since the Unix epoch into a `(year, month, day)` date, using
+/// Howard Hinnant's algorithm for the proleptic Gregorian calendar.
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+  let z = days + 719_468;
+  let era = z.div_euclid(146_097);
+  let doe = z.rem_euclid(146_097);
+  let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+  let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+  let mp = (5 * doy + 2) / 153;
+  let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+  let month = (if mp < 10 { mp + 3 } else { mp - 9 }) as u32;
+  let year = yoe + era * 400 + i64::from(month <= 2);
+  (year, month, day)
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod intl {
+  use super::NumberOptions;
+  use wasm_bindgen::{JsCast, JsValue};
+
+  pub(super) fn number(
+    locale: &str,
+    value: f64,
+    options: &NumberOptions,
+  ) -> Option<String> {
+    format(
+      "NumberFormat",
+      locale,
+      &[
+        (
+          "minimumFractionDigits",
+          JsValue::from(options.min_fraction_digits),
+        ),
+        (
+          "maximumFractionDigits",
+          JsValue::from(options.max_fraction_digits),
+        ),
+        ("useGrouping", JsValue::from(options.grouping)),
+      ],
+      &JsValue::from(value),
+    )
+  }
+
+  pub(super) fn date(locale: &str, timestamp_ms: f64) -> Option<String> {
+    format(
+      "DateTimeFormat",
+      locale,
+      &[
+        ("timeZone", JsValue::from_str("UTC")),
+        ("year", JsValue::from_str("numeric")),
+        ("month", JsValue::from_str("2-digit")),
+        ("day", JsValue::from_str("2-digit")),
+      ],
+      &js_sys::Date::new(&JsValue::from(timestamp_ms)),
+    )
+  }
+
+  /// Formats the value with a new `Intl` formatter, or returns `None` if the
+  /// formatter couldn't be created, e.g., because the locale is invalid.
+  fn format(
+    constructor: &str,
+    locale: &str,
+    options: &[(&str, JsValue)],
+    value: &JsValue,
+  ) -> Option<String> {
+    let intl = js_sys::Reflect::get(&js_sys::global(), &"Intl".into()).ok()?;
+    let constructor = js_sys::Reflect::get(&intl, &constructor.into()).ok()?;
+    let opts = js_sys::Object::new();
+    for (name, value) in options {
+      js_sys::Reflect::set(&opts, &JsValue::from_str(name), value).ok()?;
+    }
+    let formatter = js_sys::Reflect::construct(
+      constructor.dyn_ref()?,
+      &js_sys::Array::of2(&JsValue::from_str(locale), &opts),
+    )
+    .ok()?;
+    let format = js_sys::Reflect::get(&formatter, &"format".into()).ok()?;
+    format
+      .dyn_ref::<js_sys::Function>()?
+      .call1(&formatter, value)
+      .ok()?
+      .as_string()
+  }
+}
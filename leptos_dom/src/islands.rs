@@ -0,0 +1,252 @@
+//! Records which islands a server-rendered page contains, so that only
+//! their bundles are loaded in the browser, and only their HTML is hydrated.
+
+use crate::{HydrationCtx, HydrationKey, IntoView, View};
+use leptos_reactive::Scope;
+use std::{borrow::Cow, cell::RefCell, fmt::Write, rc::Rc};
+
+/// The tag name of the element that wraps each island rendered on the server.
+const ISLAND_TAG: &str = "leptos-island";
+
+/// The bundles of the islands that were rendered on a page, in the order they
+/// were first rendered, and where each island was rendered.
+///
+/// In islands mode, most of the page is only rendered on the server, and the
+/// interactive components (the islands) are compiled into bundles of their
+/// own. A server integration provides the manifest as a context while
+/// rendering, and then uses [`IslandManifest::bootstrap_script`] to load just
+/// the bundles that the page needs, instead of the whole app.
+///
+/// Each island is rendered inside a `<leptos-island>` element, which records
+/// its bundle and the range of hydration keys its HTML was rendered with. A
+/// bundle's `hydrate` function calls [`hydrate_islands`], which hydrates only
+/// the HTML inside those elements.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let manifest = IslandManifest::default();
+/// provide_context(cx, manifest.clone());
+///
+/// register_island(cx, "comments", || view! { cx, <p>"No comments"</p> });
+/// register_island(cx, "search", || view! { cx, <input/> });
+/// register_island(cx, "comments", || view! { cx, <p>"One comment"</p> });
+/// assert_eq!(manifest.bundles(), vec!["comments", "search"]);
+/// assert_eq!(manifest.islands().len(), 3);
+///
+/// let nonce = Nonce::new("r4nd0m");
+/// let script = manifest.bootstrap_script(Some(&nonce), |bundle| {
+///   (format!("/pkg/{bundle}.js"), format!("/pkg/{bundle}_bg.wasm"))
+/// });
+/// assert!(script.starts_with(r#"<script type="module" nonce="r4nd0m">"#));
+/// assert!(script.contains(r#"import("/pkg/search.js")"#));
+/// # });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct IslandManifest(Rc<RefCell<Manifest>>);
+
+#[derive(Debug, Default)]
+struct Manifest {
+  bundles: Vec<Cow<'static, str>>,
+  islands: Vec<RenderedIsland>,
+}
+
+/// An island that was rendered on the server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderedIsland {
+  /// The bundle the island is compiled into.
+  pub bundle: Cow<'static, str>,
+  /// The hydration key of the first element of the island.
+  pub start: HydrationKey,
+  /// The hydration key after the last element of the island.
+  pub end: HydrationKey,
+}
+
+impl IslandManifest {
+  /// The bundles that were registered while rendering.
+  pub fn bundles(&self) -> Vec<Cow<'static, str>> {
+    self.0.borrow().bundles.clone()
+  }
+
+  /// Each island that was rendered, in the order it was rendered.
+  pub fn islands(&self) -> Vec<RenderedIsland> {
+    self.0.borrow().islands.clone()
+  }
+
+  /// Whether no islands were rendered, in which case the page doesn't need
+  /// to load any bundles.
+  pub fn is_empty(&self) -> bool {
+    self.0.borrow().bundles.is_empty()
+  }
+
+  /// Adds a bundle, unless it was already added.
+  pub fn insert(&self, bundle: impl Into<Cow<'static, str>>) {
+    let bundle = bundle.into();
+    let bundles = &mut self.0.borrow_mut().bundles;
+    if !bundles.contains(&bundle) {
+      bundles.push(bundle);
+    }
+  }
+
+  /// Returns a `<script>` that loads each bundle and calls its `hydrate`
+  /// function, given the [Nonce](crate::Nonce) of the page's Content Security
+  /// Policy, if it has one, and a function that returns the paths of a bundle's JavaScript
+  /// and WebAssembly files. The bundles are loaded in parallel.
+  pub fn bootstrap_script(
+    &self,
+    nonce: Option<&crate::Nonce>,
+    paths: impl Fn(&str) -> (String, String),
+  ) -> String {
+    let manifest = self.0.borrow();
+    if manifest.bundles.is_empty() {
+      return String::new();
+    }
+
+    let mut script = format!(
+      r#"<script type="module"{}>"#,
+      nonce.map(crate::Nonce::to_attr).unwrap_or_default()
+    );
+    for bundle in manifest.bundles.iter() {
+      let (js_path, wasm_path) = paths(bundle);
+      _ = write!(
+        script,
+        "import({}).then(m => m.default({}).then(() => m.hydrate()));",
+        js_string(&js_path),
+        js_string(&wasm_path)
+      );
+    }
+    script.push_str("</script>");
+    script
+  }
+}
+
+/// Renders an island, which is compiled into the given bundle. This is
+/// called by components declared with `#[component(island = "bundle")]`.
+///
+/// If the server is rendering with an [`IslandManifest`], the island is
+/// recorded in it, and its view is wrapped in a `<leptos-island>` element
+/// that marks the bundle and hydration keys of its HTML. Otherwise, and in
+/// the browser, the view is returned as it is.
+pub fn register_island<V: IntoView>(
+  cx: Scope,
+  bundle: &'static str,
+  view: impl FnOnce() -> V,
+) -> View {
+  cfg_if::cfg_if! {
+    if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+      _ = bundle;
+      view().into_view(cx)
+    } else {
+      let manifest = match leptos_reactive::use_context::<IslandManifest>(cx) {
+        Some(manifest) => manifest,
+        None => return view().into_view(cx),
+      };
+
+      // the wrapper takes a key of its own, so the island's keys start after
+      // it, as they do when the island is hydrated inside it
+      let root = crate::HtmlElement::new(
+        cx,
+        crate::html::AnyElement {
+          name: ISLAND_TAG.into(),
+          is_void: false,
+          id: HydrationCtx::id(),
+        },
+      );
+      let start = HydrationCtx::peek();
+      let view = view().into_view(cx);
+      let end = HydrationCtx::peek();
+
+      manifest.insert(bundle);
+      manifest.0.borrow_mut().islands.push(RenderedIsland {
+        bundle: bundle.into(),
+        start: start.clone(),
+        end: end.clone(),
+      });
+
+      root
+        .attr("data-bundle", bundle)
+        .attr("data-hk", start.to_string())
+        .attr("data-hk-end", end.to_string())
+        .attr("style", "display: contents")
+        .child(view)
+        .into_view(cx)
+    }
+  }
+}
+
+/// Hydrates each island of the given bundle that was rendered on the page,
+/// with the view that `f` returns. This is meant to be called by the
+/// `hydrate` function that a bundle exports:
+///
+/// ```no_run
+/// # use leptos::*;
+/// # #[component(island = "comments")]
+/// # fn Comments(cx: Scope) -> impl IntoView { view! { cx, <p/> } }
+/// // #[wasm_bindgen]
+/// pub fn hydrate() {
+///   hydrate_islands("comments", |cx| view! { cx, <Comments/> });
+/// }
+/// ```
+///
+/// Only the HTML inside each island's `<leptos-island>` element is hydrated,
+/// starting from the hydration key it was rendered with. Each island gets a
+/// root [`Scope`] of its own, which lives as long as the page.
+pub fn hydrate_islands<F, N>(bundle: &str, f: F)
+where
+  F: Fn(Scope) -> N + Clone + 'static,
+  N: IntoView,
+{
+  cfg_if::cfg_if! {
+    if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+      use wasm_bindgen::JsCast;
+
+      crate::events::enable_window_delegation();
+      let roots = crate::document().get_elements_by_tag_name(ISLAND_TAG);
+      // collect them first, because hydrating moves nodes around
+      let roots = (0..roots.length())
+        .filter_map(|idx| roots.item(idx))
+        .filter(|root| {
+          root.get_attribute("data-bundle").as_deref() == Some(bundle)
+        })
+        .collect::<Vec<_>>();
+
+      for root in roots {
+        let start = match root
+          .get_attribute("data-hk")
+          .and_then(|key| key.parse::<HydrationKey>().ok())
+        {
+          Some(start) => start,
+          None => {
+            crate::warn!(
+              "[hydrate_islands] an island of `{bundle}` has no hydration \
+               key"
+            );
+            continue;
+          }
+        };
+        let root = root.unchecked_into::<web_sys::HtmlElement>();
+        let f = f.clone();
+        let (_, disposer) = HydrationCtx::with_key(start, true, move || {
+          crate::mount_view(leptos_reactive::create_runtime(), root, f)
+        });
+        // the island lives as long as the page
+        std::mem::forget(disposer);
+      }
+    } else {
+      _ = bundle;
+      _ = f;
+      crate::warn!(
+        "`hydrate_islands` should not be called outside the browser."
+      );
+    }
+  }
+}
+
+/// Quotes a string for JavaScript, so that it can't end the string, or the
+/// `<script>` that it's in.
+fn js_string(value: &str) -> String {
+  serde_json::to_string(value)
+    .unwrap_or_default()
+    .replace("</", r"<\/")
+    .replace("<!--", r"<\!--")
+}
@@ -7,40 +7,85 @@
 #[cfg_attr(debug_assertions, macro_use)]
 pub extern crate tracing;
 
+mod aria;
+mod clipboard;
 mod components;
+mod critical_css;
+mod deferred;
+pub mod dnd;
+mod errors;
 mod events;
+mod geolocation;
 mod helpers;
+mod hotkeys;
 mod html;
 mod hydration;
-mod logging;
+mod intl;
+mod islands;
+pub mod logging;
 mod macro_helpers;
+mod media_query;
 mod node_ref;
+mod nonce;
+mod page_lifecycle;
+mod permissions;
+mod renderer;
+mod serialized_view;
 mod ssr;
+mod timers;
 mod transparent;
+mod trusted_types;
+mod url;
+mod utilities;
 
+pub use aria::*;
 use cfg_if::cfg_if;
+pub use clipboard::*;
 pub use components::*;
-pub use events::typed as ev;
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+pub use critical_css::*;
+pub use deferred::*;
+pub use errors::*;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use events::{add_event_listener, add_event_listener_undelegated};
+pub use events::{metrics::*, typed as ev};
+pub use geolocation::*;
 pub use helpers::*;
+pub use hotkeys::*;
 pub use html::*;
-pub use hydration::{HydrationCtx, HydrationKey};
+pub use hydration::{HydrationCtx, HydrationErrorPolicy, HydrationKey};
+pub use intl::*;
+pub use islands::*;
 pub use js_sys;
 use leptos_reactive::Scope;
 pub use logging::*;
-pub use macro_helpers::{IntoAttribute, IntoClass, IntoProperty};
+pub use macro_helpers::{
+  escape_attr, spread_attributes_to_string, Attribute, IntoAttribute,
+  IntoClass, IntoClasses, IntoProperty, RawAttribute,
+};
+pub use media_query::*;
 pub use node_ref::*;
+pub use nonce::*;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use once_cell::unsync::Lazy as LazyCell;
+pub use page_lifecycle::*;
+pub use permissions::*;
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+pub use renderer::*;
+#[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+pub use serialized_view::*;
 #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
 use smallvec::SmallVec;
 #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
 pub use ssr::*;
-use std::{borrow::Cow, fmt};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
-use std::{cell::RefCell, rc::Rc};
+use std::cell::RefCell;
+use std::{borrow::Cow, fmt, rc::Rc};
+pub use timers::*;
 pub use transparent::*;
+pub use trusted_types::*;
+pub use url::*;
+pub use utilities::*;
 pub use wasm_bindgen;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use wasm_bindgen::JsCast;
@@ -112,6 +157,8 @@ where
   }
 }
 
+/// Renders the function's result, rendering it again whenever a signal it
+/// reads changes. This includes boxed functions like `Box<dyn Fn() -> V>`.
 impl<F, N> IntoView for F
 where
   F: Fn() -> N + 'static,
@@ -301,11 +348,9 @@ impl Comment {
 
           marker.remove();
         } else {
-          gloo::console::warn!(
-            "component with id",
-            id,
-            "not found, ignoring it for hydration"
-          );
+          logging::hydration_miss(format!(
+            "component with id {id} not found, ignoring it for hydration"
+          ));
         }
       }
     }
@@ -533,6 +578,60 @@ impl View {
     }
   }
 
+  /// Returns the element this [`View`] is mounted in, if it has been
+  /// mounted. Always returns `None` outside the browser.
+  pub fn parent_element(&self) -> Option<web_sys::Element> {
+    cfg_if! {
+      if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+        match self {
+          Self::Transparent(_) => None,
+          _ => self.get_closing_node().parent_element(),
+        }
+      } else {
+        None
+      }
+    }
+  }
+
+  /// Returns the first element in this [`View`] that matches the CSS
+  /// selector, including the view's own top-level elements. Always
+  /// returns `None` outside the browser.
+  pub fn query_selector(&self, selector: &str) -> Option<web_sys::Element> {
+    cfg_if! {
+      if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+        match self {
+          Self::Element(el) => {
+            if el.element.matches(selector).unwrap_or(false) {
+              Some(el.element.clone().unchecked_into())
+            } else {
+              el.element.query_selector(selector).ok().flatten()
+            }
+          }
+          Self::Component(c) => c
+            .children
+            .iter()
+            .find_map(|child| child.query_selector(selector)),
+          Self::CoreComponent(CoreComponent::DynChild(c)) => {
+            match &**c.child.borrow() {
+              Some(child) => child.query_selector(selector),
+              None => None,
+            }
+          }
+          Self::CoreComponent(CoreComponent::Each(c)) => c
+            .children
+            .borrow()
+            .iter()
+            .flatten()
+            .find_map(|item| item.child.query_selector(selector)),
+          _ => None,
+        }
+      } else {
+        _ = selector;
+        None
+      }
+    }
+  }
+
   /// Adds an event listener, analogous to [`HtmlElement::on`].
   ///
   /// This method will attach an event listener to **all** child
@@ -672,7 +771,7 @@ where
 {
   cfg_if! {
     if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-      mount_to(crate::document().body().expect("body element to exist"), f)
+      mount_to(crate::document().body().expect("body element to exist"), f);
     } else {
       _ = f;
       crate::warn!("`mount_to_body` should not be called outside the browser.");
@@ -680,36 +779,204 @@ where
   }
 }
 
-/// Runs the provided closure and mounts the result to the provided element.
-pub fn mount_to<F, N>(parent: web_sys::HtmlElement, f: F)
+/// Runs the provided closure and mounts the result to the provided element,
+/// returning a [`MountHandle`] that can unmount it again.
+///
+/// Each call creates a separate root [`Scope`], so several independent views
+/// can be mounted to different elements of the same page, e.g., to embed
+/// widgets into a page that was rendered by another framework. They still
+/// share one reactive runtime; use [`mount_scoped`] to isolate them.
+///
+/// ```no_run
+/// # use leptos::*;
+/// let target = document()
+///   .get_element_by_id("comments")
+///   .unwrap()
+///   .unchecked_into::<web_sys::HtmlElement>();
+/// let comments = mount_to(target, |cx| view! { cx, <p>"No comments yet."</p> });
+///
+/// // later, e.g., when the widget is closed
+/// comments.unmount();
+/// ```
+pub fn mount_to<F, N>(parent: web_sys::HtmlElement, f: F) -> MountHandle
 where
   F: FnOnce(Scope) -> N + 'static,
   N: IntoView,
 {
   cfg_if! {
     if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
-      let disposer = leptos_reactive::create_scope(
-        leptos_reactive::create_runtime(),
-        move |cx| {
-          let node = f(cx).into_view(cx);
+      events::enable_window_delegation();
+      let (nodes, disposer) =
+        mount_view(leptos_reactive::create_runtime(), parent, f);
 
-          HydrationCtx::stop_hydrating();
-
-          parent.append_child(&node.get_mountable_node()).unwrap();
+      MountHandle { nodes, disposer, scoped: None }
+    } else {
+      _ = parent;
+      _ = f;
+      crate::warn!("`mount_to` should not be called outside the browser.");
 
-          std::mem::forget(node);
-        },
-      );
+      MountHandle {}
+    }
+  }
+}
 
-      std::mem::forget(disposer);
+/// Mounts a view like [`mount_to`], in a reactive runtime of its own that is
+/// isolated from the rest of the page.
+///
+/// This is meant for micro-frontends: independent widgets, possibly built by
+/// different teams and loaded as separate bundles, that are embedded in the
+/// same host page. Each scoped widget
+/// 1. has its own runtime, created with
+///    [`create_scoped_runtime`](leptos_reactive::create_scoped_runtime), so
+///    its signals, effects, contexts and resources are separate from everyone
+///    else's, and `name` identifies it in
+///    [`Diagnostic`](leptos_reactive::Diagnostic)s;
+/// 2. handles its events from `parent` rather than the `window`, so that the
+///    event handlers of different widgets never run for each other's elements,
+///    or twice; and
+/// 3. uses hydration keys that start with `name`, so that it hydrates the HTML
+///    rendered by `render_to_string_scoped` with the same name, even next to
+///    other apps.
+///
+/// Signals can be shared with the host or other widgets explicitly, with a
+/// [`SignalBridge`](leptos_reactive::SignalBridge). The `name` should be
+/// unique on the page, and only contain letters, digits, `-` and `_`.
+/// Unmounting the widget also disposes of its runtime.
+///
+/// ```no_run
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // the host shares who is logged in, and nothing else
+/// let user = create_rw_signal(cx, "guest".to_string());
+/// let bridge = SignalBridge::new(cx, user);
+///
+/// let target = document()
+///   .get_element_by_id("cart")
+///   .unwrap()
+///   .unchecked_into::<web_sys::HtmlElement>();
+/// let cart = mount_scoped("cart", target, move |cx| {
+///   let user = bridge.connect(cx);
+///   view! { cx, <p>"Cart for " {move || user.get()}</p> }
+/// });
+///
+/// // later, e.g., when the widget is closed
+/// cart.unmount();
+/// # });
+/// ```
+pub fn mount_scoped<F, N>(
+  name: &str,
+  parent: web_sys::HtmlElement,
+  f: F,
+) -> MountHandle
+where
+  F: FnOnce(Scope) -> N + 'static,
+  N: IntoView,
+{
+  cfg_if! {
+    if #[cfg(all(target_arch = "wasm32", feature = "web"))] {
+      let runtime = leptos_reactive::create_scoped_runtime(name);
+      let root = events::add_delegation_root(&parent);
+      let (nodes, disposer) =
+        HydrationCtx::with_scope(name, move || mount_view(runtime, parent, f));
+
+      MountHandle {
+        nodes,
+        disposer,
+        scoped: Some((runtime, root)),
+      }
     } else {
+      _ = name;
       _ = parent;
       _ = f;
-      crate::warn!("`mount_to` should not be called outside the browser.");
+      crate::warn!("`mount_scoped` should not be called outside the browser.");
+
+      MountHandle {}
     }
   }
 }
 
+/// Renders the view into `parent`, returning its first and last nodes and
+/// the disposer of its root scope.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn mount_view<F, N>(
+  runtime: leptos_reactive::RuntimeId,
+  parent: web_sys::HtmlElement,
+  f: F,
+) -> (
+  (web_sys::Node, web_sys::Node),
+  leptos_reactive::ScopeDisposer,
+)
+where
+  F: FnOnce(Scope) -> N + 'static,
+  N: IntoView,
+{
+  let (nodes, _, disposer) =
+    leptos_reactive::run_scope_undisposed(runtime, move |cx| {
+      let node = f(cx).into_view(cx);
+
+      HydrationCtx::stop_hydrating();
+
+      parent.append_child(&node.get_mountable_node()).unwrap();
+
+      // lets end-to-end tests wait until the app is interactive
+      _ = js_sys::Reflect::set(
+        &window(),
+        &wasm_bindgen::JsValue::from_str("__LEPTOS_HYDRATED"),
+        &wasm_bindgen::JsValue::TRUE,
+      );
+
+      let nodes = (node.get_opening_node(), node.get_closing_node());
+
+      std::mem::forget(node);
+
+      nodes
+    });
+
+  (nodes, disposer)
+}
+
+/// A view that was mounted with [`mount_to`] or [`mount_scoped`]. It stays
+/// mounted until [`MountHandle::unmount`] is called, even if the handle is
+/// dropped.
+pub struct MountHandle {
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  nodes: (web_sys::Node, web_sys::Node),
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  disposer: leptos_reactive::ScopeDisposer,
+  /// The runtime and delegation root of a view mounted with [`mount_scoped`].
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  scoped: Option<(leptos_reactive::RuntimeId, usize)>,
+}
+
+impl MountHandle {
+  /// Removes the view's nodes from the DOM, and disposes of its root
+  /// [`Scope`], which cleans up its signals, effects, and resources. A view
+  /// mounted with [`mount_scoped`] also stops handling events, and its runtime
+  /// is disposed.
+  pub fn unmount(self) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+      let (opening, closing) = self.nodes;
+
+      unmount_child(&opening, &closing);
+      closing.unchecked_ref::<web_sys::Element>().remove();
+
+      self.disposer.dispose();
+
+      if let Some((runtime, root)) = self.scoped {
+        events::remove_delegation_root(root);
+        runtime.dispose();
+      }
+    }
+  }
+}
+
+impl fmt::Debug for MountHandle {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("MountHandle").finish_non_exhaustive()
+  }
+}
+
 thread_local! {
     pub(crate) static WINDOW: web_sys::Window = web_sys::window().unwrap_throw();
 
@@ -884,6 +1151,58 @@ where
   }
 }
 
+/// Renders each item of the iterator, so that a list can be rendered without
+/// collecting it into a [`Vec`] first.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn TodoList(cx: Scope, todos: Vec<String>) -> impl IntoView {
+///   view! { cx,
+///     <ul>
+///       {todos.into_iter().map(|todo| view! { cx, <li>{todo}</li> })}
+///     </ul>
+///   }
+/// }
+/// ```
+impl<I, F, V> IntoView for std::iter::Map<I, F>
+where
+  I: Iterator,
+  F: FnMut(I::Item) -> V,
+  V: IntoView,
+{
+  fn into_view(self, cx: Scope) -> View {
+    self
+      .map(|v| v.into_view(cx))
+      .collect::<Fragment>()
+      .into_view(cx)
+  }
+}
+
+/// Renders the shared value, cloning it only if it's shared with another
+/// [`Rc`].
+impl<V> IntoView for Rc<V>
+where
+  V: IntoView + Clone,
+{
+  fn into_view(self, cx: Scope) -> View {
+    Rc::try_unwrap(self)
+      .unwrap_or_else(|v| V::clone(&v))
+      .into_view(cx)
+  }
+}
+
+/// Renders a shared function reactively, in the same way as any other
+/// `Fn() -> impl IntoView`.
+impl<V> IntoView for Rc<dyn Fn() -> V>
+where
+  V: IntoView + 'static,
+{
+  fn into_view(self, cx: Scope) -> View {
+    (move || self()).into_view(cx)
+  }
+}
+
 macro_rules! viewable_primitive {
   ($($child_type:ty),* $(,)?) => {
     $(
@@ -1,5 +1,20 @@
+//! Logging helpers, and the facade used for messages from the framework itself.
+//!
+//! Framework messages, like warnings about elements that couldn't be found while
+//! hydrating, are logged with [framework_log!](crate::framework_log) at a
+//! [Level]. By default they're written to the console, but an app can send them
+//! somewhere else, like its own telemetry, with [set_log_sink], and can ignore
+//! less severe messages with [set_log_level].
+//!
+//! In release builds, framework messages are removed at compile time, along with
+//! the code that formats them, unless the `release-logging` feature is enabled.
+
 use crate::is_server;
 use cfg_if::cfg_if;
+use std::sync::{
+  atomic::{AtomicU8, Ordering},
+  Arc, RwLock,
+};
 use wasm_bindgen::JsValue;
 
 /// Uses `println!()`-style formatting to log something to the console (in the browser)
@@ -86,3 +101,117 @@ pub fn console_debug_warn(s: &str) {
       }
   }
 }
+
+/// Whether [framework_log!](crate::framework_log) logs anything: always in
+/// debug builds, and in release builds only with the `release-logging` feature.
+pub const FRAMEWORK_LOGGING: bool =
+  cfg!(any(debug_assertions, feature = "release-logging"));
+
+/// The severity of a message logged by the framework.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+  /// Details that are only useful while debugging the framework.
+  Debug,
+  /// Information about what the framework is doing.
+  Info,
+  /// Something that is probably a mistake, but that the framework can recover
+  /// from, like a hydration mismatch.
+  Warn,
+  /// Something that went wrong.
+  Error,
+}
+
+type LogSink = Arc<dyn Fn(Level, &str) + Send + Sync>;
+
+static LOG_SINK: RwLock<Option<LogSink>> = RwLock::new(None);
+static LOG_LEVEL: AtomicU8 = AtomicU8::new(Level::Debug as u8);
+
+/// Sends messages logged by the framework to the given function, instead of the
+/// console. This can be used to forward framework warnings to an app's own
+/// telemetry.
+///
+/// ```
+/// # use leptos_dom::logging::*;
+/// set_log_sink(|level, message| {
+///   if level >= Level::Warn {
+///     // send to your telemetry service here
+///     eprintln!("[leptos] {message}");
+///   }
+/// });
+/// # reset_log_sink();
+/// ```
+pub fn set_log_sink(sink: impl Fn(Level, &str) + Send + Sync + 'static) {
+  if let Ok(mut current) = LOG_SINK.write() {
+    *current = Some(Arc::new(sink));
+  }
+}
+
+/// Sends messages logged by the framework to the console again, after
+/// [set_log_sink].
+pub fn reset_log_sink() {
+  if let Ok(mut current) = LOG_SINK.write() {
+    *current = None;
+  }
+}
+
+/// Ignores messages logged by the framework that are less severe than the
+/// given level. All levels are logged by default.
+pub fn set_log_level(level: Level) {
+  LOG_LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+/// Logs a message from the framework at the given level, to the sink set with
+/// [set_log_sink], or otherwise to the console. Use
+/// [framework_log!](crate::framework_log) instead, which avoids formatting the
+/// message when it won't be logged.
+#[doc(hidden)]
+pub fn log_framework_message(level: Level, message: &str) {
+  if (level as u8) < LOG_LEVEL.load(Ordering::Relaxed) {
+    return;
+  }
+
+  let sink = LOG_SINK.read().ok().and_then(|sink| sink.clone());
+  match (sink, level) {
+    (Some(sink), _) => sink(level, message),
+    (None, Level::Debug | Level::Info) => console_log(message),
+    (None, Level::Warn) => console_warn(message),
+    (None, Level::Error) => console_error(message),
+  }
+}
+
+/// Warns that a node rendered on the server couldn't be found while hydrating,
+/// and reports it to the app's
+/// [Diagnostics](leptos_reactive::Diagnostics), if it is collecting them.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn hydration_miss(message: String) {
+  crate::framework_log!(Level::Warn, "{message}");
+  leptos_reactive::report_browser_diagnostic(leptos_reactive::Diagnostic::new(
+    leptos_reactive::DiagnosticKind::HydrationMiss,
+    message,
+  ));
+}
+
+/// Uses `format!()`-style formatting to log a message from the framework at a
+/// [Level](crate::logging::Level). See the [logging](crate::logging) module
+/// for where it is sent.
+///
+/// In release builds without the `release-logging` feature, nothing is logged
+/// and the message isn't formatted, so its formatting code can be optimized
+/// away.
+///
+/// ```
+/// # use leptos_dom::{framework_log, logging::Level};
+/// let id = 3;
+/// framework_log!(Level::Warn, "element with id {id} not found");
+/// ```
+#[macro_export]
+macro_rules! framework_log {
+  ($level:expr, $($t:tt)*) => {
+    if $crate::logging::FRAMEWORK_LOGGING {
+      $crate::logging::log_framework_message(
+        $level,
+        &format_args!($($t)*).to_string(),
+      )
+    }
+  };
+}
@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::{borrow::Cow, rc::Rc};
 
 use leptos_reactive::Scope;
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
@@ -18,14 +18,19 @@ pub enum Attribute {
   Option(Scope, Option<String>),
   /// A boolean attribute, which sets the attribute if `true` and removes the attribute if `false`.
   Bool(bool),
+  /// A value that was already escaped for HTML, which is rendered on the server as-is.
+  /// See [RawAttribute].
+  Raw(Cow<'static, str>),
 }
 
 impl Attribute {
   /// Converts the attribute to its HTML value at that moment, including the attribute name,
   /// so it can be rendered on the server.
-  pub fn as_value_string(&self, attr_name: &'static str) -> String {
+  pub fn as_value_string(&self, attr_name: &str) -> String {
     match self {
-      Attribute::String(value) => format!("{attr_name}=\"{value}\""),
+      Attribute::String(value) => {
+        format!("{attr_name}=\"{}\"", escape_attr(value))
+      }
       Attribute::Fn(_, f) => {
         let mut value = f();
         while let Attribute::Fn(_, f) = value {
@@ -35,7 +40,7 @@ impl Attribute {
       }
       Attribute::Option(_, value) => value
         .as_ref()
-        .map(|value| format!("{attr_name}=\"{value}\""))
+        .map(|value| format!("{attr_name}=\"{}\"", escape_attr(value)))
         .unwrap_or_default(),
       Attribute::Bool(include) => {
         if *include {
@@ -44,6 +49,7 @@ impl Attribute {
           String::new()
         }
       }
+      Attribute::Raw(value) => format!("{attr_name}=\"{value}\""),
     }
   }
 
@@ -51,7 +57,7 @@ impl Attribute {
   /// the attribute name, so it can be rendered on the server.
   pub fn as_nameless_value_string(&self) -> String {
     match self {
-      Attribute::String(value) => value.to_string(),
+      Attribute::String(value) => escape_attr(value).into_owned(),
       Attribute::Fn(_, f) => {
         let mut value = f();
         while let Attribute::Fn(_, f) = value {
@@ -61,9 +67,10 @@ impl Attribute {
       }
       Attribute::Option(_, value) => value
         .as_ref()
-        .map(|value| value.to_string())
+        .map(|value| escape_attr(value).into_owned())
         .unwrap_or_default(),
       Attribute::Bool(_) => String::new(),
+      Attribute::Raw(value) => value.to_string(),
     }
   }
 }
@@ -75,6 +82,7 @@ impl PartialEq for Attribute {
       (Self::Fn(_, _), Self::Fn(_, _)) => false,
       (Self::Option(_, l0), Self::Option(_, r0)) => l0 == r0,
       (Self::Bool(l0), Self::Bool(r0)) => l0 == r0,
+      (Self::Raw(l0), Self::Raw(r0)) => l0 == r0,
       _ => false,
     }
   }
@@ -87,10 +95,116 @@ impl std::fmt::Debug for Attribute {
       Self::Fn(_, _) => f.debug_tuple("Fn").finish(),
       Self::Option(_, arg0) => f.debug_tuple("Option").field(arg0).finish(),
       Self::Bool(arg0) => f.debug_tuple("Bool").field(arg0).finish(),
+      Self::Raw(arg0) => f.debug_tuple("Raw").field(arg0).finish(),
     }
   }
 }
 
+/// Escapes an attribute value so that it can be written between double quotes
+/// in HTML. Every attribute value rendered on the server goes through this,
+/// unless it's a [RawAttribute].
+///
+/// `&`, `"`, `<` and `>` are replaced with character references, and
+/// everything else, including characters outside the Basic Multilingual
+/// Plane, is kept as it is.
+/// ```
+/// # use leptos_dom::escape_attr;
+/// assert_eq!(escape_attr("plain"), "plain");
+/// assert_eq!(
+///   escape_attr(r#"say "hi" & <wave> 👋"#),
+///   "say &quot;hi&quot; &amp; &lt;wave&gt; 👋"
+/// );
+///
+/// // for any value, the escaped value can't end the attribute or start a
+/// // tag, and decodes back to the value
+/// let pieces = ["\"", "'", "<", ">", "&", "&amp;", "𝄞", "🦀", "\u{10FFFF}", "a b", ""];
+/// for a in pieces {
+///   for b in pieces {
+///     for c in pieces {
+///       let value = format!("{a}{b}{c}");
+///       let escaped = escape_attr(&value);
+///       assert!(!escaped.contains(['"', '<', '>']));
+///       assert_eq!(html_escape::decode_html_entities(&escaped), value);
+///     }
+///   }
+/// }
+/// ```
+pub fn escape_attr(value: &str) -> Cow<'_, str> {
+  html_escape::encode_double_quoted_attribute(value)
+}
+
+/// Escapes an owned attribute value, without copying it if nothing needs to be
+/// escaped.
+#[cfg_attr(all(target_arch = "wasm32", feature = "web"), allow(dead_code))]
+pub(crate) fn escape_attr_owned(
+  value: impl Into<Cow<'static, str>>,
+) -> Cow<'static, str> {
+  let value = value.into();
+  let escaped = match escape_attr(&value) {
+    Cow::Owned(escaped) => Some(escaped),
+    Cow::Borrowed(_) => None,
+  };
+  escaped.map(Cow::Owned).unwrap_or(value)
+}
+
+/// An attribute value that was already escaped for HTML, so that it isn't
+/// escaped again when it's rendered on the server.
+///
+/// Only use this for values that are known to be escaped: an unescaped `"`
+/// ends the attribute, and lets the rest of the value add attributes or
+/// elements to the page. In the browser, the value is decoded before it's set.
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // e.g., a value escaped by a CMS
+/// let title = "Fish &amp; Chips";
+/// let link = view! { cx, <a title=RawAttribute::new(title)>"Menu"</a> };
+/// assert!(link
+///   .into_view(cx)
+///   .render_to_string(cx)
+///   .contains(r#"title="Fish &amp; Chips""#));
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RawAttribute(Cow<'static, str>);
+
+impl RawAttribute {
+  /// Wraps a value that was already escaped.
+  pub fn new(escaped: impl Into<Cow<'static, str>>) -> Self {
+    Self(escaped.into())
+  }
+}
+
+impl IntoAttribute for RawAttribute {
+  fn into_attribute(self, _: Scope) -> Attribute {
+    Attribute::Raw(self.0)
+  }
+}
+
+/// Renders attributes that were spread into an element with `{..attrs}` in the
+/// [`view`](https://docs.rs/leptos_macro/latest/leptos_macro/macro.view.html)
+/// macro to HTML, each with a leading space, so they can be rendered on the
+/// server.
+#[doc(hidden)]
+pub fn spread_attributes_to_string<N, A>(
+  cx: Scope,
+  attrs: impl IntoIterator<Item = (N, A)>,
+) -> String
+where
+  N: Into<Cow<'static, str>>,
+  A: IntoAttribute,
+{
+  attrs
+    .into_iter()
+    .map(|(name, attr)| {
+      let name: Cow<'static, str> = name.into();
+      attr.into_attribute(cx).as_value_string(&name)
+    })
+    .filter(|attr| !attr.is_empty())
+    .map(|attr| format!(" {attr}"))
+    .collect()
+}
+
 /// Converts some type into an [Attribute].
 ///
 /// This is implemented by default for Rust primitive and string types.
@@ -134,6 +248,12 @@ impl<T: IntoAttribute> IntoAttribute for (Scope, T) {
   }
 }
 
+impl IntoAttribute for Attribute {
+  fn into_attribute(self, _: Scope) -> Attribute {
+    self
+  }
+}
+
 macro_rules! attr_type {
   ($attr_type:ty) => {
     impl IntoAttribute for $attr_type {
@@ -168,6 +288,47 @@ attr_type!(f32);
 attr_type!(f64);
 attr_type!(char);
 
+// Without the `stable` feature, signals implement `Fn() -> T`, so they're
+// already covered by the implementation for functions above.
+#[cfg(feature = "stable")]
+macro_rules! attr_signal_type {
+  ($signal_type:ty) => {
+    impl<T> IntoAttribute for $signal_type
+    where
+      T: IntoAttribute + Clone + 'static,
+    {
+      fn into_attribute(self, cx: Scope) -> Attribute {
+        let modified_fn = Rc::new(move || self.get().into_attribute(cx));
+        Attribute::Fn(cx, modified_fn)
+      }
+    }
+  };
+}
+
+#[cfg(feature = "stable")]
+attr_signal_type!(leptos_reactive::ReadSignal<T>);
+#[cfg(feature = "stable")]
+attr_signal_type!(leptos_reactive::RwSignal<T>);
+#[cfg(feature = "stable")]
+attr_signal_type!(leptos_reactive::Memo<T>);
+#[cfg(feature = "stable")]
+attr_signal_type!(leptos_reactive::Signal<T>);
+#[cfg(feature = "stable")]
+attr_signal_type!(leptos_reactive::MaybeSignal<T>);
+
+#[cfg(feature = "stable")]
+impl<S, T> IntoAttribute for leptos_reactive::Resource<S, T>
+where
+  S: Clone + 'static,
+  T: Clone + 'static,
+  Option<T>: IntoAttribute,
+{
+  fn into_attribute(self, cx: Scope) -> Attribute {
+    let modified_fn = Rc::new(move || self.read().into_attribute(cx));
+    Attribute::Fn(cx, modified_fn)
+  }
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub fn attribute_expression(
   el: &web_sys::Element,
@@ -178,7 +339,7 @@ pub fn attribute_expression(
     Attribute::String(value) => {
       let value = wasm_bindgen::intern(&value);
       if attr_name == "inner_html" {
-        el.set_inner_html(value);
+        crate::trusted_types::set_inner_html(el, value);
       } else {
         let attr_name = wasm_bindgen::intern(attr_name);
         el.set_attribute(attr_name, value).unwrap_throw();
@@ -186,7 +347,7 @@ pub fn attribute_expression(
     }
     Attribute::Option(_, value) => {
       if attr_name == "inner_html" {
-        el.set_inner_html(&value.unwrap_or_default());
+        crate::trusted_types::set_inner_html(el, &value.unwrap_or_default());
       } else {
         let attr_name = wasm_bindgen::intern(attr_name);
         match value {
@@ -198,6 +359,15 @@ pub fn attribute_expression(
         }
       }
     }
+    Attribute::Raw(value) => {
+      if attr_name == "inner_html" {
+        crate::trusted_types::set_inner_html(el, &value);
+      } else {
+        let attr_name = wasm_bindgen::intern(attr_name);
+        let value = html_escape::decode_html_entities(&value);
+        el.set_attribute(attr_name, &value).unwrap_throw();
+      }
+    }
     Attribute::Bool(value) => {
       let attr_name = wasm_bindgen::intern(attr_name);
       if value {
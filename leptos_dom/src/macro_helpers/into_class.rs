@@ -1,4 +1,5 @@
 use leptos_reactive::Scope;
+use std::{borrow::Cow, rc::Rc};
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 use wasm_bindgen::UnwrapThrowExt;
 
@@ -66,6 +67,130 @@ impl<T: IntoClass> IntoClass for (Scope, T) {
   }
 }
 
+impl IntoClass for Class {
+  fn into_class(self, _: Scope) -> Class {
+    self
+  }
+}
+
+/// Converts some type into a list of class names, each with a [Class], for
+/// [`HtmlElement::classes`](crate::HtmlElement::classes).
+///
+/// This is implemented for
+/// - strings, which can contain several class names separated by whitespace,
+/// - `(name, class)` pairs, where `class` is anything that implements
+///   [IntoClass], like a `bool` or a `Signal<bool>`, and
+/// - [Vec]s, arrays, and [Option]s of any of these.
+pub trait IntoClasses {
+  /// Converts the object into class names and their [Class]es.
+  fn into_classes(self, cx: Scope) -> Vec<(Cow<'static, str>, Class)>;
+}
+
+impl IntoClasses for &'static str {
+  fn into_classes(self, _: Scope) -> Vec<(Cow<'static, str>, Class)> {
+    self
+      .split_whitespace()
+      .map(|name| (Cow::Borrowed(name), Class::Value(true)))
+      .collect()
+  }
+}
+
+impl IntoClasses for String {
+  fn into_classes(self, _: Scope) -> Vec<(Cow<'static, str>, Class)> {
+    self
+      .split_whitespace()
+      .map(|name| (Cow::Owned(name.to_string()), Class::Value(true)))
+      .collect()
+  }
+}
+
+impl IntoClasses for Cow<'static, str> {
+  fn into_classes(self, cx: Scope) -> Vec<(Cow<'static, str>, Class)> {
+    match self {
+      Cow::Borrowed(names) => names.into_classes(cx),
+      Cow::Owned(names) => names.into_classes(cx),
+    }
+  }
+}
+
+impl<N, C> IntoClasses for (N, C)
+where
+  N: Into<Cow<'static, str>>,
+  C: IntoClass,
+{
+  fn into_classes(self, cx: Scope) -> Vec<(Cow<'static, str>, Class)> {
+    let names = self.0.into().into_classes(cx);
+    match self.1.into_class(cx) {
+      Class::Value(include) => names
+        .into_iter()
+        .map(|(name, _)| (name, Class::Value(include)))
+        .collect(),
+      // each name is toggled by its own effect, which all share the function
+      Class::Fn(cx, f) => {
+        let f: Rc<dyn Fn() -> bool> = Rc::from(f);
+        names
+          .into_iter()
+          .map(|(name, _)| {
+            let f = Rc::clone(&f);
+            (name, Class::Fn(cx, Box::new(move || f())))
+          })
+          .collect()
+      }
+    }
+  }
+}
+
+impl<T: IntoClasses> IntoClasses for Option<T> {
+  fn into_classes(self, cx: Scope) -> Vec<(Cow<'static, str>, Class)> {
+    self
+      .map(|classes| classes.into_classes(cx))
+      .unwrap_or_default()
+  }
+}
+
+impl<T: IntoClasses> IntoClasses for Vec<T> {
+  fn into_classes(self, cx: Scope) -> Vec<(Cow<'static, str>, Class)> {
+    self
+      .into_iter()
+      .flat_map(|classes| classes.into_classes(cx))
+      .collect()
+  }
+}
+
+impl<T: IntoClasses, const N: usize> IntoClasses for [T; N] {
+  fn into_classes(self, cx: Scope) -> Vec<(Cow<'static, str>, Class)> {
+    self
+      .into_iter()
+      .flat_map(|classes| classes.into_classes(cx))
+      .collect()
+  }
+}
+
+// Without the `stable` feature, signals implement `Fn() -> bool`, so they're
+// already covered by the implementation for functions above.
+#[cfg(feature = "stable")]
+macro_rules! class_signal_type {
+  ($signal_type:ty) => {
+    impl IntoClass for $signal_type {
+      fn into_class(self, cx: Scope) -> Class {
+        let modified_fn = Box::new(move || self.get());
+        Class::Fn(cx, modified_fn)
+      }
+    }
+  };
+}
+
+#[cfg(feature = "stable")]
+class_signal_type!(leptos_reactive::ReadSignal<bool>);
+#[cfg(feature = "stable")]
+class_signal_type!(leptos_reactive::RwSignal<bool>);
+#[cfg(feature = "stable")]
+class_signal_type!(leptos_reactive::Memo<bool>);
+#[cfg(feature = "stable")]
+class_signal_type!(leptos_reactive::Signal<bool>);
+#[cfg(feature = "stable")]
+class_signal_type!(leptos_reactive::MaybeSignal<bool>);
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub fn class_expression(
   class_list: &web_sys::DomTokenList,
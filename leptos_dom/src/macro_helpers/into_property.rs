@@ -76,6 +76,47 @@ prop_type!(f32);
 prop_type!(f64);
 prop_type!(bool);
 
+// Without the `stable` feature, signals implement `Fn() -> T`, so they're
+// already covered by the implementation for functions above.
+#[cfg(feature = "stable")]
+macro_rules! prop_signal_type {
+  ($signal_type:ty) => {
+    impl<T> IntoProperty for $signal_type
+    where
+      T: Into<JsValue> + Clone + 'static,
+    {
+      fn into_property(self, cx: Scope) -> Property {
+        let modified_fn = Box::new(move || self.get().into());
+        Property::Fn(cx, modified_fn)
+      }
+    }
+  };
+}
+
+#[cfg(feature = "stable")]
+prop_signal_type!(leptos_reactive::ReadSignal<T>);
+#[cfg(feature = "stable")]
+prop_signal_type!(leptos_reactive::RwSignal<T>);
+#[cfg(feature = "stable")]
+prop_signal_type!(leptos_reactive::Memo<T>);
+#[cfg(feature = "stable")]
+prop_signal_type!(leptos_reactive::Signal<T>);
+#[cfg(feature = "stable")]
+prop_signal_type!(leptos_reactive::MaybeSignal<T>);
+
+#[cfg(feature = "stable")]
+impl<S, T> IntoProperty for leptos_reactive::Resource<S, T>
+where
+  S: Clone + 'static,
+  T: Clone + 'static,
+  Option<T>: Into<JsValue>,
+{
+  fn into_property(self, cx: Scope) -> Property {
+    let modified_fn = Box::new(move || self.read().into());
+    Property::Fn(cx, modified_fn)
+  }
+}
+
 #[cfg(all(target_arch = "wasm32", feature = "web"))]
 pub fn property_expression(
   el: &web_sys::Element,
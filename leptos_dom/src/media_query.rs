@@ -0,0 +1,190 @@
+//! Reactive media queries.
+
+use leptos_reactive::{create_signal, use_context, Scope, Signal};
+
+/// User preferences sent by the browser as
+/// [client hints](https://developer.mozilla.org/en-US/docs/Web/HTTP/Client_hints),
+/// which are used as the values of the preference signals during server rendering.
+///
+/// The server integrations provide this as context from the
+/// `Sec-CH-Prefers-Color-Scheme` and `Sec-CH-Prefers-Reduced-Motion` request
+/// headers. Browsers only send these headers after the server has asked for them
+/// with an `Accept-CH` response header.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ClientHints {
+  /// The value of `Sec-CH-Prefers-Color-Scheme`, if it was sent.
+  pub color_scheme: Option<ColorScheme>,
+  /// The value of `Sec-CH-Prefers-Reduced-Motion`, if it was sent.
+  pub reduced_motion: Option<bool>,
+}
+
+impl ClientHints {
+  /// Reads the hints from the values of the `Sec-CH-Prefers-Color-Scheme` and
+  /// `Sec-CH-Prefers-Reduced-Motion` headers.
+  ///
+  /// ```
+  /// # use leptos_dom::*;
+  /// let hints = ClientHints::from_headers(Some("\"dark\""), None);
+  /// assert_eq!(hints.color_scheme, Some(ColorScheme::Dark));
+  /// assert_eq!(hints.reduced_motion, None);
+  /// ```
+  pub fn from_headers(
+    prefers_color_scheme: Option<&str>,
+    prefers_reduced_motion: Option<&str>,
+  ) -> Self {
+    // structured header strings are quoted
+    let unquote = |value: &str| value.trim().trim_matches('"').to_string();
+    Self {
+      color_scheme: prefers_color_scheme
+        .and_then(|value| unquote(value).parse().ok()),
+      reduced_motion: prefers_reduced_motion.and_then(|value| {
+        match unquote(value).as_str() {
+          "reduce" => Some(true),
+          "no-preference" => Some(false),
+          _ => None,
+        }
+      }),
+    }
+  }
+}
+
+/// The user's preferred color scheme.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ColorScheme {
+  /// A light background with dark text.
+  #[default]
+  Light,
+  /// A dark background with light text.
+  Dark,
+}
+
+impl std::fmt::Display for ColorScheme {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.write_str(match self {
+      Self::Light => "light",
+      Self::Dark => "dark",
+    })
+  }
+}
+
+/// Parses `"light"` or `"dark"`.
+impl std::str::FromStr for ColorScheme {
+  type Err = ();
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s {
+      "light" => Ok(Self::Light),
+      "dark" => Ok(Self::Dark),
+      _ => Err(()),
+    }
+  }
+}
+
+/// Returns a signal that is `true` whenever the given
+/// [media query](https://developer.mozilla.org/en-US/docs/Web/CSS/Media_Queries/Using_media_queries)
+/// matches, and updates when that changes. The listener is removed when the
+/// scope is disposed.
+///
+/// There's no viewport on the server, so the signal is always `false` during
+/// server rendering. Use [create_media_query_with_default] to choose a different
+/// value.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let is_small_screen = create_media_query(cx, "(max-width: 600px)");
+///
+/// let menu = move || if is_small_screen() { "hamburger" } else { "full" };
+/// # if !is_browser() {
+/// assert_eq!(menu(), "full");
+/// # }
+/// # });
+/// ```
+pub fn create_media_query(cx: Scope, query: &str) -> Signal<bool> {
+  create_media_query_with_default(cx, query, false)
+}
+
+/// Like [create_media_query], but with the value to use when media queries
+/// can't be evaluated, like during server rendering.
+pub fn create_media_query_with_default(
+  cx: Scope,
+  query: &str,
+  default: bool,
+) -> Signal<bool> {
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  {
+    use leptos_reactive::on_cleanup;
+    use wasm_bindgen::{prelude::Closure, JsCast};
+
+    let list = crate::window().match_media(query).ok().flatten();
+    let (matches, set_matches) = create_signal(
+      cx,
+      list.as_ref().map(|list| list.matches()).unwrap_or(default),
+    );
+
+    if let Some(list) = list {
+      let listener =
+        Closure::wrap(Box::new(move |ev: web_sys::MediaQueryListEvent| {
+          set_matches.set(ev.matches())
+        }) as Box<dyn Fn(web_sys::MediaQueryListEvent)>);
+      _ = list.add_event_listener_with_callback(
+        "change",
+        listener.as_ref().unchecked_ref(),
+      );
+      on_cleanup(cx, move || {
+        _ = list.remove_event_listener_with_callback(
+          "change",
+          listener.as_ref().unchecked_ref(),
+        );
+      });
+    }
+
+    matches.into()
+  }
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  {
+    _ = query;
+    let (matches, _) = create_signal(cx, default);
+    matches.into()
+  }
+}
+
+/// Returns a signal with the user's preferred color scheme, from the
+/// `prefers-color-scheme` media query.
+///
+/// During server rendering, this is the color scheme sent in [ClientHints], or
+/// [ColorScheme::Light] if there are none.
+pub fn use_prefers_color_scheme(cx: Scope) -> Signal<ColorScheme> {
+  let default = use_context::<ClientHints>(cx)
+    .and_then(|hints| hints.color_scheme)
+    .unwrap_or_default();
+  let dark = create_media_query_with_default(
+    cx,
+    "(prefers-color-scheme: dark)",
+    default == ColorScheme::Dark,
+  );
+  Signal::derive(cx, move || {
+    if dark.get() {
+      ColorScheme::Dark
+    } else {
+      ColorScheme::Light
+    }
+  })
+}
+
+/// Returns a signal that is `true` if the user has asked to minimize
+/// non-essential motion, from the `prefers-reduced-motion` media query.
+///
+/// During server rendering, this is the preference sent in [ClientHints], or
+/// `false` if there are none.
+pub fn use_prefers_reduced_motion(cx: Scope) -> Signal<bool> {
+  let default = use_context::<ClientHints>(cx)
+    .and_then(|hints| hints.reduced_motion)
+    .unwrap_or(false);
+  create_media_query_with_default(
+    cx,
+    "(prefers-reduced-motion: reduce)",
+    default,
+  )
+}
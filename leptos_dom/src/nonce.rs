@@ -0,0 +1,88 @@
+//! The nonce that a page's Content Security Policy allows inline scripts and
+//! styles with.
+
+use leptos_reactive::{provide_context, use_context, Scope};
+use std::{fmt, rc::Rc};
+
+/// A nonce that the page's Content Security Policy allows inline `<script>`
+/// and `<style>` tags with, e.g., `script-src 'nonce-r4nd0m'`.
+///
+/// Provide a new, random one for each response with [provide_nonce], in the
+/// root of the app. It's added to every inline script and style that the
+/// framework renders: the server integrations' hydration and live-reload
+/// scripts, the scripts that stream resources and `<Suspense/>` fragments,
+/// the script that loads islands, and the scripts and styles rendered by
+/// `leptos_meta`. Use [use_nonce] to add it to your own.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// provide_nonce(cx, "r4nd0m");
+///
+/// let nonce = use_nonce(cx).unwrap();
+/// assert_eq!(nonce.as_str(), "r4nd0m");
+/// assert_eq!(nonce.to_attr(), r#" nonce="r4nd0m""#);
+/// view! { cx,
+///   <script nonce=nonce.to_string()>"console.log('allowed')"</script>
+/// };
+/// # });
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Nonce(Rc<str>);
+
+impl Nonce {
+  /// Creates a nonce with the given value, which should be random and
+  /// different for each response.
+  pub fn new(nonce: impl AsRef<str>) -> Self {
+    Self(nonce.as_ref().into())
+  }
+
+  /// The value of the nonce.
+  pub fn as_str(&self) -> &str {
+    &self.0
+  }
+
+  /// The `nonce` attribute, including a leading space, escaped so that it can
+  /// be added to a tag written by hand.
+  pub fn to_attr(&self) -> String {
+    format!(" nonce=\"{}\"", crate::escape_attr(&self.0))
+  }
+}
+
+impl fmt::Display for Nonce {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.write_str(&self.0)
+  }
+}
+
+/// Provides the [Nonce] that inline scripts and styles are rendered with,
+/// for this scope and its children, and returns it.
+pub fn provide_nonce(cx: Scope, nonce: impl AsRef<str>) -> Nonce {
+  let nonce = Nonce::new(nonce);
+  provide_context(cx, nonce.clone());
+  nonce
+}
+
+/// Returns the [Nonce] that inline scripts and styles are rendered with, if
+/// one was provided with [provide_nonce], or, on the server, as the `nonce` of
+/// [StreamingOptions](crate::StreamingOptions).
+pub fn use_nonce(cx: Scope) -> Option<Nonce> {
+  use_context::<Nonce>(cx).or_else(|| {
+    #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+    return use_context::<crate::StreamingOptions>(cx)
+      .and_then(|options| options.nonce)
+      .map(Nonce::new);
+
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    None
+  })
+}
+
+/// The `nonce` attribute of the [Nonce] that inline scripts and styles are
+/// rendered with, including a leading space, or an empty string if there
+/// isn't one.
+pub fn nonce_attr(cx: Scope) -> String {
+  use_nonce(cx)
+    .map(|nonce| nonce.to_attr())
+    .unwrap_or_default()
+}
@@ -0,0 +1,207 @@
+//! Reactive page visibility and page lifecycle events.
+
+use leptos_reactive::{create_signal, Resource, Scope, Signal};
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use wasm_bindgen::JsCast;
+
+/// Whether the page is visible, from the
+/// [Page Visibility API](https://developer.mozilla.org/en-US/docs/Web/API/Page_Visibility_API).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum DocumentVisibility {
+  /// The page is at least partly visible, e.g., in the tab the user is looking at.
+  #[default]
+  Visible,
+  /// The page is hidden, e.g., in a background tab or a minimized window.
+  Hidden,
+}
+
+impl DocumentVisibility {
+  /// Whether the page is visible.
+  pub fn is_visible(&self) -> bool {
+    *self == Self::Visible
+  }
+}
+
+/// Returns a signal with the visibility of the page, which updates on
+/// `visibilitychange`. The listener is removed when the scope is disposed.
+///
+/// This is useful to pause work that nobody can see, like polling or
+/// animations, while the page is in a background tab. During server rendering,
+/// the page is always [DocumentVisibility::Visible].
+///
+/// ```
+/// # use leptos::*;
+/// # use std::time::Duration;
+/// # run_scope(create_runtime(), |cx| {
+/// let visibility = use_document_visibility(cx);
+/// let timer = use_interval(cx, Duration::from_secs(10), || {
+///   // poll for new messages
+/// });
+///
+/// create_effect(cx, move |_| {
+///   if visibility.get().is_visible() {
+///     timer.resume();
+///   } else {
+///     timer.pause();
+///   }
+/// });
+/// # });
+/// ```
+pub fn use_document_visibility(cx: Scope) -> Signal<DocumentVisibility> {
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  {
+    let current = || {
+      if crate::document().hidden() {
+        DocumentVisibility::Hidden
+      } else {
+        DocumentVisibility::Visible
+      }
+    };
+    let (visibility, set_visibility) = create_signal(cx, current());
+    listen(
+      cx,
+      crate::document().unchecked_ref(),
+      "visibilitychange",
+      move |_: web_sys::Event| set_visibility.set(current()),
+    );
+    visibility.into()
+  }
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  {
+    let (visibility, _) = create_signal(cx, DocumentVisibility::Visible);
+    visibility.into()
+  }
+}
+
+/// Calls `f` on `pagehide`, when the user navigates away from the page or
+/// closes it, until the scope is disposed. `f` is passed whether the page may
+/// be kept in the back/forward cache, in which case it can be shown again.
+///
+/// This is the last reliable moment to save state, e.g., with
+/// `navigator.sendBeacon()`. It never runs on the server.
+pub fn on_page_hide(cx: Scope, f: impl Fn(bool) + 'static) {
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  listen(
+    cx,
+    crate::window().unchecked_ref(),
+    "pagehide",
+    move |ev: web_sys::PageTransitionEvent| f(ev.persisted()),
+  );
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  {
+    _ = cx;
+    _ = f;
+  }
+}
+
+/// Calls `f` on `pageshow`, when the page is shown, until the scope is
+/// disposed. `f` is passed whether the page was restored from the
+/// back/forward cache, rather than loaded. It never runs on the server.
+pub fn on_page_show(cx: Scope, f: impl Fn(bool) + 'static) {
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  listen(
+    cx,
+    crate::window().unchecked_ref(),
+    "pageshow",
+    move |ev: web_sys::PageTransitionEvent| f(ev.persisted()),
+  );
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  {
+    _ = cx;
+    _ = f;
+  }
+}
+
+/// Calls `f` on `beforeunload`, when the page is about to be unloaded, until
+/// the scope is disposed. If `f` returns `true`, the browser asks the user to
+/// confirm that they want to leave, e.g., because a form has unsaved changes.
+///
+/// Listening for `beforeunload` keeps some browsers from putting the page in
+/// the back/forward cache, so this should only be used while it's needed,
+/// e.g., in the scope of the form. It never runs on the server.
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (unsaved, _set_unsaved) = create_signal(cx, false);
+/// on_before_unload(cx, move || unsaved.get());
+/// # });
+/// ```
+pub fn on_before_unload(cx: Scope, f: impl Fn() -> bool + 'static) {
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  listen(
+    cx,
+    crate::window().unchecked_ref(),
+    "beforeunload",
+    move |ev: web_sys::BeforeUnloadEvent| {
+      if f() {
+        ev.prevent_default();
+        // older browsers only ask if a return value is set
+        ev.set_return_value("");
+      }
+    },
+  );
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  {
+    _ = cx;
+    _ = f;
+  }
+}
+
+/// Refetches the resource whenever the page becomes visible again, e.g., when
+/// the user switches back to its tab, so that it doesn't show data that went
+/// stale in the background.
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if false {
+/// let notifications = create_resource(cx, || (), |_| async { Vec::<String>::new() });
+/// refetch_on_visible(cx, notifications);
+/// # }
+/// # });
+/// ```
+pub fn refetch_on_visible<S, T>(cx: Scope, resource: Resource<S, T>)
+where
+  S: Clone + 'static,
+  T: 'static,
+{
+  let visibility = use_document_visibility(cx);
+  leptos_reactive::create_effect(cx, move |was_visible: Option<bool>| {
+    let is_visible = visibility.get().is_visible();
+    if was_visible == Some(false) && is_visible {
+      resource.refetch();
+    }
+    is_visible
+  });
+}
+
+/// Adds an event listener to the target, which is removed when the scope is
+/// disposed.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn listen<E>(
+  cx: Scope,
+  target: &web_sys::EventTarget,
+  event_name: &'static str,
+  f: impl Fn(E) + 'static,
+) where
+  E: wasm_bindgen::convert::FromWasmAbi + 'static,
+{
+  use wasm_bindgen::prelude::Closure;
+
+  let listener = Closure::wrap(Box::new(f) as Box<dyn Fn(E)>);
+  _ = target.add_event_listener_with_callback(
+    event_name,
+    listener.as_ref().unchecked_ref(),
+  );
+
+  let target = target.clone();
+  leptos_reactive::on_cleanup(cx, move || {
+    _ = target.remove_event_listener_with_callback(
+      event_name,
+      listener.as_ref().unchecked_ref(),
+    );
+  });
+}
@@ -0,0 +1,150 @@
+//! Reactive access to the state of browser permissions.
+
+use leptos_reactive::{create_rw_signal, Scope, Signal};
+
+/// The state of a permission the page has asked the browser for.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum PermissionState {
+  /// The user has allowed the page to use the feature.
+  Granted,
+  /// The user has blocked the page from using the feature.
+  Denied,
+  /// The browser will ask the user the next time the page uses the feature.
+  Prompt,
+  /// The state isn't known yet, or the browser doesn't report it. This is
+  /// always the state during server rendering.
+  #[default]
+  Unknown,
+}
+
+impl PermissionState {
+  /// Reads the value of
+  /// [`PermissionStatus.state`](https://developer.mozilla.org/en-US/docs/Web/API/PermissionStatus/state).
+  ///
+  /// ```
+  /// # use leptos_dom::*;
+  /// assert_eq!(PermissionState::from_status("granted"), PermissionState::Granted);
+  /// assert_eq!(PermissionState::from_status("bogus"), PermissionState::Unknown);
+  /// ```
+  pub fn from_status(state: &str) -> Self {
+    match state {
+      "granted" => Self::Granted,
+      "denied" => Self::Denied,
+      "prompt" => Self::Prompt,
+      _ => Self::Unknown,
+    }
+  }
+}
+
+/// Returns a signal with the state of the permission with the given
+/// [name](https://developer.mozilla.org/en-US/docs/Web/API/Permissions/query#name),
+/// like `"geolocation"` or `"notifications"`, which updates when the user
+/// changes it. The listener is removed when the scope is disposed.
+///
+/// The state is [PermissionState::Unknown] until the browser has answered, if
+/// the browser doesn't recognize the name, and during server rendering.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let camera = use_permission(cx, "camera");
+/// let show_camera_hint = move || camera.get() == PermissionState::Denied;
+/// # });
+/// ```
+pub fn use_permission(cx: Scope, name: &str) -> Signal<PermissionState> {
+  let state = create_rw_signal(cx, PermissionState::Unknown);
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  web::query(cx, name, state);
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  _ = name;
+
+  state.into()
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+mod web {
+  use super::PermissionState;
+  use leptos_reactive::{on_cleanup, RwSignal, Scope};
+  use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+  };
+  use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+  use wasm_bindgen_futures::JsFuture;
+
+  type Listener = (web_sys::EventTarget, Closure<dyn Fn()>);
+
+  pub(super) fn query(cx: Scope, name: &str, state: RwSignal<PermissionState>) {
+    let promise = match start_query(name) {
+      Some(promise) => promise,
+      None => return,
+    };
+
+    // the status arrives after this function returns, so the listener is
+    // stored here for the cleanup to remove
+    let listener: Rc<RefCell<Option<Listener>>> = Default::default();
+    let disposed = Rc::new(Cell::new(false));
+    on_cleanup(cx, {
+      let (listener, disposed) = (listener.clone(), disposed.clone());
+      move || {
+        disposed.set(true);
+        if let Some((status, handler)) = listener.borrow_mut().take() {
+          _ = status.remove_event_listener_with_callback(
+            "change",
+            handler.as_ref().unchecked_ref(),
+          );
+        }
+      }
+    });
+
+    leptos_reactive::spawn_local(async move {
+      let status = match JsFuture::from(promise).await {
+        Ok(status) if !disposed.get() => status,
+        _ => return,
+      };
+      let read = {
+        let status = status.clone();
+        move || {
+          let value = js_sys::Reflect::get(&status, &"state".into())
+            .ok()
+            .and_then(|state| state.as_string())
+            .unwrap_or_default();
+          state.set(PermissionState::from_status(&value));
+        }
+      };
+      read();
+
+      let status = status.unchecked_into::<web_sys::EventTarget>();
+      let handler = Closure::wrap(Box::new(read) as Box<dyn Fn()>);
+      _ = status.add_event_listener_with_callback(
+        "change",
+        handler.as_ref().unchecked_ref(),
+      );
+      *listener.borrow_mut() = Some((status, handler));
+    });
+  }
+
+  /// Calls `navigator.permissions.query({ name })`, if it's supported.
+  fn start_query(name: &str) -> Option<js_sys::Promise> {
+    let permissions =
+      js_sys::Reflect::get(&crate::window().navigator(), &"permissions".into())
+        .ok()
+        .filter(|permissions| !permissions.is_undefined())?;
+    let query = js_sys::Reflect::get(&permissions, &"query".into())
+      .ok()?
+      .dyn_into::<js_sys::Function>()
+      .ok()?;
+
+    let descriptor = js_sys::Object::new();
+    js_sys::Reflect::set(&descriptor, &"name".into(), &JsValue::from_str(name))
+      .ok()?;
+    // throws synchronously in some browsers if the name isn't recognized
+    query
+      .call1(&permissions, &descriptor)
+      .ok()?
+      .dyn_into::<js_sys::Promise>()
+      .ok()
+  }
+}
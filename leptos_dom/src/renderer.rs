@@ -0,0 +1,206 @@
+#![cfg(not(all(target_arch = "wasm32", feature = "web")))]
+
+//! An experimental seam for rendering views to targets other than the DOM.
+
+use crate::{ssr::HYDRATION_MARKERS, IntoView, ViewNode};
+use leptos_reactive::{create_isomorphic_effect, Scope, ScopeDisposer};
+use std::{borrow::Cow, cell::RefCell, rc::Rc};
+
+/// A target that views can be rendered to instead of the DOM, like a
+/// terminal UI, a canvas scene graph, or a tree that tests can inspect.
+///
+/// This is **experimental**. Views are built by the same components and
+/// reactive system as for the DOM, but a renderer only sees elements, text,
+/// and HTML that was rendered ahead of time. Event listeners and properties
+/// are not passed on, and when a signal that a view read changes, the whole
+/// view is rendered again rather than only the part of it that changed.
+///
+/// Views that use the `view` macro's server-side templates are given to the
+/// renderer as HTML, so renderers should be used without the `ssr` feature.
+pub trait Renderer {
+  /// A node in the target's tree.
+  type Node;
+
+  /// Creates an element with the given tag name and attributes.
+  fn create_element(
+    &mut self,
+    name: &str,
+    attrs: &[(Cow<'static, str>, Cow<'static, str>)],
+  ) -> Self::Node;
+
+  /// Creates a text node.
+  fn create_text(&mut self, text: &str) -> Self::Node;
+
+  /// Creates a node for HTML that was rendered ahead of time. By default,
+  /// this creates a text node with the HTML.
+  fn create_html(&mut self, html: &str) -> Self::Node {
+    self.create_text(html)
+  }
+
+  /// Adds a node as the last child of `parent`.
+  fn append_child(&mut self, parent: &Self::Node, child: Self::Node);
+
+  /// Removes all of the children of `parent`.
+  fn clear_children(&mut self, parent: &Self::Node);
+}
+
+/// Renders the view returned by `f` as the children of `root`, using the
+/// given [`Renderer`], and renders it again whenever the signals it read
+/// change. Returns a disposer that stops rendering it.
+///
+/// ```
+/// # use leptos::*;
+/// # use std::{borrow::Cow, cell::RefCell, rc::Rc};
+/// /// A tree of nodes that tests can inspect.
+/// #[derive(Clone)]
+/// struct TestNode(Rc<RefCell<(String, Vec<TestNode>)>>);
+///
+/// impl TestNode {
+///   fn new(name: String) -> Self {
+///     Self(Rc::new(RefCell::new((name, Vec::new()))))
+///   }
+///
+///   fn print(&self) -> String {
+///     let (name, children) = &*self.0.borrow();
+///     if children.is_empty() {
+///       name.clone()
+///     } else {
+///       let children = children.iter().map(TestNode::print).collect::<Vec<_>>();
+///       format!("{name}({})", children.join(", "))
+///     }
+///   }
+/// }
+///
+/// struct TestRenderer;
+///
+/// impl Renderer for TestRenderer {
+///   type Node = TestNode;
+///
+///   fn create_element(
+///     &mut self,
+///     name: &str,
+///     _: &[(Cow<'static, str>, Cow<'static, str>)],
+///   ) -> TestNode {
+///     TestNode::new(name.to_string())
+///   }
+///
+///   fn create_text(&mut self, text: &str) -> TestNode {
+///     TestNode::new(format!("{text:?}"))
+///   }
+///
+///   fn append_child(&mut self, parent: &TestNode, child: TestNode) {
+///     parent.0.borrow_mut().1.push(child);
+///   }
+///
+///   fn clear_children(&mut self, parent: &TestNode) {
+///     parent.0.borrow_mut().1.clear();
+///   }
+/// }
+///
+/// # run_scope(create_runtime(), |cx| {
+/// let (count, set_count) = create_signal(cx, 0);
+/// let root = TestNode::new("root".to_string());
+///
+/// render_with(cx, Rc::new(RefCell::new(TestRenderer)), root.clone(), move |cx| {
+///   ul(cx).child(li(cx).child(move || count.get().to_string()))
+/// });
+/// assert_eq!(root.print(), r#"root(ul(li("0")))"#);
+///
+/// set_count.set(1);
+/// assert_eq!(root.print(), r#"root(ul(li("1")))"#);
+/// # });
+/// ```
+pub fn render_with<R, F, N>(
+  cx: Scope,
+  renderer: Rc<RefCell<R>>,
+  root: R::Node,
+  f: F,
+) -> ScopeDisposer
+where
+  R: Renderer + 'static,
+  F: Fn(Scope) -> N + 'static,
+  N: IntoView,
+{
+  let (_, disposer) = cx.run_child_scope(|cx| {
+    create_isomorphic_effect(cx, move |prev: Option<ScopeDisposer>| {
+      if let Some(prev) = prev {
+        prev.dispose();
+      }
+
+      let (view, disposer) = cx.run_child_scope(|cx| {
+        // markers are only needed to hydrate HTML
+        let prev_markers = HYDRATION_MARKERS.with(|m| m.replace(false));
+        let view = f(cx).into_view(cx);
+        HYDRATION_MARKERS.with(|m| m.set(prev_markers));
+        view
+      });
+
+      let mut renderer = renderer.borrow_mut();
+      renderer.clear_children(&root);
+      render_node(&mut *renderer, &root, view.into());
+
+      disposer
+    });
+  });
+
+  disposer
+}
+
+fn render_node<R: Renderer>(
+  renderer: &mut R,
+  parent: &R::Node,
+  node: ViewNode,
+) {
+  match node {
+    ViewNode::Element {
+      name,
+      attrs,
+      id,
+      children,
+      ..
+    } => {
+      // leave out the attributes that are only used for hydration
+      let hydration_id = format!("_{id}");
+      let attrs = attrs
+        .into_iter()
+        .filter(|(name, value)| {
+          !(name == "leptos-hk" || (name == "id" && *value == hydration_id))
+        })
+        .collect::<Vec<_>>();
+
+      let element = renderer.create_element(&name, &attrs);
+      for child in children {
+        render_node(renderer, &element, child);
+      }
+      renderer.append_child(parent, element);
+    }
+    ViewNode::Html(html) => {
+      if !html.is_empty() {
+        let node = renderer.create_html(&html);
+        renderer.append_child(parent, node);
+      }
+    }
+    ViewNode::Text(text) => {
+      if !text.is_empty() {
+        let node = renderer.create_text(&text);
+        renderer.append_child(parent, node);
+      }
+    }
+    ViewNode::Component { children, .. } => {
+      for child in children {
+        render_node(renderer, parent, child);
+      }
+    }
+    ViewNode::DynChild { child, .. } => {
+      if let Some(child) = child {
+        render_node(renderer, parent, *child);
+      }
+    }
+    ViewNode::Each { items, .. } => {
+      for (_, item) in items {
+        render_node(renderer, parent, item);
+      }
+    }
+    ViewNode::Unit { .. } => {}
+  }
+}
@@ -0,0 +1,268 @@
+#![cfg(not(all(target_arch = "wasm32", feature = "web")))]
+
+//! A serializable form of server-rendered views, so that rendered fragments
+//! can be cached.
+
+use crate::{
+  ssr::{
+    render_dyn_text, render_element, render_unit, to_kebab_case,
+    wrap_in_markers,
+  },
+  CoreComponent, Element, HydrationCtx, HydrationKey, IntoView, View,
+};
+use itertools::Itertools;
+use leptos_reactive::Scope;
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+
+/// The intermediate representation of a [View] that is rendered to HTML on
+/// the server.
+///
+/// Unlike a [View], it can be serialized, e.g., to cache the fragment in a
+/// key-value store. Text is kept as escaped HTML, and the parts of the view
+/// that can change on the client, like dynamic children and lists, are kept
+/// with the hydration keys of their markers, so the HTML rendered from a
+/// `ViewNode` can still be hydrated.
+///
+/// Rendering a [View] to a string doesn't go through a `ViewNode`; one is only
+/// built when it's asked for, e.g., by [SerializedView::render].
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ViewNode {
+  /// An HTML element.
+  Element {
+    /// The tag name.
+    name: Cow<'static, str>,
+    /// The attributes, whose values are already escaped for HTML.
+    attrs: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    /// Whether the element is a void element, like `<br/>`.
+    is_void: bool,
+    /// The hydration key of the element.
+    id: HydrationKey,
+    /// The element's children.
+    children: Vec<ViewNode>,
+  },
+  /// HTML that was already rendered, e.g., by the `view` macro's templates.
+  Html(Cow<'static, str>),
+  /// Escaped text.
+  Text(Cow<'static, str>),
+  /// A component and its children.
+  Component {
+    /// The component's name, which is only known on debug builds.
+    name: Cow<'static, str>,
+    /// The hydration key of the component's markers.
+    id: HydrationKey,
+    /// The component's children.
+    children: Vec<ViewNode>,
+  },
+  /// A hole for a dynamic child, e.g., `{move || count.get()}`.
+  DynChild {
+    /// The hydration key of the child's markers.
+    id: HydrationKey,
+    /// The child as it was rendered on the server.
+    child: Option<Box<ViewNode>>,
+  },
+  /// A keyed list, e.g., rendered by `<For/>`.
+  Each {
+    /// The hydration key of the list's markers.
+    id: HydrationKey,
+    /// Each item, with the hydration key of its markers.
+    items: Vec<(HydrationKey, ViewNode)>,
+  },
+  /// An empty view, which only renders a marker.
+  Unit {
+    /// The hydration key of the marker.
+    id: HydrationKey,
+  },
+}
+
+impl From<View> for ViewNode {
+  fn from(view: View) -> Self {
+    match view {
+      View::Text(node) => Self::Text(node.content),
+      View::Component(node) => {
+        #[cfg(debug_assertions)]
+        let name = node.name;
+        #[cfg(not(debug_assertions))]
+        let name = Cow::Borrowed("");
+
+        Self::Component {
+          name,
+          id: node.id,
+          children: node.children.into_iter().map(Self::from).collect(),
+        }
+      }
+      View::CoreComponent(CoreComponent::Unit(u)) => Self::Unit { id: u.id },
+      View::CoreComponent(CoreComponent::DynChild(node)) => {
+        let child = node.child.take();
+
+        Self::DynChild {
+          id: node.id,
+          child: (*child).map(|child| Box::new(child.into())),
+        }
+      }
+      View::CoreComponent(CoreComponent::Each(node)) => {
+        let children = node.children.take();
+
+        Self::Each {
+          id: node.id,
+          items: children
+            .into_iter()
+            .flatten()
+            .map(|item| (item.id, item.child.into()))
+            .collect(),
+        }
+      }
+      View::Element(el) => match el.prerendered {
+        Some(prerendered) => Self::Html(prerendered),
+        None => Self::Element {
+          name: el.name,
+          attrs: el.attrs.into_iter().collect(),
+          is_void: el.is_void,
+          id: el.id,
+          children: el.children.into_iter().map(Self::from).collect(),
+        },
+      },
+      View::Transparent(_) => Self::Html(Cow::Borrowed("")),
+    }
+  }
+}
+
+impl ViewNode {
+  /// Consumes the node and renders it into an HTML string, in the same way
+  /// as [View::render_to_string].
+  pub fn render_to_string(self) -> Cow<'static, str> {
+    match self {
+      ViewNode::Html(html) | ViewNode::Text(html) => html,
+      ViewNode::Component { name, id, children } => {
+        let content = children
+          .into_iter()
+          .map(ViewNode::render_to_string)
+          .join("");
+
+        wrap_in_markers(&to_kebab_case(&name), &id, content.into())
+      }
+      ViewNode::Unit { id } => render_unit(&id),
+      ViewNode::DynChild { id, child } => {
+        let content = match child.map(|child| *child) {
+          Some(ViewNode::Text(content)) => render_dyn_text(content),
+          Some(child) => child.render_to_string(),
+          None => "".into(),
+        };
+
+        wrap_in_markers("dyn-child", &id, content)
+      }
+      ViewNode::Each { id, items } => {
+        let content = items
+          .into_iter()
+          .map(|(id, node)| {
+            wrap_in_markers("each-item", &id, node.render_to_string())
+          })
+          .join("");
+
+        wrap_in_markers("each", &id, content.into())
+      }
+      ViewNode::Element {
+        name,
+        attrs,
+        is_void,
+        id,
+        children,
+      } => render_element(
+        &name,
+        &attrs,
+        is_void,
+        &id,
+        children.into_iter().map(ViewNode::render_to_string),
+      ),
+    }
+  }
+}
+
+/// A fragment of a view that was rendered on the server, which can be
+/// serialized and cached, e.g., in Redis or an edge key-value store, and
+/// rendered again without running its components.
+///
+/// The hydration keys of the elements in a fragment depend on where it's
+/// rendered in the page, so a cached fragment should be rendered in the same
+/// place as it was created, e.g., as the same child of the same component.
+/// It can then be hydrated on the client like the rest of the page.
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// # use leptos::*;
+/// # let runtime = create_runtime();
+/// # run_scope(runtime, |cx| {
+/// let fragment = SerializedView::render(cx, |cx| {
+///   view! { cx, <p class="post">"A post that rarely changes."</p> }
+/// });
+///
+/// // store it, e.g., as JSON...
+/// let json = serde_json::to_string(&fragment).unwrap();
+///
+/// // ...and render it again later, without running any components
+/// let cached: SerializedView = serde_json::from_str(&json).unwrap();
+/// assert_eq!(cached, fragment);
+/// assert!(cached.html().contains(r#"class="post""#));
+/// # });
+/// # runtime.dispose();
+/// # }}
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SerializedView {
+  start: HydrationKey,
+  end: HydrationKey,
+  view: ViewNode,
+}
+
+impl SerializedView {
+  /// Renders the view returned by `f` into a fragment that can be
+  /// serialized. This should be called where the fragment is rendered in
+  /// the page, just like rendering the view itself.
+  pub fn render<IV: IntoView>(cx: Scope, f: impl FnOnce(Scope) -> IV) -> Self {
+    let start = HydrationCtx::peek();
+    let view = f(cx).into_view(cx).into();
+    let end = HydrationCtx::peek();
+
+    Self { start, end, view }
+  }
+
+  /// The intermediate representation of the view.
+  pub fn view(&self) -> &ViewNode {
+    &self.view
+  }
+
+  /// Renders the fragment into an HTML string.
+  pub fn html(&self) -> Cow<'static, str> {
+    self.view.clone().render_to_string()
+  }
+}
+
+impl IntoView for SerializedView {
+  #[cfg_attr(
+    debug_assertions,
+    instrument(level = "trace", name = "SerializedView", skip_all)
+  )]
+  fn into_view(self, _: Scope) -> View {
+    if HydrationCtx::peek() != self.start {
+      crate::warn!(
+        "a cached `SerializedView` is being rendered with hydration key {}, \
+         but it was created with {}, so it will not hydrate correctly",
+        HydrationCtx::peek(),
+        self.start
+      );
+    }
+
+    // skip the hydration keys the fragment's components would have used
+    HydrationCtx::continue_from(self.end);
+
+    View::Element(Element {
+      name: "".into(),
+      is_void: false,
+      attrs: Default::default(),
+      children: Default::default(),
+      prerendered: Some(self.view.render_to_string()),
+      id: Default::default(),
+    })
+  }
+}
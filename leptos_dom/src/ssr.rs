@@ -1,11 +1,18 @@
 #![cfg(not(all(target_arch = "wasm32", feature = "web")))]
 
-use crate::{CoreComponent, HydrationCtx, IntoView, View};
+use crate::{
+  nonce_attr, use_nonce, CoreComponent, HydrationCtx, HydrationKey, IntoView,
+  View,
+};
 use cfg_if::cfg_if;
 use futures::{stream::FuturesUnordered, Stream, StreamExt};
 use itertools::Itertools;
 use leptos_reactive::*;
-use std::borrow::Cow;
+use std::{borrow::Cow, cell::Cell, rc::Rc};
+
+thread_local! {
+  pub(crate) static HYDRATION_MARKERS: Cell<bool> = Cell::new(true);
+}
 
 /// Renders the given function to a static HTML string.
 ///
@@ -27,19 +34,218 @@ where
   let runtime = leptos_reactive::create_runtime();
   HydrationCtx::reset_id();
 
-  let html = leptos_reactive::run_scope(runtime, |cx| {
-    f(cx).into_view(cx).render_to_string(cx)
+  let (html, nonce) = leptos_reactive::run_scope(runtime, |cx| {
+    let html = f(cx).into_view(cx).render_to_string(cx);
+    (html, nonce_attr(cx))
   });
 
   runtime.dispose();
 
   #[cfg(debug_assertions)]
   {
-    format!("<style>[leptos]{{display:none;}}</style>{html}")
+    format!("<style{nonce}>[leptos]{{display:none;}}</style>{html}")
+  }
+
+  #[cfg(not(debug_assertions))]
+  format!("<style{nonce}>l-m{{display:none;}}</style>{html}")
+}
+
+/// Renders the given function to a static HTML string like [render_to_string],
+/// for a widget that will be mounted in the browser with
+/// [mount_scoped](crate::mount_scoped) under the same `name`.
+///
+/// The widget is rendered in a runtime of its own, and its hydration keys
+/// start with `name`, so that it can be embedded in a page next to other apps,
+/// including the one rendering the page, and still be hydrated on its own.
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// # use leptos::*;
+/// let html = render_to_string_scoped("cart", |cx| view! { cx,
+///   <p>"Your cart is empty."</p>
+/// });
+/// assert!(html.ends_with("<p id=\"_cart-0-1\">Your cart is empty.</p>"));
+/// # }}
+/// ```
+pub fn render_to_string_scoped<F, N>(name: &str, f: F) -> String
+where
+  F: FnOnce(Scope) -> N + 'static,
+  N: IntoView,
+{
+  // this may be called while the page the widget is embedded in is rendering
+  let prev_id = HydrationCtx::peek();
+  HydrationCtx::continue_from(HydrationKey::scoped(name));
+
+  let runtime = create_scoped_runtime(name);
+  let (html, nonce) = run_scope(runtime, |cx| {
+    let html = f(cx).into_view(cx).render_to_string(cx).into_owned();
+    (html, nonce_attr(cx))
+  });
+  runtime.dispose();
+
+  HydrationCtx::continue_from(prev_id);
+
+  #[cfg(debug_assertions)]
+  {
+    format!("<style{nonce}>[leptos]{{display:none;}}</style>{html}")
   }
 
   #[cfg(not(debug_assertions))]
-  format!("<style>l-m{{display:none;}}</style>{html}")
+  format!("<style{nonce}>l-m{{display:none;}}</style>{html}")
+}
+
+/// Renders the given function to an HTML fragment, without the document shell
+/// or any of the hydration markers and ids used by [render_to_string].
+///
+/// This can be used to reuse your app's components outside of the app itself,
+/// e.g., to return HTML from a server function for an htmx-style endpoint, or to
+/// render a transactional email. The view is rendered synchronously, so any
+/// `<Suspense/>` will render its fallback.
+///
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// # use leptos::*;
+/// let html = render_fragment_to_string(|cx| view! { cx,
+///   <p>"Hello, world!"</p>
+/// });
+/// assert_eq!(html, "<p>Hello, world!</p>");
+/// # }}
+/// ```
+pub fn render_fragment_to_string<F, N>(f: F) -> String
+where
+  F: FnOnce(Scope) -> N + 'static,
+  N: IntoView,
+{
+  render_fragment(f, false)
+}
+
+/// Renders the given function to an HTML fragment without the document shell,
+/// like [render_fragment_to_string], but keeps the hydration markers and ids.
+pub fn render_fragment_to_string_with_hydration_ids<F, N>(f: F) -> String
+where
+  F: FnOnce(Scope) -> N + 'static,
+  N: IntoView,
+{
+  render_fragment(f, true)
+}
+
+fn render_fragment<F, N>(f: F, hydration_markers: bool) -> String
+where
+  F: FnOnce(Scope) -> N + 'static,
+  N: IntoView,
+{
+  // this may be called while another view is being rendered on this thread,
+  // e.g., from a server function, so restore its hydration state afterward
+  let prev_id = HydrationCtx::peek();
+  let prev_markers = HYDRATION_MARKERS.with(|m| m.replace(hydration_markers));
+  HydrationCtx::reset_id();
+
+  let runtime = leptos_reactive::create_runtime();
+  let html = leptos_reactive::run_scope(runtime, |cx| {
+    f(cx).into_view(cx).render_to_string(cx).into_owned()
+  });
+  runtime.dispose();
+
+  HYDRATION_MARKERS.with(|m| m.set(prev_markers));
+  HydrationCtx::continue_from(prev_id);
+
+  html
+}
+
+/// Renders the given function to an HTML fragment without the document shell,
+/// like [render_fragment_to_string], but waits for the resources read under
+/// each `<Suspense/>` and renders its children instead of its fallback, as
+/// [render_to_stream] does once they have loaded.
+///
+/// This can be used to answer requests for part of a page, e.g., from htmx,
+/// which can't run the scripts that stream each `<Suspense/>` into place.
+pub async fn render_fragment_to_string_async<F, N>(f: F) -> String
+where
+  F: FnOnce(Scope) -> N + 'static,
+  N: IntoView,
+{
+  HydrationCtx::reset_id();
+
+  let runtime = create_runtime();
+  let ((mut html, cx), _, disposer) = run_scope_undisposed(runtime, |cx| {
+    provide_context(cx, AwaitSuspense);
+    let html =
+      without_markers(|| f(cx).into_view(cx).render_to_string(cx).into_owned());
+    (html, cx)
+  });
+
+  // a <Suspense/> inside another one is only registered once the outer one
+  // has rendered its children
+  loop {
+    let fragments = cx.pending_fragments();
+    if fragments.is_empty() {
+      break;
+    }
+    let fragments = futures::future::join_all(
+      fragments.into_iter().map(|(key, fragment)| async move {
+        (key, WithoutMarkers(fragment.html).await)
+      }),
+    )
+    .await;
+    for (key, fragment) in fragments {
+      html =
+        html.replacen(&AwaitSuspense::placeholder_html(&key), &fragment, 1);
+    }
+  }
+
+  disposer.dispose();
+  runtime.dispose();
+
+  html
+}
+
+/// Provided as context by [render_fragment_to_string_async], so that each
+/// `<Suspense/>` whose resources are still loading renders a placeholder,
+/// which is replaced with its children once they have loaded, instead of
+/// its fallback.
+#[derive(Clone, Copy, Debug)]
+pub struct AwaitSuspense;
+
+impl AwaitSuspense {
+  /// Marks where the children of the `<Suspense/>` with the given fragment
+  /// key go.
+  pub fn placeholder(cx: Scope, key: &str) -> View {
+    crate::HtmlElement::from_html(
+      cx,
+      crate::html::Template::default(),
+      Self::placeholder_html(key),
+    )
+    .into_view(cx)
+  }
+
+  fn placeholder_html(key: &str) -> String {
+    format!("<!--leptos-suspense-{key}-->")
+  }
+}
+
+fn without_markers<T>(f: impl FnOnce() -> T) -> T {
+  let prev_markers = HYDRATION_MARKERS.with(|m| m.replace(false));
+  let value = f();
+  HYDRATION_MARKERS.with(|m| m.set(prev_markers));
+  value
+}
+
+/// Renders without hydration markers while the inner future is polled, without
+/// changing them for other views rendered on this thread in the meantime.
+struct WithoutMarkers<F>(F);
+
+impl<F> std::future::Future for WithoutMarkers<F>
+where
+  F: std::future::Future + Unpin,
+{
+  type Output = F::Output;
+
+  fn poll(
+    mut self: std::pin::Pin<&mut Self>,
+    cx: &mut std::task::Context<'_>,
+  ) -> std::task::Poll<Self::Output> {
+    without_markers(|| std::pin::Pin::new(&mut self.0).poll(cx))
+  }
 }
 
 /// Renders a function to a stream of HTML strings.
@@ -54,6 +260,14 @@ where
 ///    it is waiting for a resource to resolve from the server, it doesn't run it initially.
 /// 3) HTML fragments to replace each `<Suspense/>` fallback with its actual data as the resources
 ///    read under that `<Suspense/>` resolve.
+///
+/// If any [blocking resources](leptos_reactive::create_blocking_resource) are created, the shell
+/// isn't sent until they have resolved, and it includes the HTML for every `<Suspense/>` that
+/// reads one.
+///
+/// By default, each `<Suspense/>` is sent as soon as it resolves, no matter where it is in the
+/// page, and the data for each resource is sent as soon as it loads. Provide [StreamingOptions]
+/// with a different [StreamingMode] to change this.
 pub fn render_to_stream(
   view: impl FnOnce(Scope) -> View + 'static,
 ) -> impl Stream<Item = String> {
@@ -109,7 +323,15 @@ pub fn render_to_stream_with_prefix_undisposed(
   let runtime = create_runtime();
 
   let (
-    (shell, prefix, pending_resources, pending_fragments, serializers),
+    (
+      shell,
+      prefix,
+      pending_resources,
+      (blocking_resources, blocking_fragments),
+      pending_fragments,
+      serializers,
+      streaming,
+    ),
     scope,
     _,
   ) = run_scope_undisposed(runtime, {
@@ -120,80 +342,91 @@ pub fn render_to_stream_with_prefix_undisposed(
 
       let resources = cx.pending_resources();
       let pending_resources = serde_json::to_string(&resources).unwrap();
-      let prefix = prefix(cx);
+      let blocking_resources = cx.pending_blocking_resources();
+      let (blocking_fragments, pending_fragments): (Vec<_>, Vec<_>) = cx
+        .pending_fragments()
+        .into_iter()
+        .partition(|(_, fragment)| fragment.should_block);
+
+      // blocking data may change the <head> (e.g., a <Title/> that depends on it),
+      // so the prefix waits for it
+      let prefix =
+        if blocking_resources.is_empty() && blocking_fragments.is_empty() {
+          Ok(prefix(cx))
+        } else {
+          Err(move || prefix(cx))
+        };
+
+      let mut streaming =
+        use_context::<StreamingOptions>(cx).unwrap_or_default();
+      // the stream's scripts are allowed by the same nonce as the rest of the
+      // page's
+      streaming.nonce = use_nonce(cx).map(|nonce| nonce.to_string());
 
       let shell = {
+        let nonce = streaming.nonce_attr();
+
         #[cfg(debug_assertions)]
         {
-          format!("<style>[leptos]{{display:none;}}</style>{shell}")
+          format!("<style{nonce}>[leptos]{{display:none;}}</style>{shell}")
         }
 
         #[cfg(not(debug_assertions))]
-        format!("<style>l-m{{display:none;}}</style>{shell}")
+        format!("<style{nonce}>l-m{{display:none;}}</style>{shell}")
       };
 
       (
         shell,
         prefix,
         pending_resources,
-        cx.pending_fragments(),
+        (blocking_resources, blocking_fragments),
+        pending_fragments,
         cx.serialization_resolvers(),
+        streaming,
       )
     }
   });
 
-  let fragments = FuturesUnordered::new();
-  for (fragment_id, (key_before, fut)) in pending_fragments {
-    fragments.push(async move { (fragment_id, key_before, fut.await) })
+  let mut pending_fragments = pending_fragments.into_iter().collect::<Vec<_>>();
+  if streaming.mode == StreamingMode::InOrder {
+    pending_fragments
+      .sort_by_cached_key(|(fragment_id, _)| document_order(fragment_id));
   }
+  let fragments =
+    pending_fragments
+      .into_iter()
+      .map(|(fragment_id, fragment)| async move {
+        (
+          fragment_id,
+          fragment.key_before_suspense,
+          fragment.html.await,
+        )
+      });
+  let fragments = match streaming.mode {
+    // each <Suspense/> as soon as it resolves
+    StreamingMode::OutOfOrder => {
+      fragments.collect::<FuturesUnordered<_>>().boxed_local()
+    }
+    // each <Suspense/> once it and every <Suspense/> before it have resolved
+    StreamingMode::InOrder => futures::stream::iter(fragments)
+      .then(|html| html)
+      .boxed_local(),
+  };
 
   // resources and fragments
   // stream HTML for each <Suspense/> as it resolves
-  let fragments = fragments.map(|(fragment_id, id_before_suspense, html)| {
-    cfg_if! {
-      if #[cfg(debug_assertions)] {
-        _ = id_before_suspense;
-        // Debug-mode <Suspense/>-replacement code
-        format!(
-          r#"
-                  <template id="{fragment_id}f">{html}</template>
-                  <script>
-                      var start = document.getElementById("_{fragment_id}o");
-                      var end = document.getElementById("_{fragment_id}c");
-                      var range = new Range();
-                      range.setStartBefore(start.nextSibling.nextSibling);
-                      range.setEndAfter(end.previousSibling.previousSibling);
-                      range.deleteContents();
-                      var tpl = document.getElementById("{fragment_id}f");
-                      end.parentNode.insertBefore(tpl.content.cloneNode(true), end.previousSibling);
-                  </script>
-                  "#
-        )
-      } else {
-        // Release-mode <Suspense/>-replacement code
-        format!(
-          r#"
-                  <template id="{fragment_id}f">{html}</template>
-                  <script>
-                      var start = document.getElementById("_{id_before_suspense}");
-                      var end = document.getElementById("_{fragment_id}");
-                      var range = new Range();
-                      range.setStartAfter(start);
-                      range.setEndBefore(end);
-                      range.deleteContents();
-                      var tpl = document.getElementById("{fragment_id}f");
-                      end.parentNode.insertBefore(tpl.content.cloneNode(true), end.previousSibling);
-                  </script>
-                  "#
-        )
-      }
-    }
-  });
+  let fragments = {
+    let streaming = streaming.clone();
+    fragments.map(move |(fragment_id, id_before_suspense, html)| {
+      streaming.fragment_to_string(&fragment_id, &id_before_suspense, &html)
+    })
+  };
   // stream data for each Resource as it resolves
-  let resources = serializers.map(|(id, json)| {
+  let nonce = streaming.nonce_attr();
+  let resources = serializers.map(move |(id, json)| {
     let id = serde_json::to_string(&id).unwrap();
     format!(
-      r#"<script>
+      r#"<script{nonce}>
                   if(__LEPTOS_RESOURCE_RESOLVERS.get({id})) {{
                       __LEPTOS_RESOURCE_RESOLVERS.get({id})({json:?})
                   }} else {{
@@ -205,11 +438,31 @@ pub fn render_to_stream_with_prefix_undisposed(
 
   // HTML for the view function and script to store resources
   let stream = futures::stream::once(async move {
+    // wait for blocking resources, and render any <Suspense/> that reads them,
+    // before sending anything
+    futures::future::join_all(blocking_resources).await;
+    let blocking_fragments =
+      futures::future::join_all(blocking_fragments.into_iter().map(
+        |(fragment_id, fragment)| async move {
+          let html = fragment.html.await;
+          (fragment_id, fragment.key_before_suspense, html)
+        },
+      ))
+      .await
+      .into_iter()
+      .map(|(fragment_id, id_before_suspense, html)| {
+        streaming.fragment_to_string(&fragment_id, &id_before_suspense, &html)
+      })
+      .collect::<String>();
+    let prefix = prefix.unwrap_or_else(|prefix| prefix());
+
+    let nonce = streaming.nonce_attr();
     format!(
       r#"
               {prefix}
               {shell}
-              <script>
+              {blocking_fragments}
+              <script{nonce}>
                   __LEPTOS_PENDING_RESOURCES = {pending_resources};
                   __LEPTOS_RESOLVED_RESOURCES = new Map();
                   __LEPTOS_RESOURCE_RESOLVERS = new Map();
@@ -217,206 +470,387 @@ pub fn render_to_stream_with_prefix_undisposed(
           "#
     )
   })
-  // TODO these should be combined again in a way that chains them appropriately
-  // such that individual resources can resolve before all fragments are done
-  .chain(fragments)
-  .chain(resources);
+  // resources can resolve before all fragments are done, and vice versa
+  .chain(futures::stream::select(fragments, resources));
 
   (stream, runtime, scope)
 }
 
+/// Controls the order of, and the inline scripts emitted while, streaming HTML
+/// with [render_to_stream] and related functions.
+///
+/// Provide this as context in the root of your app to change the defaults:
+/// ```
+/// # cfg_if::cfg_if! { if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// provide_context(
+///   cx,
+///   StreamingOptions {
+///     // satisfies a `script-src 'nonce-...'` Content Security Policy
+///     nonce: Some("r4nd0m".to_string()),
+///     // content is readable even if the swap scripts never run
+///     fragment_swap: FragmentSwap::NoJs,
+///     // content is revealed from the top of the page down
+///     mode: StreamingMode::InOrder,
+///   },
+/// );
+/// # });
+/// # }}
+/// ```
+#[derive(Clone, Default)]
+pub struct StreamingOptions {
+  /// A `nonce` attribute added to every inline `<script>` in the stream. A
+  /// [Nonce](crate::Nonce) provided with [provide_nonce](crate::provide_nonce)
+  /// takes its place, and is also added to the page's other inline scripts
+  /// and styles.
+  pub nonce: Option<String>,
+  /// How `<Suspense/>` fragments are sent once their resources resolve.
+  pub fragment_swap: FragmentSwap,
+  /// The order in which `<Suspense/>` fragments are sent.
+  pub mode: StreamingMode,
+}
+
+/// The order in which the HTML for each `<Suspense/>` is streamed.
+///
+/// In either mode, the shell is sent first, with the `fallback` of each
+/// `<Suspense/>` between two placeholder markers, and the resolved HTML is
+/// sent later with a small inline script that swaps it in between them. The
+/// data for each resource is sent as soon as it has loaded.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum StreamingMode {
+  /// Each `<Suspense/>` is sent as soon as its resources resolve, so a slow
+  /// `<Suspense/>` near the top of the page doesn't hold up the rest.
+  #[default]
+  OutOfOrder,
+  /// Each `<Suspense/>` is sent once it, and every `<Suspense/>` before it in
+  /// the page, have resolved, so that content appears from the top down and
+  /// doesn't shift around as it arrives.
+  InOrder,
+}
+
+/// Orders `<Suspense/>` fragment IDs, which are hydration keys like `0-2-11`,
+/// as they appear in the page.
+fn document_order(fragment_id: &str) -> Vec<usize> {
+  fragment_id
+    .split('-')
+    .filter_map(|part| part.parse().ok())
+    .collect()
+}
+
+/// How the HTML for a `<Suspense/>` is swapped in for its fallback once its
+/// resources have resolved.
+#[derive(Clone, Default)]
+pub enum FragmentSwap {
+  /// The HTML is sent in a `<template>`, and an inline script swaps it in.
+  #[default]
+  Script,
+  /// The HTML is sent in a visible `<leptos-fragment>` element, and an inline
+  /// script moves it into place. If scripts are disabled or blocked, the
+  /// content can still be read at the end of the page.
+  NoJs,
+  /// Generates the HTML for each fragment yourself, e.g., to integrate with
+  /// a custom transition animation.
+  Custom(Rc<dyn Fn(&StreamedFragment) -> String>),
+}
+
+impl std::fmt::Debug for FragmentSwap {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    match self {
+      Self::Script => write!(f, "Script"),
+      Self::NoJs => write!(f, "NoJs"),
+      Self::Custom(_) => write!(f, "Custom"),
+    }
+  }
+}
+
+impl std::fmt::Debug for StreamingOptions {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("StreamingOptions")
+      .field("nonce", &self.nonce)
+      .field("fragment_swap", &self.fragment_swap)
+      .field("mode", &self.mode)
+      .finish()
+  }
+}
+
+/// A `<Suspense/>` fragment whose resources have resolved, passed to
+/// [FragmentSwap::Custom].
+#[derive(Debug)]
+pub struct StreamedFragment<'a> {
+  /// The rendered HTML of the `<Suspense/>` children.
+  pub html: &'a str,
+  /// The `id` of the marker element before the fallback. The fallback's
+  /// nodes are everything between this marker and [end_marker_id](Self::end_marker_id),
+  /// not counting any whitespace or comment nodes around the markers in
+  /// debug builds.
+  pub start_marker_id: String,
+  /// The `id` of the marker element after the fallback.
+  pub end_marker_id: String,
+  /// A unique `id` that can be used for an element holding the HTML.
+  pub fragment_id: String,
+  /// The `nonce` attribute (including a leading space) to add to any inline
+  /// `<script>`, or an empty string.
+  pub nonce_attr: &'a str,
+}
+
+impl StreamingOptions {
+  fn nonce_attr(&self) -> String {
+    self
+      .nonce
+      .as_ref()
+      .map(|nonce| {
+        format!(
+          " nonce=\"{}\"",
+          html_escape::encode_double_quoted_attribute(nonce)
+        )
+      })
+      .unwrap_or_default()
+  }
+
+  fn fragment_to_string(
+    &self,
+    fragment_id: &str,
+    id_before_suspense: &str,
+    html: &str,
+  ) -> String {
+    let nonce = self.nonce_attr();
+    let (start_marker_id, end_marker_id) = if cfg!(debug_assertions) {
+      (format!("_{fragment_id}o"), format!("_{fragment_id}c"))
+    } else {
+      (format!("_{id_before_suspense}"), format!("_{fragment_id}"))
+    };
+    // in debug mode, the markers are surrounded by whitespace and comments
+    let (set_start, set_end) = if cfg!(debug_assertions) {
+      (
+        "range.setStartBefore(start.nextSibling.nextSibling);",
+        "range.setEndAfter(end.previousSibling.previousSibling);",
+      )
+    } else {
+      ("range.setStartAfter(start);", "range.setEndBefore(end);")
+    };
+
+    match &self.fragment_swap {
+      FragmentSwap::Script => format!(
+        r#"
+                  <template id="{fragment_id}f">{html}</template>
+                  <script{nonce}>
+                      var start = document.getElementById("{start_marker_id}");
+                      var end = document.getElementById("{end_marker_id}");
+                      var range = new Range();
+                      {set_start}
+                      {set_end}
+                      range.deleteContents();
+                      var tpl = document.getElementById("{fragment_id}f");
+                      end.parentNode.insertBefore(tpl.content.cloneNode(true), end.previousSibling);
+                  </script>
+                  "#
+      ),
+      FragmentSwap::NoJs => format!(
+        r#"
+                  <leptos-fragment id="{fragment_id}f">{html}</leptos-fragment>
+                  <script{nonce}>
+                      var start = document.getElementById("{start_marker_id}");
+                      var end = document.getElementById("{end_marker_id}");
+                      var range = new Range();
+                      {set_start}
+                      {set_end}
+                      range.deleteContents();
+                      var frag = document.getElementById("{fragment_id}f");
+                      var content = document.createDocumentFragment();
+                      while (frag.firstChild) content.appendChild(frag.firstChild);
+                      end.parentNode.insertBefore(content, end.previousSibling);
+                      frag.remove();
+                  </script>
+                  "#
+      ),
+      FragmentSwap::Custom(f) => f(&StreamedFragment {
+        html,
+        start_marker_id,
+        end_marker_id,
+        fragment_id: format!("{fragment_id}f"),
+        nonce_attr: &nonce,
+      }),
+    }
+  }
+}
+
 impl View {
   /// Consumes the node and renders it into an HTML string.
-  pub fn render_to_string(self, _cx: Scope) -> Cow<'static, str> {
-    self.render_to_string_helper()
+  pub fn render_to_string(self, cx: Scope) -> Cow<'static, str> {
+    crate::critical_css::with_selector_collector(cx, || {
+      self.render_to_string_helper()
+    })
   }
 
   pub(crate) fn render_to_string_helper(self) -> Cow<'static, str> {
     match self {
       View::Text(node) => node.content,
       View::Component(node) => {
-        let content = || {
-          node
-            .children
-            .into_iter()
-            .map(|node| node.render_to_string_helper())
-            .join("")
-        };
-        cfg_if! {
-          if #[cfg(debug_assertions)] {
-            format!(r#"<leptos-{name}-start leptos id="{}"></leptos-{name}-start>{}<leptos-{name}-end leptos id="{}"></leptos-{name}-end>"#,
-              HydrationCtx::to_string(&node.id, false),
-              content(),
-              HydrationCtx::to_string(&node.id, true),
-              name = to_kebab_case(&node.name)
-            ).into()
-          } else {
-            format!(
-              r#"{}<l-m id="{}"></l-m>"#,
-              content(),
-              HydrationCtx::to_string(&node.id, true)
-            ).into()
-          }
-        }
+        #[cfg(debug_assertions)]
+        let name = to_kebab_case(&node.name);
+        #[cfg(not(debug_assertions))]
+        let name = "";
+
+        let content = node
+          .children
+          .into_iter()
+          .map(View::render_to_string_helper)
+          .join("");
+
+        wrap_in_markers(&name, &node.id, content.into())
       }
-      View::CoreComponent(node) => {
-        let (id, name, wrap, content) = match node {
-          CoreComponent::Unit(u) => (
-            u.id.clone(),
-            "",
-            false,
-            Box::new(move || {
-              #[cfg(debug_assertions)]
-              {
-                format!(
-                  "<leptos-unit leptos id={}></leptos-unit>",
-                  HydrationCtx::to_string(&u.id, true)
-                )
-                .into()
-              }
-
-              #[cfg(not(debug_assertions))]
-              format!("<l-m id={}></l-m>", HydrationCtx::to_string(&u.id, true))
-                .into()
-            }) as Box<dyn FnOnce() -> Cow<'static, str>>,
-          ),
-          CoreComponent::DynChild(node) => {
-            let child = node.child.take();
-            (
-              node.id,
-              "dyn-child",
-              true,
-              Box::new(move || {
-                if let Some(child) = *child {
-                  // On debug builds, `DynChild` has two marker nodes,
-                  // so there is no way for the text to be merged with
-                  // surrounding text when the browser parses the HTML,
-                  // but in release, `DynChild` only has a trailing marker,
-                  // and the browser automatically merges the dynamic text
-                  // into one single node, so we need to artificially make the
-                  // browser create the dynamic text as it's own text node
-                  if let View::Text(t) = child {
-                    if !cfg!(debug_assertions) {
-                      format!("<!>{}", t.content).into()
-                    } else {
-                      t.content
-                    }
-                  } else {
-                    child.render_to_string_helper()
-                  }
-                } else {
-                  "".into()
-                }
-              }) as Box<dyn FnOnce() -> Cow<'static, str>>,
-            )
-          }
-          CoreComponent::Each(node) => {
-            let children = node.children.take();
-
-            (
-              node.id,
-              "each",
-              true,
-              Box::new(move || {
-                children
-                  .into_iter()
-                  .flatten()
-                  .map(|node| {
-                    let id = node.id;
-
-                    let content = || node.child.render_to_string_helper();
-
-                    #[cfg(debug_assertions)]
-                    {
-                      format!(
-                        "<leptos-each-item-start leptos \
-                         id=\"{}\"></\
-                         leptos-each-item-start>{}<leptos-each-item-end \
-                         leptos id=\"{}\"></leptos-each-item-end>",
-                        HydrationCtx::to_string(&id, false),
-                        content(),
-                        HydrationCtx::to_string(&id, true),
-                      )
-                    }
-
-                    #[cfg(not(debug_assertions))]
-                    format!(
-                      "{}<l-m id=\"{}\"></l-m>",
-                      content(),
-                      HydrationCtx::to_string(&id, true)
-                    )
-                  })
-                  .join("")
-                  .into()
-              }) as Box<dyn FnOnce() -> Cow<'static, str>>,
-            )
-          }
+      View::CoreComponent(CoreComponent::Unit(u)) => render_unit(&u.id),
+      View::CoreComponent(CoreComponent::DynChild(node)) => {
+        let child = node.child.take();
+        let content = match *child {
+          Some(View::Text(t)) => render_dyn_text(t.content),
+          Some(child) => child.render_to_string_helper(),
+          None => "".into(),
         };
 
-        if wrap {
-          cfg_if! {
-            if #[cfg(debug_assertions)] {
-              format!(
-                r#"<leptos-{name}-start leptos id="{}"></leptos-{name}-start>{}<leptos-{name}-end leptos id="{}"></leptos-{name}-end>"#,
-                HydrationCtx::to_string(&id, false),
-                content(),
-                HydrationCtx::to_string(&id, true),
-              ).into()
-            } else {
-              let _ = name;
-
-              format!(
-                r#"{}<l-m id="{}"></l-m>"#,
-                content(),
-                HydrationCtx::to_string(&id, true)
-              ).into()
-            }
-          }
-        } else {
-          content()
-        }
+        wrap_in_markers("dyn-child", &node.id, content)
       }
-      View::Element(el) => {
-        if let Some(prerendered) = el.prerendered {
-          prerendered
-        } else {
-          let tag_name = el.name;
-
-          let attrs = el
-            .attrs
-            .into_iter()
-            .map(|(name, value)| -> Cow<'static, str> {
-              if value.is_empty() {
-                format!(" {name}").into()
-              } else {
-                format!(
-                  " {name}=\"{}\"",
-                  html_escape::encode_double_quoted_attribute(&value)
-                )
-                .into()
-              }
-            })
-            .join("");
-
-          if el.is_void {
-            format!("<{tag_name}{attrs}/>").into()
-          } else {
-            let children = el
-              .children
-              .into_iter()
-              .map(|node| node.render_to_string_helper())
-              .join("");
-
-            format!("<{tag_name}{attrs}>{children}</{tag_name}>").into()
-          }
-        }
+      View::CoreComponent(CoreComponent::Each(node)) => {
+        let children = node.children.take();
+        let content = children
+          .into_iter()
+          .flatten()
+          .map(|item| {
+            wrap_in_markers(
+              "each-item",
+              &item.id,
+              item.child.render_to_string_helper(),
+            )
+          })
+          .join("");
+
+        wrap_in_markers("each", &node.id, content.into())
       }
+      View::Element(el) => match el.prerendered {
+        Some(prerendered) => prerendered,
+        None => render_element(
+          &el.name,
+          &el.attrs,
+          el.is_void,
+          &el.id,
+          el.children.into_iter().map(View::render_to_string_helper),
+        ),
+      },
       View::Transparent(_) => Default::default(),
     }
   }
 }
 
-fn to_kebab_case(name: &str) -> String {
+/// Adds the markers of a component, dynamic child or list around its
+/// content.
+pub(crate) fn wrap_in_markers(
+  name: &str,
+  id: &HydrationKey,
+  content: Cow<'static, str>,
+) -> Cow<'static, str> {
+  if !HYDRATION_MARKERS.with(Cell::get) {
+    return content;
+  }
+
+  cfg_if! {
+    if #[cfg(debug_assertions)] {
+      format!(
+        r#"<leptos-{name}-start leptos id="{}"></leptos-{name}-start>{}<leptos-{name}-end leptos id="{}"></leptos-{name}-end>"#,
+        HydrationCtx::to_string(id, false),
+        content,
+        HydrationCtx::to_string(id, true),
+      ).into()
+    } else {
+      let _ = name;
+
+      format!(
+        r#"{}<l-m id="{}"></l-m>"#,
+        content,
+        HydrationCtx::to_string(id, true)
+      ).into()
+    }
+  }
+}
+
+/// Renders the marker of an empty view.
+pub(crate) fn render_unit(id: &HydrationKey) -> Cow<'static, str> {
+  if !HYDRATION_MARKERS.with(Cell::get) {
+    // units only render a marker
+    return Default::default();
+  }
+
+  #[cfg(debug_assertions)]
+  {
+    format!(
+      "<leptos-unit leptos id={}></leptos-unit>",
+      HydrationCtx::to_string(id, true)
+    )
+    .into()
+  }
+
+  #[cfg(not(debug_assertions))]
+  format!("<l-m id={}></l-m>", HydrationCtx::to_string(id, true)).into()
+}
+
+/// Renders the text of a dynamic child.
+pub(crate) fn render_dyn_text(content: Cow<'static, str>) -> Cow<'static, str> {
+  // On debug builds, `DynChild` has two marker nodes,
+  // so there is no way for the text to be merged with
+  // surrounding text when the browser parses the HTML,
+  // but in release, `DynChild` only has a trailing marker,
+  // and the browser automatically merges the dynamic text
+  // into one single node, so we need to artificially make the
+  // browser create the dynamic text as it's own text node
+  if !cfg!(debug_assertions) && HYDRATION_MARKERS.with(Cell::get) {
+    format!("<!>{content}").into()
+  } else {
+    content
+  }
+}
+
+/// Renders an element, given its children rendered to HTML.
+pub(crate) fn render_element(
+  tag_name: &str,
+  attrs: &[(Cow<'static, str>, Cow<'static, str>)],
+  is_void: bool,
+  id: &HydrationKey,
+  children: impl Iterator<Item = Cow<'static, str>>,
+) -> Cow<'static, str> {
+  crate::critical_css::record_element(
+    tag_name,
+    attrs.iter().map(|(name, value)| (&**name, &**value)),
+  );
+
+  let hydration_markers = HYDRATION_MARKERS.with(Cell::get);
+  let hydration_id = format!("_{id}");
+
+  let attrs = attrs
+    .iter()
+    .filter(|(name, value)| {
+      hydration_markers
+        || !(name == "leptos-hk" || (name == "id" && *value == hydration_id))
+    })
+    .map(|(name, value)| {
+      if value.is_empty() {
+        format!(" {name}")
+      } else {
+        // escaped by `HtmlElement::attr`
+        format!(" {name}=\"{value}\"")
+      }
+    })
+    .join("");
+
+  if is_void {
+    format!("<{tag_name}{attrs}/>").into()
+  } else {
+    let children = children.join("");
+
+    format!("<{tag_name}{attrs}>{children}</{tag_name}>").into()
+  }
+}
+
+pub(crate) fn to_kebab_case(name: &str) -> String {
   if name.is_empty() {
     return String::new();
   }
@@ -449,4 +883,4 @@ fn to_kebab_case(name: &str) -> String {
   }
 
   new_name
-}
\ No newline at end of file
+}
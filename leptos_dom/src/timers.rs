@@ -0,0 +1,212 @@
+//! Scope-aware timers.
+
+use leptos_reactive::{
+  create_rw_signal, on_cleanup, store_value, RwSignal, Scope, Signal,
+  StoredValue, UntrackedGettableSignal,
+};
+use std::{rc::Rc, time::Duration};
+
+/// A timer created by [use_interval] or [use_timeout], which can be paused,
+/// resumed, and reset.
+///
+/// The timer is cancelled when the [Scope] that created it is disposed. Timers
+/// never run during server rendering.
+#[derive(Clone, Copy)]
+pub struct Timer {
+  is_active: RwSignal<bool>,
+  count: RwSignal<u64>,
+  state: StoredValue<TimerState>,
+}
+
+// timers are only started in the browser
+#[cfg_attr(not(all(target_arch = "wasm32", feature = "web")), allow(dead_code))]
+struct TimerState {
+  repeat: bool,
+  duration: Duration,
+  /// For timeouts, the time left when the timer was paused.
+  remaining: Duration,
+  started_at: f64,
+  handle: Option<i32>,
+  callback: Rc<dyn Fn()>,
+}
+
+impl std::fmt::Debug for Timer {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    f.debug_struct("Timer")
+      .field("is_active", &self.is_active)
+      .field("count", &self.count)
+      .finish()
+  }
+}
+
+/// Calls `callback` every `duration`, until the timer is paused or the scope
+/// is disposed.
+///
+/// ```
+/// # use leptos::*;
+/// # use std::time::Duration;
+/// # run_scope(create_runtime(), |cx| {
+/// let (seconds, set_seconds) = create_signal(cx, 0);
+/// let timer = use_interval(cx, Duration::from_secs(1), move || {
+///   set_seconds.update(|n| *n += 1)
+/// });
+///
+/// // e.g., in a "pause" button's click handler
+/// timer.pause();
+/// # });
+/// ```
+pub fn use_interval(
+  cx: Scope,
+  duration: Duration,
+  callback: impl Fn() + 'static,
+) -> Timer {
+  Timer::new(cx, duration, true, Rc::new(callback))
+}
+
+/// Calls `callback` once, after `duration`, unless the timer is paused or the
+/// scope is disposed first.
+pub fn use_timeout(
+  cx: Scope,
+  duration: Duration,
+  callback: impl Fn() + 'static,
+) -> Timer {
+  Timer::new(cx, duration, false, Rc::new(callback))
+}
+
+impl Timer {
+  fn new(
+    cx: Scope,
+    duration: Duration,
+    repeat: bool,
+    callback: Rc<dyn Fn()>,
+  ) -> Self {
+    let timer = Timer {
+      is_active: create_rw_signal(cx, false),
+      count: create_rw_signal(cx, 0),
+      state: store_value(
+        cx,
+        TimerState {
+          repeat,
+          duration,
+          remaining: duration,
+          started_at: 0.0,
+          handle: None,
+          callback,
+        },
+      ),
+    };
+    timer.start();
+    on_cleanup(cx, move || timer.stop());
+    timer
+  }
+
+  /// Whether the timer is currently running. A timeout is no longer active
+  /// once it has fired.
+  pub fn is_active(&self) -> Signal<bool> {
+    self.is_active.into()
+  }
+
+  /// The number of times the timer has fired since it was created or reset.
+  pub fn count(&self) -> Signal<u64> {
+    self.count.into()
+  }
+
+  /// Stops the timer. A paused timeout keeps track of the time it had left.
+  pub fn pause(&self) {
+    if self.is_active.get_untracked() {
+      self.stop();
+      #[cfg(all(target_arch = "wasm32", feature = "web"))]
+      self.state.update(|state| {
+        if !state.repeat {
+          let elapsed = Duration::from_secs_f64(
+            ((js_sys::Date::now() - state.started_at) / 1000.0).max(0.0),
+          );
+          state.remaining = state.remaining.saturating_sub(elapsed);
+        }
+      });
+    }
+  }
+
+  /// Restarts a paused timer. An interval waits its full duration before
+  /// firing again; a timeout waits for the time it had left.
+  pub fn resume(&self) {
+    if !self.is_active.get_untracked() {
+      self.start();
+    }
+  }
+
+  /// Restarts the timer from the beginning, whether or not it's active, and
+  /// resets [Timer::count] to `0`.
+  pub fn reset(&self) {
+    self.stop();
+    self.state.update(|state| state.remaining = state.duration);
+    self.count.set(0);
+    self.start();
+  }
+
+  fn start(&self) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    {
+      use wasm_bindgen::{prelude::Closure, JsCast, UnwrapThrowExt};
+
+      let this = *self;
+      let (repeat, delay, callback) = self.state.with(|state| {
+        let delay = if state.repeat {
+          state.duration
+        } else {
+          state.remaining
+        };
+        (state.repeat, delay, Rc::clone(&state.callback))
+      });
+      let delay = delay.as_millis().try_into().unwrap_throw();
+
+      let handle = if repeat {
+        let tick = Closure::wrap(Box::new(move || {
+          this.count.update(|n| *n += 1);
+          callback();
+        }) as Box<dyn Fn()>)
+        .into_js_value();
+        crate::window().set_interval_with_callback_and_timeout_and_arguments_0(
+          tick.unchecked_ref(),
+          delay,
+        )
+      } else {
+        let fire = Closure::once_into_js(move || {
+          this.state.update(|state| {
+            state.handle = None;
+            state.remaining = state.duration;
+          });
+          this.is_active.set(false);
+          this.count.update(|n| *n += 1);
+          callback();
+        });
+        crate::window().set_timeout_with_callback_and_timeout_and_arguments_0(
+          fire.unchecked_ref(),
+          delay,
+        )
+      };
+
+      if let Ok(handle) = handle {
+        self.state.update(|state| {
+          state.handle = Some(handle);
+          state.started_at = js_sys::Date::now();
+        });
+        self.is_active.set(true);
+      }
+    }
+  }
+
+  fn stop(&self) {
+    #[cfg(all(target_arch = "wasm32", feature = "web"))]
+    self.state.update(|state| {
+      if let Some(handle) = state.handle.take() {
+        if state.repeat {
+          crate::window().clear_interval_with_handle(handle);
+        } else {
+          crate::window().clear_timeout_with_handle(handle);
+        }
+      }
+    });
+    self.is_active.set(false);
+  }
+}
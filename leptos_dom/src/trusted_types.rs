@@ -0,0 +1,202 @@
+//! Support for the [Trusted Types API](https://developer.mozilla.org/en-US/docs/Web/API/Trusted_Types_API).
+//!
+//! When a page's Content Security Policy includes
+//! `require-trusted-types-for 'script'`, the browser throws if a plain string
+//! is assigned to an HTML or script sink like `innerHTML`. Where the API is
+//! present, the framework's own sinks, like [`HtmlElement::inner_html`](crate::HtmlElement::inner_html)
+//! and the `inner_html` attribute, pass their values through a Trusted Types
+//! policy first. The policy is named `leptos` unless another one is
+//! registered with [register_trusted_types_policy], so the CSP has to allow
+//! it, e.g., with `trusted-types leptos`.
+//!
+//! The scripts that the server streams to swap in `<Suspense/>` fragments are
+//! parsed with the page, rather than assigned to a sink, so they only need to
+//! be allowed by the [Nonce](crate::Nonce) provided with
+//! [provide_nonce](crate::provide_nonce).
+
+use std::{borrow::Cow, cell::RefCell, fmt, rc::Rc};
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+use wasm_bindgen::{prelude::Closure, JsCast, JsValue};
+
+type Sanitizer = Rc<dyn Fn(&str) -> String>;
+
+thread_local! {
+  static POLICY_CONFIG: RefCell<Option<TrustedTypesPolicy>> = RefCell::new(None);
+  /// The policy that was created, once it has been used: `None` if the
+  /// browser doesn't support Trusted Types.
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  static POLICY: RefCell<Option<Option<JsValue>>> = RefCell::new(None);
+}
+
+/// The Trusted Types policy that the framework creates its `TrustedHTML` and
+/// `TrustedScript` values with, given to [register_trusted_types_policy].
+///
+/// By default, values are passed through as they are, because the framework
+/// only writes HTML that the app gave it explicitly. A sanitizer can be added
+/// to check or clean it up first.
+/// ```
+/// # use leptos_dom::*;
+/// register_trusted_types_policy(
+///   TrustedTypesPolicy::new("my-app")
+///     // e.g., with a sanitizer like `ammonia`
+///     .create_html(|html| html.replace("<script", "&lt;script")),
+/// );
+/// ```
+#[derive(Clone)]
+pub struct TrustedTypesPolicy {
+  name: Cow<'static, str>,
+  create_html: Option<Sanitizer>,
+  create_script: Option<Sanitizer>,
+}
+
+impl TrustedTypesPolicy {
+  /// Creates a policy with the given name, which the page's Content Security
+  /// Policy has to allow in its `trusted-types` directive.
+  pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+    Self {
+      name: name.into(),
+      create_html: None,
+      create_script: None,
+    }
+  }
+
+  /// Calls `f` to turn each string into `TrustedHTML`.
+  pub fn create_html(mut self, f: impl Fn(&str) -> String + 'static) -> Self {
+    self.create_html = Some(Rc::new(f));
+    self
+  }
+
+  /// Calls `f` to turn each string into a `TrustedScript`.
+  pub fn create_script(mut self, f: impl Fn(&str) -> String + 'static) -> Self {
+    self.create_script = Some(Rc::new(f));
+    self
+  }
+
+  /// The name of the policy.
+  pub fn name(&self) -> &str {
+    &self.name
+  }
+}
+
+impl Default for TrustedTypesPolicy {
+  fn default() -> Self {
+    Self::new("leptos")
+  }
+}
+
+impl fmt::Debug for TrustedTypesPolicy {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    f.debug_struct("TrustedTypesPolicy")
+      .field("name", &self.name)
+      .field("create_html", &self.create_html.is_some())
+      .field("create_script", &self.create_script.is_some())
+      .finish()
+  }
+}
+
+/// Sets the Trusted Types policy that the framework uses for its HTML and
+/// script sinks. Call this before the app is mounted, because the policy is
+/// created the first time it's used, and browsers only allow a policy name to
+/// be created once.
+///
+/// This does nothing on the server, or in browsers without Trusted Types.
+pub fn register_trusted_types_policy(policy: TrustedTypesPolicy) {
+  POLICY_CONFIG.with(|config| *config.borrow_mut() = Some(policy));
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  POLICY.with(|created| *created.borrow_mut() = None);
+}
+
+/// Converts a string into a `TrustedHTML` with the framework's policy, or
+/// returns the string itself if the browser doesn't support Trusted Types.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub fn trusted_html(html: &str) -> JsValue {
+  create_trusted("createHTML", html)
+}
+
+/// Converts a string into a `TrustedScript` with the framework's policy, or
+/// returns the string itself if the browser doesn't support Trusted Types.
+/// This can be assigned to the `text` of a `<script>` created in the browser.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub fn trusted_script(script: &str) -> JsValue {
+  create_trusted("createScript", script)
+}
+
+/// Sets the `innerHTML` of an element, through the Trusted Types policy if the
+/// browser supports it.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) fn set_inner_html(el: &web_sys::Element, html: &str) {
+  if policy().is_some() {
+    _ = js_sys::Reflect::set(el, &"innerHTML".into(), &trusted_html(html));
+  } else {
+    el.set_inner_html(html);
+  }
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn create_trusted(method: &str, value: &str) -> JsValue {
+  policy()
+    .and_then(|policy| {
+      let create = js_sys::Reflect::get(&policy, &method.into()).ok()?;
+      create
+        .unchecked_ref::<js_sys::Function>()
+        .call1(&policy, &value.into())
+        .ok()
+    })
+    .unwrap_or_else(|| value.into())
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn policy() -> Option<JsValue> {
+  POLICY.with(|created| {
+    created
+      .borrow_mut()
+      .get_or_insert_with(create_policy)
+      .clone()
+  })
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn create_policy() -> Option<JsValue> {
+  let trusted_types =
+    js_sys::Reflect::get(&crate::window(), &"trustedTypes".into()).ok()?;
+  if trusted_types.is_undefined() || trusted_types.is_null() {
+    return None;
+  }
+
+  let config = POLICY_CONFIG
+    .with(|config| config.borrow().clone())
+    .unwrap_or_default();
+
+  let options = js_sys::Object::new();
+  for (method, sanitizer) in [
+    ("createHTML", config.create_html),
+    ("createScript", config.create_script),
+  ] {
+    let create =
+      Closure::wrap(Box::new(move |value: String| match &sanitizer {
+        Some(sanitizer) => sanitizer(&value),
+        None => value,
+      }) as Box<dyn Fn(String) -> String>)
+      .into_js_value();
+    _ = js_sys::Reflect::set(&options, &method.into(), &create);
+  }
+
+  let create_policy =
+    js_sys::Reflect::get(&trusted_types, &"createPolicy".into()).ok()?;
+  match create_policy.unchecked_ref::<js_sys::Function>().call2(
+    &trusted_types,
+    &JsValue::from_str(&config.name),
+    &options,
+  ) {
+    Ok(policy) => Some(policy),
+    Err(e) => {
+      crate::framework_log!(
+        crate::logging::Level::Error,
+        "couldn't create the Trusted Types policy `{}`, which may need to be \
+         allowed by the page's Content Security Policy: {e:?}",
+        config.name
+      );
+      None
+    }
+  }
+}
@@ -0,0 +1,139 @@
+//! Reactive access to the page URL, for apps that don't use a router.
+
+use leptos_reactive::{create_signal, Scope, Signal, SignalSetter};
+
+/// Returns a signal with the current URL of the page, relative to its origin
+/// (e.g., `/search?q=leptos#results`), and a setter that navigates to a new
+/// one.
+///
+/// Setting the URL adds an entry to the browser history with
+/// [`history.pushState()`](https://developer.mozilla.org/en-US/docs/Web/API/History/pushState),
+/// without reloading the page. The signal updates on `popstate`, i.e., when
+/// the user goes back or forward, and whenever any [use_url] or [use_hash]
+/// setter is called. The listener is removed when the scope is disposed.
+///
+/// This is meant for small apps and embedded widgets that only need to keep
+/// some state in the URL. Apps with several pages should use `leptos_router`
+/// instead. There is no URL during server rendering, so the signal is empty
+/// on the server.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (url, set_url) = use_url(cx);
+///
+/// let on_search = move |query: String| set_url.set(format!("/search?q={query}"));
+/// # if !is_browser() {
+/// assert_eq!(url.get(), "");
+/// # }
+/// # });
+/// ```
+pub fn use_url(cx: Scope) -> (Signal<String>, SignalSetter<String>) {
+  location_signal(cx, current_url, |url| url)
+}
+
+/// Returns a signal with the fragment of the page URL, without the leading
+/// `#`, and a setter that navigates to a new fragment.
+///
+/// This works like [use_url], so setting the fragment adds an entry to the
+/// browser history, and the signal updates when the user goes back or
+/// forward. Unlike setting `location.hash`, it doesn't scroll to the element
+/// with that `id`, so it can be used to keep state like the selected tab.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let (tab, set_tab) = use_hash(cx);
+///
+/// let is_settings = move || tab.get() == "settings";
+/// let show_settings = move |_: ev::MouseEvent| set_tab.set("settings".to_string());
+/// # });
+/// ```
+pub fn use_hash(cx: Scope) -> (Signal<String>, SignalSetter<String>) {
+  location_signal(cx, current_hash, |hash| {
+    if hash.is_empty() {
+      // an empty fragment still needs the path, or nothing changes
+      current_url_without_hash()
+    } else {
+      format!("#{hash}")
+    }
+  })
+}
+
+/// Creates a signal that reads part of the URL with `read`, and a setter
+/// that navigates to the URL returned by `to_url`.
+fn location_signal(
+  cx: Scope,
+  read: fn() -> String,
+  to_url: fn(String) -> String,
+) -> (Signal<String>, SignalSetter<String>) {
+  let (value, set_value) = create_signal(cx, read());
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  {
+    use wasm_bindgen::JsCast;
+
+    crate::page_lifecycle::listen(
+      cx,
+      crate::window().unchecked_ref(),
+      "popstate",
+      move |_: web_sys::Event| set_value.set(read()),
+    );
+
+    let setter = SignalSetter::map(cx, move |new_value: String| {
+      navigate(&to_url(new_value));
+    });
+    (value.into(), setter)
+  }
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  {
+    _ = to_url;
+    (value.into(), set_value.into())
+  }
+}
+
+/// Pushes a history entry for the URL, then lets every listener know that
+/// it has changed, as if the user had navigated.
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+fn navigate(url: &str) {
+  let window = crate::window();
+  if let Ok(history) = window.history() {
+    if history
+      .push_state_with_url(&wasm_bindgen::JsValue::NULL, "", Some(url))
+      .is_ok()
+    {
+      if let Ok(ev) = web_sys::Event::new("popstate") {
+        _ = window.dispatch_event(&ev);
+      }
+    }
+  }
+}
+
+fn current_url() -> String {
+  if crate::is_server() {
+    return String::new();
+  }
+  let location = crate::location();
+  format!(
+    "{}{}",
+    current_url_without_hash(),
+    location.hash().unwrap_or_default()
+  )
+}
+
+fn current_url_without_hash() -> String {
+  if crate::is_server() {
+    return String::new();
+  }
+  let location = crate::location();
+  format!(
+    "{}{}",
+    location.pathname().unwrap_or_default(),
+    location.search().unwrap_or_default()
+  )
+}
+
+fn current_hash() -> String {
+  crate::location_hash().unwrap_or_default()
+}
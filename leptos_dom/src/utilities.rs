@@ -0,0 +1,288 @@
+//! Reactive helpers for the size of the window, and the scroll position and
+//! bounds of elements.
+//!
+//! These update at most once per animation frame. During server rendering,
+//! they return their default values.
+
+use crate::NodeRef;
+use leptos_reactive::{create_rw_signal, Scope, Signal};
+use std::ops::Deref;
+
+/// The size of the browser window's viewport, in CSS pixels.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct WindowSize {
+  /// [`window.innerWidth`](https://developer.mozilla.org/en-US/docs/Web/API/Window/innerWidth)
+  pub width: f64,
+  /// [`window.innerHeight`](https://developer.mozilla.org/en-US/docs/Web/API/Window/innerHeight)
+  pub height: f64,
+}
+
+/// The direction of the most recent scroll.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScrollDirection {
+  /// Scrolled toward the top.
+  Up,
+  /// Scrolled toward the bottom.
+  Down,
+  /// Scrolled toward the start of the line.
+  Left,
+  /// Scrolled toward the end of the line.
+  Right,
+}
+
+/// The scroll position of an element.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ScrollState {
+  /// [`scrollLeft`](https://developer.mozilla.org/en-US/docs/Web/API/Element/scrollLeft)
+  pub x: f64,
+  /// [`scrollTop`](https://developer.mozilla.org/en-US/docs/Web/API/Element/scrollTop)
+  pub y: f64,
+  /// The direction of the most recent scroll, or `None` if it hasn't scrolled.
+  pub direction: Option<ScrollDirection>,
+  /// Whether the element is scrolled all the way to the bottom, e.g., to load
+  /// more items in an infinite list.
+  pub is_at_bottom: bool,
+}
+
+/// The size and position of an element relative to the viewport, as returned by
+/// [`getBoundingClientRect()`](https://developer.mozilla.org/en-US/docs/Web/API/Element/getBoundingClientRect).
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct ElementBounding {
+  /// The x coordinate of the element's origin.
+  pub x: f64,
+  /// The y coordinate of the element's origin.
+  pub y: f64,
+  /// The element's width.
+  pub width: f64,
+  /// The element's height.
+  pub height: f64,
+  /// The distance from the top of the viewport to the top of the element.
+  pub top: f64,
+  /// The distance from the left of the viewport to the right of the element.
+  pub right: f64,
+  /// The distance from the top of the viewport to the bottom of the element.
+  pub bottom: f64,
+  /// The distance from the left of the viewport to the left of the element.
+  pub left: f64,
+}
+
+/// Returns a signal with the size of the window's viewport, which updates when
+/// the window is resized.
+///
+/// ```
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let size = use_window_size(cx);
+/// let is_wide = move || size.with(|size| size.width > 800.0);
+/// # });
+/// ```
+pub fn use_window_size(cx: Scope) -> Signal<WindowSize> {
+  let size = create_rw_signal(cx, WindowSize::default());
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  {
+    let measure = move || {
+      let window = crate::window();
+      let dimension = |value: Result<wasm_bindgen::JsValue, _>| {
+        value
+          .ok()
+          .and_then(|value| value.as_f64())
+          .unwrap_or_default()
+      };
+      size.set(WindowSize {
+        width: dimension(window.inner_width()),
+        height: dimension(window.inner_height()),
+      });
+    };
+    measure();
+    web::listen_throttled(cx, crate::window().into(), "resize", false, measure);
+  }
+
+  size.into()
+}
+
+/// Returns a signal with the scroll position of the element in the `node_ref`,
+/// which updates when it is scrolled.
+///
+/// ```
+/// # use leptos::*;
+/// #[component]
+/// fn Feed(cx: Scope) -> impl IntoView {
+///   let list = NodeRef::<HtmlElement<Ul>>::new(cx);
+///   let scroll = use_scroll(cx, list);
+///
+///   create_effect(cx, move |_| {
+///     if scroll.with(|scroll| scroll.is_at_bottom) {
+///       // load more items...
+///     }
+///   });
+///
+///   view! { cx, <ul _ref=list style="overflow-y: scroll"></ul> }
+/// }
+/// ```
+pub fn use_scroll<T>(cx: Scope, node_ref: NodeRef<T>) -> Signal<ScrollState>
+where
+  T: Clone + Deref + 'static,
+  T::Target: AsRef<web_sys::Element>,
+{
+  let state = create_rw_signal(cx, ScrollState::default());
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  web::with_element(cx, node_ref, move |cx, el| {
+    let target = el.clone().into();
+    let measure = move || {
+      let (x, y) = (el.scroll_left() as f64, el.scroll_top() as f64);
+      let is_at_bottom =
+        el.scroll_top() + el.client_height() >= el.scroll_height() - 1;
+      state.update(|state| {
+        let direction = if y > state.y {
+          Some(ScrollDirection::Down)
+        } else if y < state.y {
+          Some(ScrollDirection::Up)
+        } else if x > state.x {
+          Some(ScrollDirection::Right)
+        } else if x < state.x {
+          Some(ScrollDirection::Left)
+        } else {
+          state.direction
+        };
+        *state = ScrollState {
+          x,
+          y,
+          direction,
+          is_at_bottom,
+        };
+      });
+    };
+    measure();
+    web::listen_throttled(cx, target, "scroll", false, measure);
+  });
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  _ = node_ref;
+
+  state.into()
+}
+
+/// Returns a signal with the size and position of the element in the `node_ref`,
+/// relative to the viewport. This updates when the window is resized, or when
+/// the page or any element on it is scrolled.
+pub fn use_element_bounding<T>(
+  cx: Scope,
+  node_ref: NodeRef<T>,
+) -> Signal<ElementBounding>
+where
+  T: Clone + Deref + 'static,
+  T::Target: AsRef<web_sys::Element>,
+{
+  let bounding = create_rw_signal(cx, ElementBounding::default());
+
+  #[cfg(all(target_arch = "wasm32", feature = "web"))]
+  web::with_element(cx, node_ref, move |cx, el| {
+    let measure = move || {
+      let rect = el.get_bounding_client_rect();
+      bounding.set(ElementBounding {
+        x: rect.x(),
+        y: rect.y(),
+        width: rect.width(),
+        height: rect.height(),
+        top: rect.top(),
+        right: rect.right(),
+        bottom: rect.bottom(),
+        left: rect.left(),
+      });
+    };
+    measure();
+    let measure = std::rc::Rc::new(measure);
+    web::listen_throttled(cx, crate::window().into(), "resize", false, {
+      let measure = measure.clone();
+      move || measure()
+    });
+    // scroll events don't bubble, so listen for any scroll during capture
+    web::listen_throttled(
+      cx,
+      crate::window().into(),
+      "scroll",
+      true,
+      move || measure(),
+    );
+  });
+
+  #[cfg(not(all(target_arch = "wasm32", feature = "web")))]
+  _ = node_ref;
+
+  bounding.into()
+}
+
+#[cfg(all(target_arch = "wasm32", feature = "web"))]
+pub(crate) mod web {
+  use crate::NodeRef;
+  use leptos_reactive::{create_effect, on_cleanup, Scope, ScopeDisposer};
+  use std::{cell::Cell, ops::Deref, rc::Rc};
+  use wasm_bindgen::{prelude::Closure, JsCast};
+
+  /// Calls `f` in a child scope whenever a new element is loaded into the
+  /// `node_ref`, disposing the previous child scope (and its listeners).
+  pub(crate) fn with_element<T>(
+    cx: Scope,
+    node_ref: NodeRef<T>,
+    f: impl Fn(Scope, web_sys::Element) + 'static,
+  ) where
+    T: Clone + Deref + 'static,
+    T::Target: AsRef<web_sys::Element>,
+  {
+    create_effect(cx, move |prev: Option<Option<ScopeDisposer>>| {
+      if let Some(Some(disposer)) = prev {
+        disposer.dispose();
+      }
+      node_ref.get().map(|node| {
+        let el = node.deref().as_ref().clone();
+        cx.child_scope(|cx| f(cx, el))
+      })
+    });
+  }
+
+  /// Adds an event listener that runs `f` at most once per animation frame,
+  /// and removes it when the scope is disposed.
+  pub(super) fn listen_throttled(
+    cx: Scope,
+    target: web_sys::EventTarget,
+    event_name: &'static str,
+    capture: bool,
+    f: impl Fn() + 'static,
+  ) {
+    let f = Rc::new(f);
+    let scheduled = Rc::new(Cell::new(false));
+    let active = Rc::new(Cell::new(true));
+
+    let handler = Closure::wrap(Box::new({
+      let active = active.clone();
+      move || {
+        if !scheduled.replace(true) {
+          let (f, scheduled, active) =
+            (f.clone(), scheduled.clone(), active.clone());
+          crate::request_animation_frame(move || {
+            scheduled.set(false);
+            if active.get() {
+              f();
+            }
+          });
+        }
+      }
+    }) as Box<dyn Fn()>);
+    _ = target.add_event_listener_with_callback_and_bool(
+      event_name,
+      handler.as_ref().unchecked_ref(),
+      capture,
+    );
+
+    on_cleanup(cx, move || {
+      active.set(false);
+      _ = target.remove_event_listener_with_callback_and_bool(
+        event_name,
+        handler.as_ref().unchecked_ref(),
+        capture,
+      );
+    });
+  }
+}
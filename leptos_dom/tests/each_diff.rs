@@ -0,0 +1,27 @@
+use leptos_dom::each_dom_moves;
+
+#[test]
+fn an_unchanged_list_moves_nothing() {
+  assert_eq!(each_dom_moves(1..=5, 1..=5), (0, 0));
+}
+
+#[test]
+fn swapping_neighbors_moves_one_of_each_pair() {
+  assert_eq!(each_dom_moves([1, 2, 3, 4, 5], [2, 1, 4, 3, 5]), (2, 4));
+}
+
+#[test]
+fn reversing_moves_all_but_one() {
+  assert_eq!(each_dom_moves(1..=5, (1..=5).rev()), (4, 4));
+}
+
+#[test]
+fn moving_the_first_item_to_the_end_moves_only_it() {
+  assert_eq!(each_dom_moves([1, 2, 3, 4, 5], [2, 3, 4, 5, 1]), (1, 5));
+}
+
+#[test]
+fn added_and_removed_items_are_not_moved() {
+  // the legacy algorithm moves the item after the added one
+  assert_eq!(each_dom_moves([1, 2, 3, 4], [0, 1, 3, 4, 5]), (0, 1));
+}
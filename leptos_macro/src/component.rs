@@ -10,6 +10,7 @@ use syn::{
 
 pub struct Model {
     is_transparent: bool,
+    island: Option<LitStr>,
     docs: Docs,
     vis: Visibility,
     name: Ident,
@@ -73,6 +74,7 @@ impl Parse for Model {
 
         Ok(Self {
             is_transparent: false,
+            island: None,
             docs,
             vis: item.vis.clone(),
             name: item.sig.ident.clone(),
@@ -101,6 +103,7 @@ impl ToTokens for Model {
     fn to_tokens(&self, tokens: &mut TokenStream) {
         let Self {
             is_transparent,
+            island,
             docs,
             vis,
             name,
@@ -168,6 +171,15 @@ impl ToTokens for Model {
             }
         };
 
+        // an island's view is wrapped in an element that marks where it was rendered, so
+        // that only that part of the page is hydrated
+        let component = match island {
+            Some(bundle) => quote! {
+                ::leptos::register_island(#scope_name, #bundle, move || #component)
+            },
+            None => component,
+        };
+
         let output = quote! {
             #[doc = #builder_name_doc]
             #[doc = ""]
@@ -213,6 +225,12 @@ impl Model {
 
         self
     }
+
+    pub fn island(mut self, island: Option<LitStr>) -> Self {
+        self.island = island;
+
+        self
+    }
 }
 
 struct Prop {
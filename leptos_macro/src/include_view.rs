@@ -0,0 +1,462 @@
+use crate::{view::render_view, Mode};
+use proc_macro2::{Delimiter, Group, Ident, Literal, TokenStream};
+use quote::{quote, ToTokens};
+use std::{collections::HashSet, path::PathBuf};
+use syn::{
+    parse::{Parse, ParseStream},
+    Expr, LitStr, Token,
+};
+
+// https://developer.mozilla.org/en-US/docs/Glossary/Empty_element
+const VOID_ELEMENTS: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "param", "source",
+    "track", "wbr",
+];
+
+// elements whose contents aren't parsed as HTML, and can't contain holes
+const RAW_TEXT_ELEMENTS: [&str; 2] = ["script", "style"];
+
+pub(crate) struct IncludeView {
+    cx: Ident,
+    path: LitStr,
+    holes: Vec<(Ident, Expr)>,
+}
+
+impl Parse for IncludeView {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let cx = input.parse()?;
+        input.parse::<Token![,]>()?;
+        let path = input.parse()?;
+
+        let mut holes = Vec::new();
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let name = input.parse()?;
+            input.parse::<Token![=]>()?;
+            holes.push((name, input.parse()?));
+        }
+
+        Ok(Self { cx, path, holes })
+    }
+}
+
+impl IncludeView {
+    pub(crate) fn into_tokens(self) -> TokenStream {
+        let Self { cx, path, holes } = self;
+
+        let full_path = PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").unwrap_or_default())
+            .join(path.value());
+        let html = std::fs::read_to_string(&full_path)
+            .unwrap_or_else(|e| abort!(path, "couldn't read `{}`: {}", full_path.display(), e));
+        let nodes = parse_html(&html)
+            .unwrap_or_else(|e| abort!(path, "couldn't parse `{}`: {}", path.value(), e));
+
+        let mut used = HashSet::new();
+        let rsx = nodes_to_rsx(&nodes, &holes, &mut used)
+            .unwrap_or_else(|e| abort!(path, "in `{}`: {}", path.value(), e));
+        if let Some((name, _)) = holes.iter().find(|(name, _)| !used.contains(name)) {
+            abort!(
+                name,
+                "`{}` doesn't have a hole named `{}`",
+                path.value(),
+                name
+            );
+        }
+
+        let view = match syn_rsx::parse2(rsx) {
+            Ok(nodes) => render_view(&cx, &nodes, Mode::Client),
+            Err(error) => return error.to_compile_error(),
+        };
+        let full_path = full_path.display().to_string();
+        quote! {
+            {
+                // so that the view is built again when the file changes
+                const _: &str = include_str!(#full_path);
+                #view
+            }
+        }
+    }
+}
+
+enum HtmlNode {
+    Element {
+        name: String,
+        attrs: Vec<(String, Option<String>)>,
+        children: Vec<HtmlNode>,
+    },
+    Text(String),
+    RawText(String),
+}
+
+/// Parses the HTML into a tree of elements and text. Comments and doctypes
+/// are dropped, as is text that's only whitespace.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<p>Hello, {{name}}</p>`
+/// include_view!(cx, "tests/templates/hole.html", name = "Ann")
+/// # ;
+/// # });
+/// ```
+///
+/// HTML that isn't well-formed is a compile error:
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<div><p>Hello</div>`
+/// include_view!(cx, "tests/templates/mismatched.html")
+/// # ;
+/// # });
+/// ```
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<div><p>Hello</p>`
+/// include_view!(cx, "tests/templates/unclosed_element.html")
+/// # ;
+/// # });
+/// ```
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<div class="greeting"`
+/// include_view!(cx, "tests/templates/unclosed_tag.html")
+/// # ;
+/// # });
+/// ```
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<p>Hello</p><!-- the end`
+/// include_view!(cx, "tests/templates/unclosed_comment.html")
+/// # ;
+/// # });
+/// ```
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<p>Hello</p></div>`
+/// include_view!(cx, "tests/templates/stray_closing_tag.html")
+/// # ;
+/// # });
+/// ```
+///
+/// So is a hole without a value, a value without a hole, or a hole that's
+/// only part of an attribute's value:
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<p>Hello, {{name}}</p>`
+/// include_view!(cx, "tests/templates/hole.html")
+/// # ;
+/// # });
+/// ```
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<p>Hello, {{name}}</p>`
+/// include_view!(cx, "tests/templates/hole.html", name = "Ann", greeting = "Hi")
+/// # ;
+/// # });
+/// ```
+/// ```rust,compile_fail
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// // `<p class="a {{class}}">Hello</p>`
+/// include_view!(cx, "tests/templates/partial_attribute_hole.html", class = "b")
+/// # ;
+/// # });
+/// ```
+fn parse_html(html: &str) -> Result<Vec<HtmlNode>, String> {
+    // each element that hasn't been closed yet, with the children parsed so far
+    let mut open = Vec::<(String, Vec<(String, Option<String>)>, Vec<HtmlNode>)>::new();
+    let mut root = Vec::new();
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let node = if let Some(after) = rest.strip_prefix("<!--") {
+            let end = after.find("-->").ok_or("unclosed comment")?;
+            rest = &after[end + 3..];
+            None
+        } else if let Some(after) = rest.strip_prefix("<!") {
+            let end = after.find('>').ok_or("unclosed `<!`")?;
+            rest = &after[end + 1..];
+            None
+        } else if let Some(after) = rest.strip_prefix("</") {
+            let end = after.find('>').ok_or("unclosed closing tag")?;
+            let name = tag_name(after[..end].trim());
+            let (open_name, attrs, children) = open
+                .pop()
+                .ok_or_else(|| format!("unexpected `</{name}>`"))?;
+            if open_name != name {
+                return Err(format!("expected `</{open_name}>`, found `</{name}>`"));
+            }
+            rest = &after[end + 1..];
+            Some(HtmlNode::Element {
+                name,
+                attrs,
+                children,
+            })
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic())
+        {
+            let (name, attrs, self_closing, after) = parse_open_tag(&rest[1..])?;
+            rest = after;
+            if self_closing || VOID_ELEMENTS.contains(&name.as_str()) {
+                Some(HtmlNode::Element {
+                    name,
+                    attrs,
+                    children: Vec::new(),
+                })
+            } else if RAW_TEXT_ELEMENTS.contains(&name.as_str()) {
+                let close = format!("</{name}");
+                let end = rest
+                    .find(&close)
+                    .ok_or_else(|| format!("unclosed `<{name}>`"))?;
+                let text = &rest[..end];
+                let after = &rest[end..];
+                rest = &after[after.find('>').ok_or("unclosed closing tag")? + 1..];
+                Some(HtmlNode::Element {
+                    name,
+                    attrs,
+                    children: if text.trim().is_empty() {
+                        Vec::new()
+                    } else {
+                        vec![HtmlNode::RawText(text.to_string())]
+                    },
+                })
+            } else {
+                open.push((name, attrs, Vec::new()));
+                None
+            }
+        } else {
+            // the text starts with at least one character, which may be a stray `<`
+            let first = rest.chars().next().map_or(0, char::len_utf8);
+            let end = rest[first..]
+                .find('<')
+                .map_or(rest.len(), |end| end + first);
+            let text = &rest[..end];
+            rest = &rest[end..];
+            (!text.trim().is_empty()).then(|| HtmlNode::Text(text.to_string()))
+        };
+
+        if let Some(node) = node {
+            match open.last_mut() {
+                Some((_, _, children)) => children.push(node),
+                None => root.push(node),
+            }
+        }
+    }
+
+    match open.last() {
+        Some((name, _, _)) => Err(format!("unclosed `<{name}>`")),
+        None => Ok(root),
+    }
+}
+
+type OpenTag<'a> = (String, Vec<(String, Option<String>)>, bool, &'a str);
+
+/// Parses an opening tag, starting just after its `<`, into its name, its
+/// attributes, whether it closes itself, and the rest of the input.
+fn parse_open_tag(input: &str) -> Result<OpenTag<'_>, String> {
+    let name_end = input
+        .find(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .ok_or("unclosed tag")?;
+    let name = tag_name(&input[..name_end]);
+    let mut rest = &input[name_end..];
+    let mut attrs = Vec::new();
+
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix("/>") {
+            return Ok((name, attrs, true, after));
+        } else if let Some(after) = rest.strip_prefix('>') {
+            return Ok((name, attrs, false, after));
+        }
+
+        let attr_end = rest
+            .find(|c: char| c.is_whitespace() || matches!(c, '=' | '>' | '/'))
+            .unwrap_or(rest.len());
+        if attr_end == 0 {
+            return Err(match rest.chars().next() {
+                Some(c) => format!("unexpected `{c}` in `<{name}>`"),
+                None => format!("unclosed `<{name}>`"),
+            });
+        }
+        let attr = rest[..attr_end].to_string();
+        rest = rest[attr_end..].trim_start();
+
+        let value = match rest.strip_prefix('=').map(str::trim_start) {
+            Some(after) => {
+                let (value, after) = match after.chars().next() {
+                    Some(quote @ ('"' | '\'')) => {
+                        let end = after[1..]
+                            .find(quote)
+                            .ok_or_else(|| format!("unclosed value of `{attr}`"))?;
+                        (&after[1..end + 1], &after[end + 2..])
+                    }
+                    _ => after.split_at(
+                        after
+                            .find(|c: char| c.is_whitespace() || c == '>')
+                            .unwrap_or(after.len()),
+                    ),
+                };
+                rest = after;
+                Some(value.to_string())
+            }
+            None => None,
+        };
+        attrs.push((attr, value));
+    }
+}
+
+/// Tag names are case-insensitive in HTML, but a capitalized name would be
+/// treated as a component by the `view` macro.
+fn tag_name(name: &str) -> String {
+    if name.starts_with(|c: char| c.is_ascii_uppercase()) {
+        name.to_ascii_lowercase()
+    } else {
+        name.to_string()
+    }
+}
+
+fn nodes_to_rsx(
+    nodes: &[HtmlNode],
+    holes: &[(Ident, Expr)],
+    used: &mut HashSet<Ident>,
+) -> Result<TokenStream, String> {
+    let mut tokens = TokenStream::new();
+    for node in nodes {
+        match node {
+            HtmlNode::Element {
+                name,
+                attrs,
+                children,
+            } => {
+                let name = name
+                    .parse::<TokenStream>()
+                    .map_err(|_| format!("`{name}` isn't a valid tag name"))?;
+                let attrs = attrs
+                    .iter()
+                    .map(|(attr, value)| attr_to_rsx(attr, value.as_deref(), holes, used))
+                    .collect::<Result<Vec<_>, _>>()?;
+                if children.is_empty() {
+                    tokens.extend(quote! { <#name #(#attrs)* /> });
+                } else {
+                    let children = nodes_to_rsx(children, holes, used)?;
+                    tokens.extend(quote! { <#name #(#attrs)*> #children </#name> });
+                }
+            }
+            HtmlNode::Text(text) => {
+                let mut rest = text.as_str();
+                while let Some(start) = rest.find("{{") {
+                    let end = rest[start..].find("}}").ok_or("unclosed `{{`")? + start;
+                    if start > 0 {
+                        tokens.extend(
+                            Literal::string(&decode_entities(&rest[..start])).into_token_stream(),
+                        );
+                    }
+                    tokens.extend(hole(rest[start + 2..end].trim(), holes, used)?);
+                    rest = &rest[end + 2..];
+                }
+                if !rest.is_empty() {
+                    tokens.extend(Literal::string(&decode_entities(rest)).into_token_stream());
+                }
+            }
+            HtmlNode::RawText(text) => tokens.extend(Literal::string(text).into_token_stream()),
+        }
+    }
+    Ok(tokens)
+}
+
+fn attr_to_rsx(
+    attr: &str,
+    value: Option<&str>,
+    holes: &[(Ident, Expr)],
+    used: &mut HashSet<Ident>,
+) -> Result<TokenStream, String> {
+    let name = attr
+        .parse::<TokenStream>()
+        .map_err(|_| format!("`{attr}` isn't a valid attribute name"))?;
+    let value = match value {
+        None => return Ok(name),
+        Some(value) => value.trim(),
+    };
+
+    let value = match value
+        .strip_prefix("{{")
+        .and_then(|value| value.strip_suffix("}}"))
+    {
+        Some(hole_name) if !hole_name.contains("{{") => hole(hole_name.trim(), holes, used)?,
+        _ if value.contains("{{") => {
+            return Err(format!(
+                "the hole in `{attr}` has to be the attribute's whole value"
+            ))
+        }
+        _ => Literal::string(&decode_entities(value)).into_token_stream(),
+    };
+    Ok(quote! { #name=#value })
+}
+
+fn hole(
+    name: &str,
+    holes: &[(Ident, Expr)],
+    used: &mut HashSet<Ident>,
+) -> Result<TokenStream, String> {
+    let (ident, value) = holes
+        .iter()
+        .find(|(ident, _)| ident == name)
+        .ok_or_else(|| format!("no value was given for `{{{{{name}}}}}`"))?;
+    used.insert(ident.clone());
+    Ok(Group::new(Delimiter::Brace, value.to_token_stream()).into_token_stream())
+}
+
+fn decode_entities(text: &str) -> String {
+    let mut decoded = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('&') {
+        decoded.push_str(&rest[..start]);
+        rest = &rest[start..];
+        let entity = rest
+            .find(';')
+            .and_then(|end| decode_entity(&rest[1..end]).map(|c| (c, end)));
+        match entity {
+            Some((c, end)) => {
+                decoded.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                decoded.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+    decoded.push_str(rest);
+    decoded
+}
+
+fn decode_entity(name: &str) -> Option<char> {
+    match name {
+        "amp" => Some('&'),
+        "lt" => Some('<'),
+        "gt" => Some('>'),
+        "quot" => Some('"'),
+        "apos" => Some('\''),
+        "nbsp" => Some('\u{a0}'),
+        "copy" => Some('©'),
+        "ndash" => Some('–'),
+        "mdash" => Some('—'),
+        "hellip" => Some('…'),
+        _ => {
+            let code = name.strip_prefix('#')?;
+            match code.strip_prefix(['x', 'X']) {
+                Some(hex) => u32::from_str_radix(hex, 16).ok(),
+                None => code.parse().ok(),
+            }
+            .and_then(char::from_u32)
+        }
+    }
+}
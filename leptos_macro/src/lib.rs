@@ -29,6 +29,7 @@ mod params;
 mod view;
 use view::render_view;
 mod component;
+mod include_view;
 mod props;
 mod server;
 
@@ -176,6 +177,26 @@ mod server;
 /// # });
 /// ```
 ///
+/// A single `class` attribute can also toggle several classes, with a `("name", value)` tuple, an
+/// array of them, or the [classes!] macro. Each class is still updated on its own.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let (active, set_active) = create_signal(cx, true);
+/// let (disabled, set_disabled) = create_signal(cx, false);
+/// view! { cx, <button class=("active", active)>"Save"</button> }
+/// # ;
+/// view! { cx, <button class=[("active", active), ("disabled", disabled)]>"Save"</button> }
+/// # ;
+/// view! { cx,
+///   <button class=classes! { "active" => active, "disabled" => move || disabled() }>"Save"</button>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// 8. You can use the `_ref` attribute to store a reference to its DOM element in a
 ///    [NodeRef](leptos_reactive::NodeRef) to use later.
 /// ```rust
@@ -191,6 +212,41 @@ mod server;
 /// # });
 /// ```
 ///
+/// 9. Attributes can be passed through to an element all at once with `{..attrs}`, where
+///    `attrs` is any collection of `(name, value)` pairs whose values implement
+///    [IntoAttribute](leptos_dom::IntoAttribute), e.g., attributes a component received as a prop.
+/// ```rust
+/// # use leptos::*;
+/// # use std::borrow::Cow;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let attrs: Vec<(Cow<'static, str>, Attribute)> =
+///     vec![("placeholder".into(), Attribute::String("Search".to_string()))];
+/// view! { cx, <input type="search" {..attrs}/> }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
+/// 10. Form controls can be bound to an [RwSignal](leptos_reactive::RwSignal) with `bind:value`
+///     (for `<input>`, `<textarea>`, and `<select>`) or `bind:checked` (for checkboxes and radio
+///     buttons). This sets the `value` or `checked` property from the signal, and updates the
+///     signal on `input` or `change`, like writing the `prop:` and `on:` attributes by hand.
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let name = create_rw_signal(cx, String::new());
+/// let subscribed = create_rw_signal(cx, false);
+/// view! { cx,
+///   <input type="text" bind:value=name/>
+///   <input type="checkbox" bind:checked=subscribed/>
+/// }
+/// # ;
+/// # }
+/// # });
+/// ```
+///
 /// Here’s a simple example that shows off several of these features, put together
 /// ```rust
 /// # use leptos::*;
@@ -244,6 +300,83 @@ pub fn view(tokens: TokenStream) -> TokenStream {
     }
 }
 
+/// Includes an HTML file as a view, as if its markup had been written in a [view](mod@view) macro,
+/// so that markup exported from a design tool doesn't have to be rewritten by hand.
+///
+/// The path is relative to the crate's root, i.e., the directory that contains its `Cargo.toml`.
+/// The file is parsed at compile time, and the crate is rebuilt whenever it changes.
+///
+/// The file can contain holes like `{{name}}`, in text or as the whole value of an attribute,
+/// which are filled in by the `name = value` arguments that follow the path. The value can be
+/// anything that could be put in braces in a [view](mod@view) macro, including a closure that
+/// reads signals, and is used at each place where its hole appears. Every hole needs a value, and
+/// every value needs a hole.
+///
+/// For example, with this `templates/card.html`:
+/// ```html
+/// <article class="card">
+///   <h2 class="{{class}}">{{title}}</h2>
+///   <p>Updated {{updated}} times &mdash; <a href="/help">help</a></p>
+/// </article>
+/// ```
+/// the card can be rendered like this:
+/// ```rust,ignore
+/// # use leptos::*;
+/// #[component]
+/// fn Card(cx: Scope, title: String, updated: ReadSignal<u32>) -> impl IntoView {
+///     include_view!(cx, "templates/card.html",
+///         class = "card-title",
+///         title = title,
+///         updated = move || updated.get(),
+///     )
+/// }
+/// ```
+///
+/// Comments, doctypes, and text that's only whitespace are left out. The contents of `<script>`
+/// and `<style>` elements are included as they are, without looking for holes.
+#[proc_macro_error::proc_macro_error]
+#[proc_macro]
+pub fn include_view(tokens: TokenStream) -> TokenStream {
+    parse_macro_input!(tokens as include_view::IncludeView)
+        .into_tokens()
+        .into()
+}
+
+/// Toggles several classes of an element with a single `class` attribute in the
+/// [view](mod@view) macro, given pairs of a class name and a `bool` or a signal that returns a
+/// `bool`, like a `class:` attribute. Each class is updated on its own, so this is the same as
+/// writing a `class:` attribute for each of them, which is useful for components with many
+/// conditional classes.
+///
+/// ```rust
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate")) {
+/// let (selected, set_selected) = create_signal(cx, false);
+/// let (loading, set_loading) = create_signal(cx, true);
+/// let html = view! { cx,
+///   <li class=classes! {
+///     "selected" => selected,
+///     "loading" => loading,
+///     "rounded" => true,
+///   }>"Item"</li>
+/// }
+/// .into_view(cx)
+/// .render_to_string(cx);
+/// assert!(html.contains("loading rounded"));
+/// # }
+/// # });
+/// ```
+///
+/// This can only be used as the value of a `class` attribute in the [view](mod@view) macro.
+#[proc_macro_error::proc_macro_error]
+#[proc_macro]
+pub fn classes(_tokens: TokenStream) -> TokenStream {
+    abort_call_site!(
+        "`classes!` can only be used as the value of a `class` attribute in the `view!` macro"
+    )
+}
+
 /// Annotates a function so that it can be used with your template as a Leptos `<Component/>`.
 ///
 /// The `#[component]` macro allows you to annotate plain Rust functions as components
@@ -386,29 +519,61 @@ pub fn view(tokens: TokenStream) -> TokenStream {
 ///   }
 /// }
 /// ```
+///
+/// 6. In islands mode, where most of a page is only rendered on the server, a component that is
+///    compiled into a bundle of its own can be declared with `#[component(island = "bundle")]`.
+///    Rendering it adds the bundle to the page's `IslandManifest`, so that only the bundles the
+///    page needs are loaded in the browser, and wraps its view in a `<leptos-island>` element, so
+///    that the bundle's `hydrate` function can hydrate just that part of the page with
+///    `hydrate_islands`. An island can't be `transparent`.
+///
+/// ```
+/// # use leptos::*;
+/// #[component(island = "comments")]
+/// fn Comments(cx: Scope) -> impl IntoView {
+///   let (count, set_count) = create_signal(cx, 0);
+///   view! { cx,
+///     <button on:click=move |_| set_count.update(|n| *n += 1)>"Like " {count}</button>
+///   }
+/// }
+/// ```
 #[proc_macro_error::proc_macro_error]
 #[proc_macro_attribute]
 pub fn component(args: proc_macro::TokenStream, s: TokenStream) -> TokenStream {
-    let is_transparent = if !args.is_empty() {
-        let transparent = parse_macro_input!(args as syn::Ident);
-
-        let transparent_token: syn::Ident = syn::parse_quote!(transparent);
+    let mut is_transparent = false;
+    let mut island = None;
 
-        if transparent != transparent_token {
-            abort!(
-                transparent,
-                "only `transparent` is supported";
+    for arg in parse_macro_input!(args as syn::AttributeArgs) {
+        match arg {
+            syn::NestedMeta::Meta(syn::Meta::Path(path)) if path.is_ident("transparent") => {
+                is_transparent = true;
+            }
+            syn::NestedMeta::Meta(syn::Meta::NameValue(syn::MetaNameValue {
+                path,
+                lit: syn::Lit::Str(bundle),
+                ..
+            })) if path.is_ident("island") => {
+                island = Some(bundle);
+            }
+            arg => abort!(
+                arg,
+                "only `transparent` and `island = \"bundle\"` are supported";
                 help = "try `#[component(transparent)]` or `#[component]`"
-            );
+            ),
         }
+    }
 
-        true
-    } else {
-        false
-    };
+    if let (true, Some(bundle)) = (is_transparent, &island) {
+        abort!(
+            bundle,
+            "an island can't be `transparent`";
+            help = "an island renders the element that it's hydrated from"
+        );
+    }
 
     parse_macro_input!(s as component::Model)
         .is_transparent(is_transparent)
+        .island(island)
         .into_token_stream()
         .into()
 }
@@ -34,6 +34,7 @@ pub fn server_macro_impl(args: proc_macro::TokenStream, s: TokenStream2) -> Resu
     let encoding = match encoding {
         Encoding::Cbor => quote! { ::leptos::Encoding::Cbor },
         Encoding::Url => quote! { ::leptos::Encoding::Url },
+        Encoding::Bincode => quote! { ::leptos::Encoding::Bincode },
     };
 
     let body = syn::parse::<ServerFnBody>(s.into())?;
@@ -1,6 +1,11 @@
 use proc_macro2::{Ident, Span, TokenStream};
-use quote::{format_ident, quote, quote_spanned};
-use syn::{spanned::Spanned, ExprPath};
+use quote::{format_ident, quote, quote_spanned, ToTokens};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    ExprPath, LitStr, Token,
+};
 use syn_rsx::{Node, NodeAttribute, NodeElement, NodeName};
 
 use crate::{is_component_node, Mode};
@@ -209,7 +214,14 @@ fn root_element_to_tokens_ssr(cx: &Ident, node: &NodeElement) -> TokenStream {
     let mut holes = Vec::<TokenStream>::new();
     let mut exprs_for_compiler = Vec::<TokenStream>::new();
 
-    element_to_tokens_ssr(cx, node, &mut template, &mut holes, &mut exprs_for_compiler);
+    element_to_tokens_ssr(
+        cx,
+        node,
+        None,
+        &mut template,
+        &mut holes,
+        &mut exprs_for_compiler,
+    );
 
     let template = if holes.is_empty() {
         quote! {
@@ -237,6 +249,7 @@ fn root_element_to_tokens_ssr(cx: &Ident, node: &NodeElement) -> TokenStream {
 fn element_to_tokens_ssr(
     cx: &Ident,
     node: &NodeElement,
+    bound_select: Option<&syn::Expr>,
     template: &mut String,
     holes: &mut Vec<TokenStream>,
     exprs_for_compiler: &mut Vec<TokenStream>,
@@ -248,26 +261,71 @@ fn element_to_tokens_ssr(
           {#component}.into_view(cx).render_to_string(cx),
         })
     } else {
+        let tag_name = node.name.to_string();
         template.push('<');
-        template.push_str(&node.name.to_string());
+        template.push_str(&tag_name);
+
+        // browsers ignore the `value` attribute of a `<textarea>` or `<select>`, so the bound
+        // value is rendered as the text of the one, and marks the matching option of the other
+        let bound_value = match tag_name.as_str() {
+            "textarea" | "select" => bound_value(node),
+            _ => None,
+        };
+        let bound_select = match tag_name.as_str() {
+            "select" => bound_value,
+            "optgroup" => bound_select,
+            _ => None,
+        };
 
         for attr in &node.attributes {
             if let Node::Attribute(attr) = attr {
-                attribute_to_tokens_ssr(cx, attr, template, holes, exprs_for_compiler);
+                if bound_value.is_none() || attr.key.to_string() != "bind:value" {
+                    attribute_to_tokens_ssr(cx, attr, template, holes, exprs_for_compiler);
+                }
+            } else if let Some(attrs) = spread_attributes(attr) {
+                template.push_str("{}");
+                holes.push(quote! {
+                  leptos::leptos_dom::spread_attributes_to_string(#cx, #attrs),
+                })
             }
         }
 
         set_class_attribute_ssr(cx, node, template, holes);
 
+        if let (Some(signal), "option") = (bound_select, tag_name.as_str()) {
+            if let Some(value) = option_value(node) {
+                template.push_str("{}");
+                holes.push(quote! {
+                  if (#cx, #signal.get()).into_attribute(#cx).as_nameless_value_string()
+                      == (#cx, #value).into_attribute(#cx).as_nameless_value_string()
+                  {
+                      " selected"
+                  } else {
+                      ""
+                  },
+                })
+            }
+        }
+
         if is_self_closing(node) {
             template.push_str("/>");
+        } else if let (Some(signal), "textarea") = (bound_value, tag_name.as_str()) {
+            template.push_str(">{}</textarea>");
+            holes.push(quote! {
+              (#cx, #signal.get()).into_attribute(#cx).as_nameless_value_string(),
+            })
         } else {
             template.push('>');
             for child in &node.children {
                 match child {
-                    Node::Element(child) => {
-                        element_to_tokens_ssr(cx, child, template, holes, exprs_for_compiler)
-                    }
+                    Node::Element(child) => element_to_tokens_ssr(
+                        cx,
+                        child,
+                        bound_select,
+                        template,
+                        holes,
+                        exprs_for_compiler,
+                    ),
                     Node::Text(text) => {
                         if let Some(value) = value_to_string(&text.value) {
                             template.push_str(&value);
@@ -303,6 +361,59 @@ fn element_to_tokens_ssr(
     }
 }
 
+/// Returns the signal bound to an element with `bind:value`.
+fn bound_value(node: &NodeElement) -> Option<&syn::Expr> {
+    node.attributes.iter().find_map(|attr| match attr {
+        Node::Attribute(attr) if attr.key.to_string() == "bind:value" => {
+            attr.value.as_ref().map(|value| value.as_ref())
+        }
+        _ => None,
+    })
+}
+
+/// Returns the value of an `<option>`, which is its text if it has no `value` attribute.
+fn option_value(node: &NodeElement) -> Option<TokenStream> {
+    let value = node.attributes.iter().find_map(|attr| match attr {
+        Node::Attribute(attr) if attr.key.to_string() == "value" => attr.value.as_ref(),
+        _ => None,
+    });
+    match (value, node.children.as_slice()) {
+        (Some(value), _) => {
+            let value = value.as_ref();
+            Some(quote! { {#value} })
+        }
+        (None, [Node::Text(text)]) => {
+            let text = text.value.as_ref();
+            Some(quote! { #text })
+        }
+        _ => None,
+    }
+}
+
+/// Returns the attributes spread into an element with `{..attrs}`.
+fn spread_attributes(node: &Node) -> Option<&syn::Expr> {
+    let block = match node {
+        Node::Block(block) => block.value.as_ref(),
+        _ => return None,
+    };
+    let expr = match block {
+        syn::Expr::Block(block) => match block.block.stmts.as_slice() {
+            [syn::Stmt::Expr(expr)] => expr,
+            _ => return None,
+        },
+        expr => expr,
+    };
+    match expr {
+        syn::Expr::Range(syn::ExprRange {
+            from: None,
+            limits: syn::RangeLimits::HalfOpen(_),
+            to: Some(attrs),
+            ..
+        }) => Some(attrs),
+        _ => None,
+    }
+}
+
 fn value_to_string(value: &syn_rsx::NodeValueExpr) -> Option<String> {
     match &value.as_ref() {
         syn::Expr::Lit(lit) => match &lit.lit {
@@ -344,6 +455,24 @@ fn attribute_to_tokens_ssr(
         exprs_for_compiler.push(quote! {
             leptos::ssr_event_listener(leptos::ev::#event_type, #handler);
         })
+    } else if let Some(name) = name.strip_prefix("bind:") {
+        if name != "value" && name != "checked" {
+            abort!(
+                node.key.span(),
+                "only `bind:value` and `bind:checked` are supported"
+            );
+        }
+        // render the current value of the signal, which is kept in sync in the browser
+        let signal = node
+            .value
+            .as_ref()
+            .expect("bind: attributes need a signal")
+            .as_ref();
+        template.push(' ');
+        template.push_str("{}");
+        holes.push(quote! {
+          (#cx, #signal.get()).into_attribute(#cx).as_value_string(#name),
+        })
     } else if name.strip_prefix("prop:").is_some() || name.strip_prefix("class:").is_some() {
         // ignore props for SSR
         // ignore classes: we'll handle these separately
@@ -361,7 +490,7 @@ fn attribute_to_tokens_ssr(
                     template.push_str("{}");
                     let value = value.as_ref();
                     holes.push(quote! {
-                      leptos::escape_attr(&{#value}.into_attribute(#cx).as_value_string(#name)),
+                      {#value}.into_attribute(#cx).as_value_string(#name),
                     })
                 }
                 template.push('"');
@@ -399,7 +528,9 @@ fn set_class_attribute_ssr(
         .filter_map(|a| {
             if let Node::Attribute(a) = a {
                 if a.key.to_string() == "class" {
-                    if a.value.as_ref().and_then(value_to_string).is_some() {
+                    if a.value.as_ref().and_then(value_to_string).is_some()
+                        || class_pairs(a).is_some()
+                    {
                         None
                     } else {
                         Some((a.key.span(), &a.value))
@@ -416,10 +547,16 @@ fn set_class_attribute_ssr(
     let class_attrs = node
         .attributes
         .iter()
-        .filter_map(|node| {
+        .flat_map(|node| {
             if let Node::Attribute(node) = node {
                 let name = node.key.to_string();
-                if name.starts_with("class:") || name.starts_with("class-") {
+                if let Some(pairs) = class_pairs(node) {
+                    let span = node.key.span();
+                    pairs
+                        .into_iter()
+                        .map(|(name, value)| (span, name, value))
+                        .collect()
+                } else if name.starts_with("class:") || name.starts_with("class-") {
                     let name = if name.starts_with("class:") {
                         name.replacen("class:", "", 1)
                     } else if name.starts_with("class-") {
@@ -431,14 +568,15 @@ fn set_class_attribute_ssr(
                         .value
                         .as_ref()
                         .expect("class: attributes need values")
-                        .as_ref();
+                        .as_ref()
+                        .to_token_stream();
                     let span = node.key.span();
-                    Some((span, name, value))
+                    vec![(span, name, value)]
                 } else {
-                    None
+                    vec![]
                 }
             } else {
-                None
+                vec![]
             }
         })
         .collect::<Vec<_>>();
@@ -453,7 +591,7 @@ fn set_class_attribute_ssr(
                 template.push_str(" {}");
                 let value = value.as_ref();
                 holes.push(quote! {
-                  leptos::escape_attr(&(cx, #value).into_attribute(#cx).as_nameless_value_string()),
+                  (cx, #value).into_attribute(#cx).as_nameless_value_string(),
                 });
             }
         }
@@ -566,7 +704,11 @@ fn element_to_tokens(cx: &Ident, node: &NodeElement, mut parent_type: TagType) -
             if let Node::Attribute(node) = node {
                 Some(attribute_to_tokens(cx, node))
             } else {
-                None
+                spread_attributes(node).map(|attrs| {
+                    quote! {
+                        .attrs(#attrs)
+                    }
+                })
             }
         });
         let children = node.children.iter().map(|node| {
@@ -650,6 +792,28 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
         quote! {
             .prop(#name, (#cx, #[allow(unused_braces)] #value))
         }
+    } else if let Some(name) = name.strip_prefix("bind:") {
+        let signal = node
+            .value
+            .as_ref()
+            .expect("bind: attributes need a signal")
+            .as_ref();
+        // the prop is read from the signal, and the signal is set on each change
+        let (event, read_event) = match name {
+            "value" => (quote! { input }, quote! { leptos::event_target_value }),
+            "checked" => (quote! { change }, quote! { leptos::event_target_checked }),
+            _ => abort!(span, "only `bind:value` and `bind:checked` are supported"),
+        };
+        quote! {
+            .prop(#name, (#cx, {
+                let signal = #signal;
+                move || signal.get()
+            }))
+            .on(leptos::ev::#event, {
+                let signal = #signal;
+                move |ev| signal.set(#read_event(&ev))
+            })
+        }
     } else if let Some(name) = name.strip_prefix("class:") {
         let value = node
             .value
@@ -659,6 +823,15 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
         quote! {
             .class(#name, (#cx, #[allow(unused_braces)] #value))
         }
+    } else if let Some(pairs) = class_pairs(node) {
+        let classes = pairs.into_iter().map(|(name, value)| {
+            quote! {
+                .class(#name, (#cx, #[allow(unused_braces)] #value))
+            }
+        });
+        quote! {
+            #(#classes)*
+        }
     } else {
         let name = name.replacen("attr:", "", 1);
         let value = match node.value.as_ref() {
@@ -675,6 +848,79 @@ fn attribute_to_tokens(cx: &Ident, node: &NodeAttribute) -> TokenStream {
     }
 }
 
+/// The names and values of the classes toggled by a `class` attribute that sets several of them
+/// at once, like `class=("active", is_active)`, `class=[("active", is_active), ("big", is_big)]`,
+/// or `class=classes! { "active" => is_active, "big" => is_big }`, each of which is lowered to its
+/// own `.class()` call.
+fn class_pairs(node: &NodeAttribute) -> Option<Vec<(String, TokenStream)>> {
+    if node.key.to_string() != "class" {
+        return None;
+    }
+    match node.value.as_ref()?.as_ref() {
+        syn::Expr::Tuple(tuple) => class_pair(tuple).map(|pair| vec![pair]),
+        syn::Expr::Array(array) if !array.elems.is_empty() => array
+            .elems
+            .iter()
+            .map(|elem| match elem {
+                syn::Expr::Tuple(tuple) => class_pair(tuple),
+                _ => None,
+            })
+            .collect(),
+        syn::Expr::Macro(mac)
+            if mac
+                .mac
+                .path
+                .segments
+                .last()
+                .map(|segment| segment.ident == "classes")
+                .unwrap_or(false) =>
+        {
+            let entries = mac
+                .mac
+                .parse_body_with(Punctuated::<ClassEntry, Token![,]>::parse_terminated)
+                .unwrap_or_else(|e| abort!(e.span(), e));
+            Some(
+                entries
+                    .into_iter()
+                    .map(|entry| (entry.name.value(), entry.value.to_token_stream()))
+                    .collect(),
+            )
+        }
+        _ => None,
+    }
+}
+
+/// A `("name", value)` tuple, if its first element is a string literal.
+fn class_pair(tuple: &syn::ExprTuple) -> Option<(String, TokenStream)> {
+    let mut elems = tuple.elems.iter();
+    match (elems.next(), elems.next(), elems.next()) {
+        (
+            Some(syn::Expr::Lit(syn::ExprLit {
+                lit: syn::Lit::Str(name),
+                ..
+            })),
+            Some(value),
+            None,
+        ) => Some((name.value(), value.to_token_stream())),
+        _ => None,
+    }
+}
+
+/// A `"name" => value` entry in `classes!`.
+struct ClassEntry {
+    name: LitStr,
+    value: syn::Expr,
+}
+
+impl Parse for ClassEntry {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        input.parse::<Token![=>]>()?;
+        let value = input.parse()?;
+        Ok(Self { name, value })
+    }
+}
+
 fn component_to_tokens(cx: &Ident, node: &NodeElement) -> TokenStream {
     let name = &node.name;
     let component_name = ident_from_tag_name(&node.name);
@@ -0,0 +1,122 @@
+use crate::{
+    runtime::{with_runtime, Runtime, RuntimeId},
+    EffectId, Scope, SignalId,
+};
+use std::collections::{btree_map::Entry, HashSet};
+
+/// Runs `f`, holding back the effects that its signal writes would run until it has finished,
+/// so that each effect, memo, and render effect that depends on the signals runs only once,
+/// rather than once for each write.
+///
+/// Signals read inside the batch have their new values right away, but memos and effects
+/// don't run until the batch ends, so memos read inside it still have their old values.
+/// Batches can be nested; the effects run when the outermost one ends.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (first, set_first) = create_signal(cx, "Grace");
+/// let (last, set_last) = create_signal(cx, "Hopper");
+/// let runs = Rc::new(Cell::new(0));
+///
+/// create_isomorphic_effect(cx, {
+///     let runs = Rc::clone(&runs);
+///     move |_| {
+///         _ = format!("{} {}", first.get(), last.get());
+///         runs.set(runs.get() + 1);
+///     }
+/// });
+/// assert_eq!(runs.get(), 1);
+///
+/// batch(cx, || {
+///     set_first.set("Ada");
+///     set_last.set("Lovelace");
+/// });
+/// assert_eq!(runs.get(), 2);
+/// # }).dispose();
+/// ```
+pub fn batch<T>(cx: Scope, f: impl FnOnce() -> T) -> T {
+    with_runtime(cx.runtime, |runtime| {
+        runtime.batch_depth.set(runtime.batch_depth.get() + 1)
+    });
+    let _guard = BatchGuard(cx.runtime);
+    let value = f();
+    let is_outermost = with_runtime(cx.runtime, |runtime| runtime.batch_depth.get() == 1);
+    if is_outermost {
+        run_pending_effects(cx.runtime);
+    }
+    value
+}
+
+/// Ends a batch when dropped, even if `f` or one of the effects panicked, so that signal
+/// writes made afterward run their effects again instead of queuing them forever.
+struct BatchGuard(RuntimeId);
+
+impl Drop for BatchGuard {
+    fn drop(&mut self) {
+        with_runtime(self.0, |runtime| {
+            let depth = runtime.batch_depth.get() - 1;
+            runtime.batch_depth.set(depth);
+            // effects left over from a panic would otherwise run at the end of the next batch
+            if depth == 0 {
+                runtime.pending_effects.borrow_mut().clear();
+            }
+        });
+    }
+}
+
+impl Runtime {
+    /// Whether signal writes should queue their subscribers rather than run them.
+    pub(crate) fn is_batching(&self) -> bool {
+        self.batch_depth.get() > 0
+    }
+
+    /// Queues the subscribers of a signal to run when the batch ends, along with the signal
+    /// that made them run. Effects that are already queued aren't queued again.
+    pub(crate) fn queue_effects(&self, subs: HashSet<EffectId>, trigger: SignalId) {
+        let mut pending = self.pending_effects.borrow_mut();
+        let order = self.effect_order.borrow();
+        for sub in subs {
+            // effects without an order have been disposed
+            if let Some(order) = order.get(sub) {
+                if let Entry::Vacant(entry) = pending.entry(*order) {
+                    entry.insert((sub, trigger));
+                }
+            }
+        }
+    }
+}
+
+/// Runs the effects queued during a batch. The batch is still active while they run, so
+/// that writes made by memos queue the effects that read them, instead of running them
+/// before they would run anyway.
+fn run_pending_effects(runtime_id: RuntimeId) {
+    loop {
+        // run effects in the order they were created, so that memos usually run before the
+        // effects that read them
+        let next = with_runtime(runtime_id, |runtime| {
+            runtime.pending_effects.borrow_mut().pop_first()
+        });
+        let (order, (id, trigger)) = match next {
+            Some(next) => next,
+            None => break,
+        };
+
+        with_runtime(runtime_id, |runtime| {
+            // the effect may have been disposed, and its id reused by a newer effect
+            if runtime.effect_order.borrow().get(id) != Some(&order) {
+                return;
+            }
+            let effect = runtime.effects.borrow().get(id).cloned();
+            if let Some(effect) = effect {
+                #[cfg(debug_assertions)]
+                runtime.render_triggers.borrow_mut().push(trigger);
+                #[cfg(not(debug_assertions))]
+                _ = trigger;
+                effect.run(id, runtime_id);
+                #[cfg(debug_assertions)]
+                runtime.render_triggers.borrow_mut().pop();
+            }
+        });
+    }
+}
@@ -0,0 +1,103 @@
+use crate::{
+    create_effect, create_rw_signal, on_cleanup, RwSignal, Scope, UntrackedGettableSignal,
+};
+use std::fmt;
+
+/// Shares a signal with other, isolated runtimes, like those created with
+/// [create_scoped_runtime](crate::create_scoped_runtime) for widgets embedded in a page.
+///
+/// Signals can't be read or written from another runtime's effects without tying the two
+/// together, so each runtime that wants the value [connects](SignalBridge::connect) to the
+/// bridge, which creates a signal of its own. The two are kept in sync both ways: setting
+/// either one updates the other, and each runtime's effects only ever depend on its own signal.
+///
+/// A connection lasts until the [Scope] it was made in is disposed. Only the signals that are
+/// explicitly bridged are shared; everything else stays separate.
+/// ```
+/// # use leptos_reactive::*;
+/// let host = create_runtime();
+/// let widget = create_scoped_runtime("cart");
+///
+/// create_scope(host, |host_cx| {
+///     let user = create_rw_signal(host_cx, "guest".to_string());
+///     let bridge = SignalBridge::new(host_cx, user);
+///
+///     create_scope(widget, |cx| {
+///         let widget_user = bridge.connect(cx);
+///         assert_eq!(widget_user.get(), "guest");
+///
+///         // the host logs in, and the widget sees it
+///         user.set("ada".to_string());
+///         assert_eq!(widget_user.get(), "ada");
+///
+///         // the widget logs out, and the host sees it
+///         widget_user.set("guest".to_string());
+///         assert_eq!(user.get(), "guest");
+///     })
+///     .dispose();
+/// })
+/// .dispose();
+///
+/// widget.dispose();
+/// host.dispose();
+/// ```
+pub struct SignalBridge<T: 'static> {
+    cx: Scope,
+    source: RwSignal<T>,
+}
+
+impl<T> Clone for SignalBridge<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SignalBridge<T> {}
+
+impl<T: fmt::Debug> fmt::Debug for SignalBridge<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SignalBridge")
+            .field("cx", &self.cx)
+            .field("source", &self.source)
+            .finish()
+    }
+}
+
+impl<T> SignalBridge<T>
+where
+    T: Clone + PartialEq + 'static,
+{
+    /// Creates a bridge that shares `source`, which belongs to the runtime of `cx`.
+    pub fn new(cx: Scope, source: RwSignal<T>) -> Self {
+        Self { cx, source }
+    }
+
+    /// Creates a signal in the runtime of `cx` that is kept in sync with the bridged signal,
+    /// until `cx` is disposed.
+    pub fn connect(&self, cx: Scope) -> RwSignal<T> {
+        let source = self.source;
+        let target = create_rw_signal(cx, source.get_untracked());
+
+        // the source's side of the connection lives in a child of the bridge's scope, so
+        // that it stops when this side is disposed
+        let (_, source_disposer) = self.cx.run_child_scope(|source_cx| {
+            create_effect(source_cx, move |_| {
+                let value = source.get();
+                if target.with_untracked(|current| current != &value) {
+                    target.set(value);
+                }
+            });
+        });
+
+        // each side only writes when the value differs, so updates don't bounce back and forth
+        create_effect(cx, move |_| {
+            let value = target.get();
+            if source.with_untracked(|current| current != &value) {
+                source.set(value);
+            }
+        });
+        on_cleanup(cx, move || source_disposer.dispose());
+
+        target
+    }
+}
@@ -136,3 +136,105 @@ where
         }
     })
 }
+
+/// Extracts a context value of type `T`, like [use_context], but panics with a message
+/// naming the missing type if it hasn't been provided.
+///
+/// ```should_panic
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone)]
+/// struct Theme(&'static str);
+///
+/// // panics: "expected a context of type `Theme` ..."
+/// let theme = expect_context::<Theme>(cx);
+/// # }).dispose();
+/// ```
+#[track_caller]
+pub fn expect_context<T>(cx: Scope) -> T
+where
+    T: Clone + 'static,
+{
+    use_context(cx).unwrap_or_else(|| {
+        panic!(
+            "expected a context of type `{}`, but it wasn't provided in {:?} or any scope \
+             above it. Make sure `provide_context` is called in a parent component, or use \
+             `use_context_or_else` to provide a default.",
+            std::any::type_name::<T>(),
+            cx.id,
+        )
+    })
+}
+
+/// Extracts a context value of type `T`, like [use_context]. If it hasn't been provided,
+/// calls `f` with the root [Scope] of the tree, and provides its result there, so that every
+/// other caller shares the same value. Anything `f` creates should belong to that root scope,
+/// so that it lives as long as the context does.
+///
+/// This lets library components rely on a context existing, without every app having to
+/// call [provide_context] at its root.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone, Debug, PartialEq)]
+/// struct ToastQueue(RwSignal<Vec<String>>);
+///
+/// let new_queue = |root| ToastQueue(create_rw_signal(root, Vec::new()));
+///
+/// let disposer = cx.child_scope(move |cx| {
+///     let queue = use_context_or_else(cx, new_queue);
+///     queue.0.update(|toasts| toasts.push("Saved!".to_string()));
+/// });
+/// // the queue belongs to the root, so it outlives the scope that created it
+/// disposer.dispose();
+///
+/// // a component somewhere else gets the same queue
+/// cx.child_scope(move |cx| {
+///     let queue = use_context_or_else(cx, new_queue);
+///     assert_eq!(queue.0.get(), vec!["Saved!".to_string()]);
+/// });
+/// # }).dispose();
+/// ```
+pub fn use_context_or_else<T>(cx: Scope, f: impl FnOnce(Scope) -> T) -> T
+where
+    T: Clone + 'static,
+{
+    use_context(cx).unwrap_or_else(|| {
+        let root = with_runtime(cx.runtime, |runtime| {
+            let parents = runtime.scope_parents.borrow();
+            let mut id = cx.id;
+            while let Some(parent) = parents.get(id) {
+                id = *parent;
+            }
+            id
+        });
+        let root = Scope {
+            runtime: cx.runtime,
+            id: root,
+        };
+        let value = f(root);
+        provide_context(root, value.clone());
+        value
+    })
+}
+
+/// Extracts a context value of type `T`, like [use_context_or_else], but uses the type's
+/// [Default] value if it hasn't been provided. Deriving [Default] for a context type
+/// makes it usable without a provider.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone, Debug, Default, PartialEq)]
+/// struct Locale(String);
+///
+/// assert_eq!(use_context_or_default::<Locale>(cx), Locale(String::new()));
+/// # }).dispose();
+/// ```
+pub fn use_context_or_default<T>(cx: Scope) -> T
+where
+    T: Clone + Default + 'static,
+{
+    use_context_or_else(cx, |_| T::default())
+}
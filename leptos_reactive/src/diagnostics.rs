@@ -0,0 +1,278 @@
+use crate::{
+    create_rw_signal, on_cleanup, provide_context,
+    runtime::{with_runtime, Runtime},
+    RwSignal, Scope, ScopeId, Signal,
+};
+use std::{any::TypeId, borrow::Cow, fmt};
+
+/// The kind of problem described by a [Diagnostic].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    /// An element or component rendered on the server couldn’t be found while hydrating,
+    /// so it was rendered again in the browser.
+    HydrationMiss,
+    /// A context that a component or hook relies on wasn’t provided above it.
+    MissingContext,
+    /// A signal was read or written after its [Scope] was disposed.
+    DisposedSignal,
+    /// An effect caused itself to run again by writing to a signal it depends on, or ran
+    /// too many times in response to one change. See [set_effect_run_limit](crate::set_effect_run_limit).
+    EffectCycle,
+    /// Any other warning from the framework or a library built on it.
+    Other,
+}
+
+impl fmt::Display for DiagnosticKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::HydrationMiss => "hydration miss",
+            Self::MissingContext => "missing context",
+            Self::DisposedSignal => "disposed signal",
+            Self::EffectCycle => "effect cycle",
+            Self::Other => "warning",
+        })
+    }
+}
+
+/// A warning from the framework, collected by [Diagnostics].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// What kind of problem this is.
+    pub kind: DiagnosticKind,
+    /// A description of the problem, as it would be logged to the console.
+    pub message: String,
+    /// The path of components in which the problem happened, from the outermost to the
+    /// innermost, like `"App > TodoList > Todo"`, if it is known.
+    ///
+    /// Components are only recorded in debug builds.
+    pub component: Option<String>,
+    /// The name of the runtime in which the problem happened, if it was created with
+    /// [create_scoped_runtime](crate::create_scoped_runtime).
+    pub runtime: Option<String>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic that isn’t associated with any component.
+    pub fn new(kind: DiagnosticKind, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            component: None,
+            runtime: None,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(runtime) = &self.runtime {
+            write!(f, "{runtime}: ")?;
+        }
+        write!(f, "[{}] {}", self.kind, self.message)?;
+        if let Some(component) = &self.component {
+            write!(f, " (in {component})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects the warnings the framework reports while an app is running, like hydration
+/// misses, missing contexts, and signals accessed after being disposed, as structured
+/// [Diagnostic]s. Created with [provide_diagnostics].
+///
+/// The warnings are still logged to the console as usual; collecting them lets tests
+/// assert that none were raised, and lets developer tools display them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Diagnostics {
+    entries: RwSignal<Vec<Diagnostic>>,
+}
+
+impl Diagnostics {
+    /// A signal with every diagnostic collected so far, in the order they were reported,
+    /// which updates as new ones are reported.
+    pub fn entries(&self) -> Signal<Vec<Diagnostic>> {
+        self.entries.into()
+    }
+
+    /// The diagnostics of the given kind collected so far.
+    pub fn of_kind(&self, kind: DiagnosticKind) -> Vec<Diagnostic> {
+        self.entries.with(|entries| {
+            entries
+                .iter()
+                .filter(|diagnostic| diagnostic.kind == kind)
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Removes and returns every diagnostic collected so far.
+    pub fn take(&self) -> Vec<Diagnostic> {
+        let mut taken = Vec::new();
+        self.entries
+            .update(|entries| taken = std::mem::take(entries));
+        taken
+    }
+
+    /// Removes every diagnostic collected so far.
+    pub fn clear(&self) {
+        self.entries.update(Vec::clear);
+    }
+}
+
+/// Starts collecting the warnings reported anywhere in this [Scope]’s runtime into a
+/// [Diagnostics], until the scope is disposed. The collector is also provided as a context.
+///
+/// Only one collector is active in each runtime; calling this again replaces it.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let diagnostics = provide_diagnostics(cx);
+///
+/// // writing to a signal whose scope has been disposed
+/// let ((_, set_count), disposer) = cx.run_child_scope(|cx| create_signal(cx, 0));
+/// disposer.dispose();
+/// set_count.set(1);
+///
+/// let warnings = diagnostics.of_kind(DiagnosticKind::DisposedSignal);
+/// assert_eq!(warnings.len(), 1);
+/// # }).dispose();
+/// ```
+pub fn provide_diagnostics(cx: Scope) -> Diagnostics {
+    let diagnostics = Diagnostics {
+        // writing to the collector shouldn’t be reported to signal write interceptors
+        entries: create_rw_signal(cx, Vec::new()).skip_interceptors(),
+    };
+    with_runtime(cx.runtime, |runtime| {
+        runtime.diagnostics.set(Some(diagnostics))
+    });
+    on_cleanup(cx, move || {
+        with_runtime(cx.runtime, |runtime| {
+            if runtime.diagnostics.get() == Some(diagnostics) {
+                runtime.diagnostics.set(None);
+            }
+        })
+    });
+    provide_context(cx, diagnostics);
+    diagnostics
+}
+
+/// Returns the [Diagnostics] that is collecting warnings in this [Scope]’s runtime, if
+/// [provide_diagnostics] has been called.
+pub fn use_diagnostics(cx: Scope) -> Option<Diagnostics> {
+    with_runtime(cx.runtime, |runtime| runtime.diagnostics.get())
+}
+
+/// Reports a warning to the [Diagnostics] collecting warnings in this [Scope]’s runtime,
+/// if there is one, along with the path of components in which it happened.
+///
+/// This doesn’t log the warning, which should be done separately.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let diagnostics = provide_diagnostics(cx);
+///
+/// report_diagnostic(cx, DiagnosticKind::MissingContext, "no ThemeContext was provided");
+///
+/// let warnings = diagnostics.take();
+/// assert_eq!(warnings[0].message, "no ThemeContext was provided");
+/// assert!(diagnostics.entries().get().is_empty());
+/// # }).dispose();
+/// ```
+pub fn report_diagnostic(cx: Scope, kind: DiagnosticKind, message: impl Into<String>) {
+    with_runtime(cx.runtime, |runtime| {
+        runtime.report_diagnostic(|| Diagnostic {
+            kind,
+            message: message.into(),
+            component: runtime.component_path(cx.id),
+            runtime: None,
+        })
+    })
+}
+
+/// Reports a warning to the [Diagnostics] collecting warnings in the browser, for code
+/// that doesn’t have access to a [Scope], like hydration. These are reported to the main
+/// runtime in the browser; on the server, this does nothing.
+#[doc(hidden)]
+pub fn report_browser_diagnostic(diagnostic: Diagnostic) {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            crate::runtime::RUNTIME.with(|runtime| runtime.report_diagnostic(|| diagnostic));
+        } else {
+            _ = diagnostic;
+        }
+    }
+}
+
+/// The name of the component that created a [Scope], used to describe where a
+/// [Diagnostic] was reported.
+#[derive(Clone)]
+struct ComponentName(Cow<'static, str>);
+
+/// Records the name of the component that created this [Scope]. Called by the framework
+/// when rendering a component in debug builds.
+#[doc(hidden)]
+pub fn set_component_name(cx: Scope, name: impl Into<Cow<'static, str>>) {
+    provide_context(cx, ComponentName(name.into()));
+}
+
+impl Runtime {
+    /// Adds a diagnostic to the active [Diagnostics], if there is one. The diagnostic is
+    /// only created if it will be collected.
+    pub(crate) fn report_diagnostic(&self, diagnostic: impl FnOnce() -> Diagnostic) {
+        // taken while reporting, so that a warning raised by the write itself
+        // can’t report another diagnostic
+        if let Some(diagnostics) = self.diagnostics.take() {
+            let mut diagnostic = diagnostic();
+            if diagnostic.runtime.is_none() {
+                diagnostic.runtime = self.name.borrow().clone();
+            }
+            diagnostics
+                .entries
+                .update(|entries| entries.push(diagnostic));
+            self.diagnostics.set(Some(diagnostics));
+        }
+    }
+
+    /// The names of the components that created this scope and its ancestors.
+    pub(crate) fn component_path(&self, scope: ScopeId) -> Option<String> {
+        let contexts = self.scope_contexts.borrow();
+        let parents = self.scope_parents.borrow();
+        let mut names = Vec::new();
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let name = contexts
+                .get(id)
+                .and_then(|contexts| contexts.get(&TypeId::of::<ComponentName>()))
+                .and_then(|name| name.downcast_ref::<ComponentName>());
+            if let Some(ComponentName(name)) = name {
+                names.push(name.to_string());
+            }
+            current = parents.get(id).copied();
+        }
+        if names.is_empty() {
+            None
+        } else {
+            names.reverse();
+            Some(names.join(" > "))
+        }
+    }
+
+    /// The innermost scope, starting with this one, that was created by a component.
+    pub(crate) fn component_scope(&self, scope: ScopeId) -> Option<ScopeId> {
+        let contexts = self.scope_contexts.borrow();
+        let parents = self.scope_parents.borrow();
+        let mut current = Some(scope);
+        while let Some(id) = current {
+            let is_component = contexts
+                .get(id)
+                .map(|contexts| contexts.contains_key(&TypeId::of::<ComponentName>()))
+                .unwrap_or(false);
+            if is_component {
+                return Some(id);
+            }
+            current = parents.get(id).copied();
+        }
+        None
+    }
+}
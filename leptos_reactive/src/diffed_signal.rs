@@ -0,0 +1,441 @@
+use crate::{
+    batch, create_rw_signal, store_value, ReadSignal, RwSignal, Scope, StoredValue,
+    UntrackedGettableSignal,
+};
+use futures::Stream;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    hash::Hash,
+    rc::Rc,
+};
+
+/// Creates a [DiffedSignal] over a list, which computes the [ListPatch]es between the
+/// old and new values every time it is set.
+///
+/// Items are identified by the `key` function, which should return a unique key for each
+/// item in the list. An item whose key is found in both the old and new list is moved
+/// (if its position changed) and updated (if it's not equal to the old item), rather than
+/// being removed and inserted again.
+/// If several items have the same key, only the first of them in each list is matched; the
+/// others are removed and inserted again. Computing the patches takes `O(n log n)` time.
+///
+/// This allows keyed lists and virtual lists to apply each change directly, rather than
+/// diffing all their children again.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let todos = create_diffed_signal(cx, vec![(1, "eat"), (2, "sleep")], |todo| todo.0);
+///
+/// todos.update(|todos| {
+///     todos.swap(0, 1);
+///     todos[1].1 = "eat breakfast";
+///     todos.push((3, "repeat"));
+/// });
+///
+/// assert_eq!(
+///     todos.patches().get(),
+///     vec![
+///         ListPatch::Move { from: 1, to: 0 },
+///         ListPatch::Update { index: 1, value: (1, "eat breakfast") },
+///         ListPatch::Insert { index: 2, value: (3, "repeat") },
+///     ]
+/// );
+/// # }).dispose();
+/// ```
+pub fn create_diffed_signal<T, K>(
+    cx: Scope,
+    value: Vec<T>,
+    key: impl Fn(&T) -> K + 'static,
+) -> DiffedSignal<Vec<T>, ListPatch<T>>
+where
+    T: PartialEq + Clone + 'static,
+    K: Eq + Hash + 'static,
+{
+    DiffedSignal::new(cx, value, move |old, new| diff_list(old, new, &key))
+}
+
+/// Creates a [DiffedSignal] over a map, which computes the [MapPatch]es between the
+/// old and new values every time it is set.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::collections::BTreeMap;
+/// # create_scope(create_runtime(), |cx| {
+/// let scores = create_diffed_map_signal(cx, BTreeMap::from([("alice", 1), ("bob", 2)]));
+///
+/// scores.update(|scores| {
+///     scores.remove("alice");
+///     scores.insert("bob", 3);
+/// });
+///
+/// assert_eq!(
+///     scores.patches().get(),
+///     vec![
+///         MapPatch::Remove { key: "alice" },
+///         MapPatch::Update { key: "bob", value: 3 },
+///     ]
+/// );
+/// # }).dispose();
+/// ```
+pub fn create_diffed_map_signal<K, V>(
+    cx: Scope,
+    value: BTreeMap<K, V>,
+) -> DiffedSignal<BTreeMap<K, V>, MapPatch<K, V>>
+where
+    K: Ord + Clone + 'static,
+    V: PartialEq + Clone + 'static,
+{
+    DiffedSignal::new(cx, value, diff_map)
+}
+
+/// A signal holding a collection, which computes the patches between its old and new
+/// values every time it is set. Created with [create_diffed_signal] or
+/// [create_diffed_map_signal].
+///
+/// The collection can be read like any other signal. The patches from the most recent
+/// change can be read with [DiffedSignal::patches] or [DiffedSignal::to_patch_stream].
+pub struct DiffedSignal<C, P>
+where
+    C: 'static,
+    P: 'static,
+{
+    cx: Scope,
+    value: RwSignal<C>,
+    patches: RwSignal<Vec<P>>,
+    #[allow(clippy::type_complexity)]
+    diff: StoredValue<Rc<dyn Fn(&C, &C) -> Vec<P>>>,
+}
+
+impl<C, P> Clone for DiffedSignal<C, P> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<C, P> Copy for DiffedSignal<C, P> {}
+
+impl<C, P> std::fmt::Debug for DiffedSignal<C, P>
+where
+    C: std::fmt::Debug,
+    P: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DiffedSignal")
+            .field("value", &self.value)
+            .field("patches", &self.patches)
+            .finish()
+    }
+}
+
+impl<C, P> DiffedSignal<C, P>
+where
+    C: 'static,
+    P: 'static,
+{
+    fn new(cx: Scope, value: C, diff: impl Fn(&C, &C) -> Vec<P> + 'static) -> Self {
+        Self {
+            cx,
+            value: create_rw_signal(cx, value),
+            patches: create_rw_signal(cx, Vec::new()),
+            diff: store_value(cx, Rc::new(diff)),
+        }
+    }
+
+    /// Applies a function to the current value of the collection, and subscribes
+    /// the running effect to this signal.
+    pub fn with<U>(&self, f: impl FnOnce(&C) -> U) -> U {
+        self.value.with(f)
+    }
+
+    /// Clones and returns the current value of the collection, and subscribes
+    /// the running effect to this signal.
+    pub fn get(&self) -> C
+    where
+        C: Clone,
+    {
+        self.value.get()
+    }
+
+    /// Sets the value of the collection, computes the patches from the old value, and
+    /// notifies subscribers. If nothing has changed, subscribers are not notified.
+    ///
+    /// The value and the patches are set together, so a subscriber to either never sees
+    /// one of them change without the other.
+    pub fn set(&self, new_value: C) {
+        let diff = self.diff.get();
+        let patches = self.value.with_untracked(|old| diff(old, &new_value));
+        if !patches.is_empty() {
+            batch(self.cx, || {
+                self.value.set(new_value);
+                self.patches.set(patches);
+            });
+        }
+    }
+
+    /// Applies a function to a copy of the collection, then sets it as with
+    /// [DiffedSignal::set].
+    pub fn update(&self, f: impl FnOnce(&mut C))
+    where
+        C: Clone,
+    {
+        let mut new_value = self.value.with_untracked(C::clone);
+        f(&mut new_value);
+        self.set(new_value);
+    }
+
+    /// The patches that turned the previous value into the current one, which can be
+    /// applied in order. This is empty until the signal has been set.
+    pub fn patches(&self) -> ReadSignal<Vec<P>> {
+        self.patches.read_only()
+    }
+
+    /// Returns a [Stream] that yields the patches for each change to the collection.
+    pub fn to_patch_stream(&self) -> impl Stream<Item = Vec<P>>
+    where
+        P: Clone,
+    {
+        self.patches.to_stream()
+    }
+
+    /// Returns a read-only handle to the collection.
+    pub fn read_only(&self) -> ReadSignal<C> {
+        self.value.read_only()
+    }
+}
+
+impl<C, P> UntrackedGettableSignal<C> for DiffedSignal<C, P> {
+    fn get_untracked(&self) -> C
+    where
+        C: Clone,
+    {
+        self.value.get_untracked()
+    }
+
+    fn with_untracked<O>(&self, f: impl FnOnce(&C) -> O) -> O {
+        self.value.with_untracked(f)
+    }
+}
+
+/// A change to a list. Applying each of the patches from [DiffedSignal::patches]
+/// in order turns the old list into the new one.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ListPatch<T> {
+    /// Inserts a new item at `index`.
+    Insert {
+        /// The index at which to insert the item.
+        index: usize,
+        /// The new item.
+        value: T,
+    },
+    /// Removes the item at `index`.
+    Remove {
+        /// The index of the item to remove.
+        index: usize,
+    },
+    /// Removes the item at `from`, and inserts it again at `to`.
+    Move {
+        /// The index of the item before it moves.
+        from: usize,
+        /// The index of the item after it moves.
+        to: usize,
+    },
+    /// Replaces the item at `index`, which has the same key as the new value.
+    Update {
+        /// The index of the item to replace.
+        index: usize,
+        /// The new value of the item.
+        value: T,
+    },
+}
+
+impl<T> ListPatch<T>
+where
+    T: Clone,
+{
+    /// Applies this change to the list.
+    pub fn apply(&self, list: &mut Vec<T>) {
+        match self {
+            Self::Insert { index, value } => list.insert(*index, value.clone()),
+            Self::Remove { index } => {
+                list.remove(*index);
+            }
+            Self::Move { from, to } => {
+                let item = list.remove(*from);
+                list.insert(*to, item);
+            }
+            Self::Update { index, value } => list[*index] = value.clone(),
+        }
+    }
+}
+
+/// A change to a map.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MapPatch<K, V> {
+    /// Inserts a new key.
+    Insert {
+        /// The new key.
+        key: K,
+        /// The value of the new key.
+        value: V,
+    },
+    /// Removes a key.
+    Remove {
+        /// The key that was removed.
+        key: K,
+    },
+    /// Changes the value of an existing key.
+    Update {
+        /// The key whose value changed.
+        key: K,
+        /// The new value.
+        value: V,
+    },
+}
+
+impl<K, V> MapPatch<K, V>
+where
+    K: Ord + Clone,
+    V: Clone,
+{
+    /// Applies this change to the map.
+    pub fn apply(&self, map: &mut BTreeMap<K, V>) {
+        match self {
+            Self::Insert { key, value } | Self::Update { key, value } => {
+                map.insert(key.clone(), value.clone());
+            }
+            Self::Remove { key } => {
+                map.remove(key);
+            }
+        }
+    }
+}
+
+fn diff_list<T, K>(old: &[T], new: &[T], key: impl Fn(&T) -> K) -> Vec<ListPatch<T>>
+where
+    T: PartialEq + Clone,
+    K: Eq + Hash,
+{
+    let new_keys = new.iter().map(&key).collect::<Vec<_>>();
+    let in_new = new_keys.iter().collect::<HashSet<_>>();
+    let mut patches = Vec::new();
+
+    // only the first old item with each key is matched; later ones are removed
+    let old_keys = old.iter().map(&key).collect::<Vec<_>>();
+    let mut first_with_key = HashMap::with_capacity(old.len());
+    for (index, item_key) in old_keys.iter().enumerate() {
+        first_with_key.entry(item_key).or_insert(index);
+    }
+
+    // remove items that aren't in the new list, from the end so that indices stay valid
+    let is_kept = |index: usize| {
+        in_new.contains(&old_keys[index]) && first_with_key[&old_keys[index]] == index
+    };
+    for index in (0..old.len()).rev() {
+        if !is_kept(index) {
+            patches.push(ListPatch::Remove { index });
+        }
+    }
+
+    // the position of each remaining old item, and the item itself
+    let kept = (0..old.len())
+        .filter(|index| is_kept(*index))
+        .enumerate()
+        .map(|(position, index)| (&old_keys[index], (position, &old[index])))
+        .collect::<HashMap<_, _>>();
+
+    // then move, update, or insert each item in the new list, in order. The list is always the
+    // new items placed so far, followed by the old items that haven't been placed yet, in
+    // their old order, so an old item is found after as many items as are still unplaced
+    // before it
+    let mut unplaced = Counts::new(kept.len());
+    let mut placed = vec![false; kept.len()];
+    for (index, (item, item_key)) in new.iter().zip(new_keys.iter()).enumerate() {
+        match kept.get(item_key) {
+            Some(&(position, old_item)) if !placed[position] => {
+                placed[position] = true;
+                let offset = unplaced.before(position);
+                unplaced.remove(position);
+                if offset > 0 {
+                    patches.push(ListPatch::Move {
+                        from: index + offset,
+                        to: index,
+                    });
+                }
+                if *old_item != *item {
+                    patches.push(ListPatch::Update {
+                        index,
+                        value: item.clone(),
+                    });
+                }
+            }
+            // a new key, or a later item with the same key as one already placed
+            _ => patches.push(ListPatch::Insert {
+                index,
+                value: item.clone(),
+            }),
+        }
+    }
+
+    patches
+}
+
+/// Counts which of a fixed number of positions are still set, and how many are set before a
+/// given position, each in `O(log n)` time (a Fenwick tree).
+struct Counts(Vec<usize>);
+
+impl Counts {
+    /// Creates a counter with every position set.
+    fn new(len: usize) -> Self {
+        let mut tree = vec![0; len + 1];
+        for index in 1..=len {
+            tree[index] += 1;
+            let parent = index + (index & index.wrapping_neg());
+            if parent <= len {
+                tree[parent] += tree[index];
+            }
+        }
+        Self(tree)
+    }
+
+    /// Clears the given position, which must be set.
+    fn remove(&mut self, position: usize) {
+        let mut index = position + 1;
+        while index < self.0.len() {
+            self.0[index] -= 1;
+            index += index & index.wrapping_neg();
+        }
+    }
+
+    /// The number of positions set before the given one.
+    fn before(&self, position: usize) -> usize {
+        let mut count = 0;
+        let mut index = position;
+        while index > 0 {
+            count += self.0[index];
+            index -= index & index.wrapping_neg();
+        }
+        count
+    }
+}
+
+fn diff_map<K, V>(old: &BTreeMap<K, V>, new: &BTreeMap<K, V>) -> Vec<MapPatch<K, V>>
+where
+    K: Ord + Clone,
+    V: PartialEq + Clone,
+{
+    let removed = old
+        .keys()
+        .filter(|key| !new.contains_key(key))
+        .map(|key| MapPatch::Remove { key: key.clone() });
+    let changed = new.iter().filter_map(|(key, value)| match old.get(key) {
+        None => Some(MapPatch::Insert {
+            key: key.clone(),
+            value: value.clone(),
+        }),
+        Some(old) if old != value => Some(MapPatch::Update {
+            key: key.clone(),
+            value: value.clone(),
+        }),
+        Some(_) => None,
+    });
+    removed.chain(changed).collect()
+}
@@ -1,8 +1,9 @@
 use crate::runtime::{with_runtime, RuntimeId};
-use crate::{debug_warn, Runtime, Scope, ScopeProperty};
+use crate::{debug_warn, on_cleanup, Runtime, Scope, ScopeProperty};
 use cfg_if::cfg_if;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::fmt::Debug;
+use std::rc::Rc;
 
 /// Effects run a certain chunk of code whenever the signals they depend on change.
 /// `create_effect` immediately runs the given function once, tracks its dependence
@@ -19,7 +20,8 @@ use std::fmt::Debug;
 ///
 /// By default, effects **do not run on the server**. This means you can call browser-specific
 /// APIs within the effect function without causing issues. If you need an effect to run on
-/// the server, use [create_isomorphic_effect].
+/// the server, use [create_isomorphic_effect], or [create_server_effect] for one that only runs
+/// there.
 /// ```
 /// # use leptos_reactive::*;
 /// # use log::*;
@@ -92,10 +94,120 @@ pub fn create_isomorphic_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'stat
 where
     T: 'static,
 {
-    let e = cx.runtime.create_effect(f);
+    let e = cx.runtime.create_effect_in_scope(Some(cx.id), f);
     cx.with_scope_property(|prop| prop.push(ScopeProperty::Effect(e)))
 }
 
+/// Creates an effect that **only runs on the server**, the converse of [create_effect]. This is
+/// useful for work that only matters while rendering HTML, like filling in the `<head>` or
+/// reading server-side state, without an `is_server()` check.
+///
+/// Without the `ssr` feature, the effect is never created, so its function is left out of
+/// client bundles.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (title, set_title) = create_signal(cx, "Home");
+/// let renders = Rc::new(Cell::new(0));
+///
+/// create_server_effect(cx, {
+///     let renders = Rc::clone(&renders);
+///     move |_| {
+///         // e.g., set the page's `<title>` while rendering it
+///         _ = title();
+///         renders.set(renders.get() + 1);
+///     }
+/// });
+///
+/// set_title("About");
+/// assert_eq!(renders.get(), if cfg!(feature = "ssr") { 2 } else { 0 });
+/// # }).dispose();
+/// ```
+pub fn create_server_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
+where
+    T: 'static,
+{
+    cfg_if! {
+        if #[cfg(feature = "ssr")] {
+            create_isomorphic_effect(cx, f);
+        } else {
+            // clear warnings
+            _ = cx;
+            _ = f;
+        }
+    }
+}
+
+/// Creates an effect whose function returns a cleanup function. The cleanup runs before
+/// the next time the effect runs, and when the [Scope] is disposed, so anything it
+/// needs can simply be captured when it's created.
+///
+/// Like [create_effect], this **does not run on the server**; use
+/// [create_isomorphic_effect_with_cleanup] for an effect that does.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (room, set_room) = create_signal(cx, "general");
+/// let log = Rc::new(RefCell::new(Vec::new()));
+///
+/// create_effect_with_cleanup(cx, {
+///     let log = Rc::clone(&log);
+///     move || {
+///         let room = room();
+///         log.borrow_mut().push(format!("join {room}"));
+///         let log = Rc::clone(&log);
+///         move || log.borrow_mut().push(format!("leave {room}"))
+///     }
+/// });
+///
+/// set_room("random");
+/// # if !cfg!(feature = "ssr") {
+/// assert_eq!(*log.borrow(), ["join general", "leave general", "join random"]);
+/// # }
+/// # }).dispose();
+/// ```
+pub fn create_effect_with_cleanup<C>(cx: Scope, f: impl Fn() -> C + 'static)
+where
+    C: FnOnce() + 'static,
+{
+    cfg_if! {
+        if #[cfg(not(feature = "ssr"))] {
+            create_isomorphic_effect_with_cleanup(cx, f);
+        } else {
+            // clear warnings
+            _ = cx;
+            _ = f;
+        }
+    }
+}
+
+/// Creates an effect whose function returns a cleanup function, like
+/// [create_effect_with_cleanup]; unlike that effect, this one will run on the server as
+/// well as the client.
+pub fn create_isomorphic_effect_with_cleanup<C>(cx: Scope, f: impl Fn() -> C + 'static)
+where
+    C: FnOnce() + 'static,
+{
+    let cleanup: Rc<Cell<Option<C>>> = Default::default();
+    create_isomorphic_effect(cx, {
+        let cleanup = Rc::clone(&cleanup);
+        move |_| {
+            // signals read while cleaning up shouldn't become dependencies of the effect
+            if let Some(cleanup) = cleanup.take() {
+                cx.untrack(cleanup);
+            }
+            cleanup.set(Some(f()));
+        }
+    });
+    on_cleanup(cx, move || {
+        if let Some(cleanup) = cleanup.take() {
+            cleanup();
+        }
+    });
+}
+
 #[doc(hidden)]
 pub fn create_render_effect<T>(cx: Scope, f: impl Fn(Option<T>) -> T + 'static)
 where
@@ -129,6 +241,13 @@ where
 {
     fn run(&self, id: EffectId, runtime: RuntimeId) {
         with_runtime(runtime, |runtime| {
+            // stop effects that are stuck in an infinite loop
+            #[cfg(debug_assertions)]
+            let _running = match runtime.enter_effect(id) {
+                Some(running) => running,
+                None => return,
+            };
+
             // clear previous dependencies
             id.cleanup(runtime);
 
@@ -138,11 +257,15 @@ where
 
             // run the effect
             let value = self.value.take();
+            #[cfg(debug_assertions)]
+            let prev_reason = runtime.begin_render(id, value.is_none());
             let new_value = (self.f)(value);
             *self.value.borrow_mut() = Some(new_value);
 
             // restore the previous observer
             runtime.observer.set(prev_observer);
+            #[cfg(debug_assertions)]
+            runtime.render_reason.replace(prev_reason);
         })
     }
 }
@@ -0,0 +1,222 @@
+use crate::{
+    runtime::{with_runtime, Runtime},
+    Diagnostic, DiagnosticKind, EffectId, Scope, SignalId, SignalTrigger,
+};
+use std::collections::{HashMap, HashSet};
+
+/// How many times an effect can run in response to a single change, unless changed with
+/// [set_effect_run_limit].
+const DEFAULT_EFFECT_RUN_LIMIT: usize = 100;
+
+/// Sets how many times an effect can run in response to a single change (or [batch]) before
+/// it is treated as an infinite loop and stopped. Defaults to 100.
+///
+/// In debug builds, the runtime notices when an effect causes itself to run again while it is
+/// running, i.e., when it writes to a signal it reads, directly or through other effects and
+/// memos. This is reported as a warning, and as a [Diagnostic] of kind
+/// [DiagnosticKind::EffectCycle], with the path of effects and signals that make up the
+/// cycle. An effect that keeps causing itself to run past this limit is reported again and
+/// isn't run again until the next change, rather than hanging the app. An effect that runs
+/// past the limit for another reason, e.g., because an outer effect writes to a signal in a
+/// loop, is only reported, and still runs every time.
+///
+/// These checks only run in debug builds.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let diagnostics = provide_diagnostics(cx);
+/// set_effect_run_limit(cx, 10);
+///
+/// let (count, set_count) = create_signal(cx, 0);
+/// let set_count = set_count.with_debug_name("count");
+///
+/// // ❌ writes to the signal it reads, so it runs again every time it runs
+/// create_isomorphic_effect(cx, move |_| {
+///     let current = count.get();
+///     set_count.set(current + 1);
+/// });
+///
+/// # if cfg!(debug_assertions) {
+/// assert_eq!(count.get_untracked(), 10);
+/// let cycles = diagnostics.of_kind(DiagnosticKind::EffectCycle);
+/// assert_eq!(
+///     cycles[0].message,
+///     "an effect writes to a signal it depends on: \
+///      an effect → `count` changed → the same effect"
+/// );
+/// # }
+/// # }).dispose();
+/// ```
+///
+/// [batch]: crate::batch
+pub fn set_effect_run_limit(cx: Scope, limit: usize) {
+    with_runtime(cx.runtime, |runtime| {
+        runtime.effect_loops.borrow_mut().limit = Some(limit)
+    })
+}
+
+/// Tracks the effects that are running, and how many times each one has run in response to
+/// the current change, to find cycles and infinite loops.
+#[derive(Debug, Default)]
+pub(crate) struct EffectLoops {
+    /// The effects that are running, from the outermost to the innermost, with the signal
+    /// whose change made each one run.
+    running: Vec<(EffectId, Option<SignalId>)>,
+    /// How many times each effect has run in response to the current change.
+    runs: HashMap<EffectId, usize>,
+    /// The effects whose cycles have already been reported for the current change.
+    reported: HashSet<EffectId>,
+    limit: Option<usize>,
+}
+
+impl EffectLoops {
+    pub(crate) fn is_empty(&self) -> bool {
+        self.running.is_empty()
+            && self.runs.is_empty()
+            && self.reported.is_empty()
+            && self.limit.is_none()
+    }
+}
+
+/// An effect that is running, which is recorded as finished when this is dropped, even if the
+/// effect panics.
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+pub(crate) struct RunningEffect<'a> {
+    runtime: &'a Runtime,
+    id: EffectId,
+}
+
+impl Drop for RunningEffect<'_> {
+    fn drop(&mut self) {
+        self.runtime.exit_effect(self.id);
+    }
+}
+
+#[cfg_attr(not(debug_assertions), allow(dead_code))]
+impl Runtime {
+    /// Records that an effect is about to run, reporting it if it is already running or has
+    /// run too many times. Returns a guard that records that it has finished when it's
+    /// dropped, or [None] if it is stuck in a cycle and shouldn't run.
+    pub(crate) fn enter_effect(&self, id: EffectId) -> Option<RunningEffect<'_>> {
+        let trigger = self.render_triggers.borrow().last().copied();
+        let mut loops = self.effect_loops.borrow_mut();
+
+        // a new change starts when nothing else is running
+        if loops.running.is_empty() && !self.is_batching() {
+            loops.runs.clear();
+            loops.reported.clear();
+        }
+
+        let limit = loops.limit.unwrap_or(DEFAULT_EFFECT_RUN_LIMIT);
+        let runs = {
+            let runs = loops.runs.entry(id).or_default();
+            *runs += 1;
+            *runs
+        };
+        let cycle = loops
+            .running
+            .iter()
+            .position(|(running, _)| *running == id)
+            .map(|start| self.describe_cycle(&loops.running[start..], trigger));
+
+        // only an effect that makes itself run again is stopped, because one that runs many
+        // times for another reason still has to see every change
+        let stop = runs > limit && cycle.is_some();
+        let message = if runs == limit + 1 {
+            // reported once, when the limit is reached
+            Some(match &cycle {
+                Some(cycle) => format!(
+                    "an effect ran more than {limit} times in response to one change, so it \
+                     was stopped; this is probably an infinite loop: {cycle}"
+                ),
+                None => format!(
+                    "an effect ran more than {limit} times in response to one change; if \
+                     that's unexpected, check for code that writes to a signal in a loop"
+                ),
+            })
+        } else {
+            cycle
+                .filter(|_| loops.reported.insert(id))
+                .map(|cycle| format!("an effect writes to a signal it depends on: {cycle}"))
+        };
+
+        if !stop {
+            loops.running.push((id, trigger));
+        }
+        drop(loops);
+
+        if let Some(message) = message {
+            crate::debug_warn!("{message}");
+            self.report_diagnostic(|| Diagnostic {
+                kind: DiagnosticKind::EffectCycle,
+                message,
+                component: self.effect_component(id),
+                runtime: None,
+            });
+        }
+
+        if stop {
+            None
+        } else {
+            Some(RunningEffect { runtime: self, id })
+        }
+    }
+
+    /// Records that an effect that was allowed to run by [Runtime::enter_effect] has finished.
+    fn exit_effect(&self, id: EffectId) {
+        let mut loops = self.effect_loops.borrow_mut();
+        if let Some(idx) = loops
+            .running
+            .iter()
+            .rposition(|(running, _)| *running == id)
+        {
+            loops.running.remove(idx);
+        }
+    }
+
+    /// Describes the effects and signals from an effect that is running to the change that
+    /// is making it run again, like "an effect in Counter → `count` changed → the same effect".
+    fn describe_cycle(
+        &self,
+        running: &[(EffectId, Option<SignalId>)],
+        trigger: Option<SignalId>,
+    ) -> String {
+        let mut steps = Vec::new();
+        for (idx, (effect, _)) in running.iter().enumerate() {
+            if idx > 0 {
+                steps.push(self.describe_trigger(running[idx].1));
+            }
+            steps.push(self.describe_effect(*effect));
+        }
+        steps.push(self.describe_trigger(trigger));
+        steps.push("the same effect".to_string());
+        steps.join(" → ")
+    }
+
+    fn describe_effect(&self, id: EffectId) -> String {
+        match self.effect_component(id) {
+            Some(component) => format!("an effect in {component}"),
+            None => "an effect".to_string(),
+        }
+    }
+
+    fn describe_trigger(&self, trigger: Option<SignalId>) -> String {
+        match trigger {
+            Some(id) => {
+                let name = self
+                    .signal_debug_info
+                    .borrow()
+                    .get(id)
+                    .and_then(|info| info.name());
+                format!("{} changed", SignalTrigger { id, name })
+            }
+            None => "ran again".to_string(),
+        }
+    }
+
+    /// The path of components that the effect was created in.
+    fn effect_component(&self, id: EffectId) -> Option<String> {
+        let scope = self.effect_scopes.borrow().get(id).copied()?;
+        self.component_path(scope)
+    }
+}
@@ -7,8 +7,31 @@ pub struct SharedContext {
     pub pending_resources: HashSet<ResourceId>,
     pub resolved_resources: HashMap<ResourceId, String>,
     #[allow(clippy::type_complexity)]
-    // index String is the fragment ID: tuple is (ID of previous component, Future of <Suspense/> HTML when resolved)
-    pub pending_fragments: HashMap<String, (String, PinnedFuture<String>)>,
+    // index String is the fragment ID
+    pub pending_fragments: HashMap<String, FragmentData>,
+    // resolves when each blocking resource has loaded
+    pub blocking_resources: Vec<PinnedFuture<()>>,
+}
+
+/// A `<Suspense/>` whose HTML will be streamed once all the resources read under it
+/// have resolved.
+pub struct FragmentData {
+    /// The hydration key of the component before the `<Suspense/>`.
+    pub key_before_suspense: String,
+    /// Yields the HTML of the `<Suspense/>` once its resources have resolved.
+    pub html: PinnedFuture<String>,
+    /// Whether a [blocking resource](crate::create_blocking_resource) was read under
+    /// the `<Suspense/>`, in which case it should be rendered before the shell is sent.
+    pub should_block: bool,
+}
+
+impl std::fmt::Debug for FragmentData {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("FragmentData")
+            .field("key_before_suspense", &self.key_before_suspense)
+            .field("should_block", &self.should_block)
+            .finish()
+    }
 }
 
 impl std::fmt::Debug for SharedContext {
@@ -55,15 +78,24 @@ impl Default for SharedContext {
                     pending_resources,
                     resolved_resources,
                     pending_fragments: Default::default(),
+                    blocking_resources: Default::default(),
                 }
             } else {
-                Self {
-                    events: Default::default(),
-                    pending_resources: Default::default(),
-                    resolved_resources: Default::default(),
-                    pending_fragments: Default::default(),
-                }
+                Self::empty()
             }
         }
     }
 }
+
+impl SharedContext {
+    /// A context with no resources from the server.
+    pub(crate) fn empty() -> Self {
+        Self {
+            events: Default::default(),
+            pending_resources: Default::default(),
+            resolved_resources: Default::default(),
+            pending_fragments: Default::default(),
+            blocking_resources: Default::default(),
+        }
+    }
+}
@@ -0,0 +1,241 @@
+use crate::{
+    on_cleanup,
+    runtime::{with_runtime, Runtime, RuntimeId},
+    RwSignal, Scope, SignalId, WriteSignal,
+};
+use std::{any::Any, fmt::Debug, rc::Rc};
+
+slotmap::new_key_type! {
+    /// Unique ID assigned to a signal write interceptor.
+    pub(crate) struct InterceptorId;
+}
+
+/// Describes a write to a signal. This is passed to every function registered with
+/// [intercept_signal_writes] after the signal’s value has changed, but before its
+/// subscribers are notified.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignalWrite {
+    /// The ID of the signal that was written to.
+    pub id: SignalId,
+    /// The name given to the signal with `with_debug_name()`, if any.
+    pub name: Option<String>,
+    /// The name of the signal’s type.
+    pub type_name: &'static str,
+    /// The [Debug] representation of the value before the write, if the signal
+    /// was given a name with `with_debug_name()`.
+    pub old: Option<String>,
+    /// The [Debug] representation of the value after the write, if the signal
+    /// was given a name with `with_debug_name()`.
+    pub new: Option<String>,
+}
+
+type DebugFormatter = fn(&dyn Any) -> Option<String>;
+
+pub(crate) struct SignalDebugInfo {
+    name: Option<String>,
+    format: Option<DebugFormatter>,
+    intercept: bool,
+}
+
+impl SignalDebugInfo {
+    /// The name given to the signal with `with_debug_name()`, if any.
+    pub(crate) fn name(&self) -> Option<String> {
+        self.name.clone()
+    }
+}
+
+impl Default for SignalDebugInfo {
+    fn default() -> Self {
+        Self {
+            name: None,
+            format: None,
+            intercept: true,
+        }
+    }
+}
+
+/// Registers a function that will be called whenever any signal in this [Scope]’s
+/// runtime is written to, e.g., for logging, persisting state, or developer tools.
+/// The function is removed when the [Scope] is disposed.
+///
+/// Signals don’t have to implement [Debug], so [SignalWrite] only includes the old
+/// and new values for signals that have opted in by calling `with_debug_name()`.
+/// Signals can opt out of being intercepted by calling `skip_interceptors()`. This
+/// is necessary for any signal that the interceptor itself writes to.
+///
+/// When no interceptors are registered, signal writes don’t do any extra work.
+/// Untracked writes, like [set_untracked](crate::UntrackedSettableSignal::set_untracked),
+/// are not intercepted.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let log = Rc::new(RefCell::new(Vec::new()));
+/// intercept_signal_writes(cx, {
+///     let log = Rc::clone(&log);
+///     move |write| {
+///         log.borrow_mut().push(format!(
+///             "{}: {} -> {}",
+///             write.name.as_deref().unwrap_or("?"),
+///             write.old.as_deref().unwrap_or("?"),
+///             write.new.as_deref().unwrap_or("?"),
+///         ))
+///     }
+/// });
+///
+/// let (count, set_count) = create_signal(cx, 0);
+/// let set_count = set_count.with_debug_name("count");
+/// let (_, set_secret) = create_signal(cx, "password");
+/// let set_secret = set_secret.skip_interceptors();
+///
+/// set_count.set(1);
+/// set_secret.set("hunter2");
+/// set_count.update(|n| *n += 1);
+///
+/// assert_eq!(*log.borrow(), vec!["count: 0 -> 1", "count: 1 -> 2"]);
+/// # }).dispose();
+/// ```
+pub fn intercept_signal_writes(cx: Scope, interceptor: impl Fn(&SignalWrite) + 'static) {
+    let id = with_runtime(cx.runtime, |runtime| {
+        runtime
+            .signal_write_interceptors
+            .borrow_mut()
+            .insert(Rc::new(interceptor))
+    });
+    on_cleanup(cx, move || {
+        with_runtime(cx.runtime, |runtime| {
+            runtime.signal_write_interceptors.borrow_mut().remove(id);
+        })
+    });
+}
+
+impl<T> WriteSignal<T>
+where
+    T: 'static,
+{
+    /// Gives the signal a name, and includes it and the signal’s old and new values
+    /// in the [SignalWrite] passed to interceptors registered with [intercept_signal_writes].
+    pub fn with_debug_name(self, name: impl Into<String>) -> Self
+    where
+        T: Debug,
+    {
+        set_debug_name::<T>(self.runtime, self.id, name.into());
+        self
+    }
+
+    /// Stops writes to this signal from being passed to interceptors registered with
+    /// [intercept_signal_writes].
+    pub fn skip_interceptors(self) -> Self {
+        skip_interceptors(self.runtime, self.id);
+        self
+    }
+}
+
+impl<T> RwSignal<T>
+where
+    T: 'static,
+{
+    /// Gives the signal a name, and includes it and the signal’s old and new values
+    /// in the [SignalWrite] passed to interceptors registered with [intercept_signal_writes].
+    pub fn with_debug_name(self, name: impl Into<String>) -> Self
+    where
+        T: Debug,
+    {
+        set_debug_name::<T>(self.runtime, self.id, name.into());
+        self
+    }
+
+    /// Stops writes to this signal from being passed to interceptors registered with
+    /// [intercept_signal_writes].
+    pub fn skip_interceptors(self) -> Self {
+        skip_interceptors(self.runtime, self.id);
+        self
+    }
+}
+
+fn set_debug_name<T>(runtime: RuntimeId, id: SignalId, name: String)
+where
+    T: Debug + 'static,
+{
+    with_debug_info(runtime, id, |info| {
+        info.name = Some(name);
+        info.format = Some(format_debug::<T>);
+    })
+}
+
+fn skip_interceptors(runtime: RuntimeId, id: SignalId) {
+    with_debug_info(runtime, id, |info| info.intercept = false)
+}
+
+fn with_debug_info(runtime: RuntimeId, id: SignalId, f: impl FnOnce(&mut SignalDebugInfo)) {
+    with_runtime(runtime, |runtime| {
+        if let Some(entry) = runtime.signal_debug_info.borrow_mut().entry(id) {
+            f(entry.or_default());
+        }
+    })
+}
+
+fn format_debug<T>(value: &dyn Any) -> Option<String>
+where
+    T: Debug + 'static,
+{
+    value.downcast_ref::<T>().map(|value| format!("{value:?}"))
+}
+
+/// A write that will be passed to interceptors once the signal has been updated.
+pub(crate) struct PendingWrite {
+    write: SignalWrite,
+    format: Option<DebugFormatter>,
+}
+
+impl PendingWrite {
+    /// Captures the signal’s current value, if any interceptors have been registered
+    /// and the signal hasn’t opted out.
+    pub(crate) fn new<T>(runtime: &Runtime, id: SignalId) -> Option<Self>
+    where
+        T: 'static,
+    {
+        if runtime.signal_write_interceptors.borrow().is_empty() {
+            return None;
+        }
+        let (name, format) = match runtime.signal_debug_info.borrow().get(id) {
+            Some(info) if !info.intercept => return None,
+            Some(info) => (info.name.clone(), info.format),
+            None => (None, None),
+        };
+        let old = format.and_then(|format| format_signal(runtime, id, format));
+        Some(Self {
+            write: SignalWrite {
+                id,
+                name,
+                type_name: std::any::type_name::<T>(),
+                old,
+                new: None,
+            },
+            format,
+        })
+    }
+
+    /// Calls each interceptor with the old and new values.
+    pub(crate) fn finish(self, runtime: &Runtime) {
+        let Self { mut write, format } = self;
+        write.new = format.and_then(|format| format_signal(runtime, write.id, format));
+
+        // clone the interceptors so they can register or remove interceptors themselves
+        let interceptors = runtime
+            .signal_write_interceptors
+            .borrow()
+            .values()
+            .cloned()
+            .collect::<Vec<_>>();
+        for interceptor in interceptors {
+            interceptor(&write);
+        }
+    }
+}
+
+fn format_signal(runtime: &Runtime, id: SignalId, format: DebugFormatter) -> Option<String> {
+    let value = runtime.signals.borrow().get(id).cloned()?;
+    let value = value.try_borrow().ok()?;
+    format(&*value)
+}
@@ -66,35 +66,61 @@
 //! });
 //! ```
 
+mod batch;
+mod bridge;
 mod context;
+mod diagnostics;
+mod diffed_signal;
 mod effect;
+mod effect_loop;
 mod hydration;
+mod interceptor;
 mod memo;
-
+mod owned_signal;
+mod paginated_resource;
+mod persisted_resource;
+mod refetch;
+mod render_tracker;
 mod resource;
 mod runtime;
 mod scope;
 mod selector;
 mod serialization;
+mod service;
 mod signal;
 mod signal_wrappers_read;
 mod signal_wrappers_write;
+mod slice;
 mod spawn;
 mod stored_value;
 mod suspense;
 
+pub use batch::*;
+pub use bridge::*;
 pub use context::*;
+pub use diagnostics::*;
+pub use diffed_signal::*;
 pub use effect::*;
+pub use effect_loop::set_effect_run_limit;
+pub use hydration::FragmentData;
+pub use interceptor::*;
 pub use memo::*;
+pub use owned_signal::*;
+pub use paginated_resource::*;
+pub use persisted_resource::*;
+pub use refetch::*;
+pub use render_tracker::*;
 pub use resource::*;
 use runtime::*;
-pub use runtime::{create_runtime, RuntimeId};
+pub use runtime::{create_runtime, create_scoped_runtime, RuntimeId};
 pub use scope::*;
 pub use selector::*;
 pub use serialization::*;
+pub use service::*;
 pub use signal::*;
 pub use signal_wrappers_read::*;
 pub use signal_wrappers_write::*;
+pub use slice::*;
 pub use spawn::*;
 pub use stored_value::*;
 pub use suspense::*;
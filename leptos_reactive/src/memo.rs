@@ -61,6 +61,93 @@ where
     cx.runtime.create_memo(f)
 }
 
+/// Creates a memo that uses the given function, rather than [PartialEq], to decide whether
+/// its value has changed. `is_equal` is called with the previous value and the new one, and
+/// dependents are only notified if it returns `false`.
+///
+/// This allows memoizing values that don't implement [PartialEq], or comparing them more
+/// cheaply or more loosely, e.g., floats that are within some epsilon of each other, or large
+/// collections that carry a version number.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (reading, set_reading) = create_signal(cx, 20.0_f64);
+///
+/// // ignores changes smaller than a tenth of a degree
+/// let temperature = create_memo_with_compare(
+///     cx,
+///     move |_| reading.get(),
+///     |prev, new| (prev - new).abs() < 0.1,
+/// );
+///
+/// let runs = Rc::new(Cell::new(0));
+/// create_isomorphic_effect(cx, {
+///     let runs = Rc::clone(&runs);
+///     move |_| {
+///         temperature.get();
+///         runs.set(runs.get() + 1);
+///     }
+/// });
+///
+/// set_reading.set(20.01);
+/// assert_eq!(temperature.get(), 20.0);
+/// assert_eq!(runs.get(), 1);
+///
+/// set_reading.set(20.5);
+/// assert_eq!(temperature.get(), 20.5);
+/// assert_eq!(runs.get(), 2);
+/// # }).dispose();
+/// ```
+pub fn create_memo_with_compare<T>(
+    cx: Scope,
+    f: impl Fn(Option<&T>) -> T + 'static,
+    is_equal: impl Fn(&T, &T) -> bool + 'static,
+) -> Memo<T>
+where
+    T: 'static,
+{
+    cx.runtime.create_memo_with_compare(f, is_equal)
+}
+
+/// Creates a memo whose function takes ownership of the previous value, so that it
+/// can be reused or mutated in place, rather than creating a new value and comparing
+/// it to the old one.
+///
+/// This is useful for large collections, which would be expensive to clone or
+/// compare. The memo function returns a tuple of the new value and whether it has
+/// changed; dependents are only notified if it has. Because the value doesn't need to
+/// be [PartialEq] or [Clone], it can be accessed with [Memo::with].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let (items, set_items) = create_signal(cx, vec![1, 2, 3, 4]);
+///
+/// // reuses the previous `Vec`'s allocation instead of creating a new one
+/// let evens = create_owning_memo(cx, move |prev: Option<Vec<i32>>| {
+///   let mut evens = prev.unwrap_or_default();
+///   let old_len = evens.len();
+///   evens.clear();
+///   items.with(|items| evens.extend(items.iter().filter(|n| *n % 2 == 0)));
+///   // in this example, the list of evens only grows
+///   let changed = evens.len() != old_len;
+///   (evens, changed)
+/// });
+///
+/// assert_eq!(evens.with(|evens| evens.len()), 2);
+/// set_items.update(|items| items.push(6));
+/// assert_eq!(evens.with(|evens| evens.to_vec()), vec![2, 4, 6]);
+/// # }).dispose();
+/// ```
+pub fn create_owning_memo<T>(cx: Scope, f: impl Fn(Option<T>) -> (T, bool) + 'static) -> Memo<T>
+where
+    T: 'static,
+{
+    cx.runtime.create_owning_memo(f)
+}
+
 /// An efficient derived reactive value based on other reactive values.
 ///
 /// Unlike a "derived signal," a memo comes with two guarantees:
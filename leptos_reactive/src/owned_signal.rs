@@ -0,0 +1,192 @@
+use crate::{create_rw_signal, on_cleanup, Scope, Signal};
+use std::{
+    cell::{Cell, RefCell},
+    fmt,
+    rc::{Rc, Weak},
+};
+
+/// A signal that owns its value, instead of storing it in a reactive [Scope].
+///
+/// Arena signals like [ReadSignal](crate::ReadSignal) are `Copy` handles into a [Scope],
+/// and stop working once it is disposed. An owned signal is reference-counted instead, so
+/// it can be created and used without a [Scope] or [Runtime](crate::RuntimeId), and lives
+/// as long as any clone of it does. This is useful for headless logic, like a form validator
+/// or a state machine in a library crate, that shouldn't depend on a scope being alive.
+///
+/// Owned signals are not tracked by effects or memos. Instead, code can
+/// [subscribe](OwnedSignal::subscribe) to changes, and components can read one reactively by
+/// converting it into a [Signal] with [OwnedSignal::to_signal].
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// // no scope needed
+/// let count = OwnedSignal::new(0);
+/// let changes = Rc::new(Cell::new(0));
+///
+/// let subscription = count.subscribe({
+///     let changes = Rc::clone(&changes);
+///     move || changes.set(changes.get() + 1)
+/// });
+///
+/// count.set(1);
+/// count.update(|n| *n += 1);
+/// assert_eq!(count.get(), 2);
+/// assert_eq!(changes.get(), 2);
+///
+/// // stops notifying once the subscription is dropped
+/// drop(subscription);
+/// count.set(3);
+/// assert_eq!(changes.get(), 2);
+/// ```
+pub struct OwnedSignal<T>(Rc<OwnedSignalInner<T>>);
+
+struct OwnedSignalInner<T> {
+    value: RefCell<T>,
+    next_id: Cell<usize>,
+    #[allow(clippy::type_complexity)]
+    subscribers: RefCell<Vec<(usize, Rc<dyn Fn()>)>>,
+}
+
+impl<T> Clone for OwnedSignal<T> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for OwnedSignal<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("OwnedSignal")
+            .field(&self.0.value.borrow())
+            .finish()
+    }
+}
+
+impl<T: Default> Default for OwnedSignal<T> {
+    fn default() -> Self {
+        Self::new(T::default())
+    }
+}
+
+impl<T> OwnedSignal<T> {
+    /// Creates an owned signal with the given value.
+    pub fn new(value: T) -> Self {
+        Self(Rc::new(OwnedSignalInner {
+            value: RefCell::new(value),
+            next_id: Cell::new(0),
+            subscribers: Default::default(),
+        }))
+    }
+
+    /// Clones and returns the current value.
+    pub fn get(&self) -> T
+    where
+        T: Clone,
+    {
+        self.0.value.borrow().clone()
+    }
+
+    /// Applies a function to the current value and returns the result.
+    pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> U {
+        f(&self.0.value.borrow())
+    }
+
+    /// Sets the value and notifies the subscribers.
+    pub fn set(&self, value: T) {
+        *self.0.value.borrow_mut() = value;
+        self.notify();
+    }
+
+    /// Updates the value in place and notifies the subscribers.
+    pub fn update(&self, f: impl FnOnce(&mut T)) {
+        f(&mut self.0.value.borrow_mut());
+        self.notify();
+    }
+
+    /// Calls `f` whenever the value changes, until the returned [OwnedSubscription] is
+    /// dropped.
+    pub fn subscribe(&self, f: impl Fn() + 'static) -> OwnedSubscription
+    where
+        T: 'static,
+    {
+        let id = self.0.next_id.get();
+        self.0.next_id.set(id + 1);
+        self.0.subscribers.borrow_mut().push((id, Rc::new(f)));
+
+        let inner = Rc::downgrade(&self.0);
+        OwnedSubscription(Some(Box::new(move || {
+            if let Some(inner) = Weak::upgrade(&inner) {
+                inner.subscribers.borrow_mut().retain(|(sub, _)| *sub != id);
+            }
+        })))
+    }
+
+    /// Converts this into a [Signal] in the given [Scope], which is updated whenever this
+    /// changes, so that components and effects can read it reactively. It stops being
+    /// updated when the scope is disposed.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let name = OwnedSignal::new("Alice".to_string());
+    /// let signal = name.to_signal(cx);
+    ///
+    /// name.set("Bob".to_string());
+    /// assert_eq!(signal.get(), "Bob");
+    /// # }).dispose();
+    /// ```
+    pub fn to_signal(&self, cx: Scope) -> Signal<T>
+    where
+        T: Clone + 'static,
+    {
+        let signal = create_rw_signal(cx, self.get());
+        let subscription = self.subscribe({
+            let this = Rc::downgrade(&self.0);
+            move || {
+                if let Some(this) = Weak::upgrade(&this) {
+                    signal.set(this.value.borrow().clone());
+                }
+            }
+        });
+        on_cleanup(cx, move || drop(subscription));
+        signal.read_only().into()
+    }
+
+    fn notify(&self) {
+        // cloned first, so that subscribers can subscribe or unsubscribe
+        let subscribers = self
+            .0
+            .subscribers
+            .borrow()
+            .iter()
+            .map(|(_, f)| Rc::clone(f))
+            .collect::<Vec<_>>();
+        for subscriber in subscribers {
+            subscriber();
+        }
+    }
+}
+
+/// A subscription to an [OwnedSignal], which stops when it is dropped.
+#[must_use = "the subscription stops as soon as it is dropped"]
+pub struct OwnedSubscription(Option<Box<dyn FnOnce()>>);
+
+impl OwnedSubscription {
+    /// Keeps the subscription for as long as the signal exists, instead of stopping it when
+    /// this is dropped.
+    pub fn forget(mut self) {
+        self.0.take();
+    }
+}
+
+impl fmt::Debug for OwnedSubscription {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OwnedSubscription").finish_non_exhaustive()
+    }
+}
+
+impl Drop for OwnedSubscription {
+    fn drop(&mut self) {
+        if let Some(unsubscribe) = self.0.take() {
+            unsubscribe();
+        }
+    }
+}
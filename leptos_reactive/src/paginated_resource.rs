@@ -0,0 +1,188 @@
+use crate::{
+    create_resource, create_rw_signal, serialization::Serializable, store_value, Resource,
+    RwSignal, Scope, ScopedTasks, StoredValue, UntrackedGettableSignal,
+};
+use std::{future::Future, pin::Pin, rc::Rc};
+
+/// Creates a [PaginatedResource], which loads a list one page at a time and
+/// accumulates the pages that have been loaded, e.g., for an infinite-scrolling feed.
+///
+/// The `fetch_page` function is called with the index of the page to load, starting
+/// at `0`. An empty page means that there are no more pages.
+///
+/// The first page is loaded like a [Resource](crate::Resource): it is read under
+/// `<Suspense/>`, and during server-side rendering it is loaded on the server and
+/// serialized to the client. Further pages are loaded on demand by calling
+/// [PaginatedResource::load_more].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn fetch_posts(page: usize) -> Vec<String> {
+///   // pretend we're calling an API
+///   if page < 3 { vec![format!("post {page}")] } else { vec![] }
+/// }
+///
+/// # // `csr`, `hydrate`, and `ssr` all have issues here
+/// # // because we're not running in a browser or in Tokio. Let's just ignore it.
+/// # if false {
+/// let posts = create_paginated_resource(cx, fetch_posts);
+///
+/// assert_eq!(posts.read(), Some(vec!["post 0".to_string()]));
+///
+/// // e.g., when the user scrolls to the bottom of the list
+/// posts.load_more();
+/// assert_eq!(posts.read(), Some(vec!["post 0".to_string(), "post 1".to_string()]));
+/// assert!(posts.has_more());
+/// # }
+/// # }).dispose();
+/// ```
+pub fn create_paginated_resource<T, Fu>(
+    cx: Scope,
+    fetch_page: impl Fn(usize) -> Fu + 'static,
+) -> PaginatedResource<T>
+where
+    T: Clone + 'static,
+    Vec<T>: Serializable,
+    Fu: Future<Output = Vec<T>> + 'static,
+{
+    let fetch_page =
+        Rc::new(move |page| Box::pin(fetch_page(page)) as Pin<Box<dyn Future<Output = Vec<T>>>>);
+    let first_page = create_resource(cx, || (), {
+        let fetch_page = Rc::clone(&fetch_page);
+        move |_| fetch_page(0)
+    });
+
+    PaginatedResource {
+        first_page,
+        more: create_rw_signal(cx, Vec::new()),
+        next_page: store_value(cx, 1),
+        generation: store_value(cx, 0),
+        reached_end: create_rw_signal(cx, false),
+        is_loading_more: create_rw_signal(cx, false),
+        fetch_page: store_value(cx, fetch_page),
+        tasks: store_value(cx, ScopedTasks::new(cx)),
+    }
+}
+
+/// A list that is loaded one page at a time. Created with [create_paginated_resource].
+pub struct PaginatedResource<T>
+where
+    T: 'static,
+{
+    first_page: Resource<(), Vec<T>>,
+    more: RwSignal<Vec<T>>,
+    next_page: StoredValue<usize>,
+    generation: StoredValue<usize>,
+    reached_end: RwSignal<bool>,
+    is_loading_more: RwSignal<bool>,
+    #[allow(clippy::type_complexity)]
+    fetch_page: StoredValue<Rc<dyn Fn(usize) -> Pin<Box<dyn Future<Output = Vec<T>>>>>>,
+    tasks: StoredValue<ScopedTasks>,
+}
+
+impl<T: Clone> Clone for PaginatedResource<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T: Clone> Copy for PaginatedResource<T> {}
+
+impl<T> std::fmt::Debug for PaginatedResource<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PaginatedResource")
+            .field("next_page", &self.next_page.get())
+            .finish()
+    }
+}
+
+impl<T> PaginatedResource<T>
+where
+    T: Clone + 'static,
+{
+    /// Clones and returns all the items loaded so far ([Option::None] if the first
+    /// page is still loading). Also subscribes the running effect to this resource.
+    pub fn read(&self) -> Option<Vec<T>> {
+        self.with(|items| items.to_vec())
+    }
+
+    /// Applies a function to all the items loaded so far, and subscribes the running
+    /// effect to this resource. If the first page hasn't loaded yet, the function won't
+    /// be called and this will return [Option::None].
+    pub fn with<U>(&self, f: impl FnOnce(&[T]) -> U) -> Option<U> {
+        let more = self.more;
+        self.first_page.with(|first| {
+            more.with(|more| {
+                if more.is_empty() {
+                    f(first)
+                } else {
+                    let items = first.iter().chain(more.iter()).cloned().collect::<Vec<_>>();
+                    f(&items)
+                }
+            })
+        })
+    }
+
+    /// Whether there may be more pages to load. This is `false` once an empty page
+    /// has been returned.
+    pub fn has_more(&self) -> bool {
+        let first_is_empty = self.first_page.with(|first| first.is_empty());
+        first_is_empty != Some(true) && !self.reached_end.get()
+    }
+
+    /// Whether a page is currently being loaded by [PaginatedResource::load_more].
+    pub fn is_loading_more(&self) -> bool {
+        self.is_loading_more.get()
+    }
+
+    /// Whether the first page is loading.
+    pub fn loading(&self) -> bool {
+        self.first_page.loading().get()
+    }
+
+    /// Loads the next page and appends it to the list. Does nothing if a page is
+    /// already loading, if the first page hasn't loaded yet, or if there are no more pages,
+    /// so it's safe to call this repeatedly, e.g., from a scroll handler.
+    pub fn load_more(&self) {
+        let first_loaded = self.first_page.with(|_| ()).is_some();
+        if !first_loaded || self.is_loading_more.get_untracked() || self.reached_end.get_untracked()
+        {
+            return;
+        }
+
+        let page = self.next_page.get();
+        let generation = self.generation.get();
+        let fut = self.fetch_page.with(|fetch_page| fetch_page(page));
+        let this = *self;
+
+        self.is_loading_more.set(true);
+        // dropped if the scope is disposed while the page is loading
+        self.tasks.with(|tasks| {
+            tasks.spawn(async move {
+                let items = fut.await;
+                // ignore pages that were requested before a reset
+                if this.generation.get() != generation {
+                    return;
+                }
+                if items.is_empty() {
+                    this.reached_end.set(true);
+                } else {
+                    this.next_page.set(page + 1);
+                    this.more.update(|more| more.extend(items));
+                }
+                this.is_loading_more.set(false);
+            });
+        });
+    }
+
+    /// Clears all the pages that have been loaded and refetches the first page.
+    pub fn reset(&self) {
+        self.generation.update(|n| *n += 1);
+        self.next_page.set(1);
+        self.more.set(Vec::new());
+        self.reached_end.set(false);
+        self.is_loading_more.set(false);
+        self.first_page.refetch();
+    }
+}
@@ -0,0 +1,366 @@
+use crate::{create_resource, serialization::Serializable, use_service, Resource, Scope};
+use serde::{Deserialize, Serialize};
+use std::{
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    fmt::Debug,
+    future::Future,
+    pin::Pin,
+    rc::Rc,
+    time::Duration,
+};
+
+/// A value stored in a [ResourceCache], along with when it was stored.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CachedValue {
+    /// The value, serialized as JSON with [Serializable].
+    pub json: String,
+    /// When the value was stored, in milliseconds since the Unix epoch.
+    pub stored_at: f64,
+}
+
+impl CachedValue {
+    /// Stores a value that was just fetched.
+    pub fn now(json: String) -> Self {
+        Self {
+            json,
+            stored_at: now_ms(),
+        }
+    }
+
+    /// Whether the value was stored less than `max_age` ago.
+    pub fn is_fresh(&self, max_age: Duration) -> bool {
+        now_ms() - self.stored_at <= max_age.as_millis() as f64
+    }
+}
+
+/// A client-side store for the values of [persisted resources](create_persisted_resource),
+/// so that they can be shown as soon as the app starts, or while it is offline.
+///
+/// Provide one at the root of the app with [provide_service]:
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::rc::Rc;
+/// # create_scope(create_runtime(), |cx| {
+/// # #[cfg(any(feature = "csr", feature = "hydrate"))]
+/// provide_service::<dyn ResourceCache>(cx, Rc::new(IndexedDbCache::new("my-app")));
+/// # #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+/// # provide_service::<dyn ResourceCache>(cx, Rc::new(MemoryCache::default()));
+/// # }).dispose();
+/// ```
+///
+/// [provide_service]: crate::provide_service
+pub trait ResourceCache {
+    /// Loads the value stored with the given key, if any.
+    fn get(&self, key: &str) -> Pin<Box<dyn Future<Output = Option<CachedValue>>>>;
+
+    /// Stores a value with the given key, replacing any value stored with it before.
+    fn set(&self, key: &str, value: CachedValue);
+
+    /// Removes the value stored with the given key.
+    fn remove(&self, key: &str);
+}
+
+/// A [ResourceCache] that keeps values in memory, e.g., for tests. Values are lost when the
+/// page is reloaded.
+/// ```
+/// # use leptos_reactive::*;
+/// let cache = MemoryCache::default();
+/// cache.set("user/1", CachedValue::now(r#""Alice""#.to_string()));
+///
+/// let value = futures::executor::block_on(cache.get("user/1")).unwrap();
+/// assert_eq!(value.json, r#""Alice""#);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryCache(Rc<RefCell<HashMap<String, CachedValue>>>);
+
+impl ResourceCache for MemoryCache {
+    fn get(&self, key: &str) -> Pin<Box<dyn Future<Output = Option<CachedValue>>>> {
+        let value = self.0.borrow().get(key).cloned();
+        Box::pin(async move { value })
+    }
+
+    fn set(&self, key: &str, value: CachedValue) {
+        self.0.borrow_mut().insert(key.to_string(), value);
+    }
+
+    fn remove(&self, key: &str) {
+        self.0.borrow_mut().remove(key);
+    }
+}
+
+/// Creates a [Resource](crate::Resource) whose values are persisted in the
+/// [ResourceCache] provided as a service, so that they can be read before they are fetched
+/// again when the app starts, and while the app is offline.
+///
+/// The `key` function turns the current value of the `source` into a string that
+/// identifies the value in the cache. The first time this resource loads a key, a value
+/// stored less than `max_age` ago is used instead of calling the `fetcher`; after that,
+/// [refetching](Resource::refetch) always calls the `fetcher`. While the browser is offline,
+/// a stored value is used whatever its age. Fetched values are stored with the time they
+/// were fetched.
+///
+/// Values that the server sends while the app is hydrated aren't fetched in the browser, so
+/// they aren't stored. If no cache has been provided (e.g., on the server), this is the same
+/// as [create_resource()].
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::time::Duration;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn fetch_user(id: u32) -> String {
+///   // pretend we're calling an API
+///   format!("user {id}")
+/// }
+///
+/// let (user_id, _) = create_signal(cx, 1);
+///
+/// # // `csr`, `hydrate`, and `ssr` all have issues here
+/// # // because we're not running in a browser or in Tokio. Let's just ignore it.
+/// # if false {
+/// let user = create_persisted_resource(
+///   cx,
+///   user_id,
+///   |id| format!("user/{id}"),
+///   fetch_user,
+///   Duration::from_secs(60 * 60),
+/// );
+/// # }
+/// # }).dispose();
+/// ```
+pub fn create_persisted_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    key: impl Fn(&S) -> String + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+    max_age: Duration,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let cache = use_service::<dyn ResourceCache>(cx);
+    // the keys this resource has already loaded, which are refetched rather than read
+    let loaded = Rc::new(RefCell::new(HashSet::new()));
+
+    create_resource(cx, source, move |source| {
+        let cache = cache.clone();
+        let key = key(&source);
+        let first_load = loaded.borrow_mut().insert(key.clone());
+        let fut = fetcher(source);
+
+        async move {
+            let cache = match cache {
+                Some(cache) => cache,
+                None => return fut.await,
+            };
+
+            if first_load || is_offline() {
+                let stored = cache
+                    .get(&key)
+                    .await
+                    .filter(|stored| is_offline() || stored.is_fresh(max_age));
+                if let Some(value) = stored.and_then(|stored| T::from_json(&stored.json).ok()) {
+                    return value;
+                }
+            }
+
+            let value = fut.await;
+            if let Ok(json) = value.to_json() {
+                cache.set(&key, CachedValue::now(json));
+            }
+            value
+        }
+    })
+}
+
+fn now_ms() -> f64 {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            js_sys::Date::now()
+        } else {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_millis() as f64)
+                .unwrap_or_default()
+        }
+    }
+}
+
+fn is_offline() -> bool {
+    cfg_if::cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            web_sys::window()
+                .map(|window| !window.navigator().on_line())
+                .unwrap_or(false)
+        } else {
+            false
+        }
+    }
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+pub use browser::*;
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+mod browser {
+    use super::{CachedValue, ResourceCache};
+    use crate::spawn_local;
+    use std::{future::Future, pin::Pin};
+    use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+    use wasm_bindgen_futures::JsFuture;
+    use web_sys::{IdbDatabase, IdbRequest, IdbTransactionMode};
+
+    /// The name of the object store that [IndexedDbCache] keeps values in.
+    const STORE: &str = "resources";
+
+    /// A [ResourceCache] that keeps values in the browser’s
+    /// [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage),
+    /// with keys that start with a prefix.
+    ///
+    /// `localStorage` is limited to a few megabytes per site; use [IndexedDbCache] for larger
+    /// values.
+    #[derive(Clone, Debug)]
+    pub struct LocalStorageCache {
+        prefix: String,
+    }
+
+    impl LocalStorageCache {
+        /// Creates a cache whose keys start with the given prefix, e.g., `"my-app:"`.
+        pub fn new(prefix: impl Into<String>) -> Self {
+            Self {
+                prefix: prefix.into(),
+            }
+        }
+
+        fn storage(&self) -> Option<web_sys::Storage> {
+            web_sys::window()?.local_storage().ok().flatten()
+        }
+    }
+
+    impl ResourceCache for LocalStorageCache {
+        fn get(&self, key: &str) -> Pin<Box<dyn Future<Output = Option<CachedValue>>>> {
+            let value = self
+                .storage()
+                .and_then(|storage| storage.get_item(&format!("{}{key}", self.prefix)).ok())
+                .flatten()
+                .and_then(|json| serde_json::from_str(&json).ok());
+            Box::pin(async move { value })
+        }
+
+        fn set(&self, key: &str, value: CachedValue) {
+            if let (Some(storage), Ok(json)) = (self.storage(), serde_json::to_string(&value)) {
+                _ = storage.set_item(&format!("{}{key}", self.prefix), &json);
+            }
+        }
+
+        fn remove(&self, key: &str) {
+            if let Some(storage) = self.storage() {
+                _ = storage.remove_item(&format!("{}{key}", self.prefix));
+            }
+        }
+    }
+
+    /// A [ResourceCache] that keeps values in an
+    /// [IndexedDB](https://developer.mozilla.org/en-US/docs/Web/API/IndexedDB_API) database
+    /// in the browser.
+    #[derive(Clone, Debug)]
+    pub struct IndexedDbCache {
+        database: String,
+    }
+
+    impl IndexedDbCache {
+        /// Creates a cache that keeps values in the database with the given name, which is
+        /// created if it doesn't exist.
+        pub fn new(database: impl Into<String>) -> Self {
+            Self {
+                database: database.into(),
+            }
+        }
+    }
+
+    impl ResourceCache for IndexedDbCache {
+        fn get(&self, key: &str) -> Pin<Box<dyn Future<Output = Option<CachedValue>>>> {
+            let database = self.database.clone();
+            let key = key.to_string();
+            Box::pin(async move {
+                let db = open(&database).await.ok()?;
+                let request = db
+                    .transaction_with_str(STORE)
+                    .and_then(|tx| tx.object_store(STORE))
+                    .and_then(|store| store.get(&JsValue::from_str(&key)))
+                    .ok()?;
+                let json = resolve(&request).await.ok()?.as_string()?;
+                serde_json::from_str(&json).ok()
+            })
+        }
+
+        fn set(&self, key: &str, value: CachedValue) {
+            let database = self.database.clone();
+            let key = key.to_string();
+            spawn_local(async move {
+                if let (Ok(db), Ok(json)) = (open(&database).await, serde_json::to_string(&value)) {
+                    _ = db
+                        .transaction_with_str_and_mode(STORE, IdbTransactionMode::Readwrite)
+                        .and_then(|tx| tx.object_store(STORE))
+                        .and_then(|store| {
+                            store.put_with_key(&JsValue::from_str(&json), &JsValue::from_str(&key))
+                        });
+                }
+            });
+        }
+
+        fn remove(&self, key: &str) {
+            let database = self.database.clone();
+            let key = key.to_string();
+            spawn_local(async move {
+                if let Ok(db) = open(&database).await {
+                    _ = db
+                        .transaction_with_str_and_mode(STORE, IdbTransactionMode::Readwrite)
+                        .and_then(|tx| tx.object_store(STORE))
+                        .and_then(|store| store.delete(&JsValue::from_str(&key)));
+                }
+            });
+        }
+    }
+
+    /// Opens the database, creating its object store if it doesn't exist yet.
+    async fn open(database: &str) -> Result<IdbDatabase, JsValue> {
+        let factory = web_sys::window()
+            .ok_or(JsValue::NULL)?
+            .indexed_db()?
+            .ok_or(JsValue::NULL)?;
+        let request = factory.open_with_u32(database, 1)?;
+        let on_upgrade = Closure::once_into_js({
+            let request = request.clone();
+            move || {
+                if let Ok(db) = request.result() {
+                    _ = db
+                        .unchecked_into::<IdbDatabase>()
+                        .create_object_store(STORE);
+                }
+            }
+        });
+        request.set_onupgradeneeded(Some(on_upgrade.unchecked_ref()));
+        Ok(resolve(&request).await?.unchecked_into())
+    }
+
+    /// Waits for an IndexedDB request to succeed, and returns its result.
+    async fn resolve(request: &IdbRequest) -> Result<JsValue, JsValue> {
+        let promise = js_sys::Promise::new(&mut |resolve, reject| {
+            let on_success = Closure::once_into_js({
+                let request = request.clone();
+                move || {
+                    let result = request.result().unwrap_or(JsValue::UNDEFINED);
+                    _ = resolve.call1(&JsValue::NULL, &result);
+                }
+            });
+            let on_error = Closure::once_into_js(move || {
+                _ = reject.call1(&JsValue::NULL, &JsValue::NULL);
+            });
+            request.set_onsuccess(Some(on_success.unchecked_ref()));
+            request.set_onerror(Some(on_error.unchecked_ref()));
+        });
+        JsFuture::from(promise).await
+    }
+}
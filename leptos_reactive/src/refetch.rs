@@ -0,0 +1,74 @@
+use crate::{on_cleanup, store_value, ResourceId, Scope, StoredValue};
+use std::{collections::HashMap, fmt, rc::Rc};
+
+/// Tracks the [Resource](crate::Resource)s that are created under a refetch context,
+/// i.e., within an [`ErrorBoundary`](https://docs.rs/leptos/latest/leptos/fn.ErrorBoundary.html),
+/// so that they can all be refetched at once, e.g., when the user tries again after
+/// one of them failed to load.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// let refetch_cx = RefetchContext::new(cx);
+/// provide_context(cx, refetch_cx);
+///
+/// let post = create_resource(cx, || (), |_| async { Err::<String, _>("timed out") });
+///
+/// // refetches `post`, and any other resource created in this scope or its children
+/// refetch_cx.refetch();
+/// # }
+/// # }).dispose();
+/// ```
+#[derive(Copy, Clone)]
+pub struct RefetchContext {
+    #[allow(clippy::type_complexity)]
+    refetchers: StoredValue<HashMap<ResourceId, Rc<dyn Fn()>>>,
+}
+
+impl fmt::Debug for RefetchContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RefetchContext")
+            .field(
+                "resources",
+                &self
+                    .refetchers
+                    .try_with(|refetchers| refetchers.len())
+                    .unwrap_or_default(),
+            )
+            .finish()
+    }
+}
+
+impl RefetchContext {
+    /// Creates an empty refetch context.
+    pub fn new(cx: Scope) -> Self {
+        Self {
+            refetchers: store_value(cx, HashMap::new()),
+        }
+    }
+
+    /// Refetches every resource that was created under this context, and that hasn't
+    /// been disposed yet.
+    pub fn refetch(&self) {
+        // cloned first, so that resources created while refetching can be registered
+        let refetchers = self
+            .refetchers
+            .try_with(|refetchers| refetchers.values().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        for refetch in refetchers {
+            refetch();
+        }
+    }
+
+    /// Adds a resource, which is removed again when the scope it was created in is disposed.
+    pub(crate) fn register(&self, cx: Scope, id: ResourceId, refetch: impl Fn() + 'static) {
+        self.refetchers.update(|refetchers| {
+            refetchers.insert(id, Rc::new(refetch));
+        });
+
+        let refetchers = self.refetchers;
+        on_cleanup(cx, move || {
+            refetchers.try_update(|refetchers| refetchers.remove(&id));
+        });
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{
+    on_cleanup, provide_context,
+    runtime::{with_runtime, Runtime},
+    EffectId, Scope, ScopeId, SignalId,
+};
+use std::{cell::RefCell, collections::VecDeque, fmt, rc::Rc};
+
+/// The most renders a [RenderTracker] keeps in its log.
+const MAX_RENDERS: usize = 1000;
+
+/// A signal whose change caused an effect to run again.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SignalTrigger {
+    /// The ID of the signal.
+    pub id: SignalId,
+    /// The name given to the signal with `with_debug_name()`, if any.
+    pub name: Option<String>,
+}
+
+impl fmt::Display for SignalTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.name {
+            Some(name) => write!(f, "`{name}`"),
+            None => write!(f, "an unnamed signal ({:?})", self.id),
+        }
+    }
+}
+
+/// Why an effect, like the one that renders a dynamic part of a view, ran.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum RenderReason {
+    /// The effect ran for the first time.
+    Initial,
+    /// A signal that the effect depends on changed. The first trigger is the signal the
+    /// effect read; each one after it is the signal whose change caused the one before
+    /// it to be written to, e.g., the signal a memo was derived from.
+    Changed(Vec<SignalTrigger>),
+    /// The effect was run by something other than a signal, like a resource.
+    Other,
+}
+
+impl fmt::Display for RenderReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Initial => f.write_str("first render"),
+            Self::Changed(triggers) => {
+                for (idx, trigger) in triggers.iter().enumerate() {
+                    if idx > 0 {
+                        f.write_str(", because ")?;
+                    }
+                    write!(f, "{trigger} changed")?;
+                }
+                Ok(())
+            }
+            Self::Other => f.write_str("run without a signal change"),
+        }
+    }
+}
+
+/// One run of an effect in a component, recorded by a [RenderTracker].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Render {
+    /// The path of components that the effect was created in, from the outermost to the
+    /// innermost, like `"App > TodoList > Todo"`.
+    pub component: String,
+    /// The scope of the innermost component, which identifies the component instance.
+    pub instance: ScopeId,
+    /// Why the effect ran.
+    pub reason: RenderReason,
+}
+
+impl fmt::Display for Render {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} rendered: {}", self.component, self.reason)
+    }
+}
+
+/// How many times the effects of one component instance have run.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RenderCount {
+    /// The path of components, like `"App > TodoList > Todo"`.
+    pub component: String,
+    /// The scope of the component instance.
+    pub instance: ScopeId,
+    /// How many times its effects have run, including the first time.
+    pub renders: usize,
+}
+
+/// Counts how many times each component instance renders, and records why each effect in it
+/// ran, to answer the question “why did this re-render?” Created with [track_renders].
+///
+/// A component renders once when it is created; after that, only the effects inside it
+/// run again, like those that update a dynamic child or attribute. Each run of one of those
+/// effects counts as a render of the component it was created in, and is recorded along with
+/// the signal that triggered it. Memos aren't counted, but when a memo triggers an effect,
+/// the signal the memo was derived from is included in the [RenderReason].
+///
+/// Components and reasons are only recorded in debug builds.
+#[derive(Clone, Default)]
+pub struct RenderTracker {
+    inner: Rc<RenderTrackerInner>,
+}
+
+#[derive(Default)]
+struct RenderTrackerInner {
+    renders: RefCell<VecDeque<Render>>,
+    counts: RefCell<Vec<RenderCount>>,
+    #[allow(clippy::type_complexity)]
+    listeners: RefCell<Vec<Rc<dyn Fn(&Render)>>>,
+}
+
+impl fmt::Debug for RenderTracker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RenderTracker")
+            .field("counts", &self.inner.counts.borrow())
+            .finish_non_exhaustive()
+    }
+}
+
+impl PartialEq for RenderTracker {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.inner, &other.inner)
+    }
+}
+
+impl Eq for RenderTracker {}
+
+impl RenderTracker {
+    /// The most recent renders, oldest first.
+    pub fn renders(&self) -> Vec<Render> {
+        self.inner.renders.borrow().iter().cloned().collect()
+    }
+
+    /// How many times each component instance has rendered, in the order in which they
+    /// first rendered.
+    pub fn counts(&self) -> Vec<RenderCount> {
+        self.inner.counts.borrow().clone()
+    }
+
+    /// How many times all instances of the component with the given name have rendered.
+    pub fn count(&self, component: &str) -> usize {
+        self.inner
+            .counts
+            .borrow()
+            .iter()
+            .filter(|count| count.component.rsplit(" > ").next() == Some(component))
+            .map(|count| count.renders)
+            .sum()
+    }
+
+    /// Calls `f` with each render once it has been recorded, e.g., to show it in developer
+    /// tools. `f` is called before the effect runs.
+    pub fn on_render(&self, f: impl Fn(&Render) + 'static) {
+        self.inner.listeners.borrow_mut().push(Rc::new(f));
+    }
+
+    /// Clears the renders and counts recorded so far.
+    pub fn clear(&self) {
+        self.inner.renders.borrow_mut().clear();
+        self.inner.counts.borrow_mut().clear();
+    }
+
+    fn record(&self, render: Render) {
+        {
+            let mut counts = self.inner.counts.borrow_mut();
+            match counts
+                .iter_mut()
+                .find(|count| count.instance == render.instance)
+            {
+                Some(count) => count.renders += 1,
+                None => counts.push(RenderCount {
+                    component: render.component.clone(),
+                    instance: render.instance,
+                    renders: 1,
+                }),
+            }
+        }
+        {
+            let mut renders = self.inner.renders.borrow_mut();
+            if renders.len() == MAX_RENDERS {
+                renders.pop_front();
+            }
+            renders.push_back(render.clone());
+        }
+
+        // cloned so that listeners can register other listeners
+        let listeners = self.inner.listeners.borrow().clone();
+        for listener in listeners {
+            listener(&render);
+        }
+    }
+}
+
+/// Starts counting the renders of every component in this [Scope]’s runtime into a
+/// [RenderTracker], until the scope is disposed. The tracker is also provided as a context.
+///
+/// Only one tracker is active in each runtime; calling this again replaces it.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// let renders = track_renders(cx);
+///
+/// let (count, set_count) = create_signal(cx, 0);
+/// let set_count = set_count.with_debug_name("count");
+/// _ = cx.child_scope(|cx| {
+///     set_component_name(cx, "Counter");
+///     create_isomorphic_effect(cx, move |_| count.get());
+/// });
+///
+/// set_count.set(1);
+///
+/// # if cfg!(debug_assertions) {
+/// assert_eq!(renders.count("Counter"), 2);
+/// let last = renders.renders().pop().unwrap();
+/// assert_eq!(last.to_string(), "Counter rendered: `count` changed");
+/// # }
+/// # }).dispose();
+/// ```
+pub fn track_renders(cx: Scope) -> RenderTracker {
+    let tracker = RenderTracker::default();
+    with_runtime(cx.runtime, |runtime| {
+        *runtime.render_tracker.borrow_mut() = Some(tracker.clone())
+    });
+    on_cleanup(cx, {
+        let tracker = tracker.clone();
+        move || {
+            with_runtime(cx.runtime, |runtime| {
+                let mut current = runtime.render_tracker.borrow_mut();
+                if current.as_ref() == Some(&tracker) {
+                    *current = None;
+                }
+            })
+        }
+    });
+    provide_context(cx, tracker.clone());
+    tracker
+}
+
+/// Returns the [RenderTracker] that is counting renders in this [Scope]’s runtime, if
+/// [track_renders] has been called.
+pub fn use_render_tracker(cx: Scope) -> Option<RenderTracker> {
+    with_runtime(cx.runtime, |runtime| {
+        runtime.render_tracker.borrow().clone()
+    })
+}
+
+/// Returns why the effect that is currently running ran, or `None` outside an effect or in
+/// release builds.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::RefCell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// let (name, set_name) = create_signal(cx, "Alice");
+/// let set_name = set_name.with_debug_name("name");
+/// let reasons = Rc::new(RefCell::new(Vec::new()));
+///
+/// create_isomorphic_effect(cx, {
+///     let reasons = Rc::clone(&reasons);
+///     move |_| {
+///         name.get();
+///         if let Some(reason) = render_reason(cx) {
+///             reasons.borrow_mut().push(reason.to_string());
+///         }
+///     }
+/// });
+/// set_name.set("Bob");
+///
+/// # if cfg!(debug_assertions) {
+/// assert_eq!(*reasons.borrow(), ["first render", "`name` changed"]);
+/// # }
+/// # }).dispose();
+/// ```
+pub fn render_reason(cx: Scope) -> Option<RenderReason> {
+    with_runtime(cx.runtime, |runtime| runtime.render_reason.borrow().clone())
+}
+
+impl Runtime {
+    /// Works out why an effect is about to run, records it if the effect belongs to a
+    /// component and renders are being tracked, and makes it available to [render_reason].
+    /// Returns the reason of the effect that was running before, to restore afterward.
+    pub(crate) fn begin_render(&self, effect: EffectId, initial: bool) -> Option<RenderReason> {
+        let reason = if initial {
+            RenderReason::Initial
+        } else {
+            let triggers = self.render_triggers.borrow();
+            if triggers.is_empty() {
+                RenderReason::Other
+            } else {
+                let debug_info = self.signal_debug_info.borrow();
+                RenderReason::Changed(
+                    triggers
+                        .iter()
+                        .rev()
+                        .map(|id| SignalTrigger {
+                            id: *id,
+                            name: debug_info.get(*id).and_then(|info| info.name()),
+                        })
+                        .collect(),
+                )
+            }
+        };
+
+        let tracker = self.render_tracker.borrow().clone();
+        if let Some(tracker) = tracker {
+            let scope = self.effect_scopes.borrow().get(effect).copied();
+            let instance = scope.and_then(|scope| self.component_scope(scope));
+            let component = scope.and_then(|scope| self.component_path(scope));
+            if let (Some(instance), Some(component)) = (instance, component) {
+                tracker.record(Render {
+                    component,
+                    instance,
+                    reason: reason.clone(),
+                });
+            }
+        }
+
+        self.render_reason.replace(Some(reason))
+    }
+}
@@ -1,19 +1,24 @@
 use std::{
-    any::Any,
+    any::{Any, TypeId},
+    borrow::Cow,
     cell::{Cell, RefCell},
     collections::HashSet,
+    convert::Infallible,
     fmt::Debug,
     future::Future,
     marker::PhantomData,
     pin::Pin,
     rc::Rc,
+    task::{Context, Poll, Waker},
 };
+use futures::future::{select, Either, FutureExt, Shared};
 use crate::{
     create_effect, create_isomorphic_effect, create_memo, create_signal, queue_microtask,
     runtime::{with_runtime, RuntimeId},
-    serialization::Serializable,
-    spawn::spawn_local,
-    use_context, Memo, ReadSignal, Scope, ScopeProperty, SuspenseContext, WriteSignal,
+    serialization::{resource_to_json, Serializable, SerializationError},
+    spawn::{ScopedTasks, TaskHandle},
+    use_context, Memo, ReadSignal, RefetchContext, Scope, ScopeProperty, Signal, SuspenseContext,
+    WriteSignal,
 };
 
 /// Creates [Resource](crate::Resource), which is a signal that reflects the
@@ -93,16 +98,79 @@ where
     S: PartialEq + Debug + Clone + 'static,
     T: Serializable + 'static,
     Fu: Future<Output = T> + 'static,
+{
+    let fetcher = Rc::new(move |s: S, _: Superseded| {
+        Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>
+    });
+    serializable_resource(cx, source, fetcher, initial_value)
+}
+
+/// Creates a [Resource](crate::Resource) whose `fetcher` is told when its
+/// [Future] is no longer needed.
+///
+/// Every resource only keeps the result of its latest fetch: when the `source`
+/// changes, or the resource is [refetched](Resource::refetch), while a [Future]
+/// is still pending, that [Future] is dropped without setting the resource.
+/// Dropping a [Future] doesn't stop work that it has already started, like an
+/// HTTP request in the browser. This `fetcher` also receives a [Superseded],
+/// which resolves as soon as a newer fetch starts, so it can cancel that work,
+/// e.g., by aborting an
+/// [`AbortController`](https://developer.mozilla.org/en-US/docs/Web/API/AbortController).
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn search(query: String, superseded: Superseded) -> Vec<String> {
+///   // start a request, and cancel it if `superseded` resolves first
+///   vec![query]
+/// }
+///
+/// let (query, set_query) = create_signal(cx, String::new());
+///
+/// # if false {
+/// let results = create_cancellable_resource(cx, query, search);
+///
+/// // only the results for the last query are ever shown
+/// set_query.set("lep".to_string());
+/// set_query.set("leptos".to_string());
+/// assert_eq!(results.read(), Some(vec!["leptos".to_string()]));
+/// # }
+/// # }).dispose();
+/// ```
+pub fn create_cancellable_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S, Superseded) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let fetcher = Rc::new(move |s: S, superseded: Superseded| {
+        Box::pin(fetcher(s, superseded)) as Pin<Box<dyn Future<Output = T>>>
+    });
+    serializable_resource(cx, source, fetcher, None)
+}
+
+fn serializable_resource<S, T>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: Fetcher<S, T>,
+    initial_value: Option<T>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + 'static,
 {
     let resolved = initial_value.is_some();
     let (value, set_value) = create_signal(cx, initial_value);
 
     let (loading, set_loading) = create_signal(cx, false);
 
-    let fetcher = Rc::new(move |s| Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>);
     let source = create_memo(cx, move |_| source());
 
-    let r = Rc::new(ResourceState {
+    let r = Rc::new(ResourceInner {
         scope: cx,
         value,
         set_value,
@@ -113,11 +181,20 @@ where
         resolved: Rc::new(Cell::new(resolved)),
         scheduled: Rc::new(Cell::new(false)),
         suspense_contexts: Default::default(),
+        blocking: Default::default(),
+        id: Default::default(),
+        debug_name: Default::default(),
+        pending_fetch: Default::default(),
+        tasks: ScopedTasks::new(cx),
+        serialization_error: Default::default(),
+        state: resource_state(cx, value, loading),
+        result_state: Default::default(),
     });
 
     let id = with_runtime(cx.runtime, |runtime| {
         runtime.create_serializable_resource(Rc::clone(&r))
     });
+    r.id.set(id);
 
     create_isomorphic_effect(cx, {
         let r = Rc::clone(&r);
@@ -128,6 +205,10 @@ where
 
     cx.with_scope_property(|prop| prop.push(ScopeProperty::Resource(id)));
 
+    if let Some(refetch_cx) = use_context::<RefetchContext>(cx) {
+        refetch_cx.register(cx, id, move || r.refetch());
+    }
+
     Resource {
         runtime: cx.runtime,
         id,
@@ -136,6 +217,177 @@ where
     }
 }
 
+/// Creates a [Resource](crate::Resource) whose requests are deduplicated by key.
+///
+/// The `key` function turns the current value of the `source` into a string that
+/// identifies the request. If another keyed resource with the same output type is
+/// already waiting on a request with the same key (for example, because two
+/// components load the same user in the same tick), this resource shares that
+/// pending [Future] instead of calling the `fetcher` again, and both resources
+/// receive a clone of the result. Once the request resolves, the next fetch with that
+/// key starts a new request.
+///
+/// Resources created with [create_resource()] are never deduplicated, so use that
+/// instead if you intentionally want to fetch the same data more than once.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn fetch_user(id: u32) -> String {
+///   // pretend we're calling an API
+///   format!("user {id}")
+/// }
+///
+/// let (user_id, _) = create_signal(cx, 1);
+///
+/// # // `csr`, `hydrate`, and `ssr` all have issues here
+/// # // because we're not running in a browser or in Tokio. Let's just ignore it.
+/// # if false {
+/// // e.g., in a header component and a sidebar component
+/// // only one request is made while both are loading
+/// let header_user =
+///   create_keyed_resource(cx, user_id, |id| format!("user/{id}"), fetch_user);
+/// let sidebar_user =
+///   create_keyed_resource(cx, user_id, |id| format!("user/{id}"), fetch_user);
+/// assert_eq!(header_user(), sidebar_user());
+/// # }
+/// # }).dispose();
+/// ```
+pub fn create_keyed_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    key: impl Fn(&S) -> String + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + Clone + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    type SharedRequest<T> = Shared<Pin<Box<dyn Future<Output = T>>>>;
+
+    let runtime = cx.runtime;
+    create_resource(cx, source, move |source| {
+        let key = (TypeId::of::<T>(), key(&source));
+
+        let pending = with_runtime(runtime, |runtime| {
+            runtime
+                .in_flight_requests
+                .borrow()
+                .get(&key)
+                .cloned()
+                .and_then(|request| request.downcast::<SharedRequest<T>>().ok())
+        });
+        let request = pending.unwrap_or_else(|| {
+            let fut = Box::pin(fetcher(source)) as Pin<Box<dyn Future<Output = T>>>;
+            let request = Rc::new(fut.shared());
+            with_runtime(runtime, |runtime| {
+                runtime
+                    .in_flight_requests
+                    .borrow_mut()
+                    .insert(key.clone(), Rc::clone(&request) as Rc<dyn Any>)
+            });
+            request
+        });
+
+        async move {
+            let res = (*request).clone().await;
+
+            // the first resource to resolve clears the request, unless it has already
+            // been replaced by a newer one with the same key
+            with_runtime(runtime, |runtime| {
+                let mut in_flight = runtime.in_flight_requests.borrow_mut();
+                if in_flight.get(&key).map(|current| {
+                    std::ptr::eq(
+                        Rc::as_ptr(current) as *const (),
+                        Rc::as_ptr(&request) as *const (),
+                    )
+                }) == Some(true)
+                {
+                    in_flight.remove(&key);
+                }
+            });
+
+            res
+        }
+    })
+}
+
+/// Creates a _blocking_ [Resource](crate::Resource), whose value is always resolved
+/// on the server before the application shell is sent.
+///
+/// By default, server-side rendering sends the shell as soon as possible, with the
+/// `fallback` of each `<Suspense/>`, and streams in the rest as resources resolve.
+/// Some data needs to be available before anything is sent: the response status code
+/// and headers, or a `<title>` or canonical URL in the `<head>`. When streaming HTML,
+/// the server waits for every blocking resource (and every `<Suspense/>` that reads
+/// one) before sending the shell, while other resources continue to stream.
+///
+/// In the browser, this is the same as [create_resource()].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// async fn fetch_post_title(id: u32) -> String {
+///   // pretend we're calling an API
+///   format!("Post #{id}")
+/// }
+///
+/// let (post_id, _) = create_signal(cx, 1);
+///
+/// # // `csr`, `hydrate`, and `ssr` all have issues here
+/// # // because we're not running in a browser or in Tokio. Let's just ignore it.
+/// # if false {
+/// // e.g., used to set the page's <title>
+/// let title = create_blocking_resource(cx, post_id, fetch_post_title);
+/// # }
+/// # }).dispose();
+/// ```
+pub fn create_blocking_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: Serializable + 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let resource = create_resource(cx, source, fetcher);
+    let value = with_runtime(cx.runtime, |runtime| {
+        runtime.resource(resource.id, |state: &ResourceInner<S, T>| {
+            state.blocking.set(true);
+            state.value
+        })
+    });
+
+    // tell the renderer to wait for this resource before sending the shell
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    {
+        use futures::StreamExt;
+
+        let (tx, mut rx) = futures::channel::mpsc::unbounded();
+        create_isomorphic_effect(cx, move |_| {
+            if value.with(Option::is_some) {
+                _ = tx.unbounded_send(());
+            }
+        });
+        with_runtime(cx.runtime, |runtime| {
+            runtime
+                .shared_context
+                .borrow_mut()
+                .blocking_resources
+                .push(Box::pin(async move {
+                    rx.next().await;
+                }))
+        });
+    }
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    _ = value;
+
+    resource
+}
+
 /// Creates a _local_ [Resource](crate::Resource), which is a signal that
 /// reflects the current state of an asynchronous task, allowing you to
 /// integrate `async` [Future]s into the synchronous reactive system.
@@ -197,16 +449,53 @@ where
     S: PartialEq + Debug + Clone + 'static,
     T: 'static,
     Fu: Future<Output = T> + 'static,
+{
+    let fetcher = Rc::new(move |s: S, _: Superseded| {
+        Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>
+    });
+    local_resource(cx, source, fetcher, initial_value)
+}
+
+/// Creates a _local_ [Resource](crate::Resource) whose `fetcher` is told when
+/// its [Future] is no longer needed, like [create_cancellable_resource()].
+///
+/// Unlike [create_cancellable_resource()], this [Future] will always run on the
+/// local system and therefore its output type does not need to be
+/// [Serializable].
+pub fn create_local_cancellable_resource<S, T, Fu>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: impl Fn(S, Superseded) -> Fu + 'static,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: 'static,
+    Fu: Future<Output = T> + 'static,
+{
+    let fetcher = Rc::new(move |s: S, superseded: Superseded| {
+        Box::pin(fetcher(s, superseded)) as Pin<Box<dyn Future<Output = T>>>
+    });
+    local_resource(cx, source, fetcher, None)
+}
+
+fn local_resource<S, T>(
+    cx: Scope,
+    source: impl Fn() -> S + 'static,
+    fetcher: Fetcher<S, T>,
+    initial_value: Option<T>,
+) -> Resource<S, T>
+where
+    S: PartialEq + Debug + Clone + 'static,
+    T: 'static,
 {
     let resolved = initial_value.is_some();
     let (value, set_value) = create_signal(cx, initial_value);
 
     let (loading, set_loading) = create_signal(cx, false);
 
-    let fetcher = Rc::new(move |s| Box::pin(fetcher(s)) as Pin<Box<dyn Future<Output = T>>>);
     let source = create_memo(cx, move |_| source());
 
-    let r = Rc::new(ResourceState {
+    let r = Rc::new(ResourceInner {
         scope: cx,
         value,
         set_value,
@@ -217,11 +506,20 @@ where
         resolved: Rc::new(Cell::new(resolved)),
         scheduled: Rc::new(Cell::new(false)),
         suspense_contexts: Default::default(),
+        blocking: Default::default(),
+        id: Default::default(),
+        debug_name: Default::default(),
+        pending_fetch: Default::default(),
+        tasks: ScopedTasks::new(cx),
+        serialization_error: Default::default(),
+        state: resource_state(cx, value, loading),
+        result_state: Default::default(),
     });
 
     let id = with_runtime(cx.runtime, |runtime| {
         runtime.create_unserializable_resource(Rc::clone(&r))
     });
+    r.id.set(id);
 
     create_effect(cx, {
         let r = Rc::clone(&r);
@@ -232,6 +530,10 @@ where
 
     cx.with_scope_property(|prop| prop.push(ScopeProperty::Resource(id)));
 
+    if let Some(refetch_cx) = use_context::<RefetchContext>(cx) {
+        refetch_cx.register(cx, id, move || r.refetch());
+    }
+
     Resource {
         runtime: cx.runtime,
         id,
@@ -241,7 +543,7 @@ where
 }
 
 #[cfg(not(feature = "hydrate"))]
-fn load_resource<S, T>(_cx: Scope, _id: ResourceId, r: Rc<ResourceState<S, T>>)
+fn load_resource<S, T>(_cx: Scope, _id: ResourceId, r: Rc<ResourceInner<S, T>>)
 where
     S: PartialEq + Debug + Clone + 'static,
     T: 'static,
@@ -250,11 +552,12 @@ where
 }
 
 #[cfg(feature = "hydrate")]
-fn load_resource<S, T>(cx: Scope, id: ResourceId, r: Rc<ResourceState<S, T>>)
+fn load_resource<S, T>(cx: Scope, id: ResourceId, r: Rc<ResourceInner<S, T>>)
 where
     S: PartialEq + Debug + Clone + 'static,
     T: Serializable + 'static,
 {
+    use crate::serialization::resource_from_json;
     use wasm_bindgen::{JsCast, UnwrapThrowExt};
 
     with_runtime(cx.runtime, |runtime| {
@@ -265,9 +568,10 @@ where
             context.pending_resources.remove(&id); // no longer pending
             r.resolved.set(true);
 
-            let res = T::from_json(&data).expect_throw("could not deserialize Resource JSON");
-
-            r.set_value.update(|n| *n = Some(res));
+            match resource_from_json::<T>(&data) {
+                Ok(res) => r.set_value.update(|n| *n = Some(res)),
+                Err(e) => *r.serialization_error.borrow_mut() = Some(e),
+            }
             r.set_loading.update(|n| *n = false);
 
             // for reactivity
@@ -282,11 +586,13 @@ where
                 let resolved = r.resolved.clone();
                 let set_value = r.set_value;
                 let set_loading = r.set_loading;
+                let serialization_error = Rc::clone(&r.serialization_error);
                 move |res: String| {
-                    let res =
-                        T::from_json(&res).expect_throw("could not deserialize Resource JSON");
                     resolved.set(true);
-                    set_value.update(|n| *n = Some(res));
+                    match resource_from_json::<T>(&res) {
+                        Ok(res) => set_value.update(|n| *n = Some(res)),
+                        Err(e) => *serialization_error.borrow_mut() = Some(e),
+                    }
                     set_loading.update(|n| *n = false);
                 }
             };
@@ -330,7 +636,7 @@ where
         T: Clone,
     {
         with_runtime(self.runtime, |runtime| {
-            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.read())
+            runtime.resource(self.id, |resource: &ResourceInner<S, T>| resource.read())
         })
     }
 
@@ -343,21 +649,70 @@ where
     /// [Resource::read].
     pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
         with_runtime(self.runtime, |runtime| {
-            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.with(f))
+            runtime.resource(self.id, |resource: &ResourceInner<S, T>| resource.with(f))
         })
     }
 
     /// Returns a signal that indicates whether the resource is currently loading.
     pub fn loading(&self) -> ReadSignal<bool> {
         with_runtime(self.runtime, |runtime| {
-            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.loading)
+            runtime.resource(self.id, |resource: &ResourceInner<S, T>| resource.loading)
         })
     }
 
+    /// Returns a signal with the [ResourceState] of the resource, which distinguishes a
+    /// first load from a reload of a resource that already has a value.
+    ///
+    /// For a resource that resolves to a [Result], [Resource::result_state] also reports
+    /// errors.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let (id, set_id) = create_signal(cx, 1);
+    /// let user = create_resource(cx, id, |id| async move { format!("user {id}") });
+    ///
+    /// let message = move || match user.state().get() {
+    ///     ResourceState::Idle | ResourceState::Loading => "Loading…",
+    ///     // keep showing the previous user while the next one loads
+    ///     ResourceState::Reloading => "Refreshing…",
+    ///     ResourceState::Resolved => "Loaded",
+    ///     ResourceState::Errored(never) => match never {},
+    /// };
+    /// # }
+    /// # }).dispose();
+    /// ```
+    pub fn state(&self) -> Signal<ResourceState> {
+        with_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceInner<S, T>| resource.state)
+        })
+    }
+
+    /// Gives the resource a name that's shown by devtools, and by fallbacks that list what a
+    /// `<Suspense/>` is waiting for with [SuspenseContext::pending].
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let profile = create_resource(cx, || (), |_| async { "Alice".to_string() })
+    ///     .with_debug_name("user profile");
+    /// # }
+    /// # }).dispose();
+    /// ```
+    pub fn with_debug_name(self, name: impl Into<Cow<'static, str>>) -> Self {
+        let name = name.into();
+        with_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceInner<S, T>| {
+                *resource.debug_name.borrow_mut() = Some(name)
+            })
+        });
+        self
+    }
+
     /// Re-runs the async function with the current source data.
     pub fn refetch(&self) {
         with_runtime(self.runtime, |runtime| {
-            runtime.resource(self.id, |resource: &ResourceState<S, T>| resource.refetch())
+            runtime.resource(self.id, |resource: &ResourceInner<S, T>| resource.refetch())
         });
     }
 
@@ -369,7 +724,7 @@ where
         T: Serializable,
     {
         with_runtime(self.runtime, |runtime| {
-            runtime.resource(self.id, |resource: &ResourceState<S, T>| {
+            runtime.resource(self.id, |resource: &ResourceInner<S, T>| {
                 resource.to_serialization_resolver(self.id)
             })
         })
@@ -377,6 +732,95 @@ where
     }
 }
 
+impl<S, V, E> Resource<S, Result<V, E>>
+where
+    S: Clone + 'static,
+    V: 'static,
+    E: Clone + 'static,
+{
+    /// Like [Resource::state], but reports a resource that resolved to an [Err] as
+    /// [ResourceState::Errored], with the error.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let post = create_resource(
+    ///     cx,
+    ///     || (),
+    ///     |_| async { Err::<String, _>("not found".to_string()) },
+    /// );
+    ///
+    /// let message = move || match post.result_state().get() {
+    ///     ResourceState::Errored(e) => format!("Error: {e}"),
+    ///     _ => String::new(),
+    /// };
+    /// # }
+    /// # }).dispose();
+    /// ```
+    pub fn result_state(&self) -> Signal<ResourceState<E>> {
+        let (cached, cx, value, loading) = with_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceInner<S, Result<V, E>>| {
+                let cached = resource
+                    .result_state
+                    .borrow()
+                    .as_ref()
+                    .and_then(|signal| signal.downcast_ref::<Signal<ResourceState<E>>>())
+                    .copied();
+                (cached, resource.scope, resource.value, resource.loading)
+            })
+        });
+        if let Some(signal) = cached {
+            return signal;
+        }
+
+        let signal = Signal::derive(cx, move || {
+            let loading = loading.get();
+            value.with(|value| match value {
+                Some(Err(e)) if !loading => ResourceState::Errored(e.clone()),
+                value => ResourceState::new(loading, value.is_some()),
+            })
+        });
+        with_runtime(self.runtime, |runtime| {
+            runtime.resource(self.id, |resource: &ResourceInner<S, Result<V, E>>| {
+                *resource.result_state.borrow_mut() = Some(Rc::new(signal));
+            })
+        });
+        signal
+    }
+}
+
+/// The loading state of a [Resource], returned by [Resource::state] and
+/// [Resource::result_state].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ResourceState<E = Infallible> {
+    /// The resource has no value and isn't loading one.
+    Idle,
+    /// The resource is loading its first value.
+    Loading,
+    /// The resource is loading a new value, and still has the previous one.
+    Reloading,
+    /// The resource has a value and isn't loading a new one.
+    Resolved,
+    /// The resource resolved to an error. Only reported by [Resource::result_state].
+    Errored(E),
+}
+
+impl<E> ResourceState<E> {
+    fn new(loading: bool, has_value: bool) -> Self {
+        match (loading, has_value) {
+            (false, false) => Self::Idle,
+            (true, false) => Self::Loading,
+            (true, true) => Self::Reloading,
+            (false, true) => Self::Resolved,
+        }
+    }
+
+    /// Whether the resource is loading a value, for the first time or not.
+    pub fn is_loading(&self) -> bool {
+        matches!(self, Self::Loading | Self::Reloading)
+    }
+}
+
 /// A signal that reflects the
 /// current state of an asynchronous task, allowing you to integrate `async`
 /// [Future]s into the synchronous reactive system.
@@ -495,8 +939,90 @@ where
     }
 }
 
+/// Decrements the suspense contexts that a resource was read under once its
+/// future resolves, or when it's dropped because the resource was disposed, so
+/// that a `<Suspense/>` that outlives the resource doesn't keep waiting for it.
+struct SuspenseGuard(ResourceId, Rc<RefCell<HashSet<SuspenseContext>>>);
+
+impl Drop for SuspenseGuard {
+    fn drop(&mut self) {
+        for suspense_context in self.1.borrow().iter() {
+            suspense_context.decrement_resource(self.0);
+        }
+    }
+}
+
+/// Starts a fetch with the current value of a resource's source.
+type Fetcher<S, T> = Rc<dyn Fn(S, Superseded) -> Pin<Box<dyn Future<Output = T>>>>;
+
+/// A [Future] that resolves when a [Resource]'s fetch is superseded by a newer
+/// one, passed to the `fetcher` of [create_cancellable_resource()].
+///
+/// The resource drops the pending [Future] itself, so this is only needed to
+/// cancel work that outlives it, like a request made through JavaScript. It can
+/// also be polled with [Superseded::is_superseded].
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// # async fn fetch_results(query: String) -> Vec<String> { vec![query] }
+/// let (query, set_query) = create_signal(cx, String::new());
+/// let results = create_cancellable_resource(cx, query, |query, superseded| async move {
+///   let results = fetch_results(query).await;
+///   if superseded.is_superseded() {
+///     log::debug!("a newer search has already started");
+///   }
+///   results
+/// });
+/// # }
+/// # }).dispose();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Superseded(Rc<SupersededInner>);
+
+#[derive(Debug, Default)]
+struct SupersededInner {
+    superseded: Cell<bool>,
+    wakers: RefCell<Vec<Waker>>,
+}
+
+impl Superseded {
+    /// Whether a newer fetch has started.
+    pub fn is_superseded(&self) -> bool {
+        self.0.superseded.get()
+    }
+
+    /// Whether both belong to the same fetch.
+    fn is(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+
+    fn supersede(&self) {
+        self.0.superseded.set(true);
+        for waker in self.0.wakers.take() {
+            waker.wake();
+        }
+    }
+}
+
+impl Future for Superseded {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, task_cx: &mut Context<'_>) -> Poll<()> {
+        if self.is_superseded() {
+            Poll::Ready(())
+        } else {
+            let mut wakers = self.0.wakers.borrow_mut();
+            if !wakers.iter().any(|waker| waker.will_wake(task_cx.waker())) {
+                wakers.push(task_cx.waker().clone());
+            }
+            Poll::Pending
+        }
+    }
+}
+
 #[derive(Clone)]
-pub(crate) struct ResourceState<S, T>
+pub(crate) struct ResourceInner<S, T>
 where
     S: 'static,
     T: 'static,
@@ -507,14 +1033,39 @@ where
     pub loading: ReadSignal<bool>,
     set_loading: WriteSignal<bool>,
     source: Memo<S>,
-    #[allow(clippy::type_complexity)]
-    fetcher: Rc<dyn Fn(S) -> Pin<Box<dyn Future<Output = T>>>>,
+    fetcher: Fetcher<S, T>,
     resolved: Rc<Cell<bool>>,
     scheduled: Rc<Cell<bool>>,
     suspense_contexts: Rc<RefCell<HashSet<SuspenseContext>>>,
+    blocking: Cell<bool>,
+    id: Cell<ResourceId>,
+    debug_name: RefCell<Option<Cow<'static, str>>>,
+    /// The latest fetch, until it resolves, and its task once it has been spawned.
+    pending_fetch: Rc<RefCell<Option<(Option<TaskHandle>, Superseded)>>>,
+    /// The fetches that are still running, which are dropped if the resource is disposed.
+    tasks: ScopedTasks,
+    /// Why the value the server sent couldn't be used, if it couldn't.
+    serialization_error: Rc<RefCell<Option<SerializationError>>>,
+    /// The signal returned by [Resource::state], which is created along with the resource.
+    state: Signal<ResourceState>,
+    /// The signal returned by [Resource::result_state], which is created the first time it's
+    /// asked for, because only resources that resolve to a [Result] have one.
+    result_state: RefCell<Option<Rc<dyn Any>>>,
 }
 
-impl<S, T> ResourceState<S, T>
+/// Creates the signal returned by [Resource::state].
+fn resource_state<T: 'static>(
+    cx: Scope,
+    value: ReadSignal<Option<T>>,
+    loading: ReadSignal<bool>,
+) -> Signal<ResourceState> {
+    Signal::derive(cx, move || {
+        let has_value = value.with(Option::is_some);
+        ResourceState::new(loading.get(), has_value)
+    })
+}
+
+impl<S, T> ResourceInner<S, T>
 where
     S: Clone + 'static,
     T: 'static,
@@ -529,14 +1080,28 @@ where
     pub fn with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
         let suspense_cx = use_context::<SuspenseContext>(self.scope);
 
+        // a <Suspense/> that reads a blocking resource is rendered before the shell is sent
+        if self.blocking.get() {
+            if let Some(s) = &suspense_cx {
+                s.set_should_block();
+            }
+        }
+
         let v = self
             .value
             .try_with(|n| n.as_ref().map(|n| Some(f(n))))
             .ok()?
             .flatten();
 
+        // a value that couldn't be serialized is shown as an error by the <Suspense/>
+        if let (Some(s), Some(error)) = (&suspense_cx, &*self.serialization_error.borrow()) {
+            s.report_error(self.id.get(), error.clone());
+        }
+
         let suspense_contexts = self.suspense_contexts.clone();
         let has_value = v.is_some();
+        let id = self.id.get();
+        let name = self.debug_name.borrow().clone();
 
         let increment = move |_: Option<()>| {
             if let Some(s) = &suspense_cx {
@@ -548,7 +1113,7 @@ where
                     // because the context has been tracked here
                     // on the first read, resource is already loading without having incremented
                     if !has_value {
-                        s.increment();
+                        s.increment_resource(id, name.clone());
                     }
                 }
             }
@@ -571,7 +1136,17 @@ where
         self.scheduled.set(false);
 
         _ = self.source.try_with(|source| {
-            let fut = (self.fetcher)(source.clone());
+            // only the latest fetch may set the value, so any older one is dropped
+            if let Some((task, superseded)) = self.pending_fetch.take() {
+                if let Some(task) = task {
+                    task.abort();
+                }
+                superseded.supersede();
+            }
+            let superseded = Superseded::default();
+            let fut = (self.fetcher)(source.clone(), superseded.clone());
+            // recorded before the fetch is spawned, because it may run to the end right away
+            *self.pending_fetch.borrow_mut() = Some((None, superseded.clone()));
 
             // `scheduled` is true for the rest of this code only
             self.scheduled.set(true);
@@ -587,28 +1162,57 @@ where
             // increment counter everywhere it's read
             let suspense_contexts = self.suspense_contexts.clone();
 
+            let id = self.id.get();
             for suspense_context in suspense_contexts.borrow().iter() {
-                suspense_context.increment();
+                suspense_context.increment_resource(id, self.debug_name.borrow().clone());
             }
 
-            // run the Future
-            spawn_local({
+            // run the Future, which is dropped if the resource is disposed or a newer fetch
+            // starts first
+            let task = self.tasks.spawn({
                 let resolved = self.resolved.clone();
                 let set_value = self.set_value;
                 let set_loading = self.set_loading;
+                let serialization_error = Rc::clone(&self.serialization_error);
+                let suspense_guard = SuspenseGuard(id, suspense_contexts);
+                let this_fetch = superseded.clone();
+                let superseded = superseded.clone();
+                let pending_fetch = Rc::clone(&self.pending_fetch);
                 async move {
-                    let res = fut.await;
+                    // checks `superseded` first, in case both are ready
+                    let res = match select(superseded, fut).await {
+                        Either::Left(_) => return,
+                        Either::Right((res, _)) => res,
+                    };
+                    // this is no longer pending, unless a newer fetch has taken its place
+                    {
+                        let mut pending_fetch = pending_fetch.borrow_mut();
+                        if let Some((_, pending)) = &*pending_fetch {
+                            if pending.is(&this_fetch) {
+                                *pending_fetch = None;
+                            }
+                        }
+                    }
 
                     resolved.set(true);
+                    if serialization_error.take().is_some() {
+                        for suspense_context in suspense_guard.1.borrow().iter() {
+                            suspense_context.clear_error(id);
+                        }
+                    }
 
                     set_value.update(|n| *n = Some(res));
                     set_loading.update(|n| *n = false);
 
-                    for suspense_context in suspense_contexts.borrow().iter() {
-                        suspense_context.decrement();
-                    }
+                    drop(suspense_guard);
                 }
-            })
+            });
+            // keeps the task, so that a newer fetch can abort it, unless it has already finished
+            if let Some((pending_task, pending)) = &mut *self.pending_fetch.borrow_mut() {
+                if pending.is(&superseded) {
+                    *pending_task = Some(task);
+                }
+            }
         });
     }
 
@@ -628,7 +1232,7 @@ where
                 let mut tx = tx.clone();
                 move |value| {
                     if let Some(value) = value.as_ref() {
-                        tx.try_send((id, value.to_json().expect("could not serialize Resource")))
+                        tx.try_send((id, resource_to_json(value)))
                             .expect("failed while trying to write to Resource serializer");
                     }
                 }
@@ -654,7 +1258,7 @@ pub(crate) trait SerializableResource {
     ) -> Pin<Box<dyn Future<Output = (ResourceId, String)>>>;
 }
 
-impl<S, T> SerializableResource for ResourceState<S, T>
+impl<S, T> SerializableResource for ResourceInner<S, T>
 where
     S: Clone,
     T: Serializable,
@@ -676,7 +1280,7 @@ pub(crate) trait UnserializableResource {
     fn as_any(&self) -> &dyn Any;
 }
 
-impl<S, T> UnserializableResource for ResourceState<S, T> {
+impl<S, T> UnserializableResource for ResourceInner<S, T> {
     fn as_any(&self) -> &dyn Any {
         self
     }
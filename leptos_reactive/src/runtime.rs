@@ -1,7 +1,11 @@
 use crate::{
-    hydration::SharedContext, serialization::Serializable, AnyEffect, AnyResource, Effect,
-    EffectId, Memo, ReadSignal, ResourceId, ResourceState, RwSignal, Scope, ScopeDisposer, ScopeId,
-    ScopeProperty, SignalId, WriteSignal,
+    effect_loop::EffectLoops,
+    hydration::SharedContext,
+    interceptor::{InterceptorId, SignalDebugInfo},
+    serialization::Serializable,
+    AnyEffect, AnyResource, Diagnostics, Effect, EffectId, Memo, ReadSignal, RenderReason,
+    RenderTracker, ResourceId, ResourceInner, RwSignal, Scope, ScopeDisposer, ScopeId,
+    ScopeProperty, SignalId, SignalWrite, UntrackedSettableSignal, WriteSignal,
 };
 use cfg_if::cfg_if;
 use futures::stream::FuturesUnordered;
@@ -9,7 +13,7 @@ use slotmap::{SecondaryMap, SlotMap, SparseSecondaryMap};
 use std::{
     any::{Any, TypeId},
     cell::{Cell, RefCell},
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     fmt::Debug,
     future::Future,
     marker::PhantomData,
@@ -23,30 +27,41 @@ cfg_if! {
     if #[cfg(any(feature = "csr", feature = "hydrate"))] {
         thread_local! {
             pub(crate) static RUNTIME: Runtime = Runtime::new();
+            /// Isolated runtimes created with [create_scoped_runtime], e.g. for widgets embedded
+            /// in a page alongside the main app.
+            static SCOPED_RUNTIMES: RefCell<SlotMap<RuntimeId, Rc<Runtime>>> = Default::default();
         }
     } else {
         thread_local! {
-            pub(crate) static RUNTIMES: RefCell<SlotMap<RuntimeId, Runtime>> = Default::default();
+            pub(crate) static RUNTIMES: RefCell<SlotMap<RuntimeId, Rc<Runtime>>> = Default::default();
+            /// Runtimes that have been disposed and reset, which are reused by [create_runtime]
+            /// instead of allocating new arenas.
+            static RUNTIME_POOL: RefCell<Vec<Runtime>> = Default::default();
         }
+
+        /// The maximum number of disposed runtimes kept in each thread's pool.
+        const MAX_POOLED_RUNTIMES: usize = 16;
     }
 }
 
 /// Get the selected runtime from the thread-local set of runtimes. On the server,
-/// this will return the correct runtime. In the browser, there should only be one runtime.
+/// this will return the correct runtime. In the browser, everything exists under one
+/// runtime, unless it was created with [create_scoped_runtime].
+///
+/// The set of runtimes isn't borrowed while `f` runs, so `f` can create and dispose of
+/// other runtimes, e.g. when an effect mounts or removes a widget with a runtime of its own.
 pub(crate) fn with_runtime<T>(id: RuntimeId, f: impl FnOnce(&Runtime) -> T) -> T {
-    // in the browser, everything should exist under one runtime
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
-            _ = id;
-            RUNTIME.with(|runtime| f(runtime))
+            if slotmap::Key::is_null(&id) {
+                RUNTIME.with(|runtime| f(runtime))
+            } else {
+                let runtime = SCOPED_RUNTIMES.with(|runtimes| runtimes.borrow().get(id).cloned());
+                f(&runtime.expect("Tried to access a Runtime that no longer exists."))
+            }
         } else {
-            RUNTIMES.with(|runtimes| {
-                let runtimes = runtimes.borrow();
-                let runtime = runtimes
-                    .get(id)
-                    .expect("Tried to access a Runtime that no longer exists.");
-                f(runtime)
-            })
+            let runtime = RUNTIMES.with(|runtimes| runtimes.borrow().get(id).cloned());
+            f(&runtime.expect("Tried to access a Runtime that no longer exists."))
         }
     }
 }
@@ -54,12 +69,78 @@ pub(crate) fn with_runtime<T>(id: RuntimeId, f: impl FnOnce(&Runtime) -> T) -> T
 #[doc(hidden)]
 #[must_use = "Runtime will leak memory if Runtime::dispose() is never called."]
 /// Creates a new reactive [Runtime]. This should almost always be handled by the framework.
+///
+/// On the server, this reuses the allocations of a runtime that has been disposed on
+/// the same thread, if there is one.
 pub fn create_runtime() -> RuntimeId {
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
             Default::default()
         } else {
-            RUNTIMES.with(|runtimes| runtimes.borrow_mut().insert(Runtime::new()))
+            let runtime = RUNTIME_POOL
+                .with(|pool| pool.borrow_mut().pop())
+                .map(|runtime| {
+                    debug_assert!(
+                        runtime.is_empty(),
+                        "a pooled Runtime should not contain any state from a previous request"
+                    );
+                    runtime
+                })
+                .unwrap_or_default();
+            RUNTIMES.with(|runtimes| runtimes.borrow_mut().insert(Rc::new(runtime)))
+        }
+    }
+}
+
+/// Creates a new reactive runtime that is isolated from every other one, with a name that
+/// identifies it in [Diagnostic](crate::Diagnostic)s and other developer tools.
+///
+/// In the browser, everything normally shares a single runtime. A scoped runtime is meant
+/// for independent widgets embedded in the same page, e.g. by several teams: each has its
+/// own signals, effects, contexts and resources, so that nothing leaks between them. To
+/// share selected signals between runtimes, use a [SignalBridge](crate::SignalBridge).
+///
+/// Resources in a scoped runtime are loaded in the browser, rather than from the data
+/// serialized into the page by the server, which belongs to the main runtime.
+///
+/// On the server, every runtime is already isolated, so this works like [create_runtime].
+/// The runtime must be disposed with [RuntimeId::dispose] once it is no longer used, and
+/// not from inside one of its own effects. It can be created and disposed from inside the
+/// effects of other runtimes, e.g. by a widget that shows or hides another one.
+/// ```
+/// # use leptos_reactive::*;
+/// let cart = create_scoped_runtime("cart");
+/// assert_eq!(cart.name().as_deref(), Some("cart"));
+///
+/// create_scope(cart, |cx| {
+///     provide_context(cx, 42);
+/// })
+/// .dispose();
+///
+/// // contexts aren't shared with other runtimes
+/// let search = create_scoped_runtime("search");
+/// create_scope(search, |cx| {
+///     assert_eq!(use_context::<i32>(cx), None);
+/// })
+/// .dispose();
+///
+/// cart.dispose();
+/// search.dispose();
+/// ```
+#[must_use = "Runtime will leak memory if Runtime::dispose() is never called."]
+pub fn create_scoped_runtime(name: impl Into<String>) -> RuntimeId {
+    let name = name.into();
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let runtime = Runtime::new();
+            // the resources serialized into the page belong to the main runtime
+            *runtime.shared_context.borrow_mut() = SharedContext::empty();
+            *runtime.name.borrow_mut() = Some(name);
+            SCOPED_RUNTIMES.with(|runtimes| runtimes.borrow_mut().insert(Rc::new(runtime)))
+        } else {
+            let id = create_runtime();
+            with_runtime(id, |runtime| *runtime.name.borrow_mut() = Some(name));
+            id
         }
     }
 }
@@ -71,15 +152,42 @@ slotmap::new_key_type! {
 
 impl RuntimeId {
     /// Removes the runtime, disposing all its child [Scope](crate::Scope)s.
+    ///
+    /// On the server, the runtime is then reset and returned to a pool, so that its
+    /// allocations can be reused by the next call to [create_runtime]. In the browser, only
+    /// runtimes created with [create_scoped_runtime] are removed.
     pub fn dispose(self) {
         cfg_if! {
-            if #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+            if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+                // the main runtime lives as long as the page
+                if !slotmap::Key::is_null(&self) {
+                    // dropped after the borrow ends, in case dropping its values uses a runtime
+                    let runtime =
+                        SCOPED_RUNTIMES.with(move |runtimes| runtimes.borrow_mut().remove(self));
+                    drop(runtime);
+                }
+            } else {
                 let runtime = RUNTIMES.with(move |runtimes| runtimes.borrow_mut().remove(self));
-                drop(runtime);
+                // if it's being used further up the stack, it's dropped once that's done,
+                // rather than reused
+                if let Some(runtime) = runtime.and_then(|runtime| Rc::try_unwrap(runtime).ok()) {
+                    runtime.reset();
+                    RUNTIME_POOL.with(|pool| {
+                        let mut pool = pool.borrow_mut();
+                        if pool.len() < MAX_POOLED_RUNTIMES {
+                            pool.push(runtime);
+                        }
+                    });
+                }
             }
         }
     }
 
+    /// The name the runtime was given by [create_scoped_runtime], if any.
+    pub fn name(self) -> Option<String> {
+        with_runtime(self, |runtime| runtime.name.borrow().clone())
+    }
+
     pub(crate) fn raw_scope_and_disposer(self) -> (Scope, ScopeDisposer) {
         with_runtime(self, |runtime| {
             let id = { runtime.scopes.borrow_mut().insert(Default::default()) };
@@ -154,6 +262,19 @@ impl RuntimeId {
     }
 
     pub(crate) fn create_effect<T>(self, f: impl Fn(Option<T>) -> T + 'static) -> EffectId
+    where
+        T: Any + 'static,
+    {
+        self.create_effect_in_scope(None, f)
+    }
+
+    /// Creates an effect, and remembers the scope it was created in, so that its runs can be
+    /// counted as renders of that scope's component.
+    pub(crate) fn create_effect_in_scope<T>(
+        self,
+        scope: Option<ScopeId>,
+        f: impl Fn(Option<T>) -> T + 'static,
+    ) -> EffectId
     where
         T: Any + 'static,
     {
@@ -163,6 +284,15 @@ impl RuntimeId {
                 value: RefCell::new(None),
             };
             let id = { runtime.effects.borrow_mut().insert(Rc::new(effect)) };
+            let order = runtime.next_effect_order.get();
+            runtime.next_effect_order.set(order + 1);
+            runtime.effect_order.borrow_mut().insert(id, order);
+            #[cfg(debug_assertions)]
+            if let Some(scope) = scope {
+                runtime.effect_scopes.borrow_mut().insert(id, scope);
+            }
+            #[cfg(not(debug_assertions))]
+            _ = scope;
             id.run::<T>(self);
             id
         })
@@ -171,13 +301,27 @@ impl RuntimeId {
     pub(crate) fn create_memo<T>(self, f: impl Fn(Option<&T>) -> T + 'static) -> Memo<T>
     where
         T: PartialEq + Any + 'static,
+    {
+        self.create_memo_with_compare(f, |prev, new| prev == new)
+    }
+
+    pub(crate) fn create_memo_with_compare<T>(
+        self,
+        f: impl Fn(Option<&T>) -> T + 'static,
+        is_equal: impl Fn(&T, &T) -> bool + 'static,
+    ) -> Memo<T>
+    where
+        T: Any + 'static,
     {
         let (read, write) = self.create_signal(None);
 
         self.create_effect(move |_| {
             let (new, changed) = read.with_no_subscription(|p| {
                 let new = f(p.as_ref());
-                let changed = Some(&new) != p.as_ref();
+                let changed = match p {
+                    Some(prev) => !is_equal(prev, &new),
+                    None => true,
+                };
                 (new, changed)
             });
 
@@ -188,6 +332,30 @@ impl RuntimeId {
 
         Memo(read)
     }
+
+    pub(crate) fn create_owning_memo<T>(
+        self,
+        f: impl Fn(Option<T>) -> (T, bool) + 'static,
+    ) -> Memo<T>
+    where
+        T: Any + 'static,
+    {
+        let (read, write) = self.create_signal(None);
+
+        self.create_effect(move |_| {
+            // take the previous value out of the signal, so it can be reused
+            let prev = write.update_returning_untracked(Option::take).flatten();
+            let (new, changed) = f(prev);
+
+            if changed {
+                write.update(|n| *n = Some(new));
+            } else {
+                write.set_untracked(Some(new));
+            }
+        });
+
+        Memo(read)
+    }
 }
 
 #[derive(Default)]
@@ -203,14 +371,48 @@ pub(crate) struct Runtime {
     pub scope_cleanups: RefCell<SparseSecondaryMap<ScopeId, Vec<Box<dyn FnOnce()>>>>,
     pub signals: RefCell<SlotMap<SignalId, Rc<RefCell<dyn Any>>>>,
     pub signal_subscribers: RefCell<SecondaryMap<SignalId, RefCell<HashSet<EffectId>>>>,
+    pub signal_debug_info: RefCell<SecondaryMap<SignalId, SignalDebugInfo>>,
+    #[allow(clippy::type_complexity)]
+    pub signal_write_interceptors: RefCell<SlotMap<InterceptorId, Rc<dyn Fn(&SignalWrite)>>>,
     pub effects: RefCell<SlotMap<EffectId, Rc<dyn AnyEffect>>>,
     pub effect_sources: RefCell<SecondaryMap<EffectId, RefCell<HashSet<SignalId>>>>,
+    /// The scope each effect was created in, which is only recorded in debug builds.
+    pub effect_scopes: RefCell<SecondaryMap<EffectId, ScopeId>>,
+    /// The signals whose subscribers are being notified, from the outermost write to the
+    /// innermost, which is only recorded in debug builds.
+    pub render_triggers: RefCell<Vec<SignalId>>,
+    /// Why the effect that is currently running ran.
+    pub render_reason: RefCell<Option<RenderReason>>,
+    /// The tracker registered with [track_renders](crate::track_renders).
+    pub render_tracker: RefCell<Option<RenderTracker>>,
+    /// How many calls to [batch](crate::batch) are running.
+    pub batch_depth: Cell<usize>,
+    /// The effects queued by signal writes during a batch, with the signal that queued them,
+    /// keyed by the order they were created in, which is the order they run in.
+    pub pending_effects: RefCell<BTreeMap<u64, (EffectId, SignalId)>>,
+    /// The order each effect was created in, which a batch runs its effects in. Effect ids can't
+    /// be compared for this, because new effects reuse the slots of disposed ones.
+    pub effect_order: RefCell<SecondaryMap<EffectId, u64>>,
+    /// The place in [Runtime::effect_order] of the next effect that is created.
+    pub next_effect_order: Cell<u64>,
+    /// The effects that are running, and how many times each has run in response to the
+    /// current change, which are only tracked in debug builds.
+    pub effect_loops: RefCell<EffectLoops>,
     pub resources: RefCell<SlotMap<ResourceId, AnyResource>>,
+    /// Requests made by keyed resources that haven't resolved yet, so that identical
+    /// requests can share a single [Future].
+    #[allow(clippy::type_complexity)]
+    pub in_flight_requests: RefCell<HashMap<(TypeId, String), Rc<dyn Any>>>,
+    /// The collector registered with [provide_diagnostics](crate::provide_diagnostics).
+    pub diagnostics: Cell<Option<Diagnostics>>,
+    /// The name given by [create_scoped_runtime], which identifies the runtime in diagnostics.
+    pub name: RefCell<Option<String>>,
 }
 
 impl Debug for Runtime {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("Runtime")
+            .field("name", &self.name)
             .field("shared_context", &self.shared_context)
             .field("observer", &self.observer)
             .field("scopes", &self.scopes)
@@ -218,8 +420,16 @@ impl Debug for Runtime {
             .field("scope_children", &self.scope_children)
             .field("signals", &self.signals)
             .field("signal_subscribers", &self.signal_subscribers)
+            .field(
+                "signal_write_interceptors",
+                &self.signal_write_interceptors.borrow().len(),
+            )
             .field("effects", &self.effects.borrow().len())
             .field("effect_sources", &self.effect_sources)
+            .field(
+                "in_flight_requests",
+                &self.in_flight_requests.borrow().keys().collect::<Vec<_>>(),
+            )
             .finish()
     }
 }
@@ -229,9 +439,107 @@ impl Runtime {
         Self::default()
     }
 
+    /// Drops everything the runtime owns, but keeps its allocations so it can be reused.
+    ///
+    /// Keys into the arenas are versioned, so any signal, effect, or resource handle
+    /// left over from before the reset won't find anything afterward.
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    fn reset(&self) {
+        // destructure so that adding a field without resetting it is a compile error
+        let Runtime {
+            shared_context,
+            observer,
+            scopes,
+            scope_parents,
+            scope_children,
+            scope_contexts,
+            scope_cleanups,
+            signals,
+            signal_subscribers,
+            signal_debug_info,
+            signal_write_interceptors,
+            effects,
+            effect_sources,
+            effect_scopes,
+            render_triggers,
+            render_reason,
+            render_tracker,
+            batch_depth,
+            pending_effects,
+            effect_order,
+            next_effect_order,
+            effect_loops,
+            resources,
+            in_flight_requests,
+            diagnostics,
+            name,
+        } = self;
+        shared_context.take();
+        observer.set(None);
+        scopes.borrow_mut().clear();
+        scope_parents.borrow_mut().clear();
+        scope_children.borrow_mut().clear();
+        scope_contexts.borrow_mut().clear();
+        scope_cleanups.borrow_mut().clear();
+        signals.borrow_mut().clear();
+        signal_subscribers.borrow_mut().clear();
+        signal_debug_info.borrow_mut().clear();
+        signal_write_interceptors.borrow_mut().clear();
+        effects.borrow_mut().clear();
+        effect_sources.borrow_mut().clear();
+        effect_scopes.borrow_mut().clear();
+        render_triggers.borrow_mut().clear();
+        render_reason.take();
+        render_tracker.take();
+        batch_depth.set(0);
+        pending_effects.borrow_mut().clear();
+        effect_order.borrow_mut().clear();
+        next_effect_order.set(0);
+        effect_loops.take();
+        resources.borrow_mut().clear();
+        in_flight_requests.borrow_mut().clear();
+        diagnostics.set(None);
+        name.take();
+    }
+
+    /// Whether the runtime owns no state at all, as after [Runtime::reset].
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    fn is_empty(&self) -> bool {
+        let shared_context = self.shared_context.borrow();
+        self.observer.get().is_none()
+            && shared_context.pending_resources.is_empty()
+            && shared_context.resolved_resources.is_empty()
+            && shared_context.pending_fragments.is_empty()
+            && shared_context.blocking_resources.is_empty()
+            && self.scopes.borrow().is_empty()
+            && self.scope_parents.borrow().is_empty()
+            && self.scope_children.borrow().is_empty()
+            && self.scope_contexts.borrow().is_empty()
+            && self.scope_cleanups.borrow().is_empty()
+            && self.signals.borrow().is_empty()
+            && self.signal_subscribers.borrow().is_empty()
+            && self.signal_debug_info.borrow().is_empty()
+            && self.signal_write_interceptors.borrow().is_empty()
+            && self.effects.borrow().is_empty()
+            && self.effect_sources.borrow().is_empty()
+            && self.effect_scopes.borrow().is_empty()
+            && self.render_triggers.borrow().is_empty()
+            && self.render_reason.borrow().is_none()
+            && self.render_tracker.borrow().is_none()
+            && self.batch_depth.get() == 0
+            && self.pending_effects.borrow().is_empty()
+            && self.effect_order.borrow().is_empty()
+            && self.next_effect_order.get() == 0
+            && self.effect_loops.borrow().is_empty()
+            && self.resources.borrow().is_empty()
+            && self.in_flight_requests.borrow().is_empty()
+            && self.diagnostics.get().is_none()
+            && self.name.borrow().is_none()
+    }
+
     pub(crate) fn create_unserializable_resource<S, T>(
         &self,
-        state: Rc<ResourceState<S, T>>,
+        state: Rc<ResourceInner<S, T>>,
     ) -> ResourceId
     where
         S: Clone + 'static,
@@ -244,7 +552,7 @@ impl Runtime {
 
     pub(crate) fn create_serializable_resource<S, T>(
         &self,
-        state: Rc<ResourceState<S, T>>,
+        state: Rc<ResourceInner<S, T>>,
     ) -> ResourceId
     where
         S: Clone + 'static,
@@ -258,7 +566,7 @@ impl Runtime {
     pub(crate) fn resource<S, T, U>(
         &self,
         id: ResourceId,
-        f: impl FnOnce(&ResourceState<S, T>) -> U,
+        f: impl FnOnce(&ResourceInner<S, T>) -> U,
     ) -> U
     where
         S: 'static,
@@ -271,13 +579,13 @@ impl Runtime {
                 AnyResource::Unserializable(res) => res.as_any(),
                 AnyResource::Serializable(res) => res.as_any(),
             }
-            .downcast_ref::<ResourceState<S, T>>();
+            .downcast_ref::<ResourceInner<S, T>>();
 
             if let Some(n) = res_state {
                 f(n)
             } else {
                 panic!(
-                    "couldn't convert {id:?} to ResourceState<{}, {}>",
+                    "couldn't convert {id:?} to ResourceInner<{}, {}>",
                     std::any::type_name::<S>(),
                     std::any::type_name::<T>(),
                 );
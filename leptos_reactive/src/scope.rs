@@ -1,4 +1,5 @@
 use crate::{
+    hydration::FragmentData,
     runtime::{with_runtime, RuntimeId},
     EffectId, PinnedFuture, ResourceId, SignalId, SuspenseContext,
 };
@@ -191,6 +192,7 @@ impl Scope {
                         ScopeProperty::Signal(id) => {
                             // remove the signal
                             runtime.signals.borrow_mut().remove(id);
+                            runtime.signal_debug_info.borrow_mut().remove(id);
                             let subs = runtime.signal_subscribers.borrow_mut().remove(id);
 
                             // each of the subs needs to remove the signal from its dependencies
@@ -207,6 +209,8 @@ impl Scope {
                         ScopeProperty::Effect(id) => {
                             runtime.effects.borrow_mut().remove(id);
                             runtime.effect_sources.borrow_mut().remove(id);
+                            runtime.effect_scopes.borrow_mut().remove(id);
+                            runtime.effect_order.borrow_mut().remove(id);
                         }
                         ScopeProperty::Resource(id) => {
                             runtime.resources.borrow_mut().remove(id);
@@ -318,26 +322,36 @@ impl Scope {
 
             shared_context.pending_fragments.insert(
                 key.to_string(),
-                (
-                    key_before_suspense.to_string(),
-                    Box::pin(async move {
+                FragmentData {
+                    key_before_suspense: key_before_suspense.to_string(),
+                    html: Box::pin(async move {
                         rx.next().await;
                         resolver()
-                    })
-                ),
+                    }),
+                    should_block: context.should_block(),
+                },
             );
         })
     }
 
     /// The set of all HTML fragments current pending, by their keys (see [Self::current_fragment_key]).
-    /// Returns a tuple of the hydration ID of the previous element, and a pinned `Future` that will yield the
+    /// Each includes the hydration ID of the previous element, and a pinned `Future` that will yield the
     /// `<Suspense/>` HTML when all resources are resolved.
-    pub fn pending_fragments(&self) -> HashMap<String, (String, PinnedFuture<String>)> {
+    pub fn pending_fragments(&self) -> HashMap<String, FragmentData> {
         with_runtime(self.runtime, |runtime| {
             let mut shared_context = runtime.shared_context.borrow_mut();
             std::mem::take(&mut shared_context.pending_fragments)
         })
     }
+
+    /// Returns a `Future` for each [blocking resource](crate::create_blocking_resource) created so
+    /// far, which resolves once that resource has loaded.
+    pub fn pending_blocking_resources(&self) -> Vec<PinnedFuture<()>> {
+        with_runtime(self.runtime, |runtime| {
+            let mut shared_context = runtime.shared_context.borrow_mut();
+            std::mem::take(&mut shared_context.blocking_resources)
+        })
+    }
 }
 
 impl fmt::Debug for ScopeDisposer {
@@ -15,6 +15,35 @@ pub enum SerializationError {
     Deserialize(Rc<dyn std::error::Error>),
 }
 
+/// Marks a resource value that couldn't be serialized on the server, so that the client shows
+/// the error instead. JSON never starts with it.
+const SERIALIZATION_ERROR_MARKER: char = '!';
+
+/// A resource value that the server couldn't serialize.
+#[cfg(feature = "hydrate")]
+#[derive(Debug, Clone, Error)]
+#[error("{0}")]
+struct ServerSerializationError(String);
+
+/// Serializes a resource's value to send it to the client, or the error if it can't be.
+pub(crate) fn resource_to_json<T: Serializable>(value: &T) -> String {
+    match value.to_json() {
+        Ok(json) => json,
+        Err(e) => format!("{SERIALIZATION_ERROR_MARKER}{e}"),
+    }
+}
+
+/// Deserializes a resource's value sent by [resource_to_json].
+#[cfg(feature = "hydrate")]
+pub(crate) fn resource_from_json<T: Serializable>(json: &str) -> Result<T, SerializationError> {
+    match json.strip_prefix(SERIALIZATION_ERROR_MARKER) {
+        Some(error) => Err(SerializationError::Serialize(Rc::new(
+            ServerSerializationError(error.to_string()),
+        ))),
+        None => T::from_json(json),
+    }
+}
+
 /// Describes an object that can be serialized to or from a supported format
 /// Currently those are JSON and Cbor
 ///
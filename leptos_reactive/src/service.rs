@@ -0,0 +1,129 @@
+use crate::{provide_context, use_context, Scope};
+use std::rc::Rc;
+
+/// The context that a service of type `S` is stored in. Wrapping it keeps services apart
+/// from plain contexts of type `Rc<S>`.
+struct Service<S: ?Sized>(Rc<S>);
+
+impl<S: ?Sized> Clone for Service<S> {
+    fn clone(&self) -> Self {
+        Self(Rc::clone(&self.0))
+    }
+}
+
+/// Provides a service to the current [Scope] and all of its descendants, where it can be
+/// resolved with [use_service] or [expect_service].
+///
+/// Services are contexts that are looked up by an interface, usually a trait object like
+/// `dyn ApiClient`, rather than by their concrete type. Components can depend on the
+/// interface, while the app decides which implementation to provide at its root, and tests
+/// can swap in another one with [with_service].
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::rc::Rc;
+/// # create_scope(create_runtime(), |cx| {
+/// trait ApiClient {
+///     fn base_url(&self) -> String;
+/// }
+///
+/// struct HttpClient;
+///
+/// impl ApiClient for HttpClient {
+///     fn base_url(&self) -> String {
+///         "https://api.example.com".to_string()
+///     }
+/// }
+///
+/// provide_service::<dyn ApiClient>(cx, Rc::new(HttpClient));
+///
+/// // anywhere below, without knowing which client was provided
+/// let api = expect_service::<dyn ApiClient>(cx);
+/// assert_eq!(api.base_url(), "https://api.example.com");
+/// # }).dispose();
+/// ```
+pub fn provide_service<S>(cx: Scope, service: Rc<S>)
+where
+    S: ?Sized + 'static,
+{
+    provide_context(cx, Service(service));
+}
+
+/// Resolves the nearest service of type `S` that was provided with [provide_service],
+/// or `None` if there isn't one.
+pub fn use_service<S>(cx: Scope) -> Option<Rc<S>>
+where
+    S: ?Sized + 'static,
+{
+    use_context::<Service<S>>(cx).map(|service| service.0)
+}
+
+/// Resolves the nearest service of type `S`, like [use_service].
+///
+/// # Panics
+/// Panics if no service of type `S` was provided.
+#[track_caller]
+pub fn expect_service<S>(cx: Scope) -> Rc<S>
+where
+    S: ?Sized + 'static,
+{
+    use_service(cx).unwrap_or_else(|| {
+        panic!(
+            "expected a service of type `{}` to be provided in {:?} or one of its ancestor \
+             scopes, but none was found. Make sure `provide_service` is called at the root \
+             of the app.",
+            std::any::type_name::<S>(),
+            cx.id,
+        )
+    })
+}
+
+/// Runs `f` in a child [Scope] in which `service` replaces any service of type `S` that
+/// was provided above it. This is useful for tests that render a component with a mock
+/// implementation.
+///
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::rc::Rc;
+/// # create_scope(create_runtime(), |cx| {
+/// trait Clock {
+///     fn now(&self) -> u64;
+/// }
+///
+/// struct SystemClock;
+///
+/// impl Clock for SystemClock {
+///     fn now(&self) -> u64 {
+///         1_700_000_000
+///     }
+/// }
+///
+/// struct FixedClock(u64);
+///
+/// impl Clock for FixedClock {
+///     fn now(&self) -> u64 {
+///         self.0
+///     }
+/// }
+///
+/// provide_service::<dyn Clock>(cx, Rc::new(SystemClock));
+///
+/// let now = with_service::<dyn Clock, _>(cx, Rc::new(FixedClock(42)), |cx| {
+///     expect_service::<dyn Clock>(cx).now()
+/// });
+/// assert_eq!(now, 42);
+///
+/// // the parent scope still has the original service
+/// assert_eq!(expect_service::<dyn Clock>(cx).now(), 1_700_000_000);
+/// # }).dispose();
+/// ```
+pub fn with_service<S, T>(cx: Scope, service: Rc<S>, f: impl FnOnce(Scope) -> T) -> T
+where
+    S: ?Sized + 'static,
+{
+    let (value, _) = cx.run_child_scope(|cx| {
+        provide_service(cx, service);
+        f(cx)
+    });
+    value
+}
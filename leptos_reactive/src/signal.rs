@@ -1,7 +1,9 @@
 use crate::{
     debug_warn,
+    interceptor::PendingWrite,
     runtime::{with_runtime, RuntimeId},
-    spawn_local, Runtime, Scope, ScopeProperty, UntrackedGettableSignal, UntrackedSettableSignal,
+    spawn_local_scoped, Diagnostic, DiagnosticKind, Runtime, Scope, ScopeProperty,
+    UntrackedGettableSignal, UntrackedSettableSignal,
 };
 use futures::Stream;
 use std::{fmt::Debug, marker::PhantomData};
@@ -61,7 +63,7 @@ pub fn create_signal_from_stream<T>(
     use futures::StreamExt;
 
     let (read, write) = create_signal(cx, None);
-    spawn_local(async move {
+    spawn_local_scoped(cx, async move {
         while let Some(value) = stream.next().await {
             write.set(Some(value));
         }
@@ -799,14 +801,24 @@ impl SignalId {
         // get the value
         let value = {
             let signals = runtime.signals.borrow();
-            match signals.get(*self).cloned().ok_or(SignalError::Disposed) {
-                Ok(s) => Ok(s),
-                Err(e) => {
-                    debug_warn!("[Signal::try_with] {e}");
-                    Err(e)
-                }
+            signals.get(*self).cloned()
+        };
+        let value = match value.ok_or(SignalError::Disposed) {
+            Ok(s) => s,
+            Err(e) => {
+                debug_warn!("[Signal::try_with] {e}");
+                runtime.report_diagnostic(|| {
+                    Diagnostic::new(
+                        DiagnosticKind::DisposedSignal,
+                        format!(
+                            "Tried to read a Signal<{}> that has already been disposed of.",
+                            std::any::type_name::<T>()
+                        ),
+                    )
+                });
+                return Err(e);
             }
-        }?;
+        };
         let value = value.try_borrow().unwrap_or_else(|e| {
             debug_warn!(
                 "Signal::try_with_no_subscription failed on Signal<{}>. It seems you're trying to read the value of a signal within an effect caused by updating the signal.",
@@ -878,6 +890,15 @@ impl SignalId {
                     "[Signal::update] You’re trying to update a Signal<{}> that has already been disposed of. This is probably either a logic error in a component that creates and disposes of scopes, or a Resource resolving after its scope has been dropped without having been cleaned up.",
                     std::any::type_name::<T>()
                 );
+                runtime.report_diagnostic(|| {
+                    Diagnostic::new(
+                        DiagnosticKind::DisposedSignal,
+                        format!(
+                            "Tried to update a Signal<{}> that has already been disposed of.",
+                            std::any::type_name::<T>()
+                        ),
+                    )
+                });
                 None
             }
         })
@@ -892,9 +913,16 @@ impl SignalId {
         T: 'static,
     {
         with_runtime(runtime_id, |runtime| {
+            let pending_write = PendingWrite::new::<T>(runtime, *self);
+
             // update the value
             let updated = self.update_value(runtime_id, f);
 
+            // pass the write to any interceptors
+            if let (Some(pending_write), Some(_)) = (pending_write, &updated) {
+                pending_write.finish(runtime);
+            }
+
             // notify subscribers
             if updated.is_some() {
                 let subs = {
@@ -902,16 +930,26 @@ impl SignalId {
                     let subs = subs.get(*self);
                     subs.map(|subs| subs.borrow().clone())
                 };
-                if let Some(subs) = subs {
-                    for sub in subs {
-                        let effect = {
-                            let effects = runtime.effects.borrow();
-                            effects.get(sub).cloned()
-                        };
-                        if let Some(effect) = effect {
-                            effect.run(sub, runtime_id);
+                match subs {
+                    // run them once the batch ends
+                    Some(subs) if runtime.is_batching() => runtime.queue_effects(subs, *self),
+                    Some(subs) => {
+                        // lets each effect find out which signal made it run
+                        #[cfg(debug_assertions)]
+                        runtime.render_triggers.borrow_mut().push(*self);
+                        for sub in subs {
+                            let effect = {
+                                let effects = runtime.effects.borrow();
+                                effects.get(sub).cloned()
+                            };
+                            if let Some(effect) = effect {
+                                effect.run(sub, runtime_id);
+                            }
                         }
+                        #[cfg(debug_assertions)]
+                        runtime.render_triggers.borrow_mut().pop();
                     }
+                    None => {}
                 }
             };
             updated
@@ -1,4 +1,8 @@
-use crate::{store_value, Memo, ReadSignal, RwSignal, Scope, StoredValue, UntrackedGettableSignal};
+use crate::{
+    create_memo, store_value, Memo, ReadSignal, RwSignal, Scope, SignalSetter, StoredValue,
+    UntrackedGettableSignal,
+};
+use std::fmt::Debug;
 
 /// A wrapper for any kind of readable reactive signal: a [ReadSignal](crate::ReadSignal),
 /// [Memo](crate::Memo), [RwSignal](crate::RwSignal), or derived signal closure.
@@ -78,6 +82,9 @@ where
 {
     /// Wraps a derived signal, i.e., any computation that accesses one or more
     /// reactive signals.
+    ///
+    /// The computation runs again every time the signal is read. If it's expensive, or
+    /// the signal is read in many places, use [Signal::derive_memo] instead.
     /// ```rust
     /// # use leptos_reactive::*;
     /// # create_scope(create_runtime(), |cx| {
@@ -100,6 +107,80 @@ where
         ))
     }
 
+    /// Wraps a derived signal like [Signal::derive], but memoizes its value like a
+    /// [Memo]: the computation only runs again when the signals it reads change, rather
+    /// than every time it's read, and readers are only notified when its value changes.
+    /// ```rust
+    /// # use leptos_reactive::*;
+    /// # use std::{cell::Cell, rc::Rc};
+    /// # create_scope(create_runtime(), |cx| {
+    /// let (count, set_count) = create_signal(cx, 2);
+    /// let runs = Rc::new(Cell::new(0));
+    /// let double_count = Signal::derive_memo(cx, {
+    ///     let runs = Rc::clone(&runs);
+    ///     move || {
+    ///         runs.set(runs.get() + 1);
+    ///         count() * 2
+    ///     }
+    /// });
+    ///
+    /// assert_eq!(double_count(), 4);
+    /// assert_eq!(double_count(), 4);
+    /// assert_eq!(runs.get(), 1);
+    ///
+    /// set_count(3);
+    /// assert_eq!(double_count(), 6);
+    /// assert_eq!(runs.get(), 2);
+    /// # });
+    /// ```
+    pub fn derive_memo(cx: Scope, derived_signal: impl Fn() -> T + 'static) -> Self
+    where
+        T: PartialEq + Debug,
+    {
+        create_memo(cx, move |_| derived_signal()).into()
+    }
+
+    /// Creates a two-way mapping (a “lens”) over a projection of an [RwSignal].
+    /// Returns a [Signal] that reads the source through `getter`, and a
+    /// [SignalSetter] that writes to the source through `setter`.
+    ///
+    /// This is useful for editing one field of a struct, or for viewing a value
+    /// in a different form, like a `String` view over an `f64`.
+    /// ```rust
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let price = create_rw_signal(cx, 1.5_f64);
+    /// let (price_text, set_price_text) = Signal::map_both(
+    ///     cx,
+    ///     price,
+    ///     |price| format!("{price:.2}"),
+    ///     |price, text: String| {
+    ///         // ignore input that can't be parsed
+    ///         if let Ok(parsed) = text.parse() {
+    ///             *price = parsed;
+    ///         }
+    ///     },
+    /// );
+    ///
+    /// assert_eq!(price_text(), "1.50");
+    /// set_price_text("2.25".to_string());
+    /// assert_eq!(price(), 2.25);
+    /// assert_eq!(price_text(), "2.25");
+    /// set_price_text("not a number".to_string());
+    /// assert_eq!(price(), 2.25);
+    /// # });
+    /// ```
+    pub fn map_both<S>(
+        cx: Scope,
+        source: RwSignal<S>,
+        getter: impl Fn(&S) -> T + 'static,
+        setter: impl Fn(&mut S, T) + 'static,
+    ) -> (Self, SignalSetter<T>) {
+        let read = Self::derive(cx, move || source.with(&getter));
+        let write = SignalSetter::map(cx, move |value| source.update(|s| setter(s, value)));
+        (read, write)
+    }
+
     /// Applies a function to the current value of the signal, and subscribes
     /// the running effect to this signal.
     /// ```
@@ -355,6 +436,14 @@ where
         Self::Dynamic(Signal::derive(cx, derived_signal))
     }
 
+    /// Wraps a memoized derived signal; see [Signal::derive_memo].
+    pub fn derive_memo(cx: Scope, derived_signal: impl Fn() -> T + 'static) -> Self
+    where
+        T: PartialEq + Debug,
+    {
+        Self::Dynamic(Signal::derive_memo(cx, derived_signal))
+    }
+
     /// Applies a function to the current value of the signal, and subscribes
     /// the running effect to this signal.
     /// ```
@@ -0,0 +1,62 @@
+use crate::{create_memo, RwSignal, Scope, Signal, SignalSetter};
+use std::fmt::Debug;
+
+/// Derives a “slice” of a larger [RwSignal], like one field of an app-state struct. Returns a
+/// [Signal] that reads the part of the state that `getter` projects, and a [SignalSetter] that
+/// writes to that part of the state through `setter`.
+///
+/// The getter is memoized, so effects that read the slice only run again when the part of the
+/// state it projects changes (as determined by [PartialEq]), rather than whenever any part of
+/// the state changes.
+/// ```
+/// # use leptos_reactive::*;
+/// # use std::{cell::Cell, rc::Rc};
+/// # create_scope(create_runtime(), |cx| {
+/// #[derive(Clone, Debug, Default)]
+/// struct State {
+///     token: String,
+///     count: i32,
+/// }
+///
+/// let state = create_rw_signal(cx, State::default());
+/// let (count, set_count) = create_slice(
+///     cx,
+///     state,
+///     |state| state.count,
+///     |state, count| state.count = count,
+/// );
+/// let runs = Rc::new(Cell::new(0));
+///
+/// create_isomorphic_effect(cx, {
+///     let runs = Rc::clone(&runs);
+///     move |_| {
+///         count.get();
+///         runs.set(runs.get() + 1);
+///     }
+/// });
+/// assert_eq!(runs.get(), 1);
+///
+/// // changing another field doesn't notify readers of the slice
+/// state.update(|state| state.token = "abc".to_string());
+/// assert_eq!(runs.get(), 1);
+///
+/// set_count.set(1);
+/// assert_eq!(count.get(), 1);
+/// assert_eq!(state.with(|state| state.count), 1);
+/// assert_eq!(runs.get(), 2);
+/// # }).dispose();
+/// ```
+pub fn create_slice<T, O>(
+    cx: Scope,
+    signal: RwSignal<T>,
+    getter: impl Fn(&T) -> O + 'static,
+    setter: impl Fn(&mut T, O) + 'static,
+) -> (Signal<O>, SignalSetter<O>)
+where
+    T: 'static,
+    O: PartialEq + Debug + 'static,
+{
+    let getter = create_memo(cx, move |_| signal.with(&getter));
+    let setter = SignalSetter::map(cx, move |value| signal.update(|state| setter(state, value)));
+    (getter.into(), setter)
+}
@@ -1,5 +1,7 @@
+use crate::{on_cleanup, Scope};
 use cfg_if::cfg_if;
-use std::future::Future;
+use futures::future::{abortable, AbortHandle};
+use std::{cell::RefCell, collections::HashMap, future::Future, rc::Rc};
 
 cfg_if! {
     if #[cfg(any(feature = "csr", feature = "hydrate"))] {
@@ -46,3 +48,153 @@ where
         }
     }
 }
+
+/// Spawns a thread-local [std::future::Future] like [spawn_local], but drops it when the
+/// [Scope] is disposed, so that it can't update signals that no longer exist. Returns a
+/// [TaskHandle] that can be used to cancel it earlier.
+///
+/// Each call registers a cleanup on `cx` that is only released when the scope is disposed, so
+/// code that spawns a future over and over for the same scope, like a resource loading or an
+/// action being dispatched, should spawn them through [ScopedTasks] instead.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if false {
+/// # async fn fetch_count() -> i32 { 1 }
+/// let (count, set_count) = create_signal(cx, 0);
+///
+/// let (task, child) = cx.run_child_scope(|cx| {
+///     spawn_local_scoped(cx, async move {
+///         set_count(fetch_count().await);
+///     })
+/// });
+///
+/// // if the request is still pending, it is dropped before it can set the signal
+/// child.dispose();
+/// assert!(task.is_aborted());
+/// # }
+/// # }).dispose();
+/// ```
+pub fn spawn_local_scoped<F>(cx: Scope, fut: F) -> TaskHandle
+where
+    F: Future<Output = ()> + 'static,
+{
+    let (fut, handle) = abortable(fut);
+    let handle = TaskHandle(handle);
+
+    on_cleanup(cx, {
+        let handle = handle.clone();
+        move || handle.abort()
+    });
+
+    spawn_local(async move {
+        _ = fut.await;
+    });
+
+    handle
+}
+
+/// The futures spawned for something that lives in a [Scope], like a resource or an action,
+/// which are all dropped when that scope is disposed.
+///
+/// Unlike [spawn_local_scoped], which registers a cleanup for every future, this registers a
+/// single cleanup when it's created, and forgets each future as soon as it finishes, so spawning
+/// many futures over the life of the scope doesn't keep anything around.
+/// ```
+/// # use leptos_reactive::*;
+/// # create_scope(create_runtime(), |cx| {
+/// # if !cfg!(any(feature = "csr", feature = "hydrate", feature = "ssr")) {
+/// let (count, set_count) = create_signal(cx, 0);
+///
+/// let (tasks, child) = cx.run_child_scope(ScopedTasks::new);
+/// for n in 1..=3 {
+///     tasks.spawn(async move { set_count(n) });
+/// }
+///
+/// // futures are forgotten once they finish
+/// assert_eq!(count(), 3);
+/// assert_eq!(tasks.running(), 0);
+///
+/// // and any that are still pending are dropped when the scope is disposed
+/// child.dispose();
+/// # }
+/// # }).dispose();
+/// ```
+#[derive(Clone, Debug)]
+pub struct ScopedTasks(Rc<RefCell<RunningTasks>>);
+
+#[derive(Debug, Default)]
+struct RunningTasks {
+    next_id: usize,
+    handles: HashMap<usize, AbortHandle>,
+}
+
+impl ScopedTasks {
+    /// Creates an empty set of futures, which are canceled when `cx` is disposed.
+    pub fn new(cx: Scope) -> Self {
+        let tasks = Self(Default::default());
+        on_cleanup(cx, {
+            let tasks = tasks.clone();
+            move || tasks.abort_all()
+        });
+        tasks
+    }
+
+    /// Spawns a thread-local [std::future::Future] like [spawn_local], which is dropped if
+    /// the scope is disposed before it finishes. Returns a [TaskHandle] that can be used to
+    /// cancel it earlier.
+    pub fn spawn<F>(&self, fut: F) -> TaskHandle
+    where
+        F: Future<Output = ()> + 'static,
+    {
+        let (fut, handle) = abortable(fut);
+        let id = {
+            let mut tasks = self.0.borrow_mut();
+            let id = tasks.next_id;
+            tasks.next_id = tasks.next_id.wrapping_add(1);
+            tasks.handles.insert(id, handle.clone());
+            id
+        };
+
+        let tasks = Rc::downgrade(&self.0);
+        spawn_local(async move {
+            _ = fut.await;
+            if let Some(tasks) = tasks.upgrade() {
+                tasks.borrow_mut().handles.remove(&id);
+            }
+        });
+
+        TaskHandle(handle)
+    }
+
+    /// Cancels every future that is still running.
+    pub fn abort_all(&self) {
+        let handles = std::mem::take(&mut self.0.borrow_mut().handles);
+        for handle in handles.into_values() {
+            handle.abort();
+        }
+    }
+
+    /// How many of the futures haven't finished or been canceled yet.
+    pub fn running(&self) -> usize {
+        self.0.borrow().handles.len()
+    }
+}
+
+/// A handle to a future spawned with [spawn_local_scoped] or [ScopedTasks::spawn].
+#[derive(Clone, Debug)]
+pub struct TaskHandle(AbortHandle);
+
+impl TaskHandle {
+    /// Cancels the future. It is dropped the next time it would be polled, without running
+    /// any further.
+    pub fn abort(&self) {
+        self.0.abort();
+    }
+
+    /// Whether the future has been canceled, either with [TaskHandle::abort] or because
+    /// its [Scope] was disposed.
+    pub fn is_aborted(&self) -> bool {
+        self.0.is_aborted()
+    }
+}
@@ -1,4 +1,7 @@
-use crate::{create_rw_signal, RwSignal, Scope, UntrackedGettableSignal, UntrackedSettableSignal};
+use crate::{
+    create_rw_signal, runtime::with_runtime, RwSignal, Scope, UntrackedGettableSignal,
+    UntrackedSettableSignal,
+};
 
 /// A **non-reactive** wrapper for any value, which can be created with [store_value].
 ///
@@ -71,6 +74,27 @@ where
         self.0.with_untracked(f)
     }
 
+    /// Applies a function to the current stored value, if it still exists.
+    /// Returns [Option::None] if the value has been disposed, either by
+    /// [StoredValue::dispose] or because the [Scope] that created it was disposed.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let data = store_value(cx, "a".to_string());
+    /// assert_eq!(data.try_with(|data| data.len()), Some(1));
+    ///
+    /// data.dispose();
+    /// assert_eq!(data.try_with(|data| data.len()), None);
+    /// # }).dispose();
+    /// ```
+    pub fn try_with<U>(&self, f: impl FnOnce(&T) -> U) -> Option<U> {
+        with_runtime(self.0.runtime, |runtime| {
+            let value = runtime.signals.borrow().get(self.0.id).cloned()?;
+            let value = value.borrow();
+            value.downcast_ref::<T>().map(f)
+        })
+    }
+
     /// Applies a function to the current value to mutate it in place.
     /// ```
     /// # use leptos_reactive::*;
@@ -88,6 +112,43 @@ where
         self.0.update_untracked(f);
     }
 
+    /// Applies a function to the current value to mutate it in place, and returns
+    /// whatever that function returns. Returns [Option::None] if the value has been
+    /// disposed.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let ids = store_value(cx, 0);
+    /// let next_id = move || ids.update_returning(|id| { *id += 1; *id });
+    ///
+    /// assert_eq!(next_id(), Some(1));
+    /// assert_eq!(next_id(), Some(2));
+    /// # }).dispose();
+    /// ```
+    pub fn update_returning<U>(&self, f: impl FnOnce(&mut T) -> U) -> Option<U> {
+        self.0.update_returning_untracked(f)
+    }
+
+    /// Like [StoredValue::update_returning], but doesn't log a warning if the value
+    /// has been disposed, for handles that may outlive the [Scope] that created them.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// let data = store_value(cx, 1);
+    /// assert_eq!(data.try_update(|n| { *n += 1; *n }), Some(2));
+    ///
+    /// data.dispose();
+    /// assert_eq!(data.try_update(|n| { *n += 1; *n }), None);
+    /// # }).dispose();
+    /// ```
+    pub fn try_update<U>(&self, f: impl FnOnce(&mut T) -> U) -> Option<U> {
+        with_runtime(self.0.runtime, |runtime| {
+            let value = runtime.signals.borrow().get(self.0.id).cloned()?;
+            let mut value = value.borrow_mut();
+            value.downcast_mut::<T>().map(f)
+        })
+    }
+
     /// Sets the stored value.
     /// ```
     /// # use leptos_reactive::*;
@@ -104,6 +165,19 @@ where
     pub fn set(&self, value: T) {
         self.0.set_untracked(value);
     }
+
+    /// Drops the stored value now, rather than when its [Scope] is disposed, so that
+    /// long-lived handles can release it early. Afterward, [StoredValue::with] and
+    /// [StoredValue::get] will panic, while the other methods return [Option::None]
+    /// or do nothing.
+    pub fn dispose(self) {
+        with_runtime(self.0.runtime, |runtime| {
+            let value = runtime.signals.borrow_mut().remove(self.0.id);
+            runtime.signal_subscribers.borrow_mut().remove(self.0.id);
+            // drop the value outside the borrow, in case its `Drop` accesses the runtime
+            drop(value);
+        })
+    }
 }
 
 /// Creates a **non-reactive** wrapper for any value by storing it within
@@ -1,4 +1,8 @@
-use crate::{create_signal, spawn::queue_microtask, ReadSignal, Scope, WriteSignal};
+use crate::{
+    create_signal, spawn::queue_microtask, store_value, ReadSignal, ResourceId, Scope,
+    SerializationError, StoredValue, UntrackedGettableSignal, WriteSignal,
+};
+use std::borrow::Cow;
 
 /// Tracks [Resource](crate::Resource)s that are read under a suspense context,
 /// i.e., within a [`Suspense`](https://docs.rs/leptos_core/latest/leptos_core/fn.Suspense.html) component.
@@ -7,6 +11,11 @@ pub struct SuspenseContext {
     /// The number of resources that are currently pending.
     pub pending_resources: ReadSignal<usize>,
     set_pending_resources: WriteSignal<usize>,
+    pending: ReadSignal<Vec<PendingResource>>,
+    set_pending: WriteSignal<Vec<PendingResource>>,
+    should_block: StoredValue<bool>,
+    errors: ReadSignal<Vec<(ResourceId, SerializationError)>>,
+    set_errors: WriteSignal<Vec<(ResourceId, SerializationError)>>,
 }
 
 impl std::hash::Hash for SuspenseContext {
@@ -27,17 +36,40 @@ impl SuspenseContext {
     /// Creates an empty suspense context.
     pub fn new(cx: Scope) -> Self {
         let (pending_resources, set_pending_resources) = create_signal(cx, 0);
+        let (pending, set_pending) = create_signal(cx, Vec::new());
+        let (errors, set_errors) = create_signal(cx, Vec::new());
         Self {
             pending_resources,
             set_pending_resources,
+            pending,
+            set_pending,
+            should_block: store_value(cx, false),
+            errors,
+            set_errors,
         }
     }
 
+    /// Whether any [blocking resources](crate::create_blocking_resource) have been read
+    /// under this context, in which case the server renders it before sending the shell.
+    pub fn should_block(&self) -> bool {
+        self.should_block
+            .try_with(|should_block| *should_block)
+            .unwrap_or(false)
+    }
+
+    /// Marks the context as blocking, so that the server waits for its resources, and renders it,
+    /// before sending the shell. Anything that changes the document `<head>` based on data loaded
+    /// under this context can use this to make sure the `<head>` includes it.
+    pub fn set_should_block(&self) {
+        self.should_block.set(true);
+    }
+
     /// Notifies the suspense context that a new resource is now pending.
     pub fn increment(&self) {
         let setter = self.set_pending_resources;
         queue_microtask(move || {
             setter.update(|n| *n += 1);
+            report_pending(1);
         });
     }
 
@@ -47,10 +79,98 @@ impl SuspenseContext {
         queue_microtask(move || {
             setter.update(|n| {
                 if *n > 0 {
-                    *n -= 1
+                    *n -= 1;
+                    report_pending(-1);
+                }
+            });
+        });
+    }
+
+    /// Notifies the suspense context that the resource with the given id, and optionally a
+    /// [debug name](crate::Resource::with_debug_name), is now pending, so that it's included
+    /// in [SuspenseContext::pending].
+    pub fn increment_resource(&self, id: ResourceId, name: Option<Cow<'static, str>>) {
+        let set_pending = self.set_pending;
+        queue_microtask(move || {
+            set_pending.update(|pending| pending.push(PendingResource { id, name }));
+        });
+        self.increment();
+    }
+
+    /// Notifies the suspense context that the resource with the given id has resolved.
+    pub fn decrement_resource(&self, id: ResourceId) {
+        let set_pending = self.set_pending;
+        queue_microtask(move || {
+            set_pending.update(|pending| {
+                if let Some(idx) = pending.iter().position(|p| p.id == id) {
+                    pending.remove(idx);
                 }
             });
         });
+        self.decrement();
+    }
+
+    /// The resources read under this context whose values were sent by the server, but couldn't
+    /// be serialized there or deserialized here, with the reason. Reading this subscribes the
+    /// running effect; a `<Suspense/>` shows them like errors rendered by its children.
+    pub fn errors(&self) -> Vec<(ResourceId, SerializationError)> {
+        self.errors
+            .try_with(|errors| errors.clone())
+            .unwrap_or_default()
+    }
+
+    /// Reports that the value of a resource read under this context couldn't be used.
+    pub(crate) fn report_error(&self, id: ResourceId, error: SerializationError) {
+        let reported = self
+            .errors
+            .with_untracked(|errors| errors.iter().any(|(other, _)| *other == id));
+        if !reported {
+            self.set_errors.update(|errors| errors.push((id, error)));
+        }
+    }
+
+    /// Removes the error of a resource that has since loaded a value.
+    pub(crate) fn clear_error(&self, id: ResourceId) {
+        self.set_errors
+            .update(|errors| errors.retain(|(other, _)| *other != id));
+    }
+
+    /// The resources that this context is waiting for, in the order they started loading.
+    /// Reading this subscribes the running effect, so a fallback can show what's loading.
+    ///
+    /// Only resources that are pending under [SuspenseContext::increment_resource] are
+    /// listed, which includes every [Resource](crate::Resource) read under this context.
+    /// ```
+    /// # use leptos_reactive::*;
+    /// # create_scope(create_runtime(), |cx| {
+    /// # if false {
+    /// let user = create_resource(cx, || (), |_| async { "Alice".to_string() })
+    ///     .with_debug_name("user profile");
+    ///
+    /// let suspense = use_context::<SuspenseContext>(cx).unwrap();
+    /// let fallback = move || {
+    ///     let names = suspense
+    ///         .pending()
+    ///         .into_iter()
+    ///         .filter_map(|resource| resource.name)
+    ///         .collect::<Vec<_>>();
+    ///     format!("Loading {}…", names.join(", "))
+    /// };
+    /// # }
+    /// # }).dispose();
+    /// ```
+    pub fn pending(&self) -> Vec<PendingResource> {
+        self.pending
+            .try_with(|pending| {
+                let mut unique: Vec<PendingResource> = Vec::with_capacity(pending.len());
+                for resource in pending {
+                    if !unique.iter().any(|p| p.id == resource.id) {
+                        unique.push(resource.clone());
+                    }
+                }
+                unique
+            })
+            .unwrap_or_default()
     }
 
     /// Tests whether all of the pending resources have resolved.
@@ -60,3 +180,34 @@ impl SuspenseContext {
             .unwrap_or(false)
     }
 }
+
+/// Keeps a count of the resources that all suspense contexts are waiting for in
+/// `window.__LEPTOS_PENDING_SUSPENSE`, so that end-to-end tests can wait until a page has
+/// finished loading.
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn report_pending(delta: i32) {
+    let window = match web_sys::window() {
+        Some(window) => window,
+        None => return,
+    };
+    let key = wasm_bindgen::JsValue::from_str("__LEPTOS_PENDING_SUSPENSE");
+    let pending = js_sys::Reflect::get(&window, &key)
+        .ok()
+        .and_then(|pending| pending.as_f64())
+        .unwrap_or(0.0);
+    _ = js_sys::Reflect::set(&window, &key, &(pending + delta as f64).max(0.0).into());
+}
+
+#[cfg(not(any(feature = "csr", feature = "hydrate")))]
+fn report_pending(_delta: i32) {}
+
+/// A resource that a [SuspenseContext] is waiting for, as returned by
+/// [SuspenseContext::pending].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct PendingResource {
+    /// The resource's id.
+    pub id: ResourceId,
+    /// The name given with [Resource::with_debug_name](crate::Resource::with_debug_name),
+    /// if any.
+    pub name: Option<Cow<'static, str>>,
+}
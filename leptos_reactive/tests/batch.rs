@@ -0,0 +1,162 @@
+use leptos_reactive::{
+    batch, create_isomorphic_effect, create_memo, create_runtime, create_scope, create_signal,
+};
+use std::{
+    cell::{Cell, RefCell},
+    panic::{self, AssertUnwindSafe},
+    rc::Rc,
+};
+
+#[test]
+fn batch_runs_effects_once() {
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let (b, set_b) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                _ = a.get() + b.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        let sum = batch(cx, || {
+            set_a.set(1);
+            set_b.set(2);
+            // signals are updated right away, effects aren't
+            assert_eq!(runs.get(), 1);
+            a.get() + b.get()
+        });
+        assert_eq!(sum, 3);
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
+
+#[test]
+fn batch_runs_memos_before_effects() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 1);
+        let (label, set_label) = create_signal(cx, "count");
+        let doubled = create_memo(cx, move |_| count.get() * 2);
+        let seen = Rc::new(Cell::new(0));
+        let runs = Rc::new(Cell::new(0));
+
+        create_isomorphic_effect(cx, {
+            let (seen, runs) = (Rc::clone(&seen), Rc::clone(&runs));
+            move |_| {
+                _ = label.get();
+                seen.set(doubled.get());
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        batch(cx, || {
+            set_label.set("total");
+            set_count.set(5);
+            // nested batches run their effects when the outermost one ends
+            batch(cx, || set_count.set(6));
+            assert_eq!(runs.get(), 1);
+        });
+        assert_eq!(seen.get(), 12);
+        assert_eq!(runs.get(), 2);
+
+        // writes after the batch run effects right away again
+        set_count.set(7);
+        assert_eq!(seen.get(), 14);
+        assert_eq!(runs.get(), 3);
+    })
+    .dispose()
+}
+
+#[test]
+fn batch_runs_effects_in_creation_order() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 0);
+
+        // disposing these frees their slots, which the effects below reuse in the opposite
+        // order, so their ids don't follow the order they were created in
+        let (_, older) = cx.run_child_scope(|cx| create_isomorphic_effect(cx, |_| {}));
+        let (_, newer) = cx.run_child_scope(|cx| create_isomorphic_effect(cx, |_| {}));
+        older.dispose();
+        newer.dispose();
+
+        let log = Rc::new(RefCell::new(Vec::new()));
+        for name in ["first", "second"] {
+            let log = Rc::clone(&log);
+            create_isomorphic_effect(cx, move |_| {
+                count.get();
+                log.borrow_mut().push(name);
+            });
+        }
+        log.borrow_mut().clear();
+
+        batch(cx, || set_count.set(1));
+        assert_eq!(*log.borrow(), ["first", "second"]);
+    })
+    .dispose()
+}
+
+#[test]
+fn large_batches_run_each_effect_once_in_creation_order() {
+    create_scope(create_runtime(), |cx| {
+        let signals = (0..10).map(|_| create_signal(cx, 0)).collect::<Vec<_>>();
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        for n in 0..5_000 {
+            let log = Rc::clone(&log);
+            let signals = signals.iter().map(|(read, _)| *read).collect::<Vec<_>>();
+            create_isomorphic_effect(cx, move |_| {
+                for signal in &signals {
+                    signal.get();
+                }
+                log.borrow_mut().push(n);
+            });
+        }
+        log.borrow_mut().clear();
+
+        batch(cx, || {
+            for (_, write) in &signals {
+                write.set(1);
+            }
+        });
+        assert_eq!(*log.borrow(), (0..5_000).collect::<Vec<_>>());
+    })
+    .dispose()
+}
+
+#[test]
+fn batch_ends_when_it_panics() {
+    create_scope(create_runtime(), |cx| {
+        let (count, set_count) = create_signal(cx, 0);
+        let runs = Rc::new(Cell::new(0));
+
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                count.get();
+                runs.set(runs.get() + 1);
+            }
+        });
+
+        let result = panic::catch_unwind(AssertUnwindSafe(|| {
+            batch(cx, || {
+                set_count.set(1);
+                panic!("failed in the middle of a batch");
+            })
+        }));
+        assert!(result.is_err());
+        // the effect queued before the panic is dropped
+        assert_eq!(runs.get(), 1);
+
+        // and writes run their effects right away again
+        set_count.set(2);
+        assert_eq!(runs.get(), 2);
+        batch(cx, || set_count.set(3));
+        assert_eq!(runs.get(), 3);
+    })
+    .dispose()
+}
@@ -0,0 +1,69 @@
+use leptos_reactive::{
+    create_runtime, create_scope, create_signal, provide_diagnostics, report_diagnostic,
+    set_component_name, use_diagnostics, DiagnosticKind,
+};
+
+#[test]
+fn collects_disposed_signal_writes() {
+    create_scope(create_runtime(), |cx| {
+        let diagnostics = provide_diagnostics(cx);
+
+        let ((_, set_count), disposer) = cx.run_child_scope(|cx| create_signal(cx, 0));
+        disposer.dispose();
+        set_count.set(1);
+        set_count.update(|n| *n += 1);
+
+        let warnings = diagnostics.of_kind(DiagnosticKind::DisposedSignal);
+        assert_eq!(warnings.len(), 2);
+        assert!(warnings[0].message.contains("Signal<i32>"));
+        assert!(diagnostics
+            .of_kind(DiagnosticKind::HydrationMiss)
+            .is_empty());
+    })
+    .dispose()
+}
+
+#[test]
+fn records_component_path() {
+    create_scope(create_runtime(), |cx| {
+        let diagnostics = provide_diagnostics(cx);
+
+        set_component_name(cx, "App");
+        cx.child_scope(|cx| {
+            set_component_name(cx, "TodoList");
+            cx.child_scope(|cx| {
+                // scopes that aren't components are skipped
+                cx.child_scope(|cx| {
+                    report_diagnostic(cx, DiagnosticKind::MissingContext, "no context");
+                });
+            });
+        });
+
+        let warnings = diagnostics.take();
+        assert_eq!(warnings.len(), 1);
+        assert_eq!(warnings[0].component.as_deref(), Some("App > TodoList"));
+        assert_eq!(
+            warnings[0].to_string(),
+            "[missing context] no context (in App > TodoList)"
+        );
+        assert!(diagnostics.entries().get().is_empty());
+    })
+    .dispose()
+}
+
+#[test]
+fn stops_collecting_when_disposed() {
+    create_scope(create_runtime(), |cx| {
+        let disposer = cx.child_scope(|cx| {
+            provide_diagnostics(cx);
+        });
+        assert!(use_diagnostics(cx).is_some());
+
+        disposer.dispose();
+        assert!(use_diagnostics(cx).is_none());
+
+        // nothing to report to
+        report_diagnostic(cx, DiagnosticKind::Other, "ignored");
+    })
+    .dispose()
+}
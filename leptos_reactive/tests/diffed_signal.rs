@@ -0,0 +1,88 @@
+use leptos_reactive::{
+    create_diffed_signal, create_effect, create_runtime, create_scope, ListPatch,
+};
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn list_patches_turn_old_list_into_new_list() {
+    let cases = [
+        (vec![1, 2, 3], vec![3, 2, 1]),
+        (vec![1, 2, 3, 4, 5], vec![5, 1, 4, 2]),
+        (vec![], vec![1, 2]),
+        (vec![1, 2], vec![]),
+        (vec![1, 2, 3], vec![4, 2, 5, 1]),
+        // duplicate keys
+        (vec![1, 1, 2], vec![2, 1, 1]),
+        (vec![1, 2, 1], vec![1]),
+        (vec![3], vec![3, 3, 3]),
+        // long lists
+        ((0..5_000).collect(), (0..5_000).rev().collect()),
+        ((0..5_000).collect(), (1..5_000).chain([0]).collect()),
+    ];
+
+    create_scope(create_runtime(), move |cx| {
+        for (old, new) in cases {
+            let list = create_diffed_signal(cx, old.clone(), |n| *n);
+            list.set(new.clone());
+
+            let mut patched = old;
+            for patch in list.patches().get() {
+                patch.apply(&mut patched);
+            }
+            assert_eq!(patched, new);
+        }
+    })
+    .dispose()
+}
+
+#[test]
+fn unchanged_list_has_no_patches() {
+    create_scope(create_runtime(), |cx| {
+        let list = create_diffed_signal(cx, vec![(1, "a"), (2, "b")], |item| item.0);
+        list.set(vec![(1, "a"), (2, "b")]);
+        assert_eq!(list.patches().get(), vec![]);
+
+        list.set(vec![(1, "a"), (2, "c")]);
+        assert_eq!(
+            list.patches().get(),
+            vec![ListPatch::Update {
+                index: 1,
+                value: (2, "c")
+            }]
+        );
+    })
+    .dispose()
+}
+
+#[test]
+fn moving_the_last_item_to_the_front_is_one_patch() {
+    create_scope(create_runtime(), |cx| {
+        let list = create_diffed_signal(cx, (1..1_000).chain([0]).collect::<Vec<_>>(), |n| *n);
+        list.set((0..1_000).collect());
+        assert_eq!(
+            list.patches().get(),
+            vec![ListPatch::Move { from: 999, to: 0 }]
+        );
+    })
+    .dispose()
+}
+
+#[test]
+fn value_and_patches_are_set_together() {
+    create_scope(create_runtime(), |cx| {
+        let list = create_diffed_signal(cx, vec![1, 2], |n| *n);
+        let seen = Rc::new(RefCell::new(Vec::new()));
+        create_effect(cx, {
+            let seen = Rc::clone(&seen);
+            move |_| {
+                let value = list.get();
+                let patches = list.patches().get();
+                seen.borrow_mut().push((value, patches.len()));
+            }
+        });
+
+        list.set(vec![2, 1, 3]);
+        assert_eq!(*seen.borrow(), vec![(vec![1, 2], 0), (vec![2, 1, 3], 2)]);
+    })
+    .dispose()
+}
@@ -1,6 +1,7 @@
 #[cfg(not(feature = "stable"))]
 use leptos_reactive::{
-    create_isomorphic_effect, create_memo, create_runtime, create_scope, create_signal,
+    create_isomorphic_effect, create_isomorphic_effect_with_cleanup, create_memo, create_runtime,
+    create_scope, create_signal,
 };
 
 #[cfg(not(feature = "stable"))]
@@ -93,3 +94,40 @@ fn untrack_mutes_effect() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn effect_cleanup_runs_before_rerun_and_on_dispose() {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    create_scope(create_runtime(), |cx| {
+        let (a, set_a) = create_signal(cx, 0);
+        let (b, set_b) = create_signal(cx, 0);
+        let log = Rc::new(RefCell::new(Vec::new()));
+
+        let disposer = cx.child_scope({
+            let log = log.clone();
+            move |cx| {
+                create_isomorphic_effect_with_cleanup(cx, move || {
+                    let value = a();
+                    log.borrow_mut().push(format!("run {value}"));
+                    let log = log.clone();
+                    move || {
+                        // reading a signal here doesn't subscribe the effect to it
+                        b();
+                        log.borrow_mut().push(format!("cleanup {value}"));
+                    }
+                });
+            }
+        });
+
+        set_a(1);
+        set_b(1);
+        disposer.dispose();
+        set_a(2);
+
+        assert_eq!(*log.borrow(), ["run 0", "cleanup 0", "run 1", "cleanup 1"]);
+    })
+    .dispose()
+}
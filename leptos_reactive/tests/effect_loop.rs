@@ -0,0 +1,103 @@
+use leptos_reactive::{
+    create_isomorphic_effect, create_memo, create_runtime, create_scope, create_signal,
+    provide_diagnostics, set_effect_run_limit, DiagnosticKind, UntrackedGettableSignal,
+};
+use std::{
+    cell::Cell,
+    panic::{catch_unwind, AssertUnwindSafe},
+    rc::Rc,
+};
+
+#[test]
+fn effects_that_write_their_own_dependencies_are_stopped() {
+    create_scope(create_runtime(), |cx| {
+        let diagnostics = provide_diagnostics(cx);
+        set_effect_run_limit(cx, 5);
+
+        let (count, set_count) = create_signal(cx, 0);
+        let doubled = create_memo(cx, move |_| count.get() * 2);
+
+        // writes to `count` through a memo that depends on it
+        create_isomorphic_effect(cx, move |_| {
+            let doubled = doubled.get();
+            set_count.set(doubled / 2 + 1);
+        });
+
+        if cfg!(debug_assertions) {
+            assert!(count.get_untracked() <= 5);
+            let cycles = diagnostics.of_kind(DiagnosticKind::EffectCycle);
+            assert!(cycles
+                .iter()
+                .any(|cycle| cycle.message.contains("writes to a signal it depends on")));
+            assert!(cycles
+                .iter()
+                .any(|cycle| cycle.message.contains("ran more than 5 times")));
+        }
+
+        // the limit starts over with the next change
+        set_count.set(100);
+        if cfg!(debug_assertions) {
+            assert!((101..=105).contains(&count.get_untracked()));
+        }
+    })
+    .dispose()
+}
+
+#[test]
+fn effects_that_run_many_times_without_a_cycle_are_only_reported() {
+    create_scope(create_runtime(), |cx| {
+        let diagnostics = provide_diagnostics(cx);
+        set_effect_run_limit(cx, 5);
+
+        let (count, set_count) = create_signal(cx, 0);
+        let seen = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let seen = Rc::clone(&seen);
+            move |_| seen.set(count.get())
+        });
+
+        // each write runs the inner effect while the outer one is still running
+        create_isomorphic_effect(cx, move |_| {
+            for n in 1..=20 {
+                set_count.set(n);
+            }
+        });
+
+        assert_eq!(seen.get(), 20);
+        if cfg!(debug_assertions) {
+            let cycles = diagnostics.of_kind(DiagnosticKind::EffectCycle);
+            assert_eq!(cycles.len(), 1);
+            assert!(cycles[0].message.contains("ran more than 5 times"));
+        }
+    })
+    .dispose()
+}
+
+#[test]
+fn effects_that_panic_are_no_longer_running() {
+    create_scope(create_runtime(), |cx| {
+        set_effect_run_limit(cx, 3);
+
+        let (count, set_count) = create_signal(cx, 0);
+        let seen = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let seen = Rc::clone(&seen);
+            move |_| {
+                let count = count.get();
+                if count == 1 {
+                    panic!("the effect failed");
+                }
+                seen.set(count);
+            }
+        });
+
+        assert!(catch_unwind(AssertUnwindSafe(|| set_count.set(1))).is_err());
+
+        // each change starts over, so the effect isn't mistaken for a cycle
+        for n in 2..=10 {
+            set_count.set(n);
+        }
+        assert_eq!(seen.get(), 10);
+    })
+    .dispose()
+}
@@ -90,3 +90,50 @@ fn memo_runs_only_when_inputs_change() {
     })
     .dispose()
 }
+
+#[cfg(not(feature = "stable"))]
+#[test]
+fn memo_with_compare_uses_the_comparator() {
+    use leptos_reactive::{create_isomorphic_effect, create_memo_with_compare};
+    use std::{cell::Cell, rc::Rc};
+
+    // doesn't implement PartialEq
+    struct Versioned {
+        version: usize,
+        items: Vec<i32>,
+    }
+
+    create_scope(create_runtime(), |cx| {
+        let (items, set_items) = create_signal(cx, (0, vec![1, 2]));
+        let versioned = create_memo_with_compare(
+            cx,
+            move |_| {
+                let (version, items) = items();
+                Versioned { version, items }
+            },
+            |prev, new| prev.version == new.version,
+        );
+        let runs = Rc::new(Cell::new(0));
+        create_isomorphic_effect(cx, {
+            let runs = Rc::clone(&runs);
+            move |_| {
+                versioned.with(|v| v.items.len());
+                runs.set(runs.get() + 1);
+            }
+        });
+        assert_eq!(runs.get(), 1);
+
+        // same version: dependents aren't notified, and the old value is kept
+        set_items.update(|(_, items)| items.push(3));
+        assert_eq!(versioned.with(|v| v.items.len()), 2);
+        assert_eq!(runs.get(), 1);
+
+        set_items.update(|(version, items)| {
+            *version += 1;
+            items.push(4);
+        });
+        assert_eq!(versioned.with(|v| v.items.len()), 4);
+        assert_eq!(runs.get(), 2);
+    })
+    .dispose()
+}
@@ -0,0 +1,45 @@
+use leptos_reactive::{create_isomorphic_effect, create_runtime, create_scope, OwnedSignal};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn owned_signal_outlives_scope() {
+    let count = OwnedSignal::new(0);
+    let runs = Rc::new(Cell::new(0));
+
+    let scope = create_scope(create_runtime(), {
+        let count = count.clone();
+        let runs = Rc::clone(&runs);
+        move |cx| {
+            let signal = count.to_signal(cx);
+            create_isomorphic_effect(cx, move |_| {
+                signal.get();
+                runs.set(runs.get() + 1);
+            });
+        }
+    });
+    assert_eq!(runs.get(), 1);
+
+    count.set(1);
+    assert_eq!(runs.get(), 2);
+
+    // the owned signal keeps working once the scope is gone
+    scope.dispose();
+    count.set(2);
+    assert_eq!(count.get(), 2);
+    assert_eq!(runs.get(), 2);
+}
+
+#[test]
+fn subscribers_can_unsubscribe_while_notified() {
+    let count = OwnedSignal::new(0);
+    let first = count.subscribe(|| {});
+    let second = Rc::new(Cell::new(Some(first)));
+    let _subscription = count.subscribe({
+        let second = Rc::clone(&second);
+        move || drop(second.take())
+    });
+
+    count.set(1);
+    count.set(2);
+    assert!(second.take().is_none());
+}
@@ -0,0 +1,63 @@
+use leptos_reactive::{
+    create_persisted_resource, create_runtime, create_scope, create_signal, provide_service,
+    CachedValue, MemoryCache, ResourceCache,
+};
+use std::{cell::Cell, rc::Rc, time::Duration};
+
+#[test]
+fn cached_values_expire() {
+    let value = CachedValue {
+        json: "1".to_string(),
+        stored_at: 0.0,
+    };
+    assert!(!value.is_fresh(Duration::from_secs(60)));
+    assert!(CachedValue::now("1".to_string()).is_fresh(Duration::from_secs(60)));
+}
+
+#[test]
+fn persisted_resource_reads_fresh_values_before_fetching() {
+    create_scope(create_runtime(), |cx| {
+        let cache = MemoryCache::default();
+        cache.set("count/1", CachedValue::now("10".to_string()));
+        cache.set(
+            "count/2",
+            CachedValue {
+                json: "20".to_string(),
+                stored_at: 0.0,
+            },
+        );
+        provide_service::<dyn ResourceCache>(cx, Rc::new(cache.clone()));
+
+        let fetches = Rc::new(Cell::new(0));
+        let (id, set_id) = create_signal(cx, 1);
+        let count = create_persisted_resource(
+            cx,
+            move || id.get(),
+            |id| format!("count/{id}"),
+            {
+                let fetches = Rc::clone(&fetches);
+                move |id| {
+                    fetches.set(fetches.get() + 1);
+                    async move { id * 100 }
+                }
+            },
+            Duration::from_secs(60),
+        );
+
+        // a fresh value is read from the cache
+        assert_eq!(count.read(), Some(10));
+        assert_eq!(fetches.get(), 0);
+
+        // an expired value is fetched again and stored
+        set_id.set(2);
+        assert_eq!(count.read(), Some(200));
+        assert_eq!(fetches.get(), 1);
+        let stored = futures::executor::block_on(cache.get("count/2")).unwrap();
+        assert_eq!(stored.json, "200");
+
+        // refetching always calls the fetcher
+        count.refetch();
+        assert_eq!(fetches.get(), 2);
+    })
+    .dispose()
+}
@@ -0,0 +1,68 @@
+use leptos_reactive::{
+    create_isomorphic_effect, create_memo, create_runtime, create_scope, create_signal,
+    set_component_name, track_renders, RenderReason,
+};
+
+#[test]
+fn counts_renders_per_component_instance() {
+    create_scope(create_runtime(), |cx| {
+        let renders = track_renders(cx);
+        let (count, set_count) = create_signal(cx, 0);
+
+        for _ in 0..2 {
+            _ = cx.child_scope(|cx| {
+                set_component_name(cx, "Row");
+                create_isomorphic_effect(cx, move |_| count.get());
+            });
+        }
+        _ = cx.child_scope(|cx| {
+            set_component_name(cx, "Footer");
+            create_isomorphic_effect(cx, |_| ());
+        });
+
+        set_count.set(1);
+
+        if cfg!(debug_assertions) {
+            assert_eq!(renders.count("Row"), 4);
+            assert_eq!(renders.count("Footer"), 1);
+            let counts = renders.counts();
+            assert_eq!(counts.len(), 3);
+            assert!(counts[..2].iter().all(|count| count.renders == 2));
+
+            renders.clear();
+            assert!(renders.renders().is_empty());
+        }
+    })
+    .dispose()
+}
+
+#[test]
+fn explains_renders_caused_by_memos() {
+    create_scope(create_runtime(), |cx| {
+        let renders = track_renders(cx);
+        let (count, set_count) = create_signal(cx, 1);
+        let set_count = set_count.with_debug_name("count");
+        let is_even = create_memo(cx, move |_| count.get() % 2 == 0);
+
+        _ = cx.child_scope(|cx| {
+            set_component_name(cx, "Parity");
+            create_isomorphic_effect(cx, move |_| is_even.get());
+        });
+        set_count.set(2);
+
+        if cfg!(debug_assertions) {
+            let renders = renders.renders();
+            assert_eq!(renders.len(), 2);
+            assert_eq!(renders[0].reason, RenderReason::Initial);
+            match &renders[1].reason {
+                RenderReason::Changed(triggers) => {
+                    assert_eq!(triggers.len(), 2);
+                    assert_eq!(triggers[0].name, None);
+                    assert_eq!(triggers[1].name.as_deref(), Some("count"));
+                }
+                reason => panic!("unexpected reason {reason:?}"),
+            }
+        }
+    })
+    .dispose()
+}
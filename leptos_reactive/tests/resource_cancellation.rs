@@ -0,0 +1,54 @@
+use leptos_reactive::{
+    create_cancellable_resource, create_runtime, create_scope, create_signal, Superseded,
+};
+use std::{cell::RefCell, rc::Rc};
+
+#[test]
+fn newer_fetches_supersede_older_ones() {
+    create_scope(create_runtime(), |cx| {
+        let fetches: Rc<RefCell<Vec<Superseded>>> = Default::default();
+        let (query, set_query) = create_signal(cx, "l".to_string());
+        let results = create_cancellable_resource(cx, move || query.get(), {
+            let fetches = Rc::clone(&fetches);
+            move |query: String, superseded| {
+                fetches.borrow_mut().push(superseded);
+                async move { query.len() }
+            }
+        });
+
+        assert_eq!(results.read(), Some(1));
+        assert!(!fetches.borrow()[0].is_superseded());
+
+        set_query.set("lep".to_string());
+        assert_eq!(results.read(), Some(3));
+        assert!(fetches.borrow()[0].is_superseded());
+        assert!(!fetches.borrow()[1].is_superseded());
+
+        results.refetch();
+        assert_eq!(fetches.borrow().len(), 3);
+        assert!(fetches.borrow()[1].is_superseded());
+        assert!(!fetches.borrow()[2].is_superseded());
+    })
+    .dispose()
+}
+
+#[test]
+fn superseded_resolves_when_a_newer_fetch_starts() {
+    create_scope(create_runtime(), |cx| {
+        let (id, set_id) = create_signal(cx, 0);
+        let handles: Rc<RefCell<Vec<Superseded>>> = Default::default();
+        let _ = create_cancellable_resource(cx, move || id.get(), {
+            let handles = Rc::clone(&handles);
+            move |id: i32, superseded| {
+                handles.borrow_mut().push(superseded);
+                async move { id }
+            }
+        });
+        set_id.set(1);
+
+        // the first fetch's handle is ready, so it can be awaited without blocking
+        let first = handles.borrow()[0].clone();
+        futures::executor::block_on(first);
+    })
+    .dispose()
+}
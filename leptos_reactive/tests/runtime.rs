@@ -0,0 +1,22 @@
+use leptos_reactive::{create_runtime, create_signal, provide_context, run_scope, use_context};
+
+#[test]
+fn disposed_runtime_does_not_leak_state() {
+    let runtime = create_runtime();
+    run_scope(runtime, |cx| {
+        provide_context(cx, String::from("first request"));
+        let (count, set_count) = create_signal(cx, 1);
+        set_count.set(2);
+        assert_eq!(count.get(), 2);
+    });
+    runtime.dispose();
+
+    // on the server, this reuses the runtime that was just disposed
+    let runtime = create_runtime();
+    run_scope(runtime, |cx| {
+        assert_eq!(use_context::<String>(cx), None);
+        let (count, _) = create_signal(cx, 0);
+        assert_eq!(count.get(), 0);
+    });
+    runtime.dispose();
+}
@@ -0,0 +1,99 @@
+use leptos_reactive::{
+    create_effect, create_isomorphic_effect, create_rw_signal, create_scope, create_scoped_runtime,
+    provide_context, provide_diagnostics, report_diagnostic, DiagnosticKind, SignalBridge,
+};
+use std::{cell::Cell, rc::Rc};
+
+#[test]
+fn diagnostics_name_their_runtime() {
+    let runtime = create_scoped_runtime("search");
+    create_scope(runtime, |cx| {
+        let diagnostics = provide_diagnostics(cx);
+        report_diagnostic(cx, DiagnosticKind::Other, "something happened");
+
+        let warnings = diagnostics.take();
+        assert_eq!(warnings[0].runtime.as_deref(), Some("search"));
+        assert_eq!(
+            warnings[0].to_string(),
+            "search: [warning] something happened"
+        );
+    })
+    .dispose();
+    runtime.dispose();
+}
+
+#[test]
+fn bridge_stops_syncing_once_disconnected() {
+    let host = create_scoped_runtime("host");
+    let widget = create_scoped_runtime("widget");
+
+    create_scope(host, move |host_cx| {
+        let count = create_rw_signal(host_cx, 0);
+        let bridge = SignalBridge::new(host_cx, count);
+        let widget_runs = Rc::new(Cell::new(0));
+
+        let disposer = create_scope(widget, {
+            let widget_runs = Rc::clone(&widget_runs);
+            move |cx| {
+                let widget_count = bridge.connect(cx);
+                create_effect(cx, move |_| {
+                    widget_count.get();
+                    widget_runs.set(widget_runs.get() + 1);
+                });
+            }
+        });
+        assert_eq!(widget_runs.get(), 1);
+
+        count.set(1);
+        assert_eq!(widget_runs.get(), 2);
+
+        // setting the same value doesn't bounce between the runtimes
+        count.set(1);
+        assert_eq!(widget_runs.get(), 2);
+
+        disposer.dispose();
+        count.set(2);
+        assert_eq!(widget_runs.get(), 2);
+    })
+    .dispose();
+
+    widget.dispose();
+    host.dispose();
+}
+
+#[test]
+fn runtimes_can_be_created_and_disposed_inside_an_effect() {
+    let host = create_scoped_runtime("host");
+    let widget = Rc::new(Cell::new(None));
+
+    create_scope(host, {
+        let widget = Rc::clone(&widget);
+        move |cx| {
+            let show = create_rw_signal(cx, true);
+            // like a `<Show/>` that mounts and removes another widget
+            create_isomorphic_effect(cx, {
+                let widget = Rc::clone(&widget);
+                move |_| {
+                    if show.get() {
+                        let runtime = create_scoped_runtime("widget");
+                        let disposer = create_scope(runtime, |cx| provide_context(cx, 42));
+                        widget.set(Some((runtime, disposer)));
+                    } else if let Some((runtime, disposer)) = widget.take() {
+                        disposer.dispose();
+                        runtime.dispose();
+                    }
+                }
+            });
+
+            let (runtime, disposer) = widget.take().unwrap();
+            assert_eq!(runtime.name().as_deref(), Some("widget"));
+            widget.set(Some((runtime, disposer)));
+
+            show.set(false);
+        }
+    })
+    .dispose();
+
+    assert!(widget.take().is_none());
+    host.dispose();
+}
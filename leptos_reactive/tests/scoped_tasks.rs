@@ -0,0 +1,155 @@
+#![cfg(feature = "ssr")]
+
+use futures::channel::oneshot;
+use leptos_reactive::{
+    create_paginated_resource, create_resource, create_runtime, run_scope_undisposed, ScopedTasks,
+    UntrackedGettableSignal,
+};
+use std::{cell::RefCell, future::Future, rc::Rc};
+
+/// Runs a test on a single thread, so that futures spawned with `spawn_local` only run when the
+/// test is waiting on something.
+fn run_local(test: impl Future<Output = ()>) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    tokio::task::LocalSet::new().block_on(&runtime, test);
+}
+
+/// Lets every spawned future run until it's waiting on something again.
+async fn settle() {
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+}
+
+#[test]
+fn finished_tasks_are_forgotten() {
+    run_local(async {
+        let (tasks, _, disposer) = run_scope_undisposed(create_runtime(), ScopedTasks::new);
+        let (first_tx, first_rx) = oneshot::channel::<()>();
+        let (second_tx, second_rx) = oneshot::channel::<()>();
+        tasks.spawn(async move {
+            _ = first_rx.await;
+        });
+        tasks.spawn(async move {
+            _ = second_rx.await;
+        });
+        settle().await;
+        assert_eq!(tasks.running(), 2);
+
+        first_tx.send(()).unwrap();
+        settle().await;
+        assert_eq!(tasks.running(), 1);
+
+        disposer.dispose();
+        settle().await;
+        assert_eq!(tasks.running(), 0);
+        assert!(second_tx.is_canceled());
+    });
+}
+
+#[test]
+fn pending_fetches_are_aborted_when_the_resource_is_disposed() {
+    run_local(async {
+        let senders: Rc<RefCell<Vec<oneshot::Sender<i32>>>> = Default::default();
+        let (resource, _, disposer) = run_scope_undisposed(create_runtime(), {
+            let senders = Rc::clone(&senders);
+            move |cx| {
+                create_resource(
+                    cx,
+                    || (),
+                    move |_| {
+                        let (tx, rx) = oneshot::channel();
+                        senders.borrow_mut().push(tx);
+                        async move { rx.await.ok() }
+                    },
+                )
+            }
+        });
+        settle().await;
+
+        // each fetch replaces the last one
+        resource.refetch();
+        resource.refetch();
+        settle().await;
+        assert_eq!(senders.borrow().len(), 3);
+        assert!(senders.borrow()[0].is_canceled());
+        assert!(senders.borrow()[1].is_canceled());
+        assert!(!senders.borrow()[2].is_canceled());
+
+        disposer.dispose();
+        settle().await;
+        assert!(senders.borrow()[2].is_canceled());
+    });
+}
+
+#[test]
+fn an_older_fetch_that_resolves_last_never_sets_the_value() {
+    run_local(async {
+        let senders: Rc<RefCell<Vec<oneshot::Sender<i32>>>> = Default::default();
+        let (resource, _, disposer) = run_scope_undisposed(create_runtime(), {
+            let senders = Rc::clone(&senders);
+            move |cx| {
+                create_resource(
+                    cx,
+                    || (),
+                    move |_| {
+                        let (tx, rx) = oneshot::channel();
+                        senders.borrow_mut().push(tx);
+                        async move { rx.await.ok() }
+                    },
+                )
+            }
+        });
+        settle().await;
+        resource.refetch();
+        settle().await;
+
+        // the newer fetch resolves first
+        let mut senders = senders.take();
+        let newer = senders.pop().unwrap();
+        let older = senders.pop().unwrap();
+        newer.send(2).unwrap();
+        settle().await;
+        assert_eq!(resource.read(), Some(Some(2)));
+        assert!(!resource.loading().get_untracked());
+
+        // and the older one can't overwrite it
+        assert!(older.send(1).is_err());
+        settle().await;
+        assert_eq!(resource.read(), Some(Some(2)));
+
+        disposer.dispose();
+    });
+}
+
+#[test]
+fn pages_that_are_loading_are_dropped_when_the_scope_is_disposed() {
+    run_local(async {
+        let senders: Rc<RefCell<Vec<oneshot::Sender<Vec<i32>>>>> = Default::default();
+        let (posts, _, disposer) = run_scope_undisposed(create_runtime(), {
+            let senders = Rc::clone(&senders);
+            move |cx| {
+                create_paginated_resource(cx, move |_| {
+                    let (tx, rx) = oneshot::channel();
+                    senders.borrow_mut().push(tx);
+                    async move { rx.await.unwrap_or_default() }
+                })
+            }
+        });
+        settle().await;
+        senders.borrow_mut().remove(0).send(vec![1]).unwrap();
+        settle().await;
+        assert_eq!(posts.read(), Some(vec![1]));
+
+        posts.load_more();
+        settle().await;
+        assert!(posts.is_loading_more());
+
+        // e.g., the user navigated away before the page loaded
+        disposer.dispose();
+        settle().await;
+        assert!(senders.borrow()[0].is_canceled());
+    });
+}
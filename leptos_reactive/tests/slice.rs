@@ -0,0 +1,60 @@
+use leptos_reactive::{
+    create_isomorphic_effect, create_runtime, create_rw_signal, create_scope, create_slice,
+};
+use std::{cell::Cell, rc::Rc};
+
+#[derive(Clone, Debug, Default)]
+struct State {
+    name: String,
+    count: i32,
+}
+
+#[test]
+fn slices_only_notify_when_their_part_changes() {
+    create_scope(create_runtime(), |cx| {
+        let state = create_rw_signal(cx, State::default());
+        let (name, set_name) = create_slice(
+            cx,
+            state,
+            |state| state.name.clone(),
+            |state, name| state.name = name,
+        );
+        let (count, set_count) = create_slice(
+            cx,
+            state,
+            |state| state.count,
+            |state, count| state.count = count,
+        );
+        let name_runs = Rc::new(Cell::new(0));
+        let count_runs = Rc::new(Cell::new(0));
+
+        create_isomorphic_effect(cx, {
+            let name_runs = Rc::clone(&name_runs);
+            move |_| {
+                name.get();
+                name_runs.set(name_runs.get() + 1);
+            }
+        });
+        create_isomorphic_effect(cx, {
+            let count_runs = Rc::clone(&count_runs);
+            move |_| {
+                count.get();
+                count_runs.set(count_runs.get() + 1);
+            }
+        });
+
+        set_count.set(1);
+        assert_eq!(count.get(), 1);
+        assert_eq!((name_runs.get(), count_runs.get()), (1, 2));
+
+        set_name.set("Alice".to_string());
+        assert_eq!(name.get(), "Alice");
+        assert_eq!(state.with(|state| state.name.clone()), "Alice");
+        assert_eq!((name_runs.get(), count_runs.get()), (2, 2));
+
+        // setting the same value again doesn't notify either slice
+        set_count.set(1);
+        assert_eq!((name_runs.get(), count_runs.get()), (2, 2));
+    })
+    .dispose()
+}
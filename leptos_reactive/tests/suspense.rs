@@ -0,0 +1,23 @@
+use leptos_reactive::{create_runtime, create_scope, ResourceId, SuspenseContext};
+
+#[test]
+fn suspense_context_lists_pending_resources() {
+    create_scope(create_runtime(), |cx| {
+        let suspense = SuspenseContext::new(cx);
+        let id = ResourceId::default();
+
+        // a resource that's loading again is only listed once
+        suspense.increment_resource(id, Some("user profile".into()));
+        suspense.increment_resource(id, Some("user profile".into()));
+        assert_eq!(suspense.pending_resources.get(), 2);
+        assert_eq!(suspense.pending().len(), 1);
+        assert_eq!(suspense.pending()[0].name.as_deref(), Some("user profile"));
+
+        suspense.decrement_resource(id);
+        assert_eq!(suspense.pending().len(), 1);
+        suspense.decrement_resource(id);
+        assert!(suspense.pending().is_empty());
+        assert!(suspense.ready());
+    })
+    .dispose()
+}
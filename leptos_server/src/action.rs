@@ -1,6 +1,6 @@
 use crate::{ServerFn, ServerFnError};
 use leptos_reactive::{
-    create_rw_signal, spawn_local, store_value, ReadSignal, RwSignal, Scope, StoredValue,
+    create_rw_signal, store_value, ReadSignal, RwSignal, Scope, ScopedTasks, StoredValue,
 };
 use std::{future::Future, pin::Pin, rc::Rc};
 
@@ -155,6 +155,8 @@ where
     I: 'static,
     O: 'static,
 {
+    /// The calls that are still running, which are dropped if the action's scope is disposed.
+    tasks: ScopedTasks,
     /// How many times the action has successfully resolved.
     pub version: RwSignal<usize>,
     /// The current argument that was dispatched to the `async` function.
@@ -182,13 +184,13 @@ where
         let pending = self.pending;
         let value = self.value;
         pending.set(true);
-        spawn_local(async move {
+        self.tasks.spawn(async move {
             let new_value = fut.await;
             input.set(None);
             pending.set(false);
             value.set(Some(new_value));
             version.update(|n| *n += 1);
-        })
+        });
     }
 }
 
@@ -284,6 +286,7 @@ where
     Action(store_value(
         cx,
         ActionState {
+            tasks: ScopedTasks::new(cx),
             version,
             url: None,
             input,
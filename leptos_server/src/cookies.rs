@@ -0,0 +1,428 @@
+use leptos_reactive::*;
+use std::{borrow::Cow, cell::RefCell, collections::HashMap, rc::Rc, str::FromStr};
+
+/// The cookies sent with the current request, along with any `Set-Cookie` headers
+/// that should be added to the response.
+///
+/// The server integrations provide a `CookieJar` as context while rendering the app and
+/// while running server functions. Components will usually use [use_cookie] instead.
+#[derive(Clone, Debug, Default)]
+pub struct CookieJar {
+    cookies: Rc<RefCell<HashMap<String, String>>>,
+    set_cookies: Rc<RefCell<Vec<String>>>,
+}
+
+impl CookieJar {
+    /// Creates a jar from the value of a `Cookie` request header. Percent-encoded values
+    /// are decoded.
+    /// ```
+    /// # use leptos_server::CookieJar;
+    /// let jar = CookieJar::from_header(Some("theme=dark; lang=en; name=Ada%20L."));
+    /// assert_eq!(jar.get("theme"), Some("dark".to_string()));
+    /// assert_eq!(jar.get("name"), Some("Ada L.".to_string()));
+    /// assert_eq!(jar.get("session"), None);
+    /// ```
+    pub fn from_header(header: Option<&str>) -> Self {
+        Self {
+            cookies: Rc::new(RefCell::new(header.map(parse_cookies).unwrap_or_default())),
+            set_cookies: Default::default(),
+        }
+    }
+
+    /// Returns the value of a cookie, including any value set while handling this request.
+    pub fn get(&self, name: &str) -> Option<String> {
+        self.cookies.borrow().get(name).cloned()
+    }
+
+    /// Sets a cookie, queuing a `Set-Cookie` header for the response.
+    pub fn set(&self, cookie: Cookie) {
+        self.cookies
+            .borrow_mut()
+            .insert(cookie.name.clone(), cookie.value.clone());
+        self.set_cookies.borrow_mut().push(cookie.to_header_value());
+    }
+
+    /// Removes a cookie, queuing a `Set-Cookie` header that expires it.
+    pub fn remove(&self, name: &str, options: CookieOptions) {
+        self.cookies.borrow_mut().remove(name);
+        let cookie = Cookie {
+            name: name.to_string(),
+            value: String::new(),
+            options: CookieOptions {
+                max_age: Some(0),
+                ..options
+            },
+        };
+        self.set_cookies.borrow_mut().push(cookie.to_header_value());
+    }
+
+    /// Takes the values of the `Set-Cookie` headers queued so far. Server integrations call
+    /// this when building the response.
+    pub fn take_set_cookie_headers(&self) -> Vec<String> {
+        std::mem::take(&mut *self.set_cookies.borrow_mut())
+    }
+}
+
+/// The `SameSite` attribute of a cookie.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum SameSite {
+    /// Only sent with same-site requests.
+    Strict,
+    /// Sent with same-site requests and top-level navigations. (The browser default.)
+    Lax,
+    /// Sent with all requests; requires `Secure`.
+    None,
+}
+
+/// Attributes that control how the browser stores and sends a cookie.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CookieOptions {
+    /// The `Path` attribute. Defaults to `/`.
+    pub path: Option<String>,
+    /// The `Domain` attribute.
+    pub domain: Option<String>,
+    /// The `Max-Age` attribute, in seconds. If `None`, the cookie lasts for the browser session.
+    pub max_age: Option<i64>,
+    /// Whether to set `Secure`.
+    pub secure: bool,
+    /// Whether to set `HttpOnly`. Note that `HttpOnly` cookies can only be set by the server.
+    pub http_only: bool,
+    /// The `SameSite` attribute.
+    pub same_site: Option<SameSite>,
+}
+
+impl Default for CookieOptions {
+    fn default() -> Self {
+        Self {
+            path: Some("/".to_string()),
+            domain: None,
+            max_age: None,
+            secure: false,
+            http_only: false,
+            same_site: Some(SameSite::Lax),
+        }
+    }
+}
+
+/// A cookie to be set on the response, or in the browser.
+///
+/// Characters that aren't allowed in a cookie's value, like `;`, `,`, whitespace and
+/// non-ASCII characters, are percent-encoded, and decoded again when the cookie is read with
+/// a [CookieJar] or [use_cookie]. The name must be a token, as defined by
+/// [RFC 6265](https://www.rfc-editor.org/rfc/rfc6265#section-4.1.1), and the `Path` and
+/// `Domain` can't contain `;` or control characters.
+/// ```
+/// # use leptos_server::*;
+/// let cookie = Cookie::new("theme", "dark").with_options(CookieOptions {
+///     max_age: Some(3600),
+///     ..Default::default()
+/// });
+/// assert_eq!(cookie.to_header_value(), "theme=dark; Path=/; Max-Age=3600; SameSite=Lax");
+///
+/// let cookie = Cookie::new("name", "Ada; Path=/admin");
+/// assert_eq!(cookie.to_header_value(), "name=Ada%3B%20Path=/admin; Path=/; SameSite=Lax");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cookie {
+    /// The cookie's name.
+    pub name: String,
+    /// The cookie's value.
+    pub value: String,
+    /// The cookie's attributes.
+    pub options: CookieOptions,
+}
+
+impl Cookie {
+    /// Creates a cookie with the default [CookieOptions].
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            value: value.into(),
+            options: CookieOptions::default(),
+        }
+    }
+
+    /// Replaces the cookie's attributes.
+    pub fn with_options(mut self, options: CookieOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Serializes the cookie as the value of a `Set-Cookie` header (or of `document.cookie`).
+    ///
+    /// # Panics
+    /// Panics if the name isn't a valid cookie name, or the `Path` or `Domain` contains `;` or
+    /// control characters, since they could add attributes or headers to the response.
+    pub fn to_header_value(&self) -> String {
+        let options = &self.options;
+        assert!(
+            is_valid_name(&self.name),
+            "`{}` is not a valid cookie name",
+            self.name.escape_debug()
+        );
+        for attribute in [&options.path, &options.domain].into_iter().flatten() {
+            assert!(
+                is_valid_attribute(attribute),
+                "the attribute `{}` of cookie `{}` contains `;` or control characters",
+                attribute.escape_debug(),
+                self.name
+            );
+        }
+
+        let mut header = format!("{}={}", self.name, encode_value(&self.value));
+        if let Some(path) = &options.path {
+            header.push_str("; Path=");
+            header.push_str(path);
+        }
+        if let Some(domain) = &options.domain {
+            header.push_str("; Domain=");
+            header.push_str(domain);
+        }
+        if let Some(max_age) = options.max_age {
+            header.push_str(&format!("; Max-Age={max_age}"));
+        }
+        if options.secure {
+            header.push_str("; Secure");
+        }
+        if options.http_only {
+            header.push_str("; HttpOnly");
+        }
+        match options.same_site {
+            Some(SameSite::Strict) => header.push_str("; SameSite=Strict"),
+            Some(SameSite::Lax) => header.push_str("; SameSite=Lax"),
+            Some(SameSite::None) => header.push_str("; SameSite=None"),
+            None => {}
+        }
+        header
+    }
+}
+
+pub(crate) fn parse_cookies(header: &str) -> HashMap<String, String> {
+    header
+        .split(';')
+        .filter_map(|pair| {
+            let (name, value) = pair.split_once('=')?;
+            Some((
+                name.trim().to_string(),
+                decode_value(value.trim().trim_matches('"')),
+            ))
+        })
+        .collect()
+}
+
+/// Whether the name is a token: visible ASCII characters other than separators.
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|b| b.is_ascii_graphic() && !b"()<>@,;:\\\"/[]?={}".contains(&b))
+}
+
+/// Whether the value of an attribute can't end the attribute, or the header.
+fn is_valid_attribute(value: &str) -> bool {
+    value.bytes().all(|b| !b.is_ascii_control() && b != b';')
+}
+
+/// Percent-encodes the bytes that aren't allowed in a cookie's value, and `%` itself.
+fn encode_value(value: &str) -> Cow<'_, str> {
+    // `cookie-octet` in RFC 6265
+    let is_allowed = |b: u8| b.is_ascii_graphic() && !b"\",;\\%".contains(&b);
+    if value.bytes().all(is_allowed) {
+        return Cow::Borrowed(value);
+    }
+    let mut encoded = String::with_capacity(value.len());
+    for b in value.bytes() {
+        if is_allowed(b) {
+            encoded.push(b as char);
+        } else {
+            encoded.push_str(&format!("%{b:02X}"));
+        }
+    }
+    Cow::Owned(encoded)
+}
+
+/// Decodes a value encoded by [encode_value]. Anything that isn't a valid escape is kept
+/// as it is.
+fn decode_value(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = (bytes[i] == b'%')
+            .then(|| value.get(i + 1..i + 3))
+            .flatten()
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .and_then(|hex| u8::from_str_radix(hex, 16).ok());
+        match escaped {
+            Some(b) => {
+                decoded.push(b);
+                i += 3;
+            }
+            None => {
+                decoded.push(bytes[i]);
+                i += 1;
+            }
+        }
+    }
+    String::from_utf8(decoded)
+        .unwrap_or_else(|e| String::from_utf8_lossy(e.as_bytes()).into_owned())
+}
+
+/// Returns a signal with the value of a cookie, and a setter that updates it.
+///
+/// During server rendering, the value is read from the request's `Cookie` header, and setting it
+/// queues a `Set-Cookie` header on the response. In the browser, the value is read from and
+/// written to `document.cookie`. Setting `None` removes the cookie.
+///
+/// The same cookie can be read from a server function using the [CookieJar] in its [Scope].
+/// ```
+/// # use leptos::*;
+/// # if false { // can't access cookies in doctests
+/// # run_scope(create_runtime(), |cx| {
+/// let (theme, set_theme) = use_cookie(cx, "theme");
+/// let toggle = move |_: ()| {
+///     let next = if theme.get().as_deref() == Some("dark") { "light" } else { "dark" };
+///     set_theme.set(Some(next.to_string()));
+/// };
+/// # });
+/// # }
+/// ```
+pub fn use_cookie(
+    cx: Scope,
+    name: &str,
+) -> (ReadSignal<Option<String>>, SignalSetter<Option<String>>) {
+    use_cookie_with_options(cx, name, CookieOptions::default())
+}
+
+/// Like [use_cookie], but sets the cookie with the given attributes.
+pub fn use_cookie_with_options(
+    cx: Scope,
+    name: &str,
+    options: CookieOptions,
+) -> (ReadSignal<Option<String>>, SignalSetter<Option<String>>) {
+    create_cookie_signal(cx, name, options)
+}
+
+/// Creates a signal whose value is stored in a cookie, parsing it with [FromStr] and
+/// storing it with [ToString]. This is useful for preferences like a theme or locale,
+/// which should be the same when the page is rendered on the server and when it hydrates.
+///
+/// During server rendering, the initial value is read from the request's `Cookie` header,
+/// and setting it queues a `Set-Cookie` header on the response, so that the browser sees
+/// the new value by the time the app hydrates. In the browser, the value is read from
+/// and written to `document.cookie`. Setting `None` removes the cookie. A cookie whose
+/// value can't be parsed is treated as missing.
+///
+/// The stored value is percent-encoded where it contains characters that aren't allowed
+/// in cookies. Cookies with [CookieOptions::http_only] set can't be read in the
+/// browser, so they will always be `None` after hydration.
+/// ```
+/// # use leptos::*;
+/// #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+/// enum Theme {
+///     Light,
+///     Dark,
+/// }
+///
+/// impl std::str::FromStr for Theme {
+///     type Err = ();
+///
+///     fn from_str(s: &str) -> Result<Self, ()> {
+///         match s {
+///             "light" => Ok(Theme::Light),
+///             "dark" => Ok(Theme::Dark),
+///             _ => Err(()),
+///         }
+///     }
+/// }
+///
+/// impl std::fmt::Display for Theme {
+///     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+///         f.write_str(match self {
+///             Theme::Light => "light",
+///             Theme::Dark => "dark",
+///         })
+///     }
+/// }
+///
+/// # if false { // can't access cookies in doctests
+/// # run_scope(create_runtime(), |cx| {
+/// let (theme, set_theme) = create_cookie_signal::<Theme>(
+///     cx,
+///     "theme",
+///     CookieOptions {
+///         max_age: Some(60 * 60 * 24 * 365),
+///         ..Default::default()
+///     },
+/// );
+/// let theme = move || theme.get().unwrap_or(Theme::Light);
+/// set_theme.set(Some(Theme::Dark));
+/// # });
+/// # }
+/// ```
+pub fn create_cookie_signal<T>(
+    cx: Scope,
+    name: &str,
+    options: CookieOptions,
+) -> (ReadSignal<Option<T>>, SignalSetter<Option<T>>)
+where
+    T: FromStr + ToString + 'static,
+{
+    let name = name.to_string();
+    let initial = read_cookie(cx, &name).and_then(|value| value.parse().ok());
+    let (value, set_value) = create_signal(cx, initial);
+
+    let setter = SignalSetter::map(cx, move |new_value: Option<T>| {
+        let stored = new_value.as_ref().map(T::to_string);
+        write_cookie(cx, &name, stored.as_deref(), &options);
+        set_value.set(new_value);
+    });
+
+    (value, setter)
+}
+
+#[cfg(feature = "ssr")]
+fn read_cookie(cx: Scope, name: &str) -> Option<String> {
+    use_context::<CookieJar>(cx).and_then(|jar| jar.get(name))
+}
+
+#[cfg(feature = "ssr")]
+fn write_cookie(cx: Scope, name: &str, value: Option<&str>, options: &CookieOptions) {
+    match use_context::<CookieJar>(cx) {
+        Some(jar) => match value {
+            Some(value) => jar.set(Cookie::new(name, value).with_options(options.clone())),
+            None => jar.remove(name, options.clone()),
+        },
+        None => log::warn!(
+            "Setting cookie `{name}` during server rendering, but no CookieJar was provided. \
+             The Set-Cookie header will not be sent."
+        ),
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+fn read_cookie(_cx: Scope, name: &str) -> Option<String> {
+    use leptos_dom::wasm_bindgen::JsCast;
+
+    let cookies = leptos_dom::document()
+        .unchecked_into::<leptos_dom::web_sys::HtmlDocument>()
+        .cookie()
+        .ok()?;
+    parse_cookies(&cookies).remove(name)
+}
+
+#[cfg(not(feature = "ssr"))]
+fn write_cookie(_cx: Scope, name: &str, value: Option<&str>, options: &CookieOptions) {
+    use leptos_dom::wasm_bindgen::JsCast;
+
+    let cookie = match value {
+        Some(value) => Cookie::new(name, value).with_options(options.clone()),
+        None => Cookie::new(name, "").with_options(CookieOptions {
+            max_age: Some(0),
+            ..options.clone()
+        }),
+    };
+    _ = leptos_dom::document()
+        .unchecked_into::<leptos_dom::web_sys::HtmlDocument>()
+        .set_cookie(&cookie.to_header_value());
+}
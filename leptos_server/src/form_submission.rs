@@ -0,0 +1,284 @@
+use crate::{NestedQuery, QueryCodec, QueryError, ServerFnError};
+use leptos_reactive::{create_resource, Scope, Serializable, SerializationError};
+use serde::de::DeserializeOwned;
+use std::{fmt, rc::Rc, str::FromStr};
+
+/// The name of the cookie that carries the id of a stored [FormSubmission] to the next page.
+#[cfg(feature = "ssr")]
+const COOKIE_NAME: &str = "leptos_form_submission";
+
+/// How long a submission is kept for the page the browser is redirected to, in seconds.
+#[cfg(feature = "ssr")]
+const MAX_AGE: u64 = 60;
+
+/// The most submissions kept at once. When there are more, the oldest are forgotten.
+#[cfg(feature = "ssr")]
+const MAX_STORED: usize = 10_000;
+
+/// Fields whose names contain any of these are never stored, so that passwords and other
+/// secrets aren't kept after the request, or sent back to the browser.
+#[cfg(feature = "ssr")]
+const SENSITIVE_FIELDS: &[&str] = &[
+    "password", "passwd", "secret", "token", "card", "cvc", "cvv", "ssn",
+];
+
+#[cfg(feature = "ssr")]
+lazy_static::lazy_static! {
+    static ref SUBMISSIONS: std::sync::Mutex<std::collections::HashMap<String, (std::time::Instant, FormSubmission)>> =
+        Default::default();
+    /// Signs the ids of stored submissions. Submissions only live in this process's memory, so
+    /// a key generated when it starts is enough.
+    static ref SIGNING_KEY: [u8; 32] = {
+        let mut key = [0u8; 32];
+        getrandom::getrandom(&mut key).expect("could not generate a signing key");
+        key
+    };
+}
+
+/// A call to a server function made by submitting a `<form>` without JavaScript, carried over
+/// to the page the browser is redirected back to.
+///
+/// When a browser submits a `<form>` without JavaScript, the server integrations run the server
+/// function and then redirect back to the page the form was on. They keep what was submitted,
+/// and what the server function returned, in memory on the server for a minute, and send the
+/// browser only a signed, `HttpOnly` and `Secure` cookie with its id. The `<ActionForm/>` on that
+/// page reads the submission while it's rendered, so it can show the same errors and values it
+/// would have shown if the form had been submitted with JavaScript.
+///
+/// Fields that look like passwords or other secrets, like `password` or `card_number`, are
+/// dropped before the submission is stored. Because submissions are kept in memory, they're only
+/// found if the redirected request is handled by the same server process.
+/// ```
+/// # use leptos_server::*;
+/// let submission = FormSubmission {
+///     url: "/api/add_todo".to_string(),
+///     input: "title=Buy+milk&due=".to_string(),
+///     output: Err(ServerFnError::ServerError("`due` is required".to_string())),
+/// };
+/// let parsed = submission.to_string().parse::<FormSubmission>().unwrap();
+///
+/// assert_eq!(parsed.field("title").as_deref(), Some("Buy milk"));
+/// assert_eq!(parsed.field("due").as_deref(), Some(""));
+/// assert!(matches!(parsed.output, Err(ServerFnError::ServerError(_))));
+/// ```
+#[derive(Clone, Debug)]
+pub struct FormSubmission {
+    /// The path the form was submitted to, which is the [Action::url](crate::Action::url) of
+    /// the server function's action.
+    pub url: String,
+    /// The form's fields, URL-encoded as they were submitted.
+    pub input: String,
+    /// What the server function returned, serialized as JSON, or its error.
+    pub output: Result<String, ServerFnError>,
+}
+
+impl FormSubmission {
+    /// The value that was submitted for the form field with the given `name`, if there was one.
+    pub fn field(&self, name: &str) -> Option<String> {
+        form_urlencoded::parse(self.input.as_bytes())
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.into_owned())
+    }
+
+    /// Decodes the submitted fields into the server function's arguments, in the same way the
+    /// server did.
+    pub fn input<I: DeserializeOwned>(&self) -> Result<I, QueryError> {
+        NestedQuery::decode(&self.input)
+    }
+
+    /// Whether a request to a server function with the given `Content-Type` and `Accept` headers
+    /// is a `<form>` submitted by a browser without JavaScript, which is redirected back to a page
+    /// that can show the submission. Other clients are answered with the result, or its error.
+    /// ```
+    /// # use leptos_server::*;
+    /// assert!(FormSubmission::is_browser_form(
+    ///     Some("application/x-www-form-urlencoded"),
+    ///     Some("text/html,application/xhtml+xml,*/*;q=0.8"),
+    /// ));
+    /// assert!(!FormSubmission::is_browser_form(
+    ///     Some("application/x-www-form-urlencoded"),
+    ///     Some("*/*"),
+    /// ));
+    /// assert!(!FormSubmission::is_browser_form(Some("multipart/form-data"), Some("text/html")));
+    /// ```
+    pub fn is_browser_form(content_type: Option<&str>, accept: Option<&str>) -> bool {
+        content_type.map_or(false, |content_type| {
+            content_type.starts_with("application/x-www-form-urlencoded")
+        }) && accept.map_or(false, |accept| accept.contains("text/html"))
+    }
+
+    /// Keeps the submission on the server for a minute, without any sensitive fields, and queues
+    /// a cookie with its signed id, which is read on the page the browser is redirected to.
+    /// Called by the server integrations.
+    #[cfg(feature = "ssr")]
+    pub fn store(mut self, jar: &crate::CookieJar) {
+        use std::time::{Duration, Instant};
+
+        self.input = without_sensitive_fields(&self.input);
+
+        let mut id = [0u8; 16];
+        getrandom::getrandom(&mut id).expect("could not generate a form submission id");
+        let id: String = id.iter().map(|byte| format!("{byte:02x}")).collect();
+
+        let now = Instant::now();
+        let mut submissions = SUBMISSIONS.lock().unwrap();
+        submissions.retain(|_, (stored_at, _)| {
+            now.duration_since(*stored_at) < Duration::from_secs(MAX_AGE)
+        });
+        if submissions.len() >= MAX_STORED {
+            let oldest = submissions
+                .iter()
+                .min_by_key(|(_, (stored_at, _))| *stored_at)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                submissions.remove(&oldest);
+            }
+        }
+        submissions.insert(id.clone(), (now, self));
+
+        jar.set(
+            crate::Cookie::new(COOKIE_NAME, crate::sign_cookie_value(&*SIGNING_KEY, &id))
+                .with_options(cookie_options()),
+        );
+    }
+
+    /// Removes the submission to `url` whose id the current request's cookie carries, if it's
+    /// still stored.
+    #[cfg(feature = "ssr")]
+    fn take(cx: Scope, url: &str) -> Option<Self> {
+        let jar = leptos_reactive::use_context::<crate::CookieJar>(cx)?;
+        let id = crate::verify_cookie_value(&*SIGNING_KEY, &jar.get(COOKIE_NAME)?)?;
+
+        let mut submissions = SUBMISSIONS.lock().unwrap();
+        match submissions.get(&id) {
+            Some((stored_at, submission))
+                if stored_at.elapsed().as_secs() < MAX_AGE && submission.url == url => {}
+            _ => return None,
+        }
+        let (_, submission) = submissions.remove(&id)?;
+        jar.remove(COOKIE_NAME, cookie_options());
+        Some(submission)
+    }
+}
+
+impl fmt::Display for FormSubmission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut serializer = form_urlencoded::Serializer::new(String::new());
+        serializer
+            .append_pair("url", &self.url)
+            .append_pair("input", &self.input);
+        match &self.output {
+            Ok(output) => serializer.append_pair("output", output),
+            Err(e) => {
+                serializer.append_pair("error", &serde_json::to_string(e).map_err(|_| fmt::Error)?)
+            }
+        };
+        f.write_str(&serializer.finish())
+    }
+}
+
+impl FromStr for FormSubmission {
+    type Err = QueryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (mut url, mut input, mut output) = (None, None, None);
+        for (key, value) in form_urlencoded::parse(s.as_bytes()) {
+            match key.as_ref() {
+                "url" => url = Some(value.into_owned()),
+                "input" => input = Some(value.into_owned()),
+                "output" => output = Some(Ok(value.into_owned())),
+                "error" => {
+                    output = Some(Err(serde_json::from_str(&value)
+                        .map_err(|e| QueryError::Decode(e.to_string()))?))
+                }
+                _ => {}
+            }
+        }
+        match (url, input, output) {
+            (Some(url), Some(input), Some(output)) => Ok(Self { url, input, output }),
+            _ => Err(QueryError::Decode("incomplete form submission".to_string())),
+        }
+    }
+}
+
+/// The submission, if any, that the server sends along with the page, so that the form is
+/// hydrated with the same values it was rendered with.
+#[derive(Clone, Debug)]
+struct RenderedSubmission(Option<FormSubmission>);
+
+impl Serializable for RenderedSubmission {
+    fn to_json(&self) -> Result<String, SerializationError> {
+        serde_json::to_string(&self.0.as_ref().map(ToString::to_string))
+            .map_err(|e| SerializationError::Serialize(Rc::new(e)))
+    }
+
+    fn from_json(json: &str) -> Result<Self, SerializationError> {
+        let submission: Option<String> =
+            serde_json::from_str(json).map_err(|e| SerializationError::Deserialize(Rc::new(e)))?;
+        submission
+            .map(|submission| submission.parse())
+            .transpose()
+            .map(RenderedSubmission)
+            .map_err(|e| SerializationError::Deserialize(Rc::new(e)))
+    }
+}
+
+/// Returns the [FormSubmission] for the server function at the path `url`, if the page was
+/// rendered after submitting a form to it without JavaScript.
+///
+/// The submission is taken from the server's store while rendering, so it's only returned once.
+/// It's sent along with the page as a resource, so that the same view is rendered while hydrating,
+/// as long as that resource has arrived. Sensitive fields are never included.
+pub fn use_form_submission(cx: Scope, url: &str) -> Option<FormSubmission> {
+    #[cfg(feature = "ssr")]
+    let submission = FormSubmission::take(cx, url);
+    #[cfg(not(feature = "ssr"))]
+    let submission: Option<FormSubmission> = None;
+
+    let rendered = create_resource(cx, || (), {
+        let submission = submission.clone();
+        move |_| {
+            let submission = submission.clone();
+            async move { RenderedSubmission(submission) }
+        }
+    });
+
+    if cfg!(feature = "ssr") {
+        submission
+    } else {
+        rendered
+            .with(|rendered| rendered.0.clone())
+            .flatten()
+            .filter(|submission| submission.url == url)
+    }
+}
+
+/// Whether a field's value shouldn't be kept, because its name suggests a password or another
+/// secret.
+#[cfg(feature = "ssr")]
+fn is_sensitive(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    SENSITIVE_FIELDS.iter().any(|field| name.contains(field))
+}
+
+/// Removes sensitive fields from URL-encoded form input.
+#[cfg(feature = "ssr")]
+fn without_sensitive_fields(input: &str) -> String {
+    let mut serializer = form_urlencoded::Serializer::new(String::new());
+    for (key, value) in form_urlencoded::parse(input.as_bytes()) {
+        if !is_sensitive(&key) {
+            serializer.append_pair(&key, &value);
+        }
+    }
+    serializer.finish()
+}
+
+#[cfg(feature = "ssr")]
+fn cookie_options() -> crate::CookieOptions {
+    crate::CookieOptions {
+        max_age: Some(MAX_AGE as i64),
+        secure: true,
+        http_only: true,
+        ..Default::default()
+    }
+}
@@ -63,8 +63,12 @@
 //!   This should be fairly obvious: we have to serialize arguments to send them to the server, and we
 //!   need to deserialize the result to return it to the client.
 //! - **Arguments must be implement [serde::Serialize].** They are serialized as an `application/x-www-form-urlencoded`
-//!   form data using [`serde_urlencoded`](https://docs.rs/serde_urlencoded/latest/serde_urlencoded/) or as `application/cbor`
-//!   using [`cbor`](https://docs.rs/cbor/latest/cbor/).
+//!   form data using [NestedQuery], which supports lists and nested structs, or in a binary
+//!   [Encoding] chosen with the macro's third argument, e.g. `#[server(MyFn, "/api", "Cbor")]`:
+//!   `application/cbor` using [`ciborium`](https://docs.rs/ciborium/latest/ciborium/), or
+//!   `application/bincode` using [`bincode`](https://docs.rs/bincode/latest/bincode/). Binary
+//!   encodings avoid the cost of JSON for large arguments and results, like file chunks.
+//!   With the default encoding, a blank form field is passed as `Some("")`, not `None`.
 //! - **The [Scope](leptos_reactive::Scope) comes from the server.** Optionally, the first argument of a server function
 //!   can be a Leptos [Scope](leptos_reactive::Scope). This scope can be used to inject dependencies like the HTTP request
 //!   or response or other server-only dependencies, but it does *not* have access to reactive state that exists in the client.
@@ -83,9 +87,21 @@ use syn::{
 use thiserror::Error;
 
 mod action;
+mod cookies;
+mod form_submission;
 mod multi_action;
+mod query;
+mod redirect;
+#[cfg(feature = "ssr")]
+mod session;
 pub use action::*;
+pub use cookies::*;
+pub use form_submission::*;
 pub use multi_action::*;
+pub use query::*;
+pub use redirect::*;
+#[cfg(feature = "ssr")]
+pub use session::*;
 
 #[cfg(any(feature = "ssr", doc))]
 use std::{
@@ -100,7 +116,18 @@ type ServerFnTraitObj = dyn Fn(Scope, &[u8]) -> Pin<Box<dyn Future<Output = Resu
 
 #[cfg(any(feature = "ssr", doc))]
 lazy_static::lazy_static! {
-    static ref REGISTERED_SERVER_FUNCTIONS: Arc<RwLock<HashMap<&'static str, Arc<ServerFnTraitObj>>>> = Default::default();
+    static ref REGISTERED_SERVER_FUNCTIONS: Arc<RwLock<ServerFnRegistry>> = Default::default();
+}
+
+/// The server functions that have been registered, by the full path the client calls, and by
+/// their URL as long as only one of them has it.
+#[cfg(any(feature = "ssr", doc))]
+#[derive(Default)]
+struct ServerFnRegistry {
+    by_path: HashMap<String, Arc<ServerFnTraitObj>>,
+    /// Each URL with the full path of its server function, or [None] once server functions of
+    /// different apps have been registered with it, so that it can't be used to find either.
+    by_url: HashMap<&'static str, Option<String>>,
 }
 
 /// A dual type to hold the possible Response datatypes
@@ -157,22 +184,65 @@ pub enum Payload {
 ///     }
 /// }
 /// ```
+///
+/// The `path` can be either the full path the client calls, which is the server function's
+/// [prefix](ServerFn::prefix) followed by its [URL](ServerFn::url), or just its URL. When one
+/// server hosts several apps, two server functions can have the same URL under different
+/// prefixes, so only the full path can find them; their URL finds neither.
 #[cfg(any(feature = "ssr", doc))]
 pub fn server_fn_by_path(path: &str) -> Option<Arc<ServerFnTraitObj>> {
-    REGISTERED_SERVER_FUNCTIONS
-        .read()
-        .ok()
-        .and_then(|fns| fns.get(path).cloned())
+    let fns = REGISTERED_SERVER_FUNCTIONS.read().ok()?;
+    let path = match fns.by_url.get(path) {
+        Some(Some(full_path)) => full_path.as_str(),
+        _ => path,
+    };
+    fns.by_path.get(path).cloned()
 }
 
 /// Holds the current options for encoding types.
 /// More could be added, but they need to be serde
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Encoding {
     /// A Binary Encoding Scheme Called Cbor
     Cbor,
     /// The Default URL-encoded encoding method
     Url,
+    /// A compact binary encoding for Rust types, which is only readable by `bincode`
+    Bincode,
+}
+
+impl Encoding {
+    /// The MIME type of the request and response bodies, which the client sends as both the
+    /// `Content-Type` and `Accept` headers.
+    /// ```
+    /// # use leptos_server::Encoding;
+    /// assert_eq!(Encoding::Cbor.content_type(), "application/cbor");
+    /// assert_eq!(Encoding::from_content_type("application/bincode"), Some(Encoding::Bincode));
+    /// assert_eq!(Encoding::from_content_type("text/html"), None);
+    /// ```
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Encoding::Cbor => "application/cbor",
+            Encoding::Url => "application/x-www-form-urlencoded",
+            Encoding::Bincode => "application/bincode",
+        }
+    }
+
+    /// The encoding with the given MIME type, if there is one. The server integrations use this
+    /// to tell calls from the client, whose `Accept` header is an encoding, from `<form>` submits.
+    pub fn from_content_type(content_type: &str) -> Option<Self> {
+        match content_type {
+            "application/cbor" => Some(Encoding::Cbor),
+            "application/x-www-form-urlencoded" => Some(Encoding::Url),
+            "application/bincode" => Some(Encoding::Bincode),
+            _ => None,
+        }
+    }
+
+    /// Whether results are sent as a [Payload::Binary], rather than as JSON text.
+    pub fn is_binary(&self) -> bool {
+        !matches!(self, Encoding::Url)
+    }
 }
 
 impl FromStr for Encoding {
@@ -182,6 +252,7 @@ impl FromStr for Encoding {
         match input {
             "URL" => Ok(Encoding::Url),
             "Cbor" => Ok(Encoding::Cbor),
+            "Bincode" => Ok(Encoding::Bincode),
             _ => Err(()),
         }
     }
@@ -192,6 +263,7 @@ impl quote::ToTokens for Encoding {
         let option: syn::Ident = match *self {
             Encoding::Cbor => parse_quote!(Cbor),
             Encoding::Url => parse_quote!(Url),
+            Encoding::Bincode => parse_quote!(Bincode),
         };
         let expansion: syn::Ident = syn::parse_quote! {
           Encoding::#option
@@ -208,6 +280,7 @@ impl Parse for Encoding {
         match variant_name.as_ref() {
             "\"Url\"" => Ok(Self::Url),
             "\"Cbor\"" => Ok(Self::Cbor),
+            "\"Bincode\"" => Ok(Self::Bincode),
             _ => panic!("Encoding Not Found"),
         }
     }
@@ -264,10 +337,16 @@ where
         let run_server_fn = Arc::new(|cx: Scope, data: &[u8]| {
             // decode the args
             let value = match Self::encoding() {
-                Encoding::Url => serde_urlencoded::from_bytes(data)
-                    .map_err(|e| ServerFnError::Deserialization(e.to_string())),
+                Encoding::Url => std::str::from_utf8(data)
+                    .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+                    .and_then(|data| {
+                        NestedQuery::decode(data)
+                            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+                    }),
                 Encoding::Cbor => ciborium::de::from_reader(data)
                     .map_err(|e| ServerFnError::Deserialization(e.to_string())),
+                Encoding::Bincode => bincode::deserialize(data)
+                    .map_err(|e| ServerFnError::Deserialization(e.to_string())),
             };
             Box::pin(async move {
                 let value: Self = match value {
@@ -298,6 +377,12 @@ where
                             Err(e) => return Err(e),
                         }
                     }
+                    Encoding::Bincode => match bincode::serialize(&result)
+                        .map_err(|e| ServerFnError::Serialization(e.to_string()))
+                    {
+                        Ok(r) => Payload::Binary(r),
+                        Err(e) => return Err(e),
+                    },
                 };
 
                 Ok(result)
@@ -308,7 +393,14 @@ where
         let mut write = REGISTERED_SERVER_FUNCTIONS
             .write()
             .map_err(|e| ServerFnError::Registration(e.to_string()))?;
-        write.insert(Self::url(), run_server_fn);
+        let path = format!("{}/{}", Self::prefix(), Self::url());
+        // a URL that another app's server function already has can't tell them apart
+        let unique = match write.by_url.get(Self::url()) {
+            None => true,
+            Some(other) => other.as_ref() == Some(&path),
+        };
+        write.by_url.insert(Self::url(), unique.then(|| path.clone()));
+        write.by_path.insert(path, run_server_fn);
 
         Ok(())
     }
@@ -361,8 +453,7 @@ where
     }
     let args_encoded = match &enc {
         Encoding::Url => Payload::Url(
-            serde_urlencoded::to_string(&args)
-                .map_err(|e| ServerFnError::Serialization(e.to_string()))?,
+            NestedQuery::encode(&args).map_err(|e| ServerFnError::Serialization(e.to_string()))?,
         ),
         Encoding::Cbor => {
             let mut buffer: Vec<u8> = Vec::new();
@@ -370,17 +461,13 @@ where
                 .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
             Payload::Binary(buffer)
         }
+        Encoding::Bincode => Payload::Binary(
+            bincode::serialize(&args).map_err(|e| ServerFnError::Serialization(e.to_string()))?,
+        ),
     };
 
-    let content_type_header = match &enc {
-        Encoding::Url => "application/x-www-form-urlencoded",
-        Encoding::Cbor => "application/cbor",
-    };
-
-    let accept_header = match &enc {
-        Encoding::Url => "application/x-www-form-urlencoded",
-        Encoding::Cbor => "application/cbor",
-    };
+    let content_type_header = enc.content_type();
+    let accept_header = enc.content_type();
 
     let resp = match args_encoded {
         Payload::Binary(b) => {
@@ -409,14 +496,21 @@ where
         return Err(ServerFnError::ServerError(resp.status_text()));
     }
 
-    if enc == Encoding::Cbor {
+    // the server function asked the client to go somewhere else once it has its result
+    let redirect_to = resp.headers().get(REDIRECT_HEADER);
+
+    let result = if enc.is_binary() {
         let binary = resp
             .binary()
             .await
             .map_err(|e| ServerFnError::Deserialization(e.to_string()))?;
 
-        ciborium::de::from_reader(binary.as_slice())
-            .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+        if enc == Encoding::Bincode {
+            bincode::deserialize(&binary).map_err(|e| ServerFnError::Deserialization(e.to_string()))
+        } else {
+            ciborium::de::from_reader(binary.as_slice())
+                .map_err(|e| ServerFnError::Deserialization(e.to_string()))
+        }
     } else {
         let text = resp
             .text()
@@ -425,5 +519,10 @@ where
 
         let mut deserializer = JSONDeserializer::from_str(&text);
         T::deserialize(&mut deserializer).map_err(|e| ServerFnError::Deserialization(e.to_string()))
+    };
+
+    if let Some(path) = redirect_to {
+        call_redirect_hook(&path);
     }
+    result
 }
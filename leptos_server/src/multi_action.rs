@@ -1,8 +1,9 @@
 use crate::{ServerFn, ServerFnError};
 use leptos_reactive::{
-    create_rw_signal, spawn_local, store_value, ReadSignal, RwSignal, Scope, StoredValue,
+    create_rw_signal, store_value, ReadSignal, RwSignal, Scope, ScopedTasks, Signal, StoredValue,
+    UntrackedSettableSignal,
 };
-use std::{future::Future, pin::Pin, rc::Rc};
+use std::{any::Any, convert::Infallible, future::Future, pin::Pin, rc::Rc};
 
 /// An action that synchronizes multiple imperative `async` calls to the reactive system,
 /// tracking the progress of each one.
@@ -134,6 +135,9 @@ where
     O: 'static,
 {
     cx: Scope,
+    /// The submissions that are still running, which are dropped if the action's scope is
+    /// disposed.
+    tasks: ScopedTasks,
     /// How many times an action has successfully resolved.
     pub version: RwSignal<usize>,
     submissions: RwSignal<Vec<Submission<I, O>>>,
@@ -156,6 +160,14 @@ where
     pub(crate) pending: RwSignal<bool>,
     /// Controls this submission has been canceled.
     pub canceled: RwSignal<bool>,
+    /// The argument, moved out of `input` once the submission has resolved.
+    resolved_input: StoredValue<Option<I>>,
+    /// The signals returned by [Submission::state] and [Submission::result_state], which are
+    /// created the first time each one is asked for.
+    state: StoredValue<Option<Box<dyn Any>>>,
+    result_state: StoredValue<Option<Box<dyn Any>>>,
+    submitted_at: f64,
+    cx: Scope,
 }
 
 impl<I, O> Clone for Submission<I, O> {
@@ -165,6 +177,11 @@ impl<I, O> Clone for Submission<I, O> {
             value: self.value,
             pending: self.pending,
             canceled: self.canceled,
+            resolved_input: self.resolved_input,
+            state: self.state,
+            result_state: self.result_state,
+            submitted_at: self.submitted_at,
+            cx: self.cx,
         }
     }
 }
@@ -185,6 +202,158 @@ where
     pub fn cancel(&self) {
         self.canceled.set(true);
     }
+
+    /// When the submission was dispatched, in milliseconds since the Unix epoch, like
+    /// [`Date.now()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/now).
+    pub fn submitted_at(&self) -> f64 {
+        self.submitted_at
+    }
+
+    /// Calls the function with a reference to the argument that was dispatched, both while the
+    /// submission is pending and after it has resolved, unlike [Submission::input]. This is
+    /// useful for showing the submission in a list of operations, such as an optimistic UI.
+    pub fn with_input<U>(&self, f: impl FnOnce(&I) -> U) -> U {
+        if self.input.with(Option::is_some) {
+            self.input.with(|input| f(input.as_ref().unwrap()))
+        } else {
+            self.resolved_input.with(|input| {
+                f(input
+                    .as_ref()
+                    .expect("a resolved submission keeps its input"))
+            })
+        }
+    }
+
+    /// Returns a signal with the [SubmissionState] of the submission.
+    ///
+    /// For a submission that resolves to a [Result], [Submission::result_state] also
+    /// reports errors.
+    pub fn state(&self) -> Signal<SubmissionState<O>>
+    where
+        O: Clone,
+    {
+        let Submission {
+            value,
+            pending,
+            canceled,
+            ..
+        } = *self;
+        cached_signal(self.state, || {
+            Signal::derive(self.cx, move || {
+                if canceled.get() {
+                    SubmissionState::Canceled
+                } else if pending.get() {
+                    SubmissionState::Pending
+                } else {
+                    value
+                        .get()
+                        .map_or(SubmissionState::Pending, SubmissionState::Success)
+                }
+            })
+        })
+    }
+}
+
+impl<I, T, E> Submission<I, Result<T, E>>
+where
+    I: 'static,
+    T: Clone + 'static,
+    E: Clone + 'static,
+{
+    /// Like [Submission::state], but reports a submission that resolved to an [Err] as
+    /// [SubmissionState::Failed], with the error.
+    /// ```
+    /// # use leptos::*;
+    /// # run_scope(create_runtime(), |cx| {
+    /// let add_todo = create_multi_action(cx, |title: &String| {
+    ///     let title = title.clone();
+    ///     async move {
+    ///         if title.is_empty() {
+    ///             Err("a todo needs a title".to_string())
+    ///         } else {
+    ///             Ok(title)
+    ///         }
+    ///     }
+    /// });
+    ///
+    /// // show every submission, with the ones still in flight greyed out
+    /// let todos = move || {
+    ///     add_todo
+    ///         .submissions()
+    ///         .get()
+    ///         .into_iter()
+    ///         .map(|submission| {
+    ///             let title = submission.with_input(String::clone);
+    ///             match submission.result_state().get() {
+    ///                 SubmissionState::Pending => format!("{title} (saving…)"),
+    ///                 SubmissionState::Success(title) => title,
+    ///                 SubmissionState::Failed(e) => format!("{title} ({e})"),
+    ///                 SubmissionState::Canceled => format!("{title} (canceled)"),
+    ///             }
+    ///         })
+    ///         .collect::<Vec<_>>()
+    /// };
+    /// # if false {
+    /// add_todo.dispatch("Buy milk".to_string());
+    /// # }
+    /// # });
+    /// ```
+    pub fn result_state(&self) -> Signal<SubmissionState<T, E>> {
+        let Submission {
+            value,
+            pending,
+            canceled,
+            ..
+        } = *self;
+        cached_signal(self.result_state, || {
+            Signal::derive(self.cx, move || {
+                if canceled.get() {
+                    SubmissionState::Canceled
+                } else if pending.get() {
+                    SubmissionState::Pending
+                } else {
+                    match value.get() {
+                        Some(Ok(value)) => SubmissionState::Success(value),
+                        Some(Err(e)) => SubmissionState::Failed(e),
+                        None => SubmissionState::Pending,
+                    }
+                }
+            })
+        })
+    }
+}
+
+/// Returns the signal stored in `cache`, creating it the first time, so that reading the state of
+/// a submission again and again doesn't keep creating signals until its scope is disposed.
+fn cached_signal<T: 'static>(
+    cache: StoredValue<Option<Box<dyn Any>>>,
+    create: impl FnOnce() -> Signal<T>,
+) -> Signal<T> {
+    let cached = cache.with(|cached| {
+        cached
+            .as_ref()
+            .and_then(|signal| signal.downcast_ref::<Signal<T>>())
+            .copied()
+    });
+    cached.unwrap_or_else(|| {
+        let signal = create();
+        cache.set(Some(Box::new(signal)));
+        signal
+    })
+}
+
+/// The state of a [Submission], returned by [Submission::state] and
+/// [Submission::result_state].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SubmissionState<T, E = Infallible> {
+    /// The submission is waiting to resolve.
+    Pending,
+    /// The submission resolved to this value.
+    Success(T),
+    /// The submission resolved to this error. Only reported by [Submission::result_state].
+    Failed(E),
+    /// The submission was canceled with [Submission::cancel].
+    Canceled,
 }
 
 impl<I, O> MultiActionState<I, O>
@@ -202,6 +371,11 @@ where
             value: create_rw_signal(cx, None),
             pending: create_rw_signal(cx, true),
             canceled: create_rw_signal(cx, false),
+            resolved_input: store_value(cx, None),
+            state: store_value(cx, None),
+            result_state: store_value(cx, None),
+            submitted_at: now(),
+            cx,
         };
 
         self.submissions.update(|subs| subs.push(submission));
@@ -210,18 +384,22 @@ where
         let input = submission.input;
         let pending = submission.pending;
         let value = submission.value;
+        let resolved_input = submission.resolved_input;
         let version = self.version;
 
-        spawn_local(async move {
+        self.tasks.spawn(async move {
             let new_value = fut.await;
             let canceled = cx.untrack(move || canceled.get());
-            input.set(None);
+            // keep the input around for Submission::with_input, before anything that reads it is
+            // told that it's gone
+            resolved_input.set(input.update_returning_untracked(Option::take).flatten());
+            input.update(|_| {});
             pending.set(false);
             if !canceled {
                 value.set(Some(new_value));
             }
             version.update(|n| *n += 1);
-        })
+        });
     }
 
     /// The set of all submissions to this multi-action.
@@ -230,6 +408,23 @@ where
     }
 }
 
+/// Milliseconds since the Unix epoch.
+fn now() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    {
+        leptos_dom::js_sys::Date::now()
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|elapsed| elapsed.as_secs_f64() * 1000.0)
+            .unwrap_or_default()
+    }
+}
+
 /// Creates an [MultiAction] to synchronize an imperative `async` call to the synchronous reactive system.
 ///
 /// If you’re trying to load data by running an `async` function reactively, you probably
@@ -298,6 +493,7 @@ where
         cx,
         MultiActionState {
             cx,
+            tasks: ScopedTasks::new(cx),
             version,
             submissions,
             url: None,
@@ -0,0 +1,388 @@
+use serde::{
+    de::{
+        self,
+        value::{MapDeserializer, SeqDeserializer},
+        DeserializeOwned, Deserializer, IntoDeserializer, Visitor,
+    },
+    forward_to_deserialize_any, Serialize,
+};
+use serde_json::Value;
+use std::fmt;
+use thiserror::Error;
+
+/// A way of encoding values as `application/x-www-form-urlencoded` query strings, and
+/// decoding them again. This is used for URL search queries (see `use_query_as` in
+/// `leptos_router`) and for the arguments of server functions using [Encoding::Url](crate::Encoding::Url).
+pub trait QueryCodec {
+    /// Encodes the value as a query string, without a leading `?`.
+    fn encode<T: Serialize>(value: &T) -> Result<String, QueryError>;
+
+    /// Decodes a query string, which may start with `?`.
+    fn decode<T: DeserializeOwned>(query: &str) -> Result<T, QueryError>;
+}
+
+/// Errors that can occur while encoding or decoding a query string with a [QueryCodec].
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum QueryError {
+    /// The value could not be encoded as a query string.
+    #[error("could not encode query string: {0}")]
+    Encode(String),
+    /// The query string could not be decoded into the requested type.
+    #[error("could not decode query string: {0}")]
+    Decode(String),
+}
+
+/// Encodes values as flat `key=value` pairs, using
+/// [`serde_urlencoded`](https://docs.rs/serde_urlencoded/latest/serde_urlencoded/).
+///
+/// Only structs or maps whose values are strings, numbers, booleans, or options of those
+/// can be encoded.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct FlatQuery;
+
+impl QueryCodec for FlatQuery {
+    fn encode<T: Serialize>(value: &T) -> Result<String, QueryError> {
+        serde_urlencoded::to_string(value).map_err(|e| QueryError::Encode(e.to_string()))
+    }
+
+    fn decode<T: DeserializeOwned>(query: &str) -> Result<T, QueryError> {
+        serde_urlencoded::from_str(query.trim_start_matches('?'))
+            .map_err(|e| QueryError::Decode(e.to_string()))
+    }
+}
+
+/// Encodes values as `key=value` pairs, using brackets for lists and nested values. This
+/// is the default codec for search queries and server function arguments.
+/// - Lists are encoded as `tags[]=a&tags[]=b`. Repeating a key (`tags=a&tags=b`) is
+///   decoded as a list too, and a single value can be decoded as a list of one item.
+/// - Nested structs and maps are encoded as `filter[min]=1&filter[max]=5`.
+/// - Lists of structs or lists are encoded with indices, as `items[0][id]=1&items[1][id]=2`.
+/// - `None` and empty lists are left out, so fields with these values should be an [Option]
+///   or use `#[serde(default)]`. An empty value (`key=`), like a blank `<input>`, is decoded
+///   as `Some("")`, not `None`.
+/// - A key can't be used both for a value and for a list or nested value, as in
+///   `a=1&a[b]=2`; decoding such a query string fails.
+///
+/// Flat `key=value` pairs are encoded in the same way as with [FlatQuery].
+/// ```
+/// # use leptos_server::*;
+/// # use serde::{Deserialize, Serialize};
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// struct Range {
+///     min: u32,
+///     max: Option<u32>,
+/// }
+///
+/// #[derive(Serialize, Deserialize, Debug, PartialEq)]
+/// struct Filters {
+///     search: String,
+///     tags: Vec<String>,
+///     price: Range,
+/// }
+///
+/// let filters = Filters {
+///     search: "red shoes".to_string(),
+///     tags: vec!["sale".to_string(), "new".to_string()],
+///     price: Range { min: 10, max: None },
+/// };
+/// let query = NestedQuery::encode(&filters).unwrap();
+/// assert_eq!(query, "price%5Bmin%5D=10&search=red+shoes&tags%5B%5D=sale&tags%5B%5D=new");
+/// assert_eq!(NestedQuery::decode::<Filters>(&query), Ok(filters));
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub struct NestedQuery;
+
+impl QueryCodec for NestedQuery {
+    fn encode<T: Serialize>(value: &T) -> Result<String, QueryError> {
+        let value = serde_json::to_value(value).map_err(|e| QueryError::Encode(e.to_string()))?;
+        let mut pairs = form_urlencoded::Serializer::new(String::new());
+        match value {
+            Value::Object(fields) => {
+                for (key, value) in fields {
+                    encode_value(&mut pairs, key, value);
+                }
+            }
+            Value::Null => {}
+            _ => {
+                return Err(QueryError::Encode(
+                    "only structs and maps can be encoded as a query string".to_string(),
+                ))
+            }
+        }
+        Ok(pairs.finish())
+    }
+
+    fn decode<T: DeserializeOwned>(query: &str) -> Result<T, QueryError> {
+        let mut root = Vec::new();
+        for (key, value) in form_urlencoded::parse(query.trim_start_matches('?').as_bytes()) {
+            if !key.is_empty() {
+                insert(&mut root, &key_segments(&key), value.into_owned())?;
+            }
+        }
+        T::deserialize(QueryValue::Map(root)).map_err(|e| QueryError::Decode(e.0))
+    }
+}
+
+fn encode_value(pairs: &mut form_urlencoded::Serializer<String>, key: String, value: Value) {
+    match value {
+        Value::Null => {}
+        Value::Bool(value) => {
+            pairs.append_pair(&key, if value { "true" } else { "false" });
+        }
+        Value::Number(value) => {
+            pairs.append_pair(&key, &value.to_string());
+        }
+        Value::String(value) => {
+            pairs.append_pair(&key, &value);
+        }
+        Value::Array(items) => {
+            let flat = items
+                .iter()
+                .all(|item| !matches!(item, Value::Array(_) | Value::Object(_) | Value::Null));
+            for (index, item) in items.into_iter().enumerate() {
+                let key = if flat {
+                    format!("{key}[]")
+                } else {
+                    format!("{key}[{index}]")
+                };
+                encode_value(pairs, key, item);
+            }
+        }
+        Value::Object(fields) => {
+            for (field, value) in fields {
+                encode_value(pairs, format!("{key}[{field}]"), value);
+            }
+        }
+    }
+}
+
+/// Splits `a[b][]` into `["a", "b", ""]`. Keys with unbalanced brackets are used as-is.
+fn key_segments(key: &str) -> Vec<String> {
+    let (base, mut rest) = match key.find('[') {
+        Some(0) | None => return vec![key.to_string()],
+        Some(index) => key.split_at(index),
+    };
+    let mut segments = vec![base.to_string()];
+    while !rest.is_empty() {
+        match rest.strip_prefix('[').and_then(|rest| rest.split_once(']')) {
+            Some((segment, remaining)) => {
+                segments.push(segment.to_string());
+                rest = remaining;
+            }
+            None => return vec![key.to_string()],
+        }
+    }
+    segments
+}
+
+/// A decoded query string, which has not yet been deserialized into a concrete type.
+#[derive(Debug)]
+enum QueryValue {
+    Leaf(String),
+    Seq(Vec<QueryValue>),
+    Map(Vec<(String, QueryValue)>),
+}
+
+fn insert(
+    map: &mut Vec<(String, QueryValue)>,
+    segments: &[String],
+    value: String,
+) -> Result<(), QueryError> {
+    let (key, rest) = match segments.split_first() {
+        Some(split) => split,
+        None => return Ok(()),
+    };
+    let position = map.iter().position(|(k, _)| k == key);
+    let conflict = || {
+        Err(QueryError::Decode(format!(
+            "`{key}` is used both for a value and for a list or nested value"
+        )))
+    };
+
+    if rest.is_empty() {
+        match position.map(|index| &mut map[index].1) {
+            // a repeated key is collected into a list
+            Some(QueryValue::Seq(items)) => items.push(QueryValue::Leaf(value)),
+            Some(QueryValue::Map(_)) => return conflict(),
+            Some(existing) => {
+                let previous = std::mem::replace(existing, QueryValue::Seq(Vec::new()));
+                *existing = QueryValue::Seq(vec![previous, QueryValue::Leaf(value)]);
+            }
+            None => map.push((key.clone(), QueryValue::Leaf(value))),
+        }
+        return Ok(());
+    }
+
+    let index = position.unwrap_or_else(|| {
+        let child = if rest[0].is_empty() {
+            QueryValue::Seq(Vec::new())
+        } else {
+            QueryValue::Map(Vec::new())
+        };
+        map.push((key.clone(), child));
+        map.len() - 1
+    });
+    let child = &mut map[index].1;
+    // `key=a&key[]=b` is a list, just like `key[]=a&key=b`
+    if matches!(child, QueryValue::Leaf(_)) && rest[0].is_empty() {
+        let previous = std::mem::replace(child, QueryValue::Seq(Vec::new()));
+        *child = QueryValue::Seq(vec![previous]);
+    }
+    match child {
+        // each `[]` adds a new item
+        QueryValue::Seq(items) if rest[0].is_empty() => {
+            if rest.len() == 1 {
+                items.push(QueryValue::Leaf(value));
+            } else {
+                let mut item = Vec::new();
+                insert(&mut item, &rest[1..], value)?;
+                items.push(QueryValue::Map(item));
+            }
+            Ok(())
+        }
+        QueryValue::Map(fields) if !rest[0].is_empty() => insert(fields, rest, value),
+        // `key=` with `key[field]=`, or `key[]=` with `key[field]=`
+        _ => conflict(),
+    }
+}
+
+#[derive(Debug)]
+struct DecodeError(String);
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl de::Error for DecodeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        Self(msg.to_string())
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DecodeError> for QueryValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+macro_rules! deserialize_parsed {
+    ($($method:ident => $visit:ident),* $(,)?) => {
+        $(
+            fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DecodeError> {
+                match self {
+                    QueryValue::Leaf(value) => visitor.$visit(
+                        value
+                            .parse()
+                            .map_err(|e| DecodeError(format!("{value:?}: {e}")))?,
+                    ),
+                    other => other.deserialize_any(visitor),
+                }
+            }
+        )*
+    };
+}
+
+impl<'de> de::Deserializer<'de> for QueryValue {
+    type Error = DecodeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DecodeError> {
+        match self {
+            QueryValue::Leaf(value) => visitor.visit_string(value),
+            QueryValue::Seq(items) => visitor.visit_seq(SeqDeserializer::new(items.into_iter())),
+            QueryValue::Map(fields) => visitor.visit_map(MapDeserializer::new(fields.into_iter())),
+        }
+    }
+
+    deserialize_parsed! {
+        deserialize_bool => visit_bool,
+        deserialize_i8 => visit_i8,
+        deserialize_i16 => visit_i16,
+        deserialize_i32 => visit_i32,
+        deserialize_i64 => visit_i64,
+        deserialize_u8 => visit_u8,
+        deserialize_u16 => visit_u16,
+        deserialize_u32 => visit_u32,
+        deserialize_u64 => visit_u64,
+        deserialize_f32 => visit_f32,
+        deserialize_f64 => visit_f64,
+        deserialize_char => visit_char,
+    }
+
+    // fields that are left out are `None`, so any value that is present is `Some`, even if
+    // it's empty
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DecodeError> {
+        visitor.visit_some(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DecodeError> {
+        match self {
+            QueryValue::Leaf(value) => visitor.visit_seq(SeqDeserializer::new(std::iter::once(
+                QueryValue::Leaf(value),
+            ))),
+            QueryValue::Seq(items) => visitor.visit_seq(SeqDeserializer::new(items.into_iter())),
+            // lists of structs or lists are encoded with indices, e.g., `items[0][id]=1`
+            QueryValue::Map(fields) => {
+                let mut indexed = fields
+                    .into_iter()
+                    .map(|(key, value)| key.parse::<usize>().map(|index| (index, value)))
+                    .collect::<Result<Vec<_>, _>>()
+                    .map_err(|_| DecodeError("expected a list, found a map".to_string()))?;
+                indexed.sort_by_key(|(index, _)| *index);
+                visitor.visit_seq(SeqDeserializer::new(
+                    indexed.into_iter().map(|(_, value)| value),
+                ))
+            }
+        }
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DecodeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, DecodeError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, DecodeError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, DecodeError> {
+        match self {
+            QueryValue::Leaf(value) => visitor.visit_enum(value.into_deserializer()),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, DecodeError> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        str string bytes byte_buf unit_struct map struct identifier ignored_any
+    }
+}
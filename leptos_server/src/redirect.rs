@@ -0,0 +1,87 @@
+use leptos_reactive::{use_context, Scope};
+use std::{cell::RefCell, rc::Rc};
+
+/// The response header that tells the client to navigate somewhere else once a server function
+/// called with `fetch()` has finished.
+///
+/// `fetch()` follows HTTP redirects itself, so a `Location` header would only change which
+/// response the client receives. The server integrations send this header instead, and the client
+/// navigates once it has read the server function's result.
+pub const REDIRECT_HEADER: &str = "serverfnredirect";
+
+/// Where a server function has asked the client to go once it finishes.
+///
+/// The server integrations provide a `ServerFnRedirect` as context while running a server
+/// function, and read it when building the response. Server functions will usually call
+/// [redirect] instead.
+#[derive(Clone, Debug, Default)]
+pub struct ServerFnRedirect {
+    location: Rc<RefCell<Option<String>>>,
+}
+
+impl ServerFnRedirect {
+    /// Asks the client to navigate to the given path once the server function finishes.
+    pub fn redirect(&self, path: impl Into<String>) {
+        *self.location.borrow_mut() = Some(path.into());
+    }
+
+    /// Takes the path the client should navigate to, if a redirect was asked for.
+    pub fn take(&self) -> Option<String> {
+        self.location.borrow_mut().take()
+    }
+}
+
+/// Asks the client to navigate to `path` once the current server function finishes.
+///
+/// If the server function was called from a `<form>` submitted without JavaScript, the browser
+/// is redirected to `path` rather than back to the page the form was on. Otherwise, the response
+/// carries the [REDIRECT_HEADER], and the client navigates after reading the server function's
+/// result: with the router, if a `<Router/>` has set a [redirect hook](set_redirect_hook), or by
+/// loading the new page if not.
+///
+/// This should only be called inside a server function; it does nothing elsewhere.
+/// ```rust,ignore
+/// # use leptos::*;
+/// #[server(Login, "/api")]
+/// async fn login(cx: Scope, username: String, password: String) -> Result<(), ServerFnError> {
+///     // check the password and start a session...
+///     redirect(cx, "/dashboard");
+///     Ok(())
+/// }
+/// ```
+pub fn redirect(cx: Scope, path: &str) {
+    match use_context::<ServerFnRedirect>(cx) {
+        Some(redirect) => redirect.redirect(path),
+        None => leptos_reactive::debug_warn!(
+            "redirect({path:?}) was called outside a server function, so it has no effect"
+        ),
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+thread_local! {
+    static REDIRECT_HOOK: RefCell<Option<Rc<dyn Fn(&str)>>> = RefCell::new(None);
+}
+
+/// Sets the function that's called to navigate when a server function response carries the
+/// [REDIRECT_HEADER]. The `<Router/>` sets this, so that redirects use client-side routing;
+/// without it, the client loads the new page.
+pub fn set_redirect_hook(hook: impl Fn(&str) + 'static) {
+    #[cfg(not(feature = "ssr"))]
+    REDIRECT_HOOK.with(|current| *current.borrow_mut() = Some(Rc::new(hook)));
+    #[cfg(feature = "ssr")]
+    _ = hook;
+}
+
+/// Navigates to a path the server asked for, using the [redirect hook](set_redirect_hook) if
+/// one has been set.
+#[cfg(not(feature = "ssr"))]
+pub fn call_redirect_hook(path: &str) {
+    // cloned out, so that navigating can set a new hook
+    match REDIRECT_HOOK.with(|hook| hook.borrow().clone()) {
+        Some(hook) => hook(path),
+        None => {
+            _ = leptos_dom::window().location().set_href(path);
+        }
+    }
+}
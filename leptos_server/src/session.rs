@@ -0,0 +1,249 @@
+use crate::{Cookie, CookieJar, CookieOptions, ServerFnError};
+use hmac::{Hmac, Mac};
+use leptos_reactive::*;
+use serde::{de::DeserializeOwned, Serialize};
+use sha2::Sha256;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+    sync::{Arc, RwLock},
+};
+
+type SessionData = HashMap<String, String>;
+
+/// Storage for server-side session data. Sessions are identified by a random id, which is
+/// sent to the browser in a signed cookie, so the data itself never leaves the server.
+pub trait SessionStore: Send + Sync + 'static {
+    /// Loads the data for a session, if it exists.
+    fn load(&self, id: &str) -> Option<HashMap<String, String>>;
+    /// Saves the data for a session, replacing any previous data.
+    fn store(&self, id: &str, data: HashMap<String, String>);
+    /// Deletes a session.
+    fn destroy(&self, id: &str);
+}
+
+/// A [SessionStore] that keeps sessions in memory. Sessions are lost when the server
+/// restarts, and aren't shared between multiple server processes.
+#[derive(Debug, Default)]
+pub struct MemorySessionStore(RwLock<HashMap<String, SessionData>>);
+
+impl SessionStore for MemorySessionStore {
+    fn load(&self, id: &str) -> Option<HashMap<String, String>> {
+        self.0.read().unwrap().get(id).cloned()
+    }
+
+    fn store(&self, id: &str, data: HashMap<String, String>) {
+        self.0.write().unwrap().insert(id.to_string(), data);
+    }
+
+    fn destroy(&self, id: &str) {
+        self.0.write().unwrap().remove(id);
+    }
+}
+
+/// Configures sessions: where they're stored, the secret used to sign session cookies,
+/// and the attributes of those cookies.
+///
+/// Each app has its own configuration, which the server integrations read from the app's state
+/// and provide to [use_session]: add it with `.layer(Extension(config))` to an Axum router, or
+/// `.app_data(config)` to an Actix app.
+/// ```
+/// # use leptos_server::*;
+/// # if false {
+/// let config =
+///     SessionConfig::new(MemorySessionStore::default(), std::env::var("SESSION_SECRET").unwrap());
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct SessionConfig {
+    store: Arc<dyn SessionStore>,
+    secret: Arc<[u8]>,
+    cookie_name: String,
+    cookie_options: CookieOptions,
+}
+
+impl SessionConfig {
+    /// Creates a configuration with the given store and signing secret. The secret should be
+    /// at least 32 random bytes, and kept out of your source code.
+    pub fn new(store: impl SessionStore, secret: impl AsRef<[u8]>) -> Self {
+        Self {
+            store: Arc::new(store),
+            secret: secret.as_ref().into(),
+            cookie_name: "leptos_session".to_string(),
+            cookie_options: CookieOptions {
+                http_only: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Sets the name of the session cookie. Defaults to `leptos_session`.
+    pub fn cookie_name(mut self, name: impl Into<String>) -> Self {
+        self.cookie_name = name.into();
+        self
+    }
+
+    /// Sets the attributes of the session cookie. Defaults to `HttpOnly; SameSite=Lax; Path=/`.
+    pub fn cookie_options(mut self, options: CookieOptions) -> Self {
+        self.cookie_options = options;
+        self
+    }
+
+    fn sign(&self, id: &str) -> String {
+        sign_cookie_value(&self.secret, id)
+    }
+
+    fn verify(&self, cookie: &str) -> Option<String> {
+        verify_cookie_value(&self.secret, cookie)
+    }
+}
+
+/// Signs a cookie value with HMAC-SHA256, so that it can be checked with [verify_cookie_value]
+/// when the browser sends it back. The value is still readable by the browser; signing only
+/// prevents it from being changed.
+/// ```
+/// # use leptos_server::*;
+/// let signed = sign_cookie_value(b"secret", "user-1");
+/// assert!(signed.starts_with("user-1."));
+/// assert_eq!(verify_cookie_value(b"secret", &signed).as_deref(), Some("user-1"));
+/// ```
+pub fn sign_cookie_value(secret: &[u8], value: &str) -> String {
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).expect("HMAC can take a key of any size");
+    mac.update(value.as_bytes());
+    let signature: String = mac
+        .finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect();
+    format!("{value}.{signature}")
+}
+
+/// Returns the value of a cookie signed with [sign_cookie_value], or `None` if it wasn't signed
+/// with the same secret or has been changed.
+pub fn verify_cookie_value(secret: &[u8], signed: &str) -> Option<String> {
+    let (value, signature) = signed.rsplit_once('.')?;
+    let signature = (0..signature.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(signature.get(i..i + 2)?, 16).ok())
+        .collect::<Option<Vec<u8>>>()?;
+    let mut mac = Hmac::<Sha256>::new_from_slice(secret).ok()?;
+    mac.update(value.as_bytes());
+    // constant-time comparison
+    mac.verify_slice(&signature).ok()?;
+    Some(value.to_string())
+}
+
+/// The session for the current request. Values are serialized as JSON and written through to
+/// the [SessionStore] as soon as they're changed.
+#[derive(Clone)]
+pub struct Session {
+    config: SessionConfig,
+    jar: CookieJar,
+    state: Rc<RefCell<SessionState>>,
+}
+
+struct SessionState {
+    id: Option<String>,
+    data: SessionData,
+}
+
+impl Session {
+    /// Returns the value stored under `key`, if any.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Option<T> {
+        self.state
+            .borrow()
+            .data
+            .get(key)
+            .and_then(|json| serde_json::from_str(json).ok())
+    }
+
+    /// Stores a value under `key`. If this is a new session, the signed session cookie is
+    /// queued on the response.
+    pub fn insert<T: Serialize>(&self, key: &str, value: &T) -> Result<(), ServerFnError> {
+        let json = serde_json::to_string(value)
+            .map_err(|e| ServerFnError::Serialization(e.to_string()))?;
+        let mut state = self.state.borrow_mut();
+        state.data.insert(key.to_string(), json);
+        let id = self.ensure_id(&mut state);
+        self.config.store.store(&id, state.data.clone());
+        Ok(())
+    }
+
+    /// Removes the value stored under `key`.
+    pub fn remove(&self, key: &str) {
+        let mut state = self.state.borrow_mut();
+        if state.data.remove(key).is_some() {
+            if let Some(id) = &state.id {
+                self.config.store.store(id, state.data.clone());
+            }
+        }
+    }
+
+    /// Deletes the session from the store and removes the session cookie, e.g., when logging out.
+    pub fn destroy(&self) {
+        let mut state = self.state.borrow_mut();
+        if let Some(id) = state.id.take() {
+            self.config.store.destroy(&id);
+            self.jar
+                .remove(&self.config.cookie_name, self.config.cookie_options.clone());
+        }
+        state.data.clear();
+    }
+
+    fn ensure_id(&self, state: &mut SessionState) -> String {
+        if let Some(id) = &state.id {
+            return id.clone();
+        }
+        let mut bytes = [0u8; 32];
+        getrandom::getrandom(&mut bytes).expect("could not generate a session id");
+        let id: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+        self.jar.set(
+            Cookie::new(&self.config.cookie_name, self.config.sign(&id))
+                .with_options(self.config.cookie_options.clone()),
+        );
+        state.id = Some(id.clone());
+        id
+    }
+}
+
+/// Returns the session for the current request, from a server function or while server rendering.
+///
+/// Returns `None` if no [SessionConfig] has been added to the app's state, or if no [CookieJar] has
+/// been provided by the server integration. Session cookies with invalid signatures are ignored, and a new session
+/// is started.
+/// ```
+/// # use leptos::*;
+/// # if false {
+/// #[server(Login, "/api")]
+/// pub async fn login(cx: Scope, user_id: u32) -> Result<(), ServerFnError> {
+///     let session = use_session(cx)
+///         .ok_or_else(|| ServerFnError::ServerError("sessions are not configured".into()))?;
+///     session.insert("user_id", &user_id)
+/// }
+/// # }
+/// ```
+pub fn use_session(cx: Scope) -> Option<Session> {
+    if let Some(session) = use_context::<Session>(cx) {
+        return Some(session);
+    }
+
+    let config = use_context::<SessionConfig>(cx)?;
+    let jar = use_context::<CookieJar>(cx)?;
+    let id = jar
+        .get(&config.cookie_name)
+        .and_then(|cookie| config.verify(&cookie));
+    let data = id.as_deref().and_then(|id| config.store.load(id));
+    let session = Session {
+        config,
+        jar,
+        state: Rc::new(RefCell::new(SessionState {
+            // the cookie may be valid, but point at a session that has since been destroyed
+            id: data.as_ref().and(id),
+            data: data.unwrap_or_default(),
+        })),
+    };
+    provide_context(cx, session.clone());
+    Some(session)
+}
@@ -0,0 +1,72 @@
+#![cfg(feature = "ssr")]
+
+use leptos::*;
+use std::{cell::RefCell, future::Future, rc::Rc};
+use tokio::sync::oneshot;
+
+/// Runs a test on a single thread, so that dispatched actions only run when the test is waiting
+/// on something.
+fn run_local(test: impl Future<Output = ()>) {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    tokio::task::LocalSet::new().block_on(&runtime, test);
+}
+
+/// Lets every dispatched action run until it's waiting on something again.
+async fn settle() {
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+}
+
+type Senders = Rc<RefCell<Vec<oneshot::Sender<i32>>>>;
+
+/// An `async` function that waits until the test sends it a value.
+fn wait_for_value(senders: &Senders) -> impl Future<Output = i32> {
+    let (tx, rx) = oneshot::channel();
+    senders.borrow_mut().push(tx);
+    async move { rx.await.unwrap_or_default() }
+}
+
+#[test]
+fn pending_actions_are_aborted_when_disposed() {
+    run_local(async {
+        let senders = Senders::default();
+        let (action, _, disposer) = run_scope_undisposed(create_runtime(), {
+            let senders = Rc::clone(&senders);
+            move |cx| create_action(cx, move |_: &()| wait_for_value(&senders))
+        });
+        action.dispatch(());
+        action.dispatch(());
+        settle().await;
+        assert!(senders.borrow().iter().all(|tx| !tx.is_closed()));
+
+        disposer.dispose();
+        settle().await;
+        assert!(senders.borrow().iter().all(|tx| tx.is_closed()));
+    });
+}
+
+#[test]
+fn pending_multi_actions_are_aborted_when_disposed() {
+    run_local(async {
+        let senders = Senders::default();
+        let (action, _, disposer) = run_scope_undisposed(create_runtime(), {
+            let senders = Rc::clone(&senders);
+            move |cx| create_multi_action(cx, move |_: &()| wait_for_value(&senders))
+        });
+        action.dispatch(());
+        action.dispatch(());
+        settle().await;
+
+        // a finished submission doesn't need to be aborted
+        senders.borrow_mut().remove(0).send(1).unwrap();
+        settle().await;
+        assert_eq!(action.version().get(), 1);
+
+        disposer.dispose();
+        settle().await;
+        assert!(senders.borrow()[0].is_closed());
+    });
+}
@@ -0,0 +1,149 @@
+use leptos_server::{Cookie, CookieJar, CookieOptions};
+
+#[test]
+fn parses_the_cookie_header() {
+    let jar = CookieJar::from_header(Some(r#"theme=dark;lang="en" ; empty=; broken; a=b=c"#));
+    assert_eq!(jar.get("theme").as_deref(), Some("dark"));
+    assert_eq!(jar.get("lang").as_deref(), Some("en"));
+    assert_eq!(jar.get("empty").as_deref(), Some(""));
+    assert_eq!(jar.get("broken"), None);
+    assert_eq!(jar.get("a").as_deref(), Some("b=c"));
+    assert_eq!(CookieJar::from_header(None).get("theme"), None);
+}
+
+#[test]
+fn decodes_percent_encoded_values() {
+    let jar = CookieJar::from_header(Some("name=J%C3%BCrgen%3B%20x; odd=100%; bad=%zz%+1"));
+    assert_eq!(jar.get("name").as_deref(), Some("Jürgen; x"));
+    assert_eq!(jar.get("odd").as_deref(), Some("100%"));
+    assert_eq!(jar.get("bad").as_deref(), Some("%zz%+1"));
+}
+
+#[test]
+fn values_are_encoded_so_they_cant_add_attributes() {
+    for value in [
+        "a; Domain=evil.com",
+        "a, b",
+        "a\r\nSet-Cookie: b=c",
+        "\"quoted\"",
+        "100%",
+    ] {
+        let header = Cookie::new("name", value).to_header_value();
+        let (pair, attributes) = header.split_once(';').unwrap();
+        assert_eq!(attributes, " Path=/; SameSite=Lax", "{header}");
+
+        // and they round-trip through the jar
+        let jar = CookieJar::from_header(Some(pair));
+        assert_eq!(jar.get("name").as_deref(), Some(value));
+    }
+}
+
+#[test]
+fn set_cookies_are_encoded() {
+    let jar = CookieJar::default();
+    jar.set(Cookie::new("greeting", "hello world"));
+    assert_eq!(jar.get("greeting").as_deref(), Some("hello world"));
+    assert_eq!(
+        jar.take_set_cookie_headers(),
+        vec!["greeting=hello%20world; Path=/; SameSite=Lax".to_string()]
+    );
+}
+
+#[test]
+#[should_panic(expected = "not a valid cookie name")]
+fn names_with_separators_are_rejected() {
+    Cookie::new("name; Domain=evil.com", "value").to_header_value();
+}
+
+#[test]
+#[should_panic(expected = "not a valid cookie name")]
+fn empty_names_are_rejected() {
+    Cookie::new("", "value").to_header_value();
+}
+
+#[test]
+#[should_panic(expected = "contains `;` or control characters")]
+fn attributes_that_add_attributes_are_rejected() {
+    Cookie::new("name", "value")
+        .with_options(CookieOptions {
+            path: Some("/; Domain=evil.com".to_string()),
+            ..Default::default()
+        })
+        .to_header_value();
+}
+
+#[cfg(feature = "ssr")]
+mod signed {
+    use leptos_server::{sign_cookie_value, verify_cookie_value};
+
+    #[test]
+    fn signed_values_are_verified() {
+        let signed = sign_cookie_value(b"secret", "user.1");
+        assert_eq!(
+            verify_cookie_value(b"secret", &signed).as_deref(),
+            Some("user.1")
+        );
+    }
+
+    #[test]
+    fn values_signed_with_another_secret_are_rejected() {
+        let signed = sign_cookie_value(b"secret", "user-1");
+        assert_eq!(verify_cookie_value(b"other secret", &signed), None);
+    }
+
+    #[test]
+    fn changed_values_and_signatures_are_rejected() {
+        let signed = sign_cookie_value(b"secret", "user-1");
+        let (_, signature) = signed.rsplit_once('.').unwrap();
+        assert_eq!(
+            verify_cookie_value(b"secret", &format!("user-2.{signature}")),
+            None
+        );
+
+        let mut tampered = signed.clone();
+        let last = if tampered.ends_with('0') { "1" } else { "0" };
+        tampered.replace_range(tampered.len() - 1.., last);
+        assert_eq!(verify_cookie_value(b"secret", &tampered), None);
+    }
+
+    #[test]
+    fn malformed_values_are_rejected() {
+        for signed in ["", "user-1", "user-1.", "user-1.zz", "user-1.abc"] {
+            assert_eq!(verify_cookie_value(b"secret", signed), None, "{signed}");
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[test]
+fn each_app_uses_its_own_session_config() {
+    use leptos_reactive::{create_runtime, create_scope, provide_context};
+    use leptos_server::{use_session, MemorySessionStore, SessionConfig};
+
+    let session_cookie = |config: Option<SessionConfig>| {
+        let jar = CookieJar::from_header(None);
+        _ = create_scope(create_runtime(), {
+            let jar = jar.clone();
+            move |cx| {
+                provide_context(cx, jar);
+                if let Some(config) = config {
+                    provide_context(cx, config);
+                }
+                if let Some(session) = use_session(cx) {
+                    session.insert("user_id", &1).unwrap();
+                }
+            }
+        });
+        jar.take_set_cookie_headers()
+    };
+
+    let first = session_cookie(Some(
+        SessionConfig::new(MemorySessionStore::default(), "first secret").cookie_name("first"),
+    ));
+    let second = session_cookie(Some(
+        SessionConfig::new(MemorySessionStore::default(), "second secret").cookie_name("second"),
+    ));
+    assert!(first.len() == 1 && first[0].starts_with("first="));
+    assert!(second.len() == 1 && second[0].starts_with("second="));
+    assert!(session_cookie(None).is_empty());
+}
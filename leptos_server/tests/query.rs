@@ -0,0 +1,162 @@
+use leptos_server::{NestedQuery, QueryCodec, QueryError};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+struct Address {
+    street: String,
+    city: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+struct Item {
+    id: u32,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Default)]
+struct Form {
+    name: String,
+    nickname: Option<String>,
+    age: Option<u32>,
+    #[serde(default)]
+    tags: Vec<String>,
+    address: Address,
+    #[serde(default)]
+    items: Vec<Item>,
+    #[serde(default)]
+    extra: HashMap<String, String>,
+}
+
+fn round_trip(form: Form) {
+    let query = NestedQuery::encode(&form).unwrap();
+    assert_eq!(NestedQuery::decode::<Form>(&query), Ok(form), "{query}");
+}
+
+#[test]
+fn round_trips_nested_values() {
+    round_trip(Form {
+        name: "Ada Lovelace".to_string(),
+        nickname: Some("ada".to_string()),
+        age: Some(36),
+        tags: vec!["math".to_string(), "poetry & science".to_string()],
+        address: Address {
+            street: "12 St. James's Square".to_string(),
+            city: "London".to_string(),
+        },
+        items: vec![
+            Item {
+                id: 1,
+                tags: vec!["a".to_string()],
+            },
+            Item {
+                id: 2,
+                tags: vec!["b".to_string(), "c".to_string()],
+            },
+        ],
+        extra: [("color".to_string(), "blue & green=?".to_string())].into(),
+    });
+}
+
+#[test]
+fn round_trips_empty_and_missing_values() {
+    round_trip(Form::default());
+    round_trip(Form {
+        nickname: Some(String::new()),
+        ..Default::default()
+    });
+}
+
+#[test]
+fn empty_values_are_some() {
+    let form: Form =
+        NestedQuery::decode("name=&nickname=&address[street]=&address[city]=").unwrap();
+    assert_eq!(form.nickname, Some(String::new()));
+    assert_eq!(form.name, "");
+}
+
+#[test]
+fn missing_values_are_none() {
+    let form: Form = NestedQuery::decode("?name=Ada&address[street]=x&address[city]=y").unwrap();
+    assert_eq!(form.nickname, None);
+    assert_eq!(form.age, None);
+    assert!(form.tags.is_empty());
+}
+
+#[test]
+fn repeated_keys_are_lists() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Tags {
+        tags: Vec<String>,
+    }
+
+    let expected = Tags {
+        tags: vec!["a".to_string(), "b".to_string()],
+    };
+    assert_eq!(
+        NestedQuery::decode::<Tags>("tags=a&tags=b").unwrap(),
+        expected
+    );
+    assert_eq!(
+        NestedQuery::decode::<Tags>("tags[]=a&tags=b").unwrap(),
+        expected
+    );
+    assert_eq!(
+        NestedQuery::decode::<Tags>("tags=a&tags[]=b").unwrap(),
+        expected
+    );
+    assert_eq!(
+        NestedQuery::decode::<Tags>("tags=a").unwrap(),
+        Tags {
+            tags: vec!["a".to_string()]
+        }
+    );
+}
+
+#[test]
+fn keys_used_as_values_and_nested_values_are_rejected() {
+    for query in [
+        "address=x&address[city]=y",
+        "address[city]=y&address=x",
+        "tags[]=a&tags[x]=b",
+        "tags[x]=b&tags[]=a",
+    ] {
+        assert!(
+            matches!(
+                NestedQuery::decode::<HashMap<String, String>>(query),
+                Err(QueryError::Decode(_))
+            ),
+            "{query}"
+        );
+    }
+}
+
+#[test]
+fn unbalanced_brackets_are_used_as_keys() {
+    let map: HashMap<String, String> = NestedQuery::decode("a[b=1&[c]=2&d]=3").unwrap();
+    assert_eq!(map.get("a[b").map(String::as_str), Some("1"));
+    assert_eq!(map.get("[c]").map(String::as_str), Some("2"));
+    assert_eq!(map.get("d]").map(String::as_str), Some("3"));
+}
+
+#[test]
+fn invalid_numbers_are_errors() {
+    assert!(matches!(
+        NestedQuery::decode::<Form>("name=a&age=old&address[street]=x&address[city]=y"),
+        Err(QueryError::Decode(_))
+    ));
+}
+
+#[test]
+fn only_structs_and_maps_are_encoded() {
+    assert!(matches!(
+        NestedQuery::encode(&vec![1, 2]),
+        Err(QueryError::Encode(_))
+    ));
+    assert!(matches!(
+        NestedQuery::encode(&"text"),
+        Err(QueryError::Encode(_))
+    ));
+    // nothing to encode
+    assert_eq!(NestedQuery::encode(&()), Ok(String::new()));
+}
@@ -0,0 +1,79 @@
+#![cfg(feature = "ssr")]
+
+use leptos_reactive::{create_runtime, run_scope, Scope};
+use leptos_server::{server_fn_by_path, Encoding, Payload, ServerFn, ServerFnError};
+use serde::{Deserialize, Serialize};
+use std::{future::Future, pin::Pin};
+
+/// Declares a server function with the given prefix and URL, which returns its prefix.
+macro_rules! server_fn {
+    ($name:ident, $prefix:literal, $url:literal) => {
+        #[derive(Serialize, Deserialize)]
+        struct $name {}
+
+        impl ServerFn for $name {
+            type Output = String;
+
+            fn prefix() -> &'static str {
+                $prefix
+            }
+
+            fn url() -> &'static str {
+                $url
+            }
+
+            fn encoding() -> Encoding {
+                Encoding::Url
+            }
+
+            fn call_fn(
+                self,
+                _: Scope,
+            ) -> Pin<Box<dyn Future<Output = Result<String, ServerFnError>>>> {
+                Box::pin(async { Ok($prefix.to_string()) })
+            }
+        }
+    };
+}
+
+server_fn!(FirstAppFn, "/first", "shared_url");
+server_fn!(SecondAppFn, "/second", "shared_url");
+server_fn!(OnlyFn, "/api", "only_url");
+
+/// Calls the server function registered at the path, if there is one.
+fn call(path: &str) -> Option<String> {
+    let server_fn = server_fn_by_path(path)?;
+    let payload = run_scope(create_runtime(), |cx| {
+        tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap()
+            .block_on(server_fn(cx, &[]))
+    });
+    match payload {
+        Ok(Payload::Url(json)) => serde_json::from_str(&json).ok(),
+        other => panic!("unexpected response: {other:?}"),
+    }
+}
+
+#[test]
+fn server_functions_of_different_apps_are_found_by_their_full_paths() {
+    FirstAppFn::register().unwrap();
+    SecondAppFn::register().unwrap();
+
+    assert_eq!(call("/first/shared_url").as_deref(), Some("/first"));
+    assert_eq!(call("/second/shared_url").as_deref(), Some("/second"));
+
+    // the URL they share can't tell which one is meant
+    assert_eq!(call("shared_url"), None);
+}
+
+#[test]
+fn a_unique_url_finds_its_server_function() {
+    OnlyFn::register().unwrap();
+    // registering it again doesn't make it ambiguous
+    OnlyFn::register().unwrap();
+
+    assert_eq!(call("/api/only_url").as_deref(), Some("/api"));
+    assert_eq!(call("only_url").as_deref(), Some("/api"));
+    assert_eq!(call("/other/only_url"), None);
+}
@@ -0,0 +1,179 @@
+use crate::{use_head, TextProp};
+use cfg_if::cfg_if;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+
+/// The `id` of the `<link>` for the main icon, which is updated when it changes.
+const FAVICON_ID: &str = "leptos-favicon";
+
+/// Manages the icon and manifest links set by [Favicon] components.
+#[derive(Clone, Default, Debug)]
+pub struct FaviconContext {
+    links: Rc<RefCell<Vec<IconLink>>>,
+}
+
+#[derive(Clone, Debug)]
+struct IconLink {
+    rel: &'static str,
+    id: Option<&'static str>,
+    mime_type: Option<String>,
+    sizes: Option<String>,
+    href: TextProp,
+}
+
+impl IconLink {
+    fn attrs(&self) -> Vec<(&'static str, String)> {
+        let mut attrs = vec![("rel", self.rel.to_string())];
+        if let Some(id) = self.id {
+            attrs.push(("id", id.to_string()));
+        }
+        if let Some(mime_type) = &self.mime_type {
+            attrs.push(("type", mime_type.clone()));
+        }
+        if let Some(sizes) = &self.sizes {
+            attrs.push(("sizes", sizes.clone()));
+        }
+        attrs.push(("href", self.href.get()));
+        attrs
+    }
+}
+
+impl FaviconContext {
+    /// Converts the icon and manifest links into an HTML string that can be injected into the
+    /// `<head>`.
+    pub fn as_string(&self) -> String {
+        self.links
+            .borrow()
+            .iter()
+            .map(|link| {
+                let attrs: String = link
+                    .attrs()
+                    .into_iter()
+                    .map(|(name, value)| format!(r#" {name}="{}""#, escape_attr(&value)))
+                    .collect();
+                format!("<link{attrs}>")
+            })
+            .collect()
+    }
+}
+
+/// Injects the full set of icon links into the document head: the favicon, PNG icons in
+/// specific sizes, the Apple touch icon, and the web app manifest.
+///
+/// The `href` of the favicon is reactive, so it can be swapped in the browser, e.g., for a
+/// version with a notification badge. Browsers may prefer one of the sized icons over the
+/// favicon, so an app that swaps its favicon usually shouldn't set `sizes`.
+///
+/// Each path is resolved with [use_asset_path], so it will point at the fingerprinted file if an
+/// [AssetManifest] has been provided.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp(cx: Scope) -> impl IntoView {
+///   provide_meta_context(cx);
+///   let (unread, set_unread) = create_signal(cx, 0);
+///
+///   view! { cx,
+///     <Favicon
+///       href=move || {
+///         let icon = if unread() > 0 { "/favicon-badge.svg" } else { "/favicon.svg" };
+///         icon.to_string()
+///       }
+///       mime_type="image/svg+xml"
+///       apple_touch_icon="/apple-touch-icon.png"
+///       manifest="/site.webmanifest"
+///     />
+///     <main>
+///       <button on:click=move |_| set_unread.update(|n| *n += 1)>"New message"</button>
+///     </main>
+///   }
+/// }
+/// ```
+#[component(transparent)]
+pub fn Favicon(
+    cx: Scope,
+    /// The URL of the favicon, e.g. `/favicon.ico` or an SVG, which can change reactively.
+    #[prop(into)]
+    href: TextProp,
+    /// The MIME type of the favicon, e.g. `image/svg+xml`.
+    #[prop(optional, into)]
+    mime_type: Option<String>,
+    /// PNG icons in specific sizes, as `(size, path)` pairs, e.g. `(32, "/favicon-32x32.png")`
+    /// for a 32×32 icon.
+    #[prop(optional)]
+    sizes: Vec<(u32, &'static str)>,
+    /// The URL of the 180×180 icon that iOS uses for bookmarks on the home screen.
+    #[prop(optional, into)]
+    apple_touch_icon: Option<String>,
+    /// The URL of the [web app manifest](https://developer.mozilla.org/en-US/docs/Web/Manifest).
+    #[prop(optional, into)]
+    manifest: Option<String>,
+) -> impl IntoView {
+    let meta = use_head(cx);
+    let asset = move |path: &str| -> TextProp { use_asset_path(cx, path).into() };
+
+    let mut links = vec![IconLink {
+        rel: "icon",
+        id: Some(FAVICON_ID),
+        mime_type,
+        sizes: None,
+        href: TextProp::from(move || use_asset_path(cx, &href.get())),
+    }];
+    links.extend(sizes.into_iter().map(|(size, path)| IconLink {
+        rel: "icon",
+        id: None,
+        mime_type: Some("image/png".to_string()),
+        sizes: Some(format!("{size}x{size}")),
+        href: asset(path),
+    }));
+    links.extend(apple_touch_icon.map(|path| IconLink {
+        rel: "apple-touch-icon",
+        id: None,
+        mime_type: None,
+        sizes: Some("180x180".to_string()),
+        href: asset(&path),
+    }));
+    links.extend(manifest.map(|path| IconLink {
+        rel: "manifest",
+        id: None,
+        mime_type: None,
+        sizes: None,
+        href: asset(&path),
+    }));
+
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            let head = document().query_selector("head").unwrap_throw().unwrap_throw();
+            for link in &links {
+                // the server has already rendered these links into the <head>
+                let selector = match link.id {
+                    Some(id) => format!("#{id}"),
+                    None => format!(r#"link[rel="{}"][href="{}"]"#, link.rel, link.href.get()),
+                };
+                let el = match document().query_selector(&selector) {
+                    Ok(Some(el)) => el,
+                    _ => {
+                        let el = document().create_element("link").unwrap_throw();
+                        for (name, value) in link.attrs() {
+                            el.set_attribute(name, &value).unwrap_throw();
+                        }
+                        head.append_child(&el).unwrap_throw();
+                        el
+                    }
+                };
+
+                if link.id.is_some() {
+                    let href = link.href.clone();
+                    create_effect(cx, move |_| {
+                        _ = el.set_attribute("href", &href.get());
+                    });
+                }
+            }
+        }
+    }
+
+    meta.favicons.links.borrow_mut().extend(links);
+}
@@ -40,11 +40,19 @@ use std::{fmt::Debug, rc::Rc};
 
 use leptos::{leptos_dom::debug_warn, *};
 
+mod favicon;
 mod meta_tags;
+mod preload;
+mod style;
 mod stylesheet;
+mod theme;
 mod title;
+pub use favicon::*;
 pub use meta_tags::*;
+pub use preload::*;
+pub use style::*;
 pub use stylesheet::*;
+pub use theme::*;
 pub use title::*;
 
 /// Contains the current state of meta tags. To access it, you can use [use_head].
@@ -54,8 +62,12 @@ pub use title::*;
 #[derive(Debug, Clone, Default)]
 pub struct MetaContext {
     pub(crate) title: TitleContext,
+    pub(crate) preloads: PreloadContext,
     pub(crate) stylesheets: StylesheetContext,
+    pub(crate) favicons: FaviconContext,
     pub(crate) meta_tags: MetaTagsContext,
+    pub(crate) theme: ThemeContext,
+    pub(crate) nonce: Option<Nonce>,
 }
 
 /// Provides a [MetaContext], if there is not already one provided. This ensures that you can provide it
@@ -84,6 +96,11 @@ pub fn use_head(cx: Scope) -> MetaContext {
             route it may cause bugs. To be safe, you should provide_meta_context(cx) \
             somewhere in the root of the app."
             );
+            report_diagnostic(
+                cx,
+                DiagnosticKind::MissingContext,
+                "use_head() was called without a MetaContext being provided",
+            );
             let meta = MetaContext::new();
             provide_context(cx, meta.clone());
             meta
@@ -98,6 +115,16 @@ impl MetaContext {
         Default::default()
     }
 
+    /// Returns a [MetaContext] with the same metadata, whose inline `<script>` and `<style>` tags
+    /// are rendered with the given [Nonce]. The server integrations call this with
+    /// [use_nonce](leptos::use_nonce) before [MetaContext::dehydrate].
+    pub fn with_nonce(&self, nonce: Option<Nonce>) -> Self {
+        Self {
+            nonce,
+            ..self.clone()
+        }
+    }
+
     #[cfg(not(any(feature = "csr", feature = "hydrate")))]
     /// Converts the existing metadata tags into HTML that can be injected into the document head.
     ///
@@ -139,12 +166,67 @@ impl MetaContext {
             tags.push_str(&title);
             tags.push_str("</title>");
         }
+        // Preload hints
+        tags.push_str(&self.preloads.as_string());
+
         // Stylesheets
-        tags.push_str(&self.stylesheets.as_string());
+        tags.push_str(&self.stylesheets.as_string(self.nonce.as_ref()));
+
+        // Favicons
+        tags.push_str(&self.favicons.as_string());
+
+        // Meta tags
+        tags.push_str(&self.meta_tags.as_string());
+
+        // Color scheme
+        tags.push_str(&self.theme.as_script(self.nonce.as_ref()));
+
+        tags
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    /// Converts the attributes that components have set on the `<html>` element, like the color
+    /// scheme of a [Theme], into HTML that can be injected into its opening tag. Each attribute
+    /// has a leading space.
+    pub fn html_attributes(&self) -> String {
+        self.theme.as_attributes()
+    }
+
+    #[cfg(not(any(feature = "csr", feature = "hydrate")))]
+    /// Like [MetaContext::dehydrate], but inlines the given critical CSS in a `<style>` tag and loads
+    /// the stylesheets without blocking the first paint.
+    ///
+    /// The critical CSS can be generated with [extract_critical_css](leptos::extract_critical_css).
+    pub fn dehydrate_with_critical_css(&self, critical_css: &str) -> String {
+        let mut tags = String::new();
+
+        // Title
+        if let Some(title) = self.title.as_string() {
+            tags.push_str("<title>");
+            tags.push_str(&title);
+            tags.push_str("</title>");
+        }
+        // Preload hints
+        tags.push_str(&self.preloads.as_string());
+
+        // Critical CSS, then the deferred stylesheets
+        tags.push_str(&format!(
+            r#"<style id="leptos-critical-css"{}>"#,
+            self.nonce.as_ref().map(Nonce::to_attr).unwrap_or_default()
+        ));
+        tags.push_str(&stylesheet::escape_style_content(critical_css));
+        tags.push_str("</style>");
+        tags.push_str(&self.stylesheets.as_deferred_string(self.nonce.as_ref()));
+
+        // Favicons
+        tags.push_str(&self.favicons.as_string());
 
         // Meta tags
         tags.push_str(&self.meta_tags.as_string());
 
+        // Color scheme
+        tags.push_str(&self.theme.as_script(self.nonce.as_ref()));
+
         tags
     }
 }
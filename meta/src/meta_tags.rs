@@ -1,72 +1,114 @@
+use crate::{use_head, TextProp};
 use cfg_if::cfg_if;
-use leptos::{Scope, component, IntoView};
-use std::{rc::Rc, cell::{RefCell, Cell}, collections::HashMap};
+use leptos::*;
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
-use crate::{use_head, TextProp};
+/// The attribute that links a `<meta>` element rendered on the server to the [Meta] component
+/// that hydrates it.
+const META_ID_ATTRIBUTE: &str = "data-leptos-meta";
 
 /// Manages all of the `<meta>` elements set by [Meta] components.
+///
+/// Tags are de-duplicated by their `charset`, `name`, `property` or `http-equiv`: only the most
+/// recently rendered tag with each one is in the `<head>`, so that, e.g., a page can replace the
+/// description set by the root of the app. When that page's tag is removed, the one it replaced is
+/// shown again.
 #[derive(Clone, Default, Debug)]
 pub struct MetaTagsContext {
-	next_id: Cell<MetaTagId>,
-	#[allow(clippy::type_complexity)]
-    els: Rc<RefCell<HashMap<MetaTagId, (Option<MetaTag>, Option<web_sys::HtmlMetaElement>)>>>,
-}
-
-#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
-struct MetaTagId(usize);
-
-impl MetaTagsContext {
-	fn get_next_id(&self) -> MetaTagId {
-		let current_id = self.next_id.get();
-		let next_id = MetaTagId(current_id.0 + 1);
-		self.next_id.set(next_id);
-		next_id
-	}
+    next_id: Rc<Cell<usize>>,
+    tags: Rc<RefCell<Vec<MetaTag>>>,
 }
 
 #[derive(Clone, Debug)]
-enum MetaTag {
-	Charset(TextProp),
-	HttpEquiv {
-		http_equiv: TextProp,
-		content: Option<TextProp>
-	},
-	Name {
-		name: TextProp,
-		content: TextProp
-	}
+struct MetaTag {
+    id: usize,
+    /// The attribute and value that identify which tags replace each other, e.g. `name=description`.
+    key: String,
+    attrs: Vec<(&'static str, TextProp)>,
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    el: web_sys::Element,
 }
 
 impl MetaTagsContext {
+    fn next_id(&self) -> usize {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        id
+    }
+
     /// Converts the set of `<meta>` elements into an HTML string that can be injected into the `<head>`.
     pub fn as_string(&self) -> String {
-        self.els
-            .borrow()
-            .iter()
-            .filter_map(|(id, (tag, _))| {
-				tag.as_ref().map(|tag| {
-					let id = id.0;
-
-					match tag {
-						MetaTag::Charset(charset) => format!(r#"<meta charset="{}" data-leptos-meta="{id}">"#, charset.get()),
-						MetaTag::HttpEquiv { http_equiv, content } => {
-							if let Some(content) = &content {
-								format!(r#"<meta http-equiv="{}" content="{}" data-leptos-meta="{id}">"#, http_equiv.get(), content.get())
-							} else {
-								format!(r#"<meta http-equiv="{}" data-leptos-meta="{id}">"#, http_equiv.get())
-							}
-						},
-						MetaTag::Name { name, content } => format!(r#"<meta name="{}" content="{}" data-leptos-meta="{id}">"#, name.get(), content.get()),
-					}
-				})
-			})
+        let tags = self.tags.borrow();
+        tags.iter()
+            .filter(|tag| is_current(&tags, tag))
+            .map(|tag| {
+                let attrs: String = tag
+                    .attrs
+                    .iter()
+                    .map(|(name, value)| format!(r#" {name}="{}""#, escape_attr(&value.get())))
+                    .collect();
+                format!(r#"<meta{attrs} {META_ID_ATTRIBUTE}="{}">"#, tag.id)
+            })
             .collect()
     }
+
+    /// Adds a tag to the `<head>`, removing the tag it replaces.
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn insert(&self, tag: MetaTag) {
+        let mut tags = self.tags.borrow_mut();
+        if let Some(previous) = tags.iter().rev().find(|other| other.key == tag.key) {
+            previous.el.remove();
+        }
+        append_to_head(&tag.el);
+        tags.push(tag);
+    }
+
+    /// Removes a tag from the `<head>`, showing the tag it replaced again.
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    fn remove(&self, id: usize) {
+        let mut tags = self.tags.borrow_mut();
+        if let Some(idx) = tags.iter().position(|tag| tag.id == id) {
+            let was_current = is_current(&tags, &tags[idx]);
+            let tag = tags.remove(idx);
+            tag.el.remove();
+            if was_current {
+                if let Some(previous) = tags.iter().rev().find(|other| other.key == tag.key) {
+                    append_to_head(&previous.el);
+                }
+            }
+        }
+    }
+}
+
+/// Whether no tag rendered after this one replaces it.
+fn is_current(tags: &[MetaTag], tag: &MetaTag) -> bool {
+    tags.iter()
+        .rev()
+        .find(|other| other.key == tag.key)
+        .map(|other| other.id)
+        == Some(tag.id)
+}
+
+#[cfg(any(feature = "csr", feature = "hydrate"))]
+fn append_to_head(el: &web_sys::Element) {
+    document()
+        .query_selector("head")
+        .unwrap_throw()
+        .unwrap_throw()
+        .append_child(el)
+        .unwrap_throw();
 }
 
 /// Injects an [HTMLMetaElement](https://developer.mozilla.org/en-US/docs/Web/API/HTMLMetaElement) into the document
 /// head to set metadata
 ///
+/// Only one tag with each `charset`, `name`, `property` or `http_equiv` is rendered: a [Meta]
+/// replaces any that was rendered before it, until it is removed. The `content` can be reactive,
+/// and the element in the `<head>` is updated whenever it changes.
+///
 /// ```
 /// use leptos::*;
 /// use leptos_meta::*;
@@ -79,104 +121,88 @@ impl MetaTagsContext {
 ///     <main>
 ///       <Meta charset="utf-8"/>
 ///       <Meta name="description" content="A Leptos fan site."/>
+///       <Meta property="og:title" content="Leptos"/>
 ///       <Meta http_equiv="refresh" content="3;url=https://github.com/gbj/leptos"/>
 ///     </main>
 ///   }
 /// }
+///
+/// #[component]
+/// fn Post(cx: Scope, title: ReadSignal<String>) -> impl IntoView {
+///   view! { cx,
+///     // replaces the app's description while the post is shown
+///     <Meta name="description" content=move || format!("{} | A Leptos fan site.", title.get())/>
+///     <h1>{title}</h1>
+///   }
+/// }
 /// ```
 #[component(transparent)]
 pub fn Meta(
-	cx: Scope,
+    cx: Scope,
     /// The [`charset`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-charset) attribute.
     #[prop(optional, into)]
     charset: Option<TextProp>,
-	/// The [`name`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-name) attribute.
-	#[prop(optional, into)]
-	name: Option<TextProp>,
-	/// The [`http-equiv`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-http-equiv) attribute.
-	#[prop(optional, into)]
-	http_equiv: Option<TextProp>,
-	/// The [`content`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-content) attribute.
-	#[prop(optional, into)]
-	content: Option<TextProp>
+    /// The [`name`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-name) attribute.
+    #[prop(optional, into)]
+    name: Option<TextProp>,
+    /// The [`property`](https://ogp.me/) attribute, used by OpenGraph tags like `og:title`.
+    #[prop(optional, into)]
+    property: Option<TextProp>,
+    /// The [`http-equiv`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-http-equiv) attribute.
+    #[prop(optional, into)]
+    http_equiv: Option<TextProp>,
+    /// The [`content`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/meta#attr-content) attribute.
+    #[prop(optional, into)]
+    content: Option<TextProp>,
 ) -> impl IntoView {
+    let (attr, value) = match (charset, name, property, http_equiv) {
+        (Some(charset), ..) => ("charset", charset),
+        (_, Some(name), ..) => ("name", name),
+        (_, _, Some(property), _) => ("property", property),
+        (_, _, _, Some(http_equiv)) => ("http-equiv", http_equiv),
+        _ => panic!(
+            "<Meta/> tag expects one of `charset`, `name`, `property` or `http_equiv` to be set."
+        ),
+    };
+    let key = if attr == "charset" {
+        attr.to_string()
+    } else {
+        format!("{attr}={}", value.get())
+    };
+    let mut attrs = vec![(attr, value)];
+    if let Some(content) = content {
+        attrs.push(("content", content));
+    }
 
-	let tag = match (charset, name, http_equiv, content) {
-		(Some(charset), _, _, _) => MetaTag::Charset(charset),
-		(_, _, Some(http_equiv), content) => MetaTag::HttpEquiv { http_equiv, content },
-		(_, Some(name), _, Some(content)) => MetaTag::Name { name, content },
-		_ => panic!("<Meta/> tag expects either `charset`, `http_equiv`, or `name` and `content` to be set.")
-	};
+    let meta_tags = use_head(cx).meta_tags;
+    let id = meta_tags.next_id();
 
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
-            use leptos::{document, JsCast, UnwrapThrowExt, create_effect};
-
-            let meta = use_head(cx);
-			let meta_tags = meta.meta_tags;
-			let id = meta_tags.get_next_id();
-
-			let el = if let Ok(Some(el)) = document().query_selector(&format!("[data-leptos-meta='{}']", id.0)) {
-				el
-			} else {
-				document().create_element("meta").unwrap_throw()
-			};
-
-			match tag {
-				MetaTag::Charset(charset) => {
-					create_effect(cx, {
-						let el = el.clone();
-						move |_| {
-							_ = el.set_attribute("charset", &charset.get());
-						}
-					})
-				},
-				MetaTag::HttpEquiv { http_equiv, content } => {
-					create_effect(cx, {
-						let el = el.clone();
-						move |_| {
-							_ = el.set_attribute("http-equiv", &http_equiv.get());
-						}
-					});
-					if let Some(content) = content {
-						create_effect(cx, {
-							let el = el.clone();
-							move |_| {
-								_ = el.set_attribute("content", &content.get());
-							}
-						});
-					}
-				},
-				MetaTag::Name { name, content } => {
-					create_effect(cx, {
-						let el = el.clone();
-						move |_| {
-							_ = el.set_attribute("name", &name.get());
-						}
-					});
-					create_effect(cx, {
-						let el = el.clone();
-						move |_| {
-							_ = el.set_attribute("content", &content.get());
-						}
-					});
-				},
-			}
+            // the server may already have rendered this tag into the <head>
+            let el = match document().query_selector(&format!("meta[{META_ID_ATTRIBUTE}='{id}']")) {
+                Ok(Some(el)) => el,
+                _ => {
+                    let el = document().create_element("meta").unwrap_throw();
+                    el.set_attribute(META_ID_ATTRIBUTE, &id.to_string())
+                        .unwrap_throw();
+                    el
+                }
+            };
 
-			// add to head
-			document()
-                    .query_selector("head")
-                    .unwrap_throw()
-                    .unwrap_throw()
-                    .append_child(&el)
-                    .unwrap_throw();
+            for (attr, value) in attrs.iter().cloned() {
+                create_effect(cx, {
+                    let el = el.clone();
+                    move |_| {
+                        _ = el.set_attribute(attr, &value.get());
+                    }
+                });
+            }
 
-			// add to meta tags
-			meta_tags.els.borrow_mut().insert(id, (None, Some(el.unchecked_into())));
+            meta_tags.insert(MetaTag { id, key, attrs, el });
+            on_cleanup(cx, move || meta_tags.remove(id));
         } else {
-            let meta = use_head(cx);
-			let meta_tags = meta.meta_tags;
-            meta_tags.els.borrow_mut().insert(meta_tags.get_next_id(), (Some(tag), None));
+            meta_tags.tags.borrow_mut().push(MetaTag { id, key, attrs });
         }
     }
 }
@@ -0,0 +1,221 @@
+use crate::use_head;
+use cfg_if::cfg_if;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+
+/// Manages all of the preload hints requested by [Preload] components.
+#[derive(Clone, Default, Debug)]
+pub struct PreloadContext {
+    hints: Rc<RefCell<Vec<PreloadHint>>>,
+}
+
+/// The kind of resource being preloaded, which sets the `as` attribute of the `<link>`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PreloadKind {
+    /// A font file. Fonts are always fetched in CORS mode, so these get `crossorigin`.
+    Font,
+    /// An image, e.g., a hero image above the fold.
+    Image,
+    /// A stylesheet.
+    Style,
+    /// A classic script.
+    Script,
+    /// A JavaScript module, preloaded with `rel="modulepreload"`.
+    Module,
+    /// A resource loaded with `fetch()`, like a WASM binary. These get `crossorigin`.
+    Fetch,
+    /// A document that will be embedded in an `<iframe>`.
+    Document,
+}
+
+impl PreloadKind {
+    fn rel(&self) -> &'static str {
+        match self {
+            PreloadKind::Module => "modulepreload",
+            _ => "preload",
+        }
+    }
+
+    fn as_attr(&self) -> Option<&'static str> {
+        match self {
+            PreloadKind::Font => Some("font"),
+            PreloadKind::Image => Some("image"),
+            PreloadKind::Style => Some("style"),
+            PreloadKind::Script => Some("script"),
+            PreloadKind::Module => None,
+            PreloadKind::Fetch => Some("fetch"),
+            PreloadKind::Document => Some("document"),
+        }
+    }
+
+    fn needs_crossorigin(&self) -> bool {
+        matches!(self, PreloadKind::Font | PreloadKind::Fetch)
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct PreloadHint {
+    href: String,
+    kind: PreloadKind,
+    mime_type: Option<String>,
+    media: Option<String>,
+    crossorigin: Option<String>,
+}
+
+impl PreloadHint {
+    fn attrs(&self) -> Vec<(&'static str, String)> {
+        let mut attrs = vec![("rel", self.kind.rel().to_string())];
+        if let Some(as_attr) = self.kind.as_attr() {
+            attrs.push(("as", as_attr.to_string()));
+        }
+        attrs.push(("href", self.href.clone()));
+        if let Some(mime_type) = &self.mime_type {
+            attrs.push(("type", mime_type.clone()));
+        }
+        if let Some(media) = &self.media {
+            attrs.push(("media", media.clone()));
+        }
+        match &self.crossorigin {
+            Some(crossorigin) => attrs.push(("crossorigin", crossorigin.clone())),
+            None if self.kind.needs_crossorigin() => attrs.push(("crossorigin", String::new())),
+            None => {}
+        }
+        attrs
+    }
+}
+
+impl PreloadContext {
+    /// Converts the set of preload hints into an HTML string that can be injected into the `<head>`.
+    /// Attribute values are escaped, so a URL can't end its attribute or the tag.
+    ///
+    /// ```
+    /// use leptos::*;
+    /// use leptos_meta::*;
+    ///
+    /// # #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+    /// run_scope(create_runtime(), |cx| {
+    ///   provide_meta_context(cx);
+    ///   _ = view! { cx,
+    ///     <Preload href="/hero.avif?w=1&h=2" kind=PreloadKind::Image media="(min-width: 1px)"/>
+    ///     <Preload href="/font.woff2\"><script>" kind=PreloadKind::Font/>
+    ///     <Stylesheet href="/style.css?v=\"><script>"/>
+    ///   }
+    ///   .into_view(cx);
+    ///
+    ///   assert_eq!(
+    ///     use_head(cx).dehydrate(),
+    ///     concat!(
+    ///       r#"<link rel="preload" as="image" href="/hero.avif?w=1&amp;h=2" media="(min-width: 1px)">"#,
+    ///       r#"<link rel="preload" as="font" href="/font.woff2&quot;&gt;&lt;script&gt;" crossorigin="">"#,
+    ///       r#"<link rel="stylesheet" href="/style.css?v=&quot;&gt;&lt;script&gt;">"#,
+    ///     )
+    ///   );
+    /// });
+    /// # }
+    /// ```
+    pub fn as_string(&self) -> String {
+        self.hints
+            .borrow()
+            .iter()
+            .map(|hint| {
+                let attrs: String = hint
+                    .attrs()
+                    .into_iter()
+                    .map(|(name, value)| format!(r#" {name}="{}""#, escape_attr(&value)))
+                    .collect();
+                format!("<link{attrs}>")
+            })
+            .collect()
+    }
+
+    /// Adds a hint, unless the same resource has already been requested.
+    /// Returns `true` if the hint was added.
+    fn insert(&self, hint: PreloadHint) -> bool {
+        let mut hints = self.hints.borrow_mut();
+        if hints
+            .iter()
+            .any(|existing| existing.href == hint.href && existing.kind == hint.kind)
+        {
+            false
+        } else {
+            hints.push(hint);
+            true
+        }
+    }
+}
+
+/// Asks the browser to start loading a resource the page will need soon, like a font or a hero
+/// image, by injecting a `<link rel="preload">` into the document head.
+///
+/// During server rendering, hints requested while rendering the app shell are hoisted into the
+/// `<head>` of the streamed HTML. (Hints requested under a `<Suspense/>` that resolves later are
+/// added in the browser once it hydrates.) Each resource is only preloaded once.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp(cx: Scope) -> impl IntoView {
+///   provide_meta_context(cx);
+///
+///   view! { cx,
+///     <main>
+///       <Preload href="/fonts/inter.woff2" kind=PreloadKind::Font mime_type="font/woff2"/>
+///       <Preload href="/img/hero.avif" kind=PreloadKind::Image/>
+///     </main>
+///   }
+/// }
+/// ```
+#[component(transparent)]
+pub fn Preload(
+    cx: Scope,
+    /// The URL of the resource to preload.
+    #[prop(into)]
+    href: String,
+    /// What kind of resource is being loaded.
+    kind: PreloadKind,
+    /// The MIME type of the resource, e.g. `font/woff2`. Browsers skip preloads for types they don't support.
+    #[prop(optional, into)]
+    mime_type: Option<String>,
+    /// A media query, so that the resource is only preloaded when it matches.
+    #[prop(optional, into)]
+    media: Option<String>,
+    /// Overrides the `crossorigin` attribute, which is set automatically for fonts and fetches.
+    #[prop(optional, into)]
+    crossorigin: Option<String>,
+) -> impl IntoView {
+    let meta = use_head(cx);
+    let hint = PreloadHint {
+        href: use_asset_path(cx, &href),
+        kind,
+        mime_type,
+        media,
+        crossorigin,
+    };
+
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            if meta.preloads.insert(hint.clone()) {
+                // quoted as a CSS string, so that any URL can be looked up
+                let href = hint.href.replace('\\', "\\\\").replace('"', "\\\"");
+                let selector = format!(r#"link[rel="{}"][href="{href}"]"#, hint.kind.rel());
+                // the server may already have hoisted this hint into the <head>
+                if let Ok(None) = document().query_selector(&selector) {
+                    let el = document().create_element("link").unwrap_throw();
+                    for (name, value) in hint.attrs() {
+                        el.set_attribute(name, &value).unwrap_throw();
+                    }
+                    document()
+                        .query_selector("head")
+                        .unwrap_throw()
+                        .unwrap_throw()
+                        .append_child(el.unchecked_ref())
+                        .unwrap_throw();
+                }
+            }
+        } else {
+            meta.preloads.insert(hint);
+        }
+    }
+}
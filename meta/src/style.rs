@@ -0,0 +1,200 @@
+use crate::use_head;
+use cfg_if::cfg_if;
+use leptos::*;
+use std::rc::Rc;
+
+/// The attribute that records the position of an injected style's layer, so that styles injected
+/// later can be placed in order.
+pub(crate) const LAYER_ATTRIBUTE: &str = "data-leptos-layer";
+
+/// Controls where [Style] and [Stylesheet] components inject their elements, and in what order.
+///
+/// By default, styles are appended to the document `<head>` in the order in which they are
+/// rendered. A `StyleContext` can instead:
+/// 1. inject them into a [ShadowRoot](web_sys::ShadowRoot), so that they apply to the components
+///    rendered inside it, and
+/// 2. sort them into named layers, so that, e.g., utility classes always come after (and override)
+///    component styles, no matter which component happens to render first.
+///
+/// Styles without a `layer` come before all the named layers. Provide it with
+/// [provide_style_context]; a component tree rendered into a shadow root can provide its own.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp(cx: Scope) -> impl IntoView {
+///   provide_meta_context(cx);
+///   provide_style_context(
+///     cx,
+///     StyleContext::new().layers(["reset", "components", "utilities"]),
+///   );
+///
+///   view! { cx,
+///     <main>
+///       // injected after `button.css`, even though it is rendered first
+///       <Stylesheet href="/utilities.css" layer="utilities"/>
+///       <Stylesheet href="/button.css" layer="components"/>
+///     </main>
+///   }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct StyleContext {
+    root: Option<web_sys::ShadowRoot>,
+    layers: Rc<Vec<String>>,
+}
+
+impl StyleContext {
+    /// Creates a [StyleContext] that injects styles into the document `<head>`, without layers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Injects styles into the given shadow root, rather than the document `<head>`.
+    ///
+    /// Shadow roots only exist in the browser, so styles are still added to the `<head>` when
+    /// rendering on the server.
+    pub fn shadow_root(mut self, root: web_sys::ShadowRoot) -> Self {
+        self.root = Some(root);
+        self
+    }
+
+    /// Sets the names of the layers that styles can be placed in, from first to last. Styles in
+    /// later layers are injected after, and so override, styles in earlier ones.
+    pub fn layers<I, S>(mut self, layers: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.layers = Rc::new(layers.into_iter().map(Into::into).collect());
+        self
+    }
+
+    /// The position of a layer, which is `0` for styles without a layer. Layers that haven't been
+    /// declared with [StyleContext::layers] come after all the declared ones.
+    pub fn layer_order(&self, layer: Option<&str>) -> usize {
+        match layer {
+            None => 0,
+            Some(layer) => match self.layers.iter().position(|name| name == layer) {
+                Some(idx) => idx + 1,
+                None => {
+                    leptos::leptos_dom::debug_warn!(
+                        "the style layer {layer:?} hasn't been declared in the StyleContext, so \
+                         it will be injected after all the declared layers"
+                    );
+                    self.layers.len() + 1
+                }
+            },
+        }
+    }
+
+    /// Injects a `<style>` or `<link>` element into the root, after every style whose layer comes
+    /// before or is the same as its own.
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    pub(crate) fn inject(&self, el: &web_sys::Element, order: usize) {
+        let root: web_sys::Node = match &self.root {
+            Some(root) => root.clone().unchecked_into(),
+            None => document()
+                .query_selector("head")
+                .unwrap_throw()
+                .unwrap_throw()
+                .unchecked_into(),
+        };
+        if order > 0 {
+            el.set_attribute(LAYER_ATTRIBUTE, &order.to_string())
+                .unwrap_throw();
+        }
+
+        let mut next = root.first_child();
+        while let Some(node) = next {
+            let later = node
+                .dyn_ref::<web_sys::Element>()
+                .and_then(|el| el.get_attribute(LAYER_ATTRIBUTE))
+                .and_then(|other| other.parse::<usize>().ok())
+                .map(|other| other > order)
+                .unwrap_or(false);
+            if later {
+                break;
+            }
+            next = node.next_sibling();
+        }
+        root.insert_before(el.unchecked_ref(), next.as_ref())
+            .unwrap_throw();
+    }
+}
+
+/// Provides a [StyleContext] that controls where and in what order [Style] and [Stylesheet]
+/// components in this part of the app inject their elements.
+pub fn provide_style_context(cx: Scope, context: StyleContext) {
+    provide_context(cx, context);
+}
+
+/// Returns the current [StyleContext], or one that injects into the document `<head>` if none has
+/// been provided.
+pub fn use_style_context(cx: Scope) -> StyleContext {
+    use_context::<StyleContext>(cx).unwrap_or_default()
+}
+
+/// Injects an [HTMLStyleElement](https://developer.mozilla.org/en-US/docs/Web/API/HTMLStyleElement)
+/// with the given CSS into the document head, or wherever the current [StyleContext] says.
+///
+/// When the style is rendered on the server, any `</style` in the CSS is escaped, so that it
+/// can't end the `<style>` tag early.
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn MyApp(cx: Scope) -> impl IntoView {
+///   provide_meta_context(cx);
+///
+///   view! { cx,
+///     <main>
+///       <Style id="banner" css=".banner { color: red; }"/>
+///     </main>
+///   }
+/// }
+/// ```
+#[component(transparent)]
+pub fn Style(
+    cx: Scope,
+    /// The CSS to inject.
+    #[prop(into)]
+    css: String,
+    /// An ID for the `<style>` element. Styles with an ID are only injected once.
+    #[prop(optional, into)]
+    id: Option<String>,
+    /// The layer of the [StyleContext] to place the style in.
+    #[prop(optional, into)]
+    layer: Option<String>,
+) -> impl IntoView {
+    let meta = use_head(cx);
+    let styles = use_style_context(cx);
+    let order = styles.layer_order(layer.as_deref());
+
+    cfg_if! {
+        if #[cfg(any(feature = "csr", feature = "hydrate"))] {
+            if meta.stylesheets.contains_style(id.as_deref(), &css) {
+                return;
+            }
+            // the server may already have rendered this style into the <head>
+            let existing = id.as_ref().and_then(|id| document().get_element_by_id(id));
+            if existing.is_none() {
+                let el = document().create_element("style").unwrap_throw();
+                if let Some(id) = &id {
+                    el.set_attribute("id", id).unwrap_throw();
+                }
+                el.set_text_content(Some(&css));
+                styles.inject(&el, order);
+            }
+            meta.stylesheets.insert_style(id, css, order);
+        } else {
+            if !meta.stylesheets.contains_style(id.as_deref(), &css) {
+                meta.stylesheets.insert_style(id, css, order);
+            }
+        }
+    }
+}
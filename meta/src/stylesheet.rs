@@ -1,35 +1,163 @@
-use crate::use_head;
+use crate::{style::LAYER_ATTRIBUTE, use_head, use_style_context};
 use cfg_if::cfg_if;
 use leptos::*;
-use std::{cell::RefCell, collections::HashMap, rc::Rc};
+use std::{borrow::Cow, cell::RefCell, rc::Rc};
 
-/// Manages all of the stylesheets set by [Stylesheet] components.
+/// Manages all of the stylesheets set by [Stylesheet] and [Style] components.
 #[derive(Clone, Default, Debug)]
 pub struct StylesheetContext {
-    #[allow(clippy::type_complexity)]
-    els: Rc<RefCell<HashMap<(Option<String>, String), Option<web_sys::HtmlLinkElement>>>>,
+    els: Rc<RefCell<Vec<StyleEntry>>>,
+}
+
+#[derive(Clone, Debug)]
+struct StyleEntry {
+    id: Option<String>,
+    source: StyleSource,
+    /// The position of the entry's layer in the [StyleContext](crate::StyleContext).
+    order: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum StyleSource {
+    Href(String),
+    Inline(String),
+}
+
+impl StyleEntry {
+    fn attrs(&self) -> String {
+        let mut attrs = String::new();
+        if let Some(id) = &self.id {
+            attrs.push_str(&format!(r#" id="{}""#, escape_attr(id)));
+        }
+        if self.order > 0 {
+            attrs.push_str(&format!(r#" {LAYER_ATTRIBUTE}="{}""#, self.order));
+        }
+        attrs
+    }
 }
 
 impl StylesheetContext {
     /// Converts the set of stylesheets into an HTML string that can be injected into the `<head>`.
-    pub fn as_string(&self) -> String {
-        self.els
-            .borrow()
+    ///
+    /// Stylesheets are sorted by their layer, and otherwise kept in the order they were rendered.
+    /// Inline styles are allowed by the page's Content Security Policy with the given [Nonce], if
+    /// any. Their CSS can't end the `<style>` tag, and attributes are escaped.
+    ///
+    /// ```
+    /// use leptos::*;
+    /// use leptos_meta::*;
+    ///
+    /// # #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+    /// run_scope(create_runtime(), |cx| {
+    ///   provide_meta_context(cx);
+    ///   _ = view! { cx,
+    ///     <Style id="quote\"" css="p::after { content: '</STYLE><script>' }"/>
+    ///   }
+    ///   .into_view(cx);
+    ///
+    ///   assert_eq!(
+    ///     use_head(cx).dehydrate(),
+    ///     r#"<style id="quote&quot;">p::after { content: '<\/STYLE><script>' }</style>"#
+    ///   );
+    /// });
+    /// # }
+    /// ```
+    pub fn as_string(&self, nonce: Option<&Nonce>) -> String {
+        let nonce = nonce.map(Nonce::to_attr).unwrap_or_default();
+        self.sorted()
             .iter()
-            .map(|((id, href), _)| {
-                if let Some(id) = id {
-                    format!(r#"<link rel="stylesheet" id="{id}" href="{href}">"#)
-                } else {
-                    format!(r#"<link rel="stylesheet" href="{href}">"#)
-                }
+            .map(|entry| match &entry.source {
+                StyleSource::Href(href) => format!(
+                    r#"<link rel="stylesheet"{} href="{}">"#,
+                    entry.attrs(),
+                    escape_attr(href)
+                ),
+                StyleSource::Inline(css) => format!(
+                    "<style{}{nonce}>{}</style>",
+                    entry.attrs(),
+                    escape_style_content(css)
+                ),
+            })
+            .collect()
+    }
+
+    /// Converts the set of stylesheets into HTML that loads them without blocking the first paint,
+    /// for use alongside inlined critical CSS. A `<noscript>` fallback loads them normally.
+    pub fn as_deferred_string(&self, nonce: Option<&Nonce>) -> String {
+        let nonce = nonce.map(Nonce::to_attr).unwrap_or_default();
+        self.sorted()
+            .iter()
+            .map(|entry| match &entry.source {
+                StyleSource::Href(href) => format!(
+                    r#"<link rel="preload" as="style"{} href="{href}" onload="this.onload=null;this.rel='stylesheet'"><noscript><link rel="stylesheet" href="{href}"></noscript>"#,
+                    entry.attrs(),
+                    href = escape_attr(href)
+                ),
+                StyleSource::Inline(css) => format!(
+                    "<style{}{nonce}>{}</style>",
+                    entry.attrs(),
+                    escape_style_content(css)
+                ),
             })
             .collect()
     }
+
+    fn sorted(&self) -> Vec<StyleEntry> {
+        let mut entries = self.els.borrow().clone();
+        entries.sort_by_key(|entry| entry.order);
+        entries
+    }
+
+    fn contains(&self, id: Option<&str>, source: &StyleSource) -> bool {
+        self.els.borrow().iter().any(|entry| match (id, &entry.id) {
+            (Some(id), Some(existing)) => id == existing,
+            _ => entry.id.as_deref() == id && &entry.source == source,
+        })
+    }
+
+    pub(crate) fn contains_style(&self, id: Option<&str>, css: &str) -> bool {
+        self.contains(id, &StyleSource::Inline(css.to_string()))
+    }
+
+    pub(crate) fn insert_style(&self, id: Option<String>, css: String, order: usize) {
+        self.els.borrow_mut().push(StyleEntry {
+            id,
+            source: StyleSource::Inline(css),
+            order,
+        });
+    }
+}
+
+/// Escapes every `</style` in CSS that is written into a `<style>` tag, which would otherwise end
+/// the tag early, as `<\/style`, which CSS reads the same way.
+pub(crate) fn escape_style_content(css: &str) -> Cow<'_, str> {
+    const END_TAG: &str = "</style";
+
+    let lowercase = css.to_ascii_lowercase();
+    if !lowercase.contains(END_TAG) {
+        return Cow::Borrowed(css);
+    }
+
+    let mut escaped = String::with_capacity(css.len() + 1);
+    let mut rest = 0;
+    for (idx, _) in lowercase.match_indices(END_TAG) {
+        escaped.push_str(&css[rest..idx]);
+        escaped.push_str("<\\/");
+        rest = idx + 2;
+    }
+    escaped.push_str(&css[rest..]);
+    Cow::Owned(escaped)
 }
 
 /// Injects an [HTMLLinkElement](https://developer.mozilla.org/en-US/docs/Web/API/HTMLLinkElement) into the document
 /// head that loads a stylesheet from the URL given by the `href` property.
 ///
+/// Where the stylesheet is injected, and in what order, can be controlled by providing a
+/// [StyleContext](crate::StyleContext).
+///
+/// The `href` is resolved with [use_asset_path], so it will point at the fingerprinted
+/// file if an [AssetManifest] has been provided.
+///
 /// ```
 /// use leptos::*;
 /// use leptos_meta::*;
@@ -54,51 +182,53 @@ pub fn Stylesheet(
     /// An ID for the stylesheet.
     #[prop(optional, into)]
     id: Option<String>,
+    /// The layer of the [StyleContext](crate::StyleContext) to place the stylesheet in.
+    #[prop(optional, into)]
+    layer: Option<String>,
 ) -> impl IntoView {
+    let href = use_asset_path(cx, &href);
+    let meta = use_head(cx);
+    let styles = use_style_context(cx);
+    let order = styles.layer_order(layer.as_deref());
+    let source = StyleSource::Href(href.clone());
+
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
             use leptos::document;
 
-            let meta = use_head(cx);
-
-            // TODO I guess this will create a duplicated <link> when hydrating
-            let existing_el = {
-                let els = meta.stylesheets.els.borrow();
-                let key = (id.clone(), href.clone());
-                els.get(&key).cloned()
-            };
-            if let Some(Some(_)) = existing_el {
+            if meta.stylesheets.contains(id.as_deref(), &source) {
                 leptos::leptos_dom::debug_warn!("<Stylesheet/> already loaded stylesheet {href}");
             } else {
+                // the server may already have rendered this stylesheet into the <head>
                 let element_to_hydrate = id.as_ref()
                     .and_then(|id| {
                         document().get_element_by_id(&id)
                     });
 
-                let el = element_to_hydrate.unwrap_or_else(|| {
+                if element_to_hydrate.is_none() {
                     let el = document().create_element("link").unwrap_throw();
                     el.set_attribute("rel", "stylesheet").unwrap_throw();
                     if let Some(id_val) = &id{
                         el.set_attribute("id", id_val).unwrap_throw();
                     }
                     el.set_attribute("href", &href).unwrap_throw();
-                    document()
-                        .query_selector("head")
-                        .unwrap_throw()
-                        .unwrap_throw()
-                        .append_child(el.unchecked_ref())
-                        .unwrap_throw();
-                    el
-                });
+                    styles.inject(&el, order);
+                }
 
-                meta.stylesheets
-                    .els
-                    .borrow_mut()
-                    .insert((id, href), Some(el.unchecked_into()));
+                meta.stylesheets.els.borrow_mut().push(StyleEntry {
+                    id,
+                    source,
+                    order,
+                });
             }
         } else {
-            let meta = use_head(cx);
-            meta.stylesheets.els.borrow_mut().insert((id,href), None);
+            if !meta.stylesheets.contains(id.as_deref(), &source) {
+                meta.stylesheets.els.borrow_mut().push(StyleEntry {
+                    id,
+                    source,
+                    order,
+                });
+            }
         }
     }
 }
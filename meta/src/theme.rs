@@ -0,0 +1,169 @@
+use crate::use_head;
+use leptos::*;
+use std::{cell::RefCell, rc::Rc};
+
+/// The cookie in which the color scheme chosen by the user is kept.
+const COOKIE_NAME: &str = "leptos_color_scheme";
+
+/// The attribute on `<html>` that is set to the current color scheme.
+const ATTRIBUTE: &str = "data-color-scheme";
+
+/// Contains the color scheme that should be set on `<html>`, if a [Theme] has been provided.
+#[derive(Clone, Default, Debug)]
+pub struct ThemeContext {
+    color_scheme: Rc<RefCell<Option<Memo<ColorScheme>>>>,
+}
+
+impl ThemeContext {
+    /// The attributes to add to the `<html>` element, each with a leading space, e.g.,
+    /// ` data-color-scheme="dark"`.
+    pub fn as_attributes(&self) -> String {
+        match *self.color_scheme.borrow() {
+            Some(color_scheme) => {
+                format!(r#" {ATTRIBUTE}="{}""#, color_scheme.get_untracked())
+            }
+            None => String::new(),
+        }
+    }
+
+    /// An inline script that sets the color scheme on `<html>` before the page is painted, so that
+    /// a page rendered on the server for a different color scheme than the user's doesn't flash.
+    /// It's allowed by the page's Content Security Policy with the given [Nonce], if any, which
+    /// [MetaContext::dehydrate](crate::MetaContext::dehydrate) passes from
+    /// [MetaContext::with_nonce](crate::MetaContext::with_nonce).
+    ///
+    /// ```
+    /// use leptos::*;
+    /// use leptos_meta::*;
+    ///
+    /// # #[cfg(not(any(feature = "csr", feature = "hydrate")))] {
+    /// run_scope(create_runtime(), |cx| {
+    ///   provide_meta_context(cx);
+    ///   provide_theme(cx);
+    ///   let nonce = provide_nonce(cx, "r4nd0m");
+    ///
+    ///   let head = use_head(cx).with_nonce(Some(nonce)).dehydrate();
+    ///   assert!(head.contains(r#"<script nonce="r4nd0m">(function(){"#));
+    /// });
+    /// # }
+    /// ```
+    pub fn as_script(&self, nonce: Option<&Nonce>) -> String {
+        if self.color_scheme.borrow().is_none() {
+            return String::new();
+        }
+        format!(
+            "<script{}>(function(){{\
+                var c=document.cookie.match(/(?:^|; ){COOKIE_NAME}=(light|dark)(?:;|$)/);\
+                var s=c?c[1]:(matchMedia('(prefers-color-scheme: dark)').matches?'dark':'light');\
+                document.documentElement.setAttribute('{ATTRIBUTE}',s);\
+            }})()</script>",
+            nonce.map(Nonce::to_attr).unwrap_or_default()
+        )
+    }
+}
+
+/// The app's color scheme, which is either the one the user has chosen, or the one their system
+/// prefers. Use [provide_theme] to create it, and `use_context::<Theme>(cx)` to get it in other
+/// components.
+///
+/// The color scheme is set as the `data-color-scheme` attribute of the `<html>` element, both when
+/// rendering on the server and in the browser, so that it can be used in CSS:
+/// ```css
+/// [data-color-scheme="dark"] {
+///   color-scheme: dark;
+///   --background: #222;
+/// }
+/// ```
+/// The user's choice is kept in a cookie, so that the server renders the page in the right color
+/// scheme. Otherwise, the server uses the `Sec-CH-Prefers-Color-Scheme`
+/// [client hint](leptos::ClientHints) if the browser has sent it, and an inline script in the
+/// `<head>` corrects the color scheme before the page is painted if it was wrong.
+#[derive(Clone, Copy, Debug)]
+pub struct Theme {
+    color_scheme: Memo<ColorScheme>,
+    chosen: ReadSignal<Option<ColorScheme>>,
+    set_chosen: SignalSetter<Option<ColorScheme>>,
+}
+
+impl Theme {
+    /// The current color scheme.
+    pub fn color_scheme(&self) -> Memo<ColorScheme> {
+        self.color_scheme
+    }
+
+    /// The color scheme chosen by the user, or `None` if the system's preference is used.
+    pub fn chosen(&self) -> ReadSignal<Option<ColorScheme>> {
+        self.chosen
+    }
+
+    /// Chooses a color scheme, which is remembered for a year. `None` goes back to the system's
+    /// preference.
+    pub fn set(&self, color_scheme: Option<ColorScheme>) {
+        self.set_chosen.set(color_scheme);
+    }
+
+    /// Chooses the opposite of the current color scheme.
+    pub fn toggle(&self) {
+        self.set(Some(match self.color_scheme.get_untracked() {
+            ColorScheme::Light => ColorScheme::Dark,
+            ColorScheme::Dark => ColorScheme::Light,
+        }));
+    }
+}
+
+/// Provides a [Theme] as context, if there isn't one already, and returns it. This should be
+/// called near the root of the app, after [provide_meta_context](crate::provide_meta_context).
+///
+/// ```
+/// use leptos::*;
+/// use leptos_meta::*;
+///
+/// #[component]
+/// fn App(cx: Scope) -> impl IntoView {
+///   provide_meta_context(cx);
+///   let theme = provide_theme(cx);
+///
+///   view! { cx,
+///     <button on:click=move |_| theme.toggle()>
+///       {move || match theme.color_scheme().get() {
+///         ColorScheme::Light => "Dark mode",
+///         ColorScheme::Dark => "Light mode",
+///       }}
+///     </button>
+///   }
+/// }
+/// ```
+pub fn provide_theme(cx: Scope) -> Theme {
+    if let Some(theme) = use_context::<Theme>(cx) {
+        return theme;
+    }
+
+    let preferred = use_prefers_color_scheme(cx);
+    let (chosen, set_chosen) = create_cookie_signal::<ColorScheme>(
+        cx,
+        COOKIE_NAME,
+        CookieOptions {
+            max_age: Some(60 * 60 * 24 * 365),
+            ..Default::default()
+        },
+    );
+    let color_scheme = create_memo(cx, move |_| chosen.get().unwrap_or_else(|| preferred.get()));
+
+    let theme = Theme {
+        color_scheme,
+        chosen,
+        set_chosen,
+    };
+    provide_context(cx, theme);
+    *use_head(cx).theme.color_scheme.borrow_mut() = Some(color_scheme);
+
+    #[cfg(any(feature = "csr", feature = "hydrate"))]
+    create_effect(cx, move |_| {
+        let color_scheme = color_scheme.get().to_string();
+        if let Some(html) = document().document_element() {
+            _ = html.set_attribute(ATTRIBUTE, &color_scheme);
+        }
+    });
+
+    theme
+}
@@ -1,14 +1,18 @@
 use crate::{use_head, TextProp};
 use cfg_if::cfg_if;
 use leptos::*;
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
 
 /// Contains the current state of the document's `<title>`.
 #[derive(Clone, Default)]
 pub struct TitleContext {
     #[cfg(any(feature = "csr", feature = "hydrate"))]
     el: Rc<RefCell<Option<web_sys::HtmlTitleElement>>>,
-    formatter: Rc<RefCell<Option<Formatter>>>,
+    formatters: Rc<RefCell<Vec<(usize, Formatter)>>>,
+    next_formatter_id: Rc<Cell<usize>>,
     text: Rc<RefCell<Option<TextProp>>>,
 }
 
@@ -17,13 +21,33 @@ impl TitleContext {
     pub fn as_string(&self) -> Option<String> {
         let title = self.text.borrow().as_ref().map(|f| (f.0)());
         title.map(|title| {
-            if let Some(formatter) = &*self.formatter.borrow() {
-                (formatter.0)(title)
-            } else {
-                title
-            }
+            // formatters lower in the tree are added later, and are applied first
+            self.formatters
+                .borrow()
+                .iter()
+                .rev()
+                .fold(title, |title, (_, formatter)| (formatter.0)(title))
         })
     }
+
+    fn push_formatter(&self, formatter: Formatter) -> usize {
+        let id = self.next_formatter_id.get();
+        self.next_formatter_id.set(id + 1);
+        self.formatters.borrow_mut().push((id, formatter));
+        id
+    }
+
+    fn remove_formatter(&self, id: usize) {
+        self.formatters
+            .borrow_mut()
+            .retain(|(other, _)| *other != id);
+
+        // the formatter isn't tracked by the `<Title/>` effects, so update the title here
+        #[cfg(any(feature = "csr", feature = "hydrate"))]
+        if let (Some(el), Some(text)) = (&*self.el.borrow(), self.as_string()) {
+            el.set_text_content(Some(&text));
+        }
+    }
 }
 
 impl std::fmt::Debug for TitleContext {
@@ -49,6 +73,14 @@ where
 /// The `title` and `formatter` can be set independently of one another. For example, you can create a root-level
 /// `<Title formatter=.../>` that will wrap each of the text values of `<Title/>` components created lower in the tree.
 ///
+/// Formatters compose: if a `<Title formatter=.../>` lower in the tree also sets a formatter, the text is
+/// passed through the inner formatter first, and then wrapped by the outer one. When the inner component is
+/// unmounted (for example, when navigating away from a nested route), its formatter is removed again.
+///
+/// A `<Title/>` inside a [`<Suspense/>`](leptos::Suspense) only sets the title once the resources read under
+/// that `<Suspense/>` have resolved, rather than showing a placeholder while they load. When streaming HTML
+/// from the server, this makes the `<Suspense/>` block, so that the `<head>` includes the final title.
+///
 /// ```
 /// use leptos::*;
 /// use leptos_meta::*;
@@ -83,6 +115,17 @@ where
 ///     </main>
 ///   }
 /// }
+///
+/// #[component]
+/// fn Settings(cx: Scope) -> impl IntoView {
+///   view! { cx,
+///     <main>
+///       // "Profile" in a nested route sets title to "Profile | Settings — Leptos Online"
+///       <Title formatter=|text| format!("{text} | Settings")/>
+///       // ... nested routing logic here
+///     </main>
+///   }
+/// }
 /// ```
 #[component(transparent)]
 pub fn Title(
@@ -96,17 +139,20 @@ pub fn Title(
 ) -> impl IntoView {
     let meta = use_head(cx);
 
+    if let Some(formatter) = formatter {
+        let id = meta.title.push_formatter(formatter);
+        let title = meta.title.clone();
+        on_cleanup(cx, move || title.remove_formatter(id));
+    }
+
     cfg_if! {
         if #[cfg(any(feature = "csr", feature = "hydrate"))] {
-            if let Some(formatter) = formatter {
-                *meta.title.formatter.borrow_mut() = Some(formatter);
-            }
             if let Some(text) = text {
                 *meta.title.text.borrow_mut() = Some(text);
             }
 
             let el = {
-                let el_ref = meta.title.el.borrow_mut();
+                let mut el_ref = meta.title.el.borrow_mut();
                 let el = if let Some(el) = &*el_ref {
                     el.clone()
                 } else {
@@ -124,18 +170,36 @@ pub fn Title(
                         }
                     }
                 };
+                *el_ref = Some(el.clone());
                 el
             };
 
+            let suspense = use_context::<SuspenseContext>(cx);
             create_render_effect(cx, move |_| {
                 let text = meta.title.as_string().unwrap_or_default();
 
-                el.set_text_content(Some(&text));
+                match suspense {
+                    // subscribes to the pending resources, so the title is set once they resolve
+                    Some(suspense) if !suspense.ready() => {}
+                    // resources read while getting the text are only counted in a microtask,
+                    // so check again once they have been
+                    Some(suspense) => {
+                        let el = el.clone();
+                        queue_microtask(move || {
+                            if suspense.ready() {
+                                el.set_text_content(Some(&text));
+                            }
+                        });
+                    }
+                    None => el.set_text_content(Some(&text)),
+                }
             });
         } else {
-            if let Some(formatter) = formatter {
-                *meta.title.formatter.borrow_mut() = Some(formatter);
+            // the `<head>` is sent before any `<Suspense/>` that isn't blocking has resolved
+            if let Some(suspense) = use_context::<SuspenseContext>(cx) {
+                suspense.set_should_block();
             }
+
             if let Some(text) = text {
                 *meta.title.text.borrow_mut() = Some(text);
             }
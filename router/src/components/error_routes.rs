@@ -0,0 +1,185 @@
+use leptos::*;
+use std::{cell::Cell, rc::Rc};
+
+use crate::use_location;
+
+/// The views that a [Routes](crate::Routes) shows instead of a route, for each HTTP status code.
+///
+/// When `<Routes errors=...>` is given, it shows the `404 Not Found` view whenever no route
+/// matches the URL, and the view for whichever status a route passes to [show_route_error], e.g.
+/// when the user isn't allowed to see it or its data can't be loaded. This works the same way
+/// when navigating in the browser, and during server rendering, where the status is also
+/// used for the response (see [ServerStatus]).
+///
+/// A `5xx` status without a view of its own uses the `500` view, if there is one.
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # fn app(cx: Scope) -> impl IntoView {
+/// let errors = ErrorRoutes::new()
+///     .not_found(|cx| view! { cx, <h1>"Page not found"</h1> })
+///     .unauthorized(|cx| view! { cx, <h1>"Please log in"</h1> })
+///     .server_error(|cx| view! { cx, <h1>"Something went wrong"</h1> });
+///
+/// view! { cx,
+///   <Router>
+///     <Routes errors=errors>
+///       <Route path="" view=|cx| view! { cx, <p>"Home"</p> }/>
+///     </Routes>
+///   </Router>
+/// }
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct ErrorRoutes {
+    #[allow(clippy::type_complexity)]
+    views: Vec<(u16, Rc<dyn Fn(Scope) -> View>)>,
+}
+
+impl std::fmt::Debug for ErrorRoutes {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(self.views.iter().map(|(status, _)| status))
+            .finish()
+    }
+}
+
+impl ErrorRoutes {
+    /// Creates an empty set of error views.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the view for the given status code, replacing any previous one.
+    pub fn status<F, IV>(mut self, status: u16, view: F) -> Self
+    where
+        F: Fn(Scope) -> IV + 'static,
+        IV: IntoView,
+    {
+        self.views.retain(|(existing, _)| *existing != status);
+        self.views
+            .push((status, Rc::new(move |cx| view(cx).into_view(cx))));
+        self
+    }
+
+    /// Sets the view for `404 Not Found`, which is shown when no route matches.
+    pub fn not_found<F, IV>(self, view: F) -> Self
+    where
+        F: Fn(Scope) -> IV + 'static,
+        IV: IntoView,
+    {
+        self.status(404, view)
+    }
+
+    /// Sets the view for `401 Unauthorized`.
+    pub fn unauthorized<F, IV>(self, view: F) -> Self
+    where
+        F: Fn(Scope) -> IV + 'static,
+        IV: IntoView,
+    {
+        self.status(401, view)
+    }
+
+    /// Sets the view for `500 Internal Server Error`, which is also used for other `5xx` codes.
+    pub fn server_error<F, IV>(self, view: F) -> Self
+    where
+        F: Fn(Scope) -> IV + 'static,
+        IV: IntoView,
+    {
+        self.status(500, view)
+    }
+
+    /// Whether there is a view for the status code.
+    /// ```
+    /// # use leptos::*;
+    /// # use leptos_router::*;
+    /// let errors = ErrorRoutes::new().server_error(|cx| view! { cx, <p>"Oops"</p> });
+    /// assert!(errors.handles(503));
+    /// assert!(!errors.handles(404));
+    /// ```
+    pub fn handles(&self, status: u16) -> bool {
+        self.view(status).is_some()
+    }
+
+    pub(crate) fn view(&self, status: u16) -> Option<Rc<dyn Fn(Scope) -> View>> {
+        let find = |status| {
+            self.views
+                .iter()
+                .find(|(existing, _)| *existing == status)
+                .map(|(_, view)| Rc::clone(view))
+        };
+        find(status).or_else(|| {
+            if (500..600).contains(&status) {
+                find(500)
+            } else {
+                None
+            }
+        })
+    }
+}
+
+/// The error that a route has asked [Routes](crate::Routes) to show, for the path it was raised on.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct RouteErrorContext(pub RwSignal<Option<(String, u16)>>);
+
+/// Replaces the current route with the view that the enclosing [Routes](crate::Routes) has
+/// for `status` in its [ErrorRoutes], e.g. `401` when the user isn't logged in, or `500` when
+/// the route's data can't be loaded.
+///
+/// The error applies to the current URL, so navigating to another page shows its route again.
+/// During server rendering, the status is also used for the response. If the `<Routes/>` has
+/// no [ErrorRoutes], this does nothing.
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[component]
+/// fn Dashboard(cx: Scope) -> impl IntoView {
+///   let logged_in = false;
+///   if !logged_in {
+///     show_route_error(cx, 401);
+///   }
+///   view! { cx, <h1>"Dashboard"</h1> }
+/// }
+/// ```
+pub fn show_route_error(cx: Scope, status: u16) {
+    if let Some(RouteErrorContext(error)) = use_context::<RouteErrorContext>(cx) {
+        let path = use_location(cx).pathname.get_untracked();
+        error.set(Some((path, status)));
+    }
+}
+
+/// The status code of the error page that the router rendered, which the server should respond
+/// with. The router sets this when a [Routes](crate::Routes) with [ErrorRoutes] shows an error
+/// view, including its `404 Not Found` view when no route matches.
+///
+/// Server integrations provide this as a context, and check it once the app has rendered.
+/// ```
+/// # use leptos_router::*;
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let status = ServerStatus::default();
+/// provide_context(cx, status.clone());
+///
+/// // ... render the app
+///
+/// if let Some(code) = status.take() {
+///     // respond with this status code, unless the app has set one itself
+/// }
+/// # });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ServerStatus {
+    status: Rc<Cell<Option<u16>>>,
+}
+
+impl ServerStatus {
+    /// Asks the server to respond with the given status code.
+    pub fn set(&self, status: u16) {
+        self.status.set(Some(status));
+    }
+
+    /// Takes the status code the server should respond with, if an error page was rendered.
+    pub fn take(&self) -> Option<u16> {
+        self.status.take()
+    }
+}
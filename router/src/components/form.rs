@@ -96,6 +96,10 @@ where
                                 _ = navigate(&redirect_url, Default::default());
                             }
                         }
+                        // a server function asked to go somewhere else once it finished
+                        else if let Some(redirect_url) = resp.headers().get(REDIRECT_HEADER) {
+                            _ = navigate(&redirect_url, Default::default());
+                        }
                     }
                 }
             });
@@ -126,6 +130,36 @@ where
 /// Automatically turns a server [Action](leptos_server::Action) into an HTML
 /// [`form`](https://developer.mozilla.org/en-US/docs/Web/HTML/Element/form)
 /// progressively enhanced to use client-side routing.
+///
+/// If the form is submitted without JavaScript, the server redirects back to this page and
+/// the form shows the result in the same way: the action's [value](leptos_server::Action::value)
+/// is set to what the server function returned, and the [FormSubmission] is provided as a
+/// context, so its fields can be filled in with the values that were submitted.
+///
+/// If the server function calls [redirect](leptos_server::redirect), the form navigates to the
+/// new path once the server function has finished, using the router if JavaScript is available.
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # fn new_todo<AddTodo: Clone + ServerFn<Output = ()>>(cx: Scope) -> impl IntoView {
+/// let add_todo = create_server_action::<AddTodo>(cx);
+/// let error = move || match add_todo.value().get() {
+///     Some(Err(e)) => e.to_string(),
+///     _ => String::new(),
+/// };
+///
+/// view! { cx,
+///     <ActionForm action=add_todo>
+///         <input
+///             name="title"
+///             value=use_context::<FormSubmission>(cx).and_then(|s| s.field("title"))
+///         />
+///         <p class="error">{error}</p>
+///         <input type="submit" value="Add"/>
+///     </ActionForm>
+/// }
+/// # }
+/// ```
 #[component]
 pub fn ActionForm<I, O>(
     cx: Scope,
@@ -150,6 +184,14 @@ where
     let value = action.value();
     let input = action.input();
 
+    // after a submission without JavaScript, show its result as if it had been submitted here
+    if let Some(submission) = use_form_submission(cx, &action_url) {
+        value.set(Some(submission.output.clone().and_then(|json| {
+            O::from_json(&json).map_err(|e| ServerFnError::Deserialization(e.to_string()))
+        })));
+        provide_context(cx, submission);
+    }
+
     let on_form_data = Rc::new(move |form_data: &web_sys::FormData| {
         let data = action_input_from_form_data(form_data);
         match data {
@@ -343,8 +385,10 @@ fn extract_form_attributes(
 
 fn action_input_from_form_data<I: serde::de::DeserializeOwned>(
     form_data: &web_sys::FormData,
-) -> Result<I, serde_urlencoded::de::Error> {
+) -> Result<I, QueryError> {
     let data = web_sys::UrlSearchParams::new_with_str_sequence_sequence(&form_data).unwrap_throw();
     let data = data.to_string().as_string().unwrap_or_default();
-    serde_urlencoded::from_str::<I>(&data)
+    // decode in the same way as server function arguments, so that fields like
+    // `<input name="tags[]">` and `<input name="address[city]">` work
+    NestedQuery::decode::<I>(&data)
 }
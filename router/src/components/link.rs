@@ -41,7 +41,8 @@ where
 pub fn A<H>(
     cx: Scope,
     /// Used to calculate the link's `href` attribute. Will be resolved relative
-    /// to the current route.
+    /// to the current route, including `.` and `..` segments like `../sibling`;
+    /// see [use_resolved_path](crate::use_resolved_path).
     href: H,
     /// If `true`, the link is marked active when the location matches exactly;
     /// if false, link is marked active if the current route starts with it.
@@ -1,3 +1,4 @@
+mod error_routes;
 mod form;
 mod link;
 mod outlet;
@@ -5,6 +6,7 @@ mod route;
 mod router;
 mod routes;
 
+pub use error_routes::*;
 pub use form::*;
 pub use link::*;
 pub use outlet::*;
@@ -1,6 +1,6 @@
 use std::{cell::RefCell, rc::Rc};
 
-use crate::use_route;
+use crate::{use_route, RouteContext};
 use leptos::*;
 
 /// Displays the child route nested in a parent route, allowing you to control exactly where
@@ -24,6 +24,7 @@ pub fn Outlet(cx: Scope) -> impl IntoView {
             (Some(child), _) => {
                 *is_showing.borrow_mut() = Some(child.original_path().to_string());
                 provide_context(child.cx(), child.clone());
+                provide_context(child.cx(), ParentRoute(route.clone()));
                 set_outlet.set(Some(child.outlet().into_view(cx)))
             }
         }
@@ -31,3 +32,57 @@ pub fn Outlet(cx: Scope) -> impl IntoView {
 
     move || outlet.get()
 }
+
+/// The route whose [Outlet] rendered a nested route, provided to the nested route's [Scope].
+#[derive(Clone)]
+struct ParentRoute(RouteContext);
+
+/// Provides a value to the routes nested in the current route, which they can read with
+/// [use_outlet_context]. This is useful for data that belongs to part of the route
+/// hierarchy, like the item loaded by a parent route, without providing it to the whole app.
+///
+/// The value belongs to the current matched route, so it is dropped when navigating to a
+/// different route at this level, but kept while only the nested routes change.
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// #[derive(Clone)]
+/// struct Contact {
+///     name: String,
+/// }
+///
+/// #[component]
+/// fn ContactLayout(cx: Scope) -> impl IntoView {
+///     provide_outlet_context(cx, Contact { name: "Alice".into() });
+///     view! { cx, <Outlet/> }
+/// }
+///
+/// #[component]
+/// fn ContactName(cx: Scope) -> impl IntoView {
+///     let contact = use_outlet_context::<Contact>(cx).expect("inside a ContactLayout");
+///     view! { cx, <h1>{contact.name}</h1> }
+/// }
+/// ```
+pub fn provide_outlet_context<T>(cx: Scope, value: T)
+where
+    T: Clone + 'static,
+{
+    use_route(cx).provide_outlet_context(value);
+}
+
+/// Returns the value of type `T` provided with [provide_outlet_context] by the nearest route
+/// that this route is nested in, if any.
+pub fn use_outlet_context<T>(cx: Scope) -> Option<T>
+where
+    T: Clone + 'static,
+{
+    let mut parent = use_context::<ParentRoute>(cx);
+    while let Some(ParentRoute(route)) = parent {
+        if let Some(value) = route.outlet_context::<T>() {
+            return Some(value);
+        }
+        parent = use_context::<ParentRoute>(route.cx());
+    }
+    None
+}
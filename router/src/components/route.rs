@@ -1,14 +1,46 @@
-use std::{borrow::Cow, rc::Rc};
+use std::{
+    any::{Any, TypeId},
+    borrow::Cow,
+    cell::RefCell,
+    collections::HashMap,
+    rc::Rc,
+};
 
 use leptos::*;
 
 use crate::{
     matching::{resolve_path, PathMatch, RouteDefinition, RouteMatch},
-    ParamsMap, RouterContext,
+    ParamConstraint, ParamsMap, RouterContext, TrailingSlash,
 };
 
 /// Describes a portion of the nested layout of the app, specifying the route it should match,
 /// the element it should display, and data that should be loaded alongside the route.
+///
+/// When more than one route matches a URL, the most specific one is used: static segments
+/// are preferred over params, params with constraints over params without them, and
+/// params over wildcards. Routes that would match exactly the same URLs are an error
+/// when the [Routes](crate::Routes) are created, unless their params are constrained or
+/// they are given different priorities.
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # fn component(cx: Scope) -> impl IntoView {
+/// view! { cx,
+///   <Routes>
+///     // `/users/42`
+///     <Route
+///       path="/users/:id"
+///       constraints=vec![("id", ParamConstraint::parses::<u32>())]
+///       view=|cx| view! { cx, <p>"User by ID"</p> }
+///     />
+///     // `/users/alice`
+///     <Route path="/users/:name" view=|cx| view! { cx, <p>"User by name"</p> }/>
+///     // `/users/new` and `/posts/new`, instead of `/users/:name` for `/users/new`
+///     <Route path="/:section/new" priority=1 view=|cx| view! { cx, <p>"Create"</p> }/>
+///   </Routes>
+/// }
+/// # }
+/// ```
 #[component(transparent)]
 pub fn Route<E, F>(
     cx: Scope,
@@ -20,6 +52,19 @@ pub fn Route<E, F>(
     /// that takes a [Scope] and returns an [Element] (like `|cx| view! { cx, <p>"Show this"</p> })`
     /// or `|cx| view! { cx, <MyComponent/>` } or even, for a component with no props, `MyComponent`).
     view: F,
+    /// Conditions that the values of this route's params must meet for it to match,
+    /// as pairs of param names and [ParamConstraint]s.
+    #[prop(optional)]
+    constraints: Vec<(&'static str, ParamConstraint)>,
+    /// Routes with a higher priority are matched before those with a lower priority,
+    /// however specific their paths are. A route's priority is added to that of the
+    /// routes it is nested in. Defaults to `0`.
+    #[prop(optional)]
+    priority: i32,
+    /// How a trailing slash at the end of the URL's path is treated for this route,
+    /// instead of the `trailing_slash` of the [Router](crate::Router).
+    #[prop(optional)]
+    trailing_slash: Option<TrailingSlash>,
     /// `children` may be empty or include nested routes.
     #[prop(optional)]
     children: Option<Box<dyn Fn(Scope) -> Fragment>>,
@@ -45,6 +90,9 @@ where
     RouteDefinition {
         path,
         children,
+        constraints,
+        priority,
+        trailing_slash,
         view: Rc::new(move |cx| view(cx).into_view(cx)),
     }
 }
@@ -88,6 +136,7 @@ impl RouteContext {
                 original_path: route.original_path.to_string(),
                 params,
                 outlet: Box::new(move || Some(element(cx))),
+                outlet_contexts: Default::default(),
             }),
         })
     }
@@ -130,6 +179,7 @@ impl RouteContext {
                 original_path: path.to_string(),
                 params: create_memo(cx, |_| ParamsMap::new()),
                 outlet: Box::new(move || fallback.map(|f| f().into_view(cx))),
+                outlet_contexts: Default::default(),
             }),
         }
     }
@@ -148,6 +198,28 @@ impl RouteContext {
     pub fn outlet(&self) -> impl IntoView {
         (self.inner.outlet)()
     }
+
+    pub(crate) fn provide_outlet_context<T>(&self, value: T)
+    where
+        T: Clone + 'static,
+    {
+        self.inner
+            .outlet_contexts
+            .borrow_mut()
+            .insert(TypeId::of::<T>(), Box::new(value));
+    }
+
+    pub(crate) fn outlet_context<T>(&self) -> Option<T>
+    where
+        T: Clone + 'static,
+    {
+        self.inner
+            .outlet_contexts
+            .borrow()
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref::<T>())
+            .cloned()
+    }
 }
 
 pub(crate) struct RouteContextInner {
@@ -158,6 +230,8 @@ pub(crate) struct RouteContextInner {
     pub(crate) original_path: String,
     pub(crate) params: Memo<ParamsMap>,
     pub(crate) outlet: Box<dyn Fn() -> Option<View>>,
+    /// Values provided with [provide_outlet_context](crate::provide_outlet_context).
+    pub(crate) outlet_contexts: RefCell<HashMap<TypeId, Box<dyn Any>>>,
 }
 
 impl PartialEq for RouteContextInner {
@@ -29,13 +29,20 @@ pub fn Router(
     /// A fallback that should be shown if no route is matched.
     #[prop(optional)]
     fallback: Option<fn() -> View>,
+    /// How a trailing slash at the end of a URL's path is treated, for every route that
+    /// doesn't set its own. Defaults to [TrailingSlash::Ignore].
+    #[prop(optional)]
+    trailing_slash: TrailingSlash,
     /// The `<Router/>` should usually wrap your whole page. It can contain
     /// any elements, and should include a [Routes](crate::Routes) component somewhere
     /// to define and display [Route](crate::Route)s.
     children: Box<dyn Fn(Scope) -> Fragment>,
 ) -> impl IntoView {
+    #[cfg(feature = "devtools")]
+    provide_context(cx, crate::RouterDevtools::default());
+
     // create a new RouterContext and provide it to every component beneath the router
-    let router = RouterContext::new(cx, base, fallback);
+    let router = RouterContext::new(cx, base, fallback, trailing_slash);
     provide_context(cx, router);
 
     children(cx)
@@ -57,6 +64,7 @@ pub(crate) struct RouterContextInner {
     referrers: Rc<RefCell<Vec<LocationChange>>>,
     state: ReadSignal<State>,
     set_state: WriteSignal<State>,
+    pub(crate) trailing_slash: TrailingSlash,
 }
 
 impl std::fmt::Debug for RouterContextInner {
@@ -70,6 +78,7 @@ impl std::fmt::Debug for RouterContextInner {
             .field("referrers", &self.referrers)
             .field("state", &self.state)
             .field("set_state", &self.set_state)
+            .field("trailing_slash", &self.trailing_slash)
             .finish()
     }
 }
@@ -79,6 +88,7 @@ impl RouterContext {
         cx: Scope,
         base: Option<&'static str>,
         fallback: Option<fn() -> View>,
+        trailing_slash: TrailingSlash,
     ) -> Self {
         cfg_if! {
             if #[cfg(any(feature = "csr", feature = "hydrate"))] {
@@ -132,12 +142,16 @@ impl RouterContext {
         // 2) update the reference (URL)
         // 3) update the state
         // this will trigger the new route match below
+        let state_signal = state;
         create_render_effect(cx, move |_| {
             let LocationChange { value, state, .. } = source.get();
             cx.untrack(move || {
                 if value != reference.get() {
                     set_reference.update(move |r| *r = value);
                     set_state.update(move |s| *s = state);
+                } else if state != state_signal.get() {
+                    // e.g., going back to an entry with the same URL but different state
+                    set_state.update(move |s| *s = state);
                 }
             });
         });
@@ -153,6 +167,7 @@ impl RouterContext {
             referrers,
             state,
             set_state,
+            trailing_slash,
         });
 
         // handle all click events on anchor tags
@@ -163,6 +178,17 @@ impl RouterContext {
         });
         // TODO on_cleanup remove event listener
 
+        // navigate with the router when a server function asks for a redirect
+        #[cfg(not(feature = "ssr"))]
+        set_redirect_hook({
+            let inner = Rc::clone(&inner);
+            move |path| {
+                if let Err(e) = Rc::clone(&inner).navigate_from_route(path, &Default::default()) {
+                    log::error!("could not follow server function redirect to {path}: {e}");
+                }
+            }
+        });
+
         Self { inner }
     }
 
@@ -202,6 +228,11 @@ impl RouterContextInner {
                     }
 
                     if resolved_to != this.reference.get() || options.state != (this.state).get() {
+                        #[cfg(feature = "devtools")]
+                        if let Some(devtools) = use_context::<crate::RouterDevtools>(cx) {
+                            devtools.set_trigger(crate::NavigationTrigger::Navigate);
+                        }
+
                         if cfg!(feature = "server") {
                             // TODO server out
                             self.history.navigate(&LocationChange {
@@ -334,6 +365,11 @@ impl RouterContextInner {
 
             ev.prevent_default();
 
+            #[cfg(feature = "devtools")]
+            if let Some(devtools) = use_context::<crate::RouterDevtools>(self.cx) {
+                devtools.set_trigger(crate::NavigationTrigger::Link);
+            }
+
             if let Err(e) = self.navigate_from_route(
                 &to,
                 &NavigateOptions {
@@ -361,6 +397,24 @@ pub enum NavigationError {
     MaxRedirects,
 }
 
+/// How the router treats a trailing slash at the end of a URL's path, like `/users/`.
+///
+/// Whether a route's path has a trailing slash is decided by its `path`: `"/users/"` does,
+/// and `"/users"` does not. The root path `/` is never considered to have one.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum TrailingSlash {
+    /// A route matches a URL whether or not either of them has a trailing slash. (Default.)
+    #[default]
+    Ignore,
+    /// A route only matches a URL if both or neither of them have a trailing slash.
+    Strict,
+    /// A route matches a URL whether or not either of them has a trailing slash, but the
+    /// URL is then changed to match the route's path. On the server, the response is a
+    /// `301 Moved Permanently` redirect (see [ServerRedirect](crate::ServerRedirect)); in
+    /// the browser, the current history entry is replaced.
+    RedirectToCanonical,
+}
+
 /// Options that can be used to configure a navigation. Used with [use_navigate](crate::use_navigate).
 #[derive(Clone, Debug)]
 pub struct NavigateOptions {
@@ -6,13 +6,14 @@ use std::{
 };
 
 use leptos::*;
+use thiserror::Error;
 
 use crate::{
     matching::{
-        expand_optionals, get_route_matches, join_paths, Branch, Matcher, RouteDefinition,
-        RouteMatch,
+        expand_optionals, get_route_matches, has_trailing_slash, join_paths, Branch, Matcher,
+        RouteDefinition, RouteMatch,
     },
-    RouteContext, RouterContext,
+    ErrorRoutes, RouteContext, RouteErrorContext, RouterContext, TrailingSlash,
 };
 
 /// Contains route definitions and manages the actual routing process.
@@ -22,13 +23,30 @@ use crate::{
 pub fn Routes(
     cx: Scope,
     #[prop(optional)] base: Option<String>,
+    /// The views to show instead of a route for error status codes, like `404 Not Found` when
+    /// no route matches. See [ErrorRoutes].
+    #[prop(optional)]
+    errors: Option<ErrorRoutes>,
     children: Box<dyn Fn(Scope) -> Fragment>,
 ) -> impl IntoView {
     let router = use_context::<RouterContext>(cx).unwrap_or_else(|| {
         log::warn!("<Routes/> component should be nested within a <Router/>.");
+        report_diagnostic(
+            cx,
+            DiagnosticKind::MissingContext,
+            "<Routes/> was rendered outside a <Router/> component",
+        );
         panic!()
     });
 
+    // routes below can ask for an error view with `show_route_error`
+    let route_error = errors
+        .is_some()
+        .then(|| RouteErrorContext(create_rw_signal(cx, None)));
+    if let Some(route_error) = route_error {
+        provide_context(cx, route_error);
+    }
+
     let mut branches = Vec::new();
     let id_before = HydrationCtx::peek();
     let frag = children(cx);
@@ -45,16 +63,81 @@ pub fn Routes(
     create_branches(
         &children,
         &base.unwrap_or_default(),
+        router.inner.trailing_slash,
         &mut Vec::new(),
         &mut branches,
     );
+    if let Err(e) = check_branches(&branches) {
+        report_diagnostic(cx, DiagnosticKind::Other, e.to_string());
+        if cfg!(debug_assertions) {
+            panic!("{e}");
+        } else {
+            log::error!("{e}");
+        }
+    }
 
     // whenever path changes, update matches
     let matches = create_memo(cx, {
         let router = router.clone();
-        move |_| get_route_matches(branches.clone(), router.pathname().get())
+        move |_| {
+            #[cfg(feature = "devtools")]
+            if let Some(devtools) = use_context::<crate::RouterDevtools>(cx) {
+                let path = router.pathname().get();
+                let (matches, misses) = crate::matching::explain_route_matches(&branches, &path);
+                devtools.record(path, &matches, misses);
+                return matches;
+            }
+
+            get_route_matches(branches.clone(), router.pathname().get())
+        }
     });
 
+    // if the matched route asks for it, change the URL to match its trailing slash
+    let canonical = {
+        let router = router.clone();
+        move || {
+            let location = &router.inner.location;
+            let path = location
+                .pathname
+                .with(|path| matches.with(|matches| matches.last()?.route.canonical_path(path)))?;
+            // the query string starts with `?` in the browser, but not on the server
+            let search = location.search.get();
+            let search = match search.as_str() {
+                "" => search,
+                s if s.starts_with('?') => search,
+                s => format!("?{s}"),
+            };
+            Some(format!("{path}{search}{}", location.hash.get()))
+        }
+    };
+    cfg_if::cfg_if! {
+        if #[cfg(feature = "ssr")] {
+            if let Some(canonical) = canonical() {
+                if let Some(redirect) = use_context::<crate::ServerRedirect>(cx) {
+                    redirect.redirect(canonical);
+                }
+            }
+        } else {
+            create_effect(cx, {
+                let router = router.clone();
+                move |_| {
+                    if let Some(canonical) = canonical() {
+                        let options = crate::NavigateOptions {
+                            resolve: false,
+                            replace: true,
+                            scroll: false,
+                            state: router.inner.location.state.get(),
+                        };
+                        let router = router.inner.clone();
+                        if let Err(e) = router.navigate_from_route(&canonical, &options) {
+                            log::error!("{e:#?}");
+                        }
+                    }
+                }
+            });
+        }
+    }
+
     // Rebuild the list of nested routes conservatively, and show the root route here
     let disposers = RefCell::new(Vec::<ScopeDisposer>::new());
 
@@ -181,6 +264,17 @@ pub fn Routes(
         }
     });
 
+    // for partial requests, render only the innermost matched route
+    if let Some(partial) = use_context::<PartialRender>(cx) {
+        let leaf = route_states.with(|state| state.routes.borrow().last().cloned());
+        *partial.view.borrow_mut() = leaf.map(|route| {
+            provide_context(route.cx(), route.clone());
+            route.outlet().into_view(route.cx())
+        });
+        HydrationCtx::continue_from(id_before);
+        return ().into_view(cx);
+    }
+
     // show the root route
     let root = create_memo(cx, move |prev| {
         provide_context(cx, route_states);
@@ -199,8 +293,72 @@ pub fn Routes(
         })
     });
 
+    // show the error view for the current status instead, if there is one
+    let error_view = errors
+        .zip(route_error)
+        .map(|(errors, RouteErrorContext(error))| {
+            let status = create_memo(cx, {
+                let router = router.clone();
+                move |_| {
+                    let path = router.pathname().get();
+                    error
+                        .with(|error| match error {
+                            Some((raised_on, status)) if *raised_on == path => Some(*status),
+                            _ => None,
+                        })
+                        .or_else(|| matches.with(|matches| matches.is_empty()).then_some(404))
+                }
+            });
+
+            // the server responds with the status of the error view
+            #[cfg(feature = "ssr")]
+            {
+                if let Some(server_status) = use_context::<crate::ServerStatus>(cx) {
+                    create_isomorphic_effect(cx, move |_| {
+                        if let Some(status) = status.get() {
+                            server_status.set(status);
+                        }
+                    });
+                }
+            }
+
+            let disposer = RefCell::new(None::<ScopeDisposer>);
+            create_memo(cx, move |_| {
+                if let Some(disposer) = disposer.take() {
+                    disposer.dispose();
+                }
+                let view = errors.view(status.get()?)?;
+                let (view, child) = cx.run_child_scope(|cx| view(cx));
+                *disposer.borrow_mut() = Some(child);
+                Some(view)
+            })
+        });
+
     HydrationCtx::continue_from(id_before);
-    (move || root.get()).into_view(cx)
+    (move || {
+        error_view
+            .and_then(|error_view| error_view.get())
+            .or_else(|| root.get())
+    })
+    .into_view(cx)
+}
+
+/// Renders only the view of the most deeply nested matched route, instead of the whole
+/// route hierarchy and the rest of the app.
+///
+/// When this is provided as context, [Routes] stores the view of the innermost matched route
+/// here and renders nothing itself. Server integrations use this to answer partial-page
+/// requests, like those made by htmx, with just the content of the route's outlet.
+#[derive(Clone, Debug, Default)]
+pub struct PartialRender {
+    view: Rc<RefCell<Option<View>>>,
+}
+
+impl PartialRender {
+    /// Takes the view of the matched route, if `<Routes/>` has rendered and a route matched.
+    pub fn take(&self) -> Option<View> {
+        self.view.borrow_mut().take()
+    }
 }
 
 #[derive(Clone, Debug, PartialEq)]
@@ -216,9 +374,26 @@ pub struct RouteData {
     pub pattern: String,
     pub original_path: String,
     pub matcher: Matcher,
+    pub priority: i32,
+    pub trailing_slash: TrailingSlash,
 }
 
 impl RouteData {
+    /// The path that the URL should be changed to, if it is matched by this route
+    /// but has a different trailing slash and the route asks for redirects.
+    fn canonical_path(&self, path: &str) -> Option<String> {
+        let wants_slash = self.key.has_trailing_slash();
+        if self.trailing_slash != TrailingSlash::RedirectToCanonical
+            || has_trailing_slash(path) == wants_slash
+        {
+            None
+        } else if wants_slash {
+            Some(format!("{path}/"))
+        } else {
+            Some(path.trim_end_matches('/').to_string())
+        }
+    }
+
     fn score(&self) -> i32 {
         let (pattern, splat) = match self.pattern.split_once("/*") {
             Some((p, s)) => (p, Some(s)),
@@ -228,9 +403,17 @@ impl RouteData {
             .split('/')
             .filter(|n| !n.is_empty())
             .collect::<Vec<_>>();
+        let constrained = |param: &str| self.key.constraints.iter().any(|(name, _)| *name == param);
         segments.iter().fold(
-            (segments.len() as i32) - if splat.is_none() { 0 } else { 1 },
-            |score, segment| score + if segment.starts_with(':') { 2 } else { 3 },
+            (segments.len() as i32 - if splat.is_none() { 0 } else { 1 }) * 2,
+            |score, segment| {
+                score
+                    + match segment.strip_prefix(':') {
+                        Some(param) if constrained(param) => 5,
+                        Some(_) => 4,
+                        None => 6,
+                    }
+            },
         )
     }
 }
@@ -238,11 +421,13 @@ impl RouteData {
 fn create_branches(
     route_defs: &[RouteDefinition],
     base: &str,
+    trailing_slash: TrailingSlash,
     stack: &mut Vec<RouteData>,
     branches: &mut Vec<Branch>,
 ) {
     for def in route_defs {
-        let routes = create_routes(def, base);
+        let parent_priority = stack.last().map(|route| route.priority).unwrap_or_default();
+        let routes = create_routes(def, base, parent_priority, trailing_slash);
         for route in routes {
             stack.push(route.clone());
 
@@ -250,7 +435,14 @@ fn create_branches(
                 let branch = create_branch(stack, branches.len());
                 branches.push(branch);
             } else {
-                create_branches(&def.children, &route.pattern, stack, branches);
+                // the children inherit this route's policy, unless they set their own
+                create_branches(
+                    &def.children,
+                    &route.pattern,
+                    route.trailing_slash,
+                    stack,
+                    branches,
+                );
             }
 
             stack.pop();
@@ -258,20 +450,69 @@ fn create_branches(
     }
 
     if stack.is_empty() {
-        branches.sort_by_key(|branch| Reverse(branch.score));
+        branches.sort_by_key(|branch| Reverse((branch.priority, branch.score)));
     }
 }
 
 pub(crate) fn create_branch(routes: &[RouteData], index: usize) -> Branch {
+    let leaf = routes.last().unwrap();
     Branch {
         routes: routes.to_vec(),
-        score: routes.last().unwrap().score() * 10000 - (index as i32),
+        score: leaf.score() * 10000 - (index as i32),
+        priority: leaf.priority,
     }
 }
 
-fn create_routes(route_def: &RouteDefinition, base: &str) -> Vec<RouteData> {
+/// An error in the routes defined in a [Routes].
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RouteDefinitionError {
+    /// Two routes match exactly the same paths, so which one is used would only depend
+    /// on the order in which they're defined.
+    #[error(
+        "the routes {0:?} and {1:?} match the same paths; constrain their params or give \
+         one of them a higher priority"
+    )]
+    Ambiguous(String, String),
+}
+
+/// Checks that no two branches with the same priority match exactly the same paths,
+/// unless their params are constrained, which can tell them apart.
+fn check_branches(branches: &[Branch]) -> Result<(), RouteDefinitionError> {
+    let unconstrained = branches
+        .iter()
+        .filter(|branch| {
+            branch
+                .routes
+                .iter()
+                .all(|route| !route.matcher.is_constrained())
+        })
+        .collect::<Vec<_>>();
+    for (i, a) in unconstrained.iter().enumerate() {
+        for b in &unconstrained[i + 1..] {
+            let (leaf_a, leaf_b) = (a.routes.last().unwrap(), b.routes.last().unwrap());
+            if a.priority == b.priority && leaf_a.matcher.overlaps(&leaf_b.matcher) {
+                return Err(RouteDefinitionError::Ambiguous(
+                    leaf_a.pattern.clone(),
+                    leaf_b.pattern.clone(),
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
+fn create_routes(
+    route_def: &RouteDefinition,
+    base: &str,
+    parent_priority: i32,
+    trailing_slash: TrailingSlash,
+) -> Vec<RouteData> {
     let RouteDefinition { children, .. } = route_def;
     let is_leaf = children.is_empty();
+    let trailing_slash = route_def.trailing_slash.unwrap_or(trailing_slash);
+    // only the whole path can end with a slash, so only leaf routes check it
+    let strict_slash = (is_leaf && trailing_slash == TrailingSlash::Strict)
+        .then(|| route_def.has_trailing_slash());
     let mut acc = Vec::new();
     for original_path in expand_optionals(route_def.path) {
         let path = join_paths(base, &original_path);
@@ -283,11 +524,20 @@ fn create_routes(route_def: &RouteDefinition, base: &str) -> Vec<RouteData> {
                 .map(|n| n.to_string())
                 .unwrap_or(path)
         };
+        let constraints = route_def
+            .constraints
+            .iter()
+            .map(|(name, constraint)| (name.to_string(), constraint.clone()))
+            .collect();
         acc.push(RouteData {
             key: route_def.clone(),
-            matcher: Matcher::new_with_partial(&pattern, !is_leaf),
+            matcher: Matcher::new_with_partial(&pattern, !is_leaf)
+                .with_constraints(constraints)
+                .with_trailing_slash(strict_slash),
             pattern,
             original_path: original_path.to_string(),
+            priority: parent_priority + route_def.priority,
+            trailing_slash,
         });
     }
     acc
@@ -0,0 +1,191 @@
+#![cfg(feature = "devtools")]
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use leptos::{use_context, Scope};
+use serde::Serialize;
+
+use crate::matching::{MatchFailure, RouteMatch};
+
+/// The most navigations that are kept in the timeline.
+const MAX_RECORDS: usize = 100;
+
+/// What started a navigation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum NavigationTrigger {
+    /// The page loaded.
+    Initial,
+    /// A link was clicked.
+    Link,
+    /// The app navigated, e.g., with [use_navigate](crate::use_navigate), a `<Form/>`,
+    /// or a redirect.
+    Navigate,
+    /// The user went back or forward in the browser's history.
+    History,
+}
+
+/// A route in the chain of nested routes that matched a navigation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MatchedRoute {
+    /// The route's full path pattern, like `/users/:id`.
+    pub pattern: String,
+    /// The part of the URL that the route matched.
+    pub path: String,
+    /// The params that the route matched.
+    pub params: Vec<(String, String)>,
+}
+
+/// A route that was tried before the one that matched, and didn't match.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct RouteMiss {
+    /// The route's full path pattern.
+    pub pattern: String,
+    /// Why it didn't match.
+    pub reason: MatchFailure,
+}
+
+/// A navigation recorded by [RouterDevtools].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NavigationRecord {
+    /// Counts up from `0` for each navigation.
+    pub id: usize,
+    /// What started the navigation.
+    pub trigger: NavigationTrigger,
+    /// The path that was navigated to.
+    pub path: String,
+    /// The chain of nested routes that matched, from the outermost to the innermost.
+    /// Empty if no route matched.
+    pub matched: Vec<MatchedRoute>,
+    /// The routes that were tried before the matched route, in order, and why they
+    /// didn't match.
+    pub misses: Vec<RouteMiss>,
+}
+
+/// Records each navigation of a [Router](crate::Router) and how its routes were matched,
+/// so that devtools can show a timeline and explain why a URL matched a route.
+///
+/// This is only available with the `devtools` feature. Get it with [use_router_devtools].
+///
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # run_scope(create_runtime(), |cx| {
+/// # if false {
+/// let devtools = use_router_devtools(cx).expect("inside a <Router/>");
+/// devtools.on_navigation(|record| {
+///     log::debug!("{:?} to {}, matched {:?}", record.trigger, record.path, record.matched);
+///     for miss in &record.misses {
+///         log::debug!("  {} didn't match: {}", miss.pattern, miss.reason);
+///     }
+/// });
+/// # }
+/// # });
+/// ```
+#[derive(Clone, Default)]
+pub struct RouterDevtools {
+    inner: Rc<RouterDevtoolsInner>,
+}
+
+#[derive(Default)]
+struct RouterDevtoolsInner {
+    next_id: Cell<usize>,
+    pending_trigger: Cell<Option<NavigationTrigger>>,
+    timeline: RefCell<Vec<NavigationRecord>>,
+    #[allow(clippy::type_complexity)]
+    listeners: RefCell<Vec<Rc<dyn Fn(&NavigationRecord)>>>,
+}
+
+impl std::fmt::Debug for RouterDevtools {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RouterDevtools")
+            .field("timeline", &self.inner.timeline.borrow())
+            .finish_non_exhaustive()
+    }
+}
+
+impl RouterDevtools {
+    /// The most recent navigations, oldest first.
+    pub fn timeline(&self) -> Vec<NavigationRecord> {
+        self.inner.timeline.borrow().clone()
+    }
+
+    /// Clears the timeline.
+    pub fn clear(&self) {
+        self.inner.timeline.borrow_mut().clear();
+    }
+
+    /// Calls `f` with each navigation once it has been matched.
+    pub fn on_navigation(&self, f: impl Fn(&NavigationRecord) + 'static) {
+        self.inner.listeners.borrow_mut().push(Rc::new(f));
+    }
+
+    /// Marks the next navigation as started by `trigger`, unless something else
+    /// already started it.
+    pub(crate) fn set_trigger(&self, trigger: NavigationTrigger) {
+        if self.inner.pending_trigger.get().is_none() {
+            self.inner.pending_trigger.set(Some(trigger));
+        }
+    }
+
+    pub(crate) fn record(
+        &self,
+        path: String,
+        matches: &[RouteMatch],
+        misses: Vec<(String, MatchFailure)>,
+    ) {
+        let id = self.inner.next_id.get();
+        self.inner.next_id.set(id + 1);
+
+        // a navigation that the router didn't start came from the browser's history
+        let trigger = self.inner.pending_trigger.take().unwrap_or(if id == 0 {
+            NavigationTrigger::Initial
+        } else {
+            NavigationTrigger::History
+        });
+
+        let record = NavigationRecord {
+            id,
+            trigger,
+            path,
+            matched: matches
+                .iter()
+                .map(|m| MatchedRoute {
+                    pattern: m.route.pattern.clone(),
+                    path: m.path_match.path.clone(),
+                    params: m
+                        .path_match
+                        .params
+                        .0
+                        .iter()
+                        .map(|(k, v)| (k.clone(), v.clone()))
+                        .collect(),
+                })
+                .collect(),
+            misses: misses
+                .into_iter()
+                .map(|(pattern, reason)| RouteMiss { pattern, reason })
+                .collect(),
+        };
+
+        {
+            let mut timeline = self.inner.timeline.borrow_mut();
+            if timeline.len() >= MAX_RECORDS {
+                timeline.remove(0);
+            }
+            timeline.push(record.clone());
+        }
+
+        let listeners = self.inner.listeners.borrow().clone();
+        for listener in listeners {
+            listener(&record);
+        }
+    }
+}
+
+/// Returns the [RouterDevtools] of the current [Router](crate::Router), if it's inside one.
+pub fn use_router_devtools(cx: Scope) -> Option<RouterDevtools> {
+    use_context::<RouterDevtools>(cx)
+}
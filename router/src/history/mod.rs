@@ -1,4 +1,4 @@
-use std::rc::Rc;
+use std::{cell::RefCell, rc::Rc};
 
 use leptos::*;
 
@@ -43,7 +43,7 @@ impl BrowserIntegration {
                 + &loc.hash().unwrap_or_default(),
             replace: true,
             scroll: true,
-            state: State(None), // TODO
+            state: State(leptos_dom::window().history().and_then(|h| h.state()).ok()),
         }
     }
 }
@@ -166,3 +166,39 @@ impl History for ServerIntegration {
 
     fn navigate(&self, _loc: &LocationChange) {}
 }
+
+/// A redirect that the router wants the server to respond with, instead of the page it
+/// rendered, like when a URL's trailing slash doesn't match
+/// [TrailingSlash::RedirectToCanonical](crate::TrailingSlash::RedirectToCanonical).
+///
+/// Server integrations provide this as a context, and check it once the app has rendered.
+/// ```
+/// # use leptos_router::*;
+/// # use leptos::*;
+/// # run_scope(create_runtime(), |cx| {
+/// let redirect = ServerRedirect::default();
+/// provide_context(cx, redirect.clone());
+///
+/// // ... render the app
+///
+/// if let Some(location) = redirect.take() {
+///     // respond with `301 Moved Permanently` and a `Location` header
+/// }
+/// # });
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ServerRedirect {
+    location: Rc<RefCell<Option<String>>>,
+}
+
+impl ServerRedirect {
+    /// Asks the server to redirect to the given location.
+    pub fn redirect(&self, location: impl Into<String>) {
+        *self.location.borrow_mut() = Some(location.into());
+    }
+
+    /// Takes the location the server should redirect to, if a redirect was asked for.
+    pub fn take(&self) -> Option<String> {
+        self.location.borrow_mut().take()
+    }
+}
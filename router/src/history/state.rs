@@ -1,4 +1,5 @@
 use leptos::wasm_bindgen::JsValue;
+use serde::{de::DeserializeOwned, Serialize};
 
 #[derive(Debug, Clone, Default, PartialEq)]
 pub struct State(pub Option<JsValue>);
@@ -10,6 +11,31 @@ impl State {
             None => JsValue::UNDEFINED,
         }
     }
+
+    /// Encodes a value as JSON, so it can be pushed onto the history stack with
+    /// [NavigateOptions](crate::NavigateOptions) and read back with
+    /// [use_location_state](crate::use_location_state).
+    ///
+    /// History state only exists in the browser, so on the server this is always empty.
+    pub fn serialize<T: Serialize>(value: &T) -> Result<Self, serde_json::Error> {
+        let json = serde_json::to_string(value)?;
+        #[cfg(target_arch = "wasm32")]
+        let state = State(Some(JsValue::from_str(&json)));
+        // still serialize on the server, so errors are the same as in the browser
+        #[cfg(not(target_arch = "wasm32"))]
+        let state = {
+            _ = json;
+            State(None)
+        };
+        Ok(state)
+    }
+
+    /// Decodes state that was encoded with [State::serialize]. Returns `None` if there
+    /// is no state, or if it can't be decoded as a `T`.
+    pub fn deserialize<T: DeserializeOwned>(&self) -> Option<T> {
+        let json = self.0.as_ref()?.as_string()?;
+        serde_json::from_str(&json).ok()
+    }
 }
 
 impl<T> From<T> for State
@@ -1,10 +1,15 @@
 use std::rc::Rc;
 
-use leptos::{create_memo, use_context, Memo, Scope};
+use leptos::{
+    create_memo, report_diagnostic, use_context, DiagnosticKind, Memo, NestedQuery, QueryCodec,
+    QueryError, Scope,
+};
+
+use serde::de::DeserializeOwned;
 
 use crate::{
     Location, NavigateOptions, NavigationError, Params, ParamsError, ParamsMap, RouteContext,
-    RouterContext,
+    RouterContext, State,
 };
 
 /// Returns the current [RouterContext], containing information about the router's state.
@@ -13,6 +18,11 @@ pub fn use_router(cx: Scope) -> RouterContext {
         router
     } else {
         leptos::leptos_dom::debug_warn!("You must call use_router() within a <Router/> component");
+        report_diagnostic(
+            cx,
+            DiagnosticKind::MissingContext,
+            "use_router() was called outside a <Router/> component",
+        );
         panic!("You must call use_router() within a <Router/> component");
     }
 }
@@ -58,7 +68,82 @@ where
     })
 }
 
+/// Returns the current URL search query, decoded into the given type with [NestedQuery], or an
+/// error. Unlike [use_query], this supports lists (`tags[]=a&tags[]=b`) and nested structs
+/// (`price[min]=10`), so more complex filters can round-trip through the URL.
+///
+/// Query strings for links can be built with [QueryCodec::encode].
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # fn component(cx: Scope) {
+/// // e.g., for `?tags[]=sale&tags[]=new`
+/// let query = use_query_as::<std::collections::HashMap<String, Vec<String>>>(cx);
+/// let tags = move || {
+///     query.with(|query| {
+///         query
+///             .as_ref()
+///             .ok()
+///             .and_then(|query| query.get("tags").cloned())
+///             .unwrap_or_default()
+///     })
+/// };
+/// # }
+/// ```
+pub fn use_query_as<T>(cx: Scope) -> Memo<Result<T, QueryError>>
+where
+    T: DeserializeOwned + PartialEq + std::fmt::Debug + 'static,
+{
+    use_query_with::<NestedQuery, T>(cx)
+}
+
+/// Returns the current URL search query, decoded into the given type with the given
+/// [QueryCodec], or an error.
+pub fn use_query_with<C, T>(cx: Scope) -> Memo<Result<T, QueryError>>
+where
+    C: QueryCodec,
+    T: DeserializeOwned + PartialEq + std::fmt::Debug + 'static,
+{
+    let search = use_router(cx).inner.location.search;
+    create_memo(cx, move |_| search.with(|search| C::decode(search)))
+}
+
+/// Returns the [`state`](https://developer.mozilla.org/en-US/docs/Web/API/History/state) of the
+/// current history entry, decoded as a `T`, or `None` if there is no state or it can't be decoded.
+///
+/// State can be pushed by navigating with a [State](crate::State) created by
+/// [State::serialize](crate::State::serialize). Because it's stored in the history entry, it is
+/// restored when the user navigates back or forward to that entry.
+/// ```
+/// # use leptos::*;
+/// # use leptos_router::*;
+/// # fn component(cx: Scope) {
+/// // e.g., remember which item in a list was selected
+/// let navigate = use_navigate(cx);
+/// let options = NavigateOptions {
+///     state: State::serialize(&3_usize).unwrap(),
+///     ..Default::default()
+/// };
+/// _ = navigate("/details", options);
+///
+/// // in the component for /details
+/// let selected = use_location_state::<usize>(cx);
+/// # }
+/// ```
+pub fn use_location_state<T>(cx: Scope) -> Memo<Option<T>>
+where
+    T: DeserializeOwned + PartialEq + 'static,
+{
+    let state = use_router(cx).inner.location.state;
+    create_memo(cx, move |_| state.with(State::deserialize))
+}
+
 /// Resolves the given path relative to the current route.
+///
+/// A relative path is resolved against the path that the current route matched, so
+/// `"edit"` in the route `/users/:id` resolves to `/users/5/edit`. It can use `.` and `..`
+/// segments like a URL, so `"../new"` resolves to `/users/new`. An index route matches
+/// the same path as its parent, so paths are resolved in it as they are in its parent.
 pub fn use_resolved_path(cx: Scope, path: impl Fn() -> String + 'static) -> Memo<Option<String>> {
     let route = use_route(cx);
 
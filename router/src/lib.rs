@@ -141,11 +141,14 @@
 #![cfg_attr(not(feature = "stable"), feature(type_name_of_val))]
 
 mod components;
+mod devtools;
 mod history;
 mod hooks;
 mod matching;
 
 pub use components::*;
+#[cfg(feature = "devtools")]
+pub use devtools::*;
 pub use history::*;
 pub use hooks::*;
 pub use matching::*;
@@ -0,0 +1,68 @@
+use std::{fmt, rc::Rc, str::FromStr};
+
+/// A condition that a route parameter's value must meet for the route to match, given to
+/// the `constraints` prop of a [Route](crate::Route).
+///
+/// If the value of a constrained parameter doesn't meet the condition, the route doesn't
+/// match and the next-best route is tried instead, so `/users/:id` constrained to numbers
+/// and `/users/:name` can be used side by side.
+/// ```
+/// # use leptos_router::*;
+/// let id = ParamConstraint::parses::<u32>();
+/// assert!(id.test("42"));
+/// assert!(!id.test("new"));
+///
+/// let even = ParamConstraint::new(|value| value.len() % 2 == 0);
+/// assert!(even.test("ab"));
+/// assert!(!even.test("abc"));
+/// ```
+#[derive(Clone)]
+pub struct ParamConstraint(Rc<dyn Fn(&str) -> bool>);
+
+impl ParamConstraint {
+    /// A constraint that is met when the function returns `true` for the value.
+    pub fn new(test: impl Fn(&str) -> bool + 'static) -> Self {
+        Self(Rc::new(test))
+    }
+
+    /// A constraint that is met when the value can be parsed as a `T`.
+    pub fn parses<T: FromStr>() -> Self {
+        Self::new(|value| value.parse::<T>().is_ok())
+    }
+
+    /// A constraint that is met when the whole value matches the regular expression, like
+    /// `"[a-z]+"` or `"\\d{4}"`.
+    ///
+    /// The pattern uses the syntax of the [regex](https://docs.rs/regex) crate, which matches
+    /// it on the server and in the browser alike, so that both pick the same route.
+    ///
+    /// # Panics
+    /// Panics if the pattern isn't a valid regular expression.
+    pub fn regex(pattern: &str) -> Self {
+        let re = regex::Regex::new(&format!("^(?:{pattern})$"))
+            .unwrap_or_else(|e| panic!("invalid route parameter constraint: {e}"));
+        Self::new(move |value| re.is_match(value))
+    }
+
+    /// Whether the value meets the constraint.
+    pub fn test(&self, value: &str) -> bool {
+        (self.0)(value)
+    }
+}
+
+impl fmt::Debug for ParamConstraint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("ParamConstraint").finish()
+    }
+}
+
+impl PartialEq for ParamConstraint {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(
+            Rc::as_ptr(&self.0) as *const u8,
+            Rc::as_ptr(&other.0) as *const u8,
+        )
+    }
+}
+
+impl Eq for ParamConstraint {}
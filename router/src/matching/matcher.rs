@@ -1,7 +1,9 @@
 // Implementation based on Solid Router
 // see https://github.com/solidjs/solid-router/blob/main/src/utils.ts
 
-use crate::ParamsMap;
+use crate::{matching::has_trailing_slash, ParamConstraint, ParamsMap};
+use serde::Serialize;
+use thiserror::Error;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[doc(hidden)]
@@ -17,6 +19,8 @@ pub struct Matcher {
     segments: Vec<String>,
     len: usize,
     partial: bool,
+    constraints: Vec<(String, ParamConstraint)>,
+    trailing_slash: Option<bool>,
 }
 
 impl Matcher {
@@ -44,11 +48,66 @@ impl Matcher {
             segments,
             len,
             partial,
+            constraints: Vec::new(),
+            trailing_slash: None,
         }
     }
 
+    /// Only matches locations that do, or don't, end with a slash.
+    #[doc(hidden)]
+    pub fn with_trailing_slash(mut self, trailing_slash: Option<bool>) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    /// Only matches when the values of the constrained params meet their constraints.
+    #[doc(hidden)]
+    pub fn with_constraints(mut self, constraints: Vec<(String, ParamConstraint)>) -> Self {
+        self.constraints = constraints;
+        self
+    }
+
+    /// Whether this matcher has any param constraints.
+    pub(crate) fn is_constrained(&self) -> bool {
+        !self.constraints.is_empty()
+    }
+
+    /// Whether this matcher and the other one, ignoring constraints, match exactly the same
+    /// paths, because they only differ in the names of their params.
+    pub(crate) fn overlaps(&self, other: &Matcher) -> bool {
+        self.len == other.len
+            && self.partial == other.partial
+            && self.splat.is_some() == other.splat.is_some()
+            && (self.trailing_slash.is_none()
+                || other.trailing_slash.is_none()
+                || self.trailing_slash == other.trailing_slash)
+            && self
+                .segments
+                .iter()
+                .zip(other.segments.iter())
+                .all(|(a, b)| match (a.starts_with(':'), b.starts_with(':')) {
+                    (true, true) => true,
+                    (false, false) => a == b,
+                    _ => false,
+                })
+    }
+
     #[doc(hidden)]
     pub fn test(&self, location: &str) -> Option<PathMatch> {
+        self.try_match(location).ok()
+    }
+
+    /// Matches the location like [Matcher::test], but explains why it doesn't match.
+    #[doc(hidden)]
+    pub fn try_match(&self, location: &str) -> Result<PathMatch, MatchFailure> {
+        if let Some(trailing_slash) = self.trailing_slash {
+            if has_trailing_slash(location) != trailing_slash {
+                return Err(MatchFailure::TrailingSlash {
+                    expected: trailing_slash,
+                });
+            }
+        }
+
         let loc_segments = location
             .split('/')
             .filter(|n| !n.is_empty())
@@ -60,8 +119,16 @@ impl Matcher {
         // quick path: not a match if
         // 1) matcher has add'l segments not found in location
         // 2) location has add'l segments, there's no splat, and partial matches not allowed
-        if loc_len < self.len || (len_diff > 0 && self.splat.is_none() && !self.partial) {
-            None
+        if loc_len < self.len {
+            Err(MatchFailure::MissingSegments {
+                expected: self.len,
+                found: loc_len,
+            })
+        } else if len_diff > 0 && self.splat.is_none() && !self.partial {
+            Err(MatchFailure::ExtraSegments {
+                expected: self.len,
+                found: loc_len,
+            })
         }
         // otherwise, start building a match
         else {
@@ -73,7 +140,10 @@ impl Matcher {
                     params.insert(param_name.into(), (*loc_segment).into());
                 } else if segment != loc_segment {
                     // if any segment doesn't match and isn't a param, there's no path match
-                    return None;
+                    return Err(MatchFailure::Segment {
+                        expected: segment.clone(),
+                        found: (*loc_segment).to_string(),
+                    });
                 }
 
                 path.push('/');
@@ -91,7 +161,70 @@ impl Matcher {
                 }
             }
 
-            Some(PathMatch { path, params })
+            // if a constrained param doesn't meet its constraint, there's no path match
+            for (name, constraint) in &self.constraints {
+                if let Some(value) = params.get(name) {
+                    if !constraint.test(value) {
+                        return Err(MatchFailure::Constraint {
+                            param: name.clone(),
+                            value: value.clone(),
+                        });
+                    }
+                }
+            }
+
+            Ok(PathMatch { path, params })
         }
     }
 }
+
+/// Why a route's path didn't match a URL.
+#[derive(Debug, Clone, PartialEq, Eq, Error, Serialize)]
+pub enum MatchFailure {
+    /// The route only matches URLs that do, or don't, have a trailing slash.
+    #[error("the route only matches URLs {} a trailing slash", with_or_without(.expected))]
+    TrailingSlash {
+        /// Whether the route wants a trailing slash.
+        expected: bool,
+    },
+    /// The URL has fewer segments than the route.
+    #[error("the route has {expected} segments, but the URL only has {found}")]
+    MissingSegments {
+        /// The number of segments in the route, not counting a splat.
+        expected: usize,
+        /// The number of segments in the URL.
+        found: usize,
+    },
+    /// The URL has more segments than the route, and the route has no splat.
+    #[error("the URL has {found} segments, but the route only has {expected} and no splat")]
+    ExtraSegments {
+        /// The number of segments in the route.
+        expected: usize,
+        /// The number of segments in the URL.
+        found: usize,
+    },
+    /// A static segment of the route is different from the URL.
+    #[error("the URL has `{found}` where the route has `{expected}`")]
+    Segment {
+        /// The segment of the route.
+        expected: String,
+        /// The segment of the URL.
+        found: String,
+    },
+    /// The value of a param doesn't meet its constraint.
+    #[error("`{value}` doesn't meet the constraint on `:{param}`")]
+    Constraint {
+        /// The name of the param.
+        param: String,
+        /// The value of the param in the URL.
+        value: String,
+    },
+}
+
+fn with_or_without(trailing_slash: &bool) -> &'static str {
+    if *trailing_slash {
+        "with"
+    } else {
+        "without"
+    }
+}
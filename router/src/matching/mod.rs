@@ -1,10 +1,14 @@
+mod constraint;
 mod expand_optionals;
 mod matcher;
 mod resolve_path;
 mod route;
 
+pub use constraint::*;
 pub(crate) use expand_optionals::*;
-pub(crate) use matcher::*;
+pub use matcher::MatchFailure;
+#[doc(hidden)]
+pub use matcher::{Matcher, PathMatch};
 pub(crate) use resolve_path::*;
 pub use route::*;
 
@@ -25,6 +29,23 @@ pub(crate) fn get_route_matches(branches: Vec<Branch>, location: String) -> Vec<
     vec![]
 }
 
+/// Matches the location like [get_route_matches], and also returns the pattern of each
+/// branch that was tried before the match, with the reason it didn't match.
+#[cfg(feature = "devtools")]
+pub(crate) fn explain_route_matches(
+    branches: &[Branch],
+    location: &str,
+) -> (Vec<RouteMatch>, Vec<(String, MatchFailure)>) {
+    let mut misses = Vec::new();
+    for branch in branches {
+        match branch.try_match(location) {
+            Ok(matches) => return (matches, misses),
+            Err(miss) => misses.push(miss),
+        }
+    }
+    (vec![], misses)
+}
+
 /// Describes a branch of the route tree.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Branch {
@@ -32,21 +53,30 @@ pub struct Branch {
     pub routes: Vec<RouteData>,
     /// How closely this branch matches the current URL.
     pub score: i32,
+    /// The sum of the `priority` of the routes in the branch. Branches with a higher
+    /// priority are tried first, regardless of their score.
+    pub priority: i32,
 }
 
 impl Branch {
     fn matcher<'a>(&'a self, location: &'a str) -> Option<Vec<RouteMatch>> {
+        self.try_match(location).ok()
+    }
+
+    /// Matches the location like [Branch::matcher], but on failure returns the pattern of
+    /// the route that didn't match, and why.
+    fn try_match(&self, location: &str) -> Result<Vec<RouteMatch>, (String, MatchFailure)> {
         let mut matches = Vec::new();
         for route in self.routes.iter().rev() {
-            match route.matcher.test(location) {
-                None => return None,
-                Some(m) => matches.push(RouteMatch {
+            match route.matcher.try_match(location) {
+                Err(failure) => return Err((route.pattern.clone(), failure)),
+                Ok(m) => matches.push(RouteMatch {
                     path_match: m,
                     route: route.clone(),
                 }),
             }
         }
         matches.reverse();
-        Some(matches)
+        Ok(matches)
     }
 }
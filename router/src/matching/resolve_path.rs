@@ -29,8 +29,45 @@ pub fn resolve_path<'a>(
         let result_empty = result.is_empty();
         let prefix = if result_empty { "/".into() } else { result };
 
-        Some(prefix + normalize(path, result_empty))
+        let resolved = prefix + normalize(path, result_empty);
+        if has_dot_segments(path) {
+            Some(remove_dot_segments(&resolved).into())
+        } else {
+            Some(resolved)
+        }
+    }
+}
+
+/// Whether the path, not counting its query or hash, has any `.` or `..` segments.
+fn has_dot_segments(path: &str) -> bool {
+    path.split(['?', '#'])
+        .next()
+        .unwrap_or_default()
+        .split('/')
+        .any(|segment| segment == "." || segment == "..")
+}
+
+/// Removes the `.` and `..` segments of an absolute path, like a browser does when it
+/// resolves a URL, so that `/users/5/../sibling` becomes `/users/sibling`. A `..` that
+/// would go above the root is ignored.
+fn remove_dot_segments(path: &str) -> String {
+    let (path, query_and_hash) = match path.find(['?', '#']) {
+        Some(idx) => path.split_at(idx),
+        None => (path, ""),
+    };
+
+    let mut segments = Vec::new();
+    for segment in path.split('/').filter(|segment| !segment.is_empty()) {
+        match segment {
+            "." => {}
+            ".." => {
+                segments.pop();
+            }
+            segment => segments.push(segment),
+        }
     }
+
+    format!("/{}{query_and_hash}", segments.join("/"))
 }
 
 #[cfg(feature = "ssr")]
@@ -3,10 +3,15 @@ use std::rc::Rc;
 use leptos::leptos_dom::View;
 use leptos::*;
 
+use crate::{ParamConstraint, TrailingSlash};
+
 #[derive(Clone)]
 pub struct RouteDefinition {
     pub path: &'static str,
     pub children: Vec<RouteDefinition>,
+    pub constraints: Vec<(&'static str, ParamConstraint)>,
+    pub priority: i32,
+    pub trailing_slash: Option<TrailingSlash>,
     pub view: Rc<dyn Fn(Scope) -> View>,
 }
 
@@ -15,12 +20,31 @@ impl std::fmt::Debug for RouteDefinition {
         f.debug_struct("RouteDefinition")
             .field("path", &self.path)
             .field("children", &self.children)
+            .field("constraints", &self.constraints)
+            .field("priority", &self.priority)
+            .field("trailing_slash", &self.trailing_slash)
             .finish()
     }
 }
 
 impl PartialEq for RouteDefinition {
     fn eq(&self, other: &Self) -> bool {
-        self.path == other.path && self.children == other.children
+        self.path == other.path
+            && self.children == other.children
+            && self.constraints == other.constraints
+            && self.priority == other.priority
+            && self.trailing_slash == other.trailing_slash
+    }
+}
+
+impl RouteDefinition {
+    /// Whether the route's path ends with a slash, other than the root path `/`.
+    pub(crate) fn has_trailing_slash(&self) -> bool {
+        has_trailing_slash(self.path)
     }
 }
+
+/// Whether the path ends with a slash, other than the root path `/`.
+pub(crate) fn has_trailing_slash(path: &str) -> bool {
+    path.len() > 1 && path.ends_with('/')
+}
@@ -0,0 +1,170 @@
+use leptos_router::{MatchFailure, Matcher, ParamConstraint, ParamsMap, PathMatch};
+
+fn params(pairs: &[(&str, &str)]) -> ParamsMap {
+    let mut params = ParamsMap::new();
+    for (key, value) in pairs {
+        params.insert(key.to_string(), value.to_string());
+    }
+    params
+}
+
+fn path_match(path: &str, pairs: &[(&str, &str)]) -> PathMatch {
+    PathMatch {
+        path: path.to_string(),
+        params: params(pairs),
+    }
+}
+
+#[test]
+fn static_paths_match_only_themselves() {
+    let matcher = Matcher::new("/users/new");
+    assert_eq!(
+        matcher.test("/users/new"),
+        Some(path_match("/users/new", &[]))
+    );
+    assert_eq!(
+        matcher.test("/users/new/"),
+        Some(path_match("/users/new", &[]))
+    );
+    assert_eq!(
+        matcher.try_match("/users/old"),
+        Err(MatchFailure::Segment {
+            expected: "new".to_string(),
+            found: "old".to_string(),
+        })
+    );
+}
+
+#[test]
+fn params_are_captured() {
+    let matcher = Matcher::new("/users/:id/posts/:post");
+    assert_eq!(
+        matcher.test("/users/42/posts/hello"),
+        Some(path_match(
+            "/users/42/posts/hello",
+            &[("id", "42"), ("post", "hello")]
+        ))
+    );
+}
+
+#[test]
+fn the_number_of_segments_must_match() {
+    let matcher = Matcher::new("/users/:id");
+    assert_eq!(
+        matcher.try_match("/users"),
+        Err(MatchFailure::MissingSegments {
+            expected: 2,
+            found: 1,
+        })
+    );
+    assert_eq!(
+        matcher.try_match("/users/42/posts"),
+        Err(MatchFailure::ExtraSegments {
+            expected: 2,
+            found: 3,
+        })
+    );
+}
+
+#[test]
+fn partial_matchers_match_the_start_of_the_path() {
+    let matcher = Matcher::new_with_partial("/users/:id", true);
+    assert_eq!(
+        matcher.test("/users/42/posts"),
+        Some(path_match("/users/42", &[("id", "42")]))
+    );
+}
+
+#[test]
+fn splats_capture_the_rest_of_the_path() {
+    let matcher = Matcher::new("/files/*path");
+    assert_eq!(
+        matcher.test("/files/docs/readme.md"),
+        Some(path_match("/files", &[("path", "docs/readme.md")]))
+    );
+    assert_eq!(
+        matcher.test("/files"),
+        Some(path_match("/files", &[("path", "")]))
+    );
+
+    // unnamed splats don't capture anything
+    let matcher = Matcher::new("/files/*");
+    assert_eq!(
+        matcher.test("/files/docs/readme.md"),
+        Some(path_match("/files", &[]))
+    );
+}
+
+#[test]
+fn trailing_slashes_can_be_required_or_forbidden() {
+    let with = Matcher::new("/docs/").with_trailing_slash(Some(true));
+    assert!(with.test("/docs/").is_some());
+    assert_eq!(
+        with.try_match("/docs"),
+        Err(MatchFailure::TrailingSlash { expected: true })
+    );
+
+    let without = Matcher::new("/docs").with_trailing_slash(Some(false));
+    assert!(without.test("/docs").is_some());
+    assert_eq!(
+        without.try_match("/docs/"),
+        Err(MatchFailure::TrailingSlash { expected: false })
+    );
+}
+
+#[test]
+fn constrained_params_must_meet_their_constraints() {
+    let matcher = Matcher::new("/users/:id")
+        .with_constraints(vec![("id".to_string(), ParamConstraint::parses::<u32>())]);
+    assert_eq!(
+        matcher.test("/users/42"),
+        Some(path_match("/users/42", &[("id", "42")]))
+    );
+    assert_eq!(
+        matcher.try_match("/users/alice"),
+        Err(MatchFailure::Constraint {
+            param: "id".to_string(),
+            value: "alice".to_string(),
+        })
+    );
+}
+
+#[test]
+fn constraints_on_missing_params_are_ignored() {
+    let matcher = Matcher::new("/users/:name")
+        .with_constraints(vec![("id".to_string(), ParamConstraint::parses::<u32>())]);
+    assert!(matcher.test("/users/alice").is_some());
+}
+
+#[test]
+fn constraints_test_values() {
+    let id = ParamConstraint::parses::<u32>();
+    assert!(id.test("42"));
+    assert!(!id.test("-1"));
+    assert!(!id.test(""));
+
+    let short = ParamConstraint::new(|value| value.len() <= 3);
+    assert!(short.test("abc"));
+    assert!(!short.test("abcd"));
+}
+
+#[test]
+fn regex_constraints_match_the_whole_value() {
+    let year = ParamConstraint::regex("\\d{4}");
+    assert!(year.test("2023"));
+    assert!(!year.test("23"));
+    assert!(!year.test("20231"));
+    assert!(!year.test("a2023"));
+
+    // alternatives are anchored too
+    let section = ParamConstraint::regex("users|posts");
+    assert!(section.test("users"));
+    assert!(!section.test("users2"));
+    assert!(!section.test("my-posts"));
+}
+
+#[test]
+#[should_panic(expected = "invalid route parameter constraint")]
+fn invalid_regex_constraints_panic() {
+    ParamConstraint::regex("(");
+}
@@ -0,0 +1,144 @@
+#![cfg(feature = "ssr")]
+
+use leptos::*;
+use leptos_router::*;
+
+/// Renders the routes at the given path.
+fn render(path: &str, routes: impl Fn(Scope) -> View + 'static) -> String {
+    let path = format!("http://leptos{path}");
+    render_to_string(move |cx| {
+        provide_context(
+            cx,
+            RouterIntegrationContext::new(ServerIntegration { path }),
+        );
+        view! { cx, <Router>{routes(cx)}</Router> }
+    })
+}
+
+fn users(cx: Scope) -> View {
+    view! { cx,
+        <Routes>
+            <Route path="/users/*rest" view=|cx| view! { cx, <p>"Anything"</p> }/>
+            <Route path="/users/:name" view=|cx| view! { cx, <p>"By name"</p> }/>
+            <Route
+                path="/users/:id"
+                constraints=vec![("id", ParamConstraint::parses::<u32>())]
+                view=|cx| view! { cx, <p>"By ID"</p> }
+            />
+            <Route path="/users/new" view=|cx| view! { cx, <p>"New"</p> }/>
+        </Routes>
+    }
+    .into_view(cx)
+}
+
+#[test]
+fn static_segments_are_preferred_over_params() {
+    let html = render("/users/new", users);
+    assert!(html.contains("New"), "{html}");
+}
+
+#[test]
+fn constrained_params_are_preferred_over_params() {
+    let html = render("/users/42", users);
+    assert!(html.contains("By ID"), "{html}");
+
+    // unless the value doesn't meet the constraint
+    let html = render("/users/alice", users);
+    assert!(html.contains("By name"), "{html}");
+}
+
+#[test]
+fn params_are_preferred_over_splats() {
+    let html = render("/users/alice/posts", users);
+    assert!(html.contains("Anything"), "{html}");
+}
+
+#[test]
+fn higher_priorities_are_preferred_over_more_specific_paths() {
+    let routes = |cx: Scope| {
+        view! { cx,
+            <Routes>
+                <Route path="/users/:name" view=|cx| view! { cx, <p>"By name"</p> }/>
+                <Route path="/:section/new" priority=1 view=|cx| view! { cx, <p>"Create"</p> }/>
+            </Routes>
+        }
+        .into_view(cx)
+    };
+    let html = render("/users/new", routes);
+    assert!(html.contains("Create"), "{html}");
+
+    let html = render("/users/alice", routes);
+    assert!(html.contains("By name"), "{html}");
+}
+
+#[test]
+fn nested_routes_add_the_priority_of_their_parent() {
+    let routes = |cx: Scope| {
+        view! { cx,
+            <Routes>
+                <Route path="/users/new" view=|cx| view! { cx, <p>"New"</p> }/>
+                <Route path="/:section" priority=1 view=|cx| view! { cx, <Outlet/> }>
+                    <Route path=":name" view=|cx| view! { cx, <p>"By name"</p> }/>
+                </Route>
+            </Routes>
+        }
+        .into_view(cx)
+    };
+    let html = render("/users/new", routes);
+    assert!(html.contains("By name"), "{html}");
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "match the same paths")]
+fn routes_that_match_the_same_paths_are_an_error() {
+    render("/users/alice", |cx| {
+        view! { cx,
+            <Routes>
+                <Route path="/users/:id" view=|cx| view! { cx, <p>"By ID"</p> }/>
+                <Route path="/users/:name" view=|cx| view! { cx, <p>"By name"</p> }/>
+            </Routes>
+        }
+        .into_view(cx)
+    });
+}
+
+#[test]
+fn routes_that_match_the_same_paths_can_be_told_apart_by_priority() {
+    let html = render("/users/alice", |cx| {
+        view! { cx,
+            <Routes>
+                <Route path="/users/:id" view=|cx| view! { cx, <p>"By ID"</p> }/>
+                <Route path="/users/:name" priority=1 view=|cx| view! { cx, <p>"By name"</p> }/>
+            </Routes>
+        }
+        .into_view(cx)
+    });
+    assert!(html.contains("By name"), "{html}");
+}
+
+#[test]
+fn routes_with_different_strict_trailing_slashes_dont_match_the_same_paths() {
+    let routes = |cx: Scope| {
+        view! { cx,
+            <Routes>
+                <Route
+                    path="/docs"
+                    trailing_slash=TrailingSlash::Strict
+                    view=|cx| view! { cx, <p>"Page"</p> }
+                />
+                <Route
+                    path="/docs/"
+                    trailing_slash=TrailingSlash::Strict
+                    view=|cx| view! { cx, <p>"Index"</p> }
+                />
+            </Routes>
+        }
+        .into_view(cx)
+    };
+    let html = render("/docs", routes);
+    assert!(html.contains("Page"), "{html}");
+
+    let html = render("/docs/", routes);
+    assert!(html.contains("Index"), "{html}");
+}
@@ -0,0 +1,82 @@
+#![cfg(feature = "ssr")]
+
+use leptos::*;
+use leptos_router::*;
+
+/// Renders the app at the given path, and returns its HTML and the redirect it asked for.
+fn render(path: &str, app: impl FnOnce(Scope) -> View + 'static) -> (String, Option<String>) {
+    let path = format!("http://leptos{path}");
+    let redirect = ServerRedirect::default();
+    let html = render_to_string({
+        let redirect = redirect.clone();
+        move |cx| {
+            provide_context(
+                cx,
+                RouterIntegrationContext::new(ServerIntegration { path }),
+            );
+            provide_context(cx, redirect);
+            app(cx)
+        }
+    });
+    (html, redirect.take())
+}
+
+/// Docs whose `/docs` route is strict about trailing slashes, which the router isn't.
+fn strict_docs(cx: Scope) -> View {
+    view! { cx,
+        <Router>
+            <Routes>
+                <Route
+                    path="/docs"
+                    trailing_slash=TrailingSlash::Strict
+                    view=|cx| view! { cx, <main><Outlet/></main> }
+                >
+                    <Route path="intro" view=|cx| view! { cx, <p>"Intro"</p> }/>
+                    <Route
+                        path="faq"
+                        trailing_slash=TrailingSlash::Ignore
+                        view=|cx| view! { cx, <p>"FAQ"</p> }
+                    />
+                </Route>
+            </Routes>
+        </Router>
+    }
+    .into_view(cx)
+}
+
+#[test]
+fn nested_routes_inherit_the_policy_of_their_parent() {
+    let (html, _) = render("/docs/intro", strict_docs);
+    assert!(html.contains("Intro"), "{html}");
+
+    let (html, _) = render("/docs/intro/", strict_docs);
+    assert!(!html.contains("Intro"), "{html}");
+}
+
+#[test]
+fn nested_routes_can_override_the_policy_of_their_parent() {
+    let (html, _) = render("/docs/faq/", strict_docs);
+    assert!(html.contains("FAQ"), "{html}");
+}
+
+#[test]
+fn nested_routes_inherit_redirects_to_the_canonical_path() {
+    let (html, redirect) = render("/docs/intro/", |cx| {
+        view! { cx,
+            <Router trailing_slash=TrailingSlash::Strict>
+                <Routes>
+                    <Route
+                        path="/docs"
+                        trailing_slash=TrailingSlash::RedirectToCanonical
+                        view=|cx| view! { cx, <main><Outlet/></main> }
+                    >
+                        <Route path="intro" view=|cx| view! { cx, <p>"Intro"</p> }/>
+                    </Route>
+                </Routes>
+            </Router>
+        }
+        .into_view(cx)
+    });
+    assert!(html.contains("Intro"), "{html}");
+    assert_eq!(redirect.as_deref(), Some("/docs/intro"));
+}